@@ -0,0 +1,164 @@
+//! Docker and Swarm operations started from the TUI run one after another on
+//! a background thread, so a second restart pressed while the first is still
+//! running waits its turn instead of being refused. The queue keeps what is
+//! running, what waits and how the ones before it ended, for the status line.
+
+use std::collections::VecDeque;
+use std::sync::mpsc;
+use std::thread;
+
+/// The work of one action. It may report progress (e.g. "2/3 done") through
+/// the callback and returns the status line for its outcome.
+pub type Job = Box<dyn FnOnce(&dyn Fn(String)) -> Result<String, String> + Send>;
+
+enum Event {
+    Progress(String),
+    Done(Result<String, String>),
+}
+
+struct Running {
+    label: String,
+    progress: Option<String>,
+    receiver: mpsc::Receiver<Event>,
+}
+
+pub struct ActionQueue {
+    /// What a failure is logged as, e.g. "Container action".
+    context: &'static str,
+    running: Option<Running>,
+    queued: VecDeque<(String, Job)>,
+    /// How the actions since the queue was last idle ended, oldest first;
+    /// errors start with "Error: ".
+    finished: Vec<String>,
+}
+
+/// Finished actions kept for the status line.
+const MAX_FINISHED: usize = 3;
+
+impl ActionQueue {
+    pub fn new(context: &'static str) -> Self {
+        Self { context, running: None, queued: VecDeque::new(), finished: Vec::new() }
+    }
+
+    /// Queue `job`, shown as `label` (e.g. "Restarting web") while it runs.
+    /// It starts at once when nothing else is running. An action with the
+    /// same label already waiting or running is not queued twice; returns
+    /// whether it was queued.
+    pub fn push(&mut self, label: String, job: Job) -> bool {
+        let duplicate = self.running.as_ref().is_some_and(|r| r.label == label)
+            || self.queued.iter().any(|(l, _)| *l == label);
+        if duplicate {
+            return false;
+        }
+        self.queued.push_back((label, job));
+        if self.running.is_none() {
+            self.finished.clear();
+            self.start_next();
+        }
+        true
+    }
+
+    /// Whether an action is running (none waits while nothing runs).
+    pub fn is_busy(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Actions waiting behind the running one.
+    pub fn queued(&self) -> usize {
+        self.queued.len()
+    }
+
+    fn start_next(&mut self) {
+        let Some((label, job)) = self.queued.pop_front() else { return };
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let result = job(&move |p| {
+                let _ = progress_tx.send(Event::Progress(p));
+            });
+            let _ = tx.send(Event::Done(result));
+        });
+        self.running = Some(Running { label, progress: None, receiver: rx });
+    }
+
+    /// Collect progress and results, starting the next action when one
+    /// finishes. Returns true if the status changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Some(running) = self.running.as_mut() {
+            let outcome = match running.receiver.try_recv() {
+                Ok(Event::Progress(p)) => {
+                    running.progress = Some(p);
+                    changed = true;
+                    continue;
+                }
+                Ok(Event::Done(Ok(msg))) => msg,
+                Ok(Event::Done(Err(msg))) => {
+                    tracing::error!("{} failed: {}", self.context, msg);
+                    format!("Error: {}", msg)
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => format!("{} failed unexpectedly", running.label),
+            };
+            if self.finished.len() == MAX_FINISHED {
+                self.finished.remove(0);
+            }
+            self.finished.push(outcome);
+            self.running = None;
+            self.start_next();
+            changed = true;
+        }
+        changed
+    }
+
+    /// One line for the status bar: how the last few actions ended, what is
+    /// running (with its progress) and how many wait, e.g.
+    /// "Restarted web · Stopping api (1/2 done)... · 1 queued".
+    pub fn status_line(&self) -> Option<String> {
+        let mut parts = self.finished.clone();
+        if let Some(ref r) = self.running {
+            parts.push(match r.progress {
+                Some(ref p) => format!("{} ({})...", r.label, p),
+                None => format!("{}...", r.label),
+            });
+        }
+        if !self.queued.is_empty() {
+            parts.push(format!("{} queued", self.queued.len()));
+        }
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn runs_actions_in_order_and_reports_each() {
+        let mut queue = ActionQueue::new("Test action");
+        let gate = Arc::new(Mutex::new(()));
+        let held = gate.lock().unwrap();
+        let wait = Arc::clone(&gate);
+        assert!(queue.push(
+            "Restarting web".into(),
+            Box::new(move |progress| {
+                progress("1/1 done".into());
+                let _lock = wait.lock().unwrap();
+                Ok("Restarted web".into())
+            })
+        ));
+        assert!(queue.push("Stopping api".into(), Box::new(|_| Err("no such container".into()))));
+        assert!(!queue.push("Stopping api".into(), Box::new(|_| Ok(String::new()))));
+        assert_eq!(queue.queued(), 1);
+
+        drop(held);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while queue.is_busy() && Instant::now() < deadline {
+            queue.poll();
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(queue.status_line().as_deref(), Some("Restarted web · Error: no such container"));
+    }
+}
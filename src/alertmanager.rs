@@ -0,0 +1,196 @@
+//! Alertmanager silences for muted warnings, so acknowledging a warning in
+//! the terminal also stops the team's pager for it. With `[alertmanager] url`
+//! set, muting creates a silence of the same length through the v2 API on the
+//! tokio runtime, and unmuting expires it.
+
+use std::collections::BTreeMap;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::runtime::Runtime;
+
+use crate::config::AlertmanagerConfig;
+use crate::probe;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How a silence request ended, reported back to the UI thread.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SilenceOutcome {
+    /// Created for the muted warning with this `warning_key`.
+    Created { key: String, id: String },
+    Expired(String),
+    Failed(String),
+}
+
+/// Sends silence requests to the configured Alertmanager.
+pub struct Silencer {
+    rt: Arc<Runtime>,
+    /// Base URL without a trailing slash; None sends nothing.
+    url: Option<String>,
+    matchers: BTreeMap<String, String>,
+    tx: mpsc::Sender<SilenceOutcome>,
+    rx: mpsc::Receiver<SilenceOutcome>,
+}
+
+impl Silencer {
+    pub fn new(rt: Arc<Runtime>, config: &AlertmanagerConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let mut silencer = Self { rt, url: None, matchers: BTreeMap::new(), tx, rx };
+        silencer.reconfigure(config);
+        silencer
+    }
+
+    pub fn reconfigure(&mut self, config: &AlertmanagerConfig) {
+        self.url = config.url.as_ref().map(|url| url.trim_end_matches('/').to_string());
+        self.matchers = config.matchers.clone();
+    }
+
+    /// Whether `[alertmanager] url` is set.
+    pub fn is_enabled(&self) -> bool {
+        self.url.is_some()
+    }
+
+    /// Silence the alerts behind `warning` until `until` (Unix seconds).
+    pub fn silence(&self, warning: &str, until: i64, by: &str) {
+        let Some(ref base) = self.url else { return };
+        let key = crate::mutes::warning_key(warning);
+        let body = silence_body(&self.matchers, warning, chrono::Utc::now().timestamp(), until, by);
+        let (url, tx) = (format!("{}/api/v2/silences", base), self.tx.clone());
+        self.rt.spawn(async move {
+            let outcome = match body {
+                Ok(body) => match request("POST", &url, Some(body.to_string())).await {
+                    Ok(response) => match serde_json::from_str::<serde_json::Value>(&response)
+                        .ok()
+                        .and_then(|v| v["silenceID"].as_str().map(String::from))
+                    {
+                        Some(id) => SilenceOutcome::Created { key, id },
+                        None => SilenceOutcome::Failed("Alertmanager returned no silence ID".to_string()),
+                    },
+                    Err(e) => SilenceOutcome::Failed(e),
+                },
+                Err(e) => SilenceOutcome::Failed(e),
+            };
+            let _ = tx.send(outcome);
+        });
+    }
+
+    /// Expire a silence created by `silence`.
+    pub fn expire(&self, id: &str) {
+        let Some(ref base) = self.url else { return };
+        let (url, tx, id) = (format!("{}/api/v2/silence/{}", base, id), self.tx.clone(), id.to_string());
+        self.rt.spawn(async move {
+            let outcome = match request("DELETE", &url, None).await {
+                Ok(_) => SilenceOutcome::Expired(id),
+                Err(e) => SilenceOutcome::Failed(e),
+            };
+            let _ = tx.send(outcome);
+        });
+    }
+
+    /// Outcomes of the requests finished since the last call.
+    pub fn poll(&self) -> Vec<SilenceOutcome> {
+        self.rx.try_iter().collect()
+    }
+}
+
+/// The service, node or stack a warning is about: the word after its kind
+/// ("SERVICE DEGRADED: api has ..."), or after the last colon when that word
+/// is a count ("NODE DOWN: 2 node(s) unreachable: node-3, node-4" names node-3).
+pub fn warning_subject(warning: &str) -> Option<&str> {
+    let (_, rest) = warning.split_once(": ")?;
+    let rest = if rest.starts_with(|c: char| c.is_ascii_digit()) { warning.rsplit_once(": ")?.1 } else { rest };
+    rest.split([' ', ',']).find(|w| !w.is_empty())
+}
+
+/// The silence to post: the configured matchers with `{kind}` replaced by
+/// the warning's kind and `{name}` by its subject.
+pub fn silence_body(
+    matchers: &BTreeMap<String, String>,
+    warning: &str,
+    starts: i64,
+    ends: i64,
+    by: &str,
+) -> Result<serde_json::Value, String> {
+    if matchers.is_empty() {
+        return Err("[alertmanager] matchers is empty; a silence needs at least one".to_string());
+    }
+    let kind = warning.split_once(':').map_or(warning, |(kind, _)| kind);
+    let mut list = Vec::new();
+    for (label, template) in matchers {
+        let value = if template.contains("{name}") {
+            let name = warning_subject(warning).ok_or_else(|| format!("No service or node named in '{}'", warning))?;
+            template.replace("{name}", name)
+        } else {
+            template.clone()
+        };
+        let value = value.replace("{kind}", kind);
+        list.push(serde_json::json!({ "name": label, "value": value, "isRegex": false, "isEqual": true }));
+    }
+    let stamp = |secs: i64| {
+        chrono::DateTime::from_timestamp(secs, 0).map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+    };
+    Ok(serde_json::json!({
+        "matchers": list,
+        "startsAt": stamp(starts),
+        "endsAt": stamp(ends),
+        "createdBy": by,
+        "comment": format!("Muted in sitrep: {}", warning),
+    }))
+}
+
+/// A plain-HTTP request; returns the body of a 2xx response. HTTP/1.0 keeps
+/// the response unchunked and closed at its end.
+async fn request(method: &str, url: &str, body: Option<String>) -> Result<String, String> {
+    let (host, port, path) = probe::parse_http_url(url)?;
+    let exchange = async {
+        let mut stream = tokio::net::TcpStream::connect((host.as_str(), port)).await.map_err(|e| e.to_string())?;
+        let body = body.unwrap_or_default();
+        let request = format!(
+            "{} {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: sitrep\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            host,
+            body.len(),
+            body
+        );
+        stream.write_all(request.as_bytes()).await.map_err(|e| e.to_string())?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.map_err(|e| e.to_string())?;
+        Ok::<_, String>(String::from_utf8_lossy(&response).into_owned())
+    };
+    let response = tokio::time::timeout(TIMEOUT, exchange)
+        .await
+        .map_err(|_| format!("Alertmanager at {}:{} timed out", host, port))??;
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head.split_whitespace().nth(1).and_then(|c| c.parse::<u16>().ok()).unwrap_or(0);
+    if !(200..300).contains(&status) {
+        return Err(format!("Alertmanager answered {}: {}", status, body.trim()));
+    }
+    Ok(body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn silence_matches_the_service_or_node_the_warning_names() {
+        assert_eq!(warning_subject("SERVICE DEGRADED: api has 1/3 replicas"), Some("api"));
+        assert_eq!(warning_subject("NODE DOWN: 2 node(s) unreachable: node-3, node-4"), Some("node-3"));
+        assert_eq!(warning_subject("NO INGRESS NETWORK"), None);
+
+        let matchers = BTreeMap::from([
+            ("service".to_string(), "shop_{name}".to_string()),
+            ("severity".to_string(), "warning".to_string()),
+        ]);
+        let body = silence_body(&matchers, "SERVICE DEGRADED: api has 1/3 replicas", 0, 3600, "alice@web1").unwrap();
+        assert_eq!(body["matchers"][0]["value"], "shop_api");
+        assert_eq!(body["matchers"][1]["value"], "warning");
+        assert_eq!((body["startsAt"].as_str(), body["endsAt"].as_str()), (Some("1970-01-01T00:00:00Z"), Some("1970-01-01T01:00:00Z")));
+        assert!(silence_body(&matchers, "NO INGRESS NETWORK", 0, 1, "a").is_err());
+        assert!(silence_body(&BTreeMap::new(), "SERVICE DEGRADED: api has 1/3 replicas", 0, 1, "a").is_err());
+    }
+}
@@ -0,0 +1,394 @@
+//! Alerts: the warning conditions sitrep derives from collected data,
+//! gathered in one place with stable identities so they can be forwarded
+//! outside the TUI (syslog/journald) as raised/resolved transitions.
+
+use std::collections::HashMap;
+
+use crate::health_controller::{health_warning, HealthMonitor, HealthTargetKind};
+use crate::model::{
+    assess_saturation, AuthSummary, ConntrackInfo, DockerContainerInfo, LvmVolumeInfo, MonitorData, SaturationLevel,
+    Thresholds,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alert {
+    /// Stable identity: a condition keeps its key while its message changes
+    /// (e.g. a disk going from 91% to 93%).
+    pub key: String,
+    /// Subsystem that raised it, e.g. "disk", "oom", "swarm", "health".
+    pub source: &'static str,
+    /// What it is about: a mount point, interface, service name, ...
+    pub target: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Alert {
+    fn new(source: &'static str, target: &str, severity: Severity, message: String) -> Self {
+        Self {
+            key: format!("{}:{}", source, target),
+            source,
+            target: target.to_string(),
+            severity,
+            message,
+        }
+    }
+}
+
+/// Host-level alerts: disk critical (below `thresholds.disk_free_pct` free), recent OOM kills, low entropy, conntrack
+/// near full, link problems, listener changes, degraded arrays and pools,
+/// filling thin pools and snapshots, network interrupts pinned to one CPU,
+/// FD leaks, metrics far above their baseline and overall saturation.
+pub fn system_alerts(data: &MonitorData, thresholds: &Thresholds) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+
+    for disk in &data.disk_space {
+        if disk.total_gb <= 0.0 {
+            continue;
+        }
+        let used_pct = (disk.total_gb - disk.available_gb) / disk.total_gb * 100.0;
+        if used_pct > 100.0 - thresholds.disk_free_pct {
+            alerts.push(Alert::new(
+                "disk",
+                &disk.mount_point,
+                Severity::Critical,
+                format!("DISK CRITICAL: {} {:.0}% used", disk.mount_point, used_pct),
+            ));
+        }
+    }
+
+    if let Some(oom) = data.oom
+        && let Some(secs) = oom.recent_kill_secs() {
+            alerts.push(Alert::new(
+                "oom",
+                "kernel",
+                Severity::Critical,
+                format!(
+                    "OOM KILL: kernel OOM killer fired {}m ago ({} kills since boot)",
+                    secs / 60,
+                    oom.total_kills
+                ),
+            ));
+        }
+
+    if let Some(ref entropy) = data.entropy
+        && entropy.is_low() {
+            alerts.push(Alert::new(
+                "entropy",
+                "kernel",
+                Severity::Warning,
+                format!(
+                    "ENTROPY LOW: {} bits available{}, /dev/random readers may block",
+                    entropy.available,
+                    if entropy.feeder.is_none() { " and no rngd or haveged running" } else { "" }
+                ),
+            ));
+        }
+
+    if let Some(ct) = data.conntrack {
+        let pct = ct.usage_pct();
+        if pct >= ConntrackInfo::WARN_PCT {
+            let severity = if pct >= ConntrackInfo::CRITICAL_PCT {
+                Severity::Critical
+            } else {
+                Severity::Warning
+            };
+            alerts.push(Alert::new(
+                "conntrack",
+                "table",
+                severity,
+                format!("CONNTRACK: {}/{} entries ({:.0}%)", ct.count, ct.max, pct),
+            ));
+        }
+    }
+
+    for link in &data.network.links {
+        if let Some(warning) = link.warning() {
+            alerts.push(Alert::new(
+                "link",
+                &link.name,
+                Severity::Warning,
+                format!("LINK: {} {}", link.name, warning),
+            ));
+        }
+    }
+
+    for change in &data.listener_changes {
+        alerts.push(Alert::new("listen", &change.label, Severity::Warning, change.summary()));
+    }
+
+    for array in data.storage_arrays.iter().filter(|a| !a.healthy) {
+        alerts.push(Alert::new(
+            "raid",
+            &array.name,
+            Severity::Critical,
+            format!("RAID: {}", array.summary()),
+        ));
+    }
+
+    for volume in data.lvm_volumes.iter().filter(|v| v.usage_pct() >= LvmVolumeInfo::WARN_PCT) {
+        let severity = if volume.usage_pct() >= LvmVolumeInfo::CRITICAL_PCT {
+            Severity::Critical
+        } else {
+            Severity::Warning
+        };
+        alerts.push(Alert::new(
+            "lvm",
+            &format!("{}/{}", volume.vg, volume.name),
+            severity,
+            format!("LVM: {}", volume.summary()),
+        ));
+    }
+
+    if let Some((cpu, share, rate)) = data.interrupts.as_ref().and_then(|i| i.network_hotspot()) {
+        alerts.push(Alert::new(
+            "irq",
+            "net_rx",
+            Severity::Warning,
+            format!("IRQ HOTSPOT: cpu{} handles {:.0}% of NET_RX softirqs ({:.0}/s)", cpu, share * 100.0, rate),
+        ));
+    }
+
+    for leak in &data.fd_info.leaks {
+        let severity = if leak.is_critical() {
+            Severity::Critical
+        } else {
+            Severity::Warning
+        };
+        alerts.push(Alert::new(
+            "fd_leak",
+            &leak.pid.to_string(),
+            severity,
+            format!("FD leak: {}", leak.summary()),
+        ));
+    }
+
+    for anomaly in &data.anomalies {
+        alerts.push(Alert::new(
+            "anomaly",
+            &anomaly.metric,
+            Severity::Warning,
+            format!("ANOMALY: {}", anomaly.summary()),
+        ));
+    }
+
+    let saturation = assess_saturation(
+        data.load_avg,
+        data.core_count,
+        &data.pressure,
+        &data.memory,
+        data.disk_busy_pct,
+    );
+    if saturation.level == SaturationLevel::Saturated {
+        alerts.push(Alert::new(
+            "saturation",
+            "host",
+            Severity::Warning,
+            format!("SATURATED: {}", saturation.reason),
+        ));
+    }
+
+    alerts
+}
+
+/// Swarm cluster warnings, e.g. "NODE DOWN: 1 node(s) unreachable: node-3".
+pub fn swarm_alerts(warnings: &[String]) -> Vec<Alert> {
+    warnings
+        .iter()
+        .map(|warning| {
+            // Counts change while the condition persists; leave them out of the key.
+            let target = crate::mutes::warning_key(warning);
+            let severity = if warning.starts_with("NODE DOWN") {
+                Severity::Critical
+            } else {
+                Severity::Warning
+            };
+            Alert::new("swarm", &target, severity, warning.clone())
+        })
+        .collect()
+}
+
+/// Failing HTTP health checks for containers and services.
+pub fn health_alerts(health: &HealthMonitor) -> Vec<Alert> {
+    [(HealthTargetKind::Container, "container"), (HealthTargetKind::Service, "service")]
+        .into_iter()
+        .flat_map(|(kind, label)| {
+            health.failing(kind).into_iter().map(move |(name, result)| {
+                let target = format!("{}/{}", label, name);
+                Alert::new("health", &target, Severity::Warning, health_warning(name, result))
+            })
+        })
+        .collect()
+}
+
+/// Containers running an image older than `max_age_days` (0 disables), or
+/// one whose tag the registry now serves with a different digest.
+pub fn image_alerts(containers: &[DockerContainerInfo], max_age_days: u64) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+    for c in containers {
+        let age_days = c.image_age_secs().map(|s| s / 86400);
+        if max_age_days > 0 && age_days.is_some_and(|d| d > max_age_days) {
+            alerts.push(Alert::new(
+                "image_age",
+                &c.name,
+                Severity::Warning,
+                format!("OLD IMAGE: {} runs {} built {}d ago", c.name, c.image, age_days.unwrap_or(0)),
+            ));
+        }
+        if c.image_drift == Some(true) {
+            alerts.push(Alert::new(
+                "image_drift",
+                &c.name,
+                Severity::Warning,
+                format!("IMAGE DRIFT: {} runs an older {} than the registry serves", c.name, c.image),
+            ));
+        }
+    }
+    alerts
+}
+
+/// A burst of failed SSH logins.
+pub fn auth_alerts(auth: &AuthSummary) -> Vec<Alert> {
+    if !auth.is_spiking() {
+        return Vec::new();
+    }
+    let mut message = format!("{} failed logins in the last minute", auth.last_minute);
+    if let Some((remote, count)) = auth.top_offenders.first() {
+        message.push_str(&format!(", top {} x{}", remote, count));
+    }
+    vec![Alert::new("auth", "ssh", Severity::Warning, message)]
+}
+
+/// Alerts as a JSON array of {key, source, target, severity, message}.
+pub fn to_json(alerts: &[Alert]) -> serde_json::Value {
+    alerts
+        .iter()
+        .map(|a| {
+            serde_json::json!({
+                "key": a.key,
+                "source": a.source,
+                "target": a.target,
+                "severity": a.severity.label(),
+                "message": a.message,
+            })
+        })
+        .collect()
+}
+
+/// Alerts that appeared or cleared since the previous update.
+#[derive(Debug, Default)]
+pub struct AlertChanges {
+    pub raised: Vec<Alert>,
+    pub resolved: Vec<Alert>,
+}
+
+/// Remembers which alerts are active so each condition is reported once
+/// when raised and once when resolved, rather than on every tick.
+#[derive(Default)]
+pub struct AlertTracker {
+    active: HashMap<String, Alert>,
+}
+
+impl AlertTracker {
+    pub fn update(&mut self, current: Vec<Alert>) -> AlertChanges {
+        let mut changes = AlertChanges::default();
+        let mut next = HashMap::new();
+        for alert in current {
+            if !self.active.contains_key(&alert.key) {
+                changes.raised.push(alert.clone());
+            }
+            next.insert(alert.key.clone(), alert);
+        }
+        let mut resolved: Vec<Alert> = self
+            .active
+            .drain()
+            .filter(|(key, _)| !next.contains_key(key))
+            .map(|(_, alert)| alert)
+            .collect();
+        resolved.sort_by(|a, b| a.key.cmp(&b.key));
+        changes.resolved = resolved;
+        self.active = next;
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracker_reports_transitions_once() {
+        let mut tracker = AlertTracker::default();
+        let disk = |pct: u32| {
+            Alert::new("disk", "/", Severity::Critical, format!("DISK CRITICAL: / {}% used", pct))
+        };
+
+        let changes = tracker.update(vec![disk(91)]);
+        assert_eq!(changes.raised.len(), 1);
+
+        // Same condition with a new message is not raised again
+        let changes = tracker.update(vec![disk(93)]);
+        assert!(changes.raised.is_empty() && changes.resolved.is_empty());
+
+        let changes = tracker.update(Vec::new());
+        assert_eq!(changes.resolved[0].message, "DISK CRITICAL: / 93% used");
+    }
+
+    #[test]
+    fn flags_old_and_drifted_images() {
+        let mut c = DockerContainerInfo {
+            id: "abc".into(),
+            name: "web".into(),
+            image: "nginx:1.25".into(),
+            status: String::new(),
+            state: "running".into(),
+            uptime: String::new(),
+            uptime_secs: None,
+            cpu_percent: 0.0,
+            ports: String::new(),
+            ip_address: String::new(),
+            mem_usage: 0,
+            mem_limit: 0,
+            net_rx: 0,
+            net_tx: 0,
+            labels: HashMap::new(),
+            published_ports: Vec::new(),
+            image_id: "sha256:111".into(),
+            image_created: Some(chrono::Utc::now().timestamp() - 100 * 86400),
+            image_digests: vec!["nginx@sha256:aaa".into()],
+            image_drift: None,
+            image_scan: None,
+            namespace: String::new(),
+        };
+        assert_eq!(image_alerts(std::slice::from_ref(&c), 90)[0].key, "image_age:web");
+        assert!(image_alerts(std::slice::from_ref(&c), 0).is_empty());
+
+        assert!(c.runs_digest("sha256:aaa") && !c.runs_digest("sha256:bbb"));
+        c.image_drift = Some(true);
+        assert_eq!(image_alerts(std::slice::from_ref(&c), 0)[0].source, "image_drift");
+    }
+
+    #[test]
+    fn swarm_alert_keys_ignore_counts() {
+        let a = swarm_alerts(&["SERVICE DEGRADED: api has 1/3 replicas".to_string()]);
+        let b = swarm_alerts(&["SERVICE DEGRADED: api has 2/3 replicas".to_string()]);
+        assert_eq!(a[0].key, b[0].key);
+        let down = swarm_alerts(&["NODE DOWN: 1 node(s) unreachable: n1".to_string()]);
+        assert_eq!(down[0].severity, Severity::Critical);
+    }
+}
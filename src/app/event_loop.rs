@@ -1,21 +1,289 @@
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
 use std::time::Instant;
 
-use crate::model::AppView;
+use crate::history::GRAPH_WINDOW_SECS;
+use crate::config::Config;
+use crate::model::{AppView, GraphViewState, SystemPanel, WatchKind};
+use crate::recording::{Frame, SwarmFrame};
 
-use super::App;
+use super::{App, StartTarget, TOAST_DURATION};
 
 impl App {
+    /// Swap in the real Docker and Swarm monitors once startup detection
+    /// finishes, and fetch the container list so the tab bar count fills in.
+    pub fn poll_startup(&mut self) -> bool {
+        let Some(ref rx) = self.startup_probe else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok((mut docker_monitor, swarm_monitor, kube_monitor)) => {
+                tracing::info!(
+                    "Docker available: {} (CLI: {}), Swarm mode: {}, Kubernetes: {}",
+                    docker_monitor.docker_available,
+                    docker_monitor.container_cli().map_or("none", |cli| cli.name()),
+                    swarm_monitor.is_swarm(),
+                    kube_monitor.is_available()
+                );
+                docker_monitor.group_label = self.config.containers.group_label.clone();
+                docker_monitor.namespaces = self.config.containers.namespaces.clone();
+                docker_monitor.update();
+                self.docker_monitor = docker_monitor;
+                self.swarm_monitor = swarm_monitor;
+                self.swarm_monitor.stack_files = self.config.swarm.stack_files.clone();
+                self.kube_monitor = kube_monitor;
+                self.startup_probe = None;
+                self.show_replay_frame();
+                true
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.startup_probe = None;
+                false
+            }
+        }
+    }
+
+    /// Open the view asked for on the command line once its data is in.
+    pub fn poll_start_target(&mut self) -> bool {
+        let Some(target) = self.start_target.take() else {
+            return false;
+        };
+        match self.resolve_start_target(&target) {
+            Some(Ok(view)) => self.app_view = view,
+            Some(Err(message)) => self.toast = Some((message, Instant::now())),
+            None => {
+                self.start_target = Some(target);
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The view for `target`, an explanation why it can't be opened, or
+    /// None while Docker/Swarm detection or their first refresh is pending.
+    fn resolve_start_target(&mut self, target: &StartTarget) -> Option<Result<AppView, String>> {
+        const NO_DOCKER: &str = "Docker is not available";
+        const NO_SWARM: &str = "This node is not part of a Swarm";
+        if self.probing() {
+            return match target {
+                StartTarget::Tab(view) if !matches!(view, AppView::Containers | AppView::Swarm | AppView::Kubernetes) => {
+                    Some(Ok(view.clone()))
+                }
+                _ => None,
+            };
+        }
+        let docker_ready = !self.docker_monitor.is_available() || self.docker_monitor.last_updated.is_some();
+        let swarm_ready = !self.swarm_monitor.is_swarm() || self.swarm_monitor.last_updated.is_some();
+        if !swarm_ready && matches!(target, StartTarget::Service(_) | StartTarget::Logs(_)) {
+            self.swarm_monitor.update();
+        }
+
+        let find_container = |app: &App, name: &str| {
+            app.docker_monitor.containers.iter().find(|c| c.name == name).cloned()
+        };
+        let find_service = |app: &App, name: &str| {
+            app.swarm_monitor.services.iter().find(|s| s.name == name).cloned()
+        };
+        Some(match target {
+            StartTarget::Tab(view) => {
+                if super::input::tab_order(self).contains(view) {
+                    Ok(view.clone())
+                } else if *view == AppView::Containers {
+                    Err(NO_DOCKER.to_string())
+                } else if *view == AppView::Kubernetes {
+                    Err("No kubectl context is configured".to_string())
+                } else {
+                    Err(NO_SWARM.to_string())
+                }
+            }
+            StartTarget::Container(name) => {
+                if !self.docker_monitor.is_available() {
+                    return Some(Err(NO_DOCKER.to_string()));
+                }
+                if !docker_ready {
+                    return None;
+                }
+                let c = find_container(self, name).ok_or_else(|| format!("No container named '{}'", name));
+                c.map(|c| {
+                    self.docker_monitor.select_container(&c.id);
+                    AppView::Containers
+                })
+            }
+            StartTarget::Service(name) => {
+                if !self.swarm_monitor.is_swarm() {
+                    return Some(Err(NO_SWARM.to_string()));
+                }
+                if !swarm_ready {
+                    return None;
+                }
+                let svc = find_service(self, name).ok_or_else(|| format!("No service named '{}'", name));
+                svc.map(|svc| {
+                    self.swarm_monitor.enter_task_view(&svc.id, &svc.name);
+                    AppView::SwarmServiceTasks(svc.id, svc.name)
+                })
+            }
+            StartTarget::Logs(name) => {
+                if !docker_ready || !swarm_ready {
+                    return None;
+                }
+                if let Some(c) = find_container(self, name) {
+                    self.docker_monitor.start_log_stream(&c.id, &c.name);
+                    Ok(AppView::ContainerLogs(c.id))
+                } else if let Some(svc) = find_service(self, name) {
+                    self.swarm_monitor.start_service_log_stream(&svc.id, &svc.name);
+                    Ok(AppView::SwarmServiceLogs(svc.id, svc.name))
+                } else {
+                    Err(format!("No container or service named '{}'", name))
+                }
+            }
+        })
+    }
+
+    /// Apply edits to the config file picked up by the watcher, or keep the
+    /// running config when the new one doesn't parse.
+    pub fn poll_config(&mut self) -> bool {
+        let Some(ref rx) = self.config_watch else {
+            return false;
+        };
+        let Ok(result) = rx.try_recv() else {
+            return false;
+        };
+        let message = match result {
+            Ok(config) => self.apply_config(config),
+            Err(e) => {
+                tracing::warn!("Config not reloaded: {}", e);
+                format!("Config not reloaded: {}", e)
+            }
+        };
+        self.toast = Some((message, Instant::now()));
+        true
+    }
+
+    /// Act on SIGUSR1 / SIGUSR2 received since the last call.
+    pub fn poll_signals(&mut self) -> bool {
+        let mut changed = false;
+        if self.refresh_signal.swap(false, Ordering::Relaxed) {
+            tracing::info!("SIGUSR1: refreshing every monitor");
+            self.refresh_all();
+            self.toast = Some(("Refreshing (SIGUSR1)".to_string(), Instant::now()));
+            changed = true;
+        }
+        if self.dump_signal.swap(false, Ordering::Relaxed) {
+            let written = crate::commands::dump_path()
+                .and_then(|path| crate::commands::write_snapshot(self, &path).map(|()| path));
+            let message = match written {
+                Ok(path) => format!("Snapshot written to {}", path.display()),
+                Err(e) => e,
+            };
+            tracing::info!("SIGUSR2: {}", message);
+            self.toast = Some((message, Instant::now()));
+            changed = true;
+        }
+        changed
+    }
+
+    /// Start a refresh of every monitor now, whatever tab is open, and
+    /// restart the tick interval from here.
+    pub fn refresh_all(&mut self) {
+        self.monitor.update();
+        if self.docker_monitor.is_available() {
+            self.docker_monitor.update();
+        }
+        if self.swarm_monitor.is_swarm() {
+            self.swarm_monitor.update();
+        } else {
+            self.swarm_monitor.recheck_swarm();
+        }
+        self.health_monitor.update();
+        self.last_tick = Instant::now();
+    }
+
+    /// Container columns, labels and grouping, health targets, the theme, a
+    /// changed CPU scale, summary lines, the CPU budget, the schedule and
+    /// mute files, the Alertmanager, the incident provider, thresholds,
+    /// remapped keys and newly listed watchlist entries take effect at once;
+    /// the history store and the refresh interval are read at startup only.
+    fn apply_config(&mut self, config: Config) -> String {
+        self.docker_monitor.group_label = config.containers.group_label.clone();
+        self.docker_monitor.namespaces = config.containers.namespaces.clone();
+        self.swarm_monitor.stack_files = config.swarm.stack_files.clone();
+        if config.swarm.schedule_file != self.config.swarm.schedule_file && self.schedule.path().is_some() {
+            let path = config.swarm.schedule_file.clone().unwrap_or_else(crate::schedule::default_path);
+            self.schedule = crate::schedule::RestartSchedule::new(path);
+        }
+        if config.swarm.mute_file != self.config.swarm.mute_file && self.mutes.path().is_some() {
+            let path = config.swarm.mute_file.clone().unwrap_or_else(crate::mutes::default_path);
+            self.mutes = crate::mutes::MuteList::new(path);
+        }
+        if self.mutes.path().is_some() {
+            self.silencer.reconfigure(&config.alertmanager);
+            self.incidents.reconfigure(&config.incidents);
+        }
+        self.health_monitor.reconfigure(&config.health);
+        crate::view::theme::set_theme(config.ui.theme);
+        // Leaves a scale toggled with % alone unless the file changed it
+        if config.ui.cpu != self.config.ui.cpu {
+            self.set_cpu_scale(config.ui.cpu);
+        }
+        self.monitor.ui_state.guard.cpu_budget = config.self_guard.cpu_budget_percent;
+        self.monitor.ui_state.summary_lines = config.summary.order();
+        self.monitor.ui_state.thresholds = config.thresholds;
+        self.keymap = crate::keymap::KeyMap::new(&config.keys).unwrap_or_default();
+        self.macros = crate::macros::Macros::new(&config.macros).unwrap_or_default();
+        if config.summary.custom != self.config.summary.custom {
+            self.monitor.set_custom_lines(config.summary.custom.clone());
+        }
+        let added = [
+            (WatchKind::Container, &config.watchlist.containers, &self.config.watchlist.containers),
+            (WatchKind::Service, &config.watchlist.services, &self.config.watchlist.services),
+        ];
+        for (kind, new, old) in added {
+            for name in new.iter().filter(|name| !old.contains(name)) {
+                if !self.watchlist.contains(kind, name) {
+                    self.watchlist.toggle(kind, name);
+                }
+            }
+        }
+        let restart_needed = config.history != self.config.history;
+        self.config = config;
+        tracing::info!("Config reloaded");
+        if restart_needed {
+            "Config reloaded (history changes apply after restart)".to_string()
+        } else {
+            "Config reloaded".to_string()
+        }
+    }
+
+    pub fn expire_toast(&mut self) -> bool {
+        if self.toast.as_ref().is_some_and(|(_, raised)| raised.elapsed() > TOAST_DURATION) {
+            self.toast = None;
+            return true;
+        }
+        false
+    }
+
     /// Process tick-based data refresh (every 3 seconds).
     pub fn process_tick(&mut self) -> bool {
         let now = Instant::now();
-        if now.duration_since(self.last_tick) < self.tick_rate {
+        if now.duration_since(self.last_tick) < self.effective_tick_rate() {
             return false;
         }
 
         self.tick_counter += 1;
 
+        // A replay shows the next recorded frame in place of every refresh,
+        // after a first tick on the first frame
+        if let Some(ref mut replay) = self.replay {
+            if self.tick_counter > 1 && replay.advance() {
+                self.show_replay_frame();
+            }
+            self.last_tick = now;
+            return true;
+        }
+
         match &self.app_view {
-            AppView::System => {
+            AppView::System | AppView::Graphs => {
                 self.monitor.update();
             }
             AppView::Containers | AppView::ContainerLogs(_) | AppView::ContainerLogsMulti(_) => {
@@ -23,27 +291,262 @@ impl App {
                     self.docker_monitor.update();
                 }
             }
-            AppView::Swarm | AppView::SwarmServiceTasks(_, _) | AppView::SwarmServiceLogs(_, _) => {
+            // Process usage of the daemon comes from the host snapshot
+            AppView::DockerHealth => {
+                self.monitor.update();
+                self.docker_monitor.check_daemon();
+            }
+            AppView::Swarm
+            | AppView::SwarmServiceTasks(_, _)
+            | AppView::SwarmServiceLogs(_, _)
+            | AppView::SwarmSplitLogs => {
+                if self.swarm_monitor.is_swarm() {
+                    self.swarm_monitor.update();
+                }
+            }
+            AppView::Kubernetes | AppView::KubernetesPodLogs(_, _) => {
+                self.kube_monitor.update();
+            }
+            AppView::Watchlist => {
+                if self.docker_monitor.is_available() {
+                    self.docker_monitor.update();
+                }
                 if self.swarm_monitor.is_swarm() {
                     self.swarm_monitor.update();
                 }
             }
         }
 
-        if !self.swarm_monitor.is_swarm() && self.tick_counter % 10 == 0 {
+        // The pinned summary shows host data on the other tabs, so keep it fresh.
+        if self.pin_summary && self.summary_pinnable() {
+            self.monitor.update();
+        }
+
+        // Health checks run every tick regardless of tab, so warnings stay current.
+        self.health_monitor.update();
+        self.monitor.check_updates();
+        self.monitor.run_custom_lines();
+        self.run_schedule();
+        self.mutes.reload();
+        self.note_file.reload();
+        if self.config.containers.check_registry_digest {
+            self.docker_monitor.check_image_drift();
+        }
+        if self.config.containers.scan_images {
+            self.docker_monitor.check_image_scans();
+        }
+
+        if let Some(interval) = self.background_refresh
+            && now.duration_since(self.last_background_refresh) >= interval {
+                self.refresh_inactive();
+                self.last_background_refresh = now;
+            }
+
+        if !self.swarm_monitor.is_swarm() && self.tick_counter.is_multiple_of(10) {
             self.swarm_monitor.recheck_swarm();
         }
 
+        self.forward_alerts();
+        self.record_frame();
+        crate::cmd::reap();
+
         self.last_tick = now;
         true
     }
 
+    /// Append what the monitors hold now to the `--record` file. A failed
+    /// write ends the recording.
+    fn record_frame(&mut self) {
+        let Some(ref mut recorder) = self.recorder else {
+            return;
+        };
+        if self.monitor.last_data.is_none() {
+            return;
+        }
+        let swarm = &self.swarm_monitor;
+        let frame = Frame {
+            at: chrono::Utc::now().timestamp(),
+            system: self.monitor.last_data.clone(),
+            containers: self.docker_monitor.containers.clone(),
+            swarm: swarm.is_swarm().then(|| SwarmFrame {
+                cluster: swarm.cluster_info.clone(),
+                nodes: swarm.nodes.clone(),
+                services: swarm.services.clone(),
+                service_tasks: swarm.service_tasks.clone(),
+                failed_tasks: swarm.failed_tasks.clone(),
+                warnings: swarm.warnings.clone(),
+            }),
+        };
+        if let Err(e) = recorder.write(&frame) {
+            tracing::warn!("Recording stopped: {}", e);
+            self.toast = Some((format!("Recording stopped: {}", e), Instant::now()));
+            self.recorder = None;
+        }
+    }
+
+    /// Hand the replayed frame to the monitors, as a refresh would.
+    pub fn show_replay_frame(&mut self) {
+        let Some(ref replay) = self.replay else {
+            return;
+        };
+        let frame = replay.frame();
+        if let Some(ref data) = frame.system {
+            self.monitor.show_replayed(data.clone());
+        }
+        self.docker_monitor.show_replayed(frame.containers.clone());
+        if let Some(ref swarm) = frame.swarm {
+            self.swarm_monitor.show_replayed(swarm.clone());
+        }
+    }
+
+    /// Start the scheduled rolling restarts that have come due. A read-only
+    /// instance leaves them in the file for one that may act, such as the daemon.
+    pub fn run_schedule(&mut self) {
+        if self.read_only {
+            self.schedule.reload();
+            return;
+        }
+        for restart in self.schedule.take_due(chrono::Local::now().timestamp()) {
+            tracing::info!("Scheduled rolling restart of {} is due", restart.service_name);
+            self.swarm_monitor.force_restart_service(&restart.service_id);
+        }
+    }
+
+    /// Send alert transitions to syslog when `--syslog` is enabled.
+    pub fn forward_alerts(&mut self) {
+        if self.syslog.is_some() {
+            let alerts = self.current_alerts();
+            if let Some(ref mut syslog) = self.syslog {
+                syslog.forward(alerts);
+            }
+        }
+    }
+
+    /// Persist the latest system snapshot, and reload the History panel's
+    /// graphs while it is shown.
+    pub fn record_system_history(&mut self) {
+        let (Some(store), Some(data)) = (self.history.as_mut(), self.monitor.last_data.as_ref()) else {
+            return;
+        };
+        if let Err(e) = store.record_system(chrono::Utc::now().timestamp(), data) {
+            tracing::warn!("Failed to record metrics history: {}", e);
+        }
+        if self.monitor.ui_state.panel == SystemPanel::History {
+            self.load_history();
+        }
+        if self.app_view == AppView::Graphs {
+            self.load_graph();
+        }
+    }
+
+    pub fn record_container_history(&mut self) {
+        let Some(store) = self.history.as_mut() else {
+            return;
+        };
+        let ts = chrono::Utc::now().timestamp();
+        if let Err(e) = store.record_containers(ts, &self.docker_monitor.containers) {
+            tracing::warn!("Failed to record container history: {}", e);
+        }
+    }
+
+    pub fn record_service_history(&mut self) {
+        let Some(store) = self.history.as_mut() else {
+            return;
+        };
+        let ts = chrono::Utc::now().timestamp();
+        if let Err(e) = store.record_services(ts, &self.swarm_monitor.services) {
+            tracing::warn!("Failed to record service history: {}", e);
+        }
+    }
+
+    /// Open the Graphs view over the current view, listing every metric
+    /// with recorded history.
+    pub fn open_graphs(&mut self) {
+        let Some(ref store) = self.history else {
+            return;
+        };
+        match store.graph_metrics() {
+            Ok(metrics) => {
+                self.graph_state = Some(GraphViewState::new(metrics, self.app_view.clone()));
+                self.graph_status = None;
+                self.app_view = AppView::Graphs;
+                self.load_graph();
+            }
+            Err(e) => tracing::warn!("Failed to list history metrics: {}", e),
+        }
+    }
+
+    /// Reload the selected metric for the selected range.
+    pub fn load_graph(&mut self) {
+        let (Some(store), Some(state)) = (self.history.as_ref(), self.graph_state.as_mut()) else {
+            return;
+        };
+        let Some(metric) = state.metric() else {
+            return;
+        };
+        let width = crate::view::screen_size().map(|(w, _)| w as usize).unwrap_or(80);
+        let buckets = crate::view::graph_width(width) * 2;
+        let now = chrono::Utc::now().timestamp();
+        match store.metric_history(metric, now - state.range_secs(), now, buckets) {
+            Ok(values) => state.values = values,
+            Err(e) => self.graph_status = Some(format!("Error: {}", e)),
+        }
+    }
+
+    /// Load the last 24h into the History panel, one bucket per braille
+    /// column half across the terminal width.
+    pub fn load_history(&mut self) {
+        let Some(ref store) = self.history else {
+            return;
+        };
+        let width = crate::view::screen_size().map(|(w, _)| w as usize).unwrap_or(80);
+        let buckets = crate::view::graph_width(width) * 2;
+        let now = chrono::Utc::now().timestamp();
+        match store.system_history(now - GRAPH_WINDOW_SECS, now, buckets) {
+            Ok(series) => self.monitor.ui_state.history = series,
+            Err(e) => {
+                tracing::warn!("Failed to load metrics history: {}", e);
+                self.monitor.ui_state.status_message = Some(format!("Error: {}", e));
+            }
+        }
+    }
+
+    /// Refresh the monitors backing tabs that are not currently shown, so
+    /// tab-bar counts and the data seen on switching are not arbitrarily old.
+    fn refresh_inactive(&mut self) {
+        let view = &self.app_view;
+        if !matches!(view, AppView::System | AppView::Graphs) {
+            self.monitor.update();
+        }
+        let containers_active = matches!(
+            view,
+            AppView::Containers | AppView::ContainerLogs(_) | AppView::ContainerLogsMulti(_) | AppView::Watchlist
+        );
+        if !containers_active && self.docker_monitor.is_available() {
+            self.docker_monitor.update();
+        }
+        let swarm_active = matches!(
+            view,
+            AppView::Swarm
+            | AppView::SwarmServiceTasks(_, _)
+            | AppView::SwarmServiceLogs(_, _)
+            | AppView::SwarmSplitLogs
+            | AppView::Watchlist
+        );
+        if !swarm_active && self.swarm_monitor.is_swarm() {
+            self.swarm_monitor.update();
+        }
+        if !matches!(view, AppView::Kubernetes | AppView::KubernetesPodLogs(_, _)) {
+            self.kube_monitor.update();
+        }
+    }
+
     /// Poll logs if in log view.
     pub fn poll_logs(&mut self) -> bool {
         let mut needs_render = false;
 
-        if matches!(self.app_view, AppView::ContainerLogs(_)) {
-            if let AppView::ContainerLogs(container_id) = &self.app_view {
+        if matches!(self.app_view, AppView::ContainerLogs(_))
+            && let AppView::ContainerLogs(container_id) = &self.app_view {
                 let had_lines = self
                     .docker_monitor
                     .get_log_state(container_id)
@@ -59,7 +562,6 @@ impl App {
                     needs_render = true;
                 }
             }
-        }
         if matches!(self.app_view, AppView::ContainerLogsMulti(_)) {
             let had_total = self
                 .docker_monitor
@@ -97,63 +599,122 @@ impl App {
             }
         }
 
+        if matches!(self.app_view, AppView::KubernetesPodLogs(_, _)) && self.kube_monitor.poll_logs() {
+            needs_render = true;
+        }
+
+        if matches!(self.app_view, AppView::SwarmSplitLogs) && self.swarm_monitor.poll_split_logs() {
+            // Keep the other pane on the focused pane's moment as lines arrive
+            let page = self.view_height().saturating_sub(5);
+            if let Some(ref mut split) = self.swarm_monitor.split_logs {
+                split.sync(page);
+            }
+            needs_render = true;
+        }
+
         needs_render
     }
 
-    /// Poll background actions (container start/stop/restart, rolling restart, scale).
+    /// Poll background actions (container start/stop/restart, rolling restart, scale,
+    /// process profiling).
     pub fn poll_actions(&mut self) -> bool {
         let mut needs_render = false;
-        if self.docker_monitor.action_in_progress && self.docker_monitor.poll_action() {
+        if self.docker_monitor.poll_action() {
             needs_render = true;
         }
-        if self.swarm_monitor.action_in_progress && self.swarm_monitor.poll_action() {
+        if self.swarm_monitor.poll_action() {
+            needs_render = true;
+        }
+        if self.poll_silences() {
+            needs_render = true;
+        }
+        if self.poll_incidents() {
+            needs_render = true;
+        }
+        if self.monitor.poll_profile() {
+            needs_render = true;
+        }
+        if self.monitor.poll_trace() {
+            needs_render = true;
+        }
+        if self.monitor.poll_updates() {
+            needs_render = true;
+        }
+        if self.monitor.poll_custom_lines() {
+            needs_render = true;
+        }
+        self.docker_monitor.poll_events();
+        if self.docker_monitor.poll_image_drift() {
+            needs_render = true;
+        }
+        if self.docker_monitor.poll_image_scans() {
+            needs_render = true;
+        }
+        if self.docker_monitor.poll_daemon() {
+            needs_render = true;
+        }
+        if self.monitor.poll_auth() {
+            needs_render = true;
+        }
+        if self.monitor.poll_execs() {
             needs_render = true;
         }
         needs_render
     }
 
-    /// Immediate refresh on tab switch.
+    /// On a tab switch, render the tab's cached data right away and start a
+    /// background refresh of it. Every monitor skips an update while one is
+    /// in flight, so flicking through tabs never queues up work.
     pub fn refresh_on_tab_switch(&mut self) -> bool {
-        let now = Instant::now();
-        if self.app_view != self.prev_app_view {
-            let since_last = now.duration_since(self.last_tab_refresh);
-            if since_last >= self.min_refresh_interval {
-                match &self.app_view {
-                    AppView::System => {
-                        self.monitor.update();
-                    }
-                    AppView::Containers
-                    | AppView::ContainerLogs(_)
-                    | AppView::ContainerLogsMulti(_) => {
-                        if self.docker_monitor.is_available() {
-                            self.docker_monitor.update();
-                        }
-                    }
-                    AppView::Swarm
-                    | AppView::SwarmServiceTasks(_, _)
-                    | AppView::SwarmServiceLogs(_, _) => {
-                        if self.swarm_monitor.is_swarm() {
-                            self.swarm_monitor.update();
-                        }
-                    }
-                }
-                self.last_tab_refresh = now;
-            }
-            self.prev_app_view = self.app_view.clone();
-            return true;
+        if self.app_view == self.prev_app_view {
+            return false;
         }
-        false
+        match &self.app_view {
+            AppView::System | AppView::Graphs => {
+                self.monitor.update();
+            }
+            AppView::Containers
+            | AppView::ContainerLogs(_)
+            | AppView::ContainerLogsMulti(_) => {
+                if self.docker_monitor.is_available() {
+                    self.docker_monitor.update();
+                }
+            }
+            AppView::DockerHealth => {
+                self.monitor.update();
+            }
+            AppView::Swarm
+            | AppView::SwarmServiceTasks(_, _)
+            | AppView::SwarmServiceLogs(_, _)
+            | AppView::SwarmSplitLogs => {
+                if self.swarm_monitor.is_swarm() {
+                    self.swarm_monitor.update();
+                }
+            }
+            AppView::Kubernetes | AppView::KubernetesPodLogs(_, _) => {
+                self.kube_monitor.update();
+            }
+            AppView::Watchlist => {
+                if self.docker_monitor.is_available() {
+                    self.docker_monitor.update();
+                }
+                if self.swarm_monitor.is_swarm() {
+                    self.swarm_monitor.update();
+                }
+            }
+        }
+        self.prev_app_view = self.app_view.clone();
+        true
     }
 
     /// Expire pending confirmation if timed out.
     pub fn expire_pending_action(&mut self) -> bool {
         let now = Instant::now();
-        if let Some(ref pa) = self.pending_action {
-            if now > pa.expires {
+        if let Some(ref pa) = self.pending_action
+            && now > pa.expires {
                 self.pending_action = None;
                 return true;
             }
-        }
         false
     }
 }
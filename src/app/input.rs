@@ -2,17 +2,29 @@ use std::time::{Duration, Instant};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::model::{AppView, SortColumn, SwarmViewLevel};
+use crate::export::{self, ExportFormat};
+use crate::macros::Step;
+use crate::model::{
+    valid_container_name, AppView, ContainerRow, KubeRow, LabelEdit, LogPaneSource, LogSelection, ProcessGrouping, SortColumn, SwarmViewLevel, SystemPanel,
+    WarningAck, WatchKind,
+};
 use crate::view::RowKind;
 
-use super::state::{resolve_swarm_overview_item, PendingAction, PendingActionKind};
+use super::state::{
+    resolve_swarm_overview_item, reveal_swarm_hit, swarm_search_hits, Palette, PendingAction, PendingActionKind,
+    SwarmSearchHit,
+};
 use super::App;
 
+const READ_ONLY_REFUSED: &str = "Read-only mode: actions are disabled";
+
 /// Result of handling a key: Quit the app, or key was consumed (needs render).
 /// None means the key was not handled.
 pub enum InputResult {
     Quit,
     Consumed,
+    /// Ctrl+Z: stop until resumed from the shell.
+    Suspend,
 }
 
 /// Handle a key event. Returns Some(Quit) to exit, Some(Consumed) if key was handled and
@@ -25,7 +37,19 @@ pub fn handle_key(app: &mut App, key_event: KeyEvent) -> Option<InputResult> {
     if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
         return Some(InputResult::Quit);
     }
+    // Raw mode turns off the terminal's own job control keys
+    if code == KeyCode::Char('z') && modifiers.contains(KeyModifiers::CONTROL) {
+        return Some(InputResult::Suspend);
+    }
+    if app.palette.is_some() {
+        return handle_palette(app, code);
+    }
+    handle_code(app, code, true)
+}
 
+/// Handle a key without modifiers; `remap` applies `[keys]`, which macro
+/// steps skip as they already name sitrep's own keys.
+fn handle_code(app: &mut App, code: KeyCode, remap: bool) -> Option<InputResult> {
     if app.pending_action.is_some() {
         if code == KeyCode::Char('y') || code == KeyCode::Char('Y') {
             let pa = app.pending_action.take().unwrap();
@@ -39,9 +63,34 @@ pub fn handle_key(app: &mut App, key_event: KeyEvent) -> Option<InputResult> {
                 PendingActionKind::ContainerRestart(id) => {
                     app.docker_monitor.restart_container(&id);
                 }
+                PendingActionKind::ContainerBatch(action, targets) => {
+                    app.docker_monitor.batch_action(&targets, action);
+                    app.docker_monitor.ui_state.selected_containers.clear();
+                }
+                PendingActionKind::ContainerRename(id, old, new) => {
+                    app.docker_monitor.rename_container(&id, &old, &new);
+                }
+                PendingActionKind::ServiceLabel(id, name, edit) => {
+                    app.swarm_monitor.edit_service_label(&id, &name, edit);
+                }
                 PendingActionKind::SwarmRollingRestart(id) => {
                     app.swarm_monitor.force_restart_service(&id);
                 }
+                PendingActionKind::SwarmScheduleRestart(restart) => {
+                    let name = restart.service_name.clone();
+                    let when = restart.local_time().map(|t| t.format("%a %H:%M").to_string()).unwrap_or_default();
+                    app.swarm_monitor.status_message = Some(match app.schedule.add(restart) {
+                        Ok(()) => format!("Rolling restart of {} scheduled for {}", name, when),
+                        Err(e) => format!("Error: {}", e),
+                    });
+                }
+                PendingActionKind::TraceProcess(pid, name) => {
+                    app.monitor.start_trace(pid, &name);
+                }
+                PendingActionKind::CreateIncident(warning) => {
+                    let message = app.create_incident(warning.as_deref());
+                    app.toast = Some((message, Instant::now()));
+                }
             }
         } else {
             app.pending_action = None;
@@ -49,6 +98,71 @@ pub fn handle_key(app: &mut App, key_event: KeyEvent) -> Option<InputResult> {
         return Some(InputResult::Consumed);
     }
 
+    if let Some(input) = app.note_input.as_mut() {
+        match code {
+            KeyCode::Enter => {
+                let text = std::mem::take(input);
+                app.note_input = None;
+                app.set_maintenance_note(&text);
+            }
+            KeyCode::Esc => app.note_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return Some(InputResult::Consumed);
+    }
+    // Remapped keys stand in for the built-in ones, except in prompts
+    let code = if typing(app) || !remap {
+        code
+    } else {
+        match app.keymap.translate(code) {
+            Some(code) => code,
+            None => return Some(InputResult::Consumed),
+        }
+    };
+
+    if code == KeyCode::Char('!') && !typing(app) {
+        if !refuse_read_only(app) {
+            app.note_input = Some(app.maintenance_note().map(|n| n.text).unwrap_or_default());
+        }
+        return Some(InputResult::Consumed);
+    }
+
+    if code == KeyCode::Char(':') && !app.macros.is_empty() && remap && !typing(app) {
+        app.palette = Some(Palette::default());
+        return Some(InputResult::Consumed);
+    }
+
+    if code == KeyCode::Char('^') && !typing(app) {
+        confirm_incident(app, None);
+        return Some(InputResult::Consumed);
+    }
+
+    if code == KeyCode::Char('P') && app.summary_pinnable() && !typing(app) {
+        app.pin_summary = !app.pin_summary;
+        return Some(InputResult::Consumed);
+    }
+
+    if code == KeyCode::Char('%') && matches!(app.app_view, AppView::System | AppView::Containers) && !typing(app) {
+        let scale = app.monitor.ui_state.cpu_scale.toggled();
+        app.set_cpu_scale(scale);
+        app.toast = Some((format!("CPU shown as {}", scale.label()), Instant::now()));
+        return Some(InputResult::Consumed);
+    }
+
+    if matches!(code, KeyCode::Char(',' | '.' | '>')) && !typing(app)
+        && let Some(replay) = app.replay.as_mut() {
+            if code == KeyCode::Char('>') {
+                replay.playing = true;
+            } else if replay.step(code == KeyCode::Char('.')) {
+                app.show_replay_frame();
+            }
+            return Some(InputResult::Consumed);
+        }
+
     let next_tab = next_tab(app);
     let prev_tab = prev_tab(app);
 
@@ -60,71 +174,197 @@ pub fn handle_key(app: &mut App, key_event: KeyEvent) -> Option<InputResult> {
         AppView::Swarm => handle_swarm(app, code, next_tab, prev_tab),
         AppView::SwarmServiceTasks(_, _) => handle_swarm_tasks(app, code),
         AppView::SwarmServiceLogs(_, _) => handle_service_logs(app, code),
+        AppView::SwarmSplitLogs => handle_split_logs(app, code),
+        AppView::Watchlist => handle_watchlist(app, code, next_tab, prev_tab),
+        AppView::Graphs => handle_graphs(app, code),
+        AppView::DockerHealth => handle_docker_health(app, code, next_tab, prev_tab),
+        AppView::Kubernetes => handle_kubernetes(app, code, next_tab, prev_tab),
+        AppView::KubernetesPodLogs(_, _) => handle_pod_logs(app, code),
     };
 
     if let Some(InputResult::Quit) = result {
         return Some(InputResult::Quit);
     }
+    if app.read_only && app.pending_action.as_ref().is_some_and(|pa| pa.kind.mutates()) {
+        app.pending_action = None;
+        app.toast = Some((READ_ONLY_REFUSED.to_string(), Instant::now()));
+    }
     if result.is_some() {
         return Some(InputResult::Consumed);
     }
     None
 }
 
-fn next_tab(app: &App) -> AppView {
-    match &app.app_view {
-        AppView::System => {
-            if app.docker_monitor.is_available() {
-                AppView::Containers
-            } else if app.swarm_monitor.is_swarm() {
-                AppView::Swarm
-            } else {
-                AppView::System
+/// The command palette: type to filter the macros, Up/Down to pick one,
+/// Enter to run it.
+fn handle_palette(app: &mut App, code: KeyCode) -> Option<InputResult> {
+    let palette = app.palette.as_mut()?;
+    let count = app.macros.matching(&palette.input).len();
+    match code {
+        KeyCode::Esc => app.palette = None,
+        KeyCode::Enter => {
+            let name = app.macros.matching(&palette.input).get(palette.selected).map(|n| n.to_string());
+            app.palette = None;
+            if let Some(name) = name {
+                return run_macro(app, &name);
             }
         }
-        AppView::Containers | AppView::ContainerLogs(_) | AppView::ContainerLogsMulti(_) => {
-            if app.swarm_monitor.is_swarm() {
-                AppView::Swarm
-            } else {
-                AppView::System
-            }
+        KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+        KeyCode::Down if palette.selected + 1 < count => palette.selected += 1,
+        KeyCode::Backspace => {
+            palette.input.pop();
+            palette.selected = 0;
         }
-        AppView::Swarm | AppView::SwarmServiceTasks(_, _) | AppView::SwarmServiceLogs(_, _) => {
-            AppView::System
+        KeyCode::Char(c) => {
+            palette.input.push(c);
+            palette.selected = 0;
         }
+        _ => {}
     }
+    Some(InputResult::Consumed)
 }
 
-fn prev_tab(app: &App) -> AppView {
-    match &app.app_view {
-        AppView::System => {
-            if app.swarm_monitor.is_swarm() {
-                AppView::Swarm
-            } else if app.docker_monitor.is_available() {
-                AppView::Containers
-            } else {
-                AppView::System
+/// Take the steps of macro `name` in turn as if typed, stopping at the
+/// first one that can't be taken here.
+fn run_macro(app: &mut App, name: &str) -> Option<InputResult> {
+    let steps = app.macros.steps(name)?.to_vec();
+    app.toast = None;
+    for (i, step) in steps.iter().enumerate() {
+        let taken = match step {
+            Step::Key(code) => match handle_code(app, *code, false) {
+                Some(InputResult::Consumed) if app.toast.as_ref().is_some_and(|(m, _)| m == READ_ONLY_REFUSED) => {
+                    Err(READ_ONLY_REFUSED.to_string())
+                }
+                Some(InputResult::Consumed) => Ok(()),
+                Some(result) => return Some(result),
+                None => Err(format!("{:?} does nothing here", code)),
+            },
+            Step::Tab(view) if tab_order(app).contains(view) => {
+                app.app_view = view.clone();
+                Ok(())
             }
+            Step::Tab(view) => Err(format!("no {:?} tab", view)),
+            Step::Select(target) => select_named(app, target),
+        };
+        if let Err(e) = taken {
+            app.toast = Some((format!("Macro {} stopped at step {}: {}", name, i + 1, e), Instant::now()));
+            return Some(InputResult::Consumed);
         }
-        AppView::Containers | AppView::ContainerLogs(_) | AppView::ContainerLogsMulti(_) => {
-            AppView::System
-        }
-        AppView::Swarm | AppView::SwarmServiceTasks(_, _) | AppView::SwarmServiceLogs(_, _) => {
-            if app.docker_monitor.is_available() {
-                AppView::Containers
+    }
+    if app.toast.is_none() {
+        app.toast = Some((format!("Ran macro {}", name), Instant::now()));
+    }
+    Some(InputResult::Consumed)
+}
+
+/// Put the cursor on the container or service called `name` in the active tab.
+fn select_named(app: &mut App, name: &str) -> Result<(), String> {
+    match app.app_view {
+        AppView::Containers => {
+            let id = app.docker_monitor.containers.iter().find(|c| c.name == name).map(|c| c.id.clone());
+            let id = id.ok_or_else(|| format!("no container {}", name))?;
+            if app.docker_monitor.select_container(&id) {
+                Ok(())
             } else {
-                AppView::System
+                Err(format!("{} is hidden by the label filter or a collapsed group", name))
             }
         }
+        AppView::Swarm => {
+            let svc = app.swarm_monitor.services.iter().find(|s| s.name == name);
+            let hit = svc.map(|s| SwarmSearchHit::Service(s.id.clone(), s.name.clone()));
+            let hit = hit.ok_or_else(|| format!("no service {}", name))?;
+            let row = reveal_swarm_hit(&mut app.swarm_monitor, &hit).ok_or_else(|| format!("service {} is not listed", name))?;
+            app.swarm_monitor.ui_state.selected_index = row;
+            Ok(())
+        }
+        _ => Err("select works in the Containers and Swarm tabs".to_string()),
+    }
+}
+
+/// Whether the active view is taking text (a search or filter prompt).
+fn typing(app: &App) -> bool {
+    match &app.app_view {
+        AppView::Containers => {
+            let ui_state = &app.docker_monitor.ui_state;
+            ui_state.filter_mode || ui_state.rename_input.is_some()
+        }
+        AppView::Swarm => {
+            let ui_state = &app.swarm_monitor.ui_state;
+            ui_state.search_mode
+                || ui_state.label_input.is_some()
+                || ui_state.schedule_input.is_some()
+                || ui_state.warning_ack.is_some()
+        }
+        AppView::ContainerLogs(id) => app.docker_monitor.get_log_state(id).is_some_and(|s| s.search_mode),
+        AppView::ContainerLogsMulti(_) => app.docker_monitor.multi_log_state.as_ref().is_some_and(|s| s.search_mode),
+        AppView::SwarmServiceLogs(_, _) => app.swarm_monitor.log_state.as_ref().is_some_and(|s| s.search_mode),
+        AppView::KubernetesPodLogs(_, _) => app.kube_monitor.log_state.as_ref().is_some_and(|s| s.search_mode),
+        AppView::SwarmSplitLogs => app
+            .swarm_monitor
+            .split_logs
+            .as_ref()
+            .is_some_and(|s| s.panes.iter().any(|p| p.search_mode)),
+        _ => false,
+    }
+}
+
+/// Tabs in tab-bar order; Containers, Swarm, Kubernetes and Watchlist only
+/// when there is something to show.
+pub(super) fn tab_order(app: &App) -> Vec<AppView> {
+    let mut tabs = vec![AppView::System];
+    if app.docker_monitor.is_available() {
+        tabs.push(AppView::Containers);
+    }
+    if app.swarm_monitor.is_swarm() {
+        tabs.push(AppView::Swarm);
+    }
+    if app.kube_monitor.is_available() {
+        tabs.push(AppView::Kubernetes);
+    }
+    if !app.watchlist.is_empty() {
+        tabs.push(AppView::Watchlist);
+    }
+    tabs
+}
+
+/// The tab a view is reached from.
+fn tab_of(view: &AppView) -> AppView {
+    match view {
+        AppView::System | AppView::Graphs => AppView::System,
+        AppView::Containers | AppView::ContainerLogs(_) | AppView::ContainerLogsMulti(_) | AppView::DockerHealth => {
+            AppView::Containers
+        }
+        AppView::Swarm
+        | AppView::SwarmServiceTasks(_, _)
+        | AppView::SwarmServiceLogs(_, _)
+        | AppView::SwarmSplitLogs => AppView::Swarm,
+        AppView::Kubernetes | AppView::KubernetesPodLogs(_, _) => AppView::Kubernetes,
+        AppView::Watchlist => AppView::Watchlist,
     }
 }
 
+fn next_tab(app: &App) -> AppView {
+    let tabs = tab_order(app);
+    let current = tab_of(&app.app_view);
+    let pos = tabs.iter().position(|t| *t == current).unwrap_or(0);
+    tabs[(pos + 1) % tabs.len()].clone()
+}
+
+fn prev_tab(app: &App) -> AppView {
+    let tabs = tab_order(app);
+    let current = tab_of(&app.app_view);
+    let pos = tabs.iter().position(|t| *t == current).unwrap_or(0);
+    tabs[(pos + tabs.len() - 1) % tabs.len()].clone()
+}
+
 fn handle_system(
     app: &mut App,
     code: KeyCode,
     next_tab: AppView,
     prev_tab: AppView,
 ) -> Option<InputResult> {
+    // Export results stay up until the next key press
+    app.monitor.ui_state.status_message = None;
     match code {
         KeyCode::Char('q') => return Some(InputResult::Quit),
         KeyCode::Tab => {
@@ -135,28 +375,25 @@ fn handle_system(
             app.app_view = prev_tab;
             return Some(InputResult::Consumed);
         }
-        KeyCode::Up => {
-            if app.monitor.ui_state.selected_index > 0 {
+        KeyCode::Up
+            if app.monitor.ui_state.selected_index > 0 => {
                 app.monitor.ui_state.selected_index -= 1;
                 return Some(InputResult::Consumed);
             }
-        }
-        KeyCode::Down => {
-            if app.monitor.ui_state.selected_index + 1 < app.monitor.ui_state.total_rows {
+        KeyCode::Down
+            if app.monitor.ui_state.selected_index + 1 < app.monitor.ui_state.total_rows => {
                 app.monitor.ui_state.selected_index += 1;
                 return Some(InputResult::Consumed);
             }
-        }
-        KeyCode::Right => {
-            if app.monitor.ui_state.selected_index < app.row_mapping.len() {
+        KeyCode::Right
+            if app.monitor.ui_state.selected_index < app.row_mapping.len() => {
                 let (pid, kind) = app.row_mapping[app.monitor.ui_state.selected_index];
                 match kind {
-                    RowKind::SectionHeader(section_id) => {
-                        if app.monitor.layout.is_collapsed(section_id) {
+                    RowKind::SectionHeader(section_id)
+                        if app.monitor.layout.is_collapsed(section_id) => {
                             app.monitor.layout.toggle_section(section_id);
                             return Some(InputResult::Consumed);
                         }
-                    }
                     RowKind::ProcessParent => {
                         app.monitor.ui_state.expanded_pids.insert(pid);
                         return Some(InputResult::Consumed);
@@ -164,9 +401,8 @@ fn handle_system(
                     _ => {}
                 }
             }
-        }
-        KeyCode::Left => {
-            if app.monitor.ui_state.selected_index < app.row_mapping.len() {
+        KeyCode::Left
+            if app.monitor.ui_state.selected_index < app.row_mapping.len() => {
                 let (pid, kind) = app.row_mapping[app.monitor.ui_state.selected_index];
                 match kind {
                     RowKind::SectionHeader(section_id) => {
@@ -195,7 +431,6 @@ fn handle_system(
                     }
                 }
             }
-        }
         KeyCode::Char('c') => {
             app.monitor.ui_state.sort_column = SortColumn::Cpu;
             return Some(InputResult::Consumed);
@@ -220,22 +455,315 @@ fn handle_system(
             app.monitor.ui_state.sort_column = SortColumn::NetUp;
             return Some(InputResult::Consumed);
         }
+        KeyCode::Char('o') => {
+            let ui_state = &mut app.monitor.ui_state;
+            ui_state.panel = if ui_state.panel == SystemPanel::Connections {
+                SystemPanel::Processes
+            } else {
+                SystemPanel::Connections
+            };
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('b') => {
+            let history = app
+                .monitor
+                .last_data
+                .as_ref()
+                .map(|d| d.network.history.as_slice())
+                .unwrap_or(&[]);
+            app.monitor.ui_state.cycle_bandwidth_panel(history);
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('h') => {
+            if app.monitor.ui_state.panel == SystemPanel::History {
+                app.monitor.ui_state.panel = SystemPanel::Processes;
+            } else if app.history.is_some() {
+                app.monitor.ui_state.panel = SystemPanel::History;
+                app.load_history();
+            } else {
+                app.monitor.ui_state.status_message =
+                    Some("History is off; set [history] enabled = true in the config".to_string());
+            }
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('a') => {
+            app.monitor.ui_state.panel = if app.monitor.ui_state.panel == SystemPanel::Security {
+                SystemPanel::Processes
+            } else {
+                SystemPanel::Security
+            };
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('g') => {
+            let ui_state = &mut app.monitor.ui_state;
+            if !cfg!(target_os = "linux") {
+                ui_state.status_message = Some("Grouping by unit needs cgroups (Linux only)".to_string());
+                return Some(InputResult::Consumed);
+            }
+            ui_state.grouping = match ui_state.grouping {
+                ProcessGrouping::Parent => ProcessGrouping::Unit,
+                ProcessGrouping::Unit => ProcessGrouping::Parent,
+            };
+            // Group keys differ between modes
+            ui_state.expanded_pids.clear();
+            ui_state.selected_index = 0;
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('M') => {
+            app.monitor.ui_state.panel = if app.monitor.ui_state.panel == SystemPanel::Memory {
+                SystemPanel::Processes
+            } else {
+                SystemPanel::Memory
+            };
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('I') => {
+            app.monitor.ui_state.panel = if app.monitor.ui_state.panel == SystemPanel::Interrupts {
+                SystemPanel::Processes
+            } else {
+                SystemPanel::Interrupts
+            };
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('U') => {
+            app.monitor.ui_state.panel = if app.monitor.ui_state.panel == SystemPanel::Users {
+                SystemPanel::Processes
+            } else {
+                SystemPanel::Users
+            };
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('E') => {
+            if app.monitor.ui_state.panel == SystemPanel::Execs {
+                app.monitor.ui_state.panel = SystemPanel::Processes;
+            } else {
+                app.monitor.start_exec_snoop();
+            }
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('D') => {
+            app.monitor.ui_state.panel = if app.monitor.ui_state.panel == SystemPanel::Diagnostics {
+                SystemPanel::Processes
+            } else {
+                SystemPanel::Diagnostics
+            };
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('x') => return Some(export_view(app, ExportFormat::Text)),
+        KeyCode::Char('X') => return Some(export_view(app, ExportFormat::Csv)),
+        KeyCode::Char('G') => return Some(graphs_view(app)),
+        KeyCode::Char('C') => return jump_to_container(app, false),
+        KeyCode::Char('L') => return jump_to_container(app, true),
+        KeyCode::Char('p') => {
+            if let Some((pid, name)) = selected_process(app) {
+                app.monitor.start_profile(pid, &name);
+                return Some(InputResult::Consumed);
+            }
+        }
+        KeyCode::Char('t') => {
+            // strace/dtruss stop the target on every syscall, so ask first
+            if let Some((pid, name)) = selected_process(app) {
+                app.pending_action = Some(PendingAction {
+                    description: format!(
+                        "Trace syscalls of '{}' (PID {}) for {}s? This slows it down",
+                        name,
+                        pid,
+                        crate::tracer::TRACE_SECS
+                    ),
+                    kind: PendingActionKind::TraceProcess(pid, name),
+                    expires: Instant::now() + Duration::from_secs(5),
+                });
+                return Some(InputResult::Consumed);
+            }
+        }
+        KeyCode::Esc if app.monitor.ui_state.panel != SystemPanel::Processes => {
+            app.monitor.ui_state.panel = SystemPanel::Processes;
+            return Some(InputResult::Consumed);
+        }
         _ => {}
     }
     None
 }
 
+/// PID and name of the process row under the cursor in the System tab.
+pub(super) fn selected_process(app: &App) -> Option<(u32, String)> {
+    let selected = app.row_mapping.get(app.monitor.ui_state.selected_index).copied();
+    let (Some((pid, RowKind::ProcessParent | RowKind::ProcessChild)), Some(data)) =
+        (selected, app.monitor.last_data.as_ref())
+    else {
+        return None;
+    };
+    let name = data
+        .historical_top
+        .iter()
+        .flat_map(|g| std::iter::once((g.pid, &g.name)).chain(g.children.iter().map(|c| (c.pid, &c.name))))
+        .find(|(p, _)| *p == pid)
+        .map(|(_, name)| name.clone())
+        .unwrap_or_default();
+    Some((pid.as_u32(), name))
+}
+
+/// Open the Containers tab at the container the selected process runs in,
+/// or straight into that container's logs.
+fn jump_to_container(app: &mut App, logs: bool) -> Option<InputResult> {
+    let (pid, name) = selected_process(app)?;
+    let id = app.monitor.last_data.as_ref().and_then(|d| d.process_containers.get(&pid)).cloned();
+    let container = id.as_ref().and_then(|id| app.docker_monitor.containers.iter().find(|c| &c.id == id)).cloned();
+    let Some(c) = container else {
+        app.monitor.ui_state.status_message = Some(match id {
+            Some(id) => format!("Container {} is not listed in the Containers tab", id),
+            None => format!("'{}' (PID {}) does not run in a container", name, pid),
+        });
+        return Some(InputResult::Consumed);
+    };
+    if logs {
+        app.docker_monitor.start_log_stream(&c.id, &c.name);
+        app.app_view = AppView::ContainerLogs(c.id);
+    } else {
+        if !app.docker_monitor.select_container(&c.id) {
+            app.docker_monitor.status_message =
+                Some(format!("{} is hidden by the label filter or a collapsed group", c.name));
+        }
+        app.app_view = AppView::Containers;
+    }
+    Some(InputResult::Consumed)
+}
+
+/// Export the table shown in the active tab and report the outcome in that
+/// tab's status line.
+fn export_view(app: &mut App, format: ExportFormat) -> InputResult {
+    let (table, name) = match &app.app_view {
+        AppView::Containers => {
+            let monitor = &app.docker_monitor;
+            let containers = monitor.rows().into_iter().filter_map(|row| match row {
+                ContainerRow::Container(i) => monitor.containers.get(i),
+                ContainerRow::GroupHeader(_) => None,
+            });
+            (export::containers_table(containers), "containers")
+        }
+        AppView::Swarm => (export::services_table(&app.swarm_monitor.services), "services"),
+        _ => match (&app.monitor.last_data, &app.monitor.ui_state.panel) {
+            (Some(data), SystemPanel::Connections) => {
+                (export::connections_table(&data.socket_overview.remotes), "connections")
+            }
+            (Some(data), _) => (export::processes_table(data), "processes"),
+            (None, _) => return InputResult::Consumed,
+        },
+    };
+    let message = match export::export(&table, name, format) {
+        Ok(path) => format!(
+            "Exported {} rows to {} (copied to clipboard)",
+            table.rows.len(),
+            path.display()
+        ),
+        Err(e) => format!("Error: {}", e),
+    };
+    match &app.app_view {
+        AppView::Containers => app.docker_monitor.status_message = Some(message),
+        AppView::Swarm => app.swarm_monitor.status_message = Some(message),
+        _ => app.monitor.ui_state.status_message = Some(message),
+    }
+    InputResult::Consumed
+}
+
+/// Open the Graphs view, or explain in the tab's status line why it can't.
+fn graphs_view(app: &mut App) -> InputResult {
+    if app.history.is_some() {
+        app.open_graphs();
+        return InputResult::Consumed;
+    }
+    let message = Some("History is off; set [history] enabled = true in the config".to_string());
+    match &app.app_view {
+        AppView::Containers => app.docker_monitor.status_message = message,
+        AppView::Swarm => app.swarm_monitor.status_message = message,
+        _ => app.monitor.ui_state.status_message = message,
+    }
+    InputResult::Consumed
+}
+
+fn handle_graphs(app: &mut App, code: KeyCode) -> Option<InputResult> {
+    let state = app.graph_state.as_mut()?;
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.app_view = state.return_view.clone();
+            app.graph_state = None;
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Up | KeyCode::Left => state.select_prev(),
+        KeyCode::Down | KeyCode::Right => state.select_next(),
+        KeyCode::Char('+') | KeyCode::Char('=') => state.zoom_in(),
+        KeyCode::Char('-') => state.zoom_out(),
+        _ => return None,
+    }
+    app.load_graph();
+    Some(InputResult::Consumed)
+}
+
+fn handle_docker_health(
+    app: &mut App,
+    code: KeyCode,
+    next_tab: AppView,
+    prev_tab: AppView,
+) -> Option<InputResult> {
+    match code {
+        KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left => app.app_view = AppView::Containers,
+        KeyCode::Tab => app.app_view = next_tab,
+        KeyCode::BackTab => app.app_view = prev_tab,
+        _ => return None,
+    }
+    app.docker_monitor.daemon_health = None;
+    Some(InputResult::Consumed)
+}
+
 fn handle_containers(
     app: &mut App,
     code: KeyCode,
     next_tab: AppView,
     prev_tab: AppView,
 ) -> Option<InputResult> {
+    if app.docker_monitor.ui_state.filter_mode {
+        let ui_state = &mut app.docker_monitor.ui_state;
+        match code {
+            KeyCode::Enter => app.docker_monitor.apply_label_filter(),
+            KeyCode::Esc => {
+                // Discard edits, keeping the applied filter
+                ui_state.filter_mode = false;
+                ui_state.filter_input = crate::model::format_label_filter(&ui_state.label_filter);
+            }
+            KeyCode::Backspace => {
+                ui_state.filter_input.pop();
+            }
+            KeyCode::Char(c) => ui_state.filter_input.push(c),
+            _ => return None,
+        }
+        return Some(InputResult::Consumed);
+    }
+    if let Some(input) = app.docker_monitor.ui_state.rename_input.as_mut() {
+        match code {
+            KeyCode::Enter => {
+                let name = input.trim().to_string();
+                app.docker_monitor.ui_state.rename_input = None;
+                confirm_rename(app, name);
+            }
+            KeyCode::Esc => app.docker_monitor.ui_state.rename_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => return None,
+        }
+        return Some(InputResult::Consumed);
+    }
+
     match code {
         KeyCode::Char('q') | KeyCode::Esc => {
             app.app_view = AppView::System;
             return Some(InputResult::Consumed);
         }
+        KeyCode::Char('/') => {
+            app.docker_monitor.ui_state.filter_mode = true;
+            return Some(InputResult::Consumed);
+        }
         KeyCode::Tab => {
             app.app_view = next_tab;
             return Some(InputResult::Consumed);
@@ -244,28 +772,47 @@ fn handle_containers(
             app.app_view = prev_tab;
             return Some(InputResult::Consumed);
         }
-        KeyCode::Up => {
-            if app.docker_monitor.ui_state.selected_index > 0 {
-                app.docker_monitor.ui_state.selected_index -= 1;
-                app.docker_monitor.ui_state.selected_id = app.docker_monitor
-                    .containers.get(app.docker_monitor.ui_state.selected_index)
-                    .map(|c| c.id.clone());
-                app.docker_monitor.status_message = None;
+        KeyCode::Up
+            if app.docker_monitor.ui_state.selected_index > 0 => {
+                app.docker_monitor.select_row(app.docker_monitor.ui_state.selected_index - 1);
                 return Some(InputResult::Consumed);
             }
-        }
-        KeyCode::Down => {
-            if app.docker_monitor.ui_state.selected_index + 1 < app.docker_monitor.containers.len()
-            {
-                app.docker_monitor.ui_state.selected_index += 1;
-                app.docker_monitor.ui_state.selected_id = app.docker_monitor
-                    .containers.get(app.docker_monitor.ui_state.selected_index)
-                    .map(|c| c.id.clone());
-                app.docker_monitor.status_message = None;
+        KeyCode::Down
+            if app.docker_monitor.ui_state.selected_index + 1 < app.docker_monitor.ui_state.total_rows
+            => {
+                app.docker_monitor.select_row(app.docker_monitor.ui_state.selected_index + 1);
                 return Some(InputResult::Consumed);
             }
+        KeyCode::PageUp | KeyCode::PageDown | KeyCode::Home | KeyCode::End => {
+            let count = app.docker_monitor.ui_state.total_rows;
+            if count == 0 {
+                return None;
+            }
+            let page_size = app.view_height().saturating_sub(4)
+                .max(1);
+            let current = app.docker_monitor.ui_state.selected_index;
+            let target = match code {
+                KeyCode::PageUp => current.saturating_sub(page_size),
+                KeyCode::PageDown => (current + page_size).min(count - 1),
+                KeyCode::Home => 0,
+                _ => count - 1,
+            };
+            app.docker_monitor.select_row(target);
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('g') => {
+            app.docker_monitor.cycle_grouping();
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('u') => {
+            app.docker_monitor.toggle_uptime_sort();
+            return Some(InputResult::Consumed);
         }
         KeyCode::Right => {
+            if let Some(name) = app.docker_monitor.selected_group().map(|g| g.name.clone()) {
+                app.docker_monitor.set_group_collapsed(&name, false);
+                return Some(InputResult::Consumed);
+            }
             if let Some(c) = app.docker_monitor.selected_container().cloned() {
                 app.docker_monitor.start_log_stream(&c.id, &c.name);
                 app.app_view = AppView::ContainerLogs(c.id.clone());
@@ -273,6 +820,10 @@ fn handle_containers(
             }
         }
         KeyCode::Left => {
+            if let Some(name) = app.docker_monitor.selected_group().map(|g| g.name.clone()) {
+                app.docker_monitor.set_group_collapsed(&name, true);
+                return Some(InputResult::Consumed);
+            }
             if let Some(c) = app.docker_monitor.selected_container().cloned() {
                 if app.docker_monitor.ui_state.expanded_ids.contains(&c.id) {
                     app.docker_monitor.ui_state.expanded_ids.remove(&c.id);
@@ -317,7 +868,73 @@ fn handle_containers(
                 }
             }
         }
+        KeyCode::Char('p') => {
+            if let Some(c) = app.docker_monitor.selected_container().cloned() {
+                app.docker_monitor.probe_ports(&c);
+                return Some(InputResult::Consumed);
+            }
+        }
+        KeyCode::Char('w') => {
+            if let Some(c) = app.docker_monitor.selected_container() {
+                let name = c.name.clone();
+                app.docker_monitor.status_message = Some(toggle_watch(app, WatchKind::Container, &name));
+                return Some(InputResult::Consumed);
+            }
+        }
+        KeyCode::Char('V') => {
+            if let Some(c) = app.docker_monitor.selected_container().cloned() {
+                let source = LogPaneSource::Container { id: c.id, name: c.name };
+                match app.swarm_monitor.mark_split_source(source, &app.docker_monitor) {
+                    Some(message) => app.docker_monitor.status_message = Some(message),
+                    None => app.app_view = AppView::SwarmSplitLogs,
+                }
+                return Some(InputResult::Consumed);
+            }
+        }
+        KeyCode::Char('x') => return Some(export_view(app, ExportFormat::Text)),
+        KeyCode::Char('X') => return Some(export_view(app, ExportFormat::Csv)),
+        KeyCode::Char('G') => return Some(graphs_view(app)),
+        KeyCode::Char('D') if app.docker_monitor.container_cli().is_some() => {
+            let cli = app.docker_monitor.container_cli().map_or("", |cli| cli.name());
+            app.docker_monitor.status_message =
+                Some(format!("The daemon panel needs Docker; these containers come from {}", cli));
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('D') => {
+            app.app_view = AppView::DockerHealth;
+            app.docker_monitor.check_daemon();
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Char('n') => {
+            if let Some(c) = app.docker_monitor.selected_container().cloned() {
+                app.docker_monitor.namespace_sockets(&c);
+                return Some(InputResult::Consumed);
+            }
+        }
+        KeyCode::Char('N') => {
+            if let Some(name) = app.docker_monitor.selected_container().map(|c| c.name.clone()) {
+                if !refuse_read_only(app) {
+                    app.docker_monitor.ui_state.rename_input = Some(name);
+                }
+                return Some(InputResult::Consumed);
+            }
+        }
+        KeyCode::Char('E') => {
+            if let Some(c) = app.docker_monitor.selected_container() {
+                let how = match c.labels.get("com.docker.swarm.service.name") {
+                    Some(service) => format!("press E on service {} in the Swarm tab", service),
+                    None => "set them where it is defined and recreate it".to_string(),
+                };
+                app.docker_monitor.status_message =
+                    Some(format!("Docker can't change a container's labels once it is created; {}", how));
+                return Some(InputResult::Consumed);
+            }
+        }
         KeyCode::Char('S') => {
+            if let Some(pa) = batch_confirmation(app, "start") {
+                app.pending_action = Some(pa);
+                return Some(InputResult::Consumed);
+            }
             if let Some(c) = app.docker_monitor.selected_container().cloned() {
                 app.pending_action = Some(PendingAction {
                     description: format!("Start container '{}'?", c.name),
@@ -328,6 +945,10 @@ fn handle_containers(
             }
         }
         KeyCode::Char('T') => {
+            if let Some(pa) = batch_confirmation(app, "stop") {
+                app.pending_action = Some(pa);
+                return Some(InputResult::Consumed);
+            }
             if let Some(c) = app.docker_monitor.selected_container().cloned() {
                 app.pending_action = Some(PendingAction {
                     description: format!("Stop container '{}'?", c.name),
@@ -338,6 +959,10 @@ fn handle_containers(
             }
         }
         KeyCode::Char('R') => {
+            if let Some(pa) = batch_confirmation(app, "restart") {
+                app.pending_action = Some(pa);
+                return Some(InputResult::Consumed);
+            }
             if let Some(c) = app.docker_monitor.selected_container().cloned() {
                 app.pending_action = Some(PendingAction {
                     description: format!("Restart container '{}'?", c.name),
@@ -352,16 +977,170 @@ fn handle_containers(
     None
 }
 
+/// With `--read-only`, refuse to open an action's prompt, as the action
+/// itself would be refused; returns whether it was.
+fn refuse_read_only(app: &mut App) -> bool {
+    if app.read_only {
+        app.toast = Some((READ_ONLY_REFUSED.to_string(), Instant::now()));
+    }
+    app.read_only
+}
+
+/// Ask to schedule a rolling restart of a service for the time typed at
+/// the prompt, or cancel its pending one when nothing was typed.
+fn schedule_restart(app: &mut App, service_id: String, service_name: String, text: &str) {
+    if text.trim().is_empty() {
+        app.swarm_monitor.status_message = Some(match app.schedule.cancel(&service_id) {
+            Ok(Some(_)) => format!("Cancelled the scheduled restart of {}", service_name),
+            Ok(None) => format!("No restart is scheduled for {}", service_name),
+            Err(e) => format!("Error: {}", e),
+        });
+        return;
+    }
+    let now = chrono::Local::now();
+    match crate::schedule::parse_when(text, now) {
+        Ok(at) => {
+            let until = crate::schedule::format_until(at.timestamp(), now.timestamp());
+            app.pending_action = Some(PendingAction {
+                description: format!(
+                    "Schedule a rolling restart of '{}' for {} (in {})?",
+                    service_name,
+                    at.format("%a %H:%M"),
+                    until
+                ),
+                kind: PendingActionKind::SwarmScheduleRestart(crate::schedule::ScheduledRestart {
+                    service_id,
+                    service_name,
+                    at: at.timestamp(),
+                }),
+                expires: Instant::now() + Duration::from_secs(5),
+            });
+        }
+        Err(e) => app.swarm_monitor.status_message = Some(e),
+    }
+}
+
+/// Ask before opening an incident about `warning`, or a triage report.
+fn confirm_incident(app: &mut App, warning: Option<String>) {
+    let Some(provider) = app.incidents.provider() else {
+        app.toast = Some(("Set [incidents] provider and key to open incidents".to_string(), Instant::now()));
+        return;
+    };
+    let summary = app.incident(warning.as_deref()).summary;
+    app.pending_action = Some(PendingAction {
+        description: format!("Open a {} incident: {}?", provider.label(), summary),
+        kind: PendingActionKind::CreateIncident(warning),
+        expires: Instant::now() + Duration::from_secs(10),
+    });
+}
+
+/// Ask to rename the selected container to what was typed at the prompt.
+fn confirm_rename(app: &mut App, name: String) {
+    let Some(c) = app.docker_monitor.selected_container().cloned() else { return };
+    if name == c.name {
+        return;
+    }
+    if !valid_container_name(&name) {
+        app.docker_monitor.status_message = Some(format!(
+            "Invalid container name '{}': start with a letter or digit, then letters, digits, _ . or -",
+            name
+        ));
+        return;
+    }
+    app.pending_action = Some(PendingAction {
+        description: format!("Rename container '{}' to '{}'?", c.name, name),
+        kind: PendingActionKind::ContainerRename(c.id, c.name, name),
+        expires: Instant::now() + Duration::from_secs(5),
+    });
+}
+
+/// One confirmation for `action` on every marked container still listed,
+/// naming them; `None` when nothing is marked.
+fn batch_confirmation(app: &App, action: &'static str) -> Option<PendingAction> {
+    let marked = &app.docker_monitor.ui_state.selected_containers;
+    let targets: Vec<(String, String)> = app
+        .docker_monitor
+        .containers
+        .iter()
+        .filter(|c| marked.contains(&c.id))
+        .map(|c| (c.id.clone(), c.name.clone()))
+        .collect();
+    if targets.is_empty() {
+        return None;
+    }
+    let mut names: Vec<String> = targets.iter().take(4).map(|(_, name)| name.clone()).collect();
+    if targets.len() > names.len() {
+        names.push(format!("+{} more", targets.len() - names.len()));
+    }
+    let verb = match action {
+        "start" => "Start",
+        "stop" => "Stop",
+        _ => "Restart",
+    };
+    let noun = if targets.len() == 1 { "container" } else { "containers" };
+    Some(PendingAction {
+        description: format!("{} {} marked {} ({})?", verb, targets.len(), noun, names.join(", ")),
+        kind: PendingActionKind::ContainerBatch(action, targets),
+        expires: Instant::now() + Duration::from_secs(5),
+    })
+}
+
+/// Line cursor keys shared by the log viewers: `v` starts or stops
+/// selecting, `#` toggles line numbers, and while selecting ↑↓/PgUp/PgDn
+/// move the cursor, `y` copies the line, Enter shows it in full and Esc
+/// stops. Any key closes the full-line popup. `line_text` returns the text
+/// of a buffer index.
+#[allow(clippy::too_many_arguments)]
+fn handle_log_cursor(
+    code: KeyCode,
+    selection: &mut LogSelection,
+    display: &[usize],
+    first: u64,
+    scroll_offset: &mut usize,
+    auto_follow: &mut bool,
+    page: usize,
+    line_text: impl Fn(usize) -> Option<String>,
+) -> Option<InputResult> {
+    let copy = |selection: &mut LogSelection| {
+        let line = selection.line?;
+        let text = line_text(line.saturating_sub(first) as usize)?;
+        let _ = crate::export::copy_to_clipboard(&mut std::io::stdout(), &text);
+        selection.copied = Some(line);
+        Some(())
+    };
+    if selection.expanded {
+        if code == KeyCode::Char('y') {
+            copy(selection);
+        }
+        selection.expanded = false;
+        return Some(InputResult::Consumed);
+    }
+    match code {
+        KeyCode::Char('v') if selection.is_active() => selection.stop(),
+        KeyCode::Char('v') => selection.start(display, first, *scroll_offset, *auto_follow, page),
+        KeyCode::Char('#') => selection.line_numbers = !selection.line_numbers,
+        _ if !selection.is_active() => return None,
+        KeyCode::Up => selection.move_by(display, first, -1, page, scroll_offset, auto_follow),
+        KeyCode::Down => selection.move_by(display, first, 1, page, scroll_offset, auto_follow),
+        KeyCode::PageUp => selection.move_by(display, first, -(page as isize), page, scroll_offset, auto_follow),
+        KeyCode::PageDown => selection.move_by(display, first, page as isize, page, scroll_offset, auto_follow),
+        KeyCode::Char('y') => {
+            copy(selection);
+        }
+        KeyCode::Enter => selection.expanded = true,
+        KeyCode::Esc => selection.stop(),
+        _ => return None,
+    }
+    Some(InputResult::Consumed)
+}
+
 fn handle_container_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
-    let page_size = crossterm::terminal::size()
-        .map(|(_, h)| h as usize)
-        .unwrap_or(24)
-        .saturating_sub(4);
+    let page_size = app.view_height().saturating_sub(4);
 
     if let AppView::ContainerLogs(container_id) = &app.app_view {
         let log_state = app.docker_monitor.get_log_state_mut(container_id);
-        if let Some(ref log_state) = log_state {
-            if log_state.search_mode {
+        if let Some(ref log_state) = log_state
+            && log_state.search_mode {
                 return match code {
                     KeyCode::Enter => {
                         if let Some(ref mut ls) = app.docker_monitor.get_log_state_mut(container_id)
@@ -395,9 +1174,27 @@ fn handle_container_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
                     _ => None,
                 };
             }
-        }
     }
 
+    if let AppView::ContainerLogs(container_id) = &app.app_view.clone()
+        && let Some(ls) = app.docker_monitor.get_log_state_mut(container_id) {
+            let display = ls.with_filtered_indices(|d| d.to_vec());
+            let lines = &ls.lines;
+            let result = handle_log_cursor(
+                code,
+                &mut ls.selection,
+                &display,
+                ls.truncated_count,
+                &mut ls.scroll_offset,
+                &mut ls.auto_follow,
+                page_size,
+                |i| lines.get(i).cloned(),
+            );
+            if result.is_some() {
+                return result;
+            }
+        }
+
     match code {
         KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left => {
             // Single-container logs: stop the active stream on exit.
@@ -406,8 +1203,8 @@ fn handle_container_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
             Some(InputResult::Consumed)
         }
         KeyCode::Up => {
-            if let AppView::ContainerLogs(container_id) = &app.app_view.clone() {
-                if let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(&container_id)
+            if let AppView::ContainerLogs(container_id) = &app.app_view.clone()
+                && let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(container_id)
                 {
                     log_state.auto_follow = false;
                     let max_offset = log_state.lines.len().saturating_sub(1);
@@ -415,66 +1212,58 @@ fn handle_container_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
                         log_state.scroll_offset += 1;
                     }
                 }
-            }
             Some(InputResult::Consumed)
         }
         KeyCode::Down => {
-            if let AppView::ContainerLogs(container_id) = &app.app_view.clone() {
-                if let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(&container_id)
-                {
-                    if log_state.scroll_offset > 0 {
+            if let AppView::ContainerLogs(container_id) = &app.app_view.clone()
+                && let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(container_id)
+                    && log_state.scroll_offset > 0 {
                         log_state.scroll_offset -= 1;
                         if log_state.scroll_offset == 0 {
                             log_state.auto_follow = true;
                         }
                     }
-                }
-            }
             Some(InputResult::Consumed)
         }
         KeyCode::Char('f') | KeyCode::End => {
-            if let AppView::ContainerLogs(container_id) = &app.app_view.clone() {
-                if let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(&container_id)
+            if let AppView::ContainerLogs(container_id) = &app.app_view.clone()
+                && let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(container_id)
                 {
                     log_state.auto_follow = true;
                     log_state.scroll_offset = 0;
                 }
-            }
             Some(InputResult::Consumed)
         }
         KeyCode::Char('/') => {
-            if let AppView::ContainerLogs(container_id) = &app.app_view.clone() {
-                if let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(&container_id)
+            if let AppView::ContainerLogs(container_id) = &app.app_view.clone()
+                && let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(container_id)
                 {
                     log_state.search_mode = true;
                     log_state.search_query.clear();
                 }
-            }
             Some(InputResult::Consumed)
         }
         KeyCode::Char('n') => {
-            if let AppView::ContainerLogs(container_id) = &app.app_view.clone() {
-                if let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(&container_id)
+            if let AppView::ContainerLogs(container_id) = &app.app_view.clone()
+                && let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(container_id)
                 {
                     log_state.search_query.clear();
                 }
-            }
             Some(InputResult::Consumed)
         }
         KeyCode::PageUp => {
-            if let AppView::ContainerLogs(container_id) = &app.app_view.clone() {
-                if let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(&container_id)
+            if let AppView::ContainerLogs(container_id) = &app.app_view.clone()
+                && let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(container_id)
                 {
                     log_state.auto_follow = false;
                     let max_offset = log_state.lines.len().saturating_sub(1);
                     log_state.scroll_offset = (log_state.scroll_offset + page_size).min(max_offset);
                 }
-            }
             Some(InputResult::Consumed)
         }
         KeyCode::PageDown => {
-            if let AppView::ContainerLogs(container_id) = &app.app_view.clone() {
-                if let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(&container_id)
+            if let AppView::ContainerLogs(container_id) = &app.app_view.clone()
+                && let Some(ref mut log_state) = app.docker_monitor.get_log_state_mut(container_id)
                 {
                     if log_state.scroll_offset > page_size {
                         log_state.scroll_offset -= page_size;
@@ -483,7 +1272,6 @@ fn handle_container_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
                         log_state.auto_follow = true;
                     }
                 }
-            }
             Some(InputResult::Consumed)
         }
         _ => None,
@@ -491,10 +1279,7 @@ fn handle_container_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
 }
 
 fn handle_container_logs_multi(app: &mut App, code: KeyCode) -> Option<InputResult> {
-    let page_size = crossterm::terminal::size()
-        .map(|(_, h)| h as usize)
-        .unwrap_or(24)
-        .saturating_sub(4);
+    let page_size = app.view_height().saturating_sub(4);
 
     if app
         .docker_monitor
@@ -533,6 +1318,24 @@ fn handle_container_logs_multi(app: &mut App, code: KeyCode) -> Option<InputResu
         };
     }
 
+    if let Some(ref mut ls) = app.docker_monitor.multi_log_state {
+        let display = ls.with_filtered_indices(|d| d.to_vec());
+        let lines = &ls.lines;
+        let result = handle_log_cursor(
+            code,
+            &mut ls.selection,
+            &display,
+            ls.truncated_count,
+            &mut ls.scroll_offset,
+            &mut ls.auto_follow,
+            page_size,
+            |i| lines.get(i).map(|entry| entry.line.clone()),
+        );
+        if result.is_some() {
+            return result;
+        }
+    }
+
     match code {
         KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left => {
             // Multi-container logs: preserve existing streams when exiting the view.
@@ -550,14 +1353,13 @@ fn handle_container_logs_multi(app: &mut App, code: KeyCode) -> Option<InputResu
             Some(InputResult::Consumed)
         }
         KeyCode::Down => {
-            if let Some(ref mut log_state) = app.docker_monitor.multi_log_state {
-                if log_state.scroll_offset > 0 {
+            if let Some(ref mut log_state) = app.docker_monitor.multi_log_state
+                && log_state.scroll_offset > 0 {
                     log_state.scroll_offset -= 1;
                     if log_state.scroll_offset == 0 {
                         log_state.auto_follow = true;
                     }
                 }
-            }
             Some(InputResult::Consumed)
         }
         KeyCode::Char('f') | KeyCode::End => {
@@ -609,14 +1411,105 @@ fn handle_swarm(
     next_tab: AppView,
     prev_tab: AppView,
 ) -> Option<InputResult> {
+    if app.swarm_monitor.ui_state.search_mode {
+        let ui_state = &mut app.swarm_monitor.ui_state;
+        match code {
+            KeyCode::Enter => ui_state.search_mode = false,
+            KeyCode::Esc => {
+                ui_state.search_mode = false;
+                ui_state.search_query.clear();
+                app.swarm_monitor.status_message = None;
+                return Some(InputResult::Consumed);
+            }
+            KeyCode::Backspace => {
+                ui_state.search_query.pop();
+            }
+            KeyCode::Char(c) => ui_state.search_query.push(c),
+            _ => return Some(InputResult::Consumed),
+        }
+        // Jump as the query is typed
+        app.swarm_monitor.ui_state.search_hit = 0;
+        select_swarm_hit(app, 0);
+        return Some(InputResult::Consumed);
+    }
+
+    if app.swarm_monitor.ui_state.warning_ack.is_some() {
+        return handle_warning_ack(app, code);
+    }
+
     let sel = app.swarm_monitor.ui_state.selected_index;
     let item = resolve_swarm_overview_item(&app.swarm_monitor, sel);
 
+    if let Some(input) = app.swarm_monitor.ui_state.label_input.as_mut() {
+        match code {
+            KeyCode::Enter => {
+                let text = std::mem::take(input);
+                app.swarm_monitor.ui_state.label_input = None;
+                if let super::state::SwarmOverviewItem::Service(id, name) = item {
+                    match LabelEdit::parse(&text) {
+                        Ok(edit) => {
+                            app.pending_action = Some(PendingAction {
+                                description: match edit {
+                                    LabelEdit::Add(..) => format!("Label service '{}' {}?", name, edit),
+                                    LabelEdit::Remove(ref key) => format!("Remove label '{}' from service '{}'?", key, name),
+                                },
+                                kind: PendingActionKind::ServiceLabel(id, name, edit),
+                                expires: Instant::now() + Duration::from_secs(5),
+                            });
+                        }
+                        Err(e) => app.swarm_monitor.status_message = Some(e),
+                    }
+                }
+            }
+            KeyCode::Esc => app.swarm_monitor.ui_state.label_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return Some(InputResult::Consumed);
+    }
+    if let Some(input) = app.swarm_monitor.ui_state.schedule_input.as_mut() {
+        match code {
+            KeyCode::Enter => {
+                let text = std::mem::take(input);
+                app.swarm_monitor.ui_state.schedule_input = None;
+                if let super::state::SwarmOverviewItem::Service(id, name) = item {
+                    schedule_restart(app, id, name, &text);
+                }
+            }
+            KeyCode::Esc => app.swarm_monitor.ui_state.schedule_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return Some(InputResult::Consumed);
+    }
+
     match code {
+        KeyCode::Char('/') => {
+            app.swarm_monitor.ui_state.search_mode = true;
+            app.swarm_monitor.ui_state.search_query.clear();
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Char('n') if !app.swarm_monitor.ui_state.search_query.is_empty() => {
+            select_swarm_hit(app, 1);
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Char('N') if !app.swarm_monitor.ui_state.search_query.is_empty() => {
+            select_swarm_hit(app, -1);
+            Some(InputResult::Consumed)
+        }
         KeyCode::Char('q') | KeyCode::Esc => {
             app.app_view = AppView::System;
             Some(InputResult::Consumed)
         }
+        KeyCode::Char('x') => Some(export_view(app, ExportFormat::Text)),
+        KeyCode::Char('X') => Some(export_view(app, ExportFormat::Csv)),
+        KeyCode::Char('G') => Some(graphs_view(app)),
         KeyCode::Tab => {
             app.app_view = next_tab;
             Some(InputResult::Consumed)
@@ -691,6 +1584,186 @@ fn handle_swarm(
                 None
             }
         }
+        KeyCode::Char('a') => {
+            let (shown, muted) = app.swarm_warnings();
+            if shown.is_empty() && muted.is_empty() {
+                app.swarm_monitor.status_message = Some("No warnings to mute".to_string());
+            } else if !refuse_read_only(app) {
+                app.swarm_monitor.ui_state.warning_ack =
+                    Some(WarningAck { selected: 0, duration: "4h".to_string() });
+            }
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Char('@') => {
+            if let super::state::SwarmOverviewItem::Service(_, _) = item {
+                if !refuse_read_only(app) {
+                    app.swarm_monitor.ui_state.schedule_input = Some(String::new());
+                }
+                Some(InputResult::Consumed)
+            } else {
+                None
+            }
+        }
+        KeyCode::Char('E') => {
+            if let super::state::SwarmOverviewItem::Service(_, _) = item {
+                if !refuse_read_only(app) {
+                    app.swarm_monitor.ui_state.label_input = Some(String::new());
+                }
+                Some(InputResult::Consumed)
+            } else {
+                None
+            }
+        }
+        KeyCode::Char('w') => {
+            if let super::state::SwarmOverviewItem::Service(_, name) = item {
+                app.swarm_monitor.status_message = Some(toggle_watch(app, WatchKind::Service, &name));
+                Some(InputResult::Consumed)
+            } else {
+                None
+            }
+        }
+        KeyCode::Char('V') => {
+            if let super::state::SwarmOverviewItem::Service(id, name) = item {
+                let source = LogPaneSource::Service { id, name };
+                match app.swarm_monitor.mark_split_source(source, &app.docker_monitor) {
+                    Some(message) => app.swarm_monitor.status_message = Some(message),
+                    None => app.app_view = AppView::SwarmSplitLogs,
+                }
+                Some(InputResult::Consumed)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Keys of the warning picker: ↑↓ pick a warning, Enter mutes it for the
+/// typed duration or unmutes a muted one, ^ opens an incident about it, and
+/// Esc closes the picker.
+fn handle_warning_ack(app: &mut App, code: KeyCode) -> Option<InputResult> {
+    let (shown, muted) = app.swarm_warnings();
+    let ack = app.swarm_monitor.ui_state.warning_ack.as_mut()?;
+    let count = shown.len() + muted.len();
+    match code {
+        KeyCode::Up => ack.selected = ack.selected.saturating_sub(1),
+        KeyCode::Down if ack.selected + 1 < count => ack.selected += 1,
+        KeyCode::Backspace => {
+            ack.duration.pop();
+        }
+        KeyCode::Char('^') => {
+            let selected = ack.selected;
+            let warning = shown.get(selected).or_else(|| muted.get(selected - shown.len()).map(|(w, _)| w)).cloned();
+            app.swarm_monitor.ui_state.warning_ack = None;
+            confirm_incident(app, warning);
+        }
+        KeyCode::Char(c) => ack.duration.push(c),
+        KeyCode::Esc => app.swarm_monitor.ui_state.warning_ack = None,
+        KeyCode::Enter => {
+            let (selected, duration) = (ack.selected, ack.duration.clone());
+            let result = if let Some(warning) = shown.get(selected) {
+                crate::mutes::parse_duration(&duration)
+                    .and_then(|secs| app.mute_warning(warning, secs, duration.trim()))
+            } else if let Some((warning, _)) = muted.get(selected - shown.len()) {
+                app.unmute_warning(warning)
+            } else {
+                Ok(String::new())
+            };
+            match result {
+                Ok(message) => {
+                    app.swarm_monitor.ui_state.warning_ack = None;
+                    app.swarm_monitor.status_message = Some(message);
+                }
+                // A bad duration leaves the picker open to fix it
+                Err(e) => app.swarm_monitor.status_message = Some(format!("Error: {}", e)),
+            }
+        }
+        _ => {}
+    }
+    Some(InputResult::Consumed)
+}
+
+/// Select the Swarm search match `step` away from the current one, wrapping
+/// around, and say which it is in the status line.
+fn select_swarm_hit(app: &mut App, step: isize) {
+    let monitor = &mut app.swarm_monitor;
+    let query = monitor.ui_state.search_query.clone();
+    if query.is_empty() {
+        monitor.status_message = None;
+        return;
+    }
+    let hits = swarm_search_hits(monitor, &query);
+    if hits.is_empty() {
+        monitor.status_message = Some(format!("No match for '{}'", query));
+        return;
+    }
+    let idx = (monitor.ui_state.search_hit as isize + step).rem_euclid(hits.len() as isize) as usize;
+    monitor.ui_state.search_hit = idx;
+    if let Some(row) = reveal_swarm_hit(monitor, &hits[idx]) {
+        monitor.ui_state.selected_index = row;
+    }
+    monitor.status_message = Some(format!("Match {}/{}: {} (n/N: next/previous)", idx + 1, hits.len(), hits[idx].describe()));
+}
+
+/// Pin or unpin an object on the Watchlist tab; returns the status line.
+fn toggle_watch(app: &mut App, kind: WatchKind, name: &str) -> String {
+    if app.watchlist.toggle(kind, name) {
+        format!("Watching {} {} (Watchlist tab)", kind.label(), name)
+    } else {
+        format!("Removed {} {} from the watchlist", kind.label(), name)
+    }
+}
+
+fn handle_watchlist(
+    app: &mut App,
+    code: KeyCode,
+    next_tab: AppView,
+    prev_tab: AppView,
+) -> Option<InputResult> {
+    let len = app.watchlist.items.len();
+    match code {
+        KeyCode::Char('q') | KeyCode::Esc => {
+            app.app_view = AppView::System;
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Tab => {
+            app.app_view = next_tab;
+            Some(InputResult::Consumed)
+        }
+        KeyCode::BackTab => {
+            app.app_view = prev_tab;
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Up if app.watchlist.selected > 0 => {
+            app.watchlist.selected -= 1;
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Down if app.watchlist.selected + 1 < len => {
+            app.watchlist.selected += 1;
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Char('w') | KeyCode::Delete => {
+            let item = app.watchlist.selected_item()?.clone();
+            app.watch_status = Some(toggle_watch(app, item.kind, &item.name));
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Right | KeyCode::Enter => {
+            let item = app.watchlist.selected_item()?.clone();
+            match item.kind {
+                WatchKind::Container => {
+                    let c = app.docker_monitor.containers.iter().find(|c| c.name == item.name)?.clone();
+                    app.docker_monitor.start_log_stream(&c.id, &c.name);
+                    app.app_view = AppView::ContainerLogs(c.id);
+                }
+                WatchKind::Service => {
+                    let svc = app.swarm_monitor.services.iter().find(|s| s.name == item.name)?.clone();
+                    app.swarm_monitor.start_service_log_stream(&svc.id, &svc.name);
+                    app.app_view = AppView::SwarmServiceLogs(svc.id, svc.name);
+                }
+            }
+            app.watch_status = None;
+            Some(InputResult::Consumed)
+        }
         _ => None,
     }
 }
@@ -729,6 +1802,10 @@ fn handle_swarm_tasks(app: &mut App, code: KeyCode) -> Option<InputResult> {
                 None
             }
         }
+        KeyCode::Char('T') => {
+            app.swarm_monitor.toggle_timeline();
+            Some(InputResult::Consumed)
+        }
         KeyCode::Char('R') => {
             if let SwarmViewLevel::ServiceTasks(ref svc_id, ref svc_name) =
                 app.swarm_monitor.ui_state.view_level.clone()
@@ -747,17 +1824,76 @@ fn handle_swarm_tasks(app: &mut App, code: KeyCode) -> Option<InputResult> {
     }
 }
 
+fn handle_split_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
+    // Header, separator, pane titles and footer
+    let page_size = app.view_height().saturating_sub(5);
+    let Some(ref mut split) = app.swarm_monitor.split_logs else {
+        app.app_view = AppView::Swarm;
+        return Some(InputResult::Consumed);
+    };
+
+    let pane = split.focused_mut();
+    if pane.search_mode {
+        match code {
+            KeyCode::Enter => pane.search_mode = false,
+            KeyCode::Esc => {
+                pane.search_mode = false;
+                pane.search_query.clear();
+            }
+            KeyCode::Backspace => {
+                pane.search_query.pop();
+            }
+            KeyCode::Char(c) => pane.search_query.push(c),
+            _ => return None,
+        }
+        split.sync(page_size);
+        return Some(InputResult::Consumed);
+    }
+
+    match code {
+        KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left => {
+            app.swarm_monitor.stop_split_logs();
+            app.app_view = AppView::Swarm;
+            return Some(InputResult::Consumed);
+        }
+        KeyCode::Tab | KeyCode::BackTab => split.focus = 1 - split.focus,
+        KeyCode::Up | KeyCode::PageUp => {
+            let step = if code == KeyCode::Up { 1 } else { page_size };
+            let max_offset = pane.with_filtered_indices(|d| d.len()).saturating_sub(1);
+            pane.auto_follow = false;
+            pane.scroll_offset = (pane.scroll_offset + step).min(max_offset);
+        }
+        KeyCode::Down | KeyCode::PageDown => {
+            let step = if code == KeyCode::Down { 1 } else { page_size };
+            pane.scroll_offset = pane.scroll_offset.saturating_sub(step);
+            if pane.scroll_offset == 0 {
+                pane.auto_follow = true;
+            }
+        }
+        KeyCode::Char('f') | KeyCode::End => {
+            pane.auto_follow = true;
+            pane.scroll_offset = 0;
+        }
+        KeyCode::Char('e') => pane.filter_errors = !pane.filter_errors,
+        KeyCode::Char('/') => {
+            pane.search_mode = true;
+            pane.search_query.clear();
+        }
+        KeyCode::Char('n') => pane.search_query.clear(),
+        _ => return None,
+    }
+    split.sync(page_size);
+    Some(InputResult::Consumed)
+}
+
 fn handle_service_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
-    let page_size = crossterm::terminal::size()
-        .map(|(_, h)| h as usize)
-        .unwrap_or(24)
-        .saturating_sub(4);
+    let page_size = app.view_height().saturating_sub(4);
 
     if app
         .swarm_monitor
         .log_state
         .as_ref()
-        .map_or(false, |s| s.search_mode)
+        .is_some_and(|s| s.search_mode)
     {
         return match code {
             KeyCode::Enter => {
@@ -789,6 +1925,24 @@ fn handle_service_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
         };
     }
 
+    if let Some(ref mut ls) = app.swarm_monitor.log_state {
+        let display = ls.with_filtered_indices(|d| d.to_vec());
+        let lines = &ls.lines;
+        let result = handle_log_cursor(
+            code,
+            &mut ls.selection,
+            &display,
+            ls.truncated_count,
+            &mut ls.scroll_offset,
+            &mut ls.auto_follow,
+            page_size,
+            |i| lines.get(i).cloned(),
+        );
+        if result.is_some() {
+            return result;
+        }
+    }
+
     match code {
         KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left => {
             app.swarm_monitor.stop_log_stream();
@@ -807,14 +1961,13 @@ fn handle_service_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
             Some(InputResult::Consumed)
         }
         KeyCode::Down => {
-            if let Some(ref mut log_state) = app.swarm_monitor.log_state {
-                if log_state.scroll_offset > 0 {
+            if let Some(ref mut log_state) = app.swarm_monitor.log_state
+                && log_state.scroll_offset > 0 {
                     log_state.scroll_offset -= 1;
                     if log_state.scroll_offset == 0 {
                         log_state.auto_follow = true;
                     }
                 }
-            }
             Some(InputResult::Consumed)
         }
         KeyCode::Char('f') | KeyCode::End => {
@@ -843,6 +1996,19 @@ fn handle_service_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
             }
             Some(InputResult::Consumed)
         }
+        KeyCode::Char('o') => {
+            let local = app.swarm_monitor.nodes.iter().find(|n| n.is_self).map(|n| n.hostname.clone());
+            if let Some(ref mut log_state) = app.swarm_monitor.log_state {
+                log_state.cycle_node_filter(local.as_deref());
+            }
+            Some(InputResult::Consumed)
+        }
+        KeyCode::Char('O') => {
+            if let Some(ref mut log_state) = app.swarm_monitor.log_state {
+                log_state.node_filter = None;
+            }
+            Some(InputResult::Consumed)
+        }
         KeyCode::PageUp => {
             if let Some(ref mut log_state) = app.swarm_monitor.log_state {
                 log_state.auto_follow = false;
@@ -865,3 +2031,118 @@ fn handle_service_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
         _ => None,
     }
 }
+
+fn handle_kubernetes(
+    app: &mut App,
+    code: KeyCode,
+    next_tab: AppView,
+    prev_tab: AppView,
+) -> Option<InputResult> {
+    let len = app.kube_monitor.rows().len();
+    let ui_state = &mut app.kube_monitor.ui_state;
+    match code {
+        KeyCode::Char('q') | KeyCode::Esc => app.app_view = AppView::System,
+        KeyCode::Tab => app.app_view = next_tab,
+        KeyCode::BackTab => app.app_view = prev_tab,
+        KeyCode::Up if ui_state.selected_index > 0 => ui_state.selected_index -= 1,
+        KeyCode::Down if ui_state.selected_index + 1 < len => ui_state.selected_index += 1,
+        // Space folds the namespace of whatever row is selected
+        KeyCode::Char(' ') => {
+            let namespace = match app.kube_monitor.selected_row()? {
+                KubeRow::Namespace(ns) => ns,
+                KubeRow::Deployment(i) => app.kube_monitor.deployments[i].namespace.clone(),
+                KubeRow::Pod(i) => app.kube_monitor.pods[i].namespace.clone(),
+            };
+            app.kube_monitor.toggle_namespace(&namespace);
+            let header = KubeRow::Namespace(namespace);
+            app.kube_monitor.ui_state.selected_index =
+                app.kube_monitor.rows().iter().position(|r| *r == header).unwrap_or(0);
+        }
+        KeyCode::Right | KeyCode::Enter => match app.kube_monitor.selected_row()? {
+            KubeRow::Namespace(ns) => app.kube_monitor.toggle_namespace(&ns),
+            KubeRow::Pod(i) => {
+                app.kube_monitor.start_log_stream(i);
+                let pod = &app.kube_monitor.pods[i];
+                app.app_view = AppView::KubernetesPodLogs(pod.namespace.clone(), pod.name.clone());
+            }
+            KubeRow::Deployment(_) => return None,
+        },
+        _ => return None,
+    }
+    Some(InputResult::Consumed)
+}
+
+fn handle_pod_logs(app: &mut App, code: KeyCode) -> Option<InputResult> {
+    let page_size = app.view_height().saturating_sub(4);
+
+    if let Some(ref mut ls) = app.kube_monitor.log_state {
+        if ls.search_mode {
+            match code {
+                KeyCode::Enter => ls.search_mode = false,
+                KeyCode::Esc => {
+                    ls.search_mode = false;
+                    ls.search_query.clear();
+                }
+                KeyCode::Backspace => {
+                    ls.search_query.pop();
+                }
+                KeyCode::Char(c) => ls.search_query.push(c),
+                _ => return None,
+            }
+            return Some(InputResult::Consumed);
+        }
+
+        let display = ls.with_filtered_indices(|d| d.to_vec());
+        let lines = &ls.lines;
+        let result = handle_log_cursor(
+            code,
+            &mut ls.selection,
+            &display,
+            ls.truncated_count,
+            &mut ls.scroll_offset,
+            &mut ls.auto_follow,
+            page_size,
+            |i| lines.get(i).cloned(),
+        );
+        if result.is_some() {
+            return result;
+        }
+    }
+
+    if matches!(code, KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left) {
+        app.kube_monitor.stop_log_stream();
+        app.app_view = AppView::Kubernetes;
+        return Some(InputResult::Consumed);
+    }
+    let log_state = app.kube_monitor.log_state.as_mut()?;
+    let max_offset = log_state.lines.len().saturating_sub(1);
+    match code {
+        KeyCode::Up => {
+            log_state.auto_follow = false;
+            log_state.scroll_offset = (log_state.scroll_offset + 1).min(max_offset);
+        }
+        KeyCode::Down => {
+            log_state.scroll_offset = log_state.scroll_offset.saturating_sub(1);
+            log_state.auto_follow = log_state.scroll_offset == 0;
+        }
+        KeyCode::Char('f') | KeyCode::End => {
+            log_state.auto_follow = true;
+            log_state.scroll_offset = 0;
+        }
+        KeyCode::Char('/') => {
+            log_state.search_mode = true;
+            log_state.search_query.clear();
+        }
+        KeyCode::Char('n') => log_state.search_query.clear(),
+        KeyCode::PageUp => {
+            log_state.auto_follow = false;
+            log_state.scroll_offset = (log_state.scroll_offset + page_size).min(max_offset);
+        }
+        KeyCode::PageDown => {
+            log_state.scroll_offset = log_state.scroll_offset.saturating_sub(page_size);
+            log_state.auto_follow = log_state.scroll_offset == 0;
+        }
+        _ => return None,
+    }
+    Some(InputResult::Consumed)
+}
@@ -4,7 +4,7 @@ mod render;
 mod input;
 
 use std::io;
-use std::sync::Arc;
+use std::sync::{mpsc, Arc, Mutex};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
@@ -13,14 +13,32 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, Clear, ClearType},
 };
 
+use crate::cli::StartTab;
+use crate::config::Config;
 use crate::controller::Monitor;
 use crate::docker_controller::DockerMonitor;
+use crate::health_controller::HealthMonitor;
+use crate::history::HistoryStore;
+use crate::incidents::{Incident, IncidentSender};
+use crate::keymap::KeyMap;
+use crate::macros::Macros;
+use crate::recording::{Recorder, Replay};
+use crate::alertmanager::{SilenceOutcome, Silencer};
+use crate::alerts::Severity;
+use crate::mutes::{self, MuteList, MutedWarning};
+use crate::notes::{self, MaintenanceNote, NoteFile};
+use crate::schedule::{self, RestartSchedule};
 use crate::swarm_controller::SwarmMonitor;
-use crate::model::AppView;
+use crate::k8s_controller::KubeMonitor;
+use crate::syslog::SyslogForwarder;
+use crate::model::{AppView, Freshness, GraphViewState, KubeRow, Selection, SwarmViewLevel, SystemPanel, Watchlist};
 use crate::view::{Presenter, RowKind};
 use sysinfo::Pid;
 
-pub use state::{PendingAction, PendingActionKind, SwarmOverviewItem, resolve_swarm_overview_item};
+/// How long a toast stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+pub use state::{Palette, PendingAction, PendingActionKind, StartTarget, SwarmOverviewItem, resolve_swarm_overview_item};
 
 /// Restore the terminal to normal mode. Safe to call multiple times.
 pub fn restore_terminal() {
@@ -28,64 +46,634 @@ pub fn restore_terminal() {
     let _ = disable_raw_mode();
 }
 
+/// Holds the terminal in raw mode on the alternate screen, and restores it
+/// when dropped, so an error returned with `?` or a panic unwinding out of
+/// `run` still leaves the shell usable.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        let guard = TerminalGuard;
+        execute!(io::stdout(), EnterAlternateScreen, Clear(ClearType::All))?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// The first panic of a collector, log follower or tokio worker while the
+/// TUI runs. Printing it then would land on the alternate screen, so the
+/// event loop quits with it instead of drawing over data that stopped.
+static WORKER_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Record a panic on a thread other than main, for `run` to exit with.
+pub fn worker_panicked(message: String) {
+    if let Ok(mut slot) = WORKER_PANIC.lock() {
+        slot.get_or_insert(message);
+    }
+}
+
+fn take_worker_panic() -> Option<String> {
+    WORKER_PANIC.lock().ok()?.take()
+}
+
+/// Take the terminal back after a stop; the caller redraws.
+fn resume_terminal() -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, Clear(ClearType::All))
+}
+
+/// Hand the terminal back to the shell and stop, as Ctrl+Z does outside raw
+/// mode. Returns once the shell continues the job (`fg`).
+fn suspend() -> io::Result<()> {
+    tracing::info!("Suspending");
+    restore_terminal();
+    signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)?;
+    tracing::info!("Resumed");
+    resume_terminal()
+}
+
 /// Main application state and event loop.
 pub struct App {
     pub monitor: Monitor,
     pub docker_monitor: DockerMonitor,
     pub swarm_monitor: SwarmMonitor,
+    pub kube_monitor: KubeMonitor,
+    /// Docker and Swarm detection running in the background; until it
+    /// delivers, both monitors are unavailable placeholders.
+    startup_probe: Option<mpsc::Receiver<(DockerMonitor, SwarmMonitor, KubeMonitor)>>,
+    pub health_monitor: HealthMonitor,
     pub app_view: AppView,
     pub row_mapping: Vec<(Pid, RowKind)>,
     pub pending_action: Option<PendingAction>,
     pub last_tick: Instant,
     pub tick_counter: u64,
-    pub last_tab_refresh: Instant,
     pub prev_app_view: AppView,
     pub tick_rate: Duration,
-    pub min_refresh_interval: Duration,
+    /// Refresh cadence for monitors whose tab is not active (None = disabled).
+    pub background_refresh: Option<Duration>,
+    pub last_background_refresh: Instant,
+    pub config: Config,
+    /// Set with `--syslog`.
+    pub syslog: Option<SyslogForwarder>,
+    /// Re-parsed config from the file watcher.
+    config_watch: Option<mpsc::Receiver<Result<Config, String>>>,
+    /// Raised by SIGUSR1 (refresh now) and SIGUSR2 (dump a snapshot).
+    refresh_signal: Arc<AtomicBool>,
+    dump_signal: Arc<AtomicBool>,
+    /// Set with `--read-only`: container and service actions are refused.
+    pub read_only: bool,
+    /// View asked for on the command line, until it can be opened.
+    pub start_target: Option<StartTarget>,
+    /// Transient notice (e.g. "Config reloaded") and when it was raised.
+    pub toast: Option<(String, Instant)>,
+    /// Set when `[history] enabled = true`.
+    pub history: Option<HistoryStore>,
+    /// Present while the Graphs view is open.
+    pub graph_state: Option<GraphViewState>,
+    pub graph_status: Option<String>,
+    /// Containers and services pinned to the Watchlist tab.
+    pub watchlist: Watchlist,
+    pub watch_status: Option<String>,
+    /// Keep the system summary on screen above containers and logs.
+    pub pin_summary: bool,
+    /// Rows the pinned summary took in the last render (0 when not shown).
+    pub pinned_rows: u16,
+    /// Rolling restarts scheduled for later, shared with other sitrep processes.
+    pub schedule: RestartSchedule,
+    /// Swarm warnings acknowledged for a while, shared with other sitrep processes.
+    pub mutes: MuteList,
+    /// Creates an Alertmanager silence for each mute when one is configured.
+    pub silencer: Silencer,
+    /// Opens PagerDuty or Opsgenie incidents when `[incidents]` is configured.
+    pub incidents: IncidentSender,
+    /// This host's maintenance note file.
+    pub note_file: NoteFile,
+    /// Text of the maintenance note prompt while it is open.
+    pub note_input: Option<String>,
+    /// Keys remapped in `[keys]`.
+    pub keymap: KeyMap,
+    /// Runbook macros from `[macros]`.
+    pub macros: Macros,
+    /// The command palette while it is open.
+    pub palette: Option<Palette>,
+    /// Appends each tick's state to the `--record` file.
+    pub recorder: Option<Recorder>,
+    /// The recording shown in place of this host with `--replay`.
+    pub replay: Option<Replay>,
 }
 
 impl App {
-    pub fn new(rt: Arc<tokio::runtime::Runtime>, tick_rate_secs: u64, no_docker: bool) -> Self {
-        let tick_rate = Duration::from_secs(tick_rate_secs);
+    pub fn new(
+        rt: Arc<tokio::runtime::Runtime>,
+        tick_rate_secs: u64,
+        no_docker: bool,
+        background_refresh_secs: Option<u64>,
+        config: Config,
+    ) -> Self {
+        let (rt_clone, rt_swarm) = (Arc::clone(&rt), Arc::clone(&rt));
+        let probe = move || (DockerMonitor::new(rt_clone, no_docker), SwarmMonitor::new(rt_swarm), KubeMonitor::new());
+        Self::with_monitors(rt, tick_rate_secs, background_refresh_secs, config, Monitor::new(), probe)
+    }
 
-        let monitor_handle = std::thread::spawn(Monitor::new);
-        let swarm_handle = std::thread::spawn(SwarmMonitor::new);
+    /// An app over the made-up host, containers and cluster of `--demo`.
+    pub fn demo(rt: Arc<tokio::runtime::Runtime>, tick_rate_secs: u64, background_refresh_secs: Option<u64>, config: Config) -> Self {
         let rt_clone = Arc::clone(&rt);
-        let docker_handle = std::thread::spawn(move || DockerMonitor::new(rt_clone, no_docker));
+        let probe = move || (DockerMonitor::demo(rt_clone), SwarmMonitor::demo(), KubeMonitor::undetected());
+        let mut app = Self::with_monitors(rt, tick_rate_secs, background_refresh_secs, config, Monitor::demo(), probe);
+        app.schedule = RestartSchedule::in_memory();
+        app.mutes = MuteList::in_memory();
+        // Made-up warnings page no one
+        app.silencer.reconfigure(&crate::config::AlertmanagerConfig { url: None, ..Default::default() });
+        app.incidents.reconfigure(&crate::config::IncidentsConfig::default());
+        app
+    }
+
+    /// An app showing `replay` frame by frame, with nothing collected.
+    pub fn from_recording(rt: Arc<tokio::runtime::Runtime>, tick_rate_secs: u64, config: Config, replay: Replay) -> Self {
+        let (has_containers, cluster) = (replay.has_containers(), replay.cluster());
+        let rt_clone = Arc::clone(&rt);
+        let probe = move || (DockerMonitor::replayed(rt_clone, has_containers), SwarmMonitor::replayed(cluster), KubeMonitor::undetected());
+        let mut app = Self::with_monitors(rt, tick_rate_secs, None, config, Monitor::replayed(), probe);
+        app.schedule = RestartSchedule::in_memory();
+        app.mutes = MuteList::in_memory();
+        app.silencer.reconfigure(&crate::config::AlertmanagerConfig { url: None, ..Default::default() });
+        app.incidents.reconfigure(&crate::config::IncidentsConfig::default());
+        app.replay = Some(replay);
+        app.show_replay_frame();
+        app
+    }
 
-        let monitor = monitor_handle.join().expect("Monitor init panicked");
-        let docker_monitor = docker_handle.join().expect("DockerMonitor init panicked");
-        let swarm_monitor = swarm_handle.join().expect("SwarmMonitor init panicked");
+    fn with_monitors(
+        rt: Arc<tokio::runtime::Runtime>,
+        tick_rate_secs: u64,
+        background_refresh_secs: Option<u64>,
+        config: Config,
+        mut monitor: Monitor,
+        probe: impl FnOnce() -> (DockerMonitor, SwarmMonitor, KubeMonitor) + Send + 'static,
+    ) -> Self {
+        let tick_rate = Duration::from_secs(tick_rate_secs);
+        let background_refresh = background_refresh_secs.map(Duration::from_secs);
+
+        // Nothing here blocks on sysinfo or Docker: the first frame is drawn
+        // right away and each section fills in as its collector reports.
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(probe());
+        });
         let app_view = AppView::System;
 
-        tracing::info!(
-            "Docker available: {}, Swarm mode: {}",
-            docker_monitor.docker_available,
-            swarm_monitor.is_swarm()
-        );
+        let history = if config.history.enabled {
+            match HistoryStore::open(&config.history) {
+                Ok(store) => {
+                    if let Some(path) = store.path() {
+                        tracing::info!("Recording metrics history to {}", path.display());
+                    }
+                    Some(store)
+                }
+                Err(e) => {
+                    tracing::warn!("Metrics history disabled: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let watchlist = Watchlist::new(&config.watchlist.containers, &config.watchlist.services);
+        crate::view::theme::set_theme(config.ui.theme);
+        monitor.ui_state.guard.cpu_budget = config.self_guard.cpu_budget_percent;
+        monitor.ui_state.cpu_scale = config.ui.cpu;
+        monitor.ui_state.summary_lines = config.summary.order();
+        monitor.ui_state.thresholds = config.thresholds;
+        monitor.set_custom_lines(config.summary.custom.clone());
+        let silencer = Silencer::new(Arc::clone(&rt), &config.alertmanager);
+        let incidents = IncidentSender::new(&config.incidents);
+        // Checked when the config was parsed
+        let keymap = KeyMap::new(&config.keys).unwrap_or_default();
+        let macros = Macros::new(&config.macros).unwrap_or_default();
+        let mut docker_monitor = DockerMonitor::new(Arc::clone(&rt), true);
+        docker_monitor.ui_state.cpu_scale = config.ui.cpu;
+        let schedule = RestartSchedule::new(config.swarm.schedule_file.clone().unwrap_or_else(schedule::default_path));
+        let mutes = MuteList::new(config.swarm.mute_file.clone().unwrap_or_else(mutes::default_path));
+        let note_file = NoteFile::new(config.maintenance.file.clone().unwrap_or_else(|| notes::DEFAULT_FILE.into()));
 
         Self {
             monitor,
             docker_monitor,
-            swarm_monitor,
+            swarm_monitor: SwarmMonitor::undetected(),
+            kube_monitor: KubeMonitor::undetected(),
+            startup_probe: Some(rx),
+            health_monitor: HealthMonitor::new(&config.health),
             app_view: app_view.clone(),
             row_mapping: Vec::new(),
             pending_action: None,
             last_tick: Instant::now() - tick_rate,
             tick_counter: 0,
-            last_tab_refresh: Instant::now() - Duration::from_millis(500),
             prev_app_view: app_view,
             tick_rate,
-            min_refresh_interval: Duration::from_millis(500),
+            background_refresh,
+            last_background_refresh: Instant::now(),
+            config,
+            config_watch: None,
+            refresh_signal: Arc::new(AtomicBool::new(false)),
+            dump_signal: Arc::new(AtomicBool::new(false)),
+            read_only: false,
+            start_target: None,
+            toast: None,
+            syslog: None,
+            history,
+            graph_state: None,
+            graph_status: None,
+            watchlist,
+            watch_status: None,
+            pin_summary: false,
+            pinned_rows: 0,
+            schedule,
+            mutes,
+            silencer,
+            incidents,
+            note_file,
+            note_input: None,
+            keymap,
+            macros,
+            palette: None,
+            recorder: None,
+            replay: None,
+        }
+    }
+
+    /// The Swarm overview's warnings, health check failures included, split
+    /// into those shown at the top and those muted.
+    pub fn swarm_warnings(&self) -> (Vec<String>, Vec<(String, MutedWarning)>) {
+        let mut warnings = self.swarm_monitor.warnings.clone();
+        warnings.extend(self.health_monitor.warnings(crate::health_controller::HealthTargetKind::Service));
+        self.mutes.split(warnings, chrono::Local::now().timestamp())
+    }
+
+    /// Mute a warning for `secs`, with an Alertmanager silence as long when
+    /// one is configured. Returns the status line to show.
+    pub fn mute_warning(&mut self, warning: &str, secs: i64, label: &str) -> Result<String, String> {
+        let now = chrono::Local::now().timestamp();
+        let by = notes::author();
+        self.mutes.mute(warning, secs, now, &by)?;
+        if self.silencer.is_enabled() {
+            self.silencer.silence(warning, now + secs, &by);
+            return Ok(format!("Muted for {}, silencing in Alertmanager: {}", label, warning));
+        }
+        Ok(format!("Muted for {}: {}", label, warning))
+    }
+
+    /// Lift a mute, expiring its Alertmanager silence.
+    pub fn unmute_warning(&mut self, warning: &str) -> Result<String, String> {
+        let lifted = self.mutes.unmute(warning, chrono::Local::now().timestamp())?;
+        if let Some(id) = lifted.and_then(|m| m.silence_id) {
+            self.silencer.expire(&id);
+        }
+        Ok(format!("Unmuted: {}", warning))
+    }
+
+    /// Record finished silence requests. Returns true if any finished.
+    pub fn poll_silences(&mut self) -> bool {
+        let outcomes = self.silencer.poll();
+        for outcome in &outcomes {
+            let message = match outcome {
+                SilenceOutcome::Created { key, id } => {
+                    match self.mutes.set_silence(key, id, chrono::Local::now().timestamp()) {
+                        Ok(()) => format!("Alertmanager silence {} created", id),
+                        Err(e) => format!("Error: {}", e),
+                    }
+                }
+                SilenceOutcome::Expired(id) => format!("Alertmanager silence {} expired", id),
+                SilenceOutcome::Failed(e) => format!("Error: Alertmanager silence failed: {}", e),
+            };
+            self.swarm_monitor.status_message = Some(message);
+        }
+        !outcomes.is_empty()
+    }
+
+    /// The incident `^` opens: about `warning` when one is picked in the Swarm
+    /// overview, else a triage report of every current alert. Either way the
+    /// snapshot is attached.
+    pub fn incident(&self, warning: Option<&str>) -> Incident {
+        let host = sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string());
+        let mut alerts = self.current_alerts();
+        alerts.sort_by_key(|a| std::cmp::Reverse(a.severity));
+        let (summary, severity, dedup_key) = match warning {
+            Some(warning) => {
+                let severity = alerts.iter().find(|a| a.message == warning).map_or(Severity::Warning, |a| a.severity);
+                (format!("{}: {}", host, warning), severity, format!("sitrep:{}:{}", host, mutes::warning_key(warning)))
+            }
+            None => {
+                let headline = match alerts.as_slice() {
+                    [] => "triage report, no alerts".to_string(),
+                    [alert] => alert.message.clone(),
+                    [alert, rest @ ..] => format!("{} (+{} more)", alert.message, rest.len()),
+                };
+                let severity = alerts.first().map_or(Severity::Warning, |a| a.severity);
+                let key = format!("sitrep:{}:report:{}", host, chrono::Utc::now().timestamp());
+                (format!("{}: {}", host, headline), severity, key)
+            }
+        };
+        Incident { summary, severity, dedup_key, source: host, details: crate::commands::snapshot_json(self) }
+    }
+
+    /// Send the incident for `warning` (or the triage report). Returns the
+    /// notice to show.
+    pub fn create_incident(&mut self, warning: Option<&str>) -> String {
+        let Some(provider) = self.incidents.provider() else {
+            return "Set [incidents] provider and key to open incidents".to_string();
+        };
+        self.incidents.create(self.incident(warning));
+        format!("Opening {} incident...", provider.label())
+    }
+
+    /// Show how finished incident requests went. Returns true if any finished.
+    pub fn poll_incidents(&mut self) -> bool {
+        let outcomes = self.incidents.poll();
+        for outcome in &outcomes {
+            let message = match outcome {
+                Ok(message) => message.clone(),
+                Err(e) => format!("Error: {}", e),
+            };
+            self.toast = Some((message, Instant::now()));
+        }
+        !outcomes.is_empty()
+    }
+
+    /// The maintenance note to show: the newest of this host's and the cluster's.
+    pub fn maintenance_note(&self) -> Option<MaintenanceNote> {
+        notes::newest(self.note_file.note.clone(), self.swarm_monitor.maintenance_note())
+    }
+
+    /// Set the maintenance note (cluster-wide on a Swarm manager, else for
+    /// this host), or clear it everywhere it is set when `text` is empty.
+    pub fn set_maintenance_note(&mut self, text: &str) {
+        let note = (!text.trim().is_empty()).then(|| MaintenanceNote::new(text));
+        let manager = self.swarm_monitor.cluster_info.as_ref().is_some_and(|c| c.is_manager);
+        let cluster_note = self.swarm_monitor.maintenance_note().is_some();
+        let message = if manager && (note.is_some() || cluster_note) {
+            self.swarm_monitor.set_maintenance_note(note.clone());
+            match note {
+                Some(_) => "Maintenance note sent to the cluster".to_string(),
+                None => "Clearing the maintenance note from the cluster".to_string(),
+            }
+        } else {
+            match self.note_file.set(note.clone()) {
+                Ok(()) if note.is_some() => "Maintenance note set for this host".to_string(),
+                Ok(()) => "Maintenance note cleared".to_string(),
+                Err(e) => e,
+            }
+        };
+        // Clearing also removes a note left in this host's file
+        if note.is_none() && manager && self.note_file.note.is_some()
+            && let Err(e) = self.note_file.set(None) {
+                tracing::warn!("{}", e);
+            }
+        self.toast = Some((message, Instant::now()));
+    }
+
+    /// Show CPU columns of the System and Containers views as `scale`.
+    pub fn set_cpu_scale(&mut self, scale: crate::model::CpuScale) {
+        self.monitor.ui_state.cpu_scale = scale;
+        self.docker_monitor.ui_state.cpu_scale = scale;
+    }
+
+    /// Whether Docker and Swarm detection is still running.
+    pub fn probing(&self) -> bool {
+        self.startup_probe.is_some()
+    }
+
+    /// Apply edits to the config file at `path` while running.
+    pub fn watch_config(&mut self, path: std::path::PathBuf) {
+        self.config_watch = Some(crate::config::watch(path));
+    }
+
+    /// Let other processes poke this one: SIGUSR1 refreshes every monitor at
+    /// once and SIGUSR2 writes a JSON snapshot to `commands::dump_path()`.
+    pub fn watch_signals(&mut self) {
+        for (signal, flag) in [
+            (signal_hook::consts::SIGUSR1, &self.refresh_signal),
+            (signal_hook::consts::SIGUSR2, &self.dump_signal),
+        ] {
+            if let Err(e) = signal_hook::flag::register(signal, Arc::clone(flag)) {
+                tracing::warn!("Cannot handle signal {}: {}", signal, e);
+            }
+        }
+    }
+
+    /// Start forwarding alerts to journald/syslog (`--syslog`).
+    pub fn enable_syslog(&mut self) {
+        match SyslogForwarder::connect() {
+            Ok(forwarder) => {
+                tracing::info!("Forwarding alerts to {}", forwarder.target().display());
+                self.syslog = Some(forwarder);
+            }
+            Err(e) => tracing::warn!("Syslog forwarding disabled: {}", e),
+        }
+    }
+
+    /// Every active alert: host conditions, Swarm warnings and failing health checks.
+    pub fn current_alerts(&self) -> Vec<crate::alerts::Alert> {
+        let mut alerts = Vec::new();
+        if let Some(ref data) = self.monitor.last_data {
+            alerts.extend(crate::alerts::system_alerts(data, &self.monitor.ui_state.thresholds));
+        }
+        alerts.extend(crate::alerts::swarm_alerts(&self.swarm_monitor.warnings));
+        alerts.extend(crate::alerts::health_alerts(&self.health_monitor));
+        alerts.extend(crate::alerts::image_alerts(
+            &self.docker_monitor.containers,
+            self.config.containers.image_max_age_days,
+        ));
+        alerts.extend(crate::alerts::auth_alerts(&self.monitor.ui_state.auth));
+        alerts
+    }
+
+    /// The tick interval stretched by the self guard's back-off.
+    pub fn effective_tick_rate(&self) -> Duration {
+        self.tick_rate * self.monitor.ui_state.guard.factor
+    }
+
+    /// Feed sitrep's own CPU use from the latest snapshot to the self guard.
+    pub fn check_self_usage(&mut self) {
+        let Some(cpu) = self.monitor.last_data.as_ref().and_then(|d| d.self_usage.as_ref()).map(|u| u.total_cpu_percent())
+        else {
+            return;
+        };
+        let guard = &mut self.monitor.ui_state.guard;
+        if guard.observe(cpu) {
+            if guard.backing_off() {
+                tracing::warn!(
+                    "sitrep using {:.1}% CPU (budget {:.0}%), collector interval x{}",
+                    cpu,
+                    guard.cpu_budget,
+                    guard.factor
+                );
+            } else {
+                tracing::info!("sitrep back under its CPU budget, collector interval restored");
+            }
+        }
+    }
+
+    /// Whether the active view can show the pinned summary above it.
+    pub fn summary_pinnable(&self) -> bool {
+        matches!(
+            self.app_view,
+            AppView::Containers
+                | AppView::ContainerLogs(_)
+                | AppView::ContainerLogsMulti(_)
+                | AppView::SwarmServiceLogs(_, _)
+                | AppView::SwarmSplitLogs
+                | AppView::KubernetesPodLogs(_, _)
+        )
+    }
+
+    /// Terminal rows available to the active view, below the pinned summary.
+    pub fn view_height(&self) -> usize {
+        let height = crate::view::screen_size().map(|(_, h)| h).unwrap_or(24);
+        height.saturating_sub(self.pinned_rows) as usize
+    }
+
+    /// How current the data behind the active view is: the collector's last
+    /// good result for tables, the newest line for logs.
+    pub fn active_freshness(&self) -> Freshness {
+        let interval = self.effective_tick_rate();
+        let age = |at: Option<Instant>| at.map(|t| t.elapsed());
+        let system = Freshness::new(age(self.monitor.last_updated), self.monitor.update_failed, interval);
+        let docker = &self.docker_monitor;
+        let swarm = &self.swarm_monitor;
+        // A failed Swarm refresh still stamps last_updated, so its age says nothing
+        let swarm_age = if swarm.update_failed { None } else { age(swarm.last_updated) };
+        match &self.app_view {
+            AppView::System | AppView::Graphs | AppView::DockerHealth => system,
+            AppView::Containers => Freshness::new(age(docker.last_updated), docker.update_failed, interval),
+            AppView::ContainerLogs(id) => match docker.get_log_state(id) {
+                Some(log) => Freshness::of_log(age(log.last_line_at), log.ended),
+                None => Freshness::Collecting,
+            },
+            AppView::ContainerLogsMulti(_) => match docker.multi_log_state {
+                Some(ref multi) => Freshness::of_log(age(multi.last_line_at), false),
+                None => Freshness::Collecting,
+            },
+            AppView::Swarm | AppView::SwarmServiceTasks(_, _) => {
+                Freshness::new(swarm_age, swarm.update_failed, interval)
+            }
+            AppView::SwarmServiceLogs(_, _) => match swarm.log_state {
+                Some(ref log) => Freshness::of_log(age(log.last_line_at), log.ended),
+                None => Freshness::Collecting,
+            },
+            // Current while either pane still streams
+            AppView::SwarmSplitLogs => match swarm.split_logs {
+                Some(ref split) => Freshness::of_log(
+                    split.panes.iter().filter_map(|p| p.last_line_at).max().map(|t| t.elapsed()),
+                    split.panes.iter().all(|p| p.ended),
+                ),
+                None => Freshness::Collecting,
+            },
+            AppView::Kubernetes => {
+                // A failed refresh stamps last_updated too, as for Swarm
+                let kube = &self.kube_monitor;
+                let kube_age = if kube.update_failed { None } else { age(kube.last_updated) };
+                Freshness::new(kube_age, kube.update_failed, interval)
+            }
+            AppView::KubernetesPodLogs(_, _) => match self.kube_monitor.log_state {
+                Some(ref log) => Freshness::of_log(age(log.last_line_at), log.ended),
+                None => Freshness::Collecting,
+            },
+            // The older of the two sources, and failed if either is
+            AppView::Watchlist => Freshness::new(
+                [docker.last_updated, swarm.last_updated].into_iter().flatten().min().map(|t| t.elapsed()),
+                docker.update_failed || swarm.update_failed,
+                interval,
+            ),
+        }
+    }
+
+    /// The name and kind of the selected row, when the quick-action bar
+    /// should list its actions. The bar's row also holds the System notes
+    /// and the search and filter prompts, which take precedence.
+    pub fn quick_selection(&self) -> Option<(String, Selection)> {
+        match &self.app_view {
+            AppView::System => {
+                let ui_state = &self.monitor.ui_state;
+                if ui_state.panel != SystemPanel::Processes
+                    || ui_state.status_message.is_some()
+                    || ui_state.has_expansions()
+                {
+                    return None;
+                }
+                let (pid, name) = input::selected_process(self)?;
+                let in_container =
+                    self.monitor.last_data.as_ref().is_some_and(|d| d.process_containers.contains_key(&pid));
+                Some((name, Selection::Process { in_container }))
+            }
+            AppView::Containers => {
+                let docker = &self.docker_monitor;
+                if docker.ui_state.filter_mode || docker.ui_state.rename_input.is_some() {
+                    return None;
+                }
+                if let Some(group) = docker.selected_group() {
+                    return Some((group.name.clone(), Selection::ContainerGroup));
+                }
+                let c = docker.selected_container()?;
+                Some((c.name.clone(), Selection::Container { running: c.state == "running" }))
+            }
+            AppView::Swarm => {
+                let swarm = &self.swarm_monitor;
+                if swarm.ui_state.search_mode
+                    || swarm.ui_state.label_input.is_some()
+                    || swarm.ui_state.schedule_input.is_some()
+                    || swarm.ui_state.warning_ack.is_some()
+                    || swarm.ui_state.view_level != SwarmViewLevel::Overview
+                {
+                    return None;
+                }
+                match resolve_swarm_overview_item(swarm, swarm.ui_state.selected_index) {
+                    SwarmOverviewItem::Service(_, name) => Some((name, Selection::Service)),
+                    _ => None,
+                }
+            }
+            AppView::Watchlist => self.watchlist.selected_item().map(|item| (item.name.clone(), Selection::WatchItem)),
+            AppView::Kubernetes => match self.kube_monitor.selected_row()? {
+                KubeRow::Pod(i) => Some((self.kube_monitor.pods[i].name.clone(), Selection::Pod)),
+                _ => None,
+            },
+            _ => None,
         }
     }
 }
 
+/// The view the command line asks to open on, if any.
+fn start_target(cli: &crate::cli::Cli) -> Option<StartTarget> {
+    if let Some(ref name) = cli.container {
+        return Some(StartTarget::Container(name.clone()));
+    }
+    if let Some(ref name) = cli.service {
+        return Some(StartTarget::Service(name.clone()));
+    }
+    if let Some(ref name) = cli.logs {
+        return Some(StartTarget::Logs(name.clone()));
+    }
+    let view = match cli.tab? {
+        StartTab::System => AppView::System,
+        StartTab::Containers => AppView::Containers,
+        StartTab::Swarm => AppView::Swarm,
+        StartTab::Kubernetes => AppView::Kubernetes,
+        StartTab::Watchlist => AppView::Watchlist,
+    };
+    Some(StartTarget::Tab(view))
+}
+
 /// Run the application. Sets up terminal, runs the main loop, restores terminal on exit.
-pub fn run(should_quit: Arc<AtomicBool>, cli: &crate::cli::Cli) -> io::Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, Clear(ClearType::All))?;
+pub fn run(should_quit: Arc<AtomicBool>, cli: &crate::cli::Cli, config: Config) -> io::Result<()> {
+    // Before the alternate screen, so a bad path stays visible
+    let replay = cli.replay.as_deref().map(Replay::load).transpose().map_err(io::Error::other)?;
+    let recorder = cli.record.as_deref().map(Recorder::create).transpose().map_err(io::Error::other)?;
+    let _terminal = TerminalGuard::enter()?;
 
     let rt = Arc::new(
         tokio::runtime::Builder::new_multi_thread()
@@ -95,25 +683,103 @@ pub fn run(should_quit: Arc<AtomicBool>, cli: &crate::cli::Cli) -> io::Result<()
             .expect("Failed to create tokio runtime"),
     );
 
-    Presenter::render_splash()?;
+    let mut app = if let Some(replay) = replay {
+        App::from_recording(Arc::clone(&rt), cli.interval(&config), config, replay)
+    } else if cli.demo {
+        App::demo(Arc::clone(&rt), cli.interval(&config), cli.background_refresh, config)
+    } else {
+        App::new(
+            Arc::clone(&rt),
+            cli.interval(&config),
+            cli.no_docker,
+            cli.background_refresh,
+            config,
+        )
+    };
+    if cli.syslog {
+        app.enable_syslog();
+    }
+    // Demo and replayed containers and services have nothing behind them to act on
+    app.read_only = cli.read_only || cli.demo || app.replay.is_some();
+    if recorder.is_some() {
+        // Frames hold every tab, not just the one open
+        app.background_refresh.get_or_insert(app.tick_rate);
+        app.recorder = recorder;
+    }
+    app.start_target = start_target(cli);
+    if let Some(path) = cli.config_path() {
+        app.watch_config(path);
+    }
+    app.watch_signals();
 
-    let mut app = App::new(Arc::clone(&rt), cli.refresh_rate, cli.no_docker);
+    // `kill -TSTP` suspends like Ctrl+Z; SIGCONT after an uncatchable SIGSTOP
+    // finds the shell's terminal modes and is answered by taking it back.
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    let cont_signal = Arc::new(AtomicBool::new(false));
+    let _ = signal_hook::flag::register(signal_hook::consts::SIGTSTP, Arc::clone(&stop_signal));
+    let _ = signal_hook::flag::register(signal_hook::consts::SIGCONT, Arc::clone(&cont_signal));
     let mut needs_render = true;
 
     loop {
         if should_quit.load(Ordering::Relaxed) {
             break;
         }
+        if let Some(message) = take_worker_panic() {
+            return Err(io::Error::other(format!("a background thread panicked: {}", message)));
+        }
+        if stop_signal.swap(false, Ordering::Relaxed) {
+            suspend()?;
+            cont_signal.store(false, Ordering::Relaxed);
+            needs_render = true;
+        }
+        if cont_signal.swap(false, Ordering::Relaxed) {
+            resume_terminal()?;
+            needs_render = true;
+        }
 
         let now = Instant::now();
 
-        if app.expire_pending_action() {
+        if app.expire_pending_action() || app.expire_toast() {
+            needs_render = true;
+        }
+        // Both run every pass, so a reload never holds back a pending signal
+        let reloaded = app.poll_config();
+        let signalled = app.poll_signals();
+        if reloaded || signalled {
+            needs_render = true;
+        }
+        if app.poll_start_target() {
+            needs_render = true;
+        }
+        if app.poll_startup() {
             needs_render = true;
         }
         if app.monitor.poll_update() {
+            app.record_system_history();
+            app.check_self_usage();
+            if let Some(ref data) = app.monitor.last_data {
+                app.docker_monitor.ui_state.host_cores = data.core_count;
+            }
             needs_render = true;
         }
         if app.docker_monitor.poll_update() {
+            app.record_container_history();
+            app.monitor.ui_state.container_names = app
+                .docker_monitor
+                .containers
+                .iter()
+                .map(|c| (c.id.clone(), c.name.clone()))
+                .collect();
+            needs_render = true;
+        }
+        if app.swarm_monitor.poll_update() {
+            app.record_service_history();
+            needs_render = true;
+        }
+        if app.kube_monitor.poll_update() {
+            needs_render = true;
+        }
+        if app.health_monitor.poll_update() {
             needs_render = true;
         }
         if app.process_tick() {
@@ -130,7 +796,8 @@ pub fn run(should_quit: Arc<AtomicBool>, cli: &crate::cli::Cli) -> io::Result<()
         }
 
         if needs_render {
-            if Presenter::render_size_guard()? {
+            let mut out = io::stdout();
+            if Presenter::render_size_guard(&mut out)? {
                 needs_render = false;
                 let timeout = app.tick_rate.saturating_sub(now.elapsed());
                 if crossterm::event::poll(timeout.min(Duration::from_millis(100)))? {
@@ -139,27 +806,30 @@ pub fn run(should_quit: Arc<AtomicBool>, cli: &crate::cli::Cli) -> io::Result<()
                 continue;
             }
 
-            render::render(&mut app)?;
-
-            if let Some(ref pa) = app.pending_action {
-                Presenter::render_confirmation(&pa.description)?;
-            }
+            render::render(&mut out, &mut app)?;
 
             needs_render = false;
         }
 
         let timeout = app.tick_rate.saturating_sub(now.elapsed());
         if crossterm::event::poll(timeout.min(Duration::from_millis(100)))? {
-            if let crossterm::event::Event::Key(key_event) = crossterm::event::read()? {
-                match input::handle_key(&mut app, key_event) {
+            match crossterm::event::read()? {
+                crossterm::event::Event::Key(key_event) => match input::handle_key(&mut app, key_event) {
                     Some(input::InputResult::Quit) => break,
                     Some(input::InputResult::Consumed) => needs_render = true,
+                    Some(input::InputResult::Suspend) => {
+                        suspend()?;
+                        cont_signal.store(false, Ordering::Relaxed);
+                        needs_render = true;
+                    }
                     None => {}
-                }
+                },
+                // SIGWINCH: every frame clears, so redrawing at the new size is enough
+                crossterm::event::Event::Resize(..) => needs_render = true,
+                _ => {}
             }
         }
     }
 
-    restore_terminal();
     Ok(())
 }
@@ -1,13 +1,14 @@
-use std::io;
+use std::io::{self, Write};
 
 use crossterm::{cursor::MoveTo, execute, terminal::Clear, terminal::ClearType};
 
-use crate::model::{AppView, SwarmViewLevel};
-use crate::view::Presenter;
+use crate::model::{actions_for, AppView, SwarmViewLevel};
+use crate::view::theme::with_dimmed;
+use crate::view::{Presenter, Viewport};
 
 use super::App;
 
-pub fn render(app: &mut App) -> io::Result<()> {
+pub fn render(out: &mut impl Write, app: &mut App) -> io::Result<()> {
     let time_str = app
         .monitor
         .last_data
@@ -23,48 +24,103 @@ pub fn render(app: &mut App) -> io::Result<()> {
         .map(|c| c.nodes_total)
         .unwrap_or(0);
 
-    let mut out = io::stdout();
+    let kube_pods = app.kube_monitor.is_available().then_some(app.kube_monitor.pods.len());
 
-    match &app.app_view {
-        crate::model::AppView::System => {
+    // The tab bar says how current the active view's data is, and a view
+    // whose collector failed or has not finished is drawn dimmed.
+    let freshness = app.active_freshness();
+    let dim = freshness.is_out_of_date();
+
+    // With the summary pinned, containers and logs draw in the rows below it
+    let pinned = match app.monitor.last_data {
+        Some(ref data) if app.pin_summary && app.summary_pinnable() => {
             execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
             Presenter::render_tab_bar(
-                &mut out,
+                out,
                 &app.app_view,
                 app.docker_monitor.is_available(),
                 app.docker_monitor.containers.len(),
                 swarm_active,
                 swarm_node_count,
+                kube_pods,
+                app.watchlist.items.len(),
                 &time_str,
+                freshness,
             )?;
-            if let Some(ref data) = app.monitor.last_data {
-                app.row_mapping =
-                    Presenter::render(data, &mut app.monitor.ui_state, &app.monitor.layout)?;
-            }
+            Some(Presenter::render_pinned_summary(out, data, &app.monitor.ui_state)?)
         }
-        crate::model::AppView::Containers => {
+        _ => None,
+    };
+    app.pinned_rows = pinned.map_or(0, |viewport| viewport.top);
+    let log_viewport = match pinned {
+        Some(viewport) => viewport,
+        None => Viewport::full()?,
+    };
+
+    match &app.app_view {
+        crate::model::AppView::System => {
             execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
             Presenter::render_tab_bar(
-                &mut out,
+                out,
                 &app.app_view,
                 app.docker_monitor.is_available(),
                 app.docker_monitor.containers.len(),
                 swarm_active,
                 swarm_node_count,
+                kube_pods,
+                app.watchlist.items.len(),
                 &time_str,
+                freshness,
             )?;
-            Presenter::render_containers(
-                &app.docker_monitor.containers,
-                &app.docker_monitor.ui_state,
-                &app.docker_monitor.status_message,
-            )?;
+            if let Some(ref data) = app.monitor.last_data {
+                app.row_mapping = with_dimmed(dim, || {
+                    Presenter::render(out, data, &mut app.monitor.ui_state, &app.monitor.layout)
+                })?;
+            } else {
+                Presenter::render_collecting(out, app.probing())?;
+            }
+        }
+        crate::model::AppView::Containers => {
+            let viewport = match pinned {
+                Some(viewport) => viewport,
+                None => {
+                    execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+                    Presenter::render_tab_bar(
+                        out,
+                        &app.app_view,
+                        app.docker_monitor.is_available(),
+                        app.docker_monitor.containers.len(),
+                        swarm_active,
+                        swarm_node_count,
+                        kube_pods,
+                        app.watchlist.items.len(),
+                        &time_str,
+                        freshness,
+                    )?;
+                    Viewport::below(2)?
+                }
+            };
+            let rows = app.docker_monitor.rows();
+            with_dimmed(dim, || {
+                Presenter::render_containers(
+                    out,
+                    &app.docker_monitor.containers,
+                    &app.docker_monitor.groups,
+                    &rows,
+                    &mut app.docker_monitor.ui_state,
+                    &app.docker_monitor.status_message,
+                    &app.config.containers,
+                    &app.health_monitor,
+                    &app.docker_monitor.stats_history,
+                    viewport,
+                )
+            })?;
         }
         crate::model::AppView::ContainerLogs(_) => {
-            if let AppView::ContainerLogs(container_id) = &app.app_view {
-                if let Some(ref log_state) = app.docker_monitor.get_log_state(container_id) {
-                    Presenter::render_logs(log_state)?;
+            if let AppView::ContainerLogs(container_id) = &app.app_view
+                && let Some(log_state) = app.docker_monitor.get_log_state(container_id) {
+                    with_dimmed(dim, || Presenter::render_logs(out, log_state, log_viewport))?;
                 }
-            }
         }
         crate::model::AppView::ContainerLogsMulti(_) => {
             // Build the indicator list from actually active log streams,
@@ -79,51 +135,169 @@ pub fn render(app: &mut App) -> io::Result<()> {
             active_names.dedup();
 
             if let Some(ref multi_state) = app.docker_monitor.multi_log_state {
-                Presenter::render_multi_container_logs(multi_state, &active_names)?;
+                Presenter::render_multi_container_logs(out, multi_state, &active_names, log_viewport)?;
             }
         }
         crate::model::AppView::Swarm | crate::model::AppView::SwarmServiceTasks(_, _) => {
             execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
             Presenter::render_tab_bar(
-                &mut out,
+                out,
                 &app.app_view,
                 app.docker_monitor.is_available(),
                 app.docker_monitor.containers.len(),
                 swarm_active,
                 swarm_node_count,
+                kube_pods,
+                app.watchlist.items.len(),
                 &time_str,
+                freshness,
             )?;
-            match &app.swarm_monitor.ui_state.view_level {
+            with_dimmed(dim, || match &app.swarm_monitor.ui_state.view_level {
                 SwarmViewLevel::Overview => {
+                    let (warnings, muted) = app.swarm_warnings();
                     Presenter::render_swarm_overview(
+                        out,
                         &app.swarm_monitor.cluster_info,
                         &app.swarm_monitor.nodes,
                         &app.swarm_monitor.stacks,
                         &app.swarm_monitor.services,
                         &app.swarm_monitor.ui_state,
-                        &app.swarm_monitor.warnings,
+                        &warnings,
+                        &muted,
                         &app.swarm_monitor.status_message,
                         &app.swarm_monitor.service_tasks,
-                    )?;
+                        &app.swarm_monitor.stack_drift,
+                        &app.health_monitor,
+                        &app.schedule,
+                    )
                 }
                 SwarmViewLevel::ServiceTasks(_, name) => {
                     Presenter::render_swarm_tasks(
+                        out,
                         name,
                         &app.swarm_monitor.tasks,
                         &app.swarm_monitor.nodes,
+                        &app.swarm_monitor.spec,
+                        app.swarm_monitor.timeline.as_ref(),
                         app.swarm_monitor.ui_state.selected_index,
                         &app.swarm_monitor.status_message,
-                    )?;
+                    )
                 }
-                SwarmViewLevel::ServiceLogs(_, _) => {}
+                SwarmViewLevel::ServiceLogs(_, _) => Ok(()),
+            })?;
+        }
+        crate::model::AppView::Watchlist => {
+            execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+            Presenter::render_tab_bar(
+                out,
+                &app.app_view,
+                app.docker_monitor.is_available(),
+                app.docker_monitor.containers.len(),
+                swarm_active,
+                swarm_node_count,
+                kube_pods,
+                app.watchlist.items.len(),
+                &time_str,
+                freshness,
+            )?;
+            with_dimmed(dim, || {
+                Presenter::render_watchlist(
+                    out,
+                    &app.watchlist,
+                    &app.docker_monitor.containers,
+                    &app.swarm_monitor.services,
+                    &app.health_monitor,
+                    &app.watch_status,
+                )
+            })?;
+        }
+        crate::model::AppView::Kubernetes => {
+            execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+            Presenter::render_tab_bar(
+                out,
+                &app.app_view,
+                app.docker_monitor.is_available(),
+                app.docker_monitor.containers.len(),
+                swarm_active,
+                swarm_node_count,
+                kube_pods,
+                app.watchlist.items.len(),
+                &time_str,
+                freshness,
+            )?;
+            with_dimmed(dim, || Presenter::render_kubernetes(out, &app.kube_monitor))?;
+        }
+        crate::model::AppView::KubernetesPodLogs(_, _) => {
+            if let Some(ref log_state) = app.kube_monitor.log_state {
+                with_dimmed(dim, || Presenter::render_logs(out, log_state, log_viewport))?;
+            }
+        }
+        crate::model::AppView::DockerHealth => {
+            execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+            Presenter::render_tab_bar(
+                out,
+                &app.app_view,
+                app.docker_monitor.is_available(),
+                app.docker_monitor.containers.len(),
+                swarm_active,
+                swarm_node_count,
+                kube_pods,
+                app.watchlist.items.len(),
+                &time_str,
+                freshness,
+            )?;
+            let processes = app.monitor.last_data.as_ref().map_or(&[][..], |d| &d.daemon_processes[..]);
+            with_dimmed(dim, || {
+                Presenter::render_docker_health(
+                    out,
+                    app.docker_monitor.daemon_health.as_ref(),
+                    processes,
+                    &app.docker_monitor.status_message,
+                )
+            })?;
+        }
+        crate::model::AppView::Graphs => {
+            if let Some(ref state) = app.graph_state {
+                Presenter::render_graphs(out, state, &app.graph_status)?;
             }
         }
         crate::model::AppView::SwarmServiceLogs(_, _) => {
             if let Some(ref log_state) = app.swarm_monitor.log_state {
-                Presenter::render_service_logs(log_state)?;
+                with_dimmed(dim, || Presenter::render_service_logs(out, log_state, log_viewport))?;
+            }
+        }
+        crate::model::AppView::SwarmSplitLogs => {
+            if let Some(ref split) = app.swarm_monitor.split_logs {
+                with_dimmed(dim, || Presenter::render_split_logs(out, split, log_viewport))?;
             }
         }
     }
 
+    // Actions for the selected row, above the help footer
+    if let Some((name, selection)) = app.quick_selection() {
+        Presenter::render_action_bar(out, &name, &actions_for(&selection, app.read_only))?;
+    }
+
+    render_overlays(out, app)
+}
+
+/// The palette, prompts and banners over the row below the tab bar, then the
+/// confirmation and the toast, drawn over whichever view is shown.
+fn render_overlays(out: &mut impl Write, app: &App) -> io::Result<()> {
+    if let Some(ref palette) = app.palette {
+        Presenter::render_palette(out, &palette.input, &app.macros.matching(&palette.input), palette.selected)?;
+    } else if let Some(ref input) = app.note_input {
+        Presenter::render_note_prompt(out, input)?;
+    } else if let Some(ref replay) = app.replay {
+        Presenter::render_replay_banner(out, replay)?;
+    } else if let Some(note) = app.maintenance_note() {
+        Presenter::render_maintenance_banner(out, &note)?;
+    }
+    if let Some(ref pa) = app.pending_action {
+        Presenter::render_confirmation(out, &pa.description)?;
+    }
+    if let Some((ref message, _)) = app.toast {
+        Presenter::render_toast(out, message)?;
+    }
     Ok(())
 }
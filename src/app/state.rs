@@ -1,7 +1,17 @@
 use std::time::Instant;
 
+use crate::model::{AppView, LabelEdit};
+use crate::schedule::ScheduledRestart;
+use crate::swarm;
 use crate::swarm_controller::SwarmMonitor;
 
+/// The command palette: the typed filter and the highlighted macro.
+#[derive(Default)]
+pub struct Palette {
+    pub input: String,
+    pub selected: usize,
+}
+
 /// Pending destructive action awaiting confirmation.
 pub struct PendingAction {
     pub description: String,
@@ -13,7 +23,37 @@ pub enum PendingActionKind {
     ContainerStart(String),
     ContainerStop(String),
     ContainerRestart(String),
+    /// Start, stop or restart every marked container: (action, (id, name) targets).
+    ContainerBatch(&'static str, Vec<(String, String)>),
+    /// (id, current name, new name).
+    ContainerRename(String, String, String),
+    /// Set or remove a service label: (service id, service name, edit).
+    ServiceLabel(String, String, LabelEdit),
     SwarmRollingRestart(String),
+    /// Schedule a rolling restart for later.
+    SwarmScheduleRestart(ScheduledRestart),
+    /// Attach a syscall tracer: (pid, process name).
+    TraceProcess(u32, String),
+    /// Open an incident about a Swarm warning, or a triage report when None.
+    CreateIncident(Option<String>),
+}
+
+impl PendingActionKind {
+    /// Whether the action changes a container or service (refused with `--read-only`).
+    pub fn mutates(&self) -> bool {
+        !matches!(self, PendingActionKind::TraceProcess(_, _) | PendingActionKind::CreateIncident(_))
+    }
+}
+
+/// The view `--tab`, `--container`, `--service` or `--logs` asked to open
+/// on, resolved once the data it names has arrived.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StartTarget {
+    Tab(AppView),
+    Container(String),
+    Service(String),
+    /// A container or, failing that, a service.
+    Logs(String),
 }
 
 /// What kind of item is at a given row index in the Swarm overview.
@@ -63,12 +103,91 @@ pub fn resolve_swarm_overview_item(monitor: &SwarmMonitor, selected: usize) -> S
     SwarmOverviewItem::None
 }
 
+/// A Swarm overview row matched by the search box.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SwarmSearchHit {
+    Node(String, String),    // (node_id, hostname)
+    Service(String, String), // (service_id, service_name)
+}
+
+impl SwarmSearchHit {
+    pub fn describe(&self) -> String {
+        match self {
+            SwarmSearchHit::Node(_, hostname) => format!("node {}", hostname),
+            SwarmSearchHit::Service(_, name) => format!("service {}", name),
+        }
+    }
+}
+
+/// Nodes and services matching `query` (case-insensitive), in overview order:
+/// node hostnames, then service names, images and the errors of the tasks
+/// sitrep has fetched for them.
+pub fn swarm_search_hits(monitor: &SwarmMonitor, query: &str) -> Vec<SwarmSearchHit> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let found = |text: &str| text.to_lowercase().contains(&query);
+
+    let mut hits: Vec<SwarmSearchHit> = monitor
+        .nodes
+        .iter()
+        .filter(|n| found(&n.hostname))
+        .map(|n| SwarmSearchHit::Node(n.id.clone(), n.hostname.clone()))
+        .collect();
+    for stack in &monitor.stacks {
+        for &idx in &stack.service_indices {
+            let svc = &monitor.services[idx];
+            let task_error = monitor
+                .service_tasks
+                .get(&svc.id)
+                .into_iter()
+                .flatten()
+                .chain(monitor.failed_tasks.iter().filter(|t| swarm::task_service_name(&t.name) == svc.name))
+                .any(|t| found(&t.error));
+            if found(&svc.name) || found(&svc.image) || task_error {
+                hits.push(SwarmSearchHit::Service(svc.id.clone(), svc.name.clone()));
+            }
+        }
+    }
+    hits
+}
+
+/// Row of `hit` in the overview, expanding the nodes section or the
+/// service's stack so the row is shown.
+pub fn reveal_swarm_hit(monitor: &mut SwarmMonitor, hit: &SwarmSearchHit) -> Option<usize> {
+    let mut row_idx: usize = 1;
+    if let SwarmSearchHit::Node(id, _) = hit {
+        monitor.ui_state.expanded_ids.insert("__nodes__".to_string());
+        return monitor.nodes.iter().position(|n| n.id == *id).map(|pos| row_idx + pos);
+    }
+    if monitor.ui_state.expanded_ids.contains("__nodes__") {
+        row_idx += monitor.nodes.len();
+    }
+
+    let SwarmSearchHit::Service(id, _) = hit else {
+        return None;
+    };
+    for stack in &monitor.stacks {
+        row_idx += 1;
+        let pos = stack.service_indices.iter().position(|&idx| monitor.services[idx].id == *id);
+        if let Some(pos) = pos {
+            monitor.ui_state.expanded_ids.insert(stack.name.clone());
+            return Some(row_idx + pos);
+        }
+        if monitor.ui_state.expanded_ids.contains(&stack.name) {
+            row_idx += stack.service_indices.len();
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use crate::model::{SwarmNodeInfo, SwarmServiceInfo, SwarmStackInfo};
     use crate::swarm_controller::SwarmMonitor;
 
-    use super::{resolve_swarm_overview_item, SwarmOverviewItem};
+    use super::{resolve_swarm_overview_item, reveal_swarm_hit, swarm_search_hits, SwarmOverviewItem, SwarmSearchHit};
 
     fn make_monitor(
         nodes: Vec<SwarmNodeInfo>,
@@ -144,4 +263,45 @@ mod tests {
             SwarmOverviewItem::None
         ));
     }
+
+    #[test]
+    fn swarm_search_finds_and_reveals_collapsed_rows() {
+        let service = |id: &str, name: &str, image: &str| SwarmServiceInfo {
+            id: id.into(),
+            name: name.into(),
+            image: image.into(),
+            ..Default::default()
+        };
+        let nodes = vec![SwarmNodeInfo {
+            id: "n1".into(),
+            hostname: "web-node".into(),
+            ..Default::default()
+        }];
+        let services = vec![service("s1", "shop_api", "api:1.4"), service("s2", "shop_web", "nginx:1.27")];
+        let stacks = vec![
+            SwarmStackInfo { name: "other".into(), service_indices: vec![] },
+            SwarmStackInfo { name: "shop".into(), service_indices: vec![0, 1] },
+        ];
+        let mut monitor = make_monitor(nodes, services, stacks, &[]);
+        monitor.failed_tasks.push(crate::model::SwarmTaskInfo {
+            name: "shop_api.2".into(),
+            error: "No such image: api:1.4".into(),
+            ..Default::default()
+        });
+
+        let hits = swarm_search_hits(&monitor, "WEB");
+        assert_eq!(
+            hits,
+            vec![
+                SwarmSearchHit::Node("n1".into(), "web-node".into()),
+                SwarmSearchHit::Service("s2".into(), "shop_web".into()),
+            ]
+        );
+        assert_eq!(swarm_search_hits(&monitor, "no such image").len(), 1);
+
+        // Nodes header, then the expanded node, then both stack headers
+        assert_eq!(reveal_swarm_hit(&mut monitor, &hits[0]), Some(1));
+        assert_eq!(reveal_swarm_hit(&mut monitor, &hits[1]), Some(5));
+        assert!(matches!(resolve_swarm_overview_item(&monitor, 5), SwarmOverviewItem::Service(id, _) if id == "s2"));
+    }
 }
@@ -0,0 +1,107 @@
+//! Follow the system auth log for failed SSH logins: `/var/log/auth.log`
+//! (Debian/Ubuntu) or `/var/log/secure` (RHEL) when readable, otherwise
+//! journald's sshd entries. Only new lines are read; each failure is sent as
+//! the remote address it came from.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::cmd::{self, TrackedChild};
+
+/// Auth log files checked in order.
+const LOG_FILES: [&str; 2] = ["/var/log/auth.log", "/var/log/secure"];
+
+/// A running follower; the child process is stopped on drop.
+pub struct AuthLogStream {
+    /// The file followed, or "journald".
+    pub source: String,
+    pub receiver: mpsc::Receiver<String>,
+    _child: TrackedChild,
+}
+
+/// Start following the first readable auth log.
+pub fn follow() -> Result<AuthLogStream, String> {
+    if !cfg!(target_os = "linux") {
+        return Err("auth log monitoring is Linux only".to_string());
+    }
+    let (source, mut command) = match LOG_FILES.iter().find(|path| File::open(path).is_ok()) {
+        Some(path) => {
+            let mut command = Command::new("tail");
+            command.args(["-F", "-n", "0", path]);
+            (path.to_string(), command)
+        }
+        None => {
+            let mut command = Command::new("journalctl");
+            command.args(["-f", "-n", "0", "-o", "short", "-t", "sshd", "-t", "sshd-session"]);
+            ("journald".to_string(), command)
+        }
+    };
+
+    let spawned = cmd::spawn(command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::null()))
+        .map_err(|e| format!("Failed to follow {}: {}", source, e))?;
+    let stdout = spawned.stdout.ok_or("no stdout pipe")?;
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if let Some(remote) = parse_failure(&line)
+                && tx.send(remote).is_err() {
+                    break;
+                }
+        }
+    });
+
+    Ok(AuthLogStream {
+        source,
+        receiver: rx,
+        _child: spawned.child,
+    })
+}
+
+/// The remote address of a failed sshd login, e.g. from
+/// "sshd[812]: Failed password for root from 203.0.113.9 port 52144 ssh2" or
+/// "sshd[812]: Invalid user admin from 203.0.113.9 port 52144". Retries
+/// against an invalid user ("Failed password for invalid user ...") follow an
+/// "Invalid user" line and are not counted again.
+pub fn parse_failure(line: &str) -> Option<String> {
+    if !line.contains("sshd") {
+        return None;
+    }
+    let message = line.split_once("]: ").map_or(line, |(_, m)| m);
+    let counted = (message.starts_with("Failed ") && !message.contains(" for invalid user "))
+        || message.starts_with("Invalid user ");
+    if !counted {
+        return None;
+    }
+    let remote = message
+        .rsplit_once(" from ")
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .unwrap_or("unknown");
+    Some(remote.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sshd_failures() {
+        let prefix = "Oct 14 09:12:01 web1 sshd[812]: ";
+        let parse = |msg: &str| parse_failure(&format!("{}{}", prefix, msg));
+        assert_eq!(
+            parse("Failed password for root from 203.0.113.9 port 52144 ssh2"),
+            Some("203.0.113.9".to_string())
+        );
+        assert_eq!(
+            parse("Invalid user admin from 2001:db8::7 port 40022"),
+            Some("2001:db8::7".to_string())
+        );
+        assert_eq!(parse("Failed password for invalid user admin from 203.0.113.9 port 52144 ssh2"), None);
+        assert_eq!(parse("Accepted publickey for deploy from 198.51.100.4 port 50000 ssh2"), None);
+        assert_eq!(parse_failure("Oct 14 09:12:01 web1 sudo: Failed password for root"), None);
+    }
+}
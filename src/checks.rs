@@ -0,0 +1,55 @@
+//! Custom lines of the System summary, filled from commands in the manner of
+//! a Nagios plugin: the first line a command prints is shown and its exit
+//! status says whether that is fine, a warning or critical.
+
+use std::process::Command;
+
+use crate::cmd;
+use crate::model::{CheckState, CustomLineOutput};
+
+/// Run `command` with `sh -c` and read its line.
+pub fn run(command: &str) -> CustomLineOutput {
+    match cmd::output(Command::new("sh").args(["-c", command]), cmd::QUICK) {
+        Ok(output) => parse_output(
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+            output.status.code(),
+        ),
+        Err(e) => CustomLineOutput {
+            text: format!("failed to run: {}", e),
+            state: CheckState::Unknown,
+        },
+    }
+}
+
+/// The first non-empty line of stdout, or of stderr when stdout has none,
+/// with the state given by the exit code (none when killed by a signal).
+pub fn parse_output(stdout: &str, stderr: &str, code: Option<i32>) -> CustomLineOutput {
+    let first_line = |text: &str| text.lines().map(str::trim).find(|l| !l.is_empty()).map(str::to_string);
+    let state = match code {
+        Some(0) => CheckState::Ok,
+        Some(1) => CheckState::Warning,
+        Some(2) => CheckState::Critical,
+        _ => CheckState::Unknown,
+    };
+    let text = first_line(stdout).or_else(|| first_line(stderr)).unwrap_or_else(|| match code {
+        Some(code) => format!("no output (exit {})", code),
+        None => "no output (killed)".to_string(),
+    });
+    CustomLineOutput { text, state }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_first_line_and_nagios_status() {
+        let out = parse_output("\nWARNING: 120 jobs queued | queued=120\nmore\n", "", Some(1));
+        assert_eq!(out.text, "WARNING: 120 jobs queued | queued=120");
+        assert_eq!(out.state, CheckState::Warning);
+        let out = parse_output("", "sh: check_queue: not found\n", Some(127));
+        assert_eq!((out.text.as_str(), out.state), ("sh: check_queue: not found", CheckState::Unknown));
+        assert_eq!(parse_output("", "", Some(2)).text, "no output (exit 2)");
+    }
+}
@@ -1,22 +1,197 @@
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "sitrep", version, about = "Real-time terminal diagnostic tool for server triage")]
 pub struct Cli {
-    /// Refresh interval in seconds
-    #[arg(long, default_value = "3")]
-    pub refresh_rate: u64,
+    /// What to run; the TUI when omitted
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Refresh interval in seconds [default: [ui] interval_secs, else 3]
+    #[arg(long = "interval", visible_alias = "refresh-rate", value_name = "SECS", env = "SITREP_INTERVAL", global = true)]
+    pub refresh_rate: Option<u64>,
+
+    /// Also refresh inactive tabs every N seconds (disabled by default)
+    #[arg(long, value_name = "SECS")]
+    pub background_refresh: Option<u64>,
+
+    /// Config file (default: ~/.config/sitrep/config.toml)
+    #[arg(long, value_name = "PATH", env = "SITREP_CONFIG", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Tab to open on start
+    #[arg(long, value_enum, group = "start")]
+    pub tab: Option<StartTab>,
+
+    /// Open on this container, selected in the Containers tab
+    #[arg(long, value_name = "NAME", group = "start")]
+    pub container: Option<String>,
+
+    /// Open on this Swarm service's task list
+    #[arg(long, value_name = "NAME", group = "start")]
+    pub service: Option<String>,
+
+    /// Open the log stream of the container or service with this name
+    #[arg(long, value_name = "NAME", group = "start")]
+    pub logs: Option<String>,
+
+    /// Refuse container and service actions (start, stop, restart, rolling restart)
+    #[arg(long, env = "SITREP_READ_ONLY", global = true)]
+    pub read_only: bool,
+
+    /// Docker daemon to use, e.g. unix:///run/user/1000/docker.sock or
+    /// tcp://10.0.0.5:2375 (sets DOCKER_HOST for the API client and CLI).
+    /// Swarm nodes, tasks and service logs always use the docker CLI; the
+    /// Engine API client has no endpoints for them yet
+    #[arg(long, value_name = "URL", env = "SITREP_DOCKER_HOST", global = true)]
+    pub docker_host: Option<String>,
+
+    /// Docker socket to use, e.g. /run/user/1000/docker.sock; shorthand for
+    /// --docker-host unix://PATH. Without either, sitrep tries the rootful,
+    /// rootless and Docker Desktop sockets in turn
+    #[arg(long, value_name = "PATH", env = "SITREP_DOCKER_SOCKET", global = true, conflicts_with = "docker_host")]
+    pub docker_socket: Option<PathBuf>,
 
     /// Disable Docker container monitoring
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub no_docker: bool,
 
-    /// Log file path (default: ~/.sitrep/sitrep.log)
+    /// Show a made-up host, containers and Swarm cluster instead of this
+    /// machine's, for demos and screenshots (implies --read-only)
+    #[arg(long, global = true)]
+    pub demo: bool,
+
+    /// Forward warnings to the local journald/syslog as they are raised and resolved
+    #[arg(long, global = true)]
+    pub syslog: bool,
+
+    /// Same as `sitrep agent`
     #[arg(long)]
+    pub daemon: bool,
+
+    /// Same as `sitrep snapshot`
+    #[arg(long, conflicts_with = "daemon")]
+    pub snapshot: bool,
+
+    /// Collect once and print the summary and top processes as plain text,
+    /// for pasting into an incident ticket
+    #[arg(long, conflicts_with_all = ["daemon", "snapshot"])]
+    pub report: bool,
+
+    /// Write each refresh's host, container and Swarm state to PATH, for
+    /// `--replay` later. Inactive tabs are refreshed too while recording
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["daemon", "snapshot", "report", "replay"])]
+    pub record: Option<PathBuf>,
+
+    /// Step through a recording made with `--record` in place of this
+    /// machine (implies --read-only)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["daemon", "snapshot", "report", "demo"])]
+    pub replay: Option<PathBuf>,
+
+    /// Address for the agent's HTTP endpoints (/metrics, /api/alerts)
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:9469", global = true)]
+    pub listen: String,
+
+    /// Log file path (default: ~/.sitrep/sitrep.log)
+    #[arg(long, global = true)]
     pub log_file: Option<PathBuf>,
 
     /// Log level: error, warn, info, debug, trace
-    #[arg(long, default_value = "info")]
+    #[arg(long, default_value = "info", global = true)]
     pub log_level: String,
+
+    /// Trace every refresh and external command to ~/.cache/sitrep/debug.log
+    /// (or --log-file), to attach to a bug report
+    #[arg(long, global = true)]
+    pub debug: bool,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Collect once and print alerts, processes, containers and services as
+    /// JSON, with everything the monitors collected under "state"
+    Snapshot,
+    /// Run headless as a node agent: no TUI, alerts and metrics served over HTTP
+    Agent,
+    /// Run the configured health check for a container or service once
+    ///
+    /// The exit status is 0 when healthy, 2 when failing and 3 when no check
+    /// is configured for the name.
+    Check {
+        /// Container or service name from the `[health]` config table
+        name: String,
+    },
+}
+
+/// Tabs `--tab` can open.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartTab {
+    System,
+    Containers,
+    Swarm,
+    Kubernetes,
+    Watchlist,
+}
+
+impl Cli {
+    /// Whether this invocation runs the interactive TUI.
+    pub fn runs_tui(&self) -> bool {
+        self.command.is_none() && !self.daemon && !self.snapshot && !self.report
+    }
+
+    /// Whether this invocation prints a plain-text report and exits.
+    pub fn runs_report(&self) -> bool {
+        self.command.is_none() && self.report
+    }
+
+    /// Whether this invocation collects once and prints a JSON snapshot.
+    pub fn runs_snapshot(&self) -> bool {
+        self.command == Some(Command::Snapshot) || (self.command.is_none() && self.snapshot)
+    }
+
+    /// Refresh interval in seconds: the flag, else `[ui] interval_secs`, else 3.
+    pub fn interval(&self, config: &crate::config::Config) -> u64 {
+        self.refresh_rate.or(config.ui.interval_secs).unwrap_or(3)
+    }
+
+    /// Whether this invocation runs headless as a node agent.
+    pub fn runs_agent(&self) -> bool {
+        self.command == Some(Command::Agent) || (self.command.is_none() && self.daemon)
+    }
+
+    /// The config file in use: `--config` or the default location.
+    pub fn config_path(&self) -> Option<PathBuf> {
+        self.config.clone().or_else(crate::config::default_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_subcommands_and_global_flags() {
+        let cli = Cli::parse_from(["sitrep", "check", "api", "--config", "/etc/sitrep.toml"]);
+        assert_eq!(cli.command, Some(Command::Check { name: "api".to_string() }));
+        assert_eq!(cli.config, Some(PathBuf::from("/etc/sitrep.toml")));
+        assert!(!cli.runs_tui());
+
+        let cli = Cli::parse_from(["sitrep", "--refresh-rate", "5", "--tab", "containers", "--read-only"]);
+        assert_eq!((cli.refresh_rate, cli.tab, cli.read_only), (Some(5), Some(StartTab::Containers), true));
+        assert!(cli.runs_tui());
+
+        assert_eq!(Cli::parse_from(["sitrep", "--logs", "nginx"]).logs, Some("nginx".to_string()));
+        assert!(Cli::try_parse_from(["sitrep", "--container", "nginx", "--tab", "swarm"]).is_err());
+
+        assert!(Cli::parse_from(["sitrep", "agent"]).runs_agent());
+        assert!(Cli::parse_from(["sitrep", "--daemon"]).runs_agent());
+        let cli = Cli::parse_from(["sitrep", "--snapshot", "--demo"]);
+        assert!(cli.runs_snapshot() && !cli.runs_tui());
+        assert!(Cli::parse_from(["sitrep", "--report"]).runs_report());
+        assert!(Cli::try_parse_from(["sitrep", "--report", "--snapshot"]).is_err());
+        let cli = Cli::parse_from(["sitrep", "--replay", "incident.sitrep"]);
+        assert!(cli.runs_tui() && cli.replay == Some(PathBuf::from("incident.sitrep")));
+        assert!(Cli::try_parse_from(["sitrep", "--record", "a.sitrep", "--replay", "b.sitrep"]).is_err());
+    }
 }
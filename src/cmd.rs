@@ -0,0 +1,255 @@
+//! Running external tools without trusting them to finish. A wedged docker
+//! daemon, an NFS mount or a package manager waiting on a lock can keep a
+//! child blocked indefinitely, so every short-lived command goes through
+//! `output`: stdin is closed (or, with `output_with_input`, fed a fixed
+//! input), stdout and stderr are capped at MAX_OUTPUT
+//! bytes each, and the child is killed once its timeout elapses.
+//!
+//! Every child sitrep starts, long-running followers included, is spawned
+//! with `spawn` and held in one process-wide registry. A `TrackedChild`
+//! handle kills and reaps its process when dropped; `reap` collects children
+//! that exited on their own, and `kill_all` runs on quit, on a fatal signal
+//! and from the panic hook so no `docker logs --follow` outlives sitrep.
+
+use std::io::{self, Read, Write};
+use std::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Timeout for quick queries (`docker info`, `sysctl`, `netstat`).
+pub const QUICK: Duration = Duration::from_secs(10);
+/// Timeout for commands that legitimately take a while: package manager
+/// queries, or a rolling `docker service update` waiting to converge.
+pub const SLOW: Duration = Duration::from_secs(120);
+/// Bytes kept from each of stdout and stderr; the rest is read and dropped
+/// so the child never blocks on a full pipe.
+pub const MAX_OUTPUT: usize = 8 * 1024 * 1024;
+
+/// How often a running child is checked for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Every live child, reaped or not, until its handle is dropped. Entries are
+/// keyed by a counter rather than the pid, which can be reused once reaped.
+static CHILDREN: Mutex<Vec<(u64, Child)>> = Mutex::new(Vec::new());
+static NEXT_KEY: AtomicU64 = AtomicU64::new(0);
+
+/// The registry survives a panic elsewhere; the Vec is never left half-updated.
+fn children() -> MutexGuard<'static, Vec<(u64, Child)>> {
+    CHILDREN.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A registered child process. Dropping the handle kills the process if it
+/// is still running and reaps it, so an early return never leaves it behind.
+pub struct TrackedChild {
+    key: u64,
+    id: u32,
+}
+
+/// A freshly spawned child and the pipes its caller asked for.
+pub struct Spawned {
+    pub child: TrackedChild,
+    pub stdin: Option<ChildStdin>,
+    pub stdout: Option<ChildStdout>,
+    pub stderr: Option<ChildStderr>,
+}
+
+impl TrackedChild {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The exit status if the child has exited, reaping it.
+    pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
+        match children().iter_mut().find(|(key, _)| *key == self.key) {
+            Some((_, child)) => child.try_wait(),
+            // Already killed by `kill_all`
+            None => Err(io::Error::other("process was killed")),
+        }
+    }
+
+    /// Kill the child without waiting; the handle still reaps it on drop.
+    pub fn kill(&self) {
+        if let Some((_, child)) = children().iter_mut().find(|(key, _)| *key == self.key) {
+            let _ = child.kill();
+        }
+    }
+}
+
+impl Drop for TrackedChild {
+    fn drop(&mut self) {
+        let child = {
+            let mut children = children();
+            let pos = children.iter().position(|(key, _)| *key == self.key);
+            pos.map(|pos| children.swap_remove(pos).1)
+        };
+        if let Some(mut child) = child {
+            if child.try_wait().ok().flatten().is_none() {
+                let _ = child.kill();
+            }
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Spawn `command` into the registry. The stdin, stdout and stderr pipes,
+/// when configured as piped, are handed back to the caller.
+pub fn spawn(command: &mut Command) -> io::Result<Spawned> {
+    let mut child = command.spawn()?;
+    let stdin = child.stdin.take();
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let child_id = child.id();
+    let key = NEXT_KEY.fetch_add(1, Ordering::Relaxed);
+    children().push((key, child));
+    Ok(Spawned {
+        child: TrackedChild { key, id: child_id },
+        stdin,
+        stdout,
+        stderr,
+    })
+}
+
+/// Collect children that exited while their owner was not looking, so none
+/// sits as a zombie. Returns how many are still running.
+pub fn reap() -> usize {
+    let mut children = children();
+    let mut running = 0;
+    for (_, child) in children.iter_mut() {
+        if child.try_wait().ok().flatten().is_none() {
+            running += 1;
+        }
+    }
+    running
+}
+
+/// Kill and reap every registered child. Their handles become inert.
+pub fn kill_all() {
+    let drained: Vec<(u64, Child)> = children().drain(..).collect();
+    if !drained.is_empty() {
+        tracing::info!("Stopping {} child process(es)", drained.len());
+    }
+    for (_, mut child) in drained {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// Run `command` to completion like `Command::output`, but give up after
+/// `timeout` with an `ErrorKind::TimedOut` error, killing the child.
+pub fn output(command: &mut Command, timeout: Duration) -> io::Result<Output> {
+    run(command.stdin(Stdio::null()), None, timeout)
+}
+
+/// `output` with `input` written to the child's stdin, for data that should
+/// not show up in the process list, such as an API key.
+pub fn output_with_input(command: &mut Command, input: Vec<u8>, timeout: Duration) -> io::Result<Output> {
+    run(command.stdin(Stdio::piped()), Some(input), timeout)
+}
+
+fn run(command: &mut Command, input: Option<Vec<u8>>, timeout: Duration) -> io::Result<Output> {
+    let mut spawned = spawn(command.stdout(Stdio::piped()).stderr(Stdio::piped()))?;
+    if let (Some(input), Some(mut stdin)) = (input, spawned.stdin.take()) {
+        // A child that never reads must not hold us past the timeout
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    let started = Instant::now();
+    let deadline = started + timeout;
+    let stdout = read_capped(spawned.stdout);
+    let stderr = read_capped(spawned.stderr);
+
+    let status = loop {
+        if let Some(status) = spawned.child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            tracing::debug!("{} timed out after {:?}", describe(command), timeout);
+            return Err(timed_out(command, timeout));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    // A grandchild can inherit the pipes and keep them open after the child
+    // exits, so the readers get no longer than the rest of the timeout
+    let remaining = || deadline.saturating_duration_since(Instant::now());
+    let stdout = stdout.recv_timeout(remaining()).map_err(|_| timed_out(command, timeout))?;
+    let stderr = stderr.recv_timeout(remaining()).map_err(|_| timed_out(command, timeout))?;
+    tracing::debug!(
+        "{} exited with {} in {}ms, {} bytes out, {} bytes err",
+        describe(command),
+        status,
+        started.elapsed().as_millis(),
+        stdout.len(),
+        stderr.len()
+    );
+    Ok(Output { status, stdout, stderr })
+}
+
+/// The program and its arguments, for a debug trace.
+fn describe(command: &Command) -> String {
+    let mut line = command.get_program().to_string_lossy().into_owned();
+    for arg in command.get_args() {
+        line.push(' ');
+        line.push_str(&arg.to_string_lossy());
+    }
+    line
+}
+
+/// Read a pipe to EOF on its own thread, keeping the first MAX_OUTPUT bytes.
+fn read_capped(pipe: Option<impl Read + Send + 'static>) -> mpsc::Receiver<Vec<u8>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut kept = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let mut buf = [0u8; 8192];
+            loop {
+                match pipe.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        let room = MAX_OUTPUT.saturating_sub(kept.len());
+                        kept.extend_from_slice(&buf[..n.min(room)]);
+                    }
+                }
+            }
+        }
+        let _ = tx.send(kept);
+    });
+    rx
+}
+
+fn timed_out(command: &Command, timeout: Duration) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("{} timed out after {}s", command.get_program().to_string_lossy(), timeout.as_secs()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kills_a_child_that_outlives_its_timeout() {
+        let started = Instant::now();
+        let err = output(Command::new("sleep").arg("30"), Duration::from_millis(200)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let out = output(Command::new("sh").args(["-c", "echo hi; echo oops >&2"]), QUICK).unwrap();
+        assert!(out.status.success());
+        assert_eq!((out.stdout.as_slice(), out.stderr.as_slice()), (&b"hi\n"[..], &b"oops\n"[..]));
+    }
+
+    #[test]
+    fn dropping_a_handle_kills_and_unregisters_the_child() {
+        let spawned = spawn(Command::new("sleep").arg("30")).unwrap();
+        let key = spawned.child.key;
+        assert!(children().iter().any(|(k, _)| *k == key));
+        assert!(spawned.child.try_wait().unwrap().is_none());
+        drop(spawned);
+        assert!(!children().iter().any(|(k, _)| *k == key));
+    }
+}
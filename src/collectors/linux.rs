@@ -1,9 +1,13 @@
 use super::SystemCollector;
-use crate::model::{ContextSwitchInfo, FdInfo, SocketOverviewInfo};
+use crate::model::{
+    aggregate_remotes, ConntrackInfo, ContextSwitchInfo, CpuBreakdown, FdInfo, InterruptInfo, InterruptRate, LinkInfo, NamespaceSocketInfo,
+    ListenerInfo, MemoryBreakdown, MemoryDetail, OomInfo, PressureInfo, ProcessLimits, ProcessPriority, ResourceLimit, SharedMemorySegment, SocketOverviewInfo,
+};
 use sysinfo::Pid;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::path::Path;
 use std::time::Instant;
 
@@ -22,12 +26,43 @@ pub struct LinuxCollector {
     cumulative_net: HashMap<u32, (u64, u64)>,
 
     socket_scan_cache: RefCell<Option<SocketScanCache>>,
+
+    /// Per interface: last seen carrier_changes and when it last moved.
+    carrier_history: HashMap<String, (u64, Option<Instant>)>,
+
+    /// Last seen oom_kill count and when it last increased.
+    oom_history: Option<(u64, Option<Instant>)>,
+
+    /// Previous /proc/interrupts and /proc/softirqs counters.
+    prev_interrupts: Option<(Instant, InterruptCounts, InterruptCounts)>,
+
+    /// Previous aggregate `cpu` line of /proc/stat.
+    prev_cpu_ticks: Option<CpuTicks>,
 }
 
+/// user, nice, system, idle, iowait, irq, softirq and steal ticks.
+type CpuTicks = [u64; 8];
+
+/// (name, device, count per CPU) of each line of /proc/interrupts or /proc/softirqs.
+type InterruptCounts = Vec<(String, String, Vec<u64>)>;
+
+/// `(inode, tcp_state, address)`: the remote address of ESTABLISHED sockets
+/// or the bound local address of LISTEN sockets; None for other states.
+type TcpEntry = (u64, u8, Option<SocketAddr>);
+
+/// Socket inode → (pid, comm) map plus raw TCP entries.
+type SocketScan = (HashMap<u64, (u32, String)>, Vec<TcpEntry>);
+
 struct SocketScanCache {
     scanned_at: Instant,
     socket_pid_map: HashMap<u64, (u32, String)>,
-    tcp_entries: Vec<(u64, u8)>,
+    tcp_entries: Vec<TcpEntry>,
+}
+
+impl Default for LinuxCollector {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl LinuxCollector {
@@ -39,6 +74,10 @@ impl LinuxCollector {
             prev_net_time: None,
             cumulative_net: HashMap::new(),
             socket_scan_cache: RefCell::new(None),
+            carrier_history: HashMap::new(),
+            oom_history: None,
+            prev_interrupts: None,
+            prev_cpu_ticks: None,
         }
     }
 
@@ -63,11 +102,10 @@ impl LinuxCollector {
             // 12  io_ticks (ms spent doing I/Os)
             if parts.len() >= 13 {
                 let name = parts[2];
-                if is_block_device(name) {
-                    if let Ok(ticks) = parts[12].parse::<u64>() {
+                if is_block_device(name)
+                    && let Ok(ticks) = parts[12].parse::<u64>() {
                         result.insert(name.to_string(), ticks);
                     }
-                }
             }
         }
         result
@@ -136,11 +174,9 @@ impl LinuxCollector {
                     let t = target.to_string_lossy();
                     if let Some(inode_str) =
                         t.strip_prefix("socket:[").and_then(|s| s.strip_suffix(']'))
-                    {
-                        if let Ok(inode) = inode_str.parse::<u64>() {
+                        && let Ok(inode) = inode_str.parse::<u64>() {
                             map.insert(inode, (pid, comm.clone()));
                         }
-                    }
                 }
             }
         }
@@ -148,19 +184,23 @@ impl LinuxCollector {
     }
 
     /// Parse /proc/net/tcp and /proc/net/tcp6.
-    /// Returns Vec<(inode, tcp_state)>.
-    fn read_tcp_entries() -> Vec<(u64, u8)> {
+    fn read_tcp_entries() -> Vec<TcpEntry> {
         let mut entries = Vec::new();
         for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
             if let Ok(content) = fs::read_to_string(path) {
                 for line in content.lines().skip(1) {
                     let parts: Vec<&str> = line.split_whitespace().collect();
-                    // col 3 = state (hex), col 9 = inode
+                    // col 1 = local_address, col 2 = rem_address, col 3 = state (hex), col 9 = inode
                     if parts.len() >= 10 {
                         let state = u8::from_str_radix(parts[3], 16).unwrap_or(0);
                         let inode = parts[9].parse::<u64>().unwrap_or(0);
                         if inode > 0 {
-                            entries.push((inode, state));
+                            let address = match state {
+                                0x01 => parse_proc_net_addr(parts[2]),
+                                0x0A => parse_proc_net_addr(parts[1]),
+                                _ => None,
+                            };
+                            entries.push((inode, state, address));
                         }
                     }
                 }
@@ -173,13 +213,12 @@ impl LinuxCollector {
         std::time::Duration::from_secs(1)
     }
 
-    fn get_socket_scan(&self) -> (HashMap<u64, (u32, String)>, Vec<(u64, u8)>) {
+    fn get_socket_scan(&self) -> SocketScan {
         let now = Instant::now();
-        if let Some(cache) = self.socket_scan_cache.borrow().as_ref() {
-            if now.duration_since(cache.scanned_at) < Self::socket_scan_ttl() {
+        if let Some(cache) = self.socket_scan_cache.borrow().as_ref()
+            && now.duration_since(cache.scanned_at) < Self::socket_scan_ttl() {
                 return (cache.socket_pid_map.clone(), cache.tcp_entries.clone());
             }
-        }
 
         let socket_pid_map = Self::build_socket_pid_map();
         let tcp_entries = Self::read_tcp_entries();
@@ -192,6 +231,290 @@ impl LinuxCollector {
     }
 }
 
+/// TCP sockets in the network namespace of `pid`, read from
+/// /proc/<pid>/net/tcp{,6}. Those files always describe the namespace of
+/// the process, so this sees inside bridge-networked containers.
+pub fn read_namespace_sockets(pid: u32) -> Result<NamespaceSocketInfo, String> {
+    let mut tables = Vec::new();
+    for file in ["tcp", "tcp6"] {
+        let path = format!("/proc/{}/net/{}", pid, file);
+        match fs::read_to_string(&path) {
+            Ok(content) => tables.push(content),
+            // tcp6 is absent when IPv6 is disabled
+            Err(_) if file == "tcp6" => {}
+            Err(e) => return Err(format!("Failed to read {}: {}", path, e)),
+        }
+    }
+    Ok(parse_namespace_sockets(&tables))
+}
+
+fn parse_namespace_sockets(tables: &[String]) -> NamespaceSocketInfo {
+    let mut info = NamespaceSocketInfo::default();
+    for content in tables {
+        for line in content.lines().skip(1) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            // col 1 = local_address, col 3 = state (hex)
+            if parts.len() < 4 {
+                continue;
+            }
+            match u8::from_str_radix(parts[3], 16).unwrap_or(0) {
+                0x01 => info.established += 1,
+                0x06 => info.time_wait += 1,
+                0x08 => info.close_wait += 1,
+                0x0A => {
+                    info.listen += 1;
+                    if let Some(local) = parse_proc_net_addr(parts[1]) {
+                        info.listen_ports.push(local.port());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    info.listen_ports.sort_unstable();
+    info.listen_ports.dedup();
+    info
+}
+
+/// The nice value from /proc/[pid]/stat: the 19th field, counted after the
+/// parenthesised command, which may itself contain spaces.
+fn parse_stat_nice(stat: &str) -> Option<i32> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(16)?.parse().ok()
+}
+
+/// Parse the rows of /proc/[pid]/limits sitrep shows, e.g.
+/// "Max open files            1024                 524288               files".
+/// "unlimited" and missing rows both come back as None.
+fn parse_process_limits(content: &str) -> ProcessLimits {
+    let limit = |name: &str| {
+        let Some(rest) = content.lines().find_map(|l| l.strip_prefix(name)) else {
+            return ResourceLimit::default();
+        };
+        let mut values = rest.split_whitespace().map(|v| v.parse().ok());
+        ResourceLimit {
+            soft: values.next().flatten(),
+            hard: values.next().flatten(),
+        }
+    };
+    ProcessLimits {
+        nofile: limit("Max open files"),
+        nproc: limit("Max processes"),
+        memlock: limit("Max locked memory"),
+        locked_bytes: 0,
+    }
+}
+
+/// Name the unit behind a /proc/[pid]/cgroup file: a container as
+/// "container 3f2a1b9c8d7e" (docker, podman, containerd and CRI-O scopes or
+/// bare 64-hex cgroups), otherwise the innermost .service or .scope, e.g.
+/// "nginx.service". Uses the cgroup v2 line, or the v1 systemd hierarchy.
+fn parse_cgroup_unit(content: &str) -> Option<String> {
+    let path = content
+        .lines()
+        .find_map(|l| l.strip_prefix("0::"))
+        .or_else(|| content.lines().find_map(|l| l.split_once(":name=systemd:").map(|(_, p)| p)))?;
+    let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+    if parts.is_empty() {
+        return Some("kernel".to_string());
+    }
+    let is_id = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    for part in parts.iter().rev() {
+        let id = part
+            .strip_suffix(".scope")
+            .and_then(|s| s.rsplit_once('-'))
+            .map(|(_, id)| id)
+            .unwrap_or(part);
+        if is_id(id) {
+            return Some(format!("container {}", &id[..12]));
+        }
+    }
+    let unit = parts
+        .iter()
+        .rev()
+        .find(|p| p.ends_with(".service") || p.ends_with(".scope"))
+        .unwrap_or(&parts[parts.len() - 1]);
+    Some(unit.to_string())
+}
+
+/// A "Key:   123 kB" value from /proc/[pid]/status, in kB.
+fn parse_status_kb(status: &str, key: &str) -> Option<u64> {
+    let rest = status.lines().find_map(|l| l.strip_prefix(key))?;
+    rest.split_whitespace().next()?.parse().ok()
+}
+
+/// The HugeTLB and THP counters of /proc/meminfo.
+fn parse_meminfo_detail(meminfo: &str) -> MemoryDetail {
+    let value = |key: &str| parse_status_kb(meminfo, key).unwrap_or(0);
+    MemoryDetail {
+        shmem_kb: value("Shmem:"),
+        hugepage_size_kb: value("Hugepagesize:"),
+        hugepages_total: value("HugePages_Total:"),
+        hugepages_free: value("HugePages_Free:"),
+        hugepages_reserved: value("HugePages_Rsvd:"),
+        hugepages_surplus: value("HugePages_Surp:"),
+        anon_huge_kb: value("AnonHugePages:"),
+        shmem_huge_kb: value("ShmemHugePages:"),
+        ..Default::default()
+    }
+}
+
+/// Buffers, Cached, Slab and SReclaimable from /proc/meminfo, in bytes.
+fn parse_meminfo_breakdown(meminfo: &str) -> Option<MemoryBreakdown> {
+    let value = |key: &str| parse_status_kb(meminfo, key).map(|kb| kb * 1024);
+    Some(MemoryBreakdown {
+        buffers: value("Buffers:")?,
+        cached: value("Cached:")?,
+        slab: value("Slab:").unwrap_or(0),
+        slab_reclaimable: value("SReclaimable:").unwrap_or(0),
+    })
+}
+
+/// The bracketed choice of a sysfs mode file, e.g. "always [madvise] never".
+fn parse_sysfs_choice(content: &str) -> Option<String> {
+    let start = content.find('[')?;
+    let end = content[start..].find(']')?;
+    Some(content[start + 1..start + end].to_string())
+}
+
+/// /proc/sysvipc/shm rows: key shmid perms size cpid lpid nattch ...
+/// Returns (key, shmid, bytes, creator pid, attached).
+fn parse_sysvipc_shm(content: &str) -> Vec<(i64, u64, u64, u32, u32)> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|l| {
+            let cols: Vec<&str> = l.split_whitespace().collect();
+            Some((
+                cols.first()?.parse().ok()?,
+                cols.get(1)?.parse().ok()?,
+                cols.get(3)?.parse().ok()?,
+                cols.get(4)?.parse().ok()?,
+                cols.get(6)?.parse().ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Ticks of the aggregate `cpu` line of /proc/stat. Guest time is already
+/// included in user, so the columns after steal are ignored.
+fn parse_cpu_ticks(stat: &str) -> Option<CpuTicks> {
+    let line = stat.lines().find(|l| l.starts_with("cpu "))?;
+    let mut ticks = [0u64; 8];
+    let mut fields = line.split_whitespace().skip(1);
+    for (i, tick) in ticks.iter_mut().enumerate() {
+        match fields.next().map(str::parse) {
+            Some(Ok(n)) => *tick = n,
+            Some(Err(_)) => return None,
+            // steal arrived in 2.6.11, iowait in 2.6; older columns are zero
+            None if i >= 4 => break,
+            None => return None,
+        }
+    }
+    Some(ticks)
+}
+
+/// Percent of the ticks between two samples spent in each state.
+fn cpu_breakdown(prev: &CpuTicks, cur: &CpuTicks) -> Option<CpuBreakdown> {
+    let delta: Vec<f64> = cur.iter().zip(prev).map(|(c, p)| c.saturating_sub(*p) as f64).collect();
+    let total: f64 = delta.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let pct = |ticks: f64| ticks * 100.0 / total;
+    Some(CpuBreakdown {
+        user: pct(delta[0] + delta[1]),
+        system: pct(delta[2] + delta[5] + delta[6]),
+        idle: pct(delta[3]),
+        iowait: pct(delta[4]),
+        steal: pct(delta[7]),
+    })
+}
+
+/// Whether a kernel release like "4.18.0-553.el8.x86_64" predates 5.6,
+/// where /dev/random stopped blocking after boot.
+fn entropy_blocks(release: &str) -> bool {
+    let mut parts = release.trim().split(['.', '-']).map(|p| p.parse::<u32>().ok());
+    match (parts.next().flatten(), parts.next().flatten()) {
+        (Some(major), Some(minor)) => (major, minor) < (5, 6),
+        _ => false,
+    }
+}
+
+/// Counters of /proc/interrupts or /proc/softirqs. The header names one
+/// column per CPU; lines without a count for every CPU (ERR, MIS) are skipped.
+fn parse_interrupt_counts(content: &str) -> InterruptCounts {
+    let mut lines = content.lines();
+    let cpus = lines.next().map_or(0, |header| header.split_whitespace().count());
+    lines
+        .filter_map(|line| {
+            let (name, rest) = line.split_once(':')?;
+            let mut fields = rest.split_whitespace().peekable();
+            let mut counts = Vec::with_capacity(cpus);
+            while counts.len() < cpus {
+                let Some(count) = fields.peek().and_then(|f| f.parse().ok()) else { break };
+                counts.push(count);
+                fields.next();
+            }
+            if counts.len() != cpus {
+                return None;
+            }
+            let device = fields.collect::<Vec<_>>().join(" ");
+            Some((name.trim().to_string(), device, counts))
+        })
+        .collect()
+}
+
+/// Per-second rates between two readings of the same counters. Sources whose
+/// CPU count changed (hotplug) are left out.
+fn interrupt_rates(prev: &InterruptCounts, cur: &InterruptCounts, secs: f64) -> Vec<InterruptRate> {
+    cur.iter()
+        .filter_map(|(name, device, counts)| {
+            let (_, _, before) = prev.iter().find(|(n, _, _)| n == name)?;
+            if before.len() != counts.len() {
+                return None;
+            }
+            Some(InterruptRate {
+                name: name.clone(),
+                device: device.clone(),
+                per_cpu: counts.iter().zip(before).map(|(c, b)| c.saturating_sub(*b) as f64 / secs).collect(),
+            })
+        })
+        .collect()
+}
+
+/// The `avg10` value of the "some" line of a /proc/pressure file.
+fn parse_psi_some_avg10(content: &str) -> Option<f64> {
+    let line = content.lines().find(|l| l.starts_with("some "))?;
+    line.split_whitespace()
+        .find_map(|field| field.strip_prefix("avg10="))?
+        .parse()
+        .ok()
+}
+
+/// Parse a `/proc/net/tcp{,6}` address such as `0100007F:1F90`. The kernel
+/// prints each 32-bit word of the address in host byte order.
+fn parse_proc_net_addr(field: &str) -> Option<SocketAddr> {
+    let (addr_hex, port_hex) = field.split_once(':')?;
+    let port = u16::from_str_radix(port_hex, 16).ok()?;
+    let word = |i: usize| -> Option<[u8; 4]> {
+        let hex = addr_hex.get(i * 8..i * 8 + 8)?;
+        Some(u32::from_str_radix(hex, 16).ok()?.to_ne_bytes())
+    };
+    let ip = match addr_hex.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(word(0)?)),
+        32 => {
+            let mut octets = [0u8; 16];
+            for i in 0..4 {
+                octets[i * 4..i * 4 + 4].copy_from_slice(&word(i)?);
+            }
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
 // ── block-device detection ──────────────────────────────────────────────
 
 /// Return `true` if `name` looks like a whole block device rather than a
@@ -290,8 +613,8 @@ impl SystemCollector for LinuxCollector {
             }
         }
 
-        // ── per-process: top 5 FD consumers ──
-        let mut counts: Vec<(String, u64)> = Vec::new();
+        // ── per-process: every readable process, top 5 FD consumers ──
+        let mut counts: Vec<(u32, String, u64)> = Vec::new();
         if let Ok(entries) = fs::read_dir("/proc") {
             for entry in entries.flatten() {
                 let path = entry.path();
@@ -306,6 +629,9 @@ impl SystemCollector for LinuxCollector {
                     continue;
                 }
 
+                let Ok(pid) = fname.parse::<u32>() else {
+                    continue;
+                };
                 let fd_path = path.join("fd");
                 if let Ok(fd_entries) = fs::read_dir(&fd_path) {
                     let count = fd_entries.count() as u64;
@@ -313,17 +639,97 @@ impl SystemCollector for LinuxCollector {
                         let name = fs::read_to_string(path.join("comm"))
                             .map(|s| s.trim().to_string())
                             .unwrap_or(fname);
-                        counts.push((name, count));
+                        counts.push((pid, name, count));
                     }
                 }
             }
         }
-        counts.sort_by(|a, b| b.1.cmp(&a.1));
-        counts.truncate(5);
-        info.top_processes = counts;
+        counts.sort_by_key(|entry| std::cmp::Reverse(entry.2));
+        info.top_processes = counts.iter().take(5).map(|(_, name, count)| (name.clone(), *count)).collect();
+        info.per_process = counts;
         info
     }
 
+    fn get_memory_breakdown(&self) -> Option<MemoryBreakdown> {
+        parse_meminfo_breakdown(&fs::read_to_string("/proc/meminfo").ok()?)
+    }
+
+    /// HugeTLB/THP counters from /proc/meminfo and the THP sysfs knobs, plus
+    /// SysV segments from /proc/sysvipc/shm and POSIX ones in /dev/shm.
+    fn get_memory_detail(&self) -> Option<MemoryDetail> {
+        use std::os::unix::fs::MetadataExt;
+
+        let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+        let mut detail = parse_meminfo_detail(&meminfo);
+        let thp = Path::new("/sys/kernel/mm/transparent_hugepage");
+        detail.thp_enabled = fs::read_to_string(thp.join("enabled")).ok().and_then(|c| parse_sysfs_choice(&c));
+        detail.thp_defrag = fs::read_to_string(thp.join("defrag")).ok().and_then(|c| parse_sysfs_choice(&c));
+
+        let mut segments: Vec<SharedMemorySegment> = fs::read_to_string("/proc/sysvipc/shm")
+            .map(|c| parse_sysvipc_shm(&c))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|&(_, _, bytes, _, _)| bytes >= MemoryDetail::MIN_SEGMENT_BYTES)
+            .map(|(key, shmid, bytes, cpid, attached)| {
+                let owner = fs::read_to_string(format!("/proc/{}/comm", cpid))
+                    .map(|comm| format!("{}[{}]", comm.trim(), cpid))
+                    .unwrap_or_else(|_| format!("[{}] exited", cpid));
+                SharedMemorySegment {
+                    kind: "sysv".to_string(),
+                    name: format!("0x{:08x} id {}", key as u32, shmid),
+                    bytes,
+                    attached: Some(attached),
+                    owner: Some(owner),
+                }
+            })
+            .collect();
+        if let Ok(entries) = fs::read_dir("/dev/shm") {
+            for entry in entries.flatten() {
+                let Ok(meta) = entry.metadata() else { continue };
+                // Allocated size: POSIX segments are often sparse
+                let bytes = meta.blocks() * 512;
+                if meta.is_file() && bytes >= MemoryDetail::MIN_SEGMENT_BYTES {
+                    segments.push(SharedMemorySegment {
+                        kind: "posix".to_string(),
+                        name: entry.file_name().to_string_lossy().into_owned(),
+                        bytes,
+                        attached: None,
+                        owner: None,
+                    });
+                }
+            }
+        }
+        segments.sort_by_key(|s| std::cmp::Reverse(s.bytes));
+        segments.truncate(MemoryDetail::TOP_SEGMENTS);
+        detail.segments = segments;
+        Some(detail)
+    }
+
+    fn get_process_unit(&self, pid: u32) -> Option<String> {
+        parse_cgroup_unit(&fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?)
+    }
+
+    fn get_process_limits(&self, pid: u32) -> Option<ProcessLimits> {
+        let content = fs::read_to_string(format!("/proc/{}/limits", pid)).ok()?;
+        let mut limits = parse_process_limits(&content);
+        if let Ok(status) = fs::read_to_string(format!("/proc/{}/status", pid)) {
+            limits.locked_bytes = parse_status_kb(&status, "VmLck:").unwrap_or(0) * 1024;
+        }
+        Some(limits)
+    }
+
+    fn get_process_priority(&self, pid: u32) -> Option<ProcessPriority> {
+        let nice = parse_stat_nice(&fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?)?;
+        let cpu_weight = fs::read_to_string(format!("/proc/{}/cgroup", pid))
+            .ok()
+            .and_then(|cgroup| {
+                let path = cgroup.lines().find_map(|l| l.strip_prefix("0::"))?.trim().to_string();
+                fs::read_to_string(format!("/sys/fs/cgroup{}/cpu.weight", path.trim_end_matches('/'))).ok()
+            })
+            .and_then(|w| w.trim().parse().ok());
+        Some(ProcessPriority { nice, cpu_weight })
+    }
+
     /// Socket overview from /proc/net/tcp{,6} with per-process connection
     /// counts derived by mapping socket inodes back to owning PIDs.
     fn get_socket_stats(&self) -> SocketOverviewInfo {
@@ -332,7 +738,7 @@ impl SystemCollector for LinuxCollector {
         let (socket_pid_map, tcp_entries) = self.get_socket_scan();
 
         // ── aggregate state counts ──
-        for &(_inode, st) in &tcp_entries {
+        for &(_inode, st, _) in &tcp_entries {
             match st {
                 0x01 => info.established += 1,
                 0x04 | 0x05 => info.fin_wait += 1,
@@ -346,24 +752,169 @@ impl SystemCollector for LinuxCollector {
         // ── top processes by active connection count ──
         let mut proc_counts: HashMap<String, u32> = HashMap::new();
 
-        for &(inode, st) in &tcp_entries {
+        for &(inode, st, _) in &tcp_entries {
             // Count ESTABLISHED, CLOSE_WAIT, LISTEN — the states most
             // relevant for triage (same filter the macOS lsof path uses).
-            if matches!(st, 0x01 | 0x08 | 0x0A) {
-                if let Some((_pid, name)) = socket_pid_map.get(&inode) {
+            if matches!(st, 0x01 | 0x08 | 0x0A)
+                && let Some((_pid, name)) = socket_pid_map.get(&inode) {
                     *proc_counts.entry(name.clone()).or_insert(0) += 1;
                 }
-            }
         }
 
         let mut top: Vec<(String, u32)> = proc_counts.into_iter().collect();
-        top.sort_by(|a, b| b.1.cmp(&a.1));
+        top.sort_by_key(|entry| std::cmp::Reverse(entry.1));
         top.truncate(5);
         info.top_processes = top;
 
+        info.remotes = aggregate_remotes(
+            tcp_entries.iter().filter(|&&(_, st, _)| st == 0x01).filter_map(|&(_, _, remote)| remote),
+        );
+        info.listeners = tcp_entries
+            .iter()
+            .filter(|&&(_, st, _)| st == 0x0A)
+            .filter_map(|&(inode, _, local)| {
+                Some(ListenerInfo {
+                    address: local?,
+                    process: socket_pid_map.get(&inode).map(|(_, name)| name.clone()),
+                })
+            })
+            .collect();
+
         info
     }
 
+    /// Conntrack usage from /proc/sys/net/netfilter. The files only exist
+    /// while the nf_conntrack module is loaded.
+    fn get_conntrack(&self) -> Option<ConntrackInfo> {
+        let read = |name: &str| -> Option<u64> {
+            let path = format!("/proc/sys/net/netfilter/{}", name);
+            fs::read_to_string(path).ok()?.trim().parse().ok()
+        };
+        Some(ConntrackInfo {
+            count: read("nf_conntrack_count")?,
+            max: read("nf_conntrack_max")?,
+        })
+    }
+
+    /// Link state from /sys/class/net/<iface>. `speed` reads -1 or fails
+    /// for virtual interfaces and links without carrier.
+    fn get_link_stats(&mut self) -> Vec<LinkInfo> {
+        let Ok(entries) = fs::read_dir("/sys/class/net") else {
+            return Vec::new();
+        };
+        let now = Instant::now();
+        let mut links = Vec::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name == "lo" {
+                continue;
+            }
+            let dir = entry.path();
+            let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+            let up = read("operstate").is_some_and(|s| s.trim() == "up");
+            let speed_mbps = read("speed")
+                .and_then(|s| s.trim().parse::<i64>().ok())
+                .filter(|s| *s > 0)
+                .map(|s| s as u32);
+            let carrier_changes = read("carrier_changes")
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0);
+
+            let history = self.carrier_history.entry(name.clone()).or_insert((carrier_changes, None));
+            if carrier_changes != history.0 {
+                *history = (carrier_changes, Some(now));
+            }
+
+            links.push(LinkInfo {
+                name,
+                up,
+                speed_mbps,
+                physical: dir.join("device").exists(),
+                carrier_changes,
+                last_flap_secs: history.1.map(|t| now.duration_since(t).as_secs()),
+            });
+        }
+        links.sort_by(|a, b| a.name.cmp(&b.name));
+        links
+    }
+
+    /// PSI from /proc/pressure (kernel 4.20+, CONFIG_PSI) and the runnable
+    /// count from the fourth /proc/loadavg field ("running/total").
+    fn get_pressure(&self) -> PressureInfo {
+        let psi = |resource: &str| {
+            fs::read_to_string(format!("/proc/pressure/{}", resource))
+                .ok()
+                .and_then(|content| parse_psi_some_avg10(&content))
+        };
+        let runnable = fs::read_to_string("/proc/loadavg").ok().and_then(|content| {
+            content.split_whitespace().nth(3)?.split('/').next()?.parse().ok()
+        });
+        PressureInfo {
+            cpu: psi("cpu"),
+            memory: psi("memory"),
+            io: psi("io"),
+            runnable,
+        }
+    }
+
+    fn get_cpu_breakdown(&mut self) -> Option<CpuBreakdown> {
+        let ticks = parse_cpu_ticks(&fs::read_to_string("/proc/stat").ok()?)?;
+        let prev = self.prev_cpu_ticks.replace(ticks)?;
+        cpu_breakdown(&prev, &ticks)
+    }
+
+    /// OOM kills from the `oom_kill` counter in /proc/vmstat (kernel 4.13+).
+    fn get_oom_info(&mut self) -> Option<OomInfo> {
+        let vmstat = fs::read_to_string("/proc/vmstat").ok()?;
+        let total_kills: u64 = vmstat
+            .lines()
+            .find_map(|l| l.strip_prefix("oom_kill "))?
+            .trim()
+            .parse()
+            .ok()?;
+        let now = Instant::now();
+        let (prev, last_kill) = self.oom_history.get_or_insert((total_kills, None));
+        if total_kills > *prev {
+            *last_kill = Some(now);
+        }
+        *prev = total_kills;
+        Some(OomInfo {
+            total_kills,
+            last_kill_secs: last_kill.map(|t| now.duration_since(t).as_secs()),
+        })
+    }
+
+    /// entropy_avail on kernels before 5.6. Later kernels stop blocking once
+    /// the pool is initialised (and from 5.18 always report 256), so the
+    /// count says nothing there.
+    fn get_entropy(&self) -> Option<u64> {
+        if !entropy_blocks(&fs::read_to_string("/proc/sys/kernel/osrelease").ok()?) {
+            return None;
+        }
+        fs::read_to_string("/proc/sys/kernel/random/entropy_avail").ok()?.trim().parse().ok()
+    }
+
+    fn get_interrupts(&mut self) -> Option<InterruptInfo> {
+        let irqs = parse_interrupt_counts(&fs::read_to_string("/proc/interrupts").ok()?);
+        let softirqs = fs::read_to_string("/proc/softirqs").map(|c| parse_interrupt_counts(&c)).unwrap_or_default();
+        let now = Instant::now();
+        let prev = self.prev_interrupts.replace((now, irqs, softirqs));
+        let (then, prev_irqs, prev_softirqs) = prev?;
+        let secs = now.duration_since(then).as_secs_f64();
+        if secs <= 0.0 {
+            return None;
+        }
+        let (_, irqs, softirqs) = self.prev_interrupts.as_ref()?;
+        let mut top = interrupt_rates(&prev_irqs, irqs, secs);
+        top.retain(|r| r.total() > 0.0);
+        top.sort_by(|a, b| b.total().total_cmp(&a.total()));
+        top.truncate(InterruptInfo::TOP_IRQS);
+        Some(InterruptInfo {
+            irqs: top,
+            softirqs: interrupt_rates(&prev_softirqs, softirqs, secs),
+        })
+    }
+
     /// Context-switch statistics.
     ///
     /// * System-wide total from `/proc/stat` (`ctxt` line).
@@ -420,7 +971,7 @@ impl SystemCollector for LinuxCollector {
             }
         }
 
-        counts.sort_by(|a, b| b.1.cmp(&a.1));
+        counts.sort_by_key(|entry| std::cmp::Reverse(entry.1));
         counts.truncate(5);
         info.top_processes = counts;
         info
@@ -464,7 +1015,7 @@ impl SystemCollector for LinuxCollector {
                     let mut pid_conn_count: HashMap<u32, u64> = HashMap::new();
                     let mut total_conns: u64 = 0;
 
-                    for &(inode, state) in &tcp_entries {
+                    for &(inode, state, _) in &tcp_entries {
                         if state == 0x01 {
                             // ESTABLISHED
                             if let Some(&(pid, _)) = socket_pid_map.get(&inode) {
@@ -504,3 +1055,161 @@ impl SystemCollector for LinuxCollector {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cpu_breakdown, entropy_blocks, interrupt_rates, parse_cgroup_unit, parse_cpu_ticks, parse_interrupt_counts, parse_meminfo_breakdown, parse_meminfo_detail, parse_namespace_sockets, parse_proc_net_addr, parse_process_limits, parse_psi_some_avg10, parse_stat_nice,
+        parse_status_kb, parse_sysfs_choice, parse_sysvipc_shm,
+    };
+
+    #[test]
+    fn interrupt_rates_per_cpu() {
+        let before = parse_interrupt_counts(
+            "           CPU0       CPU1\n\
+             \x20 24:       1000          0  IR-PCI-MSI 524288-edge      eth0-TxRx-0\n\
+             LOC:      50000      50000   Local timer interrupts\n\
+             ERR:          0\n",
+        );
+        assert_eq!(before.len(), 2);
+        assert_eq!(before[0], ("24".into(), "IR-PCI-MSI 524288-edge eth0-TxRx-0".into(), vec![1000, 0]));
+        let after = parse_interrupt_counts(
+            "           CPU0       CPU1\n\
+             \x20 24:       7000          0  IR-PCI-MSI 524288-edge      eth0-TxRx-0\n\
+             LOC:      51000      50500   Local timer interrupts\n",
+        );
+        let rates = interrupt_rates(&before, &after, 2.0);
+        assert_eq!(rates[0].per_cpu, vec![3000.0, 0.0]);
+        assert_eq!(rates[0].busiest_cpu(), Some((0, 1.0)));
+        assert_eq!(rates[1].total(), 750.0);
+
+        let softirqs = |counts: &str| parse_interrupt_counts(&format!("    CPU0 CPU1\n    NET_RX: {}\n", counts));
+        let net_rx = interrupt_rates(&softirqs("100 100"), &softirqs("9600 200"), 1.0);
+        let info = crate::model::InterruptInfo { irqs: rates, softirqs: net_rx };
+        let (cpu, share, total) = info.network_hotspot().unwrap();
+        assert_eq!((cpu, total), (0, 9600.0));
+        assert!(share > 0.98);
+    }
+
+    #[test]
+    fn parses_psi_some_avg10() {
+        let content = "some avg10=3.24 avg60=4.03 avg300=3.89 total=90726474\n\
+                       full avg10=0.50 avg60=0.00 avg300=0.00 total=0\n";
+        assert_eq!(parse_psi_some_avg10(content), Some(3.24));
+        assert_eq!(parse_psi_some_avg10(""), None);
+    }
+
+    #[test]
+    fn parses_process_limits() {
+        let content = "Limit                     Soft Limit           Hard Limit           Units\n\
+                       Max locked memory         8388608              8388608              bytes\n\
+                       Max open files            1024                 524288               files\n\
+                       Max processes             unlimited            unlimited            processes\n";
+        let limits = parse_process_limits(content);
+        assert_eq!(limits.nofile.soft, Some(1024));
+        assert_eq!(limits.nofile.hard, Some(524288));
+        assert_eq!(limits.nproc.soft, None);
+        assert_eq!(limits.memlock.hard, Some(8388608));
+        assert_eq!(parse_status_kb("Name:\tpostgres\nVmLck:\t     64 kB\n", "VmLck:"), Some(64));
+        let stat = "4242 (tsdb (compact)) S 1 4242 4242 0 -1 4194560 1200 0 0 0 50 20 0 0 30 10 8 0 9000 1000000 300 0";
+        assert_eq!(parse_stat_nice(stat), Some(10));
+        assert_eq!(parse_stat_nice("garbage"), None);
+    }
+
+    #[test]
+    fn names_units_and_containers_from_cgroups() {
+        let id = "3f2a1b9c8d7e0011223344556677889900aabbccddeeff001122334455667788";
+        let unit = |path: &str| parse_cgroup_unit(&format!("0::{}\n", path));
+        assert_eq!(unit("/system.slice/nginx.service").as_deref(), Some("nginx.service"));
+        assert_eq!(unit(&format!("/system.slice/docker-{}.scope", id)).as_deref(), Some("container 3f2a1b9c8d7e"));
+        assert_eq!(
+            unit(&format!("/kubepods.slice/kubepods-burstable.slice/cri-containerd-{}.scope", id)).as_deref(),
+            Some("container 3f2a1b9c8d7e")
+        );
+        assert_eq!(
+            unit("/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox-4242.scope").as_deref(),
+            Some("app-firefox-4242.scope")
+        );
+        assert_eq!(unit("/").as_deref(), Some("kernel"));
+        let v1 = format!("12:pids:/docker/{}\n1:name=systemd:/docker/{}\n", id, id);
+        assert_eq!(parse_cgroup_unit(&v1).as_deref(), Some("container 3f2a1b9c8d7e"));
+    }
+
+    #[test]
+    fn parses_hugepages_and_shared_memory() {
+        let meminfo = "MemTotal:       16303412 kB\n\
+                       Shmem:            812344 kB\n\
+                       AnonHugePages:    204800 kB\n\
+                       ShmemHugePages:        0 kB\n\
+                       HugePages_Total:     512\n\
+                       HugePages_Free:      100\n\
+                       HugePages_Rsvd:       20\n\
+                       HugePages_Surp:        0\n\
+                       Hugepagesize:       2048 kB\n";
+        let detail = parse_meminfo_detail(meminfo);
+        assert_eq!(detail.shmem_kb, 812344);
+        assert_eq!(detail.hugepages_free, 100);
+        assert_eq!(detail.hugetlb_kb(), 512 * 2048);
+        assert_eq!(parse_meminfo_breakdown(meminfo), None);
+        let breakdown =
+            parse_meminfo_breakdown("Buffers:  1024 kB\nCached:  4096 kB\nSlab:  2048 kB\nSReclaimable:  1536 kB\n")
+                .unwrap();
+        assert_eq!(breakdown.cached, 4096 * 1024);
+        assert_eq!(breakdown.slab_reclaimable, 1536 * 1024);
+        assert_eq!(parse_sysfs_choice("always [madvise] never\n").as_deref(), Some("madvise"));
+
+        let shm = "       key      shmid perms                  size  cpid  lpid nattch   uid   gid  cuid  cgid\n\
+                    5432001      32768   600             150994944   812   845      6   999   999   999   999\n";
+        assert_eq!(parse_sysvipc_shm(shm), vec![(5432001, 32768, 150994944, 812, 6)]);
+    }
+
+    #[test]
+    fn namespace_sockets_count_states_and_listen_ports() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue\n\
+             0: 00000000:0050 00000000:0000 0A 00000000:00000000\n\
+             1: 0100007F:0050 0100007F:C350 01 00000000:00000000\n\
+             2: 0100007F:0050 0100007F:C351 06 00000000:00000000\n"
+            .to_string();
+        let tcp6 = "  sl  local_address\n\
+             0: 00000000000000000000000000000000:0050 00000000000000000000000000000000:0000 0A 0\n"
+            .to_string();
+        let info = parse_namespace_sockets(&[tcp, tcp6]);
+        assert_eq!((info.established, info.listen, info.time_wait), (1, 2, 1));
+        assert_eq!(info.listen_ports, vec![80]);
+        assert_eq!(info.summary(), "EST 1 LISTEN 2 TW 1 CW 0, listening on 80");
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn parses_proc_net_tcp_addresses() {
+        assert_eq!(
+            parse_proc_net_addr("0100007F:1F90"),
+            Some("127.0.0.1:8080".parse().unwrap())
+        );
+        assert_eq!(
+            parse_proc_net_addr("0000000000000000FFFF00000501A8C0:01BB"),
+            Some("[::ffff:192.168.1.5]:443".parse().unwrap())
+        );
+        assert_eq!(parse_proc_net_addr("zz"), None);
+    }
+
+    #[test]
+    fn entropy_only_matters_before_5_6() {
+        assert!(entropy_blocks("3.10.0-1160.el7.x86_64"));
+        assert!(entropy_blocks("5.4.0-150-generic\n"));
+        assert!(!entropy_blocks("5.6.0"));
+        assert!(!entropy_blocks("6.8.0-45-generic"));
+        assert!(!entropy_blocks("garbage"));
+    }
+
+    #[test]
+    fn splits_cpu_time_between_samples() {
+        let before = parse_cpu_ticks("cpu  1000 100 400 8000 200 50 50 0 0 0\ncpu0 500 50 200 4000 100 25 25 0 0 0\n").unwrap();
+        let after = parse_cpu_ticks("cpu  1500 100 600 8200 400 50 100 100 0 0\n").unwrap();
+        let b = cpu_breakdown(&before, &after).unwrap();
+        assert_eq!((b.user, b.system, b.iowait, b.steal, b.idle), (40.0, 20.0, 16.0, 8.0, 16.0));
+        assert_eq!(cpu_breakdown(&after, &after), None);
+        // A 2.6.0 kernel has no steal column
+        assert_eq!(parse_cpu_ticks("cpu  1 2 3 4 5 6 7\n"), Some([1, 2, 3, 4, 5, 6, 7, 0]));
+    }
+}
@@ -1,9 +1,12 @@
 use super::SystemCollector;
+use crate::cmd;
 use crate::model::{
-    FdInfo, SocketOverviewInfo, ContextSwitchInfo
+    aggregate_remotes, ConntrackInfo, CpuBreakdown, FdInfo, InterruptInfo, LinkInfo, ListenerInfo, MemoryBreakdown, MemoryDetail, OomInfo, PressureInfo, ProcessLimits, ProcessPriority,
+    SocketOverviewInfo, ContextSwitchInfo
 };
 use sysinfo::Pid;
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
@@ -23,6 +26,12 @@ struct MacCommandCache {
     context_switches: Option<(Instant, ContextSwitchInfo)>,
 }
 
+impl Default for MacCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MacCollector {
     pub fn new() -> Self {
         let cache: Arc<Mutex<HashMap<Pid, (u64, u64)>>> = Arc::new(Mutex::new(HashMap::new()));
@@ -69,11 +78,10 @@ impl MacCollector {
         compute: impl FnOnce() -> T,
     ) -> T {
         let now = Instant::now();
-        if let Some((cached_at, value)) = slot.as_ref() {
-            if now.duration_since(*cached_at) < Self::command_cache_ttl() {
+        if let Some((cached_at, value)) = slot.as_ref()
+            && now.duration_since(*cached_at) < Self::command_cache_ttl() {
                 return value.clone();
             }
-        }
 
         let value = compute();
         *slot = Some((now, value.clone()));
@@ -83,22 +91,20 @@ impl MacCollector {
     fn compute_fd_stats(&self) -> FdInfo {
         let mut info = FdInfo::default();
 
-        if let Ok(output) = Command::new("sysctl").arg("kern.num_files").output() {
-            if output.status.success() {
+        if let Ok(output) = cmd::output(Command::new("sysctl").arg("kern.num_files"), cmd::QUICK)
+            && output.status.success() {
                 info.system_used = Self::parse_sysctl_value(&String::from_utf8_lossy(&output.stdout));
             }
-        }
-        if let Ok(output) = Command::new("sysctl").arg("kern.maxfiles").output() {
-            if output.status.success() {
+        if let Ok(output) = cmd::output(Command::new("sysctl").arg("kern.maxfiles"), cmd::QUICK)
+            && output.status.success() {
                 info.system_max = Self::parse_sysctl_value(&String::from_utf8_lossy(&output.stdout));
             }
-        }
 
-        let cmd = "lsof -n -P | awk '{print $1}' | sort | uniq -c | sort -nr | head -5";
-        if let Ok(output) = Command::new("sh").arg("-c").arg(cmd).output() {
+        let pipeline = "lsof -n -P | awk '{print $1}' | sort | uniq -c | sort -nr | head -5";
+        if let Ok(output) = cmd::output(Command::new("sh").arg("-c").arg(pipeline), cmd::QUICK) {
             let out_str = String::from_utf8_lossy(&output.stdout);
             for line in out_str.lines() {
-                 let parts: Vec<&str> = line.trim().split_whitespace().collect();
+                 let parts: Vec<&str> = line.split_whitespace().collect();
                  if parts.len() >= 2 {
                      let count: u64 = parts[0].parse().unwrap_or(0);
                      let name = parts[1].to_string();
@@ -117,14 +123,29 @@ impl MacCollector {
         let mut close_wait = 0u32;
         let mut fin_wait = 0u32;
 
-        if let Ok(output) = Command::new("netstat").args(["-an", "-p", "tcp"]).output() {
+        let mut remotes = Vec::new();
+        let mut listeners = Vec::new();
+
+        if let Ok(output) = cmd::output(Command::new("netstat").args(["-an", "-p", "tcp"]), cmd::QUICK) {
             if !output.status.success() {
-                return SocketOverviewInfo { established, listen, time_wait, close_wait, fin_wait, top_processes: Vec::new() };
+                return SocketOverviewInfo { established, listen, time_wait, close_wait, fin_wait, ..Default::default() };
             }
             let text = String::from_utf8_lossy(&output.stdout);
             for line in text.lines() {
-                if line.contains("ESTABLISHED") { established += 1; }
-                else if line.contains("LISTEN") { listen += 1; }
+                if line.contains("ESTABLISHED") {
+                    established += 1;
+                    // Foreign address is the fifth column, e.g. `140.82.112.25.443`
+                    if let Some(remote) = line.split_whitespace().nth(4).and_then(parse_netstat_addr) {
+                        remotes.push(remote);
+                    }
+                }
+                else if line.contains("LISTEN") {
+                    listen += 1;
+                    // Local address is the fourth column, e.g. `*.22`
+                    if let Some(address) = line.split_whitespace().nth(3).and_then(parse_netstat_addr) {
+                        listeners.push(ListenerInfo { address, process: None });
+                    }
+                }
                 else if line.contains("TIME_WAIT") { time_wait += 1; }
                 else if line.contains("CLOSE_WAIT") { close_wait += 1; }
                 else if line.contains("FIN_WAIT") { fin_wait += 1; }
@@ -132,7 +153,7 @@ impl MacCollector {
         }
 
         let mut process_conns: HashMap<String, u32> = HashMap::new();
-        if let Ok(output) = Command::new("lsof").args(["-i", "-n", "-P"]).output() {
+        if let Ok(output) = cmd::output(Command::new("lsof").args(["-i", "-n", "-P"]), cmd::QUICK) {
             let text = String::from_utf8_lossy(&output.stdout);
             for line in text.lines().skip(1) {
                  if line.contains("ESTABLISHED") || line.contains("CLOSE_WAIT") || line.contains("LISTEN") {
@@ -145,25 +166,34 @@ impl MacCollector {
         }
 
         let mut top_processes: Vec<(String, u32)> = process_conns.into_iter().collect();
-        top_processes.sort_by(|a, b| b.1.cmp(&a.1));
+        top_processes.sort_by_key(|entry| std::cmp::Reverse(entry.1));
         top_processes.truncate(5);
 
-        SocketOverviewInfo { established, listen, time_wait, close_wait, fin_wait, top_processes }
+        SocketOverviewInfo {
+            established,
+            listen,
+            time_wait,
+            close_wait,
+            fin_wait,
+            top_processes,
+            remotes: aggregate_remotes(remotes),
+            listeners,
+        }
     }
 
     fn compute_context_switches(&self) -> ContextSwitchInfo {
         let mut total_csw = 0u64;
         let mut top_processes = Vec::new();
 
-        if let Ok(output) = Command::new("ps").args(["-Acro", "comm,nivcsw"]).output() {
+        if let Ok(output) = cmd::output(Command::new("ps").args(["-Acro", "comm,nivcsw"]), cmd::QUICK) {
             if !output.status.success() {
                 return ContextSwitchInfo { total_csw, top_processes };
             }
             let text = String::from_utf8_lossy(&output.stdout);
             for line in text.lines().skip(1) {
-                let parts: Vec<&str> = line.trim().split_whitespace().collect();
-                if parts.len() >= 2 {
-                    if let Ok(csw) = parts.last().unwrap_or(&"0").parse::<u64>() {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts.len() >= 2
+                    && let Ok(csw) = parts.last().unwrap_or(&"0").parse::<u64>() {
                         total_csw += csw;
                         let name_parts = &parts[..parts.len()-1];
                         let name = name_parts.join(" ");
@@ -172,11 +202,10 @@ impl MacCollector {
                             top_processes.push((name, csw));
                         }
                     }
-                }
             }
         }
 
-        top_processes.sort_by(|a, b| b.1.cmp(&a.1));
+        top_processes.sort_by_key(|entry| std::cmp::Reverse(entry.1));
         top_processes.truncate(5);
 
         ContextSwitchInfo { total_csw, top_processes }
@@ -192,25 +221,22 @@ impl Drop for MacCollector {
 /// Run nettop once and return per-PID network stats.
 fn run_nettop() -> HashMap<Pid, (u64, u64)> {
     let mut stats = HashMap::new();
-    if let Ok(output) = Command::new("nettop").args(["-P", "-L", "1"]).output() {
+    if let Ok(output) = cmd::output(Command::new("nettop").args(["-P", "-L", "1"]), cmd::QUICK) {
         if !output.status.success() {
             return stats;
         }
         let text = String::from_utf8_lossy(&output.stdout);
         for line in text.lines().skip(1) {
             let parts: Vec<&str> = line.split(',').collect();
-            if parts.len() >= 6 {
-                if let Some(name_pid) = parts.get(1) {
-                    if let Some(last_dot) = name_pid.rfind('.') {
-                        if let Ok(pid_val) = name_pid[last_dot+1..].parse::<u32>() {
+            if parts.len() >= 6
+                && let Some(name_pid) = parts.get(1)
+                    && let Some(last_dot) = name_pid.rfind('.')
+                        && let Ok(pid_val) = name_pid[last_dot+1..].parse::<u32>() {
                             let pid = Pid::from(pid_val as usize);
                             let bytes_in = parts.get(4).unwrap_or(&"0").parse::<u64>().unwrap_or(0);
                             let bytes_out = parts.get(5).unwrap_or(&"0").parse::<u64>().unwrap_or(0);
                             stats.insert(pid, (bytes_in, bytes_out));
                         }
-                    }
-                }
-            }
         }
     }
     stats
@@ -232,11 +258,65 @@ impl SystemCollector for MacCollector {
         self.get_cached(&mut cache.fd_info, || self.compute_fd_stats())
     }
 
+    fn get_memory_breakdown(&self) -> Option<MemoryBreakdown> {
+        // vm_stat's file-backed and purgeable pages do not map onto these
+        None
+    }
+
+    fn get_memory_detail(&self) -> Option<MemoryDetail> {
+        // No HugeTLB pool or THP, and SysV shm is rarely used
+        None
+    }
+
+    fn get_process_unit(&self, _pid: u32) -> Option<String> {
+        None
+    }
+
+    fn get_process_limits(&self, _pid: u32) -> Option<ProcessLimits> {
+        // Other processes' limits are not exposed without task_for_pid
+        None
+    }
+
+    fn get_process_priority(&self, _pid: u32) -> Option<ProcessPriority> {
+        // No cgroups, and a `ps` per listed process each refresh costs more
+        // than the nice value is worth
+        None
+    }
+
     fn get_socket_stats(&self) -> SocketOverviewInfo {
         let mut cache = self.command_cache.lock().unwrap_or_else(|e| e.into_inner());
         self.get_cached(&mut cache.socket_info, || self.compute_socket_stats())
     }
 
+    fn get_conntrack(&self) -> Option<ConntrackInfo> {
+        None
+    }
+
+    fn get_link_stats(&mut self) -> Vec<LinkInfo> {
+        Vec::new()
+    }
+
+    fn get_pressure(&self) -> PressureInfo {
+        PressureInfo::default()
+    }
+
+    fn get_cpu_breakdown(&mut self) -> Option<CpuBreakdown> {
+        // host_statistics has no iowait or steal, and `top -l 2` takes a second
+        None
+    }
+
+    fn get_oom_info(&mut self) -> Option<OomInfo> {
+        None
+    }
+
+    fn get_entropy(&self) -> Option<u64> {
+        None
+    }
+
+    fn get_interrupts(&mut self) -> Option<InterruptInfo> {
+        None
+    }
+
     fn get_context_switches(&self) -> ContextSwitchInfo {
         let mut cache = self.command_cache.lock().unwrap_or_else(|e| e.into_inner());
         self.get_cached(&mut cache.context_switches, || self.compute_context_switches())
@@ -247,3 +327,35 @@ impl SystemCollector for MacCollector {
         self.nettop_cache.lock().unwrap_or_else(|e| e.into_inner()).clone()
     }
 }
+
+/// Parse a netstat address such as `140.82.112.25.443`, `fe80::1%lo0.443` or
+/// `*.22` (any address), where the port follows the last dot.
+fn parse_netstat_addr(field: &str) -> Option<SocketAddr> {
+    let (host, port) = field.rsplit_once('.')?;
+    let host = host.split('%').next().unwrap_or(host);
+    let ip: IpAddr = if host == "*" {
+        IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED)
+    } else {
+        host.parse().ok()?
+    };
+    Some(SocketAddr::new(ip, port.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_netstat_addr;
+
+    #[test]
+    fn parses_netstat_foreign_addresses() {
+        assert_eq!(
+            parse_netstat_addr("140.82.112.25.443"),
+            Some("140.82.112.25:443".parse().unwrap())
+        );
+        assert_eq!(
+            parse_netstat_addr("fe80::1%lo0.8080"),
+            Some("[fe80::1]:8080".parse().unwrap())
+        );
+        assert_eq!(parse_netstat_addr("*.22"), Some("0.0.0.0:22".parse().unwrap()));
+        assert_eq!(parse_netstat_addr("*.*"), None);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::model::{
-    FdInfo, SocketOverviewInfo, ContextSwitchInfo,
+    ConntrackInfo, CpuBreakdown, FdInfo, InterruptInfo, LinkInfo, MemoryBreakdown, MemoryDetail, OomInfo, PressureInfo, ProcessLimits, ProcessPriority, SocketOverviewInfo, ContextSwitchInfo,
 };
 use sysinfo::Pid;
 use std::collections::HashMap;
@@ -19,9 +19,51 @@ pub trait SystemCollector: Send {
     /// Collect file descriptor statistics.
     fn get_fd_stats(&self) -> FdInfo;
 
+    /// The systemd unit or container a process runs in, from its cgroup, if
+    /// the platform has cgroups.
+    fn get_process_unit(&self, pid: u32) -> Option<String>;
+
+    /// Resource limits of a process, if the platform exposes them.
+    fn get_process_limits(&self, pid: u32) -> Option<ProcessLimits>;
+
+    /// Nice value and cgroup CPU weight of a process, if the platform
+    /// exposes them.
+    fn get_process_priority(&self, pid: u32) -> Option<ProcessPriority>;
+
     /// Collect socket statistics (ESTABLISHED, TIME_WAIT, etc.).
     fn get_socket_stats(&self) -> SocketOverviewInfo;
 
+    /// Collect netfilter conntrack table usage, if the platform has one.
+    fn get_conntrack(&self) -> Option<ConntrackInfo>;
+
+    /// Collect link state, negotiated speed and carrier changes per interface.
+    fn get_link_stats(&mut self) -> Vec<LinkInfo>;
+
+    /// Collect pressure stall information and the run queue length.
+    fn get_pressure(&self) -> PressureInfo;
+
+    /// Collect the user / system / iowait / steal split of CPU time since the
+    /// previous call, if the platform reports it.
+    fn get_cpu_breakdown(&mut self) -> Option<CpuBreakdown>;
+
+    /// Collect kernel OOM killer activity, if the platform reports it.
+    fn get_oom_info(&mut self) -> Option<OomInfo>;
+
+    /// Available entropy in bits, only where the kernel blocks readers of a
+    /// starved pool.
+    fn get_entropy(&self) -> Option<u64>;
+
+    /// Collect interrupt and softirq rates since the previous call, if the
+    /// platform reports them.
+    fn get_interrupts(&mut self) -> Option<InterruptInfo>;
+
+    /// Collect buffers, page cache and slab usage, if the platform reports them.
+    fn get_memory_breakdown(&self) -> Option<MemoryBreakdown>;
+
+    /// Collect shared memory segments and huge page usage, if the platform
+    /// exposes them.
+    fn get_memory_detail(&self) -> Option<MemoryDetail>;
+
     /// Collect context switch statistics.
     fn get_context_switches(&self) -> ContextSwitchInfo;
 
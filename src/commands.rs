@@ -0,0 +1,193 @@
+//! One-shot subcommands that print a result and exit without the TUI:
+//! `sitrep snapshot`, `sitrep --report` and `sitrep check <name>`. The
+//! snapshot is also what a running sitrep writes on SIGUSR2.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::config::Config;
+use crate::export;
+
+/// Give up waiting on collectors after this long and print what arrived.
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Exit statuses of `check`, following the Nagios plugin convention.
+pub const CHECK_OK: i32 = 0;
+pub const CHECK_CRITICAL: i32 = 2;
+pub const CHECK_UNKNOWN: i32 = 3;
+
+/// Columns of `--report`, wide enough for the summary bars and a command.
+const REPORT_WIDTH: u16 = 100;
+
+/// Run one collection pass of every monitor and print the result as JSON.
+pub fn snapshot(cli: &crate::cli::Cli, config: Config) -> io::Result<()> {
+    let app = collect(cli, config);
+    let mut snapshot = snapshot_json(&app);
+    snapshot["state"] = state_json(&app);
+    println!("{}", serde_json::to_string_pretty(&snapshot).map_err(io::Error::other)?);
+    Ok(())
+}
+
+/// Run one collection pass and print the System summary and top processes
+/// as plain text.
+pub fn report(cli: &crate::cli::Cli, config: Config) -> io::Result<()> {
+    let mut app = collect(cli, config);
+    let Some(data) = app.monitor.last_data.as_ref() else {
+        return Err(io::Error::other("no system data was collected in time"));
+    };
+    let host = sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string());
+    let text = crate::view::Presenter::render_report(data, &mut app.monitor.ui_state, REPORT_WIDTH)?;
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %z");
+    println!("sitrep report: {} at {}\n\n{}", host, now, text);
+    Ok(())
+}
+
+/// Start every monitor and wait, up to SNAPSHOT_TIMEOUT, for their first
+/// results.
+fn collect(cli: &crate::cli::Cli, config: Config) -> App {
+    let rt = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .worker_threads(2)
+            .build()
+            .expect("Failed to create tokio runtime"),
+    );
+    let mut app = if cli.demo {
+        App::demo(Arc::clone(&rt), cli.interval(&config), None, config)
+    } else {
+        App::new(Arc::clone(&rt), cli.interval(&config), cli.no_docker, None, config)
+    };
+    let deadline = Instant::now() + SNAPSHOT_TIMEOUT;
+    let wait = |app: &mut App, done: &dyn Fn(&App) -> bool| {
+        while !done(app) && Instant::now() < deadline {
+            app.monitor.poll_update();
+            app.poll_startup();
+            app.docker_monitor.poll_update();
+            app.swarm_monitor.poll_update();
+            app.health_monitor.poll_update();
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    };
+
+    // The system snapshot and Docker/Swarm detection start on their own
+    wait(&mut app, &|app| app.monitor.last_data.is_some() && !app.probing());
+    if app.swarm_monitor.is_swarm() {
+        app.swarm_monitor.update();
+    }
+    app.health_monitor.update();
+    wait(&mut app, &|app| {
+        let docker_done = !app.docker_monitor.is_available() || app.docker_monitor.last_updated.is_some();
+        let swarm_done = !app.swarm_monitor.is_swarm() || app.swarm_monitor.last_updated.is_some();
+        docker_done && swarm_done && !app.health_monitor.in_flight()
+    });
+    app
+}
+
+/// What the monitors last collected: alerts, processes, containers and services.
+pub fn snapshot_json(app: &App) -> serde_json::Value {
+    let data = app.monitor.last_data.as_ref();
+    serde_json::json!({
+        "time": data.map(|d| d.time.clone()),
+        "alerts": crate::alerts::to_json(&app.current_alerts()),
+        "processes": data.map(|d| export::processes_table(d).to_json()),
+        "containers": export::containers_table(&app.docker_monitor.containers).to_json(),
+        "services": export::services_table(&app.swarm_monitor.services).to_json(),
+    })
+}
+
+/// Everything the System, Containers and Swarm monitors hold, as their model
+/// types serialize, for tooling that needs more than the tables.
+pub fn state_json(app: &App) -> serde_json::Value {
+    let swarm = &app.swarm_monitor;
+    serde_json::json!({
+        "system": app.monitor.last_data.as_ref(),
+        "containers": app.docker_monitor.containers,
+        "swarm": swarm.is_swarm().then(|| serde_json::json!({
+            "cluster": swarm.cluster_info,
+            "nodes": swarm.nodes,
+            "services": swarm.services,
+            "tasks": swarm.tasks,
+            "warnings": swarm.warnings,
+        })),
+    })
+}
+
+/// Where SIGUSR2 writes its snapshot: `sitrep-<pid>/snapshot.json` under
+/// `$XDG_RUNTIME_DIR` or the temp directory, so a script can `kill -USR2 $pid`
+/// and read it back. The directory is private to the user and made on the
+/// first dump; the toast and log name it if `sitrep-<pid>` was taken.
+pub fn dump_path() -> Result<PathBuf, String> {
+    static DIR: OnceLock<PathBuf> = OnceLock::new();
+    if let Some(dir) = DIR.get() {
+        return Ok(dir.join("snapshot.json"));
+    }
+    let dir = crate::profiler::private_dir(&format!("sitrep-{}", std::process::id()))?;
+    Ok(DIR.get_or_init(|| dir).join("snapshot.json"))
+}
+
+/// Write the snapshot to a temporary file and rename it into place, so a
+/// reader never sees a half-written dump.
+pub fn write_snapshot(app: &App, path: &Path) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(&snapshot_json(app)).map_err(|e| e.to_string())?;
+    write_private(path, &text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Write `text` to `path` readable by the owner only. The partial file is
+/// created fresh, so nothing left at its name is written through.
+fn write_private(path: &Path, text: &str) -> io::Result<()> {
+    let partial = path.with_extension("json.tmp");
+    match std::fs::remove_file(&partial) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    let mut file = OpenOptions::new().write(true).create_new(true).mode(0o600).open(&partial)?;
+    file.write_all(text.as_bytes())?;
+    drop(file);
+    std::fs::rename(&partial, path)
+}
+
+/// Probe the health URL configured for `name` (a container, else a service)
+/// and print the outcome. Returns the process exit status.
+pub fn check(config: &Config, name: &str) -> i32 {
+    let Some(url) = config.health.containers.get(name).or_else(|| config.health.services.get(name)) else {
+        eprintln!("UNKNOWN: no health check configured for '{}'", name);
+        return CHECK_UNKNOWN;
+    };
+    let timeout = Duration::from_secs(config.health.timeout_secs.max(1));
+    let result = crate::health_controller::HealthResult {
+        url: url.clone(),
+        outcome: crate::probe::probe_http(url, timeout),
+        checked_at: Instant::now(),
+    };
+    if result.is_healthy() {
+        println!("OK: {} {} ({})", name, result.summary(), url);
+        CHECK_OK
+    } else {
+        println!("CRITICAL: {} {} ({})", name, result.summary(), url);
+        CHECK_CRITICAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn dump_is_written_owner_only() {
+        let dir = crate::profiler::private_dir("sitrep-test-dump").unwrap();
+        let path = dir.join("snapshot.json");
+        std::fs::write(path.with_extension("json.tmp"), "stale").unwrap();
+        write_private(&path, "{}").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(text, "{}");
+    }
+}
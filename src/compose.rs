@@ -0,0 +1,193 @@
+//! Compare a Swarm stack with the compose file it was deployed from. There is
+//! no YAML parser among the dependencies, so `parse` reads only the block
+//! mapping under `services:` (each service's `image` and `deploy.replicas` /
+//! `deploy.mode`), which is all the drift check needs. Flow mappings, anchors
+//! and `${VAR}` interpolation are not expanded; fields using them are skipped.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::model::SwarmServiceInfo;
+
+/// What a compose file asks for one service.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ComposeService {
+    pub image: Option<String>,
+    pub replicas: Option<u32>,
+    pub global: bool,
+}
+
+/// Services of a compose file, by name.
+pub fn load(path: &Path) -> Result<BTreeMap<String, ComposeService>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(parse(&text))
+}
+
+pub fn parse(text: &str) -> BTreeMap<String, ComposeService> {
+    let mut services: BTreeMap<String, ComposeService> = BTreeMap::new();
+    // (indent, key) of each open mapping above the current line
+    let mut path: Vec<(usize, String)> = Vec::new();
+
+    for raw in text.lines() {
+        let line = strip_comment(raw);
+        if line.trim().is_empty() || line.trim_start().starts_with('-') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let Some((key, value)) = line.trim().split_once(':') else { continue };
+        let (key, value) = (unquote(key.trim()), unquote(value.trim()));
+        while path.last().is_some_and(|(i, _)| *i >= indent) {
+            path.pop();
+        }
+
+        let keys: Vec<&str> = path.iter().map(|(_, k)| k.as_str()).collect();
+        match keys.as_slice() {
+            ["services"] => {
+                services.entry(key.to_string()).or_default();
+            }
+            ["services", name] if key == "image" && !value.contains("${") => {
+                services.entry(name.to_string()).or_default().image = Some(value.to_string());
+            }
+            ["services", name, "deploy"] if key == "replicas" => {
+                services.entry(name.to_string()).or_default().replicas = value.parse().ok();
+            }
+            ["services", name, "deploy"] if key == "mode" => {
+                services.entry(name.to_string()).or_default().global = value == "global";
+            }
+            _ => {}
+        }
+        if value.is_empty() {
+            path.push((indent, key.to_string()));
+        }
+    }
+    services
+}
+
+/// A `#` comment at the start of the line or after whitespace, outside quotes.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '#') if prev.is_whitespace() => return &line[..i],
+            _ => {}
+        }
+        prev = c;
+    }
+    line
+}
+
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+        .unwrap_or(s)
+}
+
+/// An image reference without digest, registry default or implied tag, so
+/// "nginx", "docker.io/library/nginx:latest" and "nginx:latest@sha256:..."
+/// compare equal.
+fn normalize_image(image: &str) -> String {
+    let image = image.split('@').next().unwrap_or(image);
+    let image = image.strip_prefix("docker.io/").unwrap_or(image);
+    let image = image.strip_prefix("library/").unwrap_or(image);
+    let name = image.rsplit('/').next().unwrap_or(image);
+    if name.contains(':') {
+        image.to_string()
+    } else {
+        format!("{}:latest", image)
+    }
+}
+
+/// Desired replicas from a `docker service ls` count like "2/3" or
+/// "3/3 (max 1 per node)".
+fn desired_replicas(replicas: &str) -> Option<u32> {
+    let (_, desired) = replicas.split_once('/')?;
+    let digits: String = desired.trim().chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// How stack `stack`'s running services differ from its compose file,
+/// one line per difference, e.g. "shop_api: 5 replicas, file has 3".
+pub fn stack_drift(stack: &str, file: &BTreeMap<String, ComposeService>, services: &[&SwarmServiceInfo]) -> Vec<String> {
+    let mut drift = Vec::new();
+    for (name, wanted) in file {
+        let full_name = format!("{}_{}", stack, name);
+        let Some(svc) = services.iter().find(|s| s.name == full_name) else {
+            drift.push(format!("{}: in the file, not deployed", full_name));
+            continue;
+        };
+        if let Some(ref image) = wanted.image
+            && normalize_image(image) != normalize_image(&svc.image) {
+                drift.push(format!("{}: runs {}, file has {}", full_name, svc.image, image));
+            }
+        if !wanted.global {
+            let wanted_replicas = wanted.replicas.unwrap_or(1);
+            if let Some(running) = desired_replicas(&svc.replicas)
+                && running != wanted_replicas && svc.mode != "global" {
+                    drift.push(format!("{}: {} replicas, file has {}", full_name, running, wanted_replicas));
+                }
+        }
+    }
+    for svc in services {
+        let short = svc.name.strip_prefix(&format!("{}_", stack)).unwrap_or(&svc.name);
+        if !file.contains_key(short) {
+            drift.push(format!("{}: deployed, not in the file", svc.name));
+        }
+    }
+    drift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_manual_updates_against_the_file() {
+        let file = parse(
+            "version: '3.8'\n\
+             services:\n\
+             \x20 api:\n\
+             \x20   image: \"registry.example.com/api:1.4\"  # pinned\n\
+             \x20   environment:\n\
+             \x20     - LOG_LEVEL=info\n\
+             \x20   deploy:\n\
+             \x20     replicas: 3\n\
+             \x20 web:\n\
+             \x20   image: nginx\n\
+             \x20 agent:\n\
+             \x20   image: agent:${TAG}\n\
+             \x20   deploy:\n\
+             \x20     mode: global\n\
+             networks:\n\
+             \x20 default:\n",
+        );
+        assert_eq!(file.len(), 3);
+        assert_eq!(file["api"].replicas, Some(3));
+        assert_eq!(file["agent"], ComposeService { image: None, replicas: None, global: true });
+
+        let svc = |name: &str, image: &str, replicas: &str| SwarmServiceInfo {
+            name: name.into(),
+            image: image.into(),
+            replicas: replicas.into(),
+            ..Default::default()
+        };
+        let running = [
+            svc("shop_api", "registry.example.com/api:1.5", "5/5"),
+            svc("shop_web", "nginx:latest", "1/1"),
+            svc("shop_debug", "busybox", "1/1"),
+        ];
+        let refs: Vec<&SwarmServiceInfo> = running.iter().collect();
+        assert_eq!(
+            stack_drift("shop", &file, &refs),
+            vec![
+                "shop_agent: in the file, not deployed",
+                "shop_api: runs registry.example.com/api:1.5, file has registry.example.com/api:1.4",
+                "shop_api: 5 replicas, file has 3",
+                "shop_debug: deployed, not in the file",
+            ]
+        );
+    }
+}
@@ -0,0 +1,568 @@
+//! User configuration loaded from `~/.config/sitrep/config.toml`.
+//!
+//! Every field is optional; a missing file or missing keys fall back to the
+//! built-in defaults, so sitrep runs unchanged without a config. The file is
+//! watched while sitrep runs and edits are applied without a restart.
+//! `SITREP_*` environment variables override values from the file.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
+
+use crate::model::{CpuScale, SummaryLine, Thresholds};
+use crate::view::theme::ThemeName;
+
+/// How often the config file's modification time is checked.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub alertmanager: AlertmanagerConfig,
+    pub containers: ContainersConfig,
+    pub health: HealthConfig,
+    pub history: HistoryConfig,
+    pub incidents: IncidentsConfig,
+    /// Keys remapped to sitrep's own, e.g. `j = "Down"`; see `keymap`.
+    pub keys: BTreeMap<String, String>,
+    /// Named key sequences for the command palette; see `macros`.
+    pub macros: BTreeMap<String, Vec<String>>,
+    pub maintenance: MaintenanceConfig,
+    pub self_guard: SelfGuardConfig,
+    pub summary: SummaryConfig,
+    pub swarm: SwarmConfig,
+    pub thresholds: Thresholds,
+    pub ui: UiConfig,
+    pub watchlist: WatchlistConfig,
+}
+
+/// `[self_guard]` table: how much CPU sitrep may use before it backs off.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SelfGuardConfig {
+    /// Percent of one core for sitrep and its subprocesses; 0 disables.
+    pub cpu_budget_percent: f64,
+}
+
+impl Default for SelfGuardConfig {
+    fn default() -> Self {
+        Self { cpu_budget_percent: 20.0 }
+    }
+}
+
+/// `[summary]` table: the lines of the System summary.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SummaryConfig {
+    /// Built-in and custom lines in display order; lines left out are hidden.
+    pub lines: Vec<SummaryLine>,
+    pub custom: Vec<CustomLineConfig>,
+}
+
+impl Default for SummaryConfig {
+    fn default() -> Self {
+        Self {
+            lines: SummaryLine::BUILT_IN.to_vec(),
+            custom: Vec::new(),
+        }
+    }
+}
+
+impl SummaryConfig {
+    /// `lines`, followed by the custom lines it does not name.
+    pub fn order(&self) -> Vec<SummaryLine> {
+        let mut order = self.lines.clone();
+        for custom in &self.custom {
+            let line = SummaryLine::Custom(custom.name.clone());
+            if !order.contains(&line) {
+                order.push(line);
+            }
+        }
+        order
+    }
+}
+
+/// `[[summary.custom]]`: a summary line showing the output of a command.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CustomLineConfig {
+    /// Shown as the line's label and used in `lines`.
+    pub name: String,
+    /// Run with `sh -c`. The first line it prints is shown, colored by the
+    /// exit status as for a Nagios plugin (0 OK, 1 warning, 2 critical).
+    pub command: String,
+    pub interval_secs: u64,
+}
+
+impl Default for CustomLineConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            command: String::new(),
+            interval_secs: 30,
+        }
+    }
+}
+
+/// `[swarm]` table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SwarmConfig {
+    /// Compose file each stack was deployed from, by stack name, for the
+    /// drift check. Stacks not listed use their services' compose-file label.
+    pub stack_files: BTreeMap<String, PathBuf>,
+    /// Where scheduled rolling restarts are kept (default
+    /// `~/.sitrep/schedules.toml`); give the TUI and the daemon the same file.
+    pub schedule_file: Option<PathBuf>,
+    /// Where muted warnings are kept (default `~/.sitrep/muted.toml`).
+    pub mute_file: Option<PathBuf>,
+}
+
+/// `[ui]` table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    /// `mocha` (dark, default) or `latte` (light).
+    pub theme: ThemeName,
+    /// CPU columns at startup: `core` (percent of one core, default) or
+    /// `total` (percent of all cores).
+    pub cpu: CpuScale,
+    /// Refresh interval in seconds when `--interval` is not given (default 3).
+    pub interval_secs: Option<u64>,
+}
+
+/// `[watchlist]` table: containers and services on the Watchlist tab at startup.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatchlistConfig {
+    pub containers: Vec<String>,
+    pub services: Vec<String>,
+}
+
+/// `[alertmanager]` table: silences created when a warning is muted.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertmanagerConfig {
+    /// Base URL, e.g. `http://alertmanager:9093`; unset leaves mutes local.
+    pub url: Option<String>,
+    /// Label matchers of the silence, by label name. In a value `{name}` is
+    /// the service or node the warning is about and `{kind}` its kind, e.g.
+    /// `SERVICE DEGRADED`.
+    pub matchers: BTreeMap<String, String>,
+}
+
+impl Default for AlertmanagerConfig {
+    fn default() -> Self {
+        Self { url: None, matchers: BTreeMap::from([("service".to_string(), "{name}".to_string())]) }
+    }
+}
+
+/// `[incidents]` table: where `^` opens an incident.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IncidentsConfig {
+    /// Unset disables the action.
+    pub provider: Option<IncidentProvider>,
+    /// PagerDuty Events API v2 integration key, or Opsgenie API key.
+    pub key: Option<String>,
+    /// The provider's API URL when not the default, e.g.
+    /// `https://api.eu.opsgenie.com/v2/alerts` for Opsgenie's EU instance.
+    pub url: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentProvider {
+    PagerDuty,
+    Opsgenie,
+}
+
+/// `[maintenance]` table: the shared maintenance note.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaintenanceConfig {
+    /// Note file shared by the instances on this host (default
+    /// `/run/sitrep/maintenance.json`); Swarm managers share theirs as a node label.
+    pub file: Option<PathBuf>,
+}
+
+/// `[history]` table: optional SQLite store of per-tick metrics.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistoryConfig {
+    pub enabled: bool,
+    /// Database file (default `~/.sitrep/history.db`).
+    pub path: Option<PathBuf>,
+    /// Samples older than this are pruned.
+    pub retention_hours: u64,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            retention_hours: 24,
+        }
+    }
+}
+
+/// `[health]` table: HTTP health URLs keyed by container or service name.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthConfig {
+    pub timeout_secs: u64,
+    pub containers: BTreeMap<String, String>,
+    pub services: BTreeMap<String, String>,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 2,
+            containers: BTreeMap::new(),
+            services: BTreeMap::new(),
+        }
+    }
+}
+
+/// `[containers]` table.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContainersConfig {
+    /// Columns shown in the container table, in display order.
+    pub columns: Vec<ContainerColumn>,
+    /// Label whose value groups containers in the "label" grouping mode.
+    pub group_label: Option<String>,
+    /// Label keys shown in the expanded container detail.
+    pub labels: Vec<String>,
+    /// Containers started less than this many seconds ago are highlighted.
+    pub recent_restart_secs: u64,
+    /// Containers running an image built longer ago than this are flagged;
+    /// 0 disables.
+    pub image_max_age_days: u64,
+    /// Hourly, ask the registry whether each pulled image's tag now points
+    /// at a different digest. Slow, and needs registry access from the daemon.
+    pub check_registry_digest: bool,
+    /// Count critical and high CVEs in each running image with trivy or
+    /// docker scout, whichever is installed; each image at most daily.
+    pub scan_images: bool,
+    /// containerd namespaces listed when containers come from nerdctl
+    /// (no Docker daemon); all of them when empty.
+    pub namespaces: Vec<String>,
+}
+
+impl Default for ContainersConfig {
+    fn default() -> Self {
+        Self {
+            columns: ContainerColumn::DEFAULT.to_vec(),
+            group_label: None,
+            labels: Vec::new(),
+            recent_restart_secs: 300,
+            image_max_age_days: 90,
+            check_registry_digest: false,
+            scan_images: false,
+            namespaces: Vec::new(),
+        }
+    }
+}
+
+/// A column of the container table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerColumn {
+    Id,
+    Name,
+    Image,
+    Status,
+    Health,
+    Uptime,
+    Cpu,
+    Mem,
+    Net,
+    Ports,
+    Ip,
+    #[serde(rename = "image_age")]
+    ImageAge,
+}
+
+impl ContainerColumn {
+    /// The layout used when no columns are configured.
+    pub const DEFAULT: [ContainerColumn; 8] = [
+        ContainerColumn::Id,
+        ContainerColumn::Name,
+        ContainerColumn::Status,
+        ContainerColumn::Health,
+        ContainerColumn::Uptime,
+        ContainerColumn::Cpu,
+        ContainerColumn::Ports,
+        ContainerColumn::Ip,
+    ];
+
+    pub fn header(self) -> &'static str {
+        match self {
+            ContainerColumn::Id => "CONTAINER ID",
+            ContainerColumn::Name => "NAME",
+            ContainerColumn::Image => "IMAGE",
+            ContainerColumn::Status => "STATE",
+            ContainerColumn::Health => "HEALTH",
+            ContainerColumn::Uptime => "UPTIME",
+            ContainerColumn::Cpu => "CPU",
+            ContainerColumn::Mem => "MEM",
+            ContainerColumn::Net => "NET RX/TX",
+            ContainerColumn::Ports => "PORTS",
+            ContainerColumn::Ip => "IP",
+            ContainerColumn::ImageAge => "IMAGE AGE",
+        }
+    }
+
+    /// Column width including trailing gap.
+    pub fn width(self) -> usize {
+        match self {
+            ContainerColumn::Id => 15,
+            ContainerColumn::Name => 20,
+            ContainerColumn::Image => 26,
+            ContainerColumn::Status => 11,
+            ContainerColumn::Health => 14,
+            ContainerColumn::Uptime => 9,
+            ContainerColumn::Cpu => 16,
+            ContainerColumn::Mem => 14,
+            ContainerColumn::Net => 14,
+            ContainerColumn::Ports => 28,
+            ContainerColumn::Ip => 16,
+            ContainerColumn::ImageAge => 11,
+        }
+    }
+}
+
+/// Default config location: `$XDG_CONFIG_HOME/sitrep/config.toml`, falling
+/// back to `~/.config/sitrep/config.toml`.
+pub fn default_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(base.join("sitrep").join("config.toml"))
+}
+
+/// Load the config from the default location. A missing file yields the defaults.
+pub fn load() -> Result<Config, String> {
+    match default_path() {
+        Some(path) if path.exists() => load_from(&path),
+        _ => {
+            let mut config = Config::default();
+            apply_env(&mut config, |name| std::env::var(name).ok())?;
+            Ok(config)
+        }
+    }
+}
+
+/// Load the config from `path`, which must exist.
+pub fn load_from(path: &Path) -> Result<Config, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut config = parse(&text).map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+    apply_env(&mut config, |name| std::env::var(name).ok())?;
+    Ok(config)
+}
+
+/// Override config values from `SITREP_*` variables looked up with `var`.
+/// Only `SITREP_THEME` maps onto the file; the others (`SITREP_INTERVAL`,
+/// `SITREP_READ_ONLY`, `SITREP_DOCKER_HOST`, `SITREP_DOCKER_SOCKET`,
+/// `SITREP_CONFIG`) back command-line flags and are read by the argument parser.
+pub fn apply_env(config: &mut Config, var: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+    if let Some(theme) = var("SITREP_THEME") {
+        config.ui.theme = ThemeName::parse(&theme).map_err(|e| format!("SITREP_THEME: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Watch `path` on a background thread and send the re-parsed config each
+/// time the file is written or created. Polls the modification time rather
+/// than using inotify/kqueue, which also covers editors that replace the
+/// file instead of writing it in place. A deleted file is ignored.
+pub fn watch(path: PathBuf) -> mpsc::Receiver<Result<Config, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let mut last: Option<SystemTime> = modified(&path);
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            let current = modified(&path);
+            if current.is_none() || current == last {
+                continue;
+            }
+            last = current;
+            if tx.send(load_from(&path)).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Parse config TOML text.
+pub fn parse(text: &str) -> Result<Config, String> {
+    let config: Config = toml::from_str(text).map_err(|e| e.to_string())?;
+    if config.containers.columns.is_empty() {
+        return Err("containers.columns must list at least one column".to_string());
+    }
+    if config.self_guard.cpu_budget_percent < 0.0 {
+        return Err("self_guard.cpu_budget_percent must not be negative".to_string());
+    }
+    if config.ui.interval_secs == Some(0) {
+        return Err("ui.interval_secs must be at least 1".to_string());
+    }
+    let thresholds = &config.thresholds;
+    if !(0.0..=100.0).contains(&thresholds.disk_free_pct) || !(0.0..=100.0).contains(&thresholds.mem_used_pct) {
+        return Err("thresholds.disk_free_pct and thresholds.mem_used_pct must be between 0 and 100".to_string());
+    }
+    crate::keymap::KeyMap::new(&config.keys)?;
+    crate::macros::Macros::new(&config.macros)?;
+    if config.history.retention_hours == 0 {
+        return Err("history.retention_hours must be at least 1".to_string());
+    }
+    for url in config.health.containers.values().chain(config.health.services.values()) {
+        crate::probe::parse_http_url(url)?;
+    }
+    let custom = &config.summary.custom;
+    for (i, line) in custom.iter().enumerate() {
+        if line.command.trim().is_empty() || line.interval_secs == 0 {
+            return Err(format!("summary.custom '{}' needs a command and an interval of at least 1s", line.name));
+        }
+        if line.name.is_empty() || !matches!(SummaryLine::from(line.name.clone()), SummaryLine::Custom(_)) {
+            return Err(format!("summary.custom name '{}' is empty or taken by a built-in line", line.name));
+        }
+        if custom[..i].iter().any(|other| other.name == line.name) {
+            return Err(format!("summary.custom '{}' is defined twice", line.name));
+        }
+    }
+    for line in &config.summary.lines {
+        if let SummaryLine::Custom(name) = line
+            && !custom.iter().any(|c| &c.name == name) {
+                return Err(format!("summary.lines: '{}' is neither a built-in line nor a summary.custom name", name));
+            }
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config = parse("").unwrap();
+        assert_eq!(config.containers.columns, ContainerColumn::DEFAULT.to_vec());
+    }
+
+    #[test]
+    fn container_columns_keep_configured_order() {
+        let config = parse("[containers]\ncolumns = [\"name\", \"image\", \"mem\", \"cpu\"]\n").unwrap();
+        assert_eq!(
+            config.containers.columns,
+            vec![
+                ContainerColumn::Name,
+                ContainerColumn::Image,
+                ContainerColumn::Mem,
+                ContainerColumn::Cpu,
+            ]
+        );
+    }
+
+    #[test]
+    fn health_urls_are_validated() {
+        let config = parse("[health.services]\napi = \"http://10.0.0.5:8080/health\"\n").unwrap();
+        assert_eq!(config.health.services["api"], "http://10.0.0.5:8080/health");
+        assert!(parse("[health.containers]\nweb = \"https://example.com\"\n").is_err());
+    }
+
+    #[test]
+    fn maintenance_note_file_is_read() {
+        assert_eq!(parse("").unwrap().maintenance.file, None);
+        let config = parse("[maintenance]\nfile = \"/srv/shared/sitrep-note.json\"\n").unwrap();
+        assert_eq!(config.maintenance.file, Some(PathBuf::from("/srv/shared/sitrep-note.json")));
+    }
+
+    #[test]
+    fn history_is_opt_in() {
+        assert!(!parse("").unwrap().history.enabled);
+        let config = parse("[history]\nenabled = true\nretention_hours = 48\n").unwrap();
+        assert!(config.history.enabled);
+        assert_eq!(config.history.retention_hours, 48);
+        assert!(parse("[history]\nretention_hours = 0\n").is_err());
+    }
+
+    #[test]
+    fn watchlist_names_are_read() {
+        let config = parse("[watchlist]\ncontainers = [\"gateway\"]\nservices = [\"shop_api\"]\n").unwrap();
+        assert_eq!(config.watchlist.containers, vec!["gateway".to_string()]);
+        assert_eq!(config.watchlist.services, vec!["shop_api".to_string()]);
+        assert!(parse("").unwrap().watchlist.containers.is_empty());
+    }
+
+    #[test]
+    fn theme_env_overrides_the_file() {
+        let mut config = parse("[ui]\ntheme = \"latte\"\n").unwrap();
+        assert_eq!(config.ui.theme, ThemeName::Latte);
+        apply_env(&mut config, |name| (name == "SITREP_THEME").then(|| "dark".to_string())).unwrap();
+        assert_eq!(config.ui.theme, ThemeName::Mocha);
+        assert!(apply_env(&mut config, |_| Some("solarized".to_string())).is_err());
+    }
+
+    #[test]
+    fn cpu_scale_is_read() {
+        assert_eq!(parse("").unwrap().ui.cpu, CpuScale::Core);
+        let scale = parse("[ui]\ncpu = \"total\"\n").unwrap().ui.cpu;
+        assert_eq!(scale, CpuScale::Total);
+        assert_eq!(scale.apply(350.0, 8.0), 43.75);
+        assert_eq!(CpuScale::Core.apply(350.0, 8.0), 350.0);
+        assert!(parse("[ui]\ncpu = \"half\"\n").is_err());
+    }
+
+    #[test]
+    fn interval_thresholds_and_keys_are_read() {
+        let config = parse("").unwrap();
+        assert_eq!((config.ui.interval_secs, config.thresholds), (None, Thresholds::default()));
+        let config = parse(
+            "[ui]\ninterval_secs = 10\n[thresholds]\nclose_wait = 50\nmem_used_pct = 95\n[keys]\nj = \"Down\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.ui.interval_secs, Some(10));
+        assert_eq!((config.thresholds.close_wait, config.thresholds.time_wait, config.thresholds.mem_used_pct), (50, 100, 95.0));
+        assert_eq!(config.keys["j"], "Down");
+        assert!(parse("[ui]\ninterval_secs = 0\n").is_err());
+        assert!(parse("[thresholds]\ndisk_free_pct = 120\n").is_err());
+        assert!(parse("[keys]\nj = \"Sideways\"\n").is_err());
+    }
+
+    #[test]
+    fn summary_lines_are_ordered_and_checked() {
+        let config = parse(
+            "[summary]\nlines = [\"deploy\", \"load\", \"mem\"]\n\
+             [[summary.custom]]\nname = \"deploy\"\ncommand = \"cat /srv/release\"\n\
+             [[summary.custom]]\nname = \"queue\"\ncommand = \"check_queue\"\ninterval_secs = 5\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.summary.order(),
+            vec![
+                SummaryLine::Custom("deploy".into()),
+                SummaryLine::Cpu,
+                SummaryLine::Mem,
+                SummaryLine::Custom("queue".into()),
+            ]
+        );
+        assert_eq!(config.summary.custom[0].interval_secs, 30);
+        assert_eq!(parse("").unwrap().summary.order(), SummaryLine::BUILT_IN.to_vec());
+        assert!(parse("[summary]\nlines = [\"cpu\", \"sokc\"]\n").is_err());
+        assert!(parse("[[summary.custom]]\nname = \"disk\"\ncommand = \"df\"\n").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_column_and_empty_list() {
+        assert!(parse("[containers]\ncolumns = [\"bogus\"]\n").is_err());
+        assert!(parse("[containers]\ncolumns = []\n").is_err());
+    }
+}
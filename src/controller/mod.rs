@@ -6,34 +6,80 @@ use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::sync::mpsc;
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use chrono::Local;
 use sysinfo::{Pid, System, Disks, Networks};
 
+use crate::config::CustomLineConfig;
 use crate::collectors::{mac::MacCollector, linux::LinuxCollector, SystemCollector};
+use crate::demo::{DemoCollector, DemoHost};
 use crate::layout::Layout;
 use crate::model::{
-    DiskSpaceInfo, MemoryInfo, MonitorData, NetworkInfo, NetworkInterfaceInfo,
-    ProcessGroup, UIState,
+    daemon_process_name, detect_anomalies, AuthFailures, EntropyInfo, AuthSummary, Baseline, ExecEvents, ExecSummary, DiskSpaceInfo, FdTracker, ListenerTracker, RestartTracker, InterfaceHistory, MemoryInfo, MonitorData, NetworkInfo, NetworkInterfaceInfo,
+    CustomLineOutput, DaemonProcessUsage, ProcessGroup, ProfileReport, ProfileState, ProfileView, ProcessGrouping, SelfUsage, StorageArrayInfo, LvmVolumeInfo, TraceReport, TraceState, TraceView, UIState, UpdateStatus,
 };
 
+/// Rate samples kept per interface for the bandwidth graph.
+const INTERFACE_HISTORY_LEN: usize = 240;
+/// md arrays and ZFS pools change slowly and `zpool status` is not free.
+const STORAGE_REFRESH: std::time::Duration = std::time::Duration::from_secs(15);
+
 pub struct Monitor {
     pub ui_state: UIState,
     pub layout: Layout,
     pub last_data: Option<MonitorData>,
+    /// When `last_data` was last replaced by a completed collection.
+    pub last_updated: Option<Instant>,
+    /// The collector thread died, so `last_data` will not be replaced.
+    pub update_failed: bool,
     worker_state: Option<MonitorWorkerState>,
     update_receiver: Option<mpsc::Receiver<MonitorUpdateResult>>,
+    profile_receiver: Option<mpsc::Receiver<Result<ProfileReport, String>>>,
+    trace_receiver: Option<mpsc::Receiver<Result<TraceReport, String>>>,
+    updates_receiver: Option<mpsc::Receiver<UpdateStatus>>,
+    /// When the last package update check started.
+    updates_checked: Option<Instant>,
+    auth_log: Option<crate::authlog::AuthLogStream>,
+    auth_failures: AuthFailures,
+    /// Runs only while the exec panel is open.
+    exec_snoop: Option<crate::execsnoop::ExecStream>,
+    exec_events: ExecEvents,
+    /// Commands behind the custom summary lines, from `[[summary.custom]]`.
+    custom_lines: Vec<CustomLineConfig>,
+    /// When each custom line's command last started, by name, and whether
+    /// it is still running.
+    custom_started: HashMap<String, (Instant, bool)>,
+    custom_sender: mpsc::Sender<(String, CustomLineOutput)>,
+    custom_receiver: mpsc::Receiver<(String, CustomLineOutput)>,
 }
 
+/// Per-interface cumulative (name, rx_bytes, tx_bytes) captured at an instant.
+type NetSnapshot = (Instant, Vec<(String, u64, u64)>);
+
 struct MonitorWorkerState {
     sys: System,
     core_count: f64,
     history: VecDeque<(Instant, HashMap<Pid, ProcessGroup>)>,
     disks: Disks,
     networks: Networks,
-    prev_net_snapshot: Option<(Instant, Vec<(String, u64, u64)>)>,
+    prev_net_snapshot: Option<NetSnapshot>,
+    /// Per-interface (rx, tx) byte rates, oldest first.
+    interface_history: HashMap<String, VecDeque<(u64, u64)>>,
+    /// Rolling norms of key metrics for anomaly flagging.
+    baselines: HashMap<&'static str, Baseline>,
+    /// Per-process FD counts for leak detection.
+    fd_tracker: FdTracker,
+    listener_tracker: ListenerTracker,
+    /// Process groups restarted under a new PID, for the history and the badge.
+    restart_tracker: RestartTracker,
+    /// Grouping of the snapshots in `history`.
+    grouping: ProcessGrouping,
+    /// Last md/ZFS/LVM scan and when it ran.
+    storage: Option<(Instant, Vec<StorageArrayInfo>, Vec<LvmVolumeInfo>)>,
     collector: Box<dyn SystemCollector>,
+    /// Stands in for sysinfo under `--demo`.
+    demo: Option<DemoHost>,
 }
 
 struct MonitorUpdateResult {
@@ -41,13 +87,22 @@ struct MonitorUpdateResult {
     data: MonitorData,
 }
 
-impl Monitor {
-    pub fn new() -> Self {
+impl Default for Monitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MonitorWorkerState {
+    /// Enumerate processes, disks and interfaces. Slow on busy hosts, so it
+    /// runs on the worker thread rather than before the first paint.
+    fn new(demo: bool) -> Self {
+        if demo {
+            return Self::demo();
+        }
         let mut sys = System::new_all();
         sys.refresh_all();
         let core_count = sys.cpus().len() as f64;
-        let disks = Disks::new_with_refreshed_list();
-        let networks = Networks::new_with_refreshed_list();
 
         let collector: Box<dyn SystemCollector> = if cfg!(target_os = "macos") {
             Box::new(MacCollector::new())
@@ -56,19 +111,112 @@ impl Monitor {
         };
 
         Self {
-            ui_state: UIState::default(),
+            sys,
+            core_count,
+            history: VecDeque::new(),
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            prev_net_snapshot: None,
+            interface_history: HashMap::new(),
+            baselines: HashMap::new(),
+            fd_tracker: FdTracker::default(),
+            listener_tracker: ListenerTracker::default(),
+            restart_tracker: RestartTracker::default(),
+            grouping: ProcessGrouping::Parent,
+            storage: None,
+            collector,
+            demo: None,
+        }
+    }
+
+    /// Made-up host data (`--demo`); sysinfo is left empty.
+    fn demo() -> Self {
+        Self {
+            sys: System::new(),
+            core_count: crate::demo::CORES,
+            history: VecDeque::new(),
+            disks: Disks::new(),
+            networks: Networks::new(),
+            prev_net_snapshot: None,
+            interface_history: HashMap::new(),
+            baselines: HashMap::new(),
+            fd_tracker: FdTracker::default(),
+            listener_tracker: ListenerTracker::default(),
+            restart_tracker: RestartTracker::default(),
+            grouping: ProcessGrouping::Parent,
+            storage: Some((Instant::now(), Vec::new(), Vec::new())),
+            collector: Box::new(DemoCollector::new()),
+            demo: Some(DemoHost::new()),
+        }
+    }
+}
+
+impl Monitor {
+    /// Returns immediately; the worker state is built and the first snapshot
+    /// collected in the background, delivered through `poll_update`.
+    pub fn new() -> Self {
+        Self::start(false)
+    }
+
+    /// A monitor of the made-up host of `--demo`.
+    pub fn demo() -> Self {
+        Self::start(true)
+    }
+
+    /// A monitor that collects nothing, shown recorded snapshots (`--replay`).
+    pub fn replayed() -> Self {
+        Self::idle(UIState::default(), None)
+    }
+
+    fn start(demo: bool) -> Self {
+        let mut ui_state = UIState::default();
+        // The demo host has no auth log of its own to show
+        let auth_log = match (!demo).then(crate::authlog::follow) {
+            None => None,
+            Some(Ok(stream)) => {
+                ui_state.auth.source = Some(stream.source.clone());
+                Some(stream)
+            }
+            Some(Err(e)) => {
+                ui_state.auth.error = Some(e);
+                None
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let sort_column = ui_state.sort_column;
+        let grouping = ui_state.grouping;
+        thread::spawn(move || {
+            let mut worker_state = MonitorWorkerState::new(demo);
+            let data = worker_state.collect_snapshot(sort_column, grouping, &[]);
+            let _ = tx.send(MonitorUpdateResult { worker_state, data });
+        });
+        Self { update_receiver: Some(rx), ..Self::idle(ui_state, auth_log) }
+    }
+
+    /// No worker state, so `update` does nothing until one is handed over.
+    fn idle(ui_state: UIState, auth_log: Option<crate::authlog::AuthLogStream>) -> Self {
+        let (custom_sender, custom_receiver) = mpsc::channel();
+        Self {
+            ui_state,
             layout: Layout::default_layout(),
             last_data: None,
-            worker_state: Some(MonitorWorkerState {
-                sys,
-                core_count,
-                history: VecDeque::new(),
-                disks,
-                networks,
-                prev_net_snapshot: None,
-                collector,
-            }),
+            last_updated: None,
+            update_failed: false,
+            worker_state: None,
             update_receiver: None,
+            profile_receiver: None,
+            trace_receiver: None,
+            updates_receiver: None,
+            updates_checked: None,
+            auth_log,
+            auth_failures: AuthFailures::default(),
+            exec_snoop: None,
+            exec_events: ExecEvents::default(),
+            custom_lines: Vec::new(),
+            custom_started: HashMap::new(),
+            custom_sender,
+            custom_receiver,
         }
     }
 
@@ -81,16 +229,24 @@ impl Monitor {
             return;
         };
         let sort_column = self.ui_state.sort_column;
+        let grouping = self.ui_state.grouping;
+        let expanded: Vec<Pid> = self.ui_state.expanded_pids.iter().copied().collect();
         let (tx, rx) = mpsc::channel();
 
         thread::spawn(move || {
-            let data = worker_state.collect_snapshot(sort_column);
+            let data = worker_state.collect_snapshot(sort_column, grouping, &expanded);
             let _ = tx.send(MonitorUpdateResult { worker_state, data });
         });
 
         self.update_receiver = Some(rx);
     }
 
+    /// Show a recorded snapshot in place of a collected one.
+    pub fn show_replayed(&mut self, data: MonitorData) {
+        self.last_data = Some(data);
+        self.last_updated = Some(Instant::now());
+    }
+
     pub fn poll_update(&mut self) -> bool {
         let Some(ref rx) = self.update_receiver else {
             return false;
@@ -100,35 +256,383 @@ impl Monitor {
             Ok(result) => {
                 self.worker_state = Some(result.worker_state);
                 self.last_data = Some(result.data);
+                self.last_updated = Some(Instant::now());
                 self.update_receiver = None;
                 true
             }
             Err(mpsc::TryRecvError::Empty) => false,
             Err(mpsc::TryRecvError::Disconnected) => {
                 self.update_receiver = None;
+                self.update_failed = true;
+                false
+            }
+        }
+    }
+}
+
+impl Monitor {
+    /// Profile a process in the background and show the result in the
+    /// Profile panel. Ignored while a capture is already running.
+    pub fn start_profile(&mut self, pid: u32, name: &str) {
+        self.ui_state.panel = crate::model::SystemPanel::Profile;
+        if self.profile_receiver.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(crate::profiler::profile(pid));
+        });
+        self.profile_receiver = Some(rx);
+        self.ui_state.profile = Some(ProfileView {
+            pid,
+            name: name.to_string(),
+            started: Instant::now(),
+            state: ProfileState::Running,
+        });
+    }
+
+    /// Pick up a finished capture. Returns true when one completed.
+    pub fn poll_profile(&mut self) -> bool {
+        let Some(ref rx) = self.profile_receiver else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Profiler thread exited".to_string()),
+        };
+        self.profile_receiver = None;
+        if let Some(ref mut profile) = self.ui_state.profile {
+            profile.state = match result {
+                Ok(report) => ProfileState::Done(report),
+                Err(e) => ProfileState::Failed(e),
+            };
+        }
+        true
+    }
+
+    /// Trace a process's syscalls in the background and show the summary in
+    /// the Trace panel. Ignored while a trace is already running.
+    pub fn start_trace(&mut self, pid: u32, name: &str) {
+        self.ui_state.panel = crate::model::SystemPanel::Trace;
+        if self.trace_receiver.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(crate::tracer::trace(pid));
+        });
+        self.trace_receiver = Some(rx);
+        self.ui_state.trace = Some(TraceView {
+            pid,
+            name: name.to_string(),
+            started: Instant::now(),
+            state: TraceState::Running,
+        });
+    }
+
+    /// Start a background package update check when the last one is older
+    /// than updates::CHECK_INTERVAL.
+    pub fn check_updates(&mut self) {
+        if self.updates_receiver.is_some()
+            || self.updates_checked.is_some_and(|t| t.elapsed() < crate::updates::CHECK_INTERVAL)
+        {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(crate::updates::check());
+        });
+        self.updates_receiver = Some(rx);
+        self.updates_checked = Some(Instant::now());
+    }
+
+    /// Pick up a finished update check. Returns true when one completed.
+    pub fn poll_updates(&mut self) -> bool {
+        let Some(ref rx) = self.updates_receiver else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(status) => {
+                self.ui_state.updates = Some(status);
+                self.updates_receiver = None;
+                true
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.updates_receiver = None;
                 false
             }
         }
     }
+
+    /// Replace the custom summary line commands. Every line runs again at
+    /// the next tick, keeping its last output until then.
+    pub fn set_custom_lines(&mut self, lines: Vec<CustomLineConfig>) {
+        self.ui_state.custom_lines.retain(|name, _| lines.iter().any(|l| &l.name == name));
+        self.custom_started.retain(|_, (_, running)| *running);
+        self.custom_lines = lines;
+    }
+
+    /// Start the command of every custom summary line that is due and not
+    /// still running from last time.
+    pub fn run_custom_lines(&mut self) {
+        for line in &self.custom_lines {
+            let due = match self.custom_started.get(&line.name) {
+                Some((_, true)) => false,
+                Some((started, false)) => started.elapsed() >= Duration::from_secs(line.interval_secs),
+                None => true,
+            };
+            if !due {
+                continue;
+            }
+            self.custom_started.insert(line.name.clone(), (Instant::now(), true));
+            let (name, command, tx) = (line.name.clone(), line.command.clone(), self.custom_sender.clone());
+            thread::spawn(move || {
+                let _ = tx.send((name, crate::checks::run(&command)));
+            });
+        }
+    }
+
+    /// Pick up finished custom line commands. Returns true when any did.
+    pub fn poll_custom_lines(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((name, output)) = self.custom_receiver.try_recv() {
+            if let Some((_, running)) = self.custom_started.get_mut(&name) {
+                *running = false;
+            }
+            // A line removed from the config while its command ran
+            if self.custom_lines.iter().any(|l| l.name == name) {
+                self.ui_state.custom_lines.insert(name, output);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Take new login failures from the auth log and refresh the summary.
+    /// Returns true when it changed.
+    pub fn poll_auth(&mut self) -> bool {
+        let Some(ref stream) = self.auth_log else {
+            return false;
+        };
+        let now = Instant::now();
+        for remote in stream.receiver.try_iter() {
+            self.auth_failures.record(now, remote);
+        }
+        let summary = AuthSummary {
+            source: self.ui_state.auth.source.clone(),
+            ..self.auth_failures.summarize(now)
+        };
+        let changed = summary != self.ui_state.auth;
+        self.ui_state.auth = summary;
+        changed
+    }
+
+    /// Open the exec panel and start snooping on process starts.
+    pub fn start_exec_snoop(&mut self) {
+        self.ui_state.panel = crate::model::SystemPanel::Execs;
+        self.exec_events.clear();
+        self.ui_state.execs = match crate::execsnoop::follow() {
+            Ok(stream) => {
+                let summary = ExecSummary { source: Some(stream.source.clone()), ..Default::default() };
+                self.exec_snoop = Some(stream);
+                summary
+            }
+            Err(e) => ExecSummary { error: Some(e), ..Default::default() },
+        };
+    }
+
+    /// Take new process starts and refresh the summary, stopping the snoop
+    /// once the panel is closed. Returns true when the summary changed.
+    pub fn poll_execs(&mut self) -> bool {
+        if self.ui_state.panel != crate::model::SystemPanel::Execs {
+            self.exec_snoop = None;
+            return false;
+        }
+        let Some(ref stream) = self.exec_snoop else {
+            return false;
+        };
+        let now = Instant::now();
+        let mut ended = false;
+        loop {
+            match stream.receiver.try_recv() {
+                Ok(command) => self.exec_events.record(now, command),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    ended = true;
+                    break;
+                }
+            }
+        }
+        let mut summary = ExecSummary {
+            source: self.ui_state.execs.source.clone(),
+            ..self.exec_events.summarize(now)
+        };
+        if ended {
+            summary.error = Some(format!("{} exited", summary.source.as_deref().unwrap_or("the snoop")));
+            self.exec_snoop = None;
+        }
+        let changed = summary != self.ui_state.execs;
+        self.ui_state.execs = summary;
+        changed
+    }
+
+    /// Pick up a finished trace. Returns true when one completed.
+    pub fn poll_trace(&mut self) -> bool {
+        let Some(ref rx) = self.trace_receiver else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Tracer thread exited".to_string()),
+        };
+        self.trace_receiver = None;
+        if let Some(ref mut trace) = self.ui_state.trace {
+            trace.state = match result {
+                Ok(report) => TraceState::Done(report),
+                Err(e) => TraceState::Failed(e),
+            };
+        }
+        true
+    }
 }
 
 impl MonitorWorkerState {
-    fn collect_snapshot(&mut self, sort_column: crate::model::SortColumn) -> MonitorData {
-        self.sys.refresh_all();
-        self.disks.refresh(true);
-        self.networks.refresh(true);
+    /// sitrep's own process and its children, from the last refresh.
+    fn self_usage(&self) -> Option<SelfUsage> {
+        let pid = sysinfo::get_current_pid().ok()?;
+        let own = self.sys.process(pid)?;
+        let mut children: HashMap<String, usize> = HashMap::new();
+        let mut children_cpu = 0.0;
+        for p in self.sys.processes().values() {
+            if p.parent() == Some(pid) && p.thread_kind().is_none() {
+                *children.entry(p.name().to_string_lossy().into_owned()).or_default() += 1;
+                children_cpu += p.cpu_usage() as f64;
+            }
+        }
+        let mut children: Vec<(String, usize)> = children.into_iter().collect();
+        children.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Some(SelfUsage {
+            pid: pid.as_u32(),
+            cpu_percent: own.cpu_usage() as f64,
+            rss_bytes: own.memory(),
+            threads: own.tasks().map(|t| t.len()),
+            children,
+            children_cpu_percent: children_cpu,
+        })
+    }
+
+    /// The daemon keeping the entropy pool topped up, if one is running.
+    fn entropy_feeder(&self) -> Option<String> {
+        self.sys
+            .processes()
+            .values()
+            .map(|p| p.name().to_string_lossy())
+            .find(|name| ["rngd", "haveged", "jitterentropy-rngd"].contains(&name.as_ref()))
+            .map(|name| name.into_owned())
+    }
+
+    /// dockerd, containerd and the shims, from the last refresh.
+    fn daemon_processes(&self) -> Vec<DaemonProcessUsage> {
+        let mut usage: Vec<DaemonProcessUsage> = Vec::new();
+        for p in self.sys.processes().values() {
+            if p.thread_kind().is_some() {
+                continue;
+            }
+            let Some(name) = daemon_process_name(&p.name().to_string_lossy()) else { continue };
+            let pos = usage.iter().position(|u| u.name == name).unwrap_or_else(|| {
+                usage.push(DaemonProcessUsage { name: name.to_string(), threads: Some(0), ..Default::default() });
+                usage.len() - 1
+            });
+            let entry = &mut usage[pos];
+            entry.count += 1;
+            entry.cpu_percent += p.cpu_usage() as f64;
+            entry.rss_bytes += p.memory();
+            entry.threads = entry.threads.zip(p.tasks().map(|t| t.len())).map(|(a, b)| a + b);
+        }
+        let rank = |name: &str| ["dockerd", "containerd", "containerd-shim"].iter().position(|n| *n == name);
+        usage.sort_by_key(|u| rank(&u.name));
+        usage
+    }
+
+    fn collect_snapshot(
+        &mut self,
+        sort_column: crate::model::SortColumn,
+        grouping: ProcessGrouping,
+        expanded: &[Pid],
+    ) -> MonitorData {
+        let started = Instant::now();
+        if self.demo.is_none() {
+            self.sys.refresh_all();
+            self.disks.refresh(true);
+            self.networks.refresh(true);
+        }
 
         let now_chrono = Local::now();
         let now_instant = Instant::now();
-        let load_avg_raw = System::load_average();
+        let load_avg = match self.demo {
+            Some(ref demo) => demo.load_average(),
+            None => {
+                let raw = System::load_average();
+                (raw.one, raw.five, raw.fifteen)
+            }
+        };
 
         let net_stats = self.collector.get_process_network_stats();
-        let fd_info = self.collector.get_fd_stats();
+        let mut fd_info = self.collector.get_fd_stats();
+        self.fd_tracker.observe(now_instant, &fd_info.per_process);
+        fd_info.leaks = self
+            .fd_tracker
+            .leaks(|pid| self.collector.get_process_limits(pid).and_then(|l| l.nofile.soft));
+        let process_limits = expanded
+            .iter()
+            .filter_map(|pid| Some((pid.as_u32(), self.collector.get_process_limits(pid.as_u32())?)))
+            .collect();
         let socket_info = self.collector.get_socket_stats();
+        let listener_changes = self.listener_tracker.observe(now_instant, &socket_info.listeners);
+        if self.demo.is_none() && self.storage.as_ref().is_none_or(|(at, _, _)| now_instant.duration_since(*at) >= STORAGE_REFRESH) {
+            self.storage = Some((now_instant, crate::storage::collect(), crate::storage::collect_lvm()));
+        }
+        let (storage_arrays, lvm_volumes) = self
+            .storage
+            .as_ref()
+            .map(|(_, arrays, lvm)| (arrays.clone(), lvm.clone()))
+            .unwrap_or_default();
+        let conntrack = self.collector.get_conntrack();
+        let links = self.collector.get_link_stats();
+        let pressure = self.collector.get_pressure();
+        let cpu_breakdown = self.collector.get_cpu_breakdown();
+        let oom = self.collector.get_oom_info();
+        let interrupts = self.collector.get_interrupts();
+        let entropy = self.collector.get_entropy().map(|available| EntropyInfo { available, feeder: self.entropy_feeder() });
+        let memory_detail = self.collector.get_memory_detail();
         let csw_info = self.collector.get_context_switches();
         let disk_busy = self.collector.get_disk_io_pct();
 
-        let live_groups = process::build_live_groups(&self.sys, &net_stats);
+        let mut live_groups = match self.demo {
+            Some(ref demo) => demo.process_groups(),
+            None => process::build_live_groups(&self.sys, &net_stats),
+        };
+        if grouping == ProcessGrouping::Unit {
+            live_groups = process::group_by_unit(live_groups, |pid| self.collector.get_process_unit(pid.as_u32()));
+        }
+        // Averaging parent and unit groups together would double count
+        if grouping != self.grouping {
+            self.history.clear();
+            self.restart_tracker.reset();
+            self.grouping = grouping;
+        }
+        // A restarted service's old PID and a reused PID's earlier command
+        // would otherwise stay in the averages for the whole window
+        let user_usage = process::usage_by_user(&live_groups);
+        let stale = self.restart_tracker.observe(now_instant, &live_groups);
+        if !stale.is_empty() {
+            for (_, groups) in &mut self.history {
+                groups.retain(|pid, _| !stale.contains(pid));
+            }
+        }
 
         self.history.push_back((now_instant, live_groups));
         if self.history.len() > 20 {
@@ -136,16 +640,42 @@ impl MonitorWorkerState {
         }
 
         let historical_top = process::compute_top_processes(&self.history, sort_column);
+        let process_priorities = historical_top
+            .iter()
+            .filter_map(|g| Some((g.pid.as_u32(), self.collector.get_process_priority(g.pid.as_u32())?)))
+            .collect();
+        // Only listed rows; reading every process's cgroup each tick adds up
+        let process_containers = process::listed_pids(&historical_top, expanded)
+            .into_iter()
+            .filter_map(|pid| {
+                let unit = self.collector.get_process_unit(pid.as_u32())?;
+                Some((pid.as_u32(), unit.strip_prefix("container ")?.to_string()))
+            })
+            .collect();
 
-        let memory = MemoryInfo {
-            total: self.sys.total_memory(),
-            used: self.sys.used_memory(),
-            available: self.sys.available_memory(),
-            swap_total: self.sys.total_swap(),
-            swap_used: self.sys.used_swap(),
+        let memory = match self.demo {
+            Some(ref demo) => {
+                let ((total, used), (swap_total, swap_used)) = (demo.memory(), demo.swap());
+                MemoryInfo {
+                    total,
+                    used,
+                    available: total - used,
+                    swap_total,
+                    swap_used,
+                    breakdown: self.collector.get_memory_breakdown(),
+                }
+            }
+            None => MemoryInfo {
+                total: self.sys.total_memory(),
+                used: self.sys.used_memory(),
+                available: self.sys.available_memory(),
+                swap_total: self.sys.total_swap(),
+                swap_used: self.sys.used_swap(),
+                breakdown: self.collector.get_memory_breakdown(),
+            },
         };
 
-        let mut disk_space = Vec::new();
+        let mut disk_space = self.demo.as_ref().map(DemoHost::disks).unwrap_or_default();
         for disk in &self.disks {
             let total = disk.total_space() as f64 / 1_000_000_000.0;
             let available = disk.available_space() as f64 / 1_000_000_000.0;
@@ -165,7 +695,7 @@ impl MonitorWorkerState {
         }
 
         let mut interfaces = Vec::new();
-        let mut current_interfaces = Vec::new();
+        let mut current_interfaces = self.demo.as_ref().map(DemoHost::interface_counters).unwrap_or_default();
         for (name, data) in &self.networks {
             current_interfaces.push((name.clone(), data.received(), data.transmitted()));
         }
@@ -184,6 +714,12 @@ impl MonitorWorkerState {
                         let rx_rate = (rx_diff as f64 / duration) as u64;
                         let tx_rate = (tx_diff as f64 / duration) as u64;
 
+                        let samples = self.interface_history.entry(name.clone()).or_default();
+                        samples.push_back((rx_rate, tx_rate));
+                        if samples.len() > INTERFACE_HISTORY_LEN {
+                            samples.pop_front();
+                        }
+
                         if rx_rate > 0 || tx_rate > 0 {
                             interfaces.push(NetworkInterfaceInfo {
                                 name: name.clone(),
@@ -195,20 +731,35 @@ impl MonitorWorkerState {
                 }
             }
         }
+        self.interface_history
+            .retain(|name, _| current_interfaces.iter().any(|(n, _, _)| n == name));
         self.prev_net_snapshot = Some((now_instant, current_interfaces));
 
+        let mut history: Vec<InterfaceHistory> = self
+            .interface_history
+            .iter()
+            .filter(|(_, samples)| samples.iter().any(|&(rx, tx)| rx > 0 || tx > 0))
+            .map(|(name, samples)| InterfaceHistory {
+                name: name.clone(),
+                samples: samples.iter().copied().collect(),
+            })
+            .collect();
+        history.sort_by(|a, b| a.name.cmp(&b.name));
+
         let network_info = NetworkInfo {
             interfaces,
+            links,
+            history,
             top_bandwidth_processes: Vec::new(),
             established: socket_info.established,
             time_wait: socket_info.time_wait,
             close_wait: socket_info.close_wait,
         };
 
-        MonitorData {
+        let mut data = MonitorData {
             time: now_chrono.format("%H:%M:%S").to_string(),
             core_count: self.core_count,
-            load_avg: (load_avg_raw.one, load_avg_raw.five, load_avg_raw.fifteen),
+            load_avg,
             historical_top,
             disk_space,
             disk_busy_pct: disk_busy,
@@ -217,6 +768,51 @@ impl MonitorWorkerState {
             fd_info,
             context_switches: csw_info,
             socket_overview: socket_info,
-        }
+            conntrack,
+            pressure,
+            cpu_breakdown,
+            oom,
+            entropy,
+            interrupts,
+            anomalies: Vec::new(),
+            process_limits,
+            process_priorities,
+            process_containers,
+            user_usage,
+            restarted_processes: self.restart_tracker.restarted(),
+            listener_changes,
+            storage_arrays,
+            lvm_volumes,
+            memory_detail,
+            self_usage: self.self_usage(),
+            daemon_processes: self.daemon_processes(),
+        };
+        data.anomalies = detect_anomalies(&mut self.baselines, &data);
+        tracing::debug!(
+            "Collected in {}ms: {} process groups, {} disks, {} anomalies; unavailable: {}",
+            started.elapsed().as_millis(),
+            data.historical_top.len(),
+            data.disk_space.len(),
+            data.anomalies.len(),
+            unavailable_sections(&data)
+        );
+        data
     }
 }
+
+/// The optional sections this host gave nothing for, e.g. no PSI on an old
+/// kernel, so a debug log shows why a panel is missing.
+fn unavailable_sections(data: &MonitorData) -> String {
+    let missing: Vec<&str> = [
+        ("conntrack", data.conntrack.is_none()),
+        ("cpu breakdown", data.cpu_breakdown.is_none()),
+        ("oom", data.oom.is_none()),
+        ("entropy", data.entropy.is_none()),
+        ("interrupts", data.interrupts.is_none()),
+        ("memory detail", data.memory_detail.is_none()),
+    ]
+    .into_iter()
+    .filter_map(|(name, missing)| missing.then_some(name))
+    .collect();
+    if missing.is_empty() { "none".to_string() } else { missing.join(", ") }
+}
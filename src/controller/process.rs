@@ -5,7 +5,7 @@ use std::collections::VecDeque;
 
 use sysinfo::{Pid, System};
 
-use crate::model::{ProcessGroup, ProcessInfo, SortColumn};
+use crate::model::{ProcessGroup, ProcessInfo, SortColumn, UserUsage};
 
 /// Build process groups from the current system snapshot.
 pub fn build_live_groups(
@@ -67,6 +67,91 @@ pub fn build_live_groups(
     live_groups
 }
 
+/// Sum the processes of `groups` per user, busiest (CPU, then memory) first.
+/// Processes whose owner can't be resolved count as "(unknown)".
+pub fn usage_by_user(groups: &HashMap<Pid, ProcessGroup>) -> Vec<UserUsage> {
+    let mut by_user: HashMap<&str, UserUsage> = HashMap::new();
+    for child in groups.values().flat_map(|g| &g.children) {
+        let user = if child.user.is_empty() { "(unknown)" } else { child.user.as_str() };
+        let usage = by_user.entry(user).or_insert_with(|| UserUsage {
+            user: user.to_string(),
+            processes: 0,
+            cpu: 0.0,
+            mem: 0,
+        });
+        usage.processes += 1;
+        usage.cpu += child.cpu as f64;
+        usage.mem += child.mem;
+    }
+    let mut users: Vec<UserUsage> = by_user.into_values().collect();
+    users.sort_by(|a, b| {
+        b.cpu
+            .partial_cmp(&a.cpu)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.mem.cmp(&a.mem))
+            .then_with(|| a.user.cmp(&b.user))
+    });
+    users
+}
+
+/// PIDs shown in the process table: each group, and the children of the
+/// expanded ones.
+pub fn listed_pids(groups: &[ProcessGroup], expanded: &[Pid]) -> Vec<Pid> {
+    let mut pids = Vec::new();
+    for g in groups {
+        pids.push(g.pid);
+        if expanded.contains(&g.pid) {
+            pids.extend(g.children.iter().map(|c| c.pid));
+        }
+    }
+    pids
+}
+
+/// Regroup the processes of `groups` by the unit `unit_of` names for each
+/// PID instead of by parent. Each unit group is keyed by its lowest PID,
+/// usually the main process, so it stays stable across snapshots.
+pub fn group_by_unit(
+    groups: HashMap<Pid, ProcessGroup>,
+    unit_of: impl Fn(Pid) -> Option<String>,
+) -> HashMap<Pid, ProcessGroup> {
+    let mut by_unit: HashMap<String, ProcessGroup> = HashMap::new();
+    for child in groups.into_values().flat_map(|g| g.children) {
+        let unit = unit_of(child.pid).unwrap_or_else(|| "(no unit)".to_string());
+        let group = by_unit.entry(unit.clone()).or_insert_with(|| ProcessGroup {
+            pid: child.pid,
+            user: child.user.clone(),
+            cpu: 0.0,
+            mem: 0,
+            read_bytes: 0,
+            written_bytes: 0,
+            net_rx_bytes: 0,
+            net_tx_bytes: 0,
+            child_count: 0,
+            name: unit,
+            children: Vec::new(),
+        });
+        if child.pid < group.pid {
+            group.pid = child.pid;
+            group.user = child.user.clone();
+        }
+        group.cpu += child.cpu as f64;
+        group.mem += child.mem;
+        group.read_bytes += child.read_bytes;
+        group.written_bytes += child.written_bytes;
+        group.net_rx_bytes += child.net_rx_bytes;
+        group.net_tx_bytes += child.net_tx_bytes;
+        group.child_count += 1;
+        group.children.push(child);
+    }
+    by_unit
+        .into_values()
+        .map(|mut g| {
+            g.children.sort_by_key(|c| c.pid);
+            (g.pid, g)
+        })
+        .collect()
+}
+
 /// Compute top processes from history, averaged and sorted by the given column.
 pub fn compute_top_processes(
     history: &VecDeque<(std::time::Instant, HashMap<Pid, ProcessGroup>)>,
@@ -129,9 +214,9 @@ mod tests {
 
     use sysinfo::Pid;
 
-    use crate::model::{ProcessGroup, SortColumn};
+    use crate::model::{ProcessGroup, ProcessInfo, SortColumn};
 
-    use super::compute_top_processes;
+    use super::{compute_top_processes, group_by_unit, listed_pids, usage_by_user};
 
     #[test]
     fn compute_top_processes_empty_history() {
@@ -198,4 +283,91 @@ mod tests {
         assert_eq!(result[1].name, "p3");
         assert_eq!(result[2].name, "p1");
     }
+
+    #[test]
+    fn group_by_unit_merges_parents_into_units() {
+        let process = |pid: usize, cpu: f32| ProcessInfo {
+            pid: Pid::from(pid),
+            user: "www".into(),
+            cpu,
+            mem: 100,
+            read_bytes: 0,
+            written_bytes: 0,
+            net_rx_bytes: 0,
+            net_tx_bytes: 0,
+            name: format!("p{}", pid),
+        };
+        let parent = |pid: usize, children: Vec<ProcessInfo>| ProcessGroup {
+            pid: Pid::from(pid),
+            user: String::new(),
+            cpu: 0.0,
+            mem: 0,
+            read_bytes: 0,
+            written_bytes: 0,
+            net_rx_bytes: 0,
+            net_tx_bytes: 0,
+            child_count: 0,
+            name: String::new(),
+            children,
+        };
+        let mut groups = HashMap::new();
+        groups.insert(Pid::from(1usize), parent(1, vec![process(20, 10.0), process(7, 5.0)]));
+        groups.insert(Pid::from(20usize), parent(20, vec![process(21, 2.5), process(30, 1.0)]));
+
+        let units = group_by_unit(groups, |pid| match pid.as_u32() {
+            7 | 20 | 21 => Some("nginx.service".to_string()),
+            _ => None,
+        });
+        assert_eq!(units.len(), 2);
+        let nginx = &units[&Pid::from(7usize)];
+        assert_eq!(nginx.name, "nginx.service");
+        assert_eq!(nginx.child_count, 3);
+        assert_eq!(nginx.mem, 300);
+        assert!((nginx.cpu - 17.5).abs() < 0.01);
+        assert_eq!(nginx.children[0].pid, Pid::from(7usize));
+        assert_eq!(units[&Pid::from(30usize)].name, "(no unit)");
+
+        // Children are looked up only under expanded groups
+        let mut groups: Vec<ProcessGroup> = units.into_values().collect();
+        groups.sort_by_key(|g| g.pid);
+        let pids = |expanded: &[Pid]| -> Vec<u32> { listed_pids(&groups, expanded).iter().map(|p| p.as_u32()).collect() };
+        assert_eq!(pids(&[]), vec![7, 30]);
+        assert_eq!(pids(&[Pid::from(7usize)]), vec![7, 7, 20, 21, 30]);
+    }
+
+    #[test]
+    fn usage_by_user_sums_processes_busiest_first() {
+        let process = |pid: usize, user: &str, cpu: f32, mem: u64| ProcessInfo {
+            pid: Pid::from(pid),
+            user: user.into(),
+            cpu,
+            mem,
+            read_bytes: 0,
+            written_bytes: 0,
+            net_rx_bytes: 0,
+            net_tx_bytes: 0,
+            name: format!("p{}", pid),
+        };
+        let group = |pid: usize, children: Vec<ProcessInfo>| ProcessGroup {
+            pid: Pid::from(pid),
+            user: String::new(),
+            cpu: 0.0,
+            mem: 0,
+            read_bytes: 0,
+            written_bytes: 0,
+            net_rx_bytes: 0,
+            net_tx_bytes: 0,
+            child_count: children.len(),
+            name: String::new(),
+            children,
+        };
+        let groups = HashMap::from([
+            (Pid::from(1usize), group(1, vec![process(10, "root", 2.0, 100), process(11, "alice", 40.0, 500)])),
+            (Pid::from(2usize), group(2, vec![process(20, "alice", 25.0, 300), process(21, "", 0.0, 50)])),
+        ]);
+        let users = usage_by_user(&groups);
+        let summary: Vec<(&str, usize, u64)> = users.iter().map(|u| (u.user.as_str(), u.processes, u.mem)).collect();
+        assert_eq!(summary, vec![("alice", 2, 800), ("root", 1, 100), ("(unknown)", 1, 50)]);
+        assert!((users[0].cpu - 65.0).abs() < 0.01);
+    }
 }
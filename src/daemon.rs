@@ -0,0 +1,293 @@
+//! Headless daemon mode (`--daemon`): collectors and the alert engine run
+//! continuously without a terminal, current state is served over HTTP, and
+//! the process integrates with systemd (`Type=notify`, `WatchdogSec=`).
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::os::unix::net::UnixDatagram;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::alerts::Alert;
+use crate::app::App;
+use crate::config::Config;
+
+/// What the HTTP endpoints serve, rebuilt whenever a monitor delivers data.
+#[derive(Default)]
+struct Snapshot {
+    metrics: String,
+    alerts: String,
+}
+
+/// Run without a TUI until SIGTERM/SIGINT.
+pub fn run(should_quit: Arc<AtomicBool>, cli: &crate::cli::Cli, config: Config) -> io::Result<()> {
+    let rt = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .worker_threads(2)
+            .build()
+            .expect("Failed to create tokio runtime"),
+    );
+
+    let listener = TcpListener::bind(&cli.listen)
+        .map_err(|e| io::Error::new(e.kind(), format!("Failed to listen on {}: {}", cli.listen, e)))?;
+    tracing::info!("Daemon mode, serving /metrics and /api/alerts on {}", cli.listen);
+
+    let mut app = App::new(Arc::clone(&rt), cli.interval(&config), cli.no_docker, None, config);
+    if cli.syslog {
+        app.enable_syslog();
+    }
+    if let Some(path) = cli.config_path() {
+        app.watch_config(path);
+    }
+    app.watch_signals();
+
+    let snapshot = Arc::new(Mutex::new(Snapshot::default()));
+    {
+        let snapshot = Arc::clone(&snapshot);
+        std::thread::spawn(move || serve(listener, snapshot));
+    }
+
+    sd_notify("READY=1");
+    let watchdog = watchdog_interval();
+    let mut last_watchdog = Instant::now();
+
+    while !should_quit.load(Ordering::Relaxed) {
+        let mut changed = app.poll_startup();
+        changed |= app.poll_config();
+        changed |= app.poll_signals();
+        if app.monitor.poll_update() {
+            app.record_system_history();
+            app.check_self_usage();
+            changed = true;
+        }
+        if app.docker_monitor.poll_update() {
+            app.record_container_history();
+            changed = true;
+        }
+        if app.swarm_monitor.poll_update() {
+            app.record_service_history();
+            changed = true;
+        }
+        changed |= app.health_monitor.poll_update();
+        changed |= app.docker_monitor.poll_image_drift();
+        changed |= app.monitor.poll_auth();
+        // Logs the outcome of scheduled restarts
+        app.swarm_monitor.poll_action();
+        changed |= tick(&mut app);
+
+        if changed {
+            let alerts = app.current_alerts();
+            let metrics = crate::metrics::render(
+                app.monitor.last_data.as_ref(),
+                &app.docker_monitor.containers,
+                &alerts,
+            );
+            let mut snapshot = snapshot.lock().unwrap_or_else(|e| e.into_inner());
+            snapshot.metrics = metrics;
+            snapshot.alerts = alerts_json(&alerts);
+        }
+
+        if let Some(interval) = watchdog
+            && last_watchdog.elapsed() >= interval {
+                sd_notify("WATCHDOG=1");
+                last_watchdog = Instant::now();
+            }
+
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    sd_notify("STOPPING=1");
+    Ok(())
+}
+
+/// Refresh every monitor once per tick; there is no active tab to favor.
+fn tick(app: &mut App) -> bool {
+    if app.last_tick.elapsed() < app.effective_tick_rate() {
+        return false;
+    }
+    app.tick_counter += 1;
+
+    app.monitor.update();
+    if app.docker_monitor.is_available() {
+        app.docker_monitor.update();
+    }
+    if app.swarm_monitor.is_swarm() {
+        app.swarm_monitor.update();
+    } else if app.tick_counter.is_multiple_of(10) {
+        app.swarm_monitor.recheck_swarm();
+    }
+    app.health_monitor.update();
+    app.run_schedule();
+    if app.config.containers.check_registry_digest {
+        app.docker_monitor.check_image_drift();
+    }
+    app.forward_alerts();
+    crate::cmd::reap();
+
+    app.last_tick = Instant::now();
+    true
+}
+
+fn alerts_json(alerts: &[Alert]) -> String {
+    crate::alerts::to_json(alerts).to_string()
+}
+
+fn serve(listener: TcpListener, snapshot: Arc<Mutex<Snapshot>>) {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = handle_connection(stream, &snapshot) {
+                    tracing::debug!("HTTP connection error: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("HTTP accept failed: {}", e),
+        }
+    }
+}
+
+/// Most a request line and its headers may take together.
+const MAX_REQUEST_BYTES: u64 = 8192;
+/// Time a client gets to send the whole request, however it trickles it.
+const REQUEST_DEADLINE: Duration = Duration::from_secs(5);
+
+/// A socket whose reads fail once `deadline` passes, rather than each read
+/// getting a fresh timeout.
+struct DeadlineReader {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "request not received in time"));
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+fn handle_connection(stream: TcpStream, snapshot: &Mutex<Snapshot>) -> io::Result<()> {
+    stream.set_write_timeout(Some(Duration::from_secs(2)))?;
+    let deadline = Instant::now() + REQUEST_DEADLINE;
+    let mut reader = BufReader::new(DeadlineReader { stream: stream.try_clone()?, deadline }.take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain headers up to the blank line; bodies are not accepted.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+    // The cap ran out before the blank line that ends the headers
+    let too_large = reader.get_ref().limit() == 0 && header.is_empty();
+
+    let (status, content_type, body) = if too_large {
+        ("431 Request Header Fields Too Large", "text/plain", "request too large\n".to_string())
+    } else {
+        let snapshot = snapshot.lock().unwrap_or_else(|e| e.into_inner());
+        route(&request_line, &snapshot)
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn route(request_line: &str, snapshot: &Snapshot) -> (&'static str, &'static str, String) {
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let path = target.split('?').next().unwrap_or("");
+    if method != "GET" {
+        return ("405 Method Not Allowed", "text/plain", "method not allowed\n".to_string());
+    }
+    match path {
+        "/metrics" => ("200 OK", "text/plain; version=0.0.4", snapshot.metrics.clone()),
+        "/api/alerts" => ("200 OK", "application/json", snapshot.alerts.clone()),
+        "/healthz" => ("200 OK", "text/plain", "ok\n".to_string()),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    }
+}
+
+/// Send a state string to systemd's notify socket; a no-op when not
+/// started by systemd with `Type=notify`.
+fn sd_notify(state: &str) {
+    let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return;
+    };
+    let Ok(socket) = UnixDatagram::unbound() else {
+        return;
+    };
+    let path = path.to_string_lossy().into_owned();
+    let result = if let Some(name) = path.strip_prefix('@') {
+        send_abstract(&socket, name, state)
+    } else {
+        socket.send_to(state.as_bytes(), &path).map(|_| ())
+    };
+    if let Err(e) = result {
+        tracing::warn!("sd_notify({}) failed: {}", state, e);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn send_abstract(socket: &UnixDatagram, name: &str, state: &str) -> io::Result<()> {
+    use std::os::linux::net::SocketAddrExt;
+    let addr = std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes())?;
+    socket.send_to_addr(state.as_bytes(), &addr).map(|_| ())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send_abstract(_socket: &UnixDatagram, _name: &str, _state: &str) -> io::Result<()> {
+    Err(io::Error::other("abstract sockets are Linux-only"))
+}
+
+/// Ping interval when systemd's watchdog is enabled for this process:
+/// half of `WATCHDOG_USEC`, as sd_watchdog_enabled(3) recommends.
+fn watchdog_interval() -> Option<Duration> {
+    if let Ok(pid) = std::env::var("WATCHDOG_PID")
+        && pid.parse::<u32>().ok() != Some(std::process::id()) {
+            return None;
+        }
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec / 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_known_paths_only() {
+        let snapshot = Snapshot {
+            metrics: "sitrep_cpu_cores 4\n".into(),
+            alerts: "[]".into(),
+        };
+        let (status, _, body) = route("GET /metrics HTTP/1.1\r\n", &snapshot);
+        assert_eq!((status, body.as_str()), ("200 OK", "sitrep_cpu_cores 4\n"));
+        let (_, content_type, body) = route("GET /api/alerts?x=1 HTTP/1.1\r\n", &snapshot);
+        assert_eq!((content_type, body.as_str()), ("application/json", "[]"));
+        assert_eq!(route("GET /nope HTTP/1.1\r\n", &snapshot).0, "404 Not Found");
+        assert_eq!(route("POST /metrics HTTP/1.1\r\n", &snapshot).0, "405 Method Not Allowed");
+    }
+
+    #[test]
+    fn oversized_requests_are_cut_off() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        client.write_all(&[b'a'; MAX_REQUEST_BYTES as usize]).unwrap();
+
+        handle_connection(server, &Mutex::new(Snapshot::default())).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 431 "), "{}", response);
+    }
+}
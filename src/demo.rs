@@ -0,0 +1,590 @@
+//! Made-up data for `--demo`: a busy 8-core host, a compose project's worth
+//! of containers and a five-node Swarm, so the UI can be shown, screenshot
+//! and exercised without a Docker host. Every value is a function of the
+//! seconds since the demo started, drifting on slow sine waves, and a
+//! service periodically drops a replica so the warnings have something to
+//! say. Nothing here reads the real host.
+
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sysinfo::Pid;
+use tokio::sync::mpsc;
+
+use crate::collectors::SystemCollector;
+use crate::model::{
+    ConntrackInfo, ContextSwitchInfo, CpuBreakdown, DiskSpaceInfo, DockerContainerInfo, FdInfo, InterruptInfo,
+    LinkInfo, MemoryBreakdown, MemoryDetail, OomInfo, PressureInfo, ProcessGroup, ProcessInfo, ProcessLimits, ProcessPriority,
+    PublishedPort, SocketOverviewInfo, SwarmClusterInfo, SwarmNodeInfo, SwarmServiceInfo, SwarmTaskInfo,
+};
+use crate::swarm::LogStreamHandle;
+
+/// Cores of the demo host.
+pub const CORES: f64 = 8.0;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+const MIB: u64 = 1024 * 1024;
+
+/// How often a demo log stream prints a line.
+const LOG_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Seconds of each cycle in which `shop_api` runs a replica short.
+const DEGRADED_PERIOD: u64 = 90;
+const DEGRADED_FOR: u64 = 15;
+
+/// A value moving between `lo` and `hi` once every `period` seconds.
+fn wave(t: f64, period: f64, lo: f64, hi: f64) -> f64 {
+    lo + (hi - lo) * (0.5 + 0.5 * (TAU * t / period).sin())
+}
+
+/// Integral of `wave` from 0 to `t`: a byte counter growing at that rate.
+fn wave_total(t: f64, period: f64, lo: f64, hi: f64) -> u64 {
+    let w = TAU / period;
+    ((lo + hi) / 2.0 * t + (hi - lo) / 2.0 * (1.0 - (w * t).cos()) / w) as u64
+}
+
+/// (pid, name, user, unit, CPU range, MiB, children) of a demo process.
+type DemoProcess = (u32, &'static str, &'static str, &'static str, (f64, f64), u64, usize);
+
+const PROCESSES: &[DemoProcess] = &[
+    (1, "systemd", "root", "init.scope", (0.0, 0.3), 12, 0),
+    (812, "sshd", "root", "ssh.service", (0.0, 0.2), 8, 2),
+    (1040, "containerd", "root", "containerd.service", (1.0, 4.0), 96, 14),
+    (1133, "dockerd", "root", "docker.service", (2.0, 9.0), 184, 3),
+    (2210, "postgres", "postgres", "container shop_db", (20.0, 95.0), 2400, 18),
+    (2388, "java", "app", "container shop_api", (60.0, 240.0), 3800, 0),
+    (2471, "node", "app", "container shop_web", (10.0, 60.0), 620, 4),
+    (2519, "nginx", "www-data", "container shop_proxy", (2.0, 14.0), 48, 8),
+    (2604, "redis-server", "redis", "container shop_cache", (3.0, 18.0), 310, 0),
+    (2733, "prometheus", "nobody", "container monitoring_prometheus", (4.0, 22.0), 900, 0),
+    (2790, "grafana", "grafana", "container monitoring_grafana", (0.5, 6.0), 210, 0),
+];
+
+/// The demo host's processes, disks, memory and interfaces.
+pub struct DemoHost {
+    started: Instant,
+}
+
+impl Default for DemoHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DemoHost {
+    pub fn new() -> Self {
+        Self { started: Instant::now() }
+    }
+
+    fn secs(&self) -> f64 {
+        self.started.elapsed().as_secs_f64()
+    }
+
+    pub fn load_average(&self) -> (f64, f64, f64) {
+        let t = self.secs();
+        (wave(t, 120.0, 2.5, 9.5), wave(t, 600.0, 4.0, 7.0), 5.2)
+    }
+
+    /// Process groups as `build_live_groups` would report them.
+    pub fn process_groups(&self) -> HashMap<Pid, ProcessGroup> {
+        process_groups(self.secs())
+    }
+
+    /// (total, used) memory in bytes.
+    pub fn memory(&self) -> (u64, u64) {
+        let t = self.secs();
+        (32 * GIB, (wave(t, 300.0, 14.0, 22.0) * GIB as f64) as u64)
+    }
+
+    pub fn swap(&self) -> (u64, u64) {
+        (4 * GIB, 180 * MIB)
+    }
+
+    pub fn disks(&self) -> Vec<DiskSpaceInfo> {
+        let t = self.secs();
+        // /var/lib/docker slowly fills toward the warning line and back
+        [("/", 480.0, 131.0), ("/var/lib/docker", 960.0, wave(t, 900.0, 70.0, 140.0)), ("/boot", 1.0, 0.62)]
+            .into_iter()
+            .map(|(mount, total, available)| {
+                let percent_free = available / total * 100.0;
+                DiskSpaceInfo {
+                    mount_point: mount.to_string(),
+                    total_gb: total,
+                    available_gb: available,
+                    percent_free,
+                    is_warning: percent_free < 10.0,
+                }
+            })
+            .collect()
+    }
+
+    /// Cumulative (name, rx_bytes, tx_bytes) per interface.
+    pub fn interface_counters(&self) -> Vec<(String, u64, u64)> {
+        let t = self.secs();
+        vec![
+            ("eth0".to_string(), wave_total(t, 60.0, 2e6, 14e6), wave_total(t, 45.0, 1e6, 6e6)),
+            ("docker0".to_string(), wave_total(t, 80.0, 3e5, 2e6), wave_total(t, 80.0, 2e5, 1.5e6)),
+        ]
+    }
+}
+
+fn process_groups(t: f64) -> HashMap<Pid, ProcessGroup> {
+    PROCESSES
+        .iter()
+        .enumerate()
+        .map(|(i, &(pid, name, user, _, (lo, hi), mib, child_count))| {
+            let cpu = wave(t + i as f64 * 13.0, 40.0 + i as f64 * 7.0, lo, hi);
+            let mem = (mib as f64 * wave(t, 200.0 + i as f64 * 30.0, 0.9, 1.1)) as u64 * MIB;
+            let io = (wave_total(t, 50.0, 0.0, 2e5) as f64 * (i % 4) as f64) as u64;
+            let children = (0..child_count)
+                .map(|c| ProcessInfo {
+                    pid: Pid::from_u32(pid + 1 + c as u32),
+                    user: user.to_string(),
+                    cpu: (cpu / (child_count + 1) as f64) as f32,
+                    mem: mem / (child_count as u64 + 1),
+                    read_bytes: 0,
+                    written_bytes: 0,
+                    net_rx_bytes: 0,
+                    net_tx_bytes: 0,
+                    name: name.to_string(),
+                })
+                .collect();
+            let group = ProcessGroup {
+                pid: Pid::from_u32(pid),
+                user: user.to_string(),
+                cpu,
+                mem,
+                read_bytes: io,
+                written_bytes: io / 2,
+                net_rx_bytes: wave_total(t, 60.0, 0.0, 1e6 * (i % 3) as f64),
+                net_tx_bytes: wave_total(t, 60.0, 0.0, 4e5 * (i % 3) as f64),
+                child_count,
+                name: name.to_string(),
+                children,
+            };
+            (group.pid, group)
+        })
+        .collect()
+}
+
+/// The collector behind the demo host's kernel-level readings.
+pub struct DemoCollector {
+    started: Instant,
+}
+
+impl Default for DemoCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DemoCollector {
+    pub fn new() -> Self {
+        Self { started: Instant::now() }
+    }
+
+    fn secs(&self) -> f64 {
+        self.started.elapsed().as_secs_f64()
+    }
+}
+
+impl SystemCollector for DemoCollector {
+    fn get_disk_io_pct(&mut self) -> f64 {
+        wave(self.secs(), 70.0, 8.0, 62.0)
+    }
+
+    fn get_fd_stats(&self) -> FdInfo {
+        let t = self.secs();
+        let per_process: Vec<(u32, String, u64)> = PROCESSES
+            .iter()
+            .map(|&(pid, name, _, _, _, mib, _)| (pid, name.to_string(), 20 + mib / 2))
+            .collect();
+        let mut top_processes: Vec<(String, u64)> = per_process.iter().map(|(_, n, c)| (n.clone(), *c)).collect();
+        top_processes.sort_by_key(|p| std::cmp::Reverse(p.1));
+        top_processes.truncate(5);
+        FdInfo {
+            system_used: wave(t, 120.0, 8_000.0, 11_000.0) as u64,
+            system_max: 1_048_576,
+            top_processes,
+            per_process,
+            leaks: Vec::new(),
+        }
+    }
+
+    fn get_process_unit(&self, pid: u32) -> Option<String> {
+        PROCESSES.iter().find(|p| p.0 == pid).map(|p| p.3.to_string())
+    }
+
+    fn get_process_limits(&self, _pid: u32) -> Option<ProcessLimits> {
+        None
+    }
+
+    fn get_process_priority(&self, pid: u32) -> Option<ProcessPriority> {
+        // Prometheus compacting its TSDB as a niced batch job
+        let (nice, cpu_weight) = if pid == 2733 { (10, 20) } else { (0, 100) };
+        PROCESSES.iter().any(|p| p.0 == pid).then_some(ProcessPriority { nice, cpu_weight: Some(cpu_weight) })
+    }
+
+    fn get_socket_stats(&self) -> SocketOverviewInfo {
+        let t = self.secs();
+        let established = wave(t, 90.0, 180.0, 640.0) as u32;
+        SocketOverviewInfo {
+            established,
+            listen: 11,
+            time_wait: wave(t, 30.0, 40.0, 900.0) as u32,
+            close_wait: 2,
+            fin_wait: 3,
+            top_processes: vec![
+                ("nginx".to_string(), established / 2),
+                ("java".to_string(), established / 3),
+                ("postgres".to_string(), 48),
+            ],
+            remotes: Vec::new(),
+            listeners: Vec::new(),
+        }
+    }
+
+    fn get_conntrack(&self) -> Option<ConntrackInfo> {
+        Some(ConntrackInfo { count: wave(self.secs(), 150.0, 18_000.0, 42_000.0) as u64, max: 262_144 })
+    }
+
+    fn get_link_stats(&mut self) -> Vec<LinkInfo> {
+        vec![LinkInfo {
+            name: "eth0".to_string(),
+            up: true,
+            speed_mbps: Some(10_000),
+            physical: true,
+            carrier_changes: 1,
+            last_flap_secs: None,
+        }]
+    }
+
+    fn get_pressure(&self) -> PressureInfo {
+        let t = self.secs();
+        PressureInfo {
+            cpu: Some(wave(t, 120.0, 1.0, 14.0)),
+            memory: Some(wave(t, 300.0, 0.0, 2.5)),
+            io: Some(wave(t, 70.0, 0.5, 9.0)),
+            runnable: Some(wave(t, 120.0, 2.0, 11.0) as u32),
+        }
+    }
+
+    fn get_cpu_breakdown(&mut self) -> Option<CpuBreakdown> {
+        let t = self.secs();
+        let (user, system, iowait, steal) =
+            (wave(t, 120.0, 22.0, 48.0), wave(t, 80.0, 6.0, 14.0), wave(t, 70.0, 0.5, 7.0), 0.8);
+        Some(CpuBreakdown { user, system, iowait, steal, idle: 100.0 - user - system - iowait - steal })
+    }
+
+    fn get_oom_info(&mut self) -> Option<OomInfo> {
+        None
+    }
+
+    fn get_entropy(&self) -> Option<u64> {
+        None
+    }
+
+    fn get_interrupts(&mut self) -> Option<InterruptInfo> {
+        None
+    }
+
+    fn get_memory_breakdown(&self) -> Option<MemoryBreakdown> {
+        Some(MemoryBreakdown { buffers: 420 * MIB, cached: 6 * GIB, slab: 640 * MIB, slab_reclaimable: 470 * MIB })
+    }
+
+    fn get_memory_detail(&self) -> Option<MemoryDetail> {
+        None
+    }
+
+    fn get_context_switches(&self) -> ContextSwitchInfo {
+        let total_csw = wave(self.secs(), 60.0, 14_000.0, 46_000.0) as u64;
+        ContextSwitchInfo {
+            total_csw,
+            top_processes: vec![("java".to_string(), total_csw / 3), ("postgres".to_string(), total_csw / 5)],
+        }
+    }
+
+    fn get_process_network_stats(&mut self) -> HashMap<Pid, (u64, u64)> {
+        HashMap::new()
+    }
+}
+
+/// (name, image, compose project, host port, CPU range, MiB, limit MiB) of a demo container.
+type DemoContainer = (&'static str, &'static str, &'static str, u16, (f64, f64), u64, u64);
+
+const CONTAINERS: &[DemoContainer] = &[
+    ("shop_proxy", "nginx:1.27", "shop", 443, (1.0, 9.0), 48, 256),
+    ("shop_web", "registry.example.com/shop-web:3.2.0", "shop", 3000, (5.0, 40.0), 620, 1024),
+    ("shop_api", "registry.example.com/shop-api:5.14.2", "shop", 8080, (30.0, 180.0), 3800, 4096),
+    ("shop_db", "postgres:16", "shop", 0, (15.0, 90.0), 2400, 0),
+    ("shop_cache", "redis:7.4", "shop", 0, (2.0, 15.0), 310, 512),
+    ("monitoring_prometheus", "prom/prometheus:v2.54.1", "monitoring", 9090, (3.0, 20.0), 900, 2048),
+    ("monitoring_grafana", "grafana/grafana:11.2.0", "monitoring", 3001, (0.5, 5.0), 210, 512),
+];
+
+/// The demo containers at `t` seconds, plus a migration job that has exited.
+pub fn containers(t: f64) -> Vec<DockerContainerInfo> {
+    let mut containers: Vec<DockerContainerInfo> = CONTAINERS
+        .iter()
+        .enumerate()
+        .map(|(i, &(name, image, project, port, (lo, hi), mib, limit))| {
+            let uptime_secs = 86_400 * (i as u64 % 3 + 1) + 3_600 * i as u64 + t as u64;
+            let published_ports: Vec<PublishedPort> = (port > 0)
+                .then(|| PublishedPort { host_ip: "0.0.0.0".into(), host_port: port, container_port: port })
+                .into_iter()
+                .collect();
+            container(i, name, image, project, "running", Some(uptime_secs), &published_ports, |c| {
+                c.cpu_percent = wave(t + i as f64 * 9.0, 50.0 + i as f64 * 5.0, lo, hi);
+                c.mem_usage = (mib as f64 * wave(t, 240.0, 0.92, 1.05)) as u64 * MIB;
+                c.mem_limit = limit * MIB;
+                c.net_rx = wave_total(t, 60.0, 2e4, 2e6 / (i + 1) as f64);
+                c.net_tx = wave_total(t, 60.0, 1e4, 1e6 / (i + 1) as f64);
+                c.ip_address = format!("172.18.0.{}", i + 2);
+            })
+        })
+        .collect();
+    containers.push(container(CONTAINERS.len(), "shop_migrate", "registry.example.com/shop-api:5.14.2", "shop", "exited", None, &[], |c| {
+        c.status = "Exited (0) 2 hours ago".to_string();
+    }));
+    containers
+}
+
+#[allow(clippy::too_many_arguments)]
+fn container(
+    index: usize,
+    name: &str,
+    image: &str,
+    project: &str,
+    state: &str,
+    uptime_secs: Option<u64>,
+    published_ports: &[PublishedPort],
+    fill: impl FnOnce(&mut DockerContainerInfo),
+) -> DockerContainerInfo {
+    let ports = published_ports
+        .iter()
+        .map(|p| format!("{}:{}->{}/tcp", p.host_ip, p.host_port, p.container_port))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut c = DockerContainerInfo {
+        id: format!("{:012x}", 0x5e1f_0000_0000_u64 + index as u64 * 0x1_0203),
+        name: name.to_string(),
+        image: image.to_string(),
+        status: uptime_secs.map(|s| format!("Up {}", crate::docker::format_uptime(s))).unwrap_or_default(),
+        state: state.to_string(),
+        uptime: uptime_secs.map(crate::docker::format_uptime).unwrap_or_else(|| "unknown".to_string()),
+        uptime_secs,
+        cpu_percent: 0.0,
+        ports,
+        ip_address: String::new(),
+        mem_usage: 0,
+        mem_limit: 0,
+        net_rx: 0,
+        net_tx: 0,
+        labels: HashMap::from([("com.docker.compose.project".to_string(), project.to_string())]),
+        published_ports: published_ports.to_vec(),
+        image_id: format!("sha256:{:064x}", index + 1),
+        image_created: None,
+        image_digests: Vec::new(),
+        image_drift: None,
+        image_scan: None,
+        namespace: String::new(),
+    };
+    fill(&mut c);
+    c
+}
+
+/// Request and error lines in the shape a web service logs them.
+fn log_message(n: u64) -> String {
+    const PATHS: &[&str] = &["/api/orders", "/api/cart", "/api/products?page=2", "/healthz", "/api/checkout"];
+    match n % 23 {
+        7 => "level=warn msg=\"slow query\" duration=812ms table=orders".to_string(),
+        19 => "level=error msg=\"upstream timed out\" upstream=payments:8443 after=5s".to_string(),
+        _ => format!(
+            "level=info method={} path={} status={} duration={}ms",
+            if n % 5 == 1 { "POST" } else { "GET" },
+            PATHS[n as usize % PATHS.len()],
+            if n % 11 == 3 { 404 } else { 200 },
+            3 + (n * 37) % 140
+        ),
+    }
+}
+
+fn timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+/// Follow a demo container's "logs" until the receiver is dropped.
+pub fn container_logs() -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel::<String>(256);
+    thread::spawn(move || {
+        for n in 0.. {
+            if tx.blocking_send(format!("{} {}", timestamp(), log_message(n))).is_err() {
+                break;
+            }
+            thread::sleep(LOG_INTERVAL);
+        }
+    });
+    rx
+}
+
+/// Follow a demo service's "logs", prefixed with the task that wrote each
+/// line like `docker service logs`.
+pub fn service_logs(service_name: &str) -> LogStreamHandle {
+    let name = service_name.to_string();
+    let mut n = 0;
+    LogStreamHandle::generated(LOG_INTERVAL, move || {
+        n += 1;
+        let replica = n % 3 + 1;
+        format!(
+            "{} {}.{}.{:x}@{}    | {}",
+            timestamp(),
+            name,
+            replica,
+            0x9a3c_0000_u64 + replica,
+            NODES[replica as usize % NODES.len()].0,
+            log_message(n)
+        )
+    })
+}
+
+/// (hostname, manager status, availability) of the demo nodes.
+const NODES: &[(&str, &str, &str)] = &[
+    ("mgr-1", "Leader", "Active"),
+    ("mgr-2", "Reachable", "Active"),
+    ("mgr-3", "Reachable", "Active"),
+    ("worker-1", "", "Active"),
+    ("worker-2", "", "Drain"),
+];
+
+pub fn cluster() -> SwarmClusterInfo {
+    SwarmClusterInfo {
+        node_id: node_id(0),
+        node_addr: "10.0.0.11".to_string(),
+        is_manager: true,
+        managers: 3,
+        nodes_total: NODES.len() as u32,
+    }
+}
+
+fn node_id(index: usize) -> String {
+    format!("demo{:021}", index + 1)
+}
+
+pub fn nodes() -> Vec<SwarmNodeInfo> {
+    NODES
+        .iter()
+        .enumerate()
+        .map(|(i, &(hostname, manager_status, availability))| SwarmNodeInfo {
+            id: node_id(i),
+            hostname: hostname.to_string(),
+            status: "Ready".to_string(),
+            availability: availability.to_string(),
+            manager_status: manager_status.to_string(),
+            engine_version: "27.3.1".to_string(),
+            is_self: i == 0,
+            ip_address: format!("10.0.0.{}", 11 + i),
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// (name, image, desired replicas or None for global, ports) of the demo services.
+const SERVICES: &[(&str, &str, Option<u32>, &str)] = &[
+    ("shop_api", "registry.example.com/shop-api:5.14.2", Some(3), "*:8080->8080/tcp"),
+    ("shop_web", "registry.example.com/shop-web:3.2.0", Some(2), "*:443->443/tcp"),
+    ("shop_db", "postgres:16", Some(1), ""),
+    ("monitoring_prometheus", "prom/prometheus:v2.54.1", Some(1), "*:9090->9090/tcp"),
+    ("monitoring_node-exporter", "prom/node-exporter:v1.8.2", None, ""),
+];
+
+fn service_id(index: usize) -> String {
+    format!("svc{:022}", index + 1)
+}
+
+/// Whether `shop_api` is running a replica short at `t`.
+fn degraded(t: f64) -> bool {
+    (t as u64) % DEGRADED_PERIOD >= DEGRADED_PERIOD - DEGRADED_FOR
+}
+
+pub fn services(t: f64) -> Vec<SwarmServiceInfo> {
+    SERVICES
+        .iter()
+        .enumerate()
+        .map(|(i, &(name, image, replicas, ports))| {
+            let desired = replicas.unwrap_or(NODES.len() as u32);
+            let running = if i == 0 && degraded(t) { desired - 1 } else { desired };
+            SwarmServiceInfo {
+                id: service_id(i),
+                name: name.to_string(),
+                mode: if replicas.is_some() { "replicated" } else { "global" }.to_string(),
+                replicas: format!("{}/{}", running, desired),
+                image: image.to_string(),
+                ports: ports.to_string(),
+                stack: name.split('_').next().unwrap_or_default().to_string(),
+                config_files: String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Tasks of service `id` at `t`: its replicas, and while degraded the one
+/// that was killed and its replacement still starting.
+pub fn service_tasks(id: &str, t: f64) -> Vec<SwarmTaskInfo> {
+    let Some(index) = SERVICES.iter().enumerate().position(|(i, _)| service_id(i) == id) else {
+        return Vec::new();
+    };
+    let (name, image, replicas, _) = SERVICES[index];
+    let desired = replicas.unwrap_or(NODES.len() as u32);
+    let task = |slot: u32, node: usize, current_state: String, desired_state: &str, error: &str| SwarmTaskInfo {
+        id: format!("task{:02}{:02}{:017}", index, slot, node),
+        name: format!("{}.{}", name, slot),
+        image: image.to_string(),
+        node: NODES[node % NODES.len()].0.to_string(),
+        desired_state: desired_state.to_string(),
+        current_state,
+        error: error.to_string(),
+        ports: String::new(),
+    };
+    let mut tasks: Vec<SwarmTaskInfo> = (1..=desired)
+        .map(|slot| task(slot, slot as usize - 1, format!("Running {} hours ago", 2 + slot), "Running", ""))
+        .collect();
+    if index == 0 && degraded(t) {
+        let since = (t as u64) % DEGRADED_PERIOD - (DEGRADED_PERIOD - DEGRADED_FOR);
+        tasks[2] = task(3, 3, format!("Preparing {} seconds ago", since.max(1)), "Running", "");
+        tasks.push(task(3, 2, format!("Failed {} seconds ago", since.max(1)), "Shutdown", "task: non-zero exit (137)"));
+    }
+    tasks
+}
+
+/// Running tasks of several services, as one `docker service ps` would list them.
+pub fn running_tasks(ids: &[String], t: f64) -> Vec<SwarmTaskInfo> {
+    ids.iter()
+        .flat_map(|id| service_tasks(id, t))
+        .filter(|task| task.current_state.starts_with("Running"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demo_data_drifts_and_degrades_a_service() {
+        assert!((wave(0.0, 60.0, 10.0, 20.0) - 15.0).abs() < 1e-9);
+        assert!((wave(15.0, 60.0, 10.0, 20.0) - 20.0).abs() < 1e-9);
+        // The counter grows at the mean rate over a full period
+        assert_eq!(wave_total(60.0, 60.0, 100.0, 300.0), 12_000);
+        assert_ne!(containers(0.0)[2].cpu_percent, containers(20.0)[2].cpu_percent);
+
+        assert_eq!(services(0.0)[0].replicas, "3/3");
+        assert_eq!(services(80.0)[0].replicas, "2/3");
+        let tasks = service_tasks(&service_id(0), 80.0);
+        assert_eq!(tasks.len(), 4);
+        assert_eq!(tasks[3].error, "task: non-zero exit (137)");
+        assert_eq!(running_tasks(&[service_id(0), service_id(4)], 80.0).len(), 2 + NODES.len());
+    }
+}
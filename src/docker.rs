@@ -1,15 +1,46 @@
 use bollard::Docker;
 use bollard::container::{
-    ListContainersOptions, StatsOptions, LogsOptions, LogOutput, Stats,
-    StopContainerOptions, RestartContainerOptions,
+    InspectContainerOptions, ListContainersOptions, StatsOptions, LogsOptions, LogOutput, Stats,
+    StopContainerOptions, RestartContainerOptions, RenameContainerOptions,
 };
+use bollard::image::ListImagesOptions;
 use bollard::models::ContainerSummary;
+use bollard::system::EventsOptions;
 use futures_util::StreamExt;
 use futures_util::future::join_all;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
-use crate::model::DockerContainerInfo;
+use crate::cmd;
+use crate::error::SitrepError;
+use crate::model::{ContainerStats, DaemonLatency, DockerContainerInfo, PublishedPort};
+
+/// How long one registry digest lookup may take.
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(15);
+/// How long a timed daemon API call may take before it counts as failed.
+const DAEMON_CALL_TIMEOUT: Duration = Duration::from_secs(5);
+/// Daemon log lines shown in the Docker health panel.
+pub const DAEMON_LOG_LINES: usize = 30;
+/// Log files of daemons not run under systemd.
+const DAEMON_LOG_FILES: [&str; 2] = ["/var/log/docker.log", "/var/log/upstart/docker.log"];
+
+/// Container events that change what the Containers tab shows.
+const CONTAINER_EVENTS: [&str; 8] = ["create", "start", "stop", "die", "destroy", "pause", "unpause", "health_status"];
+
+/// The socket a rootful daemon listens on, and bollard's default.
+pub const DEFAULT_SOCKET: &str = "/var/run/docker.sock";
+
+/// One round of the Docker health panel's checks.
+pub struct DaemonCheck {
+    pub latency: DaemonLatency,
+    /// (server version, storage driver, rootless) from `/info`.
+    pub info: Result<(String, String, bool), SitrepError>,
+    /// (source, last lines) of the daemon log.
+    pub log: Result<(String, Vec<String>), SitrepError>,
+}
 
 /// Wrapper around bollard's Docker client.
 pub struct DockerClient {
@@ -20,24 +51,49 @@ impl DockerClient {
     /// Try to connect to the Docker daemon.
     /// Returns None if Docker is not available.
     pub fn try_new() -> Option<Self> {
-        let client = Docker::connect_with_local_defaults().ok()?;
+        // Honors DOCKER_HOST (unix:// or tcp://), else the local socket
+        let client = Docker::connect_with_defaults().ok()?;
         Some(Self { client })
     }
 
+    /// `try_new` for callers that report why there is no client.
+    pub fn connect() -> Result<Self, SitrepError> {
+        Self::try_new().ok_or_else(|| SitrepError::DaemonUnavailable("Failed to connect to Docker".to_string()))
+    }
+
     /// Ping the daemon to verify it is reachable.
     pub async fn is_available(&self) -> bool {
         self.client.ping().await.is_ok()
     }
 
+    /// Time `/_ping` and `/info`, the calls every Docker client starts with.
+    pub async fn check_daemon(&self) -> (DaemonLatency, Result<(String, String, bool), SitrepError>) {
+        let started = Instant::now();
+        let ping = tokio::time::timeout(DAEMON_CALL_TIMEOUT, self.client.ping()).await;
+        let ping_ms = matches!(ping, Ok(Ok(_))).then(|| started.elapsed().as_secs_f64() * 1000.0);
+
+        let started = Instant::now();
+        let info = match tokio::time::timeout(DAEMON_CALL_TIMEOUT, self.client.info()).await {
+            Ok(Ok(info)) => {
+                let rootless = info.security_options.iter().flatten().any(|o| o.contains("name=rootless"));
+                Ok((info.server_version.unwrap_or_default(), info.driver.unwrap_or_default(), rootless))
+            }
+            Ok(Err(e)) => Err(SitrepError::from_bollard("Docker info failed", e)),
+            Err(_) => Err(SitrepError::Timeout(format!("Docker info timed out after {}s", DAEMON_CALL_TIMEOUT.as_secs()))),
+        };
+        let info_ms = info.is_ok().then(|| started.elapsed().as_secs_f64() * 1000.0);
+        (DaemonLatency { ping_ms, info_ms }, info)
+    }
+
     /// List running containers and map them to our model type.
-    pub async fn list_containers(&self) -> Result<Vec<DockerContainerInfo>, String> {
+    pub async fn list_containers(&self) -> Result<Vec<DockerContainerInfo>, SitrepError> {
         let options: ListContainersOptions<String> = ListContainersOptions {
             all: false, // only running
             ..Default::default()
         };
 
         let summaries = self.client.list_containers(Some(options)).await
-            .map_err(|e| format!("Failed to list containers: {}", e))?;
+            .map_err(|e| SitrepError::from_bollard("Failed to list containers", e))?;
 
         let mut containers = Vec::new();
         for s in &summaries {
@@ -46,16 +102,37 @@ impl DockerClient {
         Ok(containers)
     }
 
-    /// Fetch CPU stats for all containers concurrently instead of sequentially.
-    pub async fn get_all_cpu_percents(&self, ids: &[String]) -> Vec<f64> {
+    /// Build time and registry digests of every local image, by image ID.
+    pub async fn list_images(&self) -> Result<HashMap<String, (i64, Vec<String>)>, SitrepError> {
+        let images = self
+            .client
+            .list_images(Some(ListImagesOptions::<String>::default()))
+            .await
+            .map_err(|e| SitrepError::from_bollard("Failed to list images", e))?;
+        Ok(images.into_iter().map(|i| (i.id, (i.created, i.repo_digests))).collect())
+    }
+
+    /// The digest the registry currently serves for `image` (e.g. "nginx:1.25").
+    /// Talks to the registry through the daemon, so it is slow and may need
+    /// the daemon's stored credentials.
+    pub async fn registry_digest(&self, image: &str) -> Result<String, SitrepError> {
+        let inspect = tokio::time::timeout(REGISTRY_TIMEOUT, self.client.inspect_registry_image(image, None))
+            .await
+            .map_err(|_| SitrepError::Timeout(format!("{}: registry timed out", image)))?
+            .map_err(|e| SitrepError::from_bollard(image, e))?;
+        inspect.descriptor.digest.ok_or_else(|| SitrepError::ParseError(format!("{}: no digest", image)))
+    }
+
+    /// Fetch stats for all containers concurrently instead of sequentially.
+    pub async fn get_all_stats(&self, ids: &[String]) -> Vec<ContainerStats> {
         let futures: Vec<_> = ids.iter()
-            .map(|id| self.get_cpu_percent(id))
+            .map(|id| self.get_stats(id))
             .collect();
         join_all(futures).await
     }
 
-    /// Fetch a one-shot stats snapshot for a container. Returns cpu_percent.
-    pub async fn get_cpu_percent(&self, container_id: &str) -> f64 {
+    /// Fetch a one-shot stats snapshot for a container.
+    pub async fn get_stats(&self, container_id: &str) -> ContainerStats {
         let options = StatsOptions {
             stream: false,
             one_shot: true,
@@ -63,8 +140,17 @@ impl DockerClient {
 
         let mut stream = self.client.stats(container_id, Some(options));
         match tokio::time::timeout(Duration::from_secs(3), stream.next()).await {
-            Ok(Some(Ok(stats))) => calculate_cpu_percent(&stats),
-            _ => 0.0,
+            Ok(Some(Ok(stats))) => {
+                let (net_rx, net_tx) = sum_network_bytes(&stats);
+                ContainerStats {
+                    cpu_percent: calculate_cpu_percent(&stats),
+                    mem_usage: stats.memory_stats.usage.unwrap_or(0),
+                    mem_limit: stats.memory_stats.limit.unwrap_or(0),
+                    net_rx,
+                    net_tx,
+                }
+            }
+            _ => ContainerStats::default(),
         }
     }
 
@@ -116,30 +202,73 @@ impl DockerClient {
         rx
     }
 
+    /// Follow container lifecycle events. Each is sent as its action, e.g.
+    /// "die" or "health_status: unhealthy"; the channel closes when the
+    /// stream breaks, as when the daemon restarts.
+    pub fn container_events(&self, handle: &tokio::runtime::Handle) -> mpsc::Receiver<String> {
+        let (tx, rx) = mpsc::channel::<String>(256);
+        let mut filters = HashMap::new();
+        filters.insert("type".to_string(), vec!["container".to_string()]);
+        filters.insert("event".to_string(), CONTAINER_EVENTS.iter().map(|e| e.to_string()).collect());
+        let stream = self.client.events(Some(EventsOptions { filters, ..Default::default() }));
+
+        handle.spawn(async move {
+            let mut stream = Box::pin(stream);
+            while let Some(Ok(event)) = stream.next().await {
+                if tx.send(event.action.unwrap_or_default()).await.is_err() {
+                    break; // receiver dropped
+                }
+            }
+        });
+
+        rx
+    }
+
     /// Start a stopped container.
-    pub async fn start_container(&self, container_id: &str) -> Result<(), String> {
+    pub async fn start_container(&self, container_id: &str) -> Result<(), SitrepError> {
         self.client
             .start_container::<String>(container_id, None)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(|e| SitrepError::from_bollard(&format!("Failed to start {}", container_id), e))
     }
 
     /// Stop a running container.
-    pub async fn stop_container(&self, container_id: &str) -> Result<(), String> {
+    pub async fn stop_container(&self, container_id: &str) -> Result<(), SitrepError> {
         let options = StopContainerOptions { t: 10 };
         self.client
             .stop_container(container_id, Some(options))
             .await
-            .map_err(|e| e.to_string())
+            .map_err(|e| SitrepError::from_bollard(&format!("Failed to stop {}", container_id), e))
     }
 
     /// Restart a container.
-    pub async fn restart_container(&self, container_id: &str) -> Result<(), String> {
+    pub async fn restart_container(&self, container_id: &str) -> Result<(), SitrepError> {
         let options = RestartContainerOptions { t: 10 };
         self.client
             .restart_container(container_id, Some(options))
             .await
-            .map_err(|e| e.to_string())
+            .map_err(|e| SitrepError::from_bollard(&format!("Failed to restart {}", container_id), e))
+    }
+
+    /// Give a container a new name.
+    pub async fn rename_container(&self, container_id: &str, name: &str) -> Result<(), SitrepError> {
+        self.client
+            .rename_container(container_id, RenameContainerOptions { name })
+            .await
+            .map_err(|e| SitrepError::from_bollard(&format!("Failed to rename {}", container_id), e))
+    }
+
+    /// Host PID of a running container's init process.
+    pub async fn container_pid(&self, container_id: &str) -> Result<u32, SitrepError> {
+        let inspect = self.client
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| SitrepError::from_bollard(&format!("Failed to inspect {}", container_id), e))?;
+        inspect.state
+            .and_then(|s| s.pid)
+            .filter(|pid| *pid > 0)
+            .map(|pid| pid as u32)
+            .ok_or_else(|| SitrepError::Failed(format!("{} is not running", container_id)))
     }
 
     // --- Internal helpers ---
@@ -158,7 +287,13 @@ impl DockerClient {
         let state = s.state.clone().unwrap_or_default();
         let status = s.status.clone().unwrap_or_default();
 
-        let uptime = format_uptime(s.created.unwrap_or(0));
+        // Prefer the "Up 5 minutes" status, which counts from the last (re)start;
+        // the created timestamp doesn't move when a container restarts.
+        let uptime_secs = parse_status_uptime(&status)
+            .or_else(|| secs_since(s.created.unwrap_or(0)));
+        let uptime = uptime_secs
+            .map(format_uptime)
+            .unwrap_or_else(|| "unknown".to_string());
 
         let ports = format_ports(s);
 
@@ -171,9 +306,24 @@ impl DockerClient {
             status,
             state,
             uptime,
+            uptime_secs,
             cpu_percent: 0.0, // filled separately via stats
             ports,
             ip_address,
+            // filled separately via stats
+            mem_usage: 0,
+            mem_limit: 0,
+            net_rx: 0,
+            net_tx: 0,
+            labels: s.labels.clone().unwrap_or_default(),
+            published_ports: published_tcp_ports(s),
+            image_id: s.image_id.clone().unwrap_or_default(),
+            // filled separately from the image list
+            image_created: None,
+            image_digests: Vec::new(),
+            image_drift: None,
+            image_scan: None,
+            namespace: String::new(),
         }
     }
 }
@@ -205,13 +355,147 @@ fn calculate_cpu_percent(stats: &Stats) -> f64 {
     }
 }
 
-fn format_uptime(created_ts: i64) -> String {
-    if created_ts == 0 {
-        return "unknown".to_string();
+/// Total received/sent bytes across all of a container's interfaces.
+fn sum_network_bytes(stats: &Stats) -> (u64, u64) {
+    if let Some(ref networks) = stats.networks {
+        networks.values().fold((0, 0), |(rx, tx), n| (rx + n.rx_bytes, tx + n.tx_bytes))
+    } else if let Some(ref n) = stats.network {
+        (n.rx_bytes, n.tx_bytes)
+    } else {
+        (0, 0)
+    }
+}
+
+/// Seconds elapsed since a Unix timestamp, or None if the timestamp is unset.
+pub(crate) fn secs_since(ts: i64) -> Option<u64> {
+    if ts == 0 {
+        return None;
     }
     let now = chrono::Utc::now().timestamp();
-    let secs = (now - created_ts).max(0) as u64;
+    Some((now - ts).max(0) as u64)
+}
+
+/// Parse the running duration out of a Docker status string such as
+/// "Up 5 minutes (healthy)". Docker rounds these, so the result is approximate.
+pub(crate) fn parse_status_uptime(status: &str) -> Option<u64> {
+    let rest = status.strip_prefix("Up ")?;
+    let duration = rest.split(" (").next().unwrap_or(rest).trim();
+    match duration {
+        "Less than a second" => return Some(0),
+        "About a minute" => return Some(60),
+        "About an hour" => return Some(3600),
+        _ => {}
+    }
+    let (count, unit) = duration.split_once(' ')?;
+    let count: u64 = count.parse().ok()?;
+    let unit_secs = match unit.trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 3600,
+        "day" => 86400,
+        "week" => 7 * 86400,
+        "month" => 30 * 86400,
+        "year" => 365 * 86400,
+        _ => return None,
+    };
+    Some(count * unit_secs)
+}
+
+/// Unix sockets a local daemon may listen on, in the order they are tried:
+/// the rootful default, rootless Docker under the user's runtime directory,
+/// Docker Desktop's per-user socket, then Podman's Docker-compatible API
+/// socket, rootful and rootless.
+pub fn socket_candidates(runtime_dir: Option<&str>, uid: Option<u32>, home: Option<&str>) -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from(DEFAULT_SOCKET)];
+    if let Some(dir) = runtime_dir.filter(|d| !d.is_empty()) {
+        candidates.push(Path::new(dir).join("docker.sock"));
+    }
+    if let Some(uid) = uid {
+        candidates.push(PathBuf::from(format!("/run/user/{}/docker.sock", uid)));
+    }
+    if let Some(home) = home.filter(|h| !h.is_empty()) {
+        candidates.push(Path::new(home).join(".docker/run/docker.sock"));
+        candidates.push(Path::new(home).join(".docker/desktop/docker.sock"));
+    }
+    candidates.push(PathBuf::from("/run/podman/podman.sock"));
+    if let Some(dir) = runtime_dir.filter(|d| !d.is_empty()) {
+        candidates.push(Path::new(dir).join("podman/podman.sock"));
+    }
+    if let Some(uid) = uid {
+        candidates.push(PathBuf::from(format!("/run/user/{}/podman/podman.sock", uid)));
+    }
+    candidates.dedup();
+    candidates
+}
+
+/// The first candidate socket a daemon answers on, when DOCKER_HOST is unset.
+pub fn detect_socket() -> Option<PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+    let runtime_dir = std::env::var("XDG_RUNTIME_DIR").ok();
+    let home = std::env::var("HOME").ok();
+    // The owner of /proc/self is the effective user
+    let uid = std::fs::metadata("/proc/self").ok().map(|m| m.uid());
+    socket_candidates(runtime_dir.as_deref(), uid, home.as_deref())
+        .into_iter()
+        .find(|path| std::os::unix::net::UnixStream::connect(path).is_ok())
+}
+
+/// The daemon address in use, as shown in the Docker health panel.
+pub fn endpoint() -> String {
+    std::env::var("DOCKER_HOST")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| format!("unix://{}", DEFAULT_SOCKET))
+}
+
+/// The last `lines` lines of the local daemon's log: journald's docker unit,
+/// else a classic log file. A remote DOCKER_HOST has no local log.
+pub fn daemon_log_tail(lines: usize) -> Result<(String, Vec<String>), SitrepError> {
+    let host = std::env::var("DOCKER_HOST").unwrap_or_default();
+    if host.starts_with("tcp://") || host.starts_with("ssh://") {
+        return Err(SitrepError::Failed(format!("the daemon runs on {}; its log is not on this host", host)));
+    }
+    if !cfg!(target_os = "linux") {
+        return Err(SitrepError::Failed("the daemon log is read from journald or /var/log/docker.log on Linux".to_string()));
+    }
+
+    // Rootless Docker runs as a user unit
+    let count = lines.to_string();
+    for (scope, source) in [("--system", "journald"), ("--user", "journald, user unit")] {
+        let journal = cmd::output(
+            Command::new("journalctl").args([
+                scope, "-u", "docker.service", "-u", "snap.docker.dockerd.service",
+                "-n", &count, "--no-pager", "-q", "-o", "short-iso",
+            ]),
+            cmd::QUICK,
+        );
+        if let Ok(output) = journal {
+            let text = String::from_utf8_lossy(&output.stdout);
+            let tail: Vec<String> = text.lines().filter(|l| !l.starts_with("-- ")).map(String::from).collect();
+            if output.status.success() && !tail.is_empty() {
+                return Ok((source.to_string(), tail));
+            }
+        }
+    }
+
+    for path in DAEMON_LOG_FILES {
+        if !std::path::Path::new(path).exists() {
+            continue;
+        }
+        let output = cmd::output(Command::new("tail").args(["-n", &count, path]), cmd::QUICK)
+            .map_err(|e| SitrepError::from_io(&format!("Failed to read {}", path), &e))?;
+        if !output.status.success() {
+            return Err(SitrepError::from_stderr(path, &String::from_utf8_lossy(&output.stderr)));
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        return Ok((path.to_string(), text.lines().map(String::from).collect()));
+    }
+    Err(SitrepError::Failed(
+        "no daemon log in journald (docker.service) or /var/log/docker.log; it may need root to read".to_string(),
+    ))
+}
 
+pub fn format_uptime(secs: u64) -> String {
     if secs < 60 {
         format!("{}s", secs)
     } else if secs < 3600 {
@@ -244,18 +528,78 @@ fn format_ports(s: &ContainerSummary) -> String {
     parts.join(", ")
 }
 
+fn published_tcp_ports(s: &ContainerSummary) -> Vec<PublishedPort> {
+    let Some(ports) = &s.ports else { return Vec::new() };
+    let mut published: Vec<PublishedPort> = ports
+        .iter()
+        .filter(|p| p.typ.as_ref().is_none_or(|t| t.to_string() == "tcp"))
+        .filter_map(|p| {
+            Some(PublishedPort {
+                host_ip: p.ip.clone().unwrap_or_default(),
+                host_port: p.public_port?,
+                container_port: p.private_port,
+            })
+        })
+        .collect();
+    // Docker lists IPv4 and IPv6 bindings separately; probe each host port once.
+    published.sort_by_key(|p| p.host_port);
+    published.dedup_by_key(|p| p.host_port);
+    published
+}
+
 fn extract_ip(s: &ContainerSummary) -> String {
-    if let Some(settings) = &s.network_settings {
-        if let Some(networks) = &settings.networks {
+    if let Some(settings) = &s.network_settings
+        && let Some(networks) = &settings.networks {
             // Return the first network's IP
-            for (_name, endpoint) in networks {
-                if let Some(ip) = &endpoint.ip_address {
-                    if !ip.is_empty() {
+            for endpoint in networks.values() {
+                if let Some(ip) = &endpoint.ip_address
+                    && !ip.is_empty() {
                         return ip.clone();
                     }
-                }
             }
         }
-    }
     String::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_status_uptime, socket_candidates};
+    use std::path::PathBuf;
+
+    #[test]
+    fn tries_rootless_desktop_and_podman_sockets_after_the_default() {
+        let candidates = socket_candidates(Some("/run/user/1000"), Some(1000), Some("/home/dev"));
+        let expected: Vec<PathBuf> = [
+            "/var/run/docker.sock",
+            "/run/user/1000/docker.sock",
+            "/home/dev/.docker/run/docker.sock",
+            "/home/dev/.docker/desktop/docker.sock",
+            "/run/podman/podman.sock",
+            "/run/user/1000/podman/podman.sock",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        assert_eq!(candidates, expected);
+        assert_eq!(
+            socket_candidates(None, None, None),
+            vec![PathBuf::from("/var/run/docker.sock"), PathBuf::from("/run/podman/podman.sock")]
+        );
+    }
+
+    #[test]
+    fn parse_status_uptime_handles_docker_durations() {
+        assert_eq!(parse_status_uptime("Up Less than a second"), Some(0));
+        assert_eq!(parse_status_uptime("Up 42 seconds"), Some(42));
+        assert_eq!(parse_status_uptime("Up About a minute"), Some(60));
+        assert_eq!(parse_status_uptime("Up 3 minutes (healthy)"), Some(180));
+        assert_eq!(parse_status_uptime("Up 2 hours (Paused)"), Some(7200));
+        assert_eq!(parse_status_uptime("Up 1 day"), Some(86400));
+    }
+
+    #[test]
+    fn parse_status_uptime_rejects_non_running_status() {
+        assert_eq!(parse_status_uptime("Exited (0) 3 minutes ago"), None);
+        assert_eq!(parse_status_uptime("Created"), None);
+    }
+}
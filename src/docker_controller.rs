@@ -1,39 +1,152 @@
 use tokio::sync::mpsc;
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
 
-use crate::docker::DockerClient;
+use crate::action_queue::{ActionQueue, Job};
+use crate::docker::{DaemonCheck, DockerClient};
+use crate::error::SitrepError;
+use crate::scan::ImageScan;
 use crate::model::{
-    ContainerUIState, DockerContainerInfo, LogViewState, MultiLogLine, MultiLogViewState,
+    container_order, container_rows, group_containers, ContainerGroupInfo, ContainerRow,
+    ContainerSort, ContainerStats, ContainerStatsHistory,
+    ContainerUIState, DaemonHealth, DockerContainerInfo, LogViewState, MultiLogLine, MultiLogViewState,
 };
 
-/// Receiver for background Docker action results.
-type ActionReceiver = std::sync::mpsc::Receiver<Result<String, String>>;
-
 /// Result of a background Docker update.
 struct DockerUpdateResult {
     containers: Vec<DockerContainerInfo>,
-    cpu_cache: HashMap<String, f64>,
-    cpu_refresh_cursor: usize,
+    stats_cache: HashMap<String, ContainerStats>,
+    stats_refresh_cursor: usize,
+}
+
+/// The CLI containers are read through on a host without a Docker daemon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContainerCli {
+    /// nerdctl, on Kubernetes nodes and bare containerd hosts.
+    Nerdctl,
+    /// podman, on RHEL-family hosts whose podman socket is not running.
+    Podman,
+}
+
+impl ContainerCli {
+    /// The first CLI that can list containers.
+    fn detect() -> Option<Self> {
+        if crate::nerdctl::namespaces().is_ok() {
+            Some(Self::Nerdctl)
+        } else if crate::podman::available() {
+            Some(Self::Podman)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Nerdctl => "nerdctl",
+            Self::Podman => "podman",
+        }
+    }
+
+    // podman has no namespaces; the containerd one is ignored for it
+
+    fn list_containers(self, namespaces: &[String]) -> Result<Vec<DockerContainerInfo>, SitrepError> {
+        match self {
+            Self::Nerdctl => crate::nerdctl::list_containers(namespaces),
+            Self::Podman => crate::podman::list_containers(),
+        }
+    }
+
+    fn tail_logs(self, namespace: &str, id: &str) -> Result<(crate::cmd::TrackedChild, mpsc::Receiver<String>), SitrepError> {
+        match self {
+            Self::Nerdctl => crate::nerdctl::tail_logs(namespace, id),
+            Self::Podman => crate::podman::tail_logs(id),
+        }
+    }
+
+    fn container_action(self, namespace: &str, id: &str, action: &str) -> Result<(), SitrepError> {
+        match self {
+            Self::Nerdctl => crate::nerdctl::container_action(namespace, id, action),
+            Self::Podman => crate::podman::container_action(id, action),
+        }
+    }
+
+    fn rename(self, namespace: &str, id: &str, name: &str) -> Result<(), SitrepError> {
+        match self {
+            Self::Nerdctl => crate::nerdctl::rename(namespace, id, name),
+            Self::Podman => crate::podman::rename(id, name),
+        }
+    }
+
+    fn container_pid(self, namespace: &str, id: &str) -> Result<u32, SitrepError> {
+        match self {
+            Self::Nerdctl => crate::nerdctl::container_pid(namespace, id),
+            Self::Podman => crate::podman::container_pid(id),
+        }
+    }
 }
 
+/// How often the opt-in registry digest check runs.
+const DRIFT_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// An image's vulnerability counts are refreshed daily, as the CVE database
+/// changes under it.
+const SCAN_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// Manages Docker container data collection and log streaming.
 pub struct DockerMonitor {
     client: Option<DockerClient>,
+    /// Containers come from nerdctl or podman, on a host without Docker.
+    cli: Option<ContainerCli>,
+    /// Containers are made up (`--demo`), counted from this start.
+    demo: Option<Instant>,
+    /// containerd namespaces listed through nerdctl; all when empty (from config).
+    pub namespaces: Vec<String>,
     pub containers: Vec<DockerContainerInfo>,
-    cpu_cache: HashMap<String, f64>,
-    cpu_refresh_cursor: usize,
+    /// When `containers` was last refreshed successfully.
+    pub last_updated: Option<Instant>,
+    /// The latest refresh failed.
+    pub update_failed: bool,
+    stats_cache: HashMap<String, ContainerStats>,
+    stats_refresh_cursor: usize,
+    /// CPU and memory samples per container, for the expanded detail lines.
+    pub stats_history: ContainerStatsHistory,
+    /// Digest the registry serves per image reference, from the opt-in
+    /// `check_registry_digest` check.
+    registry_digests: HashMap<String, String>,
+    drift_receiver: Option<std::sync::mpsc::Receiver<HashMap<String, String>>>,
+    drift_checked: Option<Instant>,
+    /// Vulnerability counts by image ID from the opt-in `scan_images` check,
+    /// loaded from the scan cache on its first run.
+    image_scans: Option<BTreeMap<String, ImageScan>>,
+    scan_receiver: Option<std::sync::mpsc::Receiver<(String, ImageScan)>>,
+    scan_checked: Option<Instant>,
+    /// Latency, version and log tail of the daemon, while its panel is open.
+    pub daemon_health: Option<DaemonHealth>,
+    daemon_receiver: Option<std::sync::mpsc::Receiver<DaemonCheck>>,
     pub ui_state: ContainerUIState,
+    /// Groups for the current `ui_state.grouping`; empty when ungrouped.
+    pub groups: Vec<ContainerGroupInfo>,
+    /// Indices into `containers` for the current `ui_state.sort`.
+    display_order: Vec<usize>,
+    /// Label key used by the "label" grouping mode (from config).
+    pub group_label: Option<String>,
     pub log_states: HashMap<String, LogViewState>,
     pub multi_log_state: Option<MultiLogViewState>,
     log_receivers: HashMap<String, mpsc::Receiver<String>>,
+    /// `logs --follow` processes behind nerdctl and podman log streams.
+    log_children: Vec<crate::cmd::TrackedChild>,
     multi_log_seq: u64,
     rt: Arc<tokio::runtime::Runtime>,
     pub docker_available: bool,
     pub status_message: Option<String>,
-    action_receiver: Option<ActionReceiver>,
-    pub action_in_progress: bool,
-    update_receiver: Option<std::sync::mpsc::Receiver<Result<DockerUpdateResult, String>>>,
+    /// Start/stop/restart, port probes and socket reads, run in turn.
+    actions: ActionQueue,
+    update_receiver: Option<std::sync::mpsc::Receiver<Result<DockerUpdateResult, SitrepError>>>,
+    /// Docker's container event stream, so a start or exit shows up without
+    /// waiting for the tick. None under a CLI or until subscribed.
+    events_receiver: Option<mpsc::Receiver<String>>,
+    /// An event arrived while an update was in flight; refresh again after it.
+    refresh_pending: bool,
 }
 
 impl DockerMonitor {
@@ -49,73 +162,164 @@ impl DockerMonitor {
             };
             (if available { client } else { None }, available)
         };
+        // Kubernetes nodes, bare containerd hosts and RHEL-family hosts have no Docker daemon
+        let cli = if no_docker || docker_available { None } else { ContainerCli::detect() };
 
         Self {
             client,
+            cli,
+            demo: None,
+            namespaces: Vec::new(),
             containers: Vec::new(),
-            cpu_cache: HashMap::new(),
-            cpu_refresh_cursor: 0,
+            last_updated: None,
+            update_failed: false,
+            stats_cache: HashMap::new(),
+            stats_refresh_cursor: 0,
+            stats_history: ContainerStatsHistory::default(),
+            registry_digests: HashMap::new(),
+            drift_receiver: None,
+            drift_checked: None,
+            image_scans: None,
+            scan_receiver: None,
+            scan_checked: None,
+            daemon_health: None,
+            daemon_receiver: None,
             ui_state: ContainerUIState::default(),
+            groups: Vec::new(),
+            display_order: Vec::new(),
+            group_label: None,
             log_states: HashMap::new(),
             multi_log_state: None,
             log_receivers: HashMap::new(),
+            log_children: Vec::new(),
             multi_log_seq: 0,
             rt,
-            docker_available,
+            docker_available: docker_available || cli.is_some(),
             status_message: None,
-            action_receiver: None,
-            action_in_progress: false,
+            actions: ActionQueue::new("Container action"),
             update_receiver: None,
+            events_receiver: None,
+            refresh_pending: false,
+        }
+    }
+
+    /// The made-up containers of `--demo`, without a daemon behind them.
+    pub fn demo(rt: Arc<tokio::runtime::Runtime>) -> Self {
+        Self {
+            demo: Some(Instant::now()),
+            docker_available: true,
+            ..Self::new(rt, true)
         }
     }
 
+    /// Containers shown from a recording (`--replay`), with no runtime to
+    /// query or act on.
+    pub fn replayed(rt: Arc<tokio::runtime::Runtime>, available: bool) -> Self {
+        Self {
+            docker_available: available,
+            ..Self::new(rt, true)
+        }
+    }
+
+    /// Show a recorded container list in place of a fetched one.
+    pub fn show_replayed(&mut self, containers: Vec<DockerContainerInfo>) {
+        self.containers = containers;
+        self.stats_history.record(&self.containers);
+        self.last_updated = Some(Instant::now());
+        self.update_failed = false;
+        self.rebuild_rows();
+    }
+
     /// Spawn a background update for container list and stats. Called on the 3-second tick.
     pub fn update(&mut self) {
-        if self.client.is_none() {
+        if self.client.is_none() && self.cli.is_none() && self.demo.is_none() {
             return;
         }
         if self.update_receiver.is_some() {
             return; // update already in flight
         }
+        if let Some(started) = self.demo {
+            let result = DockerUpdateResult {
+                containers: crate::demo::containers(started.elapsed().as_secs_f64()),
+                stats_cache: HashMap::new(),
+                stats_refresh_cursor: 0,
+            };
+            let (tx, rx) = std::sync::mpsc::channel();
+            let _ = tx.send(Ok(result));
+            self.update_receiver = Some(rx);
+            return;
+        }
+        // Subscribes on the first update and again after the daemon restarts
+        if self.events_receiver.is_none()
+            && let Some(ref client) = self.client {
+                self.events_receiver = Some(client.container_events(self.rt.handle()));
+            }
 
         let (tx, rx) = std::sync::mpsc::channel();
         self.update_receiver = Some(rx);
 
+        if let Some(cli) = self.cli {
+            let namespaces = self.namespaces.clone();
+            std::thread::spawn(move || {
+                let result = cli
+                    .list_containers(&namespaces)
+                    .map(|containers| DockerUpdateResult {
+                        containers,
+                        stats_cache: HashMap::new(),
+                        stats_refresh_cursor: 0,
+                    });
+                let _ = tx.send(result);
+            });
+            return;
+        }
+
         let rt = Arc::clone(&self.rt);
-        let cpu_cache = self.cpu_cache.clone();
-        let cpu_refresh_cursor = self.cpu_refresh_cursor;
+        let stats_cache = self.stats_cache.clone();
+        let stats_refresh_cursor = self.stats_refresh_cursor;
 
         std::thread::spawn(move || {
             let result = rt.block_on(async {
-                let client = match crate::docker::DockerClient::try_new() {
-                    Some(c) => c,
-                    None => return Err("Failed to connect to Docker".to_string()),
-                };
+                let client = crate::docker::DockerClient::connect()?;
 
                 let mut containers = client.list_containers().await?;
                 let ids: Vec<String> = containers.iter().map(|c| c.id.clone()).collect();
 
-                let mut new_cpu_cache = cpu_cache;
+                // Image age and digests are nice to have; a failure leaves them unset
+                match client.list_images().await {
+                    Ok(images) => {
+                        for c in &mut containers {
+                            if let Some((created, digests)) = images.get(&c.image_id) {
+                                c.image_created = Some(*created);
+                                c.image_digests = digests.clone();
+                            }
+                        }
+                    }
+                    Err(e) => tracing::debug!("{}", e),
+                }
 
-                // Reuse recent CPU values for the full list
+                let mut new_stats_cache = stats_cache;
+
+                // Reuse recent stats for the full list
                 for c in &mut containers {
-                    c.cpu_percent = *new_cpu_cache.get(&c.id).unwrap_or(&0.0);
+                    if let Some(stats) = new_stats_cache.get(&c.id) {
+                        stats.apply(c);
+                    }
                 }
 
-                const CPU_REFRESH_BATCH_SIZE: usize = 4;
+                const STATS_REFRESH_BATCH_SIZE: usize = 4;
                 let new_cursor;
                 if !ids.is_empty() {
                     // Prioritize containers not yet in cache (new containers)
                     let mut refresh_ids: Vec<String> = ids.iter()
-                        .filter(|id| !new_cpu_cache.contains_key(id.as_str()))
-                        .take(CPU_REFRESH_BATCH_SIZE)
+                        .filter(|id| !new_stats_cache.contains_key(id.as_str()))
+                        .take(STATS_REFRESH_BATCH_SIZE)
                         .cloned()
                         .collect();
 
                     // Fill remaining slots with rotating cursor
-                    let remaining = CPU_REFRESH_BATCH_SIZE.saturating_sub(refresh_ids.len());
+                    let remaining = STATS_REFRESH_BATCH_SIZE.saturating_sub(refresh_ids.len());
                     if remaining > 0 {
-                        let start = cpu_refresh_cursor.min(ids.len());
+                        let start = stats_refresh_cursor.min(ids.len());
                         let end = (start + remaining).min(ids.len());
                         for id in &ids[start..end] {
                             if !refresh_ids.contains(id) {
@@ -125,28 +329,30 @@ impl DockerMonitor {
                         new_cursor = if end >= ids.len() { 0 } else { end };
                     } else {
                         // All slots used by new containers, don't advance cursor
-                        new_cursor = cpu_refresh_cursor;
+                        new_cursor = stats_refresh_cursor;
                     }
 
                     if !refresh_ids.is_empty() {
-                        let cpu_percents = client.get_all_cpu_percents(&refresh_ids).await;
-                        for (id, cpu) in refresh_ids.iter().zip(cpu_percents.into_iter()) {
-                            new_cpu_cache.insert(id.clone(), cpu);
+                        let stats = client.get_all_stats(&refresh_ids).await;
+                        for (id, snapshot) in refresh_ids.iter().zip(stats) {
+                            new_stats_cache.insert(id.clone(), snapshot);
                         }
                     }
                 } else {
                     new_cursor = 0;
                 }
 
-                new_cpu_cache.retain(|id, _| ids.contains(id));
+                new_stats_cache.retain(|id, _| ids.contains(id));
                 for c in &mut containers {
-                    c.cpu_percent = *new_cpu_cache.get(&c.id).unwrap_or(&c.cpu_percent);
+                    if let Some(stats) = new_stats_cache.get(&c.id) {
+                        stats.apply(c);
+                    }
                 }
 
                 Ok(DockerUpdateResult {
                     containers,
-                    cpu_cache: new_cpu_cache,
-                    cpu_refresh_cursor: new_cursor,
+                    stats_cache: new_stats_cache,
+                    stats_refresh_cursor: new_cursor,
                 })
             });
             let _ = tx.send(result);
@@ -160,36 +366,29 @@ impl DockerMonitor {
         };
         match rx.try_recv() {
             Ok(Ok(result)) => {
+                tracing::debug!("Docker update: {} containers", result.containers.len());
                 self.containers = result.containers;
-                self.cpu_cache = result.cpu_cache;
-                self.cpu_refresh_cursor = result.cpu_refresh_cursor;
+                self.stats_cache = result.stats_cache;
+                self.stats_refresh_cursor = result.stats_refresh_cursor;
+                self.stats_history.record(&self.containers);
+                self.apply_image_drift();
+                self.apply_image_scans();
                 self.update_receiver = None;
-
-                // Restore selection by container ID, or clamp
-                let total = self.containers.len();
-                self.ui_state.total_rows = total;
-                if let Some(ref prev_id) = self.ui_state.selected_id {
-                    if let Some(pos) = self.containers.iter().position(|c| &c.id == prev_id) {
-                        self.ui_state.selected_index = pos;
-                    } else if self.ui_state.selected_index >= total && total > 0 {
-                        self.ui_state.selected_index = total - 1;
-                    }
-                } else if self.ui_state.selected_index >= total && total > 0 {
-                    self.ui_state.selected_index = total - 1;
-                }
-                // Update tracked ID
-                self.ui_state.selected_id = self.containers
-                    .get(self.ui_state.selected_index)
-                    .map(|c| c.id.clone());
+                self.last_updated = Some(Instant::now());
+                self.update_failed = false;
+                self.rebuild_rows();
                 true
             }
             Ok(Err(e)) => {
                 tracing::warn!("Docker update failed: {}", e);
                 self.containers.clear();
-                self.cpu_cache.clear();
-                self.cpu_refresh_cursor = 0;
-                self.status_message = Some(format!("Error: {}", e));
+                self.groups.clear();
+                self.display_order.clear();
+                self.stats_cache.clear();
+                self.stats_refresh_cursor = 0;
+                self.status_message = Some(format!("Error: {}", e.with_hint()));
                 self.update_receiver = None;
+                self.update_failed = true;
                 true
             }
             Err(std::sync::mpsc::TryRecvError::Empty) => false,
@@ -200,26 +399,427 @@ impl DockerMonitor {
         }
     }
 
+    /// Drain container events and start an update when any arrived. A burst
+    /// (stop sends kill, die and stop) collapses into one refresh, plus one
+    /// more if it landed while an update was already running.
+    pub fn poll_events(&mut self) {
+        let Some(ref mut rx) = self.events_receiver else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(action) => {
+                    tracing::debug!("Docker event: {}", action);
+                    self.refresh_pending = true;
+                }
+                Err(mpsc::error::TryRecvError::Empty) => break,
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.events_receiver = None;
+                    break;
+                }
+            }
+        }
+        if self.refresh_pending && self.update_receiver.is_none() {
+            self.refresh_pending = false;
+            self.update();
+        }
+    }
+
+    /// Start a background lookup of the registry digest of every pulled image
+    /// when the last one is older than DRIFT_CHECK_INTERVAL.
+    pub fn check_image_drift(&mut self) {
+        if self.client.is_none()
+            || self.drift_receiver.is_some()
+            || self.drift_checked.is_some_and(|t| t.elapsed() < DRIFT_CHECK_INTERVAL)
+        {
+            return;
+        }
+        // Images built locally have no registry digest; a container whose tag
+        // has since moved reports the bare image ID
+        let mut images: Vec<String> = self
+            .containers
+            .iter()
+            .filter(|c| !c.image_digests.is_empty() && !c.image.starts_with("sha256:"))
+            .map(|c| c.image.clone())
+            .collect();
+        images.sort();
+        images.dedup();
+        if images.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let rt = Arc::clone(&self.rt);
+        std::thread::spawn(move || {
+            let digests = rt.block_on(async {
+                let Some(client) = crate::docker::DockerClient::try_new() else {
+                    return HashMap::new();
+                };
+                let mut digests = HashMap::new();
+                for image in images {
+                    match client.registry_digest(&image).await {
+                        Ok(digest) => {
+                            digests.insert(image, digest);
+                        }
+                        Err(e) => tracing::debug!("Registry digest check failed: {}", e),
+                    }
+                }
+                digests
+            });
+            let _ = tx.send(digests);
+        });
+        self.drift_receiver = Some(rx);
+        self.drift_checked = Some(Instant::now());
+    }
+
+    /// Pick up a finished registry digest check. Returns true when one completed.
+    pub fn poll_image_drift(&mut self) -> bool {
+        let Some(ref rx) = self.drift_receiver else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(digests) => {
+                self.registry_digests = digests;
+                self.drift_receiver = None;
+                self.apply_image_drift();
+                true
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => false,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.drift_receiver = None;
+                false
+            }
+        }
+    }
+
+    /// Start scanning, one after another in the background, the images that
+    /// were never scanned, were scanned over SCAN_MAX_AGE ago, or failed to
+    /// scan; at most every DRIFT_CHECK_INTERVAL.
+    pub fn check_image_scans(&mut self) {
+        if self.demo.is_some()
+            || self.scan_receiver.is_some()
+            || self.scan_checked.is_some_and(|t| t.elapsed() < DRIFT_CHECK_INTERVAL)
+            || self.containers.is_empty()
+        {
+            return;
+        }
+        let now = chrono::Utc::now().timestamp();
+        let scans = self.image_scans.get_or_insert_with(|| crate::scan::load(&crate::scan::default_path()));
+        let due = |id: &String| match scans.get(id) {
+            Some(scan) => scan.error.is_some() || now - scan.scanned >= SCAN_MAX_AGE.as_secs() as i64,
+            None => true,
+        };
+        let mut images: Vec<(String, String)> = self
+            .containers
+            .iter()
+            .filter(|c| !c.image_id.is_empty() && due(&c.image_id))
+            .map(|c| (c.image_id.clone(), c.image.clone()))
+            .collect();
+        images.sort();
+        images.dedup_by(|a, b| a.0 == b.0);
+        self.scan_checked = Some(Instant::now());
+        if images.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let Some(scanner) = crate::scan::Scanner::detect() else {
+                tracing::debug!("Image scans: neither trivy nor docker scout is installed");
+                return;
+            };
+            for (id, image) in images {
+                let scan = scanner.scan(&image, chrono::Utc::now().timestamp());
+                if let Some(ref e) = scan.error {
+                    tracing::debug!("Scanning {} failed: {}", image, e);
+                }
+                if tx.send((id, scan)).is_err() {
+                    return;
+                }
+            }
+        });
+        self.scan_receiver = Some(rx);
+    }
+
+    /// Pick up the image scans finished since the last call and write them to
+    /// the scan cache. Returns true when one finished.
+    pub fn poll_image_scans(&mut self) -> bool {
+        let Some(ref rx) = self.scan_receiver else {
+            return false;
+        };
+        let mut finished: Vec<(String, ImageScan)> = Vec::new();
+        loop {
+            match rx.try_recv() {
+                Ok(scan) => finished.push(scan),
+                Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.scan_receiver = None;
+                    break;
+                }
+            }
+        }
+        if finished.is_empty() {
+            return false;
+        }
+        let scans = self.image_scans.get_or_insert_with(BTreeMap::new);
+        scans.extend(finished);
+        if let Err(e) = crate::scan::save(&crate::scan::default_path(), scans) {
+            tracing::warn!("Saving image scans failed: {}", e);
+        }
+        self.apply_image_scans();
+        true
+    }
+
+    /// Start timing the daemon's API and reading its log tail in the
+    /// background, unless a check is still running. A daemon that takes
+    /// seconds to answer keeps only one check in flight.
+    pub fn check_daemon(&mut self) {
+        if self.client.is_none() || self.daemon_receiver.is_some() {
+            return;
+        }
+        self.daemon_health.get_or_insert_with(|| DaemonHealth {
+            endpoint: crate::docker::endpoint(),
+            ..Default::default()
+        });
+        let (tx, rx) = std::sync::mpsc::channel();
+        let rt = Arc::clone(&self.rt);
+        std::thread::spawn(move || {
+            let (latency, info) = rt.block_on(async {
+                match crate::docker::DockerClient::connect() {
+                    Ok(client) => client.check_daemon().await,
+                    Err(e) => (Default::default(), Err(e)),
+                }
+            });
+            let log = crate::docker::daemon_log_tail(crate::docker::DAEMON_LOG_LINES);
+            let _ = tx.send(DaemonCheck { latency, info, log });
+        });
+        self.daemon_receiver = Some(rx);
+    }
+
+    /// Pick up a finished daemon check. Returns true when one completed.
+    pub fn poll_daemon(&mut self) -> bool {
+        let Some(ref rx) = self.daemon_receiver else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(check) => {
+                self.daemon_receiver = None;
+                let health = self.daemon_health.get_or_insert_with(DaemonHealth::default);
+                health.record(check.latency);
+                match check.info {
+                    Ok((version, driver, rootless)) => {
+                        health.version = version;
+                        health.storage_driver = driver;
+                        health.rootless = rootless;
+                        health.error = None;
+                    }
+                    Err(e) => health.error = Some(e.with_hint()),
+                }
+                match check.log {
+                    Ok((source, lines)) => {
+                        health.log_source = source;
+                        health.log_error = None;
+                        health.log_lines = lines;
+                    }
+                    Err(e) => {
+                        health.log_error = Some(e.to_string());
+                        health.log_lines.clear();
+                    }
+                }
+                true
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => false,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.daemon_receiver = None;
+                false
+            }
+        }
+    }
+
+    fn apply_image_drift(&mut self) {
+        for c in &mut self.containers {
+            c.image_drift = self.registry_digests.get(&c.image).map(|digest| !c.runs_digest(digest));
+        }
+    }
+
+    fn apply_image_scans(&mut self) {
+        let Some(ref scans) = self.image_scans else { return };
+        for c in &mut self.containers {
+            c.image_scan = scans.get(&c.image_id).cloned();
+        }
+    }
+
     /// Check if Docker is available (for showing/hiding the tab).
     pub fn is_available(&self) -> bool {
         self.docker_available
     }
 
+    /// The CLI containers are read through instead of the Docker API.
+    pub fn container_cli(&self) -> Option<ContainerCli> {
+        self.cli
+    }
+
+    /// containerd namespace of a listed container, for nerdctl commands.
+    fn namespace_of(&self, container_id: &str) -> String {
+        self.containers
+            .iter()
+            .find(|c| c.id == container_id)
+            .map(|c| c.namespace.clone())
+            .unwrap_or_default()
+    }
+
+    /// Visible rows of the container list (group headers and containers).
+    pub fn rows(&self) -> Vec<ContainerRow> {
+        container_rows(&self.display_order, &self.groups, &self.ui_state.collapsed_groups)
+    }
+
+    /// Stable identity of a row, used to keep the selection across refreshes.
+    fn row_key(&self, row: ContainerRow) -> String {
+        match row {
+            ContainerRow::GroupHeader(gi) => format!("group:{}", self.groups[gi].name),
+            ContainerRow::Container(i) => self.containers[i].id.clone(),
+        }
+    }
+
+    /// Regroup containers and restore the selection by row identity, or clamp.
+    pub fn rebuild_rows(&mut self) {
+        self.display_order = container_order(
+            &self.containers,
+            self.ui_state.sort,
+            &self.ui_state.label_filter,
+        );
+        self.groups = group_containers(
+            &self.containers,
+            &self.display_order,
+            self.ui_state.grouping,
+            self.group_label.as_deref(),
+        );
+        let rows = self.rows();
+        let total = rows.len();
+        self.ui_state.total_rows = total;
+        if let Some(ref prev_id) = self.ui_state.selected_id
+            && let Some(pos) = rows.iter().position(|r| &self.row_key(*r) == prev_id) {
+                self.ui_state.selected_index = pos;
+            }
+        if self.ui_state.selected_index >= total && total > 0 {
+            self.ui_state.selected_index = total - 1;
+        }
+        // Update tracked ID
+        self.ui_state.selected_id = rows
+            .get(self.ui_state.selected_index)
+            .map(|r| self.row_key(*r));
+    }
+
+    /// Move the selection to the given row.
+    pub fn select_row(&mut self, index: usize) {
+        let rows = self.rows();
+        if index >= rows.len() {
+            return;
+        }
+        self.ui_state.selected_index = index;
+        self.ui_state.selected_id = Some(self.row_key(rows[index]));
+        self.status_message = None;
+    }
+
+    /// Select the row of the container with `id`. False when it is not
+    /// listed (filtered out or in a collapsed group).
+    pub fn select_container(&mut self, id: &str) -> bool {
+        let rows = self.rows();
+        let Some(pos) = rows
+            .iter()
+            .position(|r| matches!(r, ContainerRow::Container(i) if self.containers[*i].id == id))
+        else {
+            return false;
+        };
+        self.select_row(pos);
+        true
+    }
+
     /// Get the currently selected container, if any.
     pub fn selected_container(&self) -> Option<&DockerContainerInfo> {
-        self.containers.get(self.ui_state.selected_index)
+        match self.rows().get(self.ui_state.selected_index)? {
+            ContainerRow::Container(i) => self.containers.get(*i),
+            ContainerRow::GroupHeader(_) => None,
+        }
+    }
+
+    /// Get the currently selected group header, if any.
+    pub fn selected_group(&self) -> Option<&ContainerGroupInfo> {
+        match self.rows().get(self.ui_state.selected_index)? {
+            ContainerRow::GroupHeader(gi) => self.groups.get(*gi),
+            ContainerRow::Container(_) => None,
+        }
+    }
+
+    /// Switch to the next grouping mode.
+    pub fn cycle_grouping(&mut self) {
+        self.ui_state.grouping = self.ui_state.grouping.next(self.group_label.is_some());
+        self.rebuild_rows();
+        let mode = match (self.ui_state.grouping, &self.group_label) {
+            (crate::model::ContainerGrouping::Label, Some(label)) => format!("label '{}'", label),
+            (grouping, _) => grouping.label().to_string(),
+        };
+        self.status_message = Some(format!("Grouping: {}", mode));
+    }
+
+    /// Toggle between Docker's order and most-recently-started first.
+    pub fn toggle_uptime_sort(&mut self) {
+        self.ui_state.sort = match self.ui_state.sort {
+            ContainerSort::Default => ContainerSort::Uptime,
+            ContainerSort::Uptime => ContainerSort::Default,
+        };
+        self.rebuild_rows();
+        self.status_message = Some(match self.ui_state.sort {
+            ContainerSort::Default => "Sort: default".to_string(),
+            ContainerSort::Uptime => "Sort: most recently started first".to_string(),
+        });
+    }
+
+    /// Apply the label filter typed into the filter prompt. An empty filter shows
+    /// every container.
+    pub fn apply_label_filter(&mut self) {
+        match crate::model::parse_label_filter(&self.ui_state.filter_input) {
+            Ok(filter) => {
+                self.status_message = if filter.is_empty() {
+                    Some("Label filter cleared".to_string())
+                } else {
+                    Some(format!("Label filter: {}", crate::model::format_label_filter(&filter)))
+                };
+                self.ui_state.label_filter = filter;
+                self.ui_state.filter_mode = false;
+                self.rebuild_rows();
+            }
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+
+    /// Collapse or expand a group header.
+    pub fn set_group_collapsed(&mut self, name: &str, collapsed: bool) {
+        if collapsed {
+            self.ui_state.collapsed_groups.insert(name.to_string());
+        } else {
+            self.ui_state.collapsed_groups.remove(name);
+        }
+        self.rebuild_rows();
     }
 
     /// Start tailing logs for the given container.
     pub fn start_log_stream(&mut self, container_id: &str, container_name: &str) {
-        // Create a fresh client for this log stream to avoid any concurrency issues
-        let Some(client) = DockerClient::try_new() else { 
-            return 
+        let rx = if self.demo.is_some() {
+            crate::demo::container_logs()
+        } else {
+            match self.follow_logs(container_id) {
+                Ok((child, rx)) => {
+                    self.log_children.extend(child);
+                    rx
+                }
+                Err(e) => {
+                    self.status_message = Some(e);
+                    return;
+                }
+            }
         };
 
-        let handle = self.rt.handle();
-        let rx = client.tail_logs(container_id, handle);
-
         self.log_states.insert(
             container_id.to_string(),
             LogViewState::new(container_id.to_string(), container_name.to_string()),
@@ -227,6 +827,19 @@ impl DockerMonitor {
         self.log_receivers.insert(container_id.to_string(), rx);
     }
 
+    /// Follow a container's logs through whichever backend lists it: the CLI
+    /// child, if one was started, and the line receiver. Also used by the
+    /// Swarm split log view.
+    pub fn follow_logs(&self, container_id: &str) -> Result<(Option<crate::cmd::TrackedChild>, mpsc::Receiver<String>), String> {
+        if let Some(cli) = self.cli {
+            let (child, rx) = cli.tail_logs(&self.namespace_of(container_id), container_id).map_err(String::from)?;
+            return Ok((Some(child), rx));
+        }
+        // Create a fresh client for this log stream to avoid any concurrency issues
+        let client = DockerClient::try_new().ok_or_else(|| "Docker is not reachable".to_string())?;
+        Ok((None, client.tail_logs(container_id, self.rt.handle())))
+    }
+
     /// Start tailing logs for multiple containers (preserve existing streams).
     pub fn start_log_stream_multi(&mut self, containers: &[(String, String)]) {
         if self.multi_log_state.is_none() {
@@ -243,6 +856,7 @@ impl DockerMonitor {
     /// Stop the log stream and return to container list.
     pub fn stop_log_stream(&mut self) {
         self.log_receivers.clear();
+        self.log_children.clear();
         self.log_states.clear();
         self.multi_log_state = None;
         self.multi_log_seq = 0;
@@ -304,14 +918,13 @@ impl DockerMonitor {
                         disconnected_ids.push(container_id.clone());
                         if let Some(ref mut log_state) = self.log_states.get_mut(container_id) {
                             let container_name = log_state.container_name.clone();
-                            let stream_ended = "[log stream ended]".to_string();
-                            log_state.push_line(stream_ended.clone());
+                            log_state.end_stream();
                             if let Some(ref mut multi) = self.multi_log_state {
                                 self.multi_log_seq += 1;
                                 multi.push_line(MultiLogLine {
                                     container_id: container_id.clone(),
                                     container_name,
-                                    line: stream_ended,
+                                    line: crate::model::LOG_STREAM_ENDED.to_string(),
                                     seq: self.multi_log_seq,
                                 });
                             }
@@ -334,85 +947,226 @@ impl DockerMonitor {
 
     /// Container action: start (non-blocking).
     pub fn start_container(&mut self, container_id: &str) {
-        self.run_container_action(container_id, "start");
+        self.run_container_action(&[(container_id.to_string(), container_id.to_string())], "start");
     }
 
     /// Container action: stop (non-blocking).
     pub fn stop_container(&mut self, container_id: &str) {
-        self.run_container_action(container_id, "stop");
+        self.run_container_action(&[(container_id.to_string(), container_id.to_string())], "stop");
     }
 
     /// Container action: restart (non-blocking).
     pub fn restart_container(&mut self, container_id: &str) {
-        self.run_container_action(container_id, "restart");
+        self.run_container_action(&[(container_id.to_string(), container_id.to_string())], "restart");
     }
 
-    /// Run a container action in a background thread to keep the TUI responsive.
-    fn run_container_action(&mut self, container_id: &str, action: &str) {
-        if self.action_in_progress {
-            self.status_message = Some("An action is already in progress...".to_string());
+    /// Start, stop or restart each of `targets` (id, name) in turn, in the
+    /// background, reporting every container's outcome in one status line
+    /// (non-blocking).
+    pub fn batch_action(&mut self, targets: &[(String, String)], action: &str) {
+        self.run_container_action(targets, action);
+    }
+
+    /// Queue a container action on (id, label) targets, run one after the
+    /// other in the background to keep the TUI responsive.
+    fn run_container_action(&mut self, targets: &[(String, String)], action: &str) {
+        let verb = match action {
+            "start" => "Starting",
+            "stop" => "Stopping",
+            _ => "Restarting",
+        };
+        let label = match targets {
+            [(_, name)] => format!("{} {}", verb, name),
+            _ => format!("{} {} containers", verb, targets.len()),
+        };
+
+        let rt = Arc::clone(&self.rt);
+        let act = action.to_string();
+        let total = targets.len();
+        let job: Job = if let Some(cli) = self.cli {
+            let targets: Vec<(String, String, String)> = targets
+                .iter()
+                .map(|(id, label)| (id.clone(), label.clone(), self.namespace_of(id)))
+                .collect();
+            Box::new(move |progress| {
+                let mut outcomes = Vec::with_capacity(total);
+                for (i, (id, label, namespace)) in targets.into_iter().enumerate() {
+                    outcomes.push((label, cli.container_action(&namespace, &id, &act).map_err(String::from)));
+                    if total > 1 {
+                        progress(format!("{}/{} done", i + 1, total));
+                    }
+                }
+                summarize_batch(&act, outcomes)
+            })
+        } else {
+            // We need a fresh client connection since DockerClient isn't Send across threads.
+            // Instead, block on the existing tokio runtime from the queue's thread.
+            let targets = targets.to_vec();
+            Box::new(move |progress| {
+                let result = rt.block_on(async {
+                    let client = crate::docker::DockerClient::connect()?;
+                    let mut outcomes = Vec::with_capacity(total);
+                    for (i, (id, label)) in targets.into_iter().enumerate() {
+                        let result = match act.as_str() {
+                            "start" => client.start_container(&id).await,
+                            "stop" => client.stop_container(&id).await,
+                            "restart" => client.restart_container(&id).await,
+                            _ => Err(SitrepError::Failed("Unknown action".to_string())),
+                        };
+                        match &result {
+                            Ok(()) => tracing::info!("{} {}: ok", act, label),
+                            Err(e) => tracing::warn!("{} {}: {}", act, label, e),
+                        }
+                        outcomes.push((label, result.map_err(|e| e.with_hint())));
+                        if total > 1 {
+                            progress(format!("{}/{} done", i + 1, total));
+                        }
+                    }
+                    Ok::<_, SitrepError>(outcomes)
+                });
+                result.map_err(|e| e.with_hint()).and_then(|outcomes| summarize_batch(&act, outcomes))
+            })
+        };
+        self.queue_action(label, job);
+    }
+
+    /// Rename a container (non-blocking).
+    pub fn rename_container(&mut self, container_id: &str, old_name: &str, new_name: &str) {
+        let rt = Arc::clone(&self.rt);
+        let id = container_id.to_string();
+        let (old, new) = (old_name.to_string(), new_name.to_string());
+        let cli = self.cli.map(|cli| (cli, self.namespace_of(&id)));
+        self.queue_action(
+            format!("Renaming {} to {}", old_name, new_name),
+            Box::new(move |_| {
+                let result = match cli {
+                    Some((cli, namespace)) => cli.rename(&namespace, &id, &new).map_err(String::from),
+                    None => rt
+                        .block_on(async { crate::docker::DockerClient::connect()?.rename_container(&id, &new).await })
+                        .map_err(|e| e.with_hint()),
+                };
+                result.map(|()| format!("Renamed {} to {}", old, new))
+            }),
+        );
+    }
+
+    /// Add `job` to the action queue and show the queue in the status line.
+    fn queue_action(&mut self, label: String, job: Job) {
+        if !self.actions.push(label.clone(), job) {
+            self.status_message = Some(format!("{} is already queued", label));
             return;
         }
+        self.status_message = self.actions.status_line();
+    }
 
-        let (tx, rx) = std::sync::mpsc::channel();
-        self.action_receiver = Some(rx);
-        self.action_in_progress = true;
-        self.status_message = Some(format!("{}ing container {}...", capitalize(action), container_id));
+    /// Probe the selected container's published TCP ports from the host and
+    /// report connect latency or failure per port (non-blocking).
+    pub fn probe_ports(&mut self, container: &DockerContainerInfo) {
+        if container.published_ports.is_empty() {
+            self.status_message = Some(format!("{} has no published TCP ports", container.name));
+            return;
+        }
+
+        let name = container.name.clone();
+        let ports = container.published_ports.clone();
+        self.queue_action(
+            format!("Probing ports of {}", container.name),
+            Box::new(move |_| {
+                let results: Vec<String> = ports
+                    .iter()
+                    .map(|p| {
+                        let host = crate::probe::dial_host(&p.host_ip);
+                        let outcome = match crate::probe::probe_tcp(host, p.host_port, crate::probe::PROBE_TIMEOUT) {
+                            Ok(latency) => format!("ok {}", crate::probe::format_latency(latency)),
+                            Err(e) => format!("FAILED ({})", e),
+                        };
+                        format!("{}->{} {}", p.host_port, p.container_port, outcome)
+                    })
+                    .collect();
+                Ok(format!("Probe {}: {}", name, results.join(", ")))
+            }),
+        );
+    }
+
+    /// Show TCP state counts and listening ports from inside the container's
+    /// network namespace, which host-level socket stats don't break out (non-blocking).
+    pub fn namespace_sockets(&mut self, container: &DockerContainerInfo) {
+        if !cfg!(target_os = "linux") {
+            self.status_message = Some("Container socket stats need a Linux Docker host".to_string());
+            return;
+        }
 
         let rt = Arc::clone(&self.rt);
-        let id = container_id.to_string();
-        let act = action.to_string();
-        // We need a fresh client connection since DockerClient isn't Send across threads.
-        // Instead, spawn on the existing tokio runtime from a new std::thread.
-        std::thread::spawn(move || {
-            let result = rt.block_on(async {
-                let client = match crate::docker::DockerClient::try_new() {
-                    Some(c) => c,
-                    None => return Err("Failed to connect to Docker".to_string()),
+        let id = container.id.clone();
+        let name = container.name.clone();
+        let cli = self.cli.map(|cli| (cli, container.namespace.clone()));
+        self.queue_action(
+            format!("Reading sockets of {}", container.name),
+            Box::new(move |_| {
+                let result = match cli {
+                    Some((cli, namespace)) => cli.container_pid(&namespace, &id).map_err(String::from),
+                    None => rt
+                        .block_on(async { crate::docker::DockerClient::connect()?.container_pid(&id).await })
+                        .map_err(|e| e.with_hint()),
                 };
-                match act.as_str() {
-                    "start" => client.start_container(&id).await.map(|_| format!("Started {}", id)),
-                    "stop" => client.stop_container(&id).await.map(|_| format!("Stopped {}", id)),
-                    "restart" => client.restart_container(&id).await.map(|_| format!("Restarted {}", id)),
-                    _ => Err("Unknown action".to_string()),
-                }
-            });
-            let _ = tx.send(result);
-        });
+                result
+                    .and_then(crate::collectors::linux::read_namespace_sockets)
+                    .map(|info| format!("Sockets {}: {}", name, info.summary()))
+            }),
+        );
     }
 
-    /// Poll for background action completion. Returns true if status changed.
+    /// Poll the action queue. Returns true if status changed.
     pub fn poll_action(&mut self) -> bool {
-        let Some(ref rx) = self.action_receiver else { return false };
-        match rx.try_recv() {
-            Ok(Ok(msg)) => {
-                self.status_message = Some(msg);
-                self.action_in_progress = false;
-                self.action_receiver = None;
-                true
-            }
-            Ok(Err(msg)) => {
-                tracing::error!("Container action failed: {}", msg);
-                self.status_message = Some(format!("Error: {}", msg));
-                self.action_in_progress = false;
-                self.action_receiver = None;
-                true
-            }
-            Err(std::sync::mpsc::TryRecvError::Empty) => false,
-            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                self.status_message = Some("Action failed unexpectedly".to_string());
-                self.action_in_progress = false;
-                self.action_receiver = None;
-                true
-            }
+        if !self.actions.poll() {
+            return false;
         }
+        self.status_message = self.actions.status_line();
+        true
+    }
+
+    /// Whether a container action is running or queued.
+    pub fn action_in_progress(&self) -> bool {
+        self.actions.is_busy()
+    }
+}
+
+/// One status line for the outcome of `action` on each (label, result)
+/// target; an error when any of them failed. A single target reads as it
+/// always has: "Stopped web" or its error.
+fn summarize_batch(action: &str, outcomes: Vec<(String, Result<(), String>)>) -> Result<String, String> {
+    let done = match action {
+        "start" => "Started",
+        "stop" => "Stopped",
+        _ => "Restarted",
+    };
+    if let [(label, result)] = outcomes.as_slice() {
+        return result.clone().map(|_| format!("{} {}", done, label));
+    }
+    let ok = outcomes.iter().filter(|(_, r)| r.is_ok()).count();
+    let failures: Vec<String> = outcomes
+        .iter()
+        .filter_map(|(label, r)| r.as_ref().err().map(|e| format!("{}: {}", label, e)))
+        .collect();
+    let summary = format!("{} {}/{}", done, ok, outcomes.len());
+    if failures.is_empty() {
+        let labels: Vec<&str> = outcomes.iter().map(|(label, _)| label.as_str()).collect();
+        Ok(format!("{}: {}", summary, labels.join(", ")))
+    } else {
+        Err(format!("{}; failed {}", summary, failures.join("; ")))
     }
 }
 
-fn capitalize(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().to_string() + c.as_str(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_summary_names_each_failure() {
+        let ok = summarize_batch("restart", vec![("web".into(), Ok(())), ("api".into(), Ok(()))]);
+        assert_eq!(ok, Ok("Restarted 2/2: web, api".to_string()));
+        let mixed = summarize_batch("stop", vec![("web".into(), Ok(())), ("db".into(), Err("no such container".into()))]);
+        assert_eq!(mixed, Err("Stopped 1/2; failed db: no such container".to_string()));
+        assert_eq!(summarize_batch("start", vec![("web".into(), Ok(()))]), Ok("Started web".to_string()));
     }
 }
@@ -0,0 +1,140 @@
+//! Failures of the Docker API and `docker` CLI calls, sorted into the few
+//! classes an operator fixes in different ways, so a status line can say
+//! what to try next instead of only echoing the daemon's message.
+
+use std::fmt;
+use std::io;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SitrepError {
+    /// The command is not installed or not on PATH.
+    NotInstalled(String),
+    /// The socket or command needs more privileges than sitrep has.
+    PermissionDenied(String),
+    Timeout(String),
+    /// No daemon answers at the configured endpoint.
+    DaemonUnavailable(String),
+    /// Output that could not be read, e.g. from an unexpected Docker version.
+    ParseError(String),
+    /// Anything else the daemon refused, such as an unknown service.
+    Failed(String),
+}
+
+impl SitrepError {
+    /// Classify an error running `context`'s command.
+    pub fn from_io(context: &str, e: &io::Error) -> Self {
+        let message = format!("{}: {}", context, e);
+        match e.kind() {
+            io::ErrorKind::NotFound => SitrepError::NotInstalled(message),
+            io::ErrorKind::PermissionDenied => SitrepError::PermissionDenied(message),
+            io::ErrorKind::TimedOut => SitrepError::Timeout(message),
+            io::ErrorKind::ConnectionRefused => SitrepError::DaemonUnavailable(message),
+            _ => SitrepError::Failed(message),
+        }
+    }
+
+    /// Classify a failed command by what it printed to stderr.
+    pub fn from_stderr(context: &str, stderr: &str) -> Self {
+        let message = format!("{}: {}", context, stderr.trim());
+        let lower = stderr.to_lowercase();
+        if lower.contains("permission denied") {
+            SitrepError::PermissionDenied(message)
+        } else if lower.contains("cannot connect to the docker daemon") || lower.contains("is the docker daemon running") {
+            SitrepError::DaemonUnavailable(message)
+        } else if lower.contains("timed out") || lower.contains("deadline exceeded") {
+            SitrepError::Timeout(message)
+        } else {
+            SitrepError::Failed(message)
+        }
+    }
+
+    /// Classify an error from the Docker API client.
+    pub fn from_bollard(context: &str, e: bollard::errors::Error) -> Self {
+        use bollard::errors::Error;
+        let message = format!("{}: {}", context, e);
+        match e {
+            Error::IOError { ref err } if err.kind() == io::ErrorKind::NotFound => SitrepError::DaemonUnavailable(message),
+            Error::IOError { ref err } => SitrepError::from_io(context, err),
+            Error::SocketNotFoundError(_) | Error::HyperLegacyError { .. } => SitrepError::DaemonUnavailable(message),
+            Error::RequestTimeoutError => SitrepError::Timeout(message),
+            Error::DockerResponseServerError { status_code: 401 | 403, .. } => SitrepError::PermissionDenied(message),
+            Error::JsonDataError { .. } | Error::JsonSerdeError { .. } | Error::StrParseError { .. } => {
+                SitrepError::ParseError(message)
+            }
+            _ => SitrepError::Failed(message),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            SitrepError::NotInstalled(m)
+            | SitrepError::PermissionDenied(m)
+            | SitrepError::Timeout(m)
+            | SitrepError::DaemonUnavailable(m)
+            | SitrepError::ParseError(m)
+            | SitrepError::Failed(m) => m,
+        }
+    }
+
+    /// What usually fixes this class of failure.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            SitrepError::NotInstalled(_) => Some("install the Docker CLI or add it to PATH"),
+            SitrepError::PermissionDenied(_) => Some("add your user to the docker group or run sitrep as root"),
+            SitrepError::Timeout(_) => Some("the daemon is slow to answer; check its load in the daemon panel (D)"),
+            SitrepError::DaemonUnavailable(_) => {
+                Some("start the daemon (systemctl start docker) or point --docker-host at it")
+            }
+            SitrepError::ParseError(_) => Some("unexpected output from this Docker version; --log-level debug shows it"),
+            SitrepError::Failed(_) => None,
+        }
+    }
+
+    /// The message followed by the hint, for a status line.
+    pub fn with_hint(&self) -> String {
+        match self.hint() {
+            Some(hint) => format!("{} ({})", self.message(), hint),
+            None => self.message().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SitrepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for SitrepError {}
+
+/// For callers that still report errors as strings.
+impl From<SitrepError> for String {
+    fn from(e: SitrepError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_cli_and_io_failures() {
+        let e = SitrepError::from_stderr(
+            "docker node ls",
+            "permission denied while trying to connect to the Docker daemon socket at unix:///var/run/docker.sock\n",
+        );
+        assert!(matches!(e, SitrepError::PermissionDenied(_)));
+        assert!(e.with_hint().ends_with("(add your user to the docker group or run sitrep as root)"));
+
+        let e = SitrepError::from_stderr("docker service ls", "Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?");
+        assert!(matches!(e, SitrepError::DaemonUnavailable(_)));
+        let e = SitrepError::from_stderr("docker service ps", "no such service: web");
+        assert_eq!((e.message(), e.hint()), ("docker service ps: no such service: web", None));
+
+        let missing = io::Error::new(io::ErrorKind::NotFound, "No such file or directory");
+        assert!(matches!(SitrepError::from_io("docker", &missing), SitrepError::NotInstalled(_)));
+        let slow = io::Error::new(io::ErrorKind::TimedOut, "docker timed out after 10s");
+        assert!(matches!(SitrepError::from_io("docker", &slow), SitrepError::Timeout(_)));
+    }
+}
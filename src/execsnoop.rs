@@ -0,0 +1,146 @@
+//! Catch processes that start and exit between refreshes: cron spawns,
+//! health check scripts, fork storms. On Linux as root the exec tracepoint
+//! is followed with `bpftrace`, which sees every exec. Elsewhere (no root,
+//! no bpftrace, or macOS, where EndpointSecurity needs a signed and entitled
+//! binary) the process list is diffed every PS_INTERVAL, which still misses
+//! anything shorter-lived than that. Each start is sent as its command name.
+
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::cmd::{self, TrackedChild};
+
+/// How often the process list is diffed without bpftrace.
+const PS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Print the new command name of every successful exec.
+const BPFTRACE_PROGRAM: &str = r#"tracepoint:sched:sched_process_exec { printf("exec %s\n", comm); }"#;
+
+/// A running snoop; the tracer or poller stops when this is dropped.
+pub struct ExecStream {
+    /// "bpftrace" or "ps every 250ms".
+    pub source: String,
+    pub receiver: mpsc::Receiver<String>,
+    _child: Option<TrackedChild>,
+    /// Dropping it stops the ps poller.
+    _stop: Option<mpsc::Sender<()>>,
+}
+
+/// Start the most complete snoop available.
+pub fn follow() -> Result<ExecStream, String> {
+    if cfg!(target_os = "linux") && is_root() {
+        match follow_bpftrace() {
+            Ok(stream) => return Ok(stream),
+            Err(e) => tracing::debug!("{}", e),
+        }
+    }
+    follow_ps()
+}
+
+fn is_root() -> bool {
+    use std::os::unix::fs::MetadataExt;
+    // The owner of /proc/self is the effective user
+    std::fs::metadata("/proc/self").is_ok_and(|m| m.uid() == 0)
+}
+
+fn follow_bpftrace() -> Result<ExecStream, String> {
+    let spawned = cmd::spawn(
+        Command::new("bpftrace")
+            .args(["-e", BPFTRACE_PROGRAM])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null()),
+    )
+    .map_err(|e| format!("Failed to start bpftrace: {}", e))?;
+    let stdout = spawned.stdout.ok_or("no stdout pipe")?;
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            // Skips bpftrace's "Attaching 1 probe..." banner
+            if let Some(comm) = line.strip_prefix("exec ")
+                && tx.send(comm.to_string()).is_err() {
+                    break;
+                }
+        }
+    });
+    Ok(ExecStream {
+        source: "bpftrace".to_string(),
+        receiver: rx,
+        _child: Some(spawned.child),
+        _stop: None,
+    })
+}
+
+fn follow_ps() -> Result<ExecStream, String> {
+    let mut seen = list_processes()?.into_iter().map(|(pid, _)| pid).collect::<HashSet<u32>>();
+    let (tx, rx) = mpsc::channel();
+    let (stop_tx, stop_rx) = mpsc::channel::<()>();
+    thread::spawn(move || loop {
+        if stop_rx.recv_timeout(PS_INTERVAL) != Err(mpsc::RecvTimeoutError::Timeout) {
+            return;
+        }
+        let Ok(processes) = list_processes() else { continue };
+        let mut current = HashSet::with_capacity(processes.len());
+        for (pid, comm) in processes {
+            if !seen.contains(&pid) && tx.send(comm).is_err() {
+                return;
+            }
+            current.insert(pid);
+        }
+        seen = current;
+    });
+    Ok(ExecStream {
+        source: format!("ps every {}ms", PS_INTERVAL.as_millis()),
+        receiver: rx,
+        _child: None,
+        _stop: Some(stop_tx),
+    })
+}
+
+/// (pid, command name) of every process, leaving out the `ps` sitrep runs.
+fn list_processes() -> Result<Vec<(u32, String)>, String> {
+    let output = cmd::output(Command::new("ps").args(["-A", "-o", "pid=,ppid=,comm="]), cmd::QUICK)
+        .map_err(|e| format!("ps: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("ps: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(parse_ps(&String::from_utf8_lossy(&output.stdout), std::process::id()))
+}
+
+/// Lines of `ps -o pid=,ppid=,comm=`, skipping children of `own_pid`. macOS
+/// prints the full executable path as the command; only its name is kept.
+pub fn parse_ps(text: &str, own_pid: u32) -> Vec<(u32, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let ppid: u32 = fields.next()?.parse().ok()?;
+            let comm = fields.collect::<Vec<_>>().join(" ");
+            if ppid == own_pid || comm.is_empty() {
+                return None;
+            }
+            let name = comm.rsplit('/').next().unwrap_or(&comm).to_string();
+            Some((pid, name))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_ps;
+
+    #[test]
+    fn parses_ps_and_skips_own_children() {
+        let text = "    1     0 systemd\n  812     1 /usr/sbin/cron\n 9001   500 ps\n 9002     1 Google Chrome Helper\n";
+        assert_eq!(
+            parse_ps(text, 500),
+            vec![(1, "systemd".into()), (812, "cron".into()), (9002, "Google Chrome Helper".into())]
+        );
+    }
+}
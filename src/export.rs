@@ -0,0 +1,268 @@
+//! Export of the currently shown table as aligned text or CSV, written to a
+//! file and copied to the clipboard.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::model::{DockerContainerInfo, MonitorData, RemoteConnectionInfo, SwarmServiceInfo};
+use crate::view::{display_width, pad};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Text,
+    Csv,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// A plain table of strings, independent of how the view colors it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Text => self.to_text(),
+            ExportFormat::Csv => self.to_csv(),
+        }
+    }
+
+    /// Columns padded to their widest cell, separated by two spaces.
+    pub fn to_text(&self) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| display_width(h)).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                if let Some(w) = widths.get_mut(i) {
+                    *w = (*w).max(display_width(cell));
+                }
+            }
+        }
+        let line = |cells: &[String]| -> String {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, w)| pad(cell, *w))
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        };
+        let mut out = line(&self.headers);
+        out.push('\n');
+        for row in &self.rows {
+            out.push_str(&line(row));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Rows as JSON objects keyed by lowercased header.
+    pub fn to_json(&self) -> serde_json::Value {
+        self.rows
+            .iter()
+            .map(|row| {
+                let fields = self
+                    .headers
+                    .iter()
+                    .zip(row)
+                    .map(|(h, v)| (h.to_lowercase(), serde_json::Value::String(v.clone())));
+                serde_json::Value::Object(fields.collect())
+            })
+            .collect()
+    }
+
+    /// RFC 4180 CSV: cells containing commas, quotes or newlines are quoted.
+    pub fn to_csv(&self) -> String {
+        let line = |cells: &[String]| -> String {
+            cells
+                .iter()
+                .map(|cell| {
+                    if cell.contains([',', '"', '\n']) {
+                        format!("\"{}\"", cell.replace('"', "\"\""))
+                    } else {
+                        cell.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut out = line(&self.headers);
+        out.push('\n');
+        for row in &self.rows {
+            out.push_str(&line(row));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// The System tab's process list as currently sorted.
+pub fn processes_table(data: &MonitorData) -> Table {
+    let mut table = Table::new(&["PID", "USER", "CPU%", "MEM", "NET RX", "NET TX", "COMMAND"]);
+    for g in &data.historical_top {
+        table.rows.push(vec![
+            g.pid.to_string(),
+            g.user.clone(),
+            format!("{:.1}", g.cpu),
+            g.mem.to_string(),
+            g.net_rx_bytes.to_string(),
+            g.net_tx_bytes.to_string(),
+            g.name.clone(),
+        ]);
+    }
+    table
+}
+
+/// The System tab's remote connection table.
+pub fn connections_table(remotes: &[RemoteConnectionInfo]) -> Table {
+    let mut table = Table::new(&["REMOTE", "CONNECTIONS", "PORTS"]);
+    for remote in remotes {
+        let ports: Vec<String> = remote.ports.iter().map(|p| p.to_string()).collect();
+        table.rows.push(vec![
+            remote.address.to_string(),
+            remote.connections.to_string(),
+            ports.join(" "),
+        ]);
+    }
+    table
+}
+
+/// Containers in display order (grouping, sort and label filter applied).
+pub fn containers_table<'a>(containers: impl IntoIterator<Item = &'a DockerContainerInfo>) -> Table {
+    let mut table = Table::new(&[
+        "CONTAINER ID", "NAME", "IMAGE", "STATE", "STATUS", "UPTIME", "CPU%", "MEM", "MEM LIMIT",
+        "NET RX", "NET TX", "PORTS", "IP",
+    ]);
+    for c in containers {
+        table.rows.push(vec![
+            c.id.clone(),
+            c.name.clone(),
+            c.image.clone(),
+            c.state.clone(),
+            c.status.clone(),
+            c.uptime.clone(),
+            format!("{:.1}", c.cpu_percent),
+            c.mem_usage.to_string(),
+            c.mem_limit.to_string(),
+            c.net_rx.to_string(),
+            c.net_tx.to_string(),
+            c.ports.clone(),
+            c.ip_address.clone(),
+        ]);
+    }
+    table
+}
+
+pub fn services_table(services: &[SwarmServiceInfo]) -> Table {
+    let mut table = Table::new(&["ID", "NAME", "STACK", "MODE", "REPLICAS", "IMAGE", "PORTS"]);
+    for s in services {
+        table.rows.push(vec![
+            s.id.clone(),
+            s.name.clone(),
+            s.stack.clone(),
+            s.mode.clone(),
+            s.replicas.clone(),
+            s.image.clone(),
+            s.ports.clone(),
+        ]);
+    }
+    table
+}
+
+/// Write `table` to `sitrep-<name>-<timestamp>.<ext>` in the current
+/// directory and copy it to the clipboard. Returns the file path.
+pub fn export(table: &Table, name: &str, format: ExportFormat) -> Result<PathBuf, String> {
+    let text = table.render(format);
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let path = PathBuf::from(format!("sitrep-{}-{}.{}", name, stamp, format.extension()));
+    std::fs::write(&path, &text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    // Best effort: terminals without OSC 52 support simply ignore it.
+    let _ = copy_to_clipboard(&mut io::stdout(), &text);
+    Ok(path)
+}
+
+/// Copy text to the system clipboard with the OSC 52 escape sequence, which
+/// most terminals honor, including over SSH.
+pub fn copy_to_clipboard(out: &mut impl Write, text: &str) -> io::Result<()> {
+    write!(out, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    out.flush()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Table {
+        let mut table = Table::new(&["NAME", "CPU%"]);
+        table.rows.push(vec!["web".into(), "12.5".into()]);
+        table.rows.push(vec!["worker, batch".into(), "3.0".into()]);
+        table
+    }
+
+    #[test]
+    fn text_export_aligns_columns() {
+        assert_eq!(
+            sample().to_text(),
+            "NAME           CPU%\nweb            12.5\nworker, batch  3.0\n"
+        );
+        let mut wide = Table::new(&["NAME", "CPU%"]);
+        wide.rows.push(vec!["日本".into(), "1.0".into()]);
+        wide.rows.push(vec!["web".into(), "2.0".into()]);
+        assert_eq!(wide.to_text(), "NAME  CPU%\n日本  1.0\nweb   2.0\n");
+    }
+
+    #[test]
+    fn csv_export_quotes_when_needed() {
+        assert_eq!(sample().to_csv(), "NAME,CPU%\nweb,12.5\n\"worker, batch\",3.0\n");
+    }
+
+    #[test]
+    fn json_export_keys_rows_by_header() {
+        assert_eq!(
+            sample().to_json(),
+            serde_json::json!([
+                {"name": "web", "cpu%": "12.5"},
+                {"name": "worker, batch", "cpu%": "3.0"},
+            ])
+        );
+    }
+
+    #[test]
+    fn base64_pads() {
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+    }
+}
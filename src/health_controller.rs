@@ -0,0 +1,239 @@
+//! HTTP health checks for configured containers and services.
+//!
+//! Every configured URL is probed once per tick on a background thread; results
+//! feed the HEALTH column of the Containers tab, the Swarm service rows, and the
+//! warning lists of both views.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::HealthConfig;
+use crate::probe;
+
+/// What a health target refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HealthTargetKind {
+    Container,
+    Service,
+}
+
+/// Outcome of the most recent probe of one target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthResult {
+    pub url: String,
+    pub outcome: Result<(u16, Duration), String>, // (status code, latency) or error
+    pub checked_at: Instant,
+}
+
+impl HealthResult {
+    /// 2xx and 3xx responses count as healthy.
+    pub fn is_healthy(&self) -> bool {
+        matches!(self.outcome, Ok((code, _)) if (200..400).contains(&code))
+    }
+
+    /// Short cell text, e.g. "200 12ms" or "ERR refused".
+    pub fn summary(&self) -> String {
+        match &self.outcome {
+            Ok((code, latency)) => format!("{} {}", code, probe::format_latency(*latency)),
+            Err(e) => format!("ERR {}", e),
+        }
+    }
+}
+
+type HealthKey = (HealthTargetKind, String);
+
+pub struct HealthMonitor {
+    containers: BTreeMap<String, String>,
+    services: BTreeMap<String, String>,
+    timeout: Duration,
+    results: HashMap<HealthKey, HealthResult>,
+    receiver: Option<mpsc::Receiver<Vec<(HealthKey, HealthResult)>>>,
+}
+
+impl HealthMonitor {
+    pub fn new(config: &HealthConfig) -> Self {
+        Self {
+            containers: config.containers.clone(),
+            services: config.services.clone(),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+            results: HashMap::new(),
+            receiver: None,
+        }
+    }
+
+    /// Swap in edited targets and timeout, dropping results for removed targets.
+    pub fn reconfigure(&mut self, config: &HealthConfig) {
+        self.containers = config.containers.clone();
+        self.services = config.services.clone();
+        self.timeout = Duration::from_secs(config.timeout_secs.max(1));
+        let (containers, services) = (&self.containers, &self.services);
+        self.results.retain(|(kind, name), _| match kind {
+            HealthTargetKind::Container => containers.contains_key(name),
+            HealthTargetKind::Service => services.contains_key(name),
+        });
+    }
+
+    /// Whether probes spawned by `update` have not reported yet.
+    pub fn in_flight(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    pub fn has_targets(&self) -> bool {
+        !self.containers.is_empty() || !self.services.is_empty()
+    }
+
+    /// Spawn probes for every configured target. Called on the tick interval.
+    pub fn update(&mut self) {
+        if !self.has_targets() || self.receiver.is_some() {
+            return;
+        }
+
+        let targets: Vec<(HealthKey, String)> = self
+            .containers
+            .iter()
+            .map(|(name, url)| ((HealthTargetKind::Container, name.clone()), url.clone()))
+            .chain(
+                self.services
+                    .iter()
+                    .map(|(name, url)| ((HealthTargetKind::Service, name.clone()), url.clone())),
+            )
+            .collect();
+        let timeout = self.timeout;
+
+        let (tx, rx) = mpsc::channel();
+        self.receiver = Some(rx);
+        thread::spawn(move || {
+            // One thread per target so a hanging endpoint only costs its own timeout.
+            let handles: Vec<_> = targets
+                .into_iter()
+                .map(|(key, url)| {
+                    thread::spawn(move || {
+                        let outcome = probe::probe_http(&url, timeout);
+                        (key, HealthResult { url, outcome, checked_at: Instant::now() })
+                    })
+                })
+                .collect();
+            let results = handles.into_iter().filter_map(|h| h.join().ok()).collect();
+            let _ = tx.send(results);
+        });
+    }
+
+    /// Poll for probe completion. Returns true if results changed.
+    pub fn poll_update(&mut self) -> bool {
+        let Some(ref rx) = self.receiver else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(results) => {
+                for (key, result) in results {
+                    // Log transitions only, not every failing tick
+                    let was_healthy = self.results.get(&key).is_none_or(|r| r.is_healthy());
+                    if was_healthy && !result.is_healthy() {
+                        tracing::warn!("Health check failed for {}: {}", result.url, result.summary());
+                    }
+                    self.results.insert(key, result);
+                }
+                self.receiver = None;
+                true
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.receiver = None;
+                false
+            }
+        }
+    }
+
+    pub fn get(&self, kind: HealthTargetKind, name: &str) -> Option<&HealthResult> {
+        self.results.get(&(kind, name.to_string()))
+    }
+
+    /// Failing targets of the given kind, sorted by name.
+    pub fn failing(&self, kind: HealthTargetKind) -> Vec<(&str, &HealthResult)> {
+        let mut failing: Vec<(&str, &HealthResult)> = self
+            .results
+            .iter()
+            .filter(|((k, _), r)| *k == kind && !r.is_healthy())
+            .map(|((_, name), r)| (name.as_str(), r))
+            .collect();
+        failing.sort_by(|a, b| a.0.cmp(b.0));
+        failing
+    }
+
+    /// Warning lines for failing targets of the given kind, sorted by name.
+    pub fn warnings(&self, kind: HealthTargetKind) -> Vec<String> {
+        self.failing(kind)
+            .into_iter()
+            .map(|(name, r)| health_warning(name, r))
+            .collect()
+    }
+}
+
+pub fn health_warning(name: &str, result: &HealthResult) -> String {
+    format!("HEALTH FAIL: {} ({}) {}", name, result.url, result.summary())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(outcome: Result<(u16, Duration), String>) -> HealthResult {
+        HealthResult {
+            url: "http://localhost/health".into(),
+            outcome,
+            checked_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn health_result_classifies_status_codes() {
+        assert!(result(Ok((200, Duration::from_millis(3)))).is_healthy());
+        assert!(result(Ok((301, Duration::from_millis(3)))).is_healthy());
+        assert!(!result(Ok((503, Duration::from_millis(3)))).is_healthy());
+        assert!(!result(Err("refused".into())).is_healthy());
+        assert_eq!(result(Err("refused".into())).summary(), "ERR refused");
+    }
+
+    #[test]
+    fn warnings_only_list_failing_targets_of_kind() {
+        let mut monitor = HealthMonitor::new(&HealthConfig::default());
+        monitor.results.insert(
+            (HealthTargetKind::Service, "api".into()),
+            result(Ok((500, Duration::from_millis(1)))),
+        );
+        monitor.results.insert(
+            (HealthTargetKind::Service, "web".into()),
+            result(Ok((200, Duration::from_millis(1)))),
+        );
+        monitor.results.insert(
+            (HealthTargetKind::Container, "db".into()),
+            result(Err("refused".into())),
+        );
+        let warnings = monitor.warnings(HealthTargetKind::Service);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("HEALTH FAIL: api"));
+    }
+
+    #[test]
+    fn reconfigure_drops_results_of_removed_targets() {
+        let mut config = HealthConfig::default();
+        config.services.insert("api".into(), "http://10.0.0.5/health".into());
+        config.services.insert("web".into(), "http://10.0.0.6/health".into());
+        let mut monitor = HealthMonitor::new(&config);
+        for name in ["api", "web"] {
+            monitor.results.insert(
+                (HealthTargetKind::Service, name.into()),
+                result(Ok((200, Duration::from_millis(1)))),
+            );
+        }
+
+        config.services.remove("web");
+        config.timeout_secs = 5;
+        monitor.reconfigure(&config);
+        assert!(monitor.get(HealthTargetKind::Service, "api").is_some());
+        assert!(monitor.get(HealthTargetKind::Service, "web").is_none());
+        assert_eq!(monitor.timeout, Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,383 @@
+//! Optional SQLite store of per-tick summary metrics and per-container stats,
+//! kept for a retention window so the last day can be graphed in the TUI and
+//! queried after an incident (`sqlite3 ~/.sitrep/history.db`).
+
+use std::path::PathBuf;
+
+use rusqlite::{params, Connection};
+
+use crate::config::HistoryConfig;
+use crate::model::{
+    DockerContainerInfo, GraphMetric, HistorySeries, MetricUnit, MonitorData, SwarmServiceInfo,
+};
+
+/// Span shown by the History panel.
+pub const GRAPH_WINDOW_SECS: i64 = 24 * 3600;
+/// Old rows are pruned once every this many recorded samples.
+const PRUNE_EVERY: u32 = 100;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS system_samples (
+    ts            INTEGER NOT NULL,
+    load1         REAL    NOT NULL,
+    cores         REAL    NOT NULL,
+    mem_used      INTEGER NOT NULL,
+    mem_total     INTEGER NOT NULL,
+    swap_used     INTEGER NOT NULL,
+    disk_busy_pct REAL    NOT NULL,
+    net_rx        INTEGER NOT NULL,
+    net_tx        INTEGER NOT NULL,
+    established   INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS system_samples_ts ON system_samples (ts);
+CREATE TABLE IF NOT EXISTS container_samples (
+    ts          INTEGER NOT NULL,
+    id          TEXT    NOT NULL,
+    name        TEXT    NOT NULL,
+    cpu_percent REAL    NOT NULL,
+    mem_usage   INTEGER NOT NULL,
+    net_rx      INTEGER NOT NULL,
+    net_tx      INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS container_samples_ts ON container_samples (ts);
+CREATE INDEX IF NOT EXISTS container_samples_name ON container_samples (name, ts);
+CREATE TABLE IF NOT EXISTS service_samples (
+    ts       INTEGER NOT NULL,
+    name     TEXT    NOT NULL,
+    running  INTEGER NOT NULL,
+    desired  INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS service_samples_ts ON service_samples (ts);
+CREATE INDEX IF NOT EXISTS service_samples_name ON service_samples (name, ts);
+";
+
+pub struct HistoryStore {
+    conn: Connection,
+    path: Option<PathBuf>,
+    retention_secs: i64,
+    samples_since_prune: u32,
+}
+
+/// Default database location: `~/.sitrep/history.db`, next to the log file.
+pub fn default_path() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    home.join(".sitrep").join("history.db")
+}
+
+impl HistoryStore {
+    pub fn open(config: &HistoryConfig) -> Result<Self, String> {
+        let path = config.path.clone().unwrap_or_else(default_path);
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let conn = Connection::open(&path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        // WAL lets ad-hoc sqlite3 queries run while sitrep keeps writing.
+        let _ = conn.pragma_update(None, "journal_mode", "WAL");
+        let mut store = Self::with_connection(conn, config.retention_hours)?;
+        store.path = Some(path);
+        Ok(store)
+    }
+
+    fn with_connection(conn: Connection, retention_hours: u64) -> Result<Self, String> {
+        conn.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+        Ok(Self {
+            conn,
+            path: None,
+            retention_secs: retention_hours as i64 * 3600,
+            samples_since_prune: 0,
+        })
+    }
+
+    pub fn path(&self) -> Option<&std::path::Path> {
+        self.path.as_deref()
+    }
+
+    pub fn record_system(&mut self, ts: i64, data: &MonitorData) -> Result<(), String> {
+        let (net_rx, net_tx) = data
+            .network
+            .interfaces
+            .iter()
+            .filter(|i| i.name != "lo" && i.name != "lo0")
+            .fold((0u64, 0u64), |(rx, tx), i| (rx + i.rx_rate, tx + i.tx_rate));
+        self.conn
+            .execute(
+                "INSERT INTO system_samples VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    ts,
+                    data.load_avg.0,
+                    data.core_count,
+                    data.memory.used as i64,
+                    data.memory.total as i64,
+                    data.memory.swap_used as i64,
+                    data.disk_busy_pct,
+                    net_rx as i64,
+                    net_tx as i64,
+                    data.socket_overview.established,
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        self.maybe_prune(ts)
+    }
+
+    pub fn record_containers(&mut self, ts: i64, containers: &[DockerContainerInfo]) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        {
+            let mut insert = tx
+                .prepare_cached("INSERT INTO container_samples VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)")
+                .map_err(|e| e.to_string())?;
+            for c in containers.iter().filter(|c| c.state == "running") {
+                insert
+                    .execute(params![
+                        ts,
+                        c.id,
+                        c.name,
+                        c.cpu_percent,
+                        c.mem_usage as i64,
+                        c.net_rx as i64,
+                        c.net_tx as i64,
+                    ])
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        self.maybe_prune(ts)
+    }
+
+    /// Replica counts of replicated services ("2/3"); global services are skipped.
+    pub fn record_services(&mut self, ts: i64, services: &[SwarmServiceInfo]) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        {
+            let mut insert = tx
+                .prepare_cached("INSERT INTO service_samples VALUES (?1, ?2, ?3, ?4)")
+                .map_err(|e| e.to_string())?;
+            for s in services {
+                let Some((running, desired)) = s.replicas.split_once('/') else {
+                    continue;
+                };
+                let (Ok(running), Ok(desired)) = (running.trim().parse::<i64>(), desired.trim().parse::<i64>()) else {
+                    continue;
+                };
+                insert
+                    .execute(params![ts, s.name, running, desired])
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        self.maybe_prune(ts)
+    }
+
+    fn maybe_prune(&mut self, now: i64) -> Result<(), String> {
+        self.samples_since_prune += 1;
+        if self.samples_since_prune < PRUNE_EVERY {
+            return Ok(());
+        }
+        self.samples_since_prune = 0;
+        let cutoff = now - self.retention_secs;
+        for table in ["system_samples", "container_samples", "service_samples"] {
+            self.conn
+                .execute(&format!("DELETE FROM {} WHERE ts < ?1", table), [cutoff])
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Host metrics between `since` and `until`, each reduced to `buckets`
+    /// evenly spaced points. A bucket keeps the peak of its samples so short
+    /// spikes survive the downsampling; buckets without samples are 0.
+    pub fn system_history(&self, since: i64, until: i64, buckets: usize) -> Result<Vec<HistorySeries>, String> {
+        let mut series = [
+            ("Load/core", MetricUnit::Percent),
+            ("Memory", MetricUnit::Percent),
+            ("\u{2193} Net RX", MetricUnit::BytesPerSec),
+            ("\u{2191} Net TX", MetricUnit::BytesPerSec),
+        ]
+        .map(|(label, unit)| HistorySeries {
+            label,
+            unit,
+            values: vec![0; buckets],
+        });
+        if buckets == 0 || until <= since {
+            return Ok(series.to_vec());
+        }
+
+        let mut query = self
+            .conn
+            .prepare_cached(
+                "SELECT ts, load1, cores, mem_used, mem_total, net_rx, net_tx
+                 FROM system_samples WHERE ts >= ?1 AND ts <= ?2 ORDER BY ts",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = query
+            .query_map([since, until], |row| {
+                let cores: f64 = row.get(2)?;
+                let mem_total: i64 = row.get(4)?;
+                let load_pct = row.get::<_, f64>(1)? / cores.max(1.0) * 100.0;
+                let mem_pct = row.get::<_, i64>(3)? as f64 / mem_total.max(1) as f64 * 100.0;
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    [
+                        load_pct.round() as u64,
+                        mem_pct.round() as u64,
+                        row.get::<_, i64>(5)?.max(0) as u64,
+                        row.get::<_, i64>(6)?.max(0) as u64,
+                    ],
+                ))
+            })
+            .map_err(|e| e.to_string())?;
+
+        for row in rows {
+            let (ts, values) = row.map_err(|e| e.to_string())?;
+            let bucket = bucket_index(ts, since, until, buckets);
+            for (s, v) in series.iter_mut().zip(values) {
+                s.values[bucket] = s.values[bucket].max(v);
+            }
+        }
+        Ok(series.to_vec())
+    }
+
+    /// One metric between `since` and `until` in `buckets` peak-preserving
+    /// buckets, None where nothing was recorded.
+    pub fn metric_history(
+        &self,
+        metric: &GraphMetric,
+        since: i64,
+        until: i64,
+        buckets: usize,
+    ) -> Result<Vec<Option<f64>>, String> {
+        let mut values: Vec<Option<f64>> = vec![None; buckets];
+        if buckets == 0 || until <= since {
+            return Ok(values);
+        }
+        let (sql, name) = match metric {
+            GraphMetric::Load => ("SELECT ts, load1 FROM system_samples WHERE ts >= ?1 AND ts <= ?2", None),
+            GraphMetric::Memory => (
+                "SELECT ts, mem_used * 100.0 / MAX(mem_total, 1) FROM system_samples WHERE ts >= ?1 AND ts <= ?2",
+                None,
+            ),
+            GraphMetric::ContainerCpu(name) => (
+                "SELECT ts, cpu_percent FROM container_samples WHERE ts >= ?1 AND ts <= ?2 AND name = ?3",
+                Some(name),
+            ),
+            GraphMetric::ServiceReplicas(name) => (
+                "SELECT ts, running FROM service_samples WHERE ts >= ?1 AND ts <= ?2 AND name = ?3",
+                Some(name),
+            ),
+        };
+        let mut query = self.conn.prepare_cached(sql).map_err(|e| e.to_string())?;
+        let map = |row: &rusqlite::Row| Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?));
+        let rows: Vec<(i64, f64)> = match name {
+            Some(name) => query.query_map(params![since, until, name], map),
+            None => query.query_map(params![since, until], map),
+        }
+        .and_then(|rows| rows.collect())
+        .map_err(|e| e.to_string())?;
+
+        for (ts, v) in rows {
+            let slot = &mut values[bucket_index(ts, since, until, buckets)];
+            *slot = Some(slot.map_or(v, |cur| cur.max(v)));
+        }
+        Ok(values)
+    }
+
+    /// Every container and service with recorded samples, for the metric picker.
+    pub fn graph_metrics(&self) -> Result<Vec<GraphMetric>, String> {
+        let mut metrics = vec![GraphMetric::Load, GraphMetric::Memory];
+        for (table, make) in [
+            ("container_samples", GraphMetric::ContainerCpu as fn(String) -> GraphMetric),
+            ("service_samples", GraphMetric::ServiceReplicas),
+        ] {
+            let mut query = self
+                .conn
+                .prepare_cached(&format!("SELECT DISTINCT name FROM {} ORDER BY name", table))
+                .map_err(|e| e.to_string())?;
+            let names = query
+                .query_map([], |row| row.get::<_, String>(0))
+                .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+                .map_err(|e| e.to_string())?;
+            metrics.extend(names.into_iter().map(make));
+        }
+        Ok(metrics)
+    }
+}
+
+fn bucket_index(ts: i64, since: i64, until: i64, buckets: usize) -> usize {
+    let offset = (ts - since) as f64 / (until - since) as f64;
+    ((offset * buckets as f64) as usize).min(buckets - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{MemoryInfo, NetworkInfo, NetworkInterfaceInfo};
+
+    fn sample(load: f64, rx: u64) -> MonitorData {
+        MonitorData {
+            core_count: 4.0,
+            load_avg: (load, 0.0, 0.0),
+            memory: MemoryInfo {
+                total: 1000,
+                used: 250,
+                ..Default::default()
+            },
+            network: NetworkInfo {
+                interfaces: vec![
+                    NetworkInterfaceInfo { name: "eth0".into(), rx_rate: rx, tx_rate: 1 },
+                    NetworkInterfaceInfo { name: "lo".into(), rx_rate: 999, tx_rate: 999 },
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn history_buckets_keep_peaks() {
+        let mut store = HistoryStore::with_connection(Connection::open_in_memory().unwrap(), 24).unwrap();
+        store.record_system(100, &sample(1.0, 10)).unwrap();
+        store.record_system(101, &sample(2.0, 30)).unwrap();
+        store.record_system(190, &sample(4.0, 20)).unwrap();
+
+        let series = store.system_history(100, 200, 4).unwrap();
+        assert_eq!(series[0].values, vec![50, 0, 0, 100]);
+        assert_eq!(series[1].values, vec![25, 0, 0, 25]);
+        // Loopback traffic is excluded
+        assert_eq!(series[2].values, vec![30, 0, 0, 20]);
+    }
+
+    #[test]
+    fn metric_history_marks_gaps_and_filters_by_name() {
+        let mut store = HistoryStore::with_connection(Connection::open_in_memory().unwrap(), 24).unwrap();
+        let service = |replicas: &str| SwarmServiceInfo {
+            name: "api".into(),
+            replicas: replicas.into(),
+            ..Default::default()
+        };
+        store.record_services(0, &[service("3/3")]).unwrap();
+        store.record_services(90, &[service("1/3")]).unwrap();
+        store.record_services(95, &[service("2/3")]).unwrap();
+
+        let metric = GraphMetric::ServiceReplicas("api".into());
+        let values = store.metric_history(&metric, 0, 100, 2).unwrap();
+        assert_eq!(values, vec![Some(3.0), Some(2.0)]);
+        let other = GraphMetric::ServiceReplicas("web".into());
+        assert_eq!(store.metric_history(&other, 0, 100, 2).unwrap(), vec![None, None]);
+
+        let metrics = store.graph_metrics().unwrap();
+        assert_eq!(metrics.last(), Some(&metric));
+    }
+
+    #[test]
+    fn prunes_past_retention() {
+        let mut store = HistoryStore::with_connection(Connection::open_in_memory().unwrap(), 1).unwrap();
+        for i in 0..PRUNE_EVERY as i64 {
+            store.record_system(i * 60, &sample(1.0, 0)).unwrap();
+        }
+        let count: i64 = store
+            .conn
+            .query_row("SELECT COUNT(*) FROM system_samples", [], |r| r.get(0))
+            .unwrap();
+        // The last sample is at 99m, so everything before 39m is gone
+        assert_eq!(count, 61);
+    }
+}
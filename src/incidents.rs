@@ -0,0 +1,215 @@
+//! Incidents opened from the TUI, so escalating what sitrep shows doesn't
+//! mean switching to the pager's web UI. `^` sends the selected Swarm warning,
+//! or a triage report of the current alerts, to PagerDuty (Events API v2) or
+//! Opsgenie (Alert API) with the snapshot attached. Both are HTTPS only, so
+//! the request goes through `curl`, with the key and body on its stdin
+//! rather than in the process list.
+
+use std::process::Command;
+use std::sync::mpsc;
+
+use crate::alerts::Severity;
+use crate::cmd;
+use crate::config::{IncidentProvider, IncidentsConfig};
+
+const PAGERDUTY_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+const OPSGENIE_URL: &str = "https://api.opsgenie.com/v2/alerts";
+/// Opsgenie cuts the message at 130 characters and the description at 15000.
+const OPSGENIE_MESSAGE_MAX: usize = 130;
+const OPSGENIE_DESCRIPTION_MAX: usize = 15000;
+/// PagerDuty refuses events over 512 KB; a larger snapshot goes as truncated text.
+const PAGERDUTY_DETAILS_MAX: usize = 256 * 1024;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Incident {
+    /// One line: the warning, or the headline of the triage report.
+    pub summary: String,
+    pub severity: Severity,
+    /// The same condition escalated twice updates the open incident.
+    pub dedup_key: String,
+    /// The host it is about.
+    pub source: String,
+    /// What `sitrep snapshot` prints, taken when the incident was confirmed.
+    pub details: serde_json::Value,
+}
+
+impl IncidentProvider {
+    pub fn label(self) -> &'static str {
+        match self {
+            IncidentProvider::PagerDuty => "PagerDuty",
+            IncidentProvider::Opsgenie => "Opsgenie",
+        }
+    }
+}
+
+/// Opens incidents with the configured provider, one curl at a time on a
+/// background thread.
+pub struct IncidentSender {
+    config: IncidentsConfig,
+    tx: mpsc::Sender<Result<String, String>>,
+    rx: mpsc::Receiver<Result<String, String>>,
+}
+
+impl IncidentSender {
+    pub fn new(config: &IncidentsConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self { config: config.clone(), tx, rx }
+    }
+
+    pub fn reconfigure(&mut self, config: &IncidentsConfig) {
+        self.config = config.clone();
+    }
+
+    /// The provider, when both it and its key are set.
+    pub fn provider(&self) -> Option<IncidentProvider> {
+        self.config.provider.filter(|_| self.config.key.is_some())
+    }
+
+    /// Send `incident`; the outcome arrives through `poll`.
+    pub fn create(&self, incident: Incident) {
+        let (Some(provider), Some(key)) = (self.provider(), self.config.key.clone()) else { return };
+        let url = self.config.url.clone().unwrap_or_else(|| default_url(provider).to_string());
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let result = send(provider, &url, &key, &incident);
+            match &result {
+                Ok(message) => tracing::info!("{}", message),
+                Err(e) => tracing::warn!("{} incident failed: {}", provider.label(), e),
+            }
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Outcomes of the requests finished since the last call: the status line
+    /// to show, or why it failed.
+    pub fn poll(&self) -> Vec<Result<String, String>> {
+        self.rx.try_iter().collect()
+    }
+}
+
+fn default_url(provider: IncidentProvider) -> &'static str {
+    match provider {
+        IncidentProvider::PagerDuty => PAGERDUTY_URL,
+        IncidentProvider::Opsgenie => OPSGENIE_URL,
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max.saturating_sub(1)) {
+        Some((end, _)) if text.chars().count() > max => format!("{}…", &text[..end]),
+        _ => text.to_string(),
+    }
+}
+
+/// The event or alert to post, and the extra headers it needs.
+pub fn request_body(provider: IncidentProvider, key: &str, incident: &Incident) -> (serde_json::Value, Vec<String>) {
+    match provider {
+        IncidentProvider::PagerDuty => {
+            let details = match serde_json::to_string_pretty(&incident.details) {
+                Ok(text) if text.len() > PAGERDUTY_DETAILS_MAX => serde_json::Value::String(truncate(&text, PAGERDUTY_DETAILS_MAX)),
+                _ => incident.details.clone(),
+            };
+            let body = serde_json::json!({
+                "routing_key": key,
+                "event_action": "trigger",
+                "dedup_key": incident.dedup_key,
+                "client": "sitrep",
+                "payload": {
+                    "summary": truncate(&incident.summary, 1024),
+                    "source": incident.source,
+                    "severity": incident.severity.label(),
+                    "custom_details": details,
+                },
+            });
+            (body, Vec::new())
+        }
+        IncidentProvider::Opsgenie => {
+            let snapshot = serde_json::to_string_pretty(&incident.details).unwrap_or_default();
+            let body = serde_json::json!({
+                "message": truncate(&incident.summary, OPSGENIE_MESSAGE_MAX),
+                "alias": incident.dedup_key,
+                "description": truncate(&format!("{}\n\n{}", incident.summary, snapshot), OPSGENIE_DESCRIPTION_MAX),
+                "source": "sitrep",
+                "entity": incident.source,
+                "priority": match incident.severity {
+                    Severity::Critical => "P1",
+                    Severity::Warning => "P3",
+                },
+            });
+            (body, vec![format!("Authorization: GenieKey {}", key)])
+        }
+    }
+}
+
+/// A curl config file (`--config -`) posting `body` to `url`.
+pub fn curl_config(url: &str, headers: &[String], body: &serde_json::Value) -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut config = format!("url = {}\nrequest = \"POST\"\nheader = \"Content-Type: application/json\"\n", quote(url));
+    for header in headers {
+        config.push_str(&format!("header = {}\n", quote(header)));
+    }
+    config.push_str(&format!("data-binary = {}\n", quote(&body.to_string())));
+    config
+}
+
+fn send(provider: IncidentProvider, url: &str, key: &str, incident: &Incident) -> Result<String, String> {
+    let (body, headers) = request_body(provider, key, incident);
+    let config = curl_config(url, &headers, &body);
+    let output = cmd::output_with_input(
+        Command::new("curl").args(["--silent", "--show-error", "--max-time", "10", "--write-out", "\n%{http_code}", "--config", "-"]),
+        config.into_bytes(),
+        cmd::QUICK,
+    )
+    .map_err(|e| format!("curl: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (response, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status: u16 = status.trim().parse().unwrap_or(0);
+    if !(200..300).contains(&status) {
+        return Err(format!("{} answered {}: {}", provider.label(), status, response.trim()));
+    }
+    let reply: serde_json::Value = serde_json::from_str(response).unwrap_or_default();
+    Ok(match provider {
+        IncidentProvider::PagerDuty => {
+            format!("PagerDuty incident triggered ({})", reply["dedup_key"].as_str().unwrap_or(&incident.dedup_key))
+        }
+        IncidentProvider::Opsgenie => match reply["requestId"].as_str() {
+            Some(id) => format!("Opsgenie alert requested ({})", id),
+            None => "Opsgenie alert requested".to_string(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_pagerduty_and_opsgenie_requests_with_the_snapshot() {
+        let incident = Incident {
+            summary: "web1: SERVICE DEGRADED: api has 1/3 replicas".to_string(),
+            severity: Severity::Critical,
+            dedup_key: "sitrep:web1:SERVICE DEGRADED: api has / replicas".to_string(),
+            source: "web1".to_string(),
+            details: serde_json::json!({ "alerts": [{ "message": "say \"hi\"" }] }),
+        };
+        let (body, headers) = request_body(IncidentProvider::PagerDuty, "R0UT1NG", &incident);
+        assert_eq!((body["routing_key"].as_str(), body["event_action"].as_str()), (Some("R0UT1NG"), Some("trigger")));
+        assert_eq!(body["payload"]["severity"], "critical");
+        assert_eq!(body["payload"]["custom_details"]["alerts"][0]["message"], "say \"hi\"");
+        assert!(headers.is_empty());
+
+        let (body, headers) = request_body(IncidentProvider::Opsgenie, "g3n1e", &incident);
+        assert_eq!((body["priority"].as_str(), body["entity"].as_str()), (Some("P1"), Some("web1")));
+        assert!(body["description"].as_str().unwrap().contains("\"alerts\""));
+        assert_eq!(headers, vec!["Authorization: GenieKey g3n1e".to_string()]);
+        assert_eq!(truncate(&"x".repeat(200), OPSGENIE_MESSAGE_MAX).chars().count(), OPSGENIE_MESSAGE_MAX);
+
+        // Quotes and backslashes survive curl's config quoting
+        let config = curl_config(OPSGENIE_URL, &headers, &serde_json::json!({ "m": "a\"b\\c" }));
+        assert!(config.contains(r#"data-binary = "{\"m\":\"a\\\"b\\\\c\"}""#));
+        assert!(config.contains("header = \"Authorization: GenieKey g3n1e\"\n"));
+    }
+}
@@ -0,0 +1,204 @@
+//! Namespaces, deployments, pods and pod logs of the current kubectl context,
+//! read through `kubectl ... -o json` the way the Swarm views use the docker CLI.
+
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::cmd;
+use crate::error::SitrepError;
+use crate::model::{KubeDeploymentInfo, KubePodInfo};
+use crate::swarm::{tail_command, LogStreamHandle};
+
+/// The kubectl context in use, None when kubectl is missing or has no
+/// cluster configured.
+pub fn current_context() -> Option<String> {
+    let output = cmd::output(Command::new("kubectl").args(["config", "current-context"]), cmd::QUICK).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let context = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!context.is_empty()).then_some(context)
+}
+
+/// Run `kubectl <args>` and return its stdout. `name` is the command as
+/// errors mention it, e.g. "kubectl get pods".
+fn kubectl(name: &str, args: &[&str]) -> Result<String, SitrepError> {
+    let output = cmd::output(Command::new("kubectl").args(args), cmd::QUICK)
+        .map_err(|e| SitrepError::from_io(&format!("Failed to run {}", name), &e))?;
+    if !output.status.success() {
+        return Err(SitrepError::from_stderr(&format!("{} failed", name), &String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The `items` of a `kubectl get -o json` list.
+fn items(name: &str, text: &str) -> Result<Vec<Value>, SitrepError> {
+    let list: Value = serde_json::from_str(text)
+        .map_err(|e| SitrepError::ParseError(format!("Unexpected {} output: {}", name, e)))?;
+    Ok(list["items"].as_array().cloned().unwrap_or_default())
+}
+
+fn text(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+fn count(value: &Value) -> u32 {
+    value.as_u64().unwrap_or(0) as u32
+}
+
+fn created(item: &Value) -> Option<i64> {
+    let stamp = item["metadata"]["creationTimestamp"].as_str()?;
+    chrono::DateTime::parse_from_rfc3339(stamp).ok().map(|t| t.timestamp())
+}
+
+/// Namespace names, sorted.
+pub fn list_namespaces() -> Result<Vec<String>, SitrepError> {
+    let name = "kubectl get namespaces";
+    let out = kubectl(name, &["get", "namespaces", "-o", "json"])?;
+    let mut namespaces: Vec<String> = items(name, &out)?.iter().map(|ns| text(&ns["metadata"]["name"])).collect();
+    namespaces.sort();
+    Ok(namespaces)
+}
+
+pub fn list_deployments() -> Result<Vec<KubeDeploymentInfo>, SitrepError> {
+    let name = "kubectl get deployments";
+    parse_deployments(name, &kubectl(name, &["get", "deployments", "--all-namespaces", "-o", "json"])?)
+}
+
+fn parse_deployments(name: &str, out: &str) -> Result<Vec<KubeDeploymentInfo>, SitrepError> {
+    let mut deployments: Vec<KubeDeploymentInfo> = items(name, out)?
+        .iter()
+        .map(|d| {
+            let images: Vec<String> = d["spec"]["template"]["spec"]["containers"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|c| text(&c["image"]))
+                .collect();
+            KubeDeploymentInfo {
+                namespace: text(&d["metadata"]["namespace"]),
+                name: text(&d["metadata"]["name"]),
+                // A deployment without `replicas` runs one
+                desired: d["spec"]["replicas"].as_u64().map_or(1, |n| n as u32),
+                ready: count(&d["status"]["readyReplicas"]),
+                up_to_date: count(&d["status"]["updatedReplicas"]),
+                available: count(&d["status"]["availableReplicas"]),
+                images: images.join(", "),
+                created: created(d),
+            }
+        })
+        .collect();
+    deployments.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+    Ok(deployments)
+}
+
+pub fn list_pods() -> Result<Vec<KubePodInfo>, SitrepError> {
+    let name = "kubectl get pods";
+    parse_pods(name, &kubectl(name, &["get", "pods", "--all-namespaces", "-o", "json"])?)
+}
+
+fn parse_pods(name: &str, out: &str) -> Result<Vec<KubePodInfo>, SitrepError> {
+    let mut pods: Vec<KubePodInfo> = items(name, out)?
+        .iter()
+        .map(|p| {
+            let statuses = array(&p["status"]["containerStatuses"]);
+            KubePodInfo {
+                namespace: text(&p["metadata"]["namespace"]),
+                name: text(&p["metadata"]["name"]),
+                deployment: owning_deployment(p),
+                status: pod_status(p),
+                ready_containers: statuses.iter().filter(|s| s["ready"].as_bool() == Some(true)).count() as u32,
+                containers: p["spec"]["containers"].as_array().into_iter().flatten().map(|c| text(&c["name"])).collect(),
+                restarts: statuses.iter().map(|s| count(&s["restartCount"])).sum(),
+                node: text(&p["spec"]["nodeName"]),
+                created: created(p),
+            }
+        })
+        .collect();
+    pods.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+    Ok(pods)
+}
+
+/// The deployment behind a pod: its owner is a ReplicaSet named
+/// "<deployment>-<template hash>".
+fn owning_deployment(pod: &Value) -> Option<String> {
+    let owners = pod["metadata"]["ownerReferences"].as_array()?;
+    let replica_set = owners.iter().find(|o| o["kind"] == "ReplicaSet")?;
+    let name = replica_set["name"].as_str()?;
+    name.rsplit_once('-').map(|(deployment, _)| deployment.to_string())
+}
+
+/// The STATUS column of `kubectl get pods`: "Terminating" while deleted, an
+/// init container's trouble as "Init:<reason>", else the reason a container
+/// waits or ended (e.g. "CrashLoopBackOff"), else the pod's reason or phase.
+fn pod_status(pod: &Value) -> String {
+    if !pod["metadata"]["deletionTimestamp"].is_null() {
+        return "Terminating".to_string();
+    }
+    let waiting = |s: &Value| s["state"]["waiting"]["reason"].as_str().map(String::from);
+    let ended = |s: &Value| s["state"]["terminated"]["reason"].as_str().map(String::from);
+    let init = array(&pod["status"]["initContainerStatuses"]);
+    if let Some(reason) = init.iter().find_map(|s| waiting(s).filter(|r| r != "PodInitializing")) {
+        return format!("Init:{}", reason);
+    }
+    let containers = array(&pod["status"]["containerStatuses"]);
+    containers
+        .iter()
+        .find_map(waiting)
+        .or_else(|| containers.iter().find_map(ended))
+        .or_else(|| pod["status"]["reason"].as_str().map(String::from))
+        .unwrap_or_else(|| text(&pod["status"]["phase"]))
+}
+
+fn array(value: &Value) -> Vec<Value> {
+    value.as_array().cloned().unwrap_or_default()
+}
+
+/// Follow a pod's logs, every container's with a `[pod/name/container]`
+/// prefix when it has several.
+pub fn tail_pod_logs(namespace: &str, pod: &str, several_containers: bool) -> LogStreamHandle {
+    let mut command = Command::new("kubectl");
+    command.args(["logs", "--follow", "--tail", "200", "--timestamps", "--all-containers", "--namespace", namespace, pod]);
+    if several_containers {
+        command.arg("--prefix");
+    }
+    tail_command(&mut command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_pods_and_deployments_like_kubectl_get() {
+        let deployments = r#"{"items": [{
+            "metadata": {"name": "api", "namespace": "shop", "creationTimestamp": "2026-10-14T08:00:00Z"},
+            "spec": {"replicas": 3, "template": {"spec": {"containers": [{"name": "api", "image": "shop/api:1.4"}]}}},
+            "status": {"readyReplicas": 2, "updatedReplicas": 3, "availableReplicas": 2}
+        }]}"#;
+        let d = &parse_deployments("test", deployments).unwrap()[0];
+        assert_eq!((d.ready_label(), d.images.as_str(), d.is_degraded()), ("2/3".to_string(), "shop/api:1.4", true));
+
+        let pods = r#"{"items": [{
+            "metadata": {"name": "api-7d4b9c-x2k", "namespace": "shop",
+                "ownerReferences": [{"kind": "ReplicaSet", "name": "api-7d4b9c"}]},
+            "spec": {"nodeName": "k8s-2", "containers": [{"name": "api"}, {"name": "proxy"}]},
+            "status": {"phase": "Running", "containerStatuses": [
+                {"name": "api", "ready": false, "restartCount": 4, "state": {"waiting": {"reason": "CrashLoopBackOff"}}},
+                {"name": "proxy", "ready": true, "restartCount": 0, "state": {"running": {}}}
+            ]}
+        }, {
+            "metadata": {"name": "migrate", "namespace": "shop"},
+            "spec": {"containers": [{"name": "migrate"}]},
+            "status": {"phase": "Pending", "initContainerStatuses": [
+                {"state": {"waiting": {"reason": "ImagePullBackOff"}}}
+            ]}
+        }]}"#;
+        let pods = parse_pods("test", pods).unwrap();
+        assert_eq!(pods[0].deployment.as_deref(), Some("api"));
+        assert_eq!((pods[0].status.as_str(), pods[0].ready_label(), pods[0].restarts), ("CrashLoopBackOff", "1/2".to_string(), 4));
+        assert!(!pods[0].is_healthy());
+        assert_eq!((pods[1].status.as_str(), pods[1].deployment.as_deref()), ("Init:ImagePullBackOff", None));
+    }
+}
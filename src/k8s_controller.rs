@@ -0,0 +1,179 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+use crate::error::SitrepError;
+use crate::k8s;
+use crate::model::{kube_rows, KubeDeploymentInfo, KubePodInfo, KubeRow, KubeUIState, LogViewState};
+use crate::swarm::LogStreamHandle;
+
+type KubeSnapshot = (Vec<String>, Vec<KubeDeploymentInfo>, Vec<KubePodInfo>);
+
+fn joined<T>(result: thread::Result<Result<T, SitrepError>>) -> Result<T, SitrepError> {
+    result.unwrap_or_else(|_| Err(SitrepError::Failed("kubectl query panicked".to_string())))
+}
+
+/// The Kubernetes tab's data: the namespaces, deployments and pods of the
+/// current kubectl context, refreshed in the background, and the pod whose
+/// logs are open.
+pub struct KubeMonitor {
+    /// kubectl's current context; the tab is only shown with one.
+    pub context: Option<String>,
+    pub namespaces: Vec<String>,
+    pub deployments: Vec<KubeDeploymentInfo>,
+    pub pods: Vec<KubePodInfo>,
+    pub ui_state: KubeUIState,
+    pub log_state: Option<LogViewState>,
+    log_handle: Option<LogStreamHandle>,
+    /// Why the last refresh failed; the lists are from the one before.
+    pub error: Option<String>,
+    pub last_updated: Option<Instant>,
+    pub update_failed: bool,
+    pending_refresh: Option<mpsc::Receiver<Result<KubeSnapshot, SitrepError>>>,
+}
+
+impl Default for KubeMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KubeMonitor {
+    /// Look for a kubectl context to show.
+    pub fn new() -> Self {
+        let context = k8s::current_context();
+        if let Some(ref context) = context {
+            tracing::info!("Kubernetes context: {}", context);
+        }
+        Self { context, ..Self::undetected() }
+    }
+
+    /// A monitor with no cluster, until detection in `new` finishes.
+    pub fn undetected() -> Self {
+        Self {
+            context: None,
+            namespaces: Vec::new(),
+            deployments: Vec::new(),
+            pods: Vec::new(),
+            ui_state: KubeUIState::default(),
+            log_state: None,
+            log_handle: None,
+            error: None,
+            last_updated: None,
+            update_failed: false,
+            pending_refresh: None,
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.context.is_some()
+    }
+
+    /// Start a background refresh, the three lists queried at once.
+    pub fn update(&mut self) {
+        if !self.is_available() || self.pending_refresh.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let (namespaces, deployments, pods) = thread::scope(|s| {
+                let namespaces = s.spawn(k8s::list_namespaces);
+                let deployments = s.spawn(k8s::list_deployments);
+                let pods = k8s::list_pods();
+                (namespaces.join(), deployments.join(), pods)
+            });
+            let snapshot = match (joined(namespaces), joined(deployments), pods) {
+                (Ok(namespaces), Ok(deployments), Ok(pods)) => Ok((namespaces, deployments, pods)),
+                (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => Err(e),
+            };
+            let _ = tx.send(snapshot);
+        });
+        self.pending_refresh = Some(rx);
+    }
+
+    /// Apply a finished refresh. Returns true if one was applied.
+    pub fn poll_update(&mut self) -> bool {
+        let Some(ref rx) = self.pending_refresh else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => Err(SitrepError::Failed("Kubernetes refresh stopped".to_string())),
+        };
+        self.pending_refresh = None;
+        self.last_updated = Some(Instant::now());
+        match result {
+            Ok((namespaces, deployments, pods)) => {
+                tracing::debug!("Kubernetes: {} deployments, {} pods", deployments.len(), pods.len());
+                self.namespaces = namespaces;
+                self.deployments = deployments;
+                self.pods = pods;
+                self.error = None;
+                self.update_failed = false;
+            }
+            Err(e) => {
+                tracing::warn!("Kubernetes refresh failed: {}", e);
+                self.error = Some(e.message().to_string());
+                self.update_failed = true;
+            }
+        }
+        let len = self.rows().len();
+        if self.ui_state.selected_index >= len {
+            self.ui_state.selected_index = len.saturating_sub(1);
+        }
+        true
+    }
+
+    pub fn rows(&self) -> Vec<KubeRow> {
+        kube_rows(&self.namespaces, &self.deployments, &self.pods, &self.ui_state.collapsed)
+    }
+
+    pub fn selected_row(&self) -> Option<KubeRow> {
+        self.rows().into_iter().nth(self.ui_state.selected_index)
+    }
+
+    /// Fold or unfold a namespace.
+    pub fn toggle_namespace(&mut self, namespace: &str) {
+        if !self.ui_state.collapsed.remove(namespace) {
+            self.ui_state.collapsed.insert(namespace.to_string());
+        }
+    }
+
+    /// Start following the logs of `self.pods[index]`.
+    pub fn start_log_stream(&mut self, index: usize) {
+        self.stop_log_stream();
+        let Some(pod) = self.pods.get(index) else { return };
+        self.log_handle = Some(k8s::tail_pod_logs(&pod.namespace, &pod.name, pod.containers.len() > 1));
+        let mut log_state = LogViewState::new(pod.namespace.clone(), pod.name.clone());
+        log_state.section = "Kubernetes";
+        self.log_state = Some(log_state);
+    }
+
+    /// Stop the log stream and kill kubectl.
+    pub fn stop_log_stream(&mut self) {
+        if let Some(ref handle) = self.log_handle {
+            handle.kill();
+        }
+        self.log_handle = None;
+        self.log_state = None;
+    }
+
+    /// Drain pending log lines. Returns true if any arrived.
+    pub fn poll_logs(&mut self) -> bool {
+        let Some(ref handle) = self.log_handle else { return false };
+        let Some(ref mut log_state) = self.log_state else { return false };
+        let before = (log_state.lines.len(), log_state.ended);
+        for _ in 0..200 {
+            match handle.receiver.try_recv() {
+                Ok(line) => log_state.push_line(line),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    log_state.end_stream();
+                    break;
+                }
+            }
+        }
+        (log_state.lines.len(), log_state.ended) != before
+    }
+}
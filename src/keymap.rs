@@ -0,0 +1,97 @@
+//! Key remapping from the `[keys]` config table. Each entry makes a key act
+//! as one of sitrep's own keys wherever that key works (`j = "Down"`), or
+//! turns a key off (`q = "none"`). The built-in key keeps working unless it
+//! is remapped itself. Search and text prompts take keys as typed.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crossterm::event::KeyCode;
+
+/// Bound keys and what they stand for; None turns the key off.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyMap {
+    keys: HashMap<KeyCode, Option<KeyCode>>,
+}
+
+impl KeyMap {
+    pub fn new(table: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut keys = HashMap::new();
+        for (from, to) in table {
+            let from_key = parse_key(from).map_err(|e| format!("keys: {}", e))?;
+            let to_key = match to.trim().to_ascii_lowercase().as_str() {
+                "none" | "" => None,
+                _ => Some(parse_key(to).map_err(|e| format!("keys.{}: {}", from, e))?),
+            };
+            if keys.insert(from_key, to_key).is_some() {
+                return Err(format!("keys: '{}' is remapped twice", from));
+            }
+        }
+        Ok(Self { keys })
+    }
+
+    /// The key `code` stands for, or None when it is turned off.
+    pub fn translate(&self, code: KeyCode) -> Option<KeyCode> {
+        match self.keys.get(&code) {
+            Some(to) => *to,
+            None => Some(code),
+        }
+    }
+}
+
+/// A key as written in the config: a single character (case matters), or a
+/// name such as `Up`, `Enter`, `Esc`, `Tab`, `Space`, `PageDown` or `F5`.
+pub fn parse_key(text: &str) -> Result<KeyCode, String> {
+    let mut chars = text.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+    let name = text.trim().to_ascii_lowercase();
+    let code = match name.as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "enter" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" | "shift+tab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "space" => KeyCode::Char(' '),
+        _ => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n @ 1..=12) => KeyCode::F(n),
+            _ => return Err(format!("unknown key '{}'", text)),
+        },
+    };
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaps_and_turns_off_keys() {
+        let table = BTreeMap::from([
+            ("j".to_string(), "Down".to_string()),
+            ("F5".to_string(), "R".to_string()),
+            ("q".to_string(), "none".to_string()),
+        ]);
+        let keys = KeyMap::new(&table).unwrap();
+        assert_eq!(keys.translate(KeyCode::Char('j')), Some(KeyCode::Down));
+        assert_eq!(keys.translate(KeyCode::F(5)), Some(KeyCode::Char('R')));
+        assert_eq!(keys.translate(KeyCode::Char('q')), None);
+        // Keys left alone, the remapped ones' targets included
+        assert_eq!(keys.translate(KeyCode::Down), Some(KeyCode::Down));
+        assert_eq!(keys.translate(KeyCode::Char('R')), Some(KeyCode::Char('R')));
+
+        assert!(KeyMap::new(&BTreeMap::from([("F13".to_string(), "q".to_string())])).is_err());
+        assert!(KeyMap::new(&BTreeMap::from([("x".to_string(), "Hyper".to_string())])).is_err());
+        assert_eq!(parse_key("Space"), Ok(KeyCode::Char(' ')));
+    }
+}
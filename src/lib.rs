@@ -2,14 +2,49 @@
 //!
 //! This library exposes the core modules for use by the binary and by tests.
 
+pub mod action_queue;
+pub mod alertmanager;
+pub mod alerts;
+pub mod authlog;
+pub mod checks;
 pub mod cli;
+pub mod cmd;
+pub mod config;
 pub mod model;
 pub mod view;
 pub mod layout;
 pub mod controller;
 pub mod collectors;
+pub mod commands;
+pub mod compose;
+pub mod daemon;
+pub mod demo;
 pub mod docker;
 pub mod docker_controller;
+pub mod error;
+pub mod execsnoop;
+pub mod export;
+pub mod health_controller;
+pub mod k8s;
+pub mod k8s_controller;
+pub mod history;
+pub mod incidents;
+pub mod keymap;
+pub mod macros;
+pub mod metrics;
+pub mod mutes;
+pub mod nerdctl;
+pub mod podman;
+pub mod notes;
+pub mod probe;
+pub mod recording;
+pub mod scan;
+pub mod schedule;
+pub mod profiler;
 pub mod swarm;
 pub mod swarm_controller;
+pub mod storage;
+pub mod syslog;
+pub mod tracer;
+pub mod updates;
 pub mod app;
@@ -0,0 +1,108 @@
+//! Named runbook steps from the `[macros]` config table, run from the command
+//! palette (`:`). Each step is one of sitrep's own keys, written as in
+//! `[keys]`, or one of:
+//!
+//! - `tab <system|containers|swarm|kubernetes|watchlist>` to switch tabs
+//! - `select <name>` to put the cursor on the container or service of that
+//!   name in the active tab
+//!
+//! `flush-cache = ["tab containers", "select redis", "r", "y", "l"]` restarts
+//! redis and opens its logs. Keys act as if pressed, so confirmations still
+//! need their `y` step, and read-only mode still refuses actions.
+
+use std::collections::BTreeMap;
+
+use crossterm::event::KeyCode;
+
+use crate::keymap::parse_key;
+use crate::model::AppView;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    Key(KeyCode),
+    Tab(AppView),
+    Select(String),
+}
+
+impl Step {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        match text.split_once(' ') {
+            Some(("tab", name)) => {
+                let view = match name.trim().to_ascii_lowercase().as_str() {
+                    "system" => AppView::System,
+                    "containers" => AppView::Containers,
+                    "swarm" => AppView::Swarm,
+                    "kubernetes" => AppView::Kubernetes,
+                    "watchlist" => AppView::Watchlist,
+                    _ => return Err(format!("unknown tab '{}'", name.trim())),
+                };
+                Ok(Step::Tab(view))
+            }
+            Some(("select", name)) if !name.trim().is_empty() => Ok(Step::Select(name.trim().to_string())),
+            _ => parse_key(text).map(Step::Key),
+        }
+    }
+}
+
+/// Macros by name, in name order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Macros {
+    macros: BTreeMap<String, Vec<Step>>,
+}
+
+impl Macros {
+    pub fn new(table: &BTreeMap<String, Vec<String>>) -> Result<Self, String> {
+        let mut macros = BTreeMap::new();
+        for (name, steps) in table {
+            if steps.is_empty() {
+                return Err(format!("macros.{} has no steps", name));
+            }
+            let steps = steps
+                .iter()
+                .map(|s| Step::parse(s).map_err(|e| format!("macros.{}: {}", name, e)))
+                .collect::<Result<Vec<_>, _>>()?;
+            macros.insert(name.clone(), steps);
+        }
+        Ok(Self { macros })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.macros.is_empty()
+    }
+
+    pub fn steps(&self, name: &str) -> Option<&[Step]> {
+        self.macros.get(name).map(Vec::as_slice)
+    }
+
+    /// Names containing `filter`, ignoring case.
+    pub fn matching(&self, filter: &str) -> Vec<&str> {
+        let filter = filter.to_lowercase();
+        self.macros.keys().filter(|name| name.to_lowercase().contains(&filter)).map(String::as_str).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_macro_steps() {
+        let table = BTreeMap::from([
+            ("flush-cache".to_string(), vec!["tab containers".to_string(), "select redis".into(), "r".into(), "y".into()]),
+            ("db-logs".to_string(), vec!["tab Swarm".to_string(), "select db".into(), "Enter".into()]),
+        ]);
+        let macros = Macros::new(&table).unwrap();
+        assert_eq!(
+            macros.steps("flush-cache").unwrap(),
+            [Step::Tab(AppView::Containers), Step::Select("redis".into()), Step::Key(KeyCode::Char('r')), Step::Key(KeyCode::Char('y'))]
+        );
+        assert_eq!(macros.steps("db-logs").unwrap()[2], Step::Key(KeyCode::Enter));
+        assert_eq!(macros.matching("LOG"), ["db-logs"]);
+        assert_eq!(macros.matching(""), ["db-logs", "flush-cache"]);
+
+        assert!(Macros::new(&BTreeMap::from([("x".to_string(), vec!["tab graphs".to_string()])])).is_err());
+        assert!(Macros::new(&BTreeMap::from([("x".to_string(), vec!["restart".to_string()])])).is_err());
+        assert!(Macros::new(&BTreeMap::from([("x".to_string(), Vec::new())])).is_err());
+    }
+}
@@ -4,23 +4,80 @@ use std::sync::atomic::AtomicBool;
 use clap::Parser;
 
 use sitrep::app;
-use sitrep::cli::Cli;
+use sitrep::cli::{Cli, Command};
+use sitrep::cmd;
+use sitrep::commands;
+use sitrep::config;
+use sitrep::daemon;
+use sitrep::docker;
 
 fn main() -> io::Result<()> {
     let cli = Cli::parse();
+    let docker_host = match (&cli.docker_socket, &cli.docker_host) {
+        (Some(socket), _) => Some(format!("unix://{}", socket.display())),
+        (None, Some(host)) => Some(host.clone()),
+        // A rootless or Docker Desktop socket when the default one is dead
+        (None, None) if std::env::var_os("DOCKER_HOST").is_none() => docker::detect_socket()
+            .filter(|path| path.as_path() != std::path::Path::new(docker::DEFAULT_SOCKET))
+            .map(|path| format!("unix://{}", path.display())),
+        (None, None) => None,
+    };
+    if let Some(ref host) = docker_host {
+        // SAFETY: no other threads exist yet.
+        unsafe { std::env::set_var("DOCKER_HOST", host) };
+    }
 
     // Set up logging — _guard must live for the entire program
     let _guard = setup_logging(&cli);
+    tracing::info!("Docker endpoint: {}", docker::endpoint());
 
-    tracing::info!(
-        "sitrep starting, refresh_rate={}s, no_docker={}",
-        cli.refresh_rate,
-        cli.no_docker
+    tracing::debug!(
+        "sitrep {} on {} {}, args: {:?}",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::args().skip(1).collect::<Vec<_>>()
     );
 
+    // Fail before entering the alternate screen so the error stays visible.
+    let loaded = match cli.config {
+        Some(ref path) => config::load_from(path),
+        None => config::load(),
+    };
+    let config = match loaded {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("{}", e);
+            eprintln!("sitrep: {}", e);
+            exit(_guard, 1);
+        }
+    };
+
+    tracing::info!("sitrep starting, refresh_rate={}s, no_docker={}", cli.interval(&config), cli.no_docker);
+
+    if let Some(Command::Check { ref name }) = cli.command {
+        let status = commands::check(&config, name);
+        exit(_guard, status);
+    }
+
+    // Only the TUI touches the terminal, so elsewhere there is nothing to
+    // restore. Children are only stopped when the main thread is going down;
+    // a panic elsewhere under the TUI is kept for the event loop to exit with.
+    let tui = cli.runs_tui();
     let default_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
-        app::restore_terminal();
+        tracing::error!("{}", info);
+        let on_main = std::thread::current().name() == Some("main");
+        if tui && !on_main {
+            app::worker_panicked(info.to_string());
+            return;
+        }
+        if tui {
+            app::restore_terminal();
+        }
+        if on_main {
+            cmd::kill_all();
+        }
         default_hook(info);
     }));
 
@@ -33,14 +90,44 @@ fn main() -> io::Result<()> {
         let quit_flag = Arc::clone(&should_quit);
         let _ = signal_hook::flag::register(signal_hook::consts::SIGINT, quit_flag);
     }
+    {
+        // A closed terminal or SSH session should still stop every child
+        let quit_flag = Arc::clone(&should_quit);
+        let _ = signal_hook::flag::register(signal_hook::consts::SIGHUP, quit_flag);
+    }
 
-    let result = app::run(should_quit, &cli);
+    let result = if cli.runs_agent() {
+        daemon::run(should_quit, &cli, config)
+    } else if cli.runs_snapshot() {
+        commands::snapshot(&cli, config)
+    } else if cli.runs_report() {
+        commands::report(&cli, config)
+    } else {
+        app::run(should_quit, &cli, config)
+    };
+    cmd::kill_all();
+    if let Err(e) = result {
+        // The TUI has restored the terminal by now, so this stays visible
+        tracing::error!("{}", e);
+        eprintln!("sitrep: {}", e);
+        exit(_guard, 1);
+    }
     tracing::info!("sitrep exiting");
-    result
+    Ok(())
+}
+
+/// Exit with `status` after flushing the log. `std::process::exit` runs no
+/// destructors, so every early exit goes through here.
+fn exit(log_guard: tracing_appender::non_blocking::WorkerGuard, status: i32) -> ! {
+    drop(log_guard);
+    std::process::exit(status)
 }
 
 fn setup_logging(cli: &Cli) -> tracing_appender::non_blocking::WorkerGuard {
     let log_path = cli.log_file.clone().unwrap_or_else(|| {
+        if cli.debug {
+            return debug_log_path();
+        }
         let mut p = dirs_or_home();
         p.push(".sitrep");
         p.push("sitrep.log");
@@ -57,13 +144,21 @@ fn setup_logging(cli: &Cli) -> tracing_appender::non_blocking::WorkerGuard {
     // Create log directory if it doesn't exist
     let _ = std::fs::create_dir_all(log_dir);
 
-    let file_appender = tracing_appender::rolling::daily(log_dir, log_filename);
+    // A debug log keeps its exact name so the path can be quoted in an issue
+    let file_appender = if cli.debug {
+        tracing_appender::rolling::never(log_dir, log_filename)
+    } else {
+        tracing_appender::rolling::daily(log_dir, log_filename)
+    };
     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
 
-    let filter = cli
+    let mut filter = cli
         .log_level
         .parse::<tracing_subscriber::filter::LevelFilter>()
         .unwrap_or(tracing_subscriber::filter::LevelFilter::INFO);
+    if cli.debug {
+        filter = filter.max(tracing_subscriber::filter::LevelFilter::DEBUG);
+    }
 
     tracing_subscriber::fmt()
         .with_writer(non_blocking)
@@ -76,6 +171,17 @@ fn setup_logging(cli: &Cli) -> tracing_appender::non_blocking::WorkerGuard {
     guard
 }
 
+/// `$XDG_CACHE_HOME/sitrep/debug.log`, or under `~/.cache`.
+fn debug_log_path() -> std::path::PathBuf {
+    let mut p = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| dirs_or_home().join(".cache"));
+    p.push("sitrep");
+    p.push("debug.log");
+    p
+}
+
 fn dirs_or_home() -> std::path::PathBuf {
     std::env::var("HOME")
         .map(std::path::PathBuf::from)
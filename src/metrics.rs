@@ -0,0 +1,184 @@
+//! Prometheus text exposition of the collected data, served by daemon mode.
+
+use std::fmt::Write;
+
+use crate::alerts::{Alert, Severity};
+use crate::model::{assess_saturation, DockerContainerInfo, MonitorData, SaturationLevel};
+
+/// Render every metric family in the Prometheus text format (version 0.0.4).
+pub fn render(data: Option<&MonitorData>, containers: &[DockerContainerInfo], alerts: &[Alert]) -> String {
+    let mut out = String::new();
+    if let Some(data) = data {
+        render_system(&mut out, data);
+    }
+    render_containers(&mut out, containers);
+    render_alerts(&mut out, alerts);
+    out
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    if labels.is_empty() {
+        let _ = writeln!(out, "{} {}", name, value);
+        return;
+    }
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v)))
+        .collect();
+    let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), value);
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn render_system(out: &mut String, data: &MonitorData) {
+    family(out, "sitrep_load_average", "gauge", "System load average.");
+    let (l1, l5, l15) = data.load_avg;
+    for (period, value) in [("1m", l1), ("5m", l5), ("15m", l15)] {
+        sample(out, "sitrep_load_average", &[("period", period)], value);
+    }
+
+    family(out, "sitrep_cpu_cores", "gauge", "Logical CPU count.");
+    sample(out, "sitrep_cpu_cores", &[], data.core_count);
+
+    let m = &data.memory;
+    family(out, "sitrep_memory_bytes", "gauge", "Memory and swap by kind.");
+    for (kind, value) in [
+        ("total", m.total),
+        ("used", m.used),
+        ("available", m.available),
+        ("swap_total", m.swap_total),
+        ("swap_used", m.swap_used),
+    ] {
+        sample(out, "sitrep_memory_bytes", &[("kind", kind)], value as f64);
+    }
+
+    family(out, "sitrep_disk_used_ratio", "gauge", "Used fraction of each mounted filesystem.");
+    for disk in &data.disk_space {
+        if disk.total_gb > 0.0 {
+            let ratio = (disk.total_gb - disk.available_gb) / disk.total_gb;
+            sample(out, "sitrep_disk_used_ratio", &[("mount", &disk.mount_point)], ratio);
+        }
+    }
+
+    family(out, "sitrep_disk_busy_percent", "gauge", "Disk I/O busy percentage.");
+    sample(out, "sitrep_disk_busy_percent", &[], data.disk_busy_pct);
+
+    let sock = &data.socket_overview;
+    family(out, "sitrep_tcp_connections", "gauge", "TCP sockets by state.");
+    for (state, value) in [
+        ("established", sock.established),
+        ("listen", sock.listen),
+        ("time_wait", sock.time_wait),
+        ("close_wait", sock.close_wait),
+        ("fin_wait", sock.fin_wait),
+    ] {
+        sample(out, "sitrep_tcp_connections", &[("state", state)], value as f64);
+    }
+
+    family(out, "sitrep_file_descriptors", "gauge", "System-wide file descriptors.");
+    sample(out, "sitrep_file_descriptors", &[("kind", "used")], data.fd_info.system_used as f64);
+    sample(out, "sitrep_file_descriptors", &[("kind", "max")], data.fd_info.system_max as f64);
+
+    if let Some(ct) = data.conntrack {
+        family(out, "sitrep_conntrack_entries", "gauge", "Netfilter conntrack table usage.");
+        sample(out, "sitrep_conntrack_entries", &[("kind", "count")], ct.count as f64);
+        sample(out, "sitrep_conntrack_entries", &[("kind", "max")], ct.max as f64);
+    }
+
+    let p = &data.pressure;
+    let psi = [("cpu", p.cpu), ("memory", p.memory), ("io", p.io)];
+    if psi.iter().any(|(_, v)| v.is_some()) {
+        family(out, "sitrep_pressure_some_avg10", "gauge", "PSI 10s average of time some tasks stalled, percent.");
+        for (resource, value) in psi {
+            if let Some(value) = value {
+                sample(out, "sitrep_pressure_some_avg10", &[("resource", resource)], value);
+            }
+        }
+    }
+
+    if let Some(oom) = data.oom {
+        family(out, "sitrep_oom_kills_total", "counter", "Processes killed by the kernel OOM killer since boot.");
+        sample(out, "sitrep_oom_kills_total", &[], oom.total_kills as f64);
+    }
+
+    let saturation = assess_saturation(
+        data.load_avg,
+        data.core_count,
+        &data.pressure,
+        &data.memory,
+        data.disk_busy_pct,
+    );
+    family(out, "sitrep_saturation_level", "gauge", "Saturation verdict: 0 OK, 1 PRESSURE, 2 SATURATED.");
+    let level = match saturation.level {
+        SaturationLevel::Ok => 0.0,
+        SaturationLevel::Pressure => 1.0,
+        SaturationLevel::Saturated => 2.0,
+    };
+    sample(out, "sitrep_saturation_level", &[], level);
+}
+
+fn render_containers(out: &mut String, containers: &[DockerContainerInfo]) {
+    if containers.is_empty() {
+        return;
+    }
+    family(out, "sitrep_container_cpu_percent", "gauge", "Container CPU usage, percent of one core.");
+    for c in containers {
+        sample(out, "sitrep_container_cpu_percent", &[("name", &c.name)], c.cpu_percent);
+    }
+    family(out, "sitrep_container_memory_bytes", "gauge", "Container memory usage.");
+    for c in containers {
+        sample(out, "sitrep_container_memory_bytes", &[("name", &c.name)], c.mem_usage as f64);
+    }
+    family(out, "sitrep_container_network_bytes_total", "counter", "Container network bytes by direction.");
+    for c in containers {
+        sample(out, "sitrep_container_network_bytes_total", &[("name", &c.name), ("direction", "rx")], c.net_rx as f64);
+        sample(out, "sitrep_container_network_bytes_total", &[("name", &c.name), ("direction", "tx")], c.net_tx as f64);
+    }
+}
+
+fn render_alerts(out: &mut String, alerts: &[Alert]) {
+    family(out, "sitrep_alerts_active", "gauge", "Active alerts by severity.");
+    for severity in [Severity::Warning, Severity::Critical] {
+        let count = alerts.iter().filter(|a| a.severity == severity).count();
+        sample(out, "sitrep_alerts_active", &[("severity", severity.label())], count as f64);
+    }
+    family(out, "sitrep_alert", "gauge", "One series per active alert.");
+    for alert in alerts {
+        sample(
+            out,
+            "sitrep_alert",
+            &[("source", alert.source), ("target", &alert.target), ("severity", alert.severity.label())],
+            1.0,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_alert_series_with_escaped_labels() {
+        let alert = Alert {
+            key: "health:service/api".into(),
+            source: "health",
+            target: "service/\"api\"".into(),
+            severity: Severity::Warning,
+            message: "HEALTH FAIL".into(),
+        };
+        let text = render(None, &[], &[alert]);
+        assert!(text.contains("sitrep_alerts_active{severity=\"warning\"} 1\n"));
+        assert!(text.contains("sitrep_alerts_active{severity=\"critical\"} 0\n"));
+        assert!(text.contains(
+            "sitrep_alert{source=\"health\",target=\"service/\\\"api\\\"\",severity=\"warning\"} 1\n"
+        ));
+        assert!(text.contains("# TYPE sitrep_alert gauge\n"));
+    }
+}
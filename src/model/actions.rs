@@ -0,0 +1,86 @@
+//! The actions a key runs on the selected row, listed once with the kind of
+//! row they apply to, so the quick-action bar above the footer can offer
+//! exactly those that work on the current selection.
+
+/// The selected row, as far as the actions offered for it go.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Selection {
+    /// A process in the System tab's process table.
+    Process { in_container: bool },
+    Container { running: bool },
+    ContainerGroup,
+    Service,
+    /// A container or service on the Watchlist tab.
+    WatchItem,
+    /// A pod on the Kubernetes tab.
+    Pod,
+}
+
+pub struct Action {
+    pub key: &'static str,
+    pub label: &'static str,
+    /// Changes a container or service, so left out with `--read-only`.
+    pub mutates: bool,
+    applies: fn(&Selection) -> bool,
+}
+
+const fn action(key: &'static str, label: &'static str, applies: fn(&Selection) -> bool) -> Action {
+    Action { key, label, mutates: false, applies }
+}
+
+const fn mutating(key: &'static str, label: &'static str, applies: fn(&Selection) -> bool) -> Action {
+    Action { key, label, mutates: true, applies }
+}
+
+/// Every selection action, in the order the bar lists them.
+pub const ACTIONS: &[Action] = &[
+    action("p", "profile", |s| matches!(s, Selection::Process { .. })),
+    action("t", "trace", |s| matches!(s, Selection::Process { .. })),
+    action("C", "container", |s| matches!(s, Selection::Process { in_container: true })),
+    action("L", "logs", |s| matches!(s, Selection::Process { in_container: true })),
+    action("→", "logs", |s| matches!(s, Selection::Container { .. } | Selection::WatchItem)),
+    action("←", "details", |s| matches!(s, Selection::Container { .. })),
+    action("→/←", "expand/collapse", |s| matches!(s, Selection::ContainerGroup)),
+    mutating("S", "start", |s| matches!(s, Selection::Container { running: false })),
+    mutating("T", "stop", |s| matches!(s, Selection::Container { running: true })),
+    mutating("R", "restart", |s| matches!(s, Selection::Container { running: true })),
+    action("p", "probe ports", |s| matches!(s, Selection::Container { running: true })),
+    action("n", "sockets", |s| matches!(s, Selection::Container { running: true })),
+    mutating("N", "rename", |s| matches!(s, Selection::Container { .. })),
+    action("Space", "mark", |s| matches!(s, Selection::Container { .. })),
+    action("→", "tasks", |s| matches!(s, Selection::Service)),
+    mutating("R", "rolling restart", |s| matches!(s, Selection::Service)),
+    mutating("E", "label", |s| matches!(s, Selection::Service)),
+    mutating("@", "schedule restart", |s| matches!(s, Selection::Service)),
+    action("V", "split logs", |s| matches!(s, Selection::Container { .. } | Selection::Service)),
+    action("w", "watchlist", |s| matches!(s, Selection::Container { .. } | Selection::Service)),
+    action("w", "unpin", |s| matches!(s, Selection::WatchItem)),
+    action("→", "logs", |s| matches!(s, Selection::Pod)),
+];
+
+/// The actions that work on `selection`.
+pub fn actions_for(selection: &Selection, read_only: bool) -> Vec<&'static Action> {
+    ACTIONS
+        .iter()
+        .filter(|a| (a.applies)(selection) && !(read_only && a.mutates))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offers_what_applies_to_the_selection() {
+        let keys = |selection, read_only| -> Vec<(&str, &str)> {
+            actions_for(&selection, read_only).iter().map(|a| (a.key, a.label)).collect()
+        };
+        let stopped = keys(Selection::Container { running: false }, false);
+        assert!(stopped.contains(&("S", "start")));
+        assert!(!stopped.iter().any(|(key, _)| *key == "R" || *key == "n"));
+        let running = keys(Selection::Container { running: true }, true);
+        assert!(running.contains(&("n", "sockets")));
+        assert!(!running.iter().any(|(key, _)| *key == "T" || *key == "R"));
+        assert_eq!(keys(Selection::Process { in_container: false }, false), vec![("p", "profile"), ("t", "trace")]);
+    }
+}
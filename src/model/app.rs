@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// App-level view state
 #[derive(Clone, Debug, PartialEq)]
 pub enum AppView {
@@ -8,4 +10,91 @@ pub enum AppView {
     Swarm,                                     // Swarm cluster view
     SwarmServiceTasks(String, String),         // (service_id, service_name)
     SwarmServiceLogs(String, String),          // (service_id, service_name)
+    SwarmSplitLogs,                            // two log streams side by side
+    Watchlist,                                 // pinned containers and services
+    Kubernetes,                                // namespaces, deployments, pods
+    KubernetesPodLogs(String, String),         // (namespace, pod)
+    Graphs,                                    // full-screen history charts
+    DockerHealth,                              // the Docker daemon itself
+}
+
+/// How current the data behind a view is, for its "updated 2s ago".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Freshness {
+    /// The first collection has not finished.
+    Collecting,
+    /// Last collected this long ago.
+    Updated(Duration),
+    /// Last collected this long ago, over two refresh intervals.
+    Stale(Duration),
+    /// The latest refresh failed; what is shown (if anything) is this old.
+    Failed(Option<Duration>),
+    /// A log stream that stopped; its last line came this long ago.
+    Ended(Option<Duration>),
+}
+
+impl Freshness {
+    /// Classify a collector by the age of its last good result, whether the
+    /// latest attempt failed, and how often it refreshes.
+    pub fn new(age: Option<Duration>, failed: bool, interval: Duration) -> Self {
+        match age {
+            _ if failed => Freshness::Failed(age),
+            None => Freshness::Collecting,
+            Some(age) if age > interval * 2 => Freshness::Stale(age),
+            Some(age) => Freshness::Updated(age),
+        }
+    }
+
+    /// A log stream: quiet is normal, so it is only out of date once ended.
+    pub fn of_log(last_line: Option<Duration>, ended: bool) -> Self {
+        match last_line {
+            _ if ended => Freshness::Ended(last_line),
+            None => Freshness::Collecting,
+            Some(age) => Freshness::Updated(age),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Freshness::Collecting => "collecting...".to_string(),
+            Freshness::Updated(age) | Freshness::Stale(age) => format!("updated {} ago", format_age(*age)),
+            Freshness::Failed(Some(age)) => format!("update failed, data from {} ago", format_age(*age)),
+            Freshness::Failed(None) => "update failed".to_string(),
+            Freshness::Ended(_) => "stream ended".to_string(),
+        }
+    }
+
+    /// Whether what the view shows may no longer be true, so it is dimmed.
+    pub fn is_out_of_date(&self) -> bool {
+        !matches!(self, Freshness::Updated(_))
+    }
+}
+
+/// "2s", "5m" or "3h".
+fn format_age(age: Duration) -> String {
+    match age.as_secs() {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s => format!("{}h", s / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn freshness_by_age_and_failure() {
+        let tick = Duration::from_secs(3);
+        let fresh = Freshness::new(Some(Duration::from_secs(2)), false, tick);
+        assert_eq!((fresh.label(), fresh.is_out_of_date()), ("updated 2s ago".to_string(), false));
+        assert_eq!(Freshness::new(Some(Duration::from_secs(7)), false, tick), Freshness::Stale(Duration::from_secs(7)));
+        assert_eq!(Freshness::new(None, false, tick), Freshness::Collecting);
+        let failed = Freshness::new(Some(Duration::from_secs(130)), true, tick);
+        assert_eq!(failed.label(), "update failed, data from 2m ago");
+        assert!(failed.is_out_of_date());
+        // A quiet log stays current however long ago its last line was
+        assert!(!Freshness::of_log(Some(Duration::from_secs(600)), false).is_out_of_date());
+        assert!(Freshness::of_log(Some(Duration::from_secs(1)), true).is_out_of_date());
+    }
 }
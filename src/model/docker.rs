@@ -1,6 +1,13 @@
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::collections::VecDeque;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use super::logs::{LogSelection, LOG_STREAM_ENDED};
+use super::system::CpuScale;
+use crate::scan::ImageScan;
 
 struct LogSearchCache {
     line_version: u64,
@@ -14,7 +21,7 @@ struct MultiLogSearchCache {
     matches: Vec<usize>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct DockerContainerInfo {
     pub id: String,         // short ID (first 12 chars)
     pub name: String,       // container name
@@ -22,9 +29,174 @@ pub struct DockerContainerInfo {
     pub status: String,     // "running", "paused", etc.
     pub state: String,      // raw state string from Docker
     pub uptime: String,     // human-readable (e.g. "2h 34m")
+    pub uptime_secs: Option<u64>, // seconds since last start, if known
     pub cpu_percent: f64,   // from stats
     pub ports: String,      // e.g. "0.0.0.0:8080->80/tcp"
     pub ip_address: String, // internal IP from NetworkSettings
+    pub mem_usage: u64,     // bytes, from stats
+    pub mem_limit: u64,     // bytes, from stats (0 = unknown)
+    pub net_rx: u64,        // total bytes received across interfaces
+    pub net_tx: u64,        // total bytes sent across interfaces
+    pub labels: HashMap<String, String>,
+    pub published_ports: Vec<PublishedPort>, // TCP ports bound on the host
+    pub image_id: String,   // "sha256:..." of the image the container runs
+    pub image_created: Option<i64>, // Unix time the image was built
+    pub image_digests: Vec<String>, // "repo@sha256:..." from the local image
+    /// Whether the registry has a different digest for `image`; None when
+    /// unchecked (the check is opt-in) or the image was never pulled.
+    pub image_drift: Option<bool>,
+    /// Vulnerability counts of the running image; None when unscanned (the
+    /// scan is opt-in).
+    pub image_scan: Option<ImageScan>,
+    /// containerd namespace of a container listed through nerdctl; empty
+    /// under Docker.
+    pub namespace: String,
+}
+
+impl DockerContainerInfo {
+    /// Seconds since the running image was built.
+    pub fn image_age_secs(&self) -> Option<u64> {
+        let created = self.image_created.filter(|t| *t > 0)?;
+        Some((chrono::Utc::now().timestamp() - created).max(0) as u64)
+    }
+
+    /// Whether the local image matches registry `digest` ("sha256:...").
+    pub fn runs_digest(&self, digest: &str) -> bool {
+        self.image_digests.iter().any(|d| d.rsplit_once('@').is_some_and(|(_, d)| d == digest))
+    }
+
+    /// Docker's own HEALTHCHECK state, parsed from the status string
+    /// (e.g. "Up 5 minutes (healthy)"). None when no healthcheck is defined.
+    pub fn docker_health(&self) -> Option<&'static str> {
+        if self.status.contains("(healthy)") {
+            Some("healthy")
+        } else if self.status.contains("(unhealthy)") {
+            Some("unhealthy")
+        } else if self.status.contains("(health: starting)") {
+            Some("starting")
+        } else {
+            None
+        }
+    }
+}
+
+/// A container TCP port published on the host.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PublishedPort {
+    pub host_ip: String,
+    pub host_port: u16,
+    pub container_port: u16,
+}
+
+/// One stats snapshot for a container, cached between ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ContainerStats {
+    pub cpu_percent: f64,
+    pub mem_usage: u64,
+    pub mem_limit: u64,
+    pub net_rx: u64,
+    pub net_tx: u64,
+}
+
+impl ContainerStats {
+    pub fn apply(&self, info: &mut DockerContainerInfo) {
+        info.cpu_percent = self.cpu_percent;
+        info.mem_usage = self.mem_usage;
+        info.mem_limit = self.mem_limit;
+        info.net_rx = self.net_rx;
+        info.net_tx = self.net_tx;
+    }
+}
+
+/// Samples kept per container: ten minutes at the default 3s refresh.
+pub const STATS_HISTORY_LEN: usize = 200;
+
+/// Recent CPU and memory samples per container ID, oldest first, one per
+/// Docker refresh. Containers that disappear from the list are forgotten.
+#[derive(Clone, Debug, Default)]
+pub struct ContainerStatsHistory {
+    samples: HashMap<String, VecDeque<(f64, u64)>>,
+}
+
+impl ContainerStatsHistory {
+    /// Append the current stats of every listed container.
+    pub fn record(&mut self, containers: &[DockerContainerInfo]) {
+        self.samples.retain(|id, _| containers.iter().any(|c| &c.id == id));
+        for c in containers {
+            let samples = self.samples.entry(c.id.clone()).or_default();
+            if samples.len() == STATS_HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back((c.cpu_percent, c.mem_usage));
+        }
+    }
+
+    pub fn cpu(&self, id: &str) -> Vec<f64> {
+        self.samples.get(id).map(|s| s.iter().map(|(cpu, _)| *cpu).collect()).unwrap_or_default()
+    }
+
+    pub fn mem(&self, id: &str) -> Vec<u64> {
+        self.samples.get(id).map(|s| s.iter().map(|(_, mem)| *mem).collect()).unwrap_or_default()
+    }
+}
+
+// --- Docker daemon health ---
+
+/// Latency checks kept for the daemon panel: a minute at the default 3s refresh.
+pub const DAEMON_LATENCY_LEN: usize = 20;
+
+/// One timed round of daemon API calls, in milliseconds; None when the call
+/// failed or timed out.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DaemonLatency {
+    pub ping_ms: Option<f64>,
+    /// `/info` walks containers, images and the storage driver, so it slows
+    /// down well before `/_ping` does.
+    pub info_ms: Option<f64>,
+}
+
+/// What the Docker health panel shows about the daemon itself.
+#[derive(Clone, Debug, Default)]
+pub struct DaemonHealth {
+    /// Where sitrep reaches the daemon, e.g. "unix:///run/user/1000/docker.sock".
+    pub endpoint: String,
+    pub version: String,
+    pub storage_driver: String,
+    /// The daemon runs without root (rootless mode).
+    pub rootless: bool,
+    /// Why the last `/info` call failed.
+    pub error: Option<String>,
+    /// Recent checks, oldest first.
+    pub latency: VecDeque<DaemonLatency>,
+    /// Where the log tail came from ("journald", a file path); empty until
+    /// the first check finishes.
+    pub log_source: String,
+    /// Why there is no log tail.
+    pub log_error: Option<String>,
+    pub log_lines: Vec<String>,
+}
+
+impl DaemonHealth {
+    pub fn record(&mut self, sample: DaemonLatency) {
+        if self.latency.len() == DAEMON_LATENCY_LEN {
+            self.latency.pop_front();
+        }
+        self.latency.push_back(sample);
+    }
+
+    pub fn last(&self) -> Option<&DaemonLatency> {
+        self.latency.back()
+    }
+
+    /// Slowest answered call over the kept checks.
+    pub fn worst_ms(&self, field: fn(&DaemonLatency) -> Option<f64>) -> Option<f64> {
+        self.latency.iter().filter_map(field).reduce(f64::max)
+    }
+
+    /// Checks where the call got no answer.
+    pub fn failures(&self, field: fn(&DaemonLatency) -> Option<f64>) -> usize {
+        self.latency.iter().filter(|s| field(s).is_none()).count()
+    }
 }
 
 // --- Log viewer state ---
@@ -32,12 +204,19 @@ pub struct DockerContainerInfo {
 pub struct LogViewState {
     pub container_id: String,
     pub container_name: String,
+    /// The tab the logs were opened from, first in the header.
+    pub section: &'static str,
     pub lines: VecDeque<String>,
     pub scroll_offset: usize, // 0 = at bottom (following)
     pub auto_follow: bool,
     pub search_mode: bool,    // true when typing a search query
     pub search_query: String, // current search text
     pub truncated_count: u64, // number of lines dropped due to buffer cap
+    pub selection: LogSelection,
+    /// When the newest line arrived.
+    pub last_line_at: Option<Instant>,
+    /// The stream stopped and no more lines will come.
+    pub ended: bool,
     line_version: u64,
     search_cache: RefCell<Option<LogSearchCache>>,
 }
@@ -47,12 +226,16 @@ impl LogViewState {
         Self {
             container_id,
             container_name,
+            section: "Containers",
             lines: VecDeque::with_capacity(5000),
             scroll_offset: 0,
             auto_follow: true,
             search_mode: false,
             search_query: String::new(),
             truncated_count: 0,
+            selection: LogSelection::default(),
+            last_line_at: None,
+            ended: false,
             line_version: 0,
             search_cache: RefCell::new(None),
         }
@@ -65,9 +248,20 @@ impl LogViewState {
         }
         self.lines.push_back(line);
         self.line_version += 1;
+        self.last_line_at = Some(Instant::now());
         *self.search_cache.borrow_mut() = None;
     }
 
+    /// Mark the stream stopped, with a closing line the first time.
+    pub fn end_stream(&mut self) {
+        if !self.ended {
+            let last_line_at = self.last_line_at;
+            self.push_line(LOG_STREAM_ENDED.to_string());
+            self.last_line_at = last_line_at;
+            self.ended = true;
+        }
+    }
+
     pub fn with_filtered_indices<R>(&self, f: impl FnOnce(&[usize]) -> R) -> R {
         let query = self.search_query.to_lowercase();
         let mut cache = self.search_cache.borrow_mut();
@@ -112,10 +306,19 @@ pub struct MultiLogViewState {
     pub search_mode: bool,
     pub search_query: String,
     pub truncated_count: u64,
+    pub selection: LogSelection,
+    /// When the newest line from any container arrived.
+    pub last_line_at: Option<Instant>,
     line_version: u64,
     search_cache: RefCell<Option<MultiLogSearchCache>>,
 }
 
+impl Default for MultiLogViewState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MultiLogViewState {
     pub fn new() -> Self {
         Self {
@@ -125,6 +328,8 @@ impl MultiLogViewState {
             search_mode: false,
             search_query: String::new(),
             truncated_count: 0,
+            selection: LogSelection::default(),
+            last_line_at: None,
             line_version: 0,
             search_cache: RefCell::new(None),
         }
@@ -137,6 +342,7 @@ impl MultiLogViewState {
         }
         self.lines.push_back(line);
         self.line_version += 1;
+        self.last_line_at = Some(Instant::now());
         *self.search_cache.borrow_mut() = None;
     }
 
@@ -171,24 +377,302 @@ impl MultiLogViewState {
     }
 }
 
+// --- Container grouping ---
+
+/// Label Docker Compose sets on every container it creates.
+pub const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+
+/// Group name for containers without a value for the grouping key.
+pub const UNGROUPED: &str = "(ungrouped)";
+
+/// How the container list is grouped into collapsible sections.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContainerGrouping {
+    #[default]
+    None,
+    Compose,
+    Image,
+    Label,
+}
+
+impl ContainerGrouping {
+    pub fn label(self) -> &'static str {
+        match self {
+            ContainerGrouping::None => "none",
+            ContainerGrouping::Compose => "compose project",
+            ContainerGrouping::Image => "image",
+            ContainerGrouping::Label => "label",
+        }
+    }
+
+    /// Next mode in the toggle cycle. `Label` is skipped when no label is configured.
+    pub fn next(self, has_label: bool) -> Self {
+        match self {
+            ContainerGrouping::None => ContainerGrouping::Compose,
+            ContainerGrouping::Compose => ContainerGrouping::Image,
+            ContainerGrouping::Image if has_label => ContainerGrouping::Label,
+            ContainerGrouping::Image | ContainerGrouping::Label => ContainerGrouping::None,
+        }
+    }
+}
+
+/// Ordering of the container list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContainerSort {
+    /// As returned by the Docker daemon.
+    #[default]
+    Default,
+    /// Most recently (re)started first.
+    Uptime,
+}
+
+/// One term of a label filter expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LabelMatcher {
+    Exists(String),             // `team`
+    Equals(String, String),     // `env=prod`
+    NotEquals(String, String),  // `env!=prod`
+}
+
+impl LabelMatcher {
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        match self {
+            LabelMatcher::Exists(key) => labels.contains_key(key),
+            LabelMatcher::Equals(key, value) => labels.get(key) == Some(value),
+            LabelMatcher::NotEquals(key, value) => labels.get(key) != Some(value),
+        }
+    }
+}
+
+impl std::fmt::Display for LabelMatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelMatcher::Exists(key) => write!(f, "{}", key),
+            LabelMatcher::Equals(key, value) => write!(f, "{}={}", key, value),
+            LabelMatcher::NotEquals(key, value) => write!(f, "{}!={}", key, value),
+        }
+    }
+}
+
+/// Render a parsed filter back to its text form.
+pub fn format_label_filter(filter: &[LabelMatcher]) -> String {
+    filter.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+/// Parse a label filter such as `env=prod team=payments`. Terms are separated by
+/// whitespace or commas and must all match.
+pub fn parse_label_filter(text: &str) -> Result<Vec<LabelMatcher>, String> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            let matcher = if let Some((key, value)) = term.split_once("!=") {
+                LabelMatcher::NotEquals(key.to_string(), value.to_string())
+            } else if let Some((key, value)) = term.split_once('=') {
+                LabelMatcher::Equals(key.to_string(), value.to_string())
+            } else {
+                LabelMatcher::Exists(term.to_string())
+            };
+            match &matcher {
+                LabelMatcher::Exists(key)
+                | LabelMatcher::Equals(key, _)
+                | LabelMatcher::NotEquals(key, _)
+                    if key.is_empty() =>
+                {
+                    Err(format!("Invalid label filter term '{}'", term))
+                }
+                _ => Ok(matcher),
+            }
+        })
+        .collect()
+}
+
+/// A label change typed at the label prompt: `key=value` sets a label and
+/// `-key` removes it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LabelEdit {
+    Add(String, String),
+    Remove(String),
+}
+
+impl LabelEdit {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let edit = match (text.strip_prefix('-'), text.split_once('=')) {
+            (Some(key), _) => LabelEdit::Remove(key.to_string()),
+            (None, Some((key, value))) => LabelEdit::Add(key.to_string(), value.to_string()),
+            (None, None) => return Err(format!("Type key=value to set a label or -key to remove one, not '{}'", text)),
+        };
+        let (LabelEdit::Add(key, _) | LabelEdit::Remove(key)) = &edit;
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("Invalid label key '{}'", key));
+        }
+        Ok(edit)
+    }
+}
+
+impl std::fmt::Display for LabelEdit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelEdit::Add(key, value) => write!(f, "{}={}", key, value),
+            LabelEdit::Remove(key) => write!(f, "-{}", key),
+        }
+    }
+}
+
+/// Whether Docker accepts `name` for a container: a letter or digit followed
+/// by at least one letter, digit, `_`, `.` or `-`.
+pub fn valid_container_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && name.len() > 1
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Indices into `containers` in display order, keeping only those matching every
+/// label filter term.
+pub fn container_order(
+    containers: &[DockerContainerInfo],
+    sort: ContainerSort,
+    filter: &[LabelMatcher],
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..containers.len())
+        .filter(|&i| filter.iter().all(|m| m.matches(&containers[i].labels)))
+        .collect();
+    if sort == ContainerSort::Uptime {
+        // Unknown uptimes sort last
+        order.sort_by_key(|&i| containers[i].uptime_secs.unwrap_or(u64::MAX));
+    }
+    order
+}
+
+/// A group of containers sharing a compose project, image, or label value.
+pub struct ContainerGroupInfo {
+    pub name: String,
+    pub container_indices: Vec<usize>,
+}
+
+/// What occupies a row of the container list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContainerRow {
+    GroupHeader(usize), // index into groups
+    Container(usize),   // index into containers
+}
+
+/// Group containers by the given mode. Returns no groups for `ContainerGrouping::None`.
+pub fn group_containers(
+    containers: &[DockerContainerInfo],
+    order: &[usize],
+    grouping: ContainerGrouping,
+    label: Option<&str>,
+) -> Vec<ContainerGroupInfo> {
+    let key = |c: &DockerContainerInfo| -> Option<String> {
+        match grouping {
+            ContainerGrouping::None => None,
+            ContainerGrouping::Compose => c.labels.get(COMPOSE_PROJECT_LABEL).cloned(),
+            ContainerGrouping::Image => Some(c.image.clone()),
+            ContainerGrouping::Label => label.and_then(|l| c.labels.get(l).cloned()),
+        }
+    };
+    if grouping == ContainerGrouping::None {
+        return Vec::new();
+    }
+
+    let mut group_map: HashMap<String, Vec<usize>> = HashMap::new();
+    for &i in order {
+        let name = key(&containers[i])
+            .filter(|k| !k.is_empty())
+            .unwrap_or_else(|| UNGROUPED.to_string());
+        group_map.entry(name).or_default().push(i);
+    }
+
+    let mut groups: Vec<ContainerGroupInfo> = group_map
+        .into_iter()
+        .map(|(name, indices)| ContainerGroupInfo { name, container_indices: indices })
+        .collect();
+
+    // Sort: named groups first, then "(ungrouped)" last
+    groups.sort_by(|a, b| {
+        if a.name == UNGROUPED {
+            std::cmp::Ordering::Greater
+        } else if b.name == UNGROUPED {
+            std::cmp::Ordering::Less
+        } else {
+            a.name.cmp(&b.name)
+        }
+    });
+    groups
+}
+
+/// Lay out the visible rows: every container when ungrouped, otherwise group
+/// headers followed by their containers unless the group is collapsed.
+pub fn container_rows(
+    order: &[usize],
+    groups: &[ContainerGroupInfo],
+    collapsed: &HashSet<String>,
+) -> Vec<ContainerRow> {
+    if groups.is_empty() {
+        return order.iter().map(|&i| ContainerRow::Container(i)).collect();
+    }
+    let mut rows = Vec::new();
+    for (gi, group) in groups.iter().enumerate() {
+        rows.push(ContainerRow::GroupHeader(gi));
+        if !collapsed.contains(&group.name) {
+            rows.extend(group.container_indices.iter().map(|&i| ContainerRow::Container(i)));
+        }
+    }
+    rows
+}
+
 // --- Container UI state ---
 
+#[derive(Default)]
 pub struct ContainerUIState {
     pub selected_index: usize,
     pub selected_id: Option<String>,
     pub total_rows: usize,
     pub expanded_ids: HashSet<String>,
     pub selected_containers: HashSet<String>,
+    /// Index of the first row drawn in the viewport.
+    pub scroll_offset: usize,
+    pub grouping: ContainerGrouping,
+    pub sort: ContainerSort,
+    /// Applied label filter; containers must match every term.
+    pub label_filter: Vec<LabelMatcher>,
+    /// Whether the label filter prompt is open, and its text.
+    pub filter_mode: bool,
+    pub filter_input: String,
+    /// Group names whose containers are hidden. Groups start expanded.
+    pub collapsed_groups: HashSet<String>,
+    /// Text of the rename prompt while it is open.
+    pub rename_input: Option<String>,
+    pub cpu_scale: CpuScale,
+    /// CPUs of the host, from the System tab, for `CpuScale::Total`.
+    pub host_cores: f64,
 }
 
-impl Default for ContainerUIState {
-    fn default() -> Self {
-        Self {
-            selected_index: 0,
-            selected_id: None,
-            total_rows: 0,
-            expanded_ids: HashSet::new(),
-            selected_containers: HashSet::new(),
+impl ContainerUIState {
+    /// Move the viewport so the selected row is fully visible. `row_heights` holds the
+    /// number of screen lines each row occupies (more when expanded) and
+    /// `capacity` is the number of lines available for rows.
+    pub fn scroll_to_selected(&mut self, row_heights: &[usize], capacity: usize) {
+        if row_heights.is_empty() {
+            self.scroll_offset = 0;
+            return;
+        }
+        let selected = self.selected_index.min(row_heights.len() - 1);
+        if selected < self.scroll_offset {
+            self.scroll_offset = selected;
+        }
+        while self.scroll_offset < selected
+            && row_heights[self.scroll_offset..=selected].iter().sum::<usize>() > capacity
+        {
+            self.scroll_offset += 1;
+        }
+        // Don't leave blank space at the bottom when rows were removed.
+        while self.scroll_offset > 0
+            && row_heights[self.scroll_offset - 1..].iter().sum::<usize>() <= capacity
+        {
+            self.scroll_offset -= 1;
         }
     }
 }
@@ -197,6 +681,20 @@ impl Default for ContainerUIState {
 mod tests {
     use super::*;
 
+    #[test]
+    fn daemon_health_keeps_recent_latency() {
+        let mut health = DaemonHealth::default();
+        for i in 0..DAEMON_LATENCY_LEN + 5 {
+            let ping_ms = (i % 7 != 0).then_some(i as f64);
+            health.record(DaemonLatency { ping_ms, info_ms: Some(2.0) });
+        }
+        assert_eq!(health.latency.len(), DAEMON_LATENCY_LEN);
+        assert_eq!(health.worst_ms(|s| s.ping_ms), Some(24.0));
+        // 7, 14 and 21 are within the last 20 checks
+        assert_eq!(health.failures(|s| s.ping_ms), 3);
+        assert_eq!(health.last().and_then(|s| s.ping_ms), Some(24.0));
+    }
+
     #[test]
     fn log_view_state_push_line_caps_at_5000() {
         let mut state = LogViewState::new("abc123".into(), "my-container".into());
@@ -215,6 +713,191 @@ mod tests {
         assert_eq!(state.total_rows, 0);
         assert!(state.expanded_ids.is_empty());
         assert!(state.selected_containers.is_empty());
+        assert_eq!(state.scroll_offset, 0);
+    }
+
+    #[test]
+    fn stats_history_is_capped_and_forgets_removed_containers() {
+        let mut history = ContainerStatsHistory::default();
+        let mut web = container("web", "nginx", None);
+        let db = container("db", "postgres", None);
+        for i in 0..STATS_HISTORY_LEN + 5 {
+            web.cpu_percent = i as f64;
+            history.record(&[web.clone(), db.clone()]);
+        }
+        let cpu = history.cpu("web");
+        assert_eq!((cpu.len(), cpu[0], *cpu.last().unwrap()), (STATS_HISTORY_LEN, 5.0, 204.0));
+
+        history.record(std::slice::from_ref(&web));
+        assert!(history.mem("db").is_empty());
+    }
+
+    fn container(name: &str, image: &str, project: Option<&str>) -> DockerContainerInfo {
+        let mut labels = HashMap::new();
+        if let Some(p) = project {
+            labels.insert(COMPOSE_PROJECT_LABEL.to_string(), p.to_string());
+        }
+        DockerContainerInfo {
+            id: name.into(),
+            name: name.into(),
+            image: image.into(),
+            status: String::new(),
+            state: "running".into(),
+            uptime: String::new(),
+            uptime_secs: None,
+            cpu_percent: 0.0,
+            ports: String::new(),
+            ip_address: String::new(),
+            mem_usage: 0,
+            mem_limit: 0,
+            net_rx: 0,
+            net_tx: 0,
+            labels,
+            published_ports: Vec::new(),
+            image_id: String::new(),
+            image_created: None,
+            image_digests: Vec::new(),
+            image_drift: None,
+            image_scan: None,
+            namespace: String::new(),
+        }
+    }
+
+    #[test]
+    fn docker_health_parses_status_suffix() {
+        let mut c = container("a", "nginx", None);
+        c.status = "Up 5 minutes (healthy)".into();
+        assert_eq!(c.docker_health(), Some("healthy"));
+        c.status = "Up 1 second (health: starting)".into();
+        assert_eq!(c.docker_health(), Some("starting"));
+        c.status = "Up 2 hours".into();
+        assert_eq!(c.docker_health(), None);
+    }
+
+    #[test]
+    fn group_containers_by_compose_project_puts_ungrouped_last() {
+        let containers = vec![
+            container("a", "nginx", Some("web")),
+            container("b", "redis", None),
+            container("c", "postgres", Some("db")),
+            container("d", "nginx", Some("web")),
+        ];
+        let order = container_order(&containers, ContainerSort::Default, &[]);
+        let groups = group_containers(&containers, &order, ContainerGrouping::Compose, None);
+        let names: Vec<&str> = groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["db", "web", UNGROUPED]);
+        assert_eq!(groups[1].container_indices, vec![0, 3]);
+    }
+
+    #[test]
+    fn container_rows_skip_collapsed_groups() {
+        let containers = vec![
+            container("a", "nginx", None),
+            container("b", "redis", None),
+            container("c", "nginx", None),
+        ];
+        let order = container_order(&containers, ContainerSort::Default, &[]);
+        let groups = group_containers(&containers, &order, ContainerGrouping::Image, None);
+        let collapsed: HashSet<String> = ["nginx".to_string()].into_iter().collect();
+        let rows = container_rows(&order, &groups, &collapsed);
+        assert_eq!(
+            rows,
+            vec![
+                ContainerRow::GroupHeader(0),
+                ContainerRow::GroupHeader(1),
+                ContainerRow::Container(1),
+            ]
+        );
+
+        let flat = container_rows(&order, &[], &collapsed);
+        assert_eq!(flat.len(), 3);
+    }
+
+    #[test]
+    fn label_edits_and_container_names_are_checked() {
+        assert_eq!(LabelEdit::parse(" triage=quarantined"), Ok(LabelEdit::Add("triage".into(), "quarantined".into())));
+        assert_eq!(LabelEdit::parse("-triage"), Ok(LabelEdit::Remove("triage".into())));
+        assert_eq!(LabelEdit::parse("note=").map(|e| e.to_string()), Ok("note=".to_string()));
+        assert!(LabelEdit::parse("triage").is_err());
+        assert!(LabelEdit::parse("=x").is_err());
+        assert!(valid_container_name("web-old.1"));
+        assert!(!valid_container_name("w"));
+        assert!(!valid_container_name("_web"));
+        assert!(!valid_container_name("web old"));
+    }
+
+    #[test]
+    fn label_filter_parses_and_matches_all_terms() {
+        let filter = parse_label_filter("env=prod, team!=infra tier").unwrap();
+        assert_eq!(
+            filter,
+            vec![
+                LabelMatcher::Equals("env".into(), "prod".into()),
+                LabelMatcher::NotEquals("team".into(), "infra".into()),
+                LabelMatcher::Exists("tier".into()),
+            ]
+        );
+
+        let mut containers = vec![
+            container("a", "nginx", None),
+            container("b", "nginx", None),
+            container("c", "nginx", None),
+        ];
+        for (c, (env, team)) in containers.iter_mut().zip([("prod", "payments"), ("prod", "infra"), ("dev", "payments")]) {
+            c.labels.insert("env".into(), env.into());
+            c.labels.insert("team".into(), team.into());
+            c.labels.insert("tier".into(), "web".into());
+        }
+        assert_eq!(container_order(&containers, ContainerSort::Default, &filter), vec![0]);
+        assert!(parse_label_filter("=prod").is_err());
+        assert!(parse_label_filter("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn container_order_by_uptime_puts_newest_first() {
+        let mut containers = vec![
+            container("old", "nginx", None),
+            container("unknown", "nginx", None),
+            container("new", "nginx", None),
+        ];
+        containers[0].uptime_secs = Some(86400);
+        containers[2].uptime_secs = Some(30);
+        assert_eq!(container_order(&containers, ContainerSort::Uptime, &[]), vec![2, 0, 1]);
+        assert_eq!(container_order(&containers, ContainerSort::Default, &[]), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn grouping_cycle_skips_label_without_config() {
+        assert_eq!(ContainerGrouping::Image.next(false), ContainerGrouping::None);
+        assert_eq!(ContainerGrouping::Image.next(true), ContainerGrouping::Label);
+        assert_eq!(ContainerGrouping::Label.next(true), ContainerGrouping::None);
+    }
+
+    #[test]
+    fn container_ui_state_scrolls_down_to_selection() {
+        let mut state = ContainerUIState { selected_index: 12, ..Default::default() };
+        state.scroll_to_selected(&[1; 20], 10);
+        assert_eq!(state.scroll_offset, 3);
+
+        state.selected_index = 1;
+        state.scroll_to_selected(&[1; 20], 10);
+        assert_eq!(state.scroll_offset, 1);
+    }
+
+    #[test]
+    fn container_ui_state_scroll_accounts_for_expanded_rows() {
+        let mut heights = vec![1; 20];
+        heights[8] = 3;
+        let mut state = ContainerUIState { selected_index: 9, ..Default::default() };
+        state.scroll_to_selected(&heights, 10);
+        assert_eq!(state.scroll_offset, 2);
+    }
+
+    #[test]
+    fn container_ui_state_scroll_pulls_back_when_list_shrinks() {
+        let mut state = ContainerUIState { selected_index: 4, scroll_offset: 15, ..Default::default() };
+        state.scroll_to_selected(&[1; 5], 10);
+        assert_eq!(state.scroll_offset, 0);
     }
 
     #[test]
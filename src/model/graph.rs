@@ -0,0 +1,112 @@
+use super::AppView;
+
+/// A metric that can be charted from the history store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphMetric {
+    Load,
+    Memory,
+    /// CPU percent of the named container.
+    ContainerCpu(String),
+    /// Running replicas of the named Swarm service.
+    ServiceReplicas(String),
+}
+
+impl GraphMetric {
+    pub fn label(&self) -> String {
+        match self {
+            GraphMetric::Load => "Load average (1m)".to_string(),
+            GraphMetric::Memory => "Memory used %".to_string(),
+            GraphMetric::ContainerCpu(name) => format!("Container CPU % \u{2014} {}", name),
+            GraphMetric::ServiceReplicas(name) => format!("Running replicas \u{2014} {}", name),
+        }
+    }
+}
+
+/// Selectable time ranges, narrowest first: (label, seconds).
+pub const GRAPH_RANGES: [(&str, i64); 6] = [
+    ("15m", 15 * 60),
+    ("1h", 3600),
+    ("6h", 6 * 3600),
+    ("24h", 24 * 3600),
+    ("3d", 3 * 24 * 3600),
+    ("7d", 7 * 24 * 3600),
+];
+
+/// State of the full-screen Graphs view.
+#[derive(Clone, Debug)]
+pub struct GraphViewState {
+    pub metrics: Vec<GraphMetric>,
+    pub selected: usize,
+    /// Index into GRAPH_RANGES.
+    pub range: usize,
+    /// Bucketed values of the selected metric, oldest first; None where no
+    /// samples were recorded.
+    pub values: Vec<Option<f64>>,
+    /// Where Esc returns to.
+    pub return_view: AppView,
+}
+
+impl GraphViewState {
+    pub fn new(metrics: Vec<GraphMetric>, return_view: AppView) -> Self {
+        Self {
+            metrics,
+            selected: 0,
+            range: 1,
+            values: Vec::new(),
+            return_view,
+        }
+    }
+
+    pub fn metric(&self) -> Option<&GraphMetric> {
+        self.metrics.get(self.selected)
+    }
+
+    pub fn range_label(&self) -> &'static str {
+        GRAPH_RANGES[self.range].0
+    }
+
+    pub fn range_secs(&self) -> i64 {
+        GRAPH_RANGES[self.range].1
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.range = self.range.saturating_sub(1);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.range = (self.range + 1).min(GRAPH_RANGES.len() - 1);
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.metrics.is_empty() {
+            self.selected = (self.selected + 1) % self.metrics.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.metrics.is_empty() {
+            self.selected = (self.selected + self.metrics.len() - 1) % self.metrics.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoom_and_selection_stay_in_bounds() {
+        let mut state = GraphViewState::new(vec![GraphMetric::Load, GraphMetric::Memory], AppView::System);
+        state.zoom_in();
+        state.zoom_in();
+        assert_eq!(state.range_label(), "15m");
+        for _ in 0..10 {
+            state.zoom_out();
+        }
+        assert_eq!(state.range_label(), "7d");
+        state.select_prev();
+        assert_eq!(state.metric(), Some(&GraphMetric::Memory));
+        state.select_next();
+        assert_eq!(state.metric(), Some(&GraphMetric::Load));
+    }
+}
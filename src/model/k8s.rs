@@ -0,0 +1,158 @@
+//! Kubernetes namespaces, deployments and pods as the Kubernetes tab shows
+//! them: one section per namespace, each deployment with its pods under it.
+
+use std::collections::HashSet;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KubeDeploymentInfo {
+    pub namespace: String,
+    pub name: String,
+    pub desired: u32,
+    pub ready: u32,
+    pub up_to_date: u32,
+    pub available: u32,
+    /// Images of the pod template, comma-separated.
+    pub images: String,
+    /// Creation time in Unix seconds.
+    pub created: Option<i64>,
+}
+
+impl KubeDeploymentInfo {
+    /// "2/3", as in the READY column of `kubectl get deployments`.
+    pub fn ready_label(&self) -> String {
+        format!("{}/{}", self.ready, self.desired)
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.ready < self.desired
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KubePodInfo {
+    pub namespace: String,
+    pub name: String,
+    /// The deployment whose ReplicaSet owns the pod.
+    pub deployment: Option<String>,
+    /// As in the STATUS column of `kubectl get pods`: the phase, or why a
+    /// container waits or ended, e.g. "CrashLoopBackOff".
+    pub status: String,
+    pub ready_containers: u32,
+    pub containers: Vec<String>,
+    pub restarts: u32,
+    pub node: String,
+    /// Creation time in Unix seconds.
+    pub created: Option<i64>,
+}
+
+impl KubePodInfo {
+    /// "1/2", as in the READY column of `kubectl get pods`.
+    pub fn ready_label(&self) -> String {
+        format!("{}/{}", self.ready_containers, self.containers.len())
+    }
+
+    /// Running with every container ready, or finished successfully.
+    pub fn is_healthy(&self) -> bool {
+        match self.status.as_str() {
+            "Running" => self.ready_containers as usize == self.containers.len(),
+            "Succeeded" | "Completed" => true,
+            _ => false,
+        }
+    }
+}
+
+/// A line of the Kubernetes tab; deployments and pods are indices into the
+/// monitor's lists.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KubeRow {
+    Namespace(String),
+    Deployment(usize),
+    Pod(usize),
+}
+
+/// The rows to draw: each namespace, and unless it is collapsed its
+/// deployments, each followed by its pods, then the pods no listed
+/// deployment owns.
+pub fn kube_rows(
+    namespaces: &[String],
+    deployments: &[KubeDeploymentInfo],
+    pods: &[KubePodInfo],
+    collapsed: &HashSet<String>,
+) -> Vec<KubeRow> {
+    let mut rows = Vec::new();
+    for ns in namespaces {
+        rows.push(KubeRow::Namespace(ns.clone()));
+        if collapsed.contains(ns) {
+            continue;
+        }
+        let in_ns = |p: &KubePodInfo| p.namespace == *ns;
+        for (d, deployment) in deployments.iter().enumerate().filter(|(_, d)| d.namespace == *ns) {
+            rows.push(KubeRow::Deployment(d));
+            rows.extend(
+                pods.iter()
+                    .enumerate()
+                    .filter(|p| in_ns(p.1) && p.1.deployment.as_deref() == Some(deployment.name.as_str()))
+                    .map(|(i, _)| KubeRow::Pod(i)),
+            );
+        }
+        let owned = |p: &KubePodInfo| {
+            deployments.iter().any(|d| d.namespace == *ns && p.deployment.as_deref() == Some(d.name.as_str()))
+        };
+        rows.extend(
+            pods.iter()
+                .enumerate()
+                .filter(|p| in_ns(p.1) && !owned(p.1))
+                .map(|(i, _)| KubeRow::Pod(i)),
+        );
+    }
+    rows
+}
+
+/// UI state for the Kubernetes tab.
+#[derive(Clone, Debug, Default)]
+pub struct KubeUIState {
+    pub selected_index: usize,
+    /// Namespaces folded to their header row.
+    pub collapsed: HashSet<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pods_are_listed_under_their_deployment_and_namespace() {
+        let deployment = |ns: &str, name: &str| KubeDeploymentInfo { namespace: ns.into(), name: name.into(), ..Default::default() };
+        let pod = |ns: &str, name: &str, owner: Option<&str>| KubePodInfo {
+            namespace: ns.into(),
+            name: name.into(),
+            deployment: owner.map(String::from),
+            ..Default::default()
+        };
+        let namespaces = vec!["default".to_string(), "shop".to_string()];
+        let deployments = vec![deployment("shop", "api"), deployment("default", "web")];
+        let pods = vec![
+            pod("shop", "api-7d4b9-x2k", Some("api")),
+            pod("default", "debug", None),
+            pod("default", "web-5c8f-q1", Some("web")),
+            // A deployment of that name lives in another namespace only
+            pod("shop", "web-1", Some("web")),
+        ];
+        let mut collapsed = HashSet::new();
+        assert_eq!(
+            kube_rows(&namespaces, &deployments, &pods, &collapsed),
+            vec![
+                KubeRow::Namespace("default".into()),
+                KubeRow::Deployment(1),
+                KubeRow::Pod(2),
+                KubeRow::Pod(1),
+                KubeRow::Namespace("shop".into()),
+                KubeRow::Deployment(0),
+                KubeRow::Pod(0),
+                KubeRow::Pod(3),
+            ]
+        );
+        collapsed.insert("default".to_string());
+        assert_eq!(kube_rows(&namespaces, &deployments, &pods, &collapsed).len(), 5);
+    }
+}
@@ -0,0 +1,124 @@
+// --- Log line cursor shared by the log viewers ---
+
+/// Appended once when a log stream stops.
+pub const LOG_STREAM_ENDED: &str = "[log stream ended]";
+
+/// A selection cursor inside a log viewer, separate from scrolling. Lines
+/// are identified by their absolute number (lines already dropped from the
+/// buffer included), so the cursor stays on its line as new lines arrive.
+#[derive(Clone, Debug, Default)]
+pub struct LogSelection {
+    /// Absolute number of the selected line; None when not selecting.
+    pub line: Option<u64>,
+    /// Show the selected line untruncated in a popup.
+    pub expanded: bool,
+    pub line_numbers: bool,
+    /// Absolute number of the line last copied to the clipboard.
+    pub copied: Option<u64>,
+}
+
+impl LogSelection {
+    pub fn is_active(&self) -> bool {
+        self.line.is_some()
+    }
+
+    /// Position in `display` of the selected line, or of the nearest shown
+    /// line when a search hides it or it has been dropped. `first` is the
+    /// absolute number of the buffer's first line.
+    pub fn position(&self, display: &[usize], first: u64) -> Option<usize> {
+        let line = self.line?;
+        if display.is_empty() {
+            return None;
+        }
+        let idx = line.saturating_sub(first) as usize;
+        Some(match display.binary_search(&idx) {
+            Ok(pos) => pos,
+            Err(pos) => pos.min(display.len() - 1),
+        })
+    }
+
+    /// Start selecting at the bottom line on screen.
+    pub fn start(&mut self, display: &[usize], first: u64, scroll_offset: usize, auto_follow: bool, page: usize) {
+        if display.is_empty() {
+            return;
+        }
+        let start = visible_start(display.len(), page, auto_follow, scroll_offset);
+        let bottom = (start + page).min(display.len()) - 1;
+        self.line = Some(first + display[bottom] as u64);
+    }
+
+    pub fn stop(&mut self) {
+        self.line = None;
+        self.expanded = false;
+    }
+
+    /// Move the cursor `delta` lines (negative is up) and scroll so it stays
+    /// on screen. Scrolling pauses following while the cursor is out.
+    pub fn move_by(
+        &mut self,
+        display: &[usize],
+        first: u64,
+        delta: isize,
+        page: usize,
+        scroll_offset: &mut usize,
+        auto_follow: &mut bool,
+    ) {
+        let Some(pos) = self.position(display, first) else {
+            return;
+        };
+        let new = pos.saturating_add_signed(delta).min(display.len() - 1);
+        self.line = Some(first + display[new] as u64);
+        self.copied = None;
+
+        let page = page.max(1);
+        let bottom_start = display.len().saturating_sub(page);
+        let start = visible_start(display.len(), page, *auto_follow, *scroll_offset);
+        *auto_follow = false;
+        if new < start {
+            *scroll_offset = bottom_start - new;
+        } else if new >= start + page {
+            *scroll_offset = bottom_start.saturating_sub(new + 1 - page);
+        } else {
+            *scroll_offset = bottom_start - start;
+        }
+    }
+}
+
+/// Position in the displayed lines of the first one on screen, for a view
+/// `scroll_offset` lines up from the bottom.
+pub fn visible_start(total: usize, page: usize, auto_follow: bool, scroll_offset: usize) -> usize {
+    let bottom_start = total.saturating_sub(page);
+    if auto_follow {
+        bottom_start
+    } else {
+        bottom_start.saturating_sub(scroll_offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_moves_and_keeps_itself_on_screen() {
+        // 10 lines with 95 already dropped, a 4-line page, following
+        let display: Vec<usize> = (0..10).collect();
+        let (mut offset, mut follow) = (0, true);
+        let mut selection = LogSelection::default();
+        selection.start(&display, 95, offset, follow, 4);
+        assert_eq!(selection.line, Some(104));
+
+        selection.move_by(&display, 95, -3, 4, &mut offset, &mut follow);
+        assert_eq!((selection.line, offset, follow), (Some(101), 0, false));
+        selection.move_by(&display, 95, -1, 4, &mut offset, &mut follow);
+        assert_eq!((selection.line, offset), (Some(100), 1));
+        selection.move_by(&display, 95, -50, 4, &mut offset, &mut follow);
+        assert_eq!((selection.line, offset), (Some(95), 6));
+        selection.move_by(&display, 95, 9, 4, &mut offset, &mut follow);
+        assert_eq!((selection.line, offset), (Some(104), 0));
+
+        // A search hiding the selected line snaps to the next shown one
+        selection.line = Some(101);
+        assert_eq!(selection.position(&[2, 8], 95), Some(1));
+    }
+}
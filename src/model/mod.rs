@@ -1,20 +1,37 @@
 // Re-export all model types from submodules for backward compatibility.
 
-pub use app::AppView;
+pub use actions::{actions_for, Action, Selection, ACTIONS};
+pub use app::{AppView, Freshness};
 pub use docker::{
-    ContainerUIState, DockerContainerInfo, LogViewState, MultiLogLine, MultiLogViewState,
+    container_order, container_rows, group_containers, ContainerGroupInfo, ContainerGrouping,
+    ContainerRow, ContainerSort, ContainerStats, ContainerStatsHistory, DaemonHealth, DaemonLatency, LabelEdit, LabelMatcher, PublishedPort, format_label_filter,
+    parse_label_filter, valid_container_name, ContainerUIState, DockerContainerInfo, LogViewState, MultiLogLine,
+    MultiLogViewState,
 };
+pub use k8s::{kube_rows, KubeDeploymentInfo, KubePodInfo, KubeRow, KubeUIState};
+pub use graph::{GraphMetric, GraphViewState, GRAPH_RANGES};
+pub use logs::{visible_start, LogSelection, LOG_STREAM_ENDED};
 pub use swarm::{
-    ServiceLogState, SwarmClusterInfo, SwarmMode, SwarmNodeInfo, SwarmServiceInfo,
-    SwarmStackInfo, SwarmTaskInfo, SwarmUIState, SwarmViewLevel,
+    log_line_node, log_timestamp, task_timeline, LogPaneSource, ManagerQuorum, NodeProbe, PlacementConstraint, PortFailure, ServiceLogState, ServiceSpecInfo, SpecChange, SplitLogState, SwarmClusterInfo, SwarmMode, SwarmNodeInfo, SwarmServiceInfo,
+    SwarmStackInfo, SwarmTaskInfo, SwarmUIState, SwarmViewLevel, TaskTimes, TimelineCell, WarningAck,
 };
+pub use watchlist::{WatchItem, WatchKind, Watchlist};
 pub use system::{
-    ContextSwitchInfo, DiskSpaceInfo, FdInfo, MemoryInfo, MonitorData, NetworkInfo,
-    NetworkInterfaceInfo, NetworkProcessInfo, ProcessGroup, ProcessInfo,
-    SocketOverviewInfo, SortColumn, UIState,
+    aggregate_remotes, assess_saturation, daemon_process_name, detect_anomalies, format_link_speed, AnomalyInfo, ArrayActivity, AuthFailures, AuthSummary, CpuBreakdown, Baseline, ExecEvents, ExecSummary, InterruptInfo, InterruptRate, ConntrackInfo, ContextSwitchInfo, DaemonProcessUsage, DiskSpaceInfo, FdInfo, FdLeak, FdTracker,
+    EntropyInfo, HistorySeries, MetricUnit, InterfaceHistory, LinkInfo, ListenerChange, ListenerInfo, ListenerTracker, RestartTracker, UserUsage, ProcessPriority, MemoryBreakdown, MemoryInfo, MonitorData, NamespaceSocketInfo, NetworkInfo, OomInfo,
+    NetworkInterfaceInfo, NetworkProcessInfo, PressureInfo, ProcessGroup, ProcessInfo,
+    ProcessLimits, ProfileEntry, ProfileReport, ProfileState, ProfileView, ResourceLimit, SyscallStat, TraceReport, TraceState, TraceView,
+    RemoteConnectionInfo, Saturation, SaturationLevel, SelfGuard, SelfUsage, LvmKind, LvmVolumeInfo, MemoryDetail, ProcessGrouping, CpuScale, CheckState, Thresholds, CustomLineOutput, SummaryLine, SharedMemorySegment, SocketOverviewInfo, SortColumn, StorageArrayInfo,
+    SystemPanel, UIState, UpdateStatus,
+    HEAVY_REMOTE_CONNECTIONS,
 };
 
+mod actions;
 mod app;
 mod docker;
+mod graph;
+mod k8s;
+mod logs;
 mod swarm;
 mod system;
+mod watchlist;
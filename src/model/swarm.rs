@@ -1,12 +1,16 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::collections::VecDeque;
+use std::time::Instant;
+
+use super::logs::{visible_start, LogSelection, LOG_STREAM_ENDED};
 
 struct ServiceLogFilterCache {
     line_version: u64,
     query: String,
     filter_errors: bool,
+    node_filter: Option<String>,
     matches: Vec<usize>,
 }
 
@@ -18,7 +22,7 @@ pub enum SwarmMode {
 }
 
 /// Cluster-level overview
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SwarmClusterInfo {
     pub node_id: String,
     pub node_addr: String,
@@ -27,78 +31,436 @@ pub struct SwarmClusterInfo {
     pub nodes_total: u32,
 }
 
-/// A single Swarm node
-#[derive(Clone, Debug, Default, Deserialize)]
+/// A single Swarm node, read from `docker node ls` JSON or, by field name,
+/// from a session recording.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SwarmNodeInfo {
-    #[serde(rename = "ID")]
+    #[serde(rename(deserialize = "ID"), alias = "id")]
     pub id: String,
-    #[serde(rename = "Hostname")]
+    #[serde(rename(deserialize = "Hostname"), alias = "hostname")]
     pub hostname: String,
-    #[serde(rename = "Status")]
+    #[serde(rename(deserialize = "Status"), alias = "status")]
     pub status: String,         // "Ready", "Down"
-    #[serde(rename = "Availability")]
+    #[serde(rename(deserialize = "Availability"), alias = "availability")]
     pub availability: String,   // "Active", "Pause", "Drain"
-    #[serde(rename = "ManagerStatus")]
+    #[serde(rename(deserialize = "ManagerStatus"), alias = "manager_status")]
     #[serde(default)]
     pub manager_status: String, // "Leader", "Reachable", ""
-    #[serde(rename = "EngineVersion")]
+    #[serde(rename(deserialize = "EngineVersion"), alias = "engine_version")]
     #[serde(default)]
     pub engine_version: String,
-    #[serde(rename = "Self")]
+    #[serde(rename(deserialize = "Self"), alias = "is_self")]
     #[serde(default)]
     pub is_self: bool,
     /// IP address from `docker node inspect` (populated after list_nodes).
-    #[serde(skip)]
+    #[serde(default)]
     pub ip_address: String,
+    /// Node labels (`docker node update --label-add`), from inspect.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Engine labels from the node's daemon config, from inspect.
+    #[serde(default)]
+    pub engine_labels: BTreeMap<String, String>,
+    /// Platform, e.g. "linux" and "x86_64", from inspect.
+    #[serde(default)]
+    pub os: String,
+    #[serde(default)]
+    pub arch: String,
+    /// Round trip and loss from this host, absent for the local node and
+    /// until the first probe finishes.
+    #[serde(default)]
+    pub probe: Option<NodeProbe>,
+}
+
+/// Result of probing one node: a few TCP connects to its gossip port. A
+/// refused connection still counts as an answer, only timeouts are lost.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct NodeProbe {
+    pub sent: u32,
+    pub received: u32,
+    /// Mean connect time over the answered attempts.
+    pub rtt_ms: Option<f64>,
+    /// Swarm ports that did not answer the way an open port does.
+    pub failed_ports: Vec<PortFailure>,
+}
+
+/// A required Swarm port found closed on a peer, e.g. "2377/tcp refused".
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PortFailure {
+    pub port: u16,
+    pub proto: String,
+    /// "refused", "timeout", "unreachable" or, for UDP, "closed".
+    pub reason: String,
+}
+
+impl std::fmt::Display for PortFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} {}", self.port, self.proto, self.reason)
+    }
+}
+
+impl NodeProbe {
+    pub fn loss_percent(&self) -> u32 {
+        if self.sent == 0 {
+            return 0;
+        }
+        (self.sent - self.received) * 100 / self.sent
+    }
+
+    /// "0.4ms 0%", or "timeout" when nothing answered.
+    pub fn label(&self) -> String {
+        match self.rtt_ms {
+            Some(rtt) if rtt < 10.0 => format!("{:.1}ms {}%", rtt, self.loss_percent()),
+            Some(rtt) => format!("{:.0}ms {}%", rtt, self.loss_percent()),
+            None => "timeout".to_string(),
+        }
+    }
+}
+
+/// Raft quorum among the managers listed by `docker node ls`. Swarm keeps
+/// scheduling and accepting changes only while a majority of managers is
+/// reachable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManagerQuorum {
+    pub total: usize,
+    pub reachable: usize,
+    /// Hostnames of managers not reachable from the leader.
+    pub unreachable: Vec<String>,
+}
+
+impl ManagerQuorum {
+    /// None when the list has no managers (not run from a manager).
+    pub fn from_nodes(nodes: &[SwarmNodeInfo]) -> Option<Self> {
+        let managers: Vec<&SwarmNodeInfo> = nodes.iter().filter(|n| !n.manager_status.is_empty()).collect();
+        if managers.is_empty() {
+            return None;
+        }
+        let unreachable: Vec<String> = managers
+            .iter()
+            .filter(|n| !matches!(n.manager_status.as_str(), "Leader" | "Reachable"))
+            .map(|n| n.hostname.clone())
+            .collect();
+        Some(Self {
+            total: managers.len(),
+            reachable: managers.len() - unreachable.len(),
+            unreachable,
+        })
+    }
+
+    /// Managers that must be reachable to keep quorum.
+    pub fn needed(&self) -> usize {
+        self.total / 2 + 1
+    }
+
+    pub fn has_quorum(&self) -> bool {
+        self.reachable >= self.needed()
+    }
+
+    /// How many more managers can be lost before quorum is.
+    pub fn tolerates(&self) -> usize {
+        self.reachable.saturating_sub(self.needed())
+    }
+
+    /// One failure away from losing quorum. A lone manager is left to the
+    /// LOW MANAGERS check.
+    pub fn at_risk(&self) -> bool {
+        self.has_quorum() && self.tolerates() == 0 && self.total > 1
+    }
+
+    /// Quorum lost, or one failure away from it.
+    pub fn warning(&self) -> Option<String> {
+        let down = if self.unreachable.is_empty() {
+            String::new()
+        } else {
+            format!(" (unreachable: {})", self.unreachable.join(", "))
+        };
+        if !self.has_quorum() {
+            Some(format!(
+                "QUORUM LOST: {} of {} managers reachable, {} needed. Services cannot be scheduled or changed until a majority is back{}",
+                self.reachable,
+                self.total,
+                self.needed(),
+                down
+            ))
+        } else if self.at_risk() {
+            Some(format!(
+                "QUORUM AT RISK: {} of {} managers reachable. Losing one more stops cluster management{}",
+                self.reachable, self.total, down
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// One service placement constraint, e.g. `node.labels.zone==east` or
+/// `node.role!=manager`. Swarm compares values case-insensitively.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlacementConstraint {
+    pub key: String,
+    pub value: String,
+    /// `==` rather than `!=`.
+    pub equal: bool,
+}
+
+impl PlacementConstraint {
+    pub fn parse(expr: &str) -> Option<Self> {
+        let (key, value, equal) = match expr.split_once("!=") {
+            Some((key, value)) => (key, value, false),
+            None => {
+                let (key, value) = expr.split_once("==")?;
+                (key, value, true)
+            }
+        };
+        Some(Self {
+            key: key.trim().to_string(),
+            value: value.trim().to_string(),
+            equal,
+        })
+    }
+
+    /// Whether `node` satisfies the constraint, or None for a key sitrep
+    /// cannot evaluate. A label the node lacks never equals the value.
+    pub fn matches(&self, node: &SwarmNodeInfo) -> Option<bool> {
+        let role = if node.manager_status.is_empty() { "worker" } else { "manager" };
+        let actual = match self.key.as_str() {
+            "node.id" => Some(node.id.as_str()),
+            "node.hostname" => Some(node.hostname.as_str()),
+            "node.role" => Some(role),
+            "node.platform.os" => Some(node.os.as_str()),
+            "node.platform.arch" => Some(node.arch.as_str()),
+            key => {
+                if let Some(label) = key.strip_prefix("node.labels.") {
+                    node.labels.get(label).map(String::as_str)
+                } else if let Some(label) = key.strip_prefix("engine.labels.") {
+                    node.engine_labels.get(label).map(String::as_str)
+                } else {
+                    return None;
+                }
+            }
+        };
+        let same = actual.is_some_and(|actual| actual.eq_ignore_ascii_case(&self.value));
+        Some(same == self.equal)
+    }
+}
+
+impl std::fmt::Display for PlacementConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.key, if self.equal { "==" } else { "!=" }, self.value)
+    }
+}
+
+/// One field that differs between a service's `PreviousSpec` and `Spec`.
+/// Paths are dotted, with `TaskTemplate.` dropped and env vars keyed by
+/// name, e.g. "ContainerSpec.Env.LOG_LEVEL".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpecChange {
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// What `docker service inspect` says about the service in task view.
+#[derive(Clone, Debug, Default)]
+pub struct ServiceSpecInfo {
+    pub constraints: Vec<PlacementConstraint>,
+    /// None when the service was never updated (no `PreviousSpec`).
+    pub changes: Option<Vec<SpecChange>>,
+    /// State and message of the last update, e.g. "rollback_completed: ...".
+    pub update_status: Option<String>,
+}
+
+impl ServiceSpecInfo {
+    /// Build from one element of `docker service inspect` output.
+    pub fn from_inspect(inspect: &serde_json::Value) -> Self {
+        let spec = &inspect["Spec"];
+        let constraints = spec["TaskTemplate"]["Placement"]["Constraints"]
+            .as_array()
+            .map(|exprs| {
+                exprs
+                    .iter()
+                    .filter_map(|e| e.as_str().and_then(PlacementConstraint::parse))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let previous = &inspect["PreviousSpec"];
+        let changes = (!previous.is_null()).then(|| diff_specs(previous, spec));
+        let status = &inspect["UpdateStatus"];
+        let update_status = status["State"].as_str().map(|state| match status["Message"].as_str() {
+            Some(message) if !message.is_empty() => format!("{}: {}", state, message),
+            _ => state.to_string(),
+        });
+        Self {
+            constraints,
+            changes,
+            update_status,
+        }
+    }
 }
 
-/// A Swarm service
-#[derive(Clone, Debug, Default, Deserialize)]
+/// Fields that differ between two service specs, sorted by path.
+pub fn diff_specs(previous: &serde_json::Value, current: &serde_json::Value) -> Vec<SpecChange> {
+    let (mut before, mut after) = (BTreeMap::new(), BTreeMap::new());
+    flatten_spec("", previous, &mut before);
+    flatten_spec("", current, &mut after);
+    let paths: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    paths
+        .into_iter()
+        .filter(|path| before.get(*path) != after.get(*path))
+        .map(|path| SpecChange {
+            path: path.clone(),
+            before: before.get(path).cloned(),
+            after: after.get(path).cloned(),
+        })
+        .collect()
+}
+
+/// Flatten a spec into dotted paths. Env lists are keyed by variable name so
+/// one changed variable is one change; other lists compare as a whole.
+fn flatten_spec(path: &str, value: &serde_json::Value, out: &mut BTreeMap<String, String>) {
+    use serde_json::Value;
+    let join = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+    match value {
+        Value::Null => {}
+        Value::Object(map) => {
+            for (key, value) in map {
+                let child = if path.is_empty() && key == "TaskTemplate" { String::new() } else { join(key) };
+                flatten_spec(&child, value, out);
+            }
+        }
+        Value::Array(items) if path.ends_with("Env") => {
+            for item in items.iter().filter_map(Value::as_str) {
+                let (name, val) = item.split_once('=').unwrap_or((item, ""));
+                out.insert(join(name), val.to_string());
+            }
+        }
+        Value::String(s) => {
+            out.insert(path.to_string(), s.clone());
+        }
+        other => {
+            out.insert(path.to_string(), other.to_string());
+        }
+    }
+}
+
+/// When a task was created and last changed state, from `docker inspect`
+/// on the task. Unix seconds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskTimes {
+    pub created: i64,
+    pub updated: i64,
+    /// Lowercase task state, e.g. "running", "failed", "shutdown".
+    pub state: String,
+}
+
+impl TaskTimes {
+    /// Whether the task has stopped for good, so `updated` is when it ended.
+    pub fn has_ended(&self) -> bool {
+        matches!(self.state.as_str(), "complete" | "shutdown" | "failed" | "rejected" | "orphaned" | "remove")
+    }
+
+    pub fn has_failed(&self) -> bool {
+        matches!(self.state.as_str(), "failed" | "rejected")
+    }
+}
+
+/// One column of a task timeline row, ordered so the most telling event in
+/// a column wins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimelineCell {
+    Idle,
+    Running,
+    Stopped,
+    Started,
+    Failed,
+}
+
+/// One row per task slot (e.g. "api.1", history included) over the last
+/// `window` seconds before `now`, `width` columns wide. Tasks without times
+/// are left out.
+pub fn task_timeline(
+    tasks: &[SwarmTaskInfo],
+    times: &HashMap<String, TaskTimes>,
+    now: i64,
+    window: i64,
+    width: usize,
+) -> Vec<(String, Vec<TimelineCell>)> {
+    let start = now - window;
+    let column = |t: i64| (((t - start) * width as i64) / window.max(1)).clamp(0, width as i64 - 1) as usize;
+    let mut rows: BTreeMap<&str, Vec<TimelineCell>> = BTreeMap::new();
+    for task in tasks {
+        let Some(times) = times.get(&task.id) else { continue };
+        let end = if times.has_ended() { times.updated } else { now };
+        if width == 0 || end < start {
+            continue;
+        }
+        let row = rows.entry(task.name.as_str()).or_insert_with(|| vec![TimelineCell::Idle; width]);
+        let mut mark = |col: usize, cell: TimelineCell| row[col] = row[col].max(cell);
+        for col in column(times.created)..=column(end) {
+            mark(col, TimelineCell::Running);
+        }
+        if times.created >= start {
+            mark(column(times.created), TimelineCell::Started);
+        }
+        if times.has_failed() {
+            mark(column(end), TimelineCell::Failed);
+        } else if times.has_ended() {
+            mark(column(end), TimelineCell::Stopped);
+        }
+    }
+    rows.into_iter().map(|(name, cells)| (name.to_string(), cells)).collect()
+}
+
+/// A Swarm service, read like a node.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SwarmServiceInfo {
-    #[serde(rename = "ID")]
+    #[serde(rename(deserialize = "ID"), alias = "id")]
     pub id: String,
-    #[serde(rename = "Name")]
+    #[serde(rename(deserialize = "Name"), alias = "name")]
     pub name: String,
-    #[serde(rename = "Mode")]
+    #[serde(rename(deserialize = "Mode"), alias = "mode")]
     #[serde(default)]
     pub mode: String,           // "replicated", "global"
-    #[serde(rename = "Replicas")]
+    #[serde(rename(deserialize = "Replicas"), alias = "replicas")]
     #[serde(default)]
     pub replicas: String,       // "3/3"
-    #[serde(rename = "Image")]
+    #[serde(rename(deserialize = "Image"), alias = "image")]
     #[serde(default)]
     pub image: String,
-    #[serde(rename = "Ports")]
+    #[serde(rename(deserialize = "Ports"), alias = "ports")]
     #[serde(default)]
     pub ports: String,
     // Derived: stack name from label com.docker.stack.namespace
-    #[serde(skip)]
+    #[serde(default)]
     pub stack: String,
+    /// Compose file(s) the service says it was deployed from, comma-separated.
+    #[serde(default)]
+    pub config_files: String,
 }
 
-/// A Swarm task (replica of a service)
-#[derive(Clone, Debug, Default, Deserialize)]
+/// A Swarm task (replica of a service), read like a node.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SwarmTaskInfo {
-    #[serde(rename = "ID")]
+    #[serde(rename(deserialize = "ID"), alias = "id")]
     pub id: String,
-    #[serde(rename = "Name")]
+    #[serde(rename(deserialize = "Name"), alias = "name")]
     pub name: String,
-    #[serde(rename = "Image")]
+    #[serde(rename(deserialize = "Image"), alias = "image")]
     #[serde(default)]
     pub image: String,
-    #[serde(rename = "Node")]
+    #[serde(rename(deserialize = "Node"), alias = "node")]
     #[serde(default)]
     pub node: String,
-    #[serde(rename = "DesiredState")]
+    #[serde(rename(deserialize = "DesiredState"), alias = "desired_state")]
     #[serde(default)]
     pub desired_state: String,
-    #[serde(rename = "CurrentState")]
+    #[serde(rename(deserialize = "CurrentState"), alias = "current_state")]
     #[serde(default)]
     pub current_state: String,
-    #[serde(rename = "Error")]
+    #[serde(rename(deserialize = "Error"), alias = "error")]
     #[serde(default)]
     pub error: String,
-    #[serde(rename = "Ports")]
+    #[serde(rename(deserialize = "Ports"), alias = "ports")]
     #[serde(default)]
     pub ports: String,
 }
@@ -123,6 +485,26 @@ pub struct SwarmUIState {
     pub view_level: SwarmViewLevel,
     pub selected_index: usize,
     pub expanded_ids: HashSet<String>,
+    /// Typing into the overview search box.
+    pub search_mode: bool,
+    /// Last search, kept after Enter so `n` can cycle through its matches.
+    pub search_query: String,
+    /// Index of the current match in the search results.
+    pub search_hit: usize,
+    /// Text of the service label prompt while it is open.
+    pub label_input: Option<String>,
+    /// Text of the scheduled restart prompt while it is open.
+    pub schedule_input: Option<String>,
+    /// The warning picker while it is open.
+    pub warning_ack: Option<WarningAck>,
+}
+
+/// Picking a warning to mute or unmute: an index into the shown warnings
+/// followed by the muted ones, and how long to mute it for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WarningAck {
+    pub selected: usize,
+    pub duration: String,
 }
 
 impl Default for SwarmUIState {
@@ -131,6 +513,12 @@ impl Default for SwarmUIState {
             view_level: SwarmViewLevel::Overview,
             selected_index: 0,
             expanded_ids: HashSet::new(),
+            search_mode: false,
+            search_query: String::new(),
+            search_hit: 0,
+            label_input: None,
+            schedule_input: None,
+            warning_ack: None,
         }
     }
 }
@@ -145,7 +533,14 @@ pub struct ServiceLogState {
     pub filter_errors: bool,
     pub search_mode: bool,
     pub search_query: String,
+    /// Show only lines from tasks on this node (the `@node` log prefix).
+    pub node_filter: Option<String>,
     pub truncated_count: u64,
+    pub selection: LogSelection,
+    /// When the newest line arrived.
+    pub last_line_at: Option<Instant>,
+    /// The stream stopped and no more lines will come.
+    pub ended: bool,
     line_version: u64,
     filter_cache: RefCell<Option<ServiceLogFilterCache>>,
 }
@@ -161,7 +556,11 @@ impl ServiceLogState {
             filter_errors: false,
             search_mode: false,
             search_query: String::new(),
+            node_filter: None,
             truncated_count: 0,
+            selection: LogSelection::default(),
+            last_line_at: None,
+            ended: false,
             line_version: 0,
             filter_cache: RefCell::new(None),
         }
@@ -174,12 +573,53 @@ impl ServiceLogState {
         }
         self.lines.push_back(line);
         self.line_version += 1;
+        self.last_line_at = Some(Instant::now());
         *self.filter_cache.borrow_mut() = None;
     }
 
+    /// Mark the stream stopped, with a closing line the first time.
+    pub fn end_stream(&mut self) {
+        if !self.ended {
+            let last_line_at = self.last_line_at;
+            self.push_line(LOG_STREAM_ENDED.to_string());
+            self.last_line_at = last_line_at;
+            self.ended = true;
+        }
+    }
+
+    /// Nodes the buffered lines came from, the local node first.
+    pub fn log_nodes(&self, local: Option<&str>) -> Vec<String> {
+        let mut nodes: Vec<String> = self
+            .lines
+            .iter()
+            .filter_map(|l| log_line_node(l))
+            .collect::<std::collections::BTreeSet<&str>>()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        if let Some(pos) = local.and_then(|local| nodes.iter().position(|n| n == local)) {
+            let local = nodes.remove(pos);
+            nodes.insert(0, local);
+        }
+        nodes
+    }
+
+    /// Step the node filter through all nodes, local first, then back to none.
+    pub fn cycle_node_filter(&mut self, local: Option<&str>) {
+        let nodes = self.log_nodes(local);
+        let next = match self.node_filter {
+            None => 0,
+            Some(ref current) => nodes.iter().position(|n| n == current).map_or(0, |pos| pos + 1),
+        };
+        self.node_filter = nodes.get(next).cloned();
+        self.scroll_offset = 0;
+        self.auto_follow = true;
+    }
+
     pub fn with_filtered_indices<R>(&self, f: impl FnOnce(&[usize]) -> R) -> R {
         let query = self.search_query.to_lowercase();
         let filter_errors = self.filter_errors;
+        let node_filter = self.node_filter.as_deref();
         let mut cache = self.filter_cache.borrow_mut();
         let cache_miss = cache
             .as_ref()
@@ -187,6 +627,7 @@ impl ServiceLogState {
                 cached.line_version != self.line_version
                     || cached.query != query
                     || cached.filter_errors != filter_errors
+                    || cached.node_filter.as_deref() != node_filter
             })
             .unwrap_or(true);
 
@@ -199,6 +640,9 @@ impl ServiceLogState {
                     if filter_errors && !is_error_like(line) {
                         return None;
                     }
+                    if node_filter.is_some() && log_line_node(line) != node_filter {
+                        return None;
+                    }
                     if !query.is_empty() && !line.to_lowercase().contains(&query) {
                         return None;
                     }
@@ -209,6 +653,7 @@ impl ServiceLogState {
                 line_version: self.line_version,
                 query,
                 filter_errors,
+                node_filter: node_filter.map(String::from),
                 matches,
             });
         }
@@ -217,6 +662,104 @@ impl ServiceLogState {
     }
 }
 
+/// A log stream shown in one pane of the split log view.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogPaneSource {
+    Service { id: String, name: String },
+    Container { id: String, name: String },
+}
+
+impl LogPaneSource {
+    pub fn id(&self) -> &str {
+        match self {
+            LogPaneSource::Service { id, .. } | LogPaneSource::Container { id, .. } => id,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            LogPaneSource::Service { name, .. } => format!("service {}", name),
+            LogPaneSource::Container { name, .. } => format!("container {}", name),
+        }
+    }
+}
+
+/// Two log streams followed side by side. While the focused pane is paused
+/// the other one is scrolled to the same moment, so a gateway's request and
+/// the backend's handling of it sit next to each other.
+pub struct SplitLogState {
+    pub sources: [LogPaneSource; 2],
+    pub panes: [ServiceLogState; 2],
+    /// Index of the pane keys act on.
+    pub focus: usize,
+}
+
+impl SplitLogState {
+    pub fn new(first: LogPaneSource, second: LogPaneSource) -> Self {
+        let pane = |source: &LogPaneSource| {
+            let name = match source {
+                LogPaneSource::Service { name, .. } | LogPaneSource::Container { name, .. } => name,
+            };
+            ServiceLogState::new(source.id().to_string(), name.clone())
+        };
+        Self {
+            panes: [pane(&first), pane(&second)],
+            sources: [first, second],
+            focus: 0,
+        }
+    }
+
+    pub fn focused_mut(&mut self) -> &mut ServiceLogState {
+        &mut self.panes[self.focus]
+    }
+
+    /// Line the other pane up with the focused one: follow when it follows,
+    /// otherwise end at the last line no later than the focused pane's bottom
+    /// line on screen.
+    pub fn sync(&mut self, page: usize) {
+        let [a, b] = &mut self.panes;
+        let (focused, other) = if self.focus == 0 { (&*a, b) } else { (&*b, a) };
+        if focused.auto_follow {
+            other.auto_follow = true;
+            other.scroll_offset = 0;
+            return;
+        }
+        let anchor = focused.with_filtered_indices(|display| {
+            if display.is_empty() {
+                return None;
+            }
+            let start = visible_start(display.len(), page, false, focused.scroll_offset);
+            let bottom = (start + page).min(display.len()) - 1;
+            focused.lines.get(display[bottom]).and_then(|l| log_timestamp(l))
+        });
+        let Some(anchor) = anchor else { return };
+        let after = other.with_filtered_indices(|display| {
+            display
+                .iter()
+                .rev()
+                .take_while(|&&i| other.lines.get(i).and_then(|l| log_timestamp(l)).is_none_or(|ts| ts > anchor))
+                .count()
+        });
+        other.auto_follow = false;
+        other.scroll_offset = after;
+    }
+}
+
+/// The node a `docker service logs` line came from, from its task prefix,
+/// e.g. "web1" in "2024-05-02T10:15:30Z shop_api.2.kx81@web1    | ...".
+pub fn log_line_node(line: &str) -> Option<&str> {
+    let (prefix, _) = line.split_once(" | ")?;
+    let task = prefix.split_whitespace().find(|token| token.contains('@'))?;
+    task.rsplit_once('@').map(|(_, node)| node).filter(|n| !n.is_empty())
+}
+
+/// The RFC 3339 timestamp `docker ... logs --timestamps` puts before each
+/// line, e.g. "2024-05-02T10:15:30.123456789Z".
+pub fn log_timestamp(line: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    let token = line.split_whitespace().next()?;
+    chrono::DateTime::parse_from_rfc3339(token).ok()
+}
+
 fn is_error_like(line: &str) -> bool {
     let lower = line.to_lowercase();
     lower.contains("error")
@@ -231,6 +774,146 @@ fn is_error_like(line: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn placement_constraints_match_node_attributes() {
+        let node = SwarmNodeInfo {
+            hostname: "web1".into(),
+            manager_status: "Leader".into(),
+            labels: BTreeMap::from([("zone".to_string(), "east".to_string())]),
+            os: "linux".into(),
+            ..Default::default()
+        };
+        let check = |expr: &str| PlacementConstraint::parse(expr).unwrap().matches(&node);
+        assert_eq!(check("node.labels.zone == East"), Some(true));
+        assert_eq!(check("node.labels.zone!=east"), Some(false));
+        assert_eq!(check("node.labels.ssd==true"), Some(false));
+        assert_eq!(check("node.labels.ssd!=true"), Some(true));
+        assert_eq!(check("node.role==worker"), Some(false));
+        assert_eq!(check("node.platform.os==linux"), Some(true));
+        assert_eq!(check("node.custom==x"), None);
+        assert_eq!(PlacementConstraint::parse("node.role"), None);
+    }
+
+    #[test]
+    fn diffs_service_spec_against_previous() {
+        let inspect = serde_json::json!({
+            "PreviousSpec": {
+                "TaskTemplate": {
+                    "ContainerSpec": { "Image": "api:1.4", "Env": ["LOG_LEVEL=info", "OLD=1"] },
+                    "Resources": { "Limits": { "MemoryBytes": 268435456 } }
+                },
+                "Mode": { "Replicated": { "Replicas": 3 } }
+            },
+            "Spec": {
+                "TaskTemplate": {
+                    "ContainerSpec": { "Image": "api:1.5", "Env": ["LOG_LEVEL=debug", "NEW=2"] },
+                    "Resources": { "Limits": { "MemoryBytes": 268435456 } },
+                    "Placement": { "Constraints": ["node.role==worker"] }
+                },
+                "Mode": { "Replicated": { "Replicas": 3 } }
+            },
+            "UpdateStatus": { "State": "rollback_completed", "Message": "update rolled back" }
+        });
+        let info = ServiceSpecInfo::from_inspect(&inspect);
+        let changes = info.changes.unwrap();
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.before.as_deref(), c.after.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("ContainerSpec.Env.LOG_LEVEL", Some("info"), Some("debug")),
+                ("ContainerSpec.Env.NEW", None, Some("2")),
+                ("ContainerSpec.Env.OLD", Some("1"), None),
+                ("ContainerSpec.Image", Some("api:1.4"), Some("api:1.5")),
+                ("Placement.Constraints", None, Some("[\"node.role==worker\"]")),
+            ]
+        );
+        assert_eq!(info.constraints.len(), 1);
+        assert_eq!(info.update_status.as_deref(), Some("rollback_completed: update rolled back"));
+        assert!(ServiceSpecInfo::from_inspect(&serde_json::json!({ "Spec": {} })).changes.is_none());
+    }
+
+    #[test]
+    fn task_timeline_marks_starts_and_failures() {
+        let task = |id: &str, name: &str| SwarmTaskInfo {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        };
+        let tasks = vec![task("a", "api.1"), task("b", "api.1"), task("c", "api.2")];
+        let times = HashMap::from([
+            ("a".to_string(), TaskTimes { created: 0, updated: 40, state: "failed".into() }),
+            ("b".to_string(), TaskTimes { created: 50, updated: 50, state: "running".into() }),
+            ("c".to_string(), TaskTimes { created: -500, updated: -10, state: "running".into() }),
+        ]);
+        use TimelineCell::*;
+        let rows = task_timeline(&tasks, &times, 100, 100, 10);
+        assert_eq!(rows[0].0, "api.1");
+        assert_eq!(
+            rows[0].1,
+            vec![Started, Running, Running, Running, Failed, Started, Running, Running, Running, Running]
+        );
+        // Started before the window, still running
+        assert_eq!(rows[1].1, vec![Running; 10]);
+    }
+
+    #[test]
+    fn service_logs_filter_by_origin_node() {
+        let mut state = ServiceLogState::new("svc1".into(), "shop_api".into());
+        state.push_line("2024-05-02T10:15:30Z shop_api.1.kx81@web2    | GET / 200".into());
+        state.push_line("2024-05-02T10:15:31Z shop_api.2.p0q2@web1    | dns lookup failed".into());
+        state.push_line("2024-05-02T10:15:32Z shop_api.1.kx81@web2    | GET /a 200".into());
+        assert_eq!(log_line_node(&state.lines[1]), Some("web1"));
+        assert_eq!(log_line_node("plain | line @x"), None);
+
+        assert_eq!(state.log_nodes(Some("web2")), vec!["web2", "web1"]);
+        state.cycle_node_filter(Some("web2"));
+        assert_eq!(state.node_filter.as_deref(), Some("web2"));
+        assert_eq!(state.with_filtered_indices(|d| d.to_vec()), vec![0, 2]);
+        state.cycle_node_filter(Some("web2"));
+        assert_eq!(state.with_filtered_indices(|d| d.to_vec()), vec![1]);
+        state.cycle_node_filter(Some("web2"));
+        assert_eq!(state.node_filter, None);
+        assert_eq!(state.with_filtered_indices(|d| d.len()), 3);
+    }
+
+    #[test]
+    fn node_probe_reports_rtt_and_loss() {
+        let probe = NodeProbe { sent: 3, received: 2, rtt_ms: Some(0.42), ..Default::default() };
+        assert_eq!((probe.loss_percent(), probe.label()), (33, "0.4ms 33%".to_string()));
+        let far = NodeProbe { sent: 3, received: 3, rtt_ms: Some(41.7), ..Default::default() };
+        assert_eq!(far.label(), "42ms 0%");
+        let lost = NodeProbe { sent: 3, received: 0, rtt_ms: None, ..Default::default() };
+        assert_eq!((lost.loss_percent(), lost.label()), (100, "timeout".to_string()));
+    }
+
+    #[test]
+    fn manager_quorum_warns_when_at_risk_or_lost() {
+        let node = |host: &str, status: &str| SwarmNodeInfo {
+            hostname: host.into(),
+            manager_status: status.into(),
+            ..Default::default()
+        };
+        let mut nodes = vec![node("m1", "Leader"), node("m2", "Reachable"), node("m3", "Reachable"), node("w1", "")];
+        let quorum = ManagerQuorum::from_nodes(&nodes).unwrap();
+        assert_eq!((quorum.total, quorum.needed(), quorum.tolerates()), (3, 2, 1));
+        assert_eq!(quorum.warning(), None);
+
+        nodes[2].manager_status = "Unreachable".into();
+        let warning = ManagerQuorum::from_nodes(&nodes).unwrap().warning().unwrap();
+        assert!(warning.starts_with("QUORUM AT RISK: 2 of 3"), "{}", warning);
+        assert!(warning.ends_with("(unreachable: m3)"));
+
+        nodes[1].manager_status = "Unreachable".into();
+        let quorum = ManagerQuorum::from_nodes(&nodes).unwrap();
+        assert!(!quorum.has_quorum());
+        assert!(quorum.warning().unwrap().starts_with("QUORUM LOST: 1 of 3 managers reachable, 2 needed"));
+
+        assert_eq!(ManagerQuorum::from_nodes(&[node("w1", "")]), None);
+    }
+
     #[test]
     fn service_log_state_push_line_caps_at_10000() {
         let mut state = ServiceLogState::new("svc1".into(), "my-service".into());
@@ -242,6 +925,34 @@ mod tests {
         assert_eq!(state.lines.back(), Some(&"log 10009".to_string()));
     }
 
+    #[test]
+    fn split_logs_line_up_the_other_pane_by_timestamp() {
+        let service = LogPaneSource::Service { id: "s1".into(), name: "gateway".into() };
+        let container = LogPaneSource::Container { id: "c1".into(), name: "api".into() };
+        let mut split = SplitLogState::new(service, container);
+        for (secs, msg) in [(1, "a"), (3, "b"), (5, "c"), (7, "d")] {
+            split.panes[0].push_line(format!("2024-05-02T10:15:0{}Z gateway.1.x@node1    | {}", secs, msg));
+        }
+        for (secs, msg) in [(2, "w"), (4, "x"), (4, "y"), (6, "z")] {
+            split.panes[1].push_line(format!("2024-05-02T10:15:0{}.5+00:00 {}", secs, msg));
+        }
+        split.panes[1].push_line("[log stream ended]".into());
+
+        // Gateway paused with "c" (10:15:05) at the bottom of a 2-line page
+        split.panes[0].auto_follow = false;
+        split.panes[0].scroll_offset = 1;
+        split.sync(2);
+        assert!(!split.panes[1].auto_follow);
+        // "y" (10:15:04.5) ends the other pane; "z" and the end marker are below
+        assert_eq!(split.panes[1].scroll_offset, 2);
+
+        split.panes[0].auto_follow = true;
+        split.sync(2);
+        assert!(split.panes[1].auto_follow);
+        assert_eq!(split.panes[1].scroll_offset, 0);
+        assert!(log_timestamp("[log stream ended]").is_none());
+    }
+
     #[test]
     fn swarm_node_info_deserialize() {
         let json = r#"{"ID":"abc123","Hostname":"node1","Status":"Ready","Availability":"Active","ManagerStatus":"Leader","EngineVersion":"24.0","Self":true}"#;
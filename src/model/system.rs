@@ -1,10 +1,21 @@
+use serde::{Deserialize, Serialize};
 use sysinfo::Pid;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Instant;
 
 // --- Process-level data ---
 
-#[derive(Clone, Debug)]
+/// sysinfo writes a Pid as its number in a string but has no way to read
+/// one back, which a replayed recording needs.
+fn deserialize_pid<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Pid, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    text.parse().map_err(serde::de::Error::custom)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ProcessInfo {
+    #[serde(deserialize_with = "deserialize_pid")]
     pub pid: Pid,
     pub user: String,
     pub cpu: f32,
@@ -16,8 +27,9 @@ pub struct ProcessInfo {
     pub name: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ProcessGroup {
+    #[serde(deserialize_with = "deserialize_pid")]
     pub pid: Pid,
     pub user: String,
     pub cpu: f64,
@@ -31,9 +43,20 @@ pub struct ProcessGroup {
     pub children: Vec<ProcessInfo>,
 }
 
+/// The processes of one user, summed, so a shared host shows whose workload
+/// is behind the load.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct UserUsage {
+    pub user: String,
+    pub processes: usize,
+    /// Percent of one core, as for processes.
+    pub cpu: f64,
+    pub mem: u64,
+}
+
 // --- Diagnostic data structs ---
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct DiskSpaceInfo {
     pub mount_point: String,
     pub total_gb: f64,
@@ -43,52 +66,594 @@ pub struct DiskSpaceInfo {
     pub is_warning: bool,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct MemoryInfo {
     pub total: u64,
     pub used: u64,
     pub available: u64,
     pub swap_total: u64,
     pub swap_used: u64,
+    /// Where the non-application memory is, if the platform reports it.
+    pub breakdown: Option<MemoryBreakdown>,
 }
 
-#[derive(Clone, Debug)]
+/// Kernel buffers, page cache and slab from /proc/meminfo, in bytes. Most of
+/// it is given back under pressure, so a high "used" figure is often cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MemoryBreakdown {
+    pub buffers: u64,
+    /// Page cache, including tmpfs and shared memory.
+    pub cached: u64,
+    pub slab: u64,
+    /// The part of slab the kernel can free (dentry and inode caches).
+    pub slab_reclaimable: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NetworkInterfaceInfo {
     pub name: String,
     pub rx_rate: u64,
     pub tx_rate: u64,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NetworkProcessInfo {
     pub name: String,
     pub bandwidth: u64, // bytes/sec total (rx+tx)
 }
 
-#[derive(Clone, Debug, Default)]
+/// Link state of a network interface (Linux only).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LinkInfo {
+    pub name: String,
+    pub up: bool,
+    /// Negotiated speed; None when the driver doesn't report one.
+    pub speed_mbps: Option<u32>,
+    /// Backed by a physical device rather than a bridge, veth, tunnel, etc.
+    pub physical: bool,
+    pub carrier_changes: u64,
+    /// Seconds since the carrier last changed, if it changed while sitrep was running.
+    pub last_flap_secs: Option<u64>,
+}
+
+impl LinkInfo {
+    /// A carrier change within this many seconds counts as a recent flap.
+    pub const FLAP_WINDOW_SECS: u64 = 600;
+    /// Physical links negotiated below this speed are flagged.
+    pub const MIN_EXPECTED_MBPS: u32 = 1000;
+
+    /// A short warning for a down, flapping, or under-negotiated physical link.
+    pub fn warning(&self) -> Option<String> {
+        if let Some(secs) = self.last_flap_secs.filter(|s| *s < Self::FLAP_WINDOW_SECS) {
+            return Some(format!("flapped {}m ago", secs / 60));
+        }
+        if !self.physical {
+            return None;
+        }
+        if !self.up {
+            return Some("link down".to_string());
+        }
+        match self.speed_mbps {
+            Some(speed) if speed < Self::MIN_EXPECTED_MBPS => {
+                Some(format!("negotiated {}", format_link_speed(speed)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Format a link speed in Mb/s, e.g. 100 → "100M", 2500 → "2.5G".
+pub fn format_link_speed(mbps: u32) -> String {
+    if mbps < 1000 {
+        format!("{}M", mbps)
+    } else if mbps.is_multiple_of(1000) {
+        format!("{}G", mbps / 1000)
+    } else {
+        format!("{:.1}G", mbps as f64 / 1000.0)
+    }
+}
+
+/// Retained (rx, tx) byte rates for one interface, oldest first.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct InterfaceHistory {
+    pub name: String,
+    pub samples: Vec<(u64, u64)>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct NetworkInfo {
     pub interfaces: Vec<NetworkInterfaceInfo>,
+    pub links: Vec<LinkInfo>,
+    /// Rate history for interfaces that have carried traffic, sorted by name.
+    pub history: Vec<InterfaceHistory>,
     pub top_bandwidth_processes: Vec<NetworkProcessInfo>,
     pub established: u32,
     pub time_wait: u32,
     pub close_wait: u32,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct FdInfo {
     pub system_used: u64,
     pub system_max: u64,
     pub top_processes: Vec<(String, u64)>,
+    /// Open FDs of every readable process as (pid, name, count). Linux only.
+    pub per_process: Vec<(u32, String, u64)>,
+    /// Processes whose FD count has only grown across the tracking window.
+    pub leaks: Vec<FdLeak>,
+}
+
+/// Failed SSH logins from the auth log, summarised over AuthFailures::WINDOW.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AuthSummary {
+    /// The log followed, e.g. "/var/log/auth.log" or "journald".
+    pub source: Option<String>,
+    /// Why no log is followed.
+    pub error: Option<String>,
+    pub last_minute: usize,
+    pub window_total: usize,
+    /// Remote addresses by failures in the window, most first.
+    pub top_offenders: Vec<(String, usize)>,
+}
+
+impl AuthSummary {
+    /// Failures per minute that count as an attack in progress.
+    pub const SPIKE_PER_MIN: usize = 20;
+
+    pub fn is_spiking(&self) -> bool {
+        self.last_minute >= Self::SPIKE_PER_MIN
+    }
+}
+
+/// Process starts seen by the exec snoop, summarised over ExecEvents::WINDOW.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecSummary {
+    /// How starts are seen: "bpftrace" or "ps every 250ms".
+    pub source: Option<String>,
+    /// Why the snoop is not running.
+    pub error: Option<String>,
+    /// Starts per second over ExecEvents::RATE_WINDOW.
+    pub per_sec: f64,
+    pub window_total: usize,
+    /// Command names by starts in the window, most first.
+    pub top_commands: Vec<(String, usize)>,
+}
+
+/// Recent process starts with their command name, oldest first.
+#[derive(Default)]
+pub struct ExecEvents {
+    events: VecDeque<(Instant, String)>,
+}
+
+impl ExecEvents {
+    pub const WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+    pub const RATE_WINDOW: std::time::Duration = std::time::Duration::from_secs(10);
+    /// Commands listed in the summary.
+    const TOP: usize = 30;
+
+    pub fn record(&mut self, at: Instant, command: String) {
+        self.events.push_back((at, command));
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Drop starts older than the window and summarise the rest.
+    pub fn summarize(&mut self, now: Instant) -> ExecSummary {
+        while self.events.front().is_some_and(|(at, _)| now.duration_since(*at) > Self::WINDOW) {
+            self.events.pop_front();
+        }
+        let recent = self.events.iter().filter(|(at, _)| now.duration_since(*at) <= Self::RATE_WINDOW).count();
+        let mut by_command: HashMap<&str, usize> = HashMap::new();
+        for (_, command) in &self.events {
+            *by_command.entry(command).or_default() += 1;
+        }
+        let mut top_commands: Vec<(String, usize)> =
+            by_command.into_iter().map(|(command, n)| (command.to_string(), n)).collect();
+        top_commands.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_commands.truncate(Self::TOP);
+        ExecSummary {
+            per_sec: recent as f64 / Self::RATE_WINDOW.as_secs_f64(),
+            window_total: self.events.len(),
+            top_commands,
+            ..Default::default()
+        }
+    }
+}
+
+/// sitrep's own footprint, measured each snapshot.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct SelfUsage {
+    pub pid: u32,
+    /// Percent of one core.
+    pub cpu_percent: f64,
+    pub rss_bytes: u64,
+    /// None where the platform doesn't list a process's threads.
+    pub threads: Option<usize>,
+    /// Live child processes (log tails, docker CLI calls, auth log follower)
+    /// as (name, count), most first.
+    pub children: Vec<(String, usize)>,
+    /// CPU of the child processes, percent of one core.
+    pub children_cpu_percent: f64,
+}
+
+impl SelfUsage {
+    pub fn child_count(&self) -> usize {
+        self.children.iter().map(|(_, n)| n).sum()
+    }
+
+    /// CPU of sitrep and its children together, compared against the budget.
+    pub fn total_cpu_percent(&self) -> f64 {
+        self.cpu_percent + self.children_cpu_percent
+    }
+}
+
+/// The Docker daemon processes on this host: dockerd, containerd and the
+/// per-container shims, each summed over its instances.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct DaemonProcessUsage {
+    pub name: String,
+    pub count: usize,
+    /// Percent of one core.
+    pub cpu_percent: f64,
+    pub rss_bytes: u64,
+    /// None where the platform doesn't list a process's threads.
+    pub threads: Option<usize>,
+}
+
+/// Which daemon process a process name belongs to, shims grouped as one.
+pub fn daemon_process_name(name: &str) -> Option<&'static str> {
+    match name {
+        "dockerd" => Some("dockerd"),
+        "containerd" => Some("containerd"),
+        // "containerd-shim-runc-v2", truncated to 15 bytes in /proc/<pid>/stat
+        _ if name.starts_with("containerd-shim") => Some("containerd-shim"),
+        _ => None,
+    }
+}
+
+/// Stretches the collector interval while sitrep itself uses more CPU than
+/// its budget, so the tool doesn't add to the load it is diagnosing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelfGuard {
+    /// Percent of one core; 0 disables backing off.
+    pub cpu_budget: f64,
+    /// Current interval multiplier, 1 when not backing off.
+    pub factor: u32,
+}
+
+impl Default for SelfGuard {
+    fn default() -> Self {
+        Self {
+            cpu_budget: 0.0,
+            factor: 1,
+        }
+    }
+}
+
+impl SelfGuard {
+    pub const MAX_FACTOR: u32 = 8;
+
+    /// Double the interval while over budget and halve it again once usage
+    /// falls below half the budget. Returns true when the factor changed.
+    pub fn observe(&mut self, cpu_percent: f64) -> bool {
+        let factor = if self.cpu_budget <= 0.0 {
+            1
+        } else if cpu_percent > self.cpu_budget {
+            (self.factor * 2).min(Self::MAX_FACTOR)
+        } else if cpu_percent < self.cpu_budget / 2.0 {
+            (self.factor / 2).max(1)
+        } else {
+            self.factor
+        };
+        let changed = factor != self.factor;
+        self.factor = factor;
+        changed
+    }
+
+    pub fn backing_off(&self) -> bool {
+        self.factor > 1
+    }
+}
+
+/// Recent failed logins with their remote address, oldest first.
+#[derive(Default)]
+pub struct AuthFailures {
+    events: VecDeque<(Instant, String)>,
+}
+
+impl AuthFailures {
+    pub const WINDOW: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+    /// Offenders listed in the summary.
+    const TOP: usize = 20;
+
+    pub fn record(&mut self, at: Instant, remote: String) {
+        self.events.push_back((at, remote));
+    }
+
+    /// Drop failures older than the window and summarise the rest.
+    pub fn summarize(&mut self, now: Instant) -> AuthSummary {
+        while self.events.front().is_some_and(|(at, _)| now.duration_since(*at) > Self::WINDOW) {
+            self.events.pop_front();
+        }
+        let minute = std::time::Duration::from_secs(60);
+        let mut by_remote: HashMap<&str, usize> = HashMap::new();
+        for (_, remote) in &self.events {
+            *by_remote.entry(remote).or_default() += 1;
+        }
+        let mut top_offenders: Vec<(String, usize)> =
+            by_remote.into_iter().map(|(remote, n)| (remote.to_string(), n)).collect();
+        top_offenders.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_offenders.truncate(Self::TOP);
+        AuthSummary {
+            last_minute: self.events.iter().filter(|(at, _)| now.duration_since(*at) <= minute).count(),
+            window_total: self.events.len(),
+            top_offenders,
+            ..Default::default()
+        }
+    }
+}
+
+/// Pending package updates and reboot state, checked on a slow cadence.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UpdateStatus {
+    /// "apt", "dnf" or "brew"; None when no supported manager answered.
+    pub manager: Option<&'static str>,
+    pub pending: usize,
+    /// None when the manager cannot tell security updates apart.
+    pub security: Option<usize>,
+    /// Installed kernel newer than the running one.
+    pub newer_kernel: Option<String>,
+    /// /var/run/reboot-required exists (Debian/Ubuntu).
+    pub reboot_flagged: bool,
+}
+
+impl UpdateStatus {
+    pub fn needs_reboot(&self) -> bool {
+        self.reboot_flagged || self.newer_kernel.is_some()
+    }
+}
+
+/// A soft/hard resource limit pair; None means unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ResourceLimit {
+    pub soft: Option<u64>,
+    pub hard: Option<u64>,
+}
+
+/// The /proc/[pid]/limits values that processes run into in practice.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProcessLimits {
+    pub nofile: ResourceLimit,
+    /// Counts threads of the process's real UID, not just this process.
+    pub nproc: ResourceLimit,
+    /// Bytes.
+    pub memlock: ResourceLimit,
+    /// Bytes currently locked (VmLck).
+    pub locked_bytes: u64,
+}
+
+/// How much CPU a process is allowed to claim: its nice value and the
+/// `cpu.weight` of its cgroup (cgroup v2; 100 is the default share).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProcessPriority {
+    pub nice: i32,
+    pub cpu_weight: Option<u64>,
+}
+
+impl ProcessPriority {
+    /// Disk reads plus writes per second above which a low-priority process
+    /// is flagged: nice and cpu.weight don't hold back its I/O.
+    pub const HEAVY_IO: u64 = 20 * 1024 * 1024;
+
+    /// Niced, or in a cgroup with less than the default CPU share: a batch job.
+    pub fn is_low(&self) -> bool {
+        self.nice > 0 || self.cpu_weight.is_some_and(|w| w < 100)
+    }
+
+    /// Not the defaults, so worth showing.
+    pub fn is_adjusted(&self) -> bool {
+        self.nice != 0 || self.cpu_weight.is_some_and(|w| w != 100)
+    }
 }
 
-#[derive(Clone, Debug, Default)]
+/// A System V or POSIX shared memory segment.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SharedMemorySegment {
+    /// "sysv" or "posix".
+    pub kind: String,
+    /// SysV key (hex) and id, or the /dev/shm file name.
+    pub name: String,
+    pub bytes: u64,
+    /// Processes attached (SysV only).
+    pub attached: Option<u32>,
+    /// Creator, e.g. "postgres[812]" (SysV only).
+    pub owner: Option<String>,
+}
+
+/// Where memory that is neither plain anonymous nor page cache goes: shared
+/// memory, the static HugeTLB pool and transparent huge pages. All sizes in kB.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct MemoryDetail {
+    /// tmpfs plus SysV and POSIX shm; reported as both "used" and "cached".
+    pub shmem_kb: u64,
+    pub hugepage_size_kb: u64,
+    pub hugepages_total: u64,
+    pub hugepages_free: u64,
+    /// Promised to a mapping but not yet faulted in.
+    pub hugepages_reserved: u64,
+    pub hugepages_surplus: u64,
+    pub anon_huge_kb: u64,
+    pub shmem_huge_kb: u64,
+    /// THP mode, e.g. "madvise".
+    pub thp_enabled: Option<String>,
+    pub thp_defrag: Option<String>,
+    /// Largest segments first.
+    pub segments: Vec<SharedMemorySegment>,
+}
+
+impl MemoryDetail {
+    /// Segments smaller than this are not listed.
+    pub const MIN_SEGMENT_BYTES: u64 = 1024 * 1024;
+    pub const TOP_SEGMENTS: usize = 20;
+
+    /// The HugeTLB pool is taken out of MemTotal's free pool whether or not
+    /// anything uses it.
+    pub fn hugetlb_kb(&self) -> u64 {
+        self.hugepages_total * self.hugepage_size_kb
+    }
+}
+
+/// A process whose open FD count keeps climbing.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct FdLeak {
+    pub pid: u32,
+    pub name: String,
+    pub count: u64,
+    /// FDs gained since the start of the window.
+    pub growth: u64,
+    pub per_min: f64,
+    /// Soft RLIMIT_NOFILE; None when unknown or unlimited.
+    pub limit: Option<u64>,
+}
+
+impl FdLeak {
+    /// Hitting the limit sooner than this is shown as critical.
+    pub const CRITICAL_SECS: u64 = 600;
+
+    /// Projected seconds until the process hits its FD limit at the current rate.
+    pub fn secs_to_limit(&self) -> Option<u64> {
+        let limit = self.limit?;
+        if self.per_min <= 0.0 {
+            return None;
+        }
+        Some((limit.saturating_sub(self.count) as f64 / self.per_min * 60.0) as u64)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.secs_to_limit().is_some_and(|s| s < Self::CRITICAL_SECS)
+    }
+
+    /// e.g. "nginx[1234] 812 FDs (+40/min), limit 1024, full in ~5m".
+    pub fn summary(&self) -> String {
+        let mut text = format!("{}[{}] {} FDs (+{:.0}/min)", self.name, self.pid, self.count, self.per_min);
+        match (self.limit, self.secs_to_limit()) {
+            (Some(limit), Some(secs)) => {
+                text.push_str(&format!(", limit {}, full in ~{}", limit, format_eta(secs)))
+            }
+            (Some(limit), None) => text.push_str(&format!(", limit {}", limit)),
+            (None, _) => text.push_str(", no limit"),
+        }
+        text
+    }
+}
+
+fn format_eta(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// Per-process FD counts over recent snapshots, for spotting leaks.
+#[derive(Default)]
+pub struct FdTracker {
+    series: HashMap<u32, (String, VecDeque<(Instant, u64)>)>,
+}
+
+impl FdTracker {
+    /// Snapshots kept per process.
+    pub const WINDOW: usize = 60;
+    /// Snapshots needed before a process can be flagged.
+    const MIN_SAMPLES: usize = 10;
+    /// Net growth across the window that counts as a leak.
+    const MIN_GROWTH: u64 = 10;
+
+    /// Record this snapshot's counts and forget processes that exited. A PID
+    /// reused by a different program starts a fresh series.
+    pub fn observe(&mut self, at: Instant, counts: &[(u32, String, u64)]) {
+        let live: HashSet<u32> = counts.iter().map(|(pid, _, _)| *pid).collect();
+        self.series.retain(|pid, _| live.contains(pid));
+        for (pid, name, count) in counts {
+            let (seen_name, samples) = self.series.entry(*pid).or_insert_with(|| (name.clone(), VecDeque::new()));
+            if seen_name != name {
+                *seen_name = name.clone();
+                samples.clear();
+            }
+            samples.push_back((at, *count));
+            if samples.len() > Self::WINDOW {
+                samples.pop_front();
+            }
+        }
+    }
+
+    /// Processes whose count never dropped across a full enough window and
+    /// grew by at least MIN_GROWTH, soonest to hit their limit first.
+    pub fn leaks(&self, mut limit_of: impl FnMut(u32) -> Option<u64>) -> Vec<FdLeak> {
+        let mut leaks: Vec<FdLeak> = self
+            .series
+            .iter()
+            .filter(|(_, (_, samples))| samples.len() >= Self::MIN_SAMPLES)
+            .filter(|(_, (_, samples))| samples.iter().zip(samples.iter().skip(1)).all(|(a, b)| b.1 >= a.1))
+            .filter_map(|(pid, (name, samples))| {
+                let (start, first) = *samples.front()?;
+                let (end, count) = *samples.back()?;
+                let growth = count - first;
+                let mins = end.duration_since(start).as_secs_f64() / 60.0;
+                if growth < Self::MIN_GROWTH || mins <= 0.0 {
+                    return None;
+                }
+                Some(FdLeak {
+                    pid: *pid,
+                    name: name.clone(),
+                    count,
+                    growth,
+                    per_min: growth as f64 / mins,
+                    limit: limit_of(*pid),
+                })
+            })
+            .collect();
+        leaks.sort_by(|a, b| {
+            let eta = |l: &FdLeak| l.secs_to_limit().unwrap_or(u64::MAX);
+            eta(a).cmp(&eta(b)).then_with(|| b.growth.cmp(&a.growth))
+        });
+        leaks
+    }
+}
+
+/// Netfilter connection-tracking table usage (Linux only).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ConntrackInfo {
+    pub count: u64,
+    pub max: u64,
+}
+
+impl ConntrackInfo {
+    /// Usage at or above this percentage is shown as a warning.
+    pub const WARN_PCT: f64 = 80.0;
+    /// Usage at or above this percentage means new connections are about to be dropped.
+    pub const CRITICAL_PCT: f64 = 90.0;
+
+    pub fn usage_pct(&self) -> f64 {
+        if self.max == 0 {
+            0.0
+        } else {
+            self.count as f64 / self.max as f64 * 100.0
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct ContextSwitchInfo {
     pub total_csw: u64,
     pub top_processes: Vec<(String, u64)>,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SocketOverviewInfo {
     pub established: u32,
     pub listen: u32,
@@ -96,10 +661,683 @@ pub struct SocketOverviewInfo {
     pub close_wait: u32,
     pub fin_wait: u32,
     pub top_processes: Vec<(String, u32)>, // (name, connection count)
+    /// ESTABLISHED connections grouped by remote address, busiest first.
+    pub remotes: Vec<RemoteConnectionInfo>,
+    /// TCP sockets in LISTEN state.
+    pub listeners: Vec<ListenerInfo>,
+}
+
+/// A resync, recovery, scrub or resilver in progress.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ArrayActivity {
+    /// e.g. "recovery", "check", "scrub", "resilver".
+    pub operation: String,
+    pub percent: f64,
+}
+
+/// An md array or ZFS pool.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StorageArrayInfo {
+    pub name: String,
+    /// "md" or "zfs".
+    pub kind: String,
+    /// RAID level or top-level vdev type, e.g. "raid5", "mirror".
+    pub level: String,
+    /// md: "active", "inactive" or "degraded"; ZFS: pool state such as "ONLINE".
+    pub state: String,
+    /// Active with every member present, or ONLINE.
+    pub healthy: bool,
+    /// Failed, missing or non-ONLINE members, e.g. "sdb1 failed".
+    pub problem_members: Vec<String>,
+    pub activity: Option<ArrayActivity>,
+}
+
+impl StorageArrayInfo {
+    /// e.g. "md1 raid5 degraded (sdb1 failed) recovery 8.5%".
+    pub fn summary(&self) -> String {
+        let mut text = format!("{} {} {}", self.name, self.level, self.state);
+        if !self.problem_members.is_empty() {
+            text.push_str(&format!(" ({})", self.problem_members.join(", ")));
+        }
+        if let Some(ref activity) = self.activity {
+            text.push_str(&format!(" {} {:.1}%", activity.operation, activity.percent));
+        }
+        text
+    }
+}
+
+/// LVM volumes that can fill up underneath their users.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum LvmKind {
+    /// A thin pool: every thin volume in it fails once data or metadata is full.
+    ThinPool,
+    /// A classic (non-thin) snapshot: invalidated once its COW space is full.
+    Snapshot,
+}
+
+/// An LVM thin pool or classic snapshot and how full it is.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LvmVolumeInfo {
+    pub vg: String,
+    pub name: String,
+    pub kind: LvmKind,
+    /// Data (pool) or COW (snapshot) usage.
+    pub data_pct: f64,
+    /// Thin pools only.
+    pub metadata_pct: Option<f64>,
+    /// Snapshots only: the volume it was taken of.
+    pub origin: Option<String>,
+    /// Snapshot already overflowed and was dropped by LVM.
+    pub invalid: bool,
+}
+
+impl LvmVolumeInfo {
+    pub const WARN_PCT: f64 = 80.0;
+    pub const CRITICAL_PCT: f64 = 95.0;
+
+    /// The fuller of data and metadata.
+    pub fn usage_pct(&self) -> f64 {
+        if self.invalid {
+            return 100.0;
+        }
+        self.data_pct.max(self.metadata_pct.unwrap_or(0.0))
+    }
+
+    /// e.g. "vg0/pool thin data 85% meta 12%" or "vg0/snap snap of root 91%".
+    pub fn summary(&self) -> String {
+        match self.kind {
+            LvmKind::ThinPool => format!(
+                "{}/{} thin data {:.0}% meta {:.0}%",
+                self.vg,
+                self.name,
+                self.data_pct,
+                self.metadata_pct.unwrap_or(0.0)
+            ),
+            LvmKind::Snapshot if self.invalid => format!(
+                "{}/{} snap of {} INVALID",
+                self.vg,
+                self.name,
+                self.origin.as_deref().unwrap_or("?")
+            ),
+            LvmKind::Snapshot => format!(
+                "{}/{} snap of {} {:.0}%",
+                self.vg,
+                self.name,
+                self.origin.as_deref().unwrap_or("?"),
+                self.data_pct
+            ),
+        }
+    }
+}
+
+/// A listening TCP socket.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ListenerInfo {
+    pub address: SocketAddr,
+    /// Owning process, where it can be resolved.
+    pub process: Option<String>,
+}
+
+impl ListenerInfo {
+    /// ":22" for a wildcard bind of either family, "127.0.0.1:5432" otherwise.
+    pub fn label(&self) -> String {
+        if self.address.ip().is_unspecified() {
+            format!(":{}", self.address.port())
+        } else {
+            self.address.to_string()
+        }
+    }
+}
+
+/// A listener that appeared or went away within ListenerTracker::KEEP.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ListenerChange {
+    pub label: String,
+    pub process: Option<String>,
+    pub appeared: bool,
+    pub secs_ago: u64,
+}
+
+impl ListenerChange {
+    /// e.g. "new listener :8080 (python3)" or "listener :5432 (postgres) gone".
+    pub fn summary(&self) -> String {
+        let process = self.process.as_ref().map(|p| format!(" ({})", p)).unwrap_or_default();
+        if self.appeared {
+            format!("new listener {}{}", self.label, process)
+        } else {
+            format!("listener {}{} gone", self.label, process)
+        }
+    }
+}
+
+/// Compares each snapshot's listening sockets with the previous one and
+/// remembers recent changes.
+#[derive(Default)]
+pub struct ListenerTracker {
+    /// Label -> owning process; None until the first snapshot so startup
+    /// does not report every listener as new.
+    known: Option<HashMap<String, Option<String>>>,
+    /// (when, label, process, appeared), oldest first.
+    changes: Vec<(Instant, String, Option<String>, bool)>,
+}
+
+impl ListenerTracker {
+    /// How long a change stays reported.
+    pub const KEEP: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+    pub fn observe(&mut self, now: Instant, listeners: &[ListenerInfo]) -> Vec<ListenerChange> {
+        let current: HashMap<String, Option<String>> =
+            listeners.iter().map(|l| (l.label(), l.process.clone())).collect();
+        if let Some(known) = self.known.take() {
+            let mut changed: Vec<(String, Option<String>, bool)> = current
+                .iter()
+                .filter(|(label, _)| !known.contains_key(*label))
+                .map(|(label, process)| (label.clone(), process.clone(), true))
+                .collect();
+            changed.extend(
+                known
+                    .into_iter()
+                    .filter(|(label, _)| !current.contains_key(label))
+                    .map(|(label, process)| (label, process, false)),
+            );
+            changed.sort();
+            for (label, process, appeared) in changed {
+                // A listener that comes back replaces its "gone" entry, and vice versa
+                self.changes.retain(|(_, l, _, _)| *l != label);
+                self.changes.push((now, label, process, appeared));
+            }
+        }
+        self.changes.retain(|(at, ..)| now.duration_since(*at) < Self::KEEP);
+        self.known = Some(current);
+
+        self.changes
+            .iter()
+            .rev()
+            .map(|(at, label, process, appeared)| ListenerChange {
+                label: label.clone(),
+                process: process.clone(),
+                appeared: *appeared,
+                secs_ago: now.duration_since(*at).as_secs(),
+            })
+            .collect()
+    }
+}
+
+/// Notices a process group that exited and came back under a new PID with
+/// the same name (a restarted service), and a PID now running a different
+/// command, so the averaged stats don't mix two lifetimes.
+#[derive(Default)]
+pub struct RestartTracker {
+    /// Name per group PID in the previous snapshot; None until the first.
+    known: Option<HashMap<Pid, String>>,
+    /// New PID -> (when, old PID), oldest first.
+    restarts: Vec<(Pid, Instant, Pid)>,
+}
+
+impl RestartTracker {
+    /// How long a restarted group keeps its badge.
+    pub const KEEP: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+    /// Compare `groups` with the previous snapshot. Returns the PIDs whose
+    /// earlier samples belong to another lifetime and should be dropped:
+    /// the old PIDs of restarted groups and PIDs reused by a new command.
+    pub fn observe(&mut self, now: Instant, groups: &HashMap<Pid, ProcessGroup>) -> Vec<Pid> {
+        let current: HashMap<Pid, String> = groups.iter().map(|(pid, g)| (*pid, g.name.clone())).collect();
+        let mut stale = Vec::new();
+        if let Some(known) = self.known.take() {
+            let mut gone: Vec<(&Pid, &String)> = known.iter().filter(|(pid, _)| !current.contains_key(*pid)).collect();
+            gone.sort();
+            let mut appeared: Vec<(&Pid, &String)> = current.iter().filter(|(pid, _)| !known.contains_key(*pid)).collect();
+            appeared.sort();
+            for (pid, name) in appeared {
+                if let Some(i) = gone.iter().position(|(_, n)| *n == name) {
+                    let (old, _) = gone.remove(i);
+                    self.restarts.retain(|(new, ..)| new != old);
+                    self.restarts.push((*pid, now, *old));
+                    stale.push(*old);
+                }
+            }
+            for (pid, name) in &current {
+                if known.get(pid).is_some_and(|n| n != name) {
+                    self.restarts.retain(|(new, ..)| new != pid);
+                    stale.push(*pid);
+                }
+            }
+        }
+        self.restarts.retain(|(pid, at, _)| now.duration_since(*at) < Self::KEEP && current.contains_key(pid));
+        self.known = Some(current);
+        stale
+    }
+
+    /// Groups restarted within KEEP: new PID -> old PID.
+    pub fn restarted(&self) -> HashMap<u32, u32> {
+        self.restarts.iter().map(|(new, _, old)| (new.as_u32(), old.as_u32())).collect()
+    }
+
+    /// Forget the last snapshot, when its groups can't be compared with the next.
+    pub fn reset(&mut self) {
+        self.known = None;
+        self.restarts.clear();
+    }
+}
+
+/// A single remote holding at least this many connections is highlighted.
+pub const HEAVY_REMOTE_CONNECTIONS: u32 = 100;
+
+/// Maximum number of remotes kept in [`SocketOverviewInfo::remotes`].
+const MAX_REMOTES: usize = 50;
+
+/// ESTABLISHED connections to one remote address.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RemoteConnectionInfo {
+    pub address: IpAddr,
+    pub connections: u32,
+    /// Distinct remote ports, most used first.
+    pub ports: Vec<u16>,
+}
+
+impl RemoteConnectionInfo {
+    pub fn is_heavy(&self) -> bool {
+        self.connections >= HEAVY_REMOTE_CONNECTIONS
+    }
+}
+
+/// Group remote socket addresses by IP, counting connections and ports.
+/// IPv4-mapped IPv6 addresses are folded into their IPv4 form.
+pub fn aggregate_remotes(remotes: impl IntoIterator<Item = SocketAddr>) -> Vec<RemoteConnectionInfo> {
+    let mut by_ip: HashMap<IpAddr, HashMap<u16, u32>> = HashMap::new();
+    for remote in remotes {
+        let ip = match remote.ip() {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(IpAddr::V6(v6)),
+            v4 => v4,
+        };
+        *by_ip.entry(ip).or_default().entry(remote.port()).or_insert(0) += 1;
+    }
+
+    let mut aggregated: Vec<RemoteConnectionInfo> = by_ip
+        .into_iter()
+        .map(|(address, ports)| {
+            let mut ports: Vec<(u16, u32)> = ports.into_iter().collect();
+            ports.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            RemoteConnectionInfo {
+                address,
+                connections: ports.iter().map(|(_, n)| n).sum(),
+                ports: ports.into_iter().map(|(port, _)| port).collect(),
+            }
+        })
+        .collect();
+    aggregated.sort_by(|a, b| b.connections.cmp(&a.connections).then(a.address.cmp(&b.address)));
+    aggregated.truncate(MAX_REMOTES);
+    aggregated
+}
+
+/// TCP state counts and listening ports inside one network namespace.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NamespaceSocketInfo {
+    pub established: u32,
+    pub listen: u32,
+    pub time_wait: u32,
+    pub close_wait: u32,
+    /// Distinct listening ports, ascending.
+    pub listen_ports: Vec<u16>,
+}
+
+impl NamespaceSocketInfo {
+    /// One-line summary, e.g. "EST 12 LISTEN 2 TW 3 CW 0, listening on 80, 443".
+    pub fn summary(&self) -> String {
+        let mut s = format!(
+            "EST {} LISTEN {} TW {} CW {}",
+            self.established, self.listen, self.time_wait, self.close_wait
+        );
+        if !self.listen_ports.is_empty() {
+            let ports: Vec<String> = self.listen_ports.iter().map(|p| p.to_string()).collect();
+            s.push_str(&format!(", listening on {}", ports.join(", ")));
+        }
+        s
+    }
+}
+
+/// Kernel OOM killer activity (Linux only).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct OomInfo {
+    /// Processes killed by the OOM killer since boot.
+    pub total_kills: u64,
+    /// Seconds since the count last increased, if it did while sitrep was running.
+    pub last_kill_secs: Option<u64>,
+}
+
+impl OomInfo {
+    /// A kill within this many seconds is reported as recent.
+    pub const RECENT_SECS: u64 = 600;
+
+    pub fn recent_kill_secs(&self) -> Option<u64> {
+        self.last_kill_secs.filter(|s| *s < Self::RECENT_SECS)
+    }
+}
+
+/// Available entropy on a kernel old enough (before 5.6) that a starved
+/// pool blocks `/dev/random` readers, stalling TLS handshakes and key
+/// generation until enough has been gathered.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EntropyInfo {
+    /// Bits in the input pool, from /proc/sys/kernel/random/entropy_avail.
+    pub available: u64,
+    /// rngd, haveged or jitterentropy-rngd, if one is running to refill it.
+    pub feeder: Option<String>,
+}
+
+impl EntropyInfo {
+    /// Below this many bits readers of /dev/random start to block.
+    pub const LOW: u64 = 200;
+
+    pub fn is_low(&self) -> bool {
+        self.available < Self::LOW
+    }
+}
+
+/// Interrupts per second from one source, per CPU.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct InterruptRate {
+    /// IRQ number or name ("24", "LOC"), or softirq name ("NET_RX").
+    pub name: String,
+    /// What raises it, e.g. "IR-PCI-MSI 524288-edge eth0-TxRx-0"; empty for softirqs.
+    pub device: String,
+    pub per_cpu: Vec<f64>,
+}
+
+impl InterruptRate {
+    pub fn total(&self) -> f64 {
+        self.per_cpu.iter().sum()
+    }
+
+    /// The CPU taking the most of these interrupts and its share (0-1).
+    pub fn busiest_cpu(&self) -> Option<(usize, f64)> {
+        let total = self.total();
+        if total <= 0.0 {
+            return None;
+        }
+        let (cpu, rate) = self.per_cpu.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1))?;
+        Some((cpu, rate / total))
+    }
+}
+
+/// Hardware interrupt and softirq rates since the previous snapshot (Linux).
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct InterruptInfo {
+    /// Busiest IRQ sources first, at most TOP_IRQS.
+    pub irqs: Vec<InterruptRate>,
+    /// Softirqs in the kernel's order (HI, TIMER, NET_TX, NET_RX, ...).
+    pub softirqs: Vec<InterruptRate>,
+}
+
+impl InterruptInfo {
+    pub const TOP_IRQS: usize = 10;
+    /// NET_RX softirqs per second below which their spread doesn't matter.
+    pub const NET_RX_BUSY: f64 = 1000.0;
+    /// Share of NET_RX on one CPU that counts as all of it.
+    pub const HOTSPOT_SHARE: f64 = 0.9;
+
+    pub fn softirq(&self, name: &str) -> Option<&InterruptRate> {
+        self.softirqs.iter().find(|s| s.name == name)
+    }
+
+    /// (CPU, share, NET_RX/s) when one CPU of several handles (nearly) all
+    /// network receive processing, which then saturates that core while the
+    /// others idle. Usually a NIC with one queue or IRQ affinity left at
+    /// its default; RPS or irqbalance spread it.
+    pub fn network_hotspot(&self) -> Option<(usize, f64, f64)> {
+        let net_rx = self.softirq("NET_RX")?;
+        let total = net_rx.total();
+        if net_rx.per_cpu.len() < 2 || total < Self::NET_RX_BUSY {
+            return None;
+        }
+        let (cpu, share) = net_rx.busiest_cpu()?;
+        (share >= Self::HOTSPOT_SHARE).then_some((cpu, share, total))
+    }
+}
+
+/// Where CPU time went since the previous sample, as a percent of all CPU
+/// time across every core. Nice time counts as user, irq and softirq as system.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct CpuBreakdown {
+    pub user: f64,
+    pub system: f64,
+    /// Idle with disk I/O outstanding.
+    pub iowait: f64,
+    /// Time the hypervisor ran another guest while this VM wanted the CPU.
+    pub steal: f64,
+    pub idle: f64,
+}
+
+/// Kernel pressure stall information (10s "some" averages, percent) and the
+/// run queue. Every field is None where the platform doesn't expose it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PressureInfo {
+    pub cpu: Option<f64>,
+    pub memory: Option<f64>,
+    pub io: Option<f64>,
+    /// Currently runnable tasks.
+    pub runnable: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SaturationLevel {
+    Ok,
+    Pressure,
+    Saturated,
+}
+
+impl SaturationLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            SaturationLevel::Ok => "OK",
+            SaturationLevel::Pressure => "PRESSURE",
+            SaturationLevel::Saturated => "SATURATED",
+        }
+    }
+
+    fn from_thresholds(value: f64, pressure: f64, saturated: f64) -> Self {
+        if value >= saturated {
+            SaturationLevel::Saturated
+        } else if value >= pressure {
+            SaturationLevel::Pressure
+        } else {
+            SaturationLevel::Ok
+        }
+    }
+}
+
+/// Overall saturation verdict with a one-line explanation of the dominant bottleneck.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Saturation {
+    pub level: SaturationLevel,
+    pub reason: String,
+}
+
+/// Combine load trend, run queue and PSI into a single saturation verdict.
+/// Memory and I/O fall back to free memory and disk busy % without PSI.
+pub fn assess_saturation(
+    load_avg: (f64, f64, f64),
+    core_count: f64,
+    pressure: &PressureInfo,
+    memory: &MemoryInfo,
+    disk_busy_pct: f64,
+) -> Saturation {
+    let cores = core_count.max(1.0);
+    let (l1, _, l15) = load_avg;
+    let trend = if l1 > l15 * 1.2 {
+        "rising"
+    } else if l1 < l15 * 0.8 {
+        "falling"
+    } else {
+        "steady"
+    };
+
+    // CPU: load per core, runnable per core, and CPU stall time
+    let mut cpu_level = SaturationLevel::from_thresholds(l1 / cores, 1.0, 2.0);
+    let mut cpu_reason = format!("CPU: load {:.2} on {} cores, {}", l1, cores as u32, trend);
+    if let Some(runnable) = pressure.runnable {
+        cpu_level = cpu_level.max(SaturationLevel::from_thresholds(runnable as f64 / cores, 1.5, 3.0));
+        cpu_reason.push_str(&format!(", {} runnable", runnable));
+    }
+    if let Some(psi) = pressure.cpu {
+        cpu_level = cpu_level.max(SaturationLevel::from_thresholds(psi, 10.0, 50.0));
+        cpu_reason.push_str(&format!(", stalled {:.0}%", psi));
+    }
+
+    let (mem_level, mem_reason) = match pressure.memory {
+        Some(psi) => (
+            SaturationLevel::from_thresholds(psi, 5.0, 25.0),
+            format!("Memory: tasks stalled on memory {:.0}% of the time", psi),
+        ),
+        None => {
+            let available_pct = if memory.total > 0 {
+                memory.available as f64 / memory.total as f64 * 100.0
+            } else {
+                100.0
+            };
+            (
+                SaturationLevel::from_thresholds(100.0 - available_pct, 90.0, 95.0),
+                format!("Memory: only {:.0}% available", available_pct),
+            )
+        }
+    };
+
+    let (io_level, io_reason) = match pressure.io {
+        Some(psi) => (
+            SaturationLevel::from_thresholds(psi, 10.0, 40.0),
+            format!("I/O: tasks stalled on I/O {:.0}% of the time", psi),
+        ),
+        None => (
+            SaturationLevel::from_thresholds(disk_busy_pct, 60.0, 90.0),
+            format!("I/O: disk {:.0}% busy", disk_busy_pct),
+        ),
+    };
+
+    // Highest level wins; on a tie the earlier (CPU, then memory) is reported.
+    let mut verdict = (cpu_level, cpu_reason);
+    for candidate in [(mem_level, mem_reason), (io_level, io_reason)] {
+        if candidate.0 > verdict.0 {
+            verdict = candidate;
+        }
+    }
+    let (level, reason) = verdict;
+    let reason = if level == SaturationLevel::Ok {
+        format!("no bottleneck (load {:.2} on {} cores, {})", l1, cores as u32, trend)
+    } else {
+        reason
+    };
+    Saturation { level, reason }
+}
+
+/// Exponentially weighted moving average of a metric: its recent norm.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Baseline {
+    pub mean: f64,
+    pub samples: u32,
+}
+
+impl Baseline {
+    /// Weight of the newest sample; roughly the last 50 samples (2.5 min at
+    /// the default refresh rate) make up the norm.
+    pub const ALPHA: f64 = 0.04;
+    /// Samples needed before the baseline is trusted.
+    pub const WARMUP: u32 = 20;
+    /// A value this many times its baseline is anomalous.
+    pub const FACTOR: f64 = 3.0;
+
+    pub fn observe(&mut self, value: f64) {
+        self.mean = if self.samples == 0 {
+            value
+        } else {
+            self.mean + Self::ALPHA * (value - self.mean)
+        };
+        self.samples = self.samples.saturating_add(1);
+    }
+
+    /// How many times the baseline `value` is, when that is anomalous.
+    /// `floor` is the smallest absolute excess that counts, so a near-idle
+    /// metric (3 connections against a norm of 0.5) is not flagged.
+    pub fn deviation(&self, value: f64, floor: f64) -> Option<f64> {
+        if self.samples < Self::WARMUP || value - self.mean < floor {
+            return None;
+        }
+        let ratio = value / self.mean.max(f64::EPSILON);
+        (ratio >= Self::FACTOR).then_some(ratio)
+    }
+}
+
+/// A metric that deviates strongly from its baseline.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AnomalyInfo {
+    pub metric: String,
+    pub unit: MetricUnit,
+    pub value: f64,
+    pub baseline: f64,
+    pub ratio: f64,
+}
+
+impl AnomalyInfo {
+    /// e.g. "Established 812 (5.1x baseline 160)".
+    pub fn summary(&self) -> String {
+        let format = |v: f64| match self.unit {
+            MetricUnit::Count if v < 10.0 => format!("{:.2}", v),
+            MetricUnit::Count => format!("{:.0}", v),
+            MetricUnit::Percent => format!("{:.0}%", v),
+            MetricUnit::BytesPerSec => format!("{:.1} MB/s", v / 1_048_576.0),
+        };
+        format!(
+            "{} {} ({:.1}x baseline {})",
+            self.metric,
+            format(self.value),
+            self.ratio,
+            format(self.baseline)
+        )
+    }
+}
+
+/// Compare this snapshot's key metrics with their baselines, then fold the
+/// values into the baselines. Flags values far above the recent norm even
+/// when they are below any absolute threshold.
+pub fn detect_anomalies(baselines: &mut HashMap<&'static str, Baseline>, data: &MonitorData) -> Vec<AnomalyInfo> {
+    let (net_rx, net_tx) = data
+        .network
+        .interfaces
+        .iter()
+        .fold((0u64, 0u64), |(rx, tx), i| (rx + i.rx_rate, tx + i.tx_rate));
+    let sock = &data.socket_overview;
+    // (metric, value, unit, smallest excess over the baseline that counts)
+    let metrics = [
+        ("Load", data.load_avg.0, MetricUnit::Count, data.core_count.max(1.0) * 0.5),
+        ("Established", sock.established as f64, MetricUnit::Count, 50.0),
+        ("TIME_WAIT", sock.time_wait as f64, MetricUnit::Count, 100.0),
+        ("CLOSE_WAIT", sock.close_wait as f64, MetricUnit::Count, 20.0),
+        ("Net RX", net_rx as f64, MetricUnit::BytesPerSec, 1_048_576.0),
+        ("Net TX", net_tx as f64, MetricUnit::BytesPerSec, 1_048_576.0),
+        ("Disk busy", data.disk_busy_pct, MetricUnit::Percent, 20.0),
+        ("Open FDs", data.fd_info.system_used as f64, MetricUnit::Count, 1000.0),
+    ];
+
+    let mut anomalies = Vec::new();
+    for (metric, value, unit, floor) in metrics {
+        let baseline = baselines.entry(metric).or_default();
+        if let Some(ratio) = baseline.deviation(value, floor) {
+            anomalies.push(AnomalyInfo {
+                metric: metric.to_string(),
+                unit,
+                value,
+                baseline: baseline.mean,
+                ratio,
+            });
+        }
+        baseline.observe(value);
+    }
+    anomalies
 }
 
 // --- Aggregated monitor data ---
 
+#[derive(Clone, Default, Deserialize, Serialize)]
 pub struct MonitorData {
     pub time: String,
     pub core_count: f64,
@@ -113,6 +1351,42 @@ pub struct MonitorData {
     #[allow(dead_code)]
     pub context_switches: ContextSwitchInfo,
     pub socket_overview: SocketOverviewInfo,
+    /// None when conntrack is unavailable (non-Linux, module not loaded).
+    pub conntrack: Option<ConntrackInfo>,
+    pub pressure: PressureInfo,
+    /// None before a second sample, or where the platform has no /proc/stat.
+    pub cpu_breakdown: Option<CpuBreakdown>,
+    pub oom: Option<OomInfo>,
+    /// None on kernels from 5.6, where a low pool no longer blocks, and off Linux.
+    pub entropy: Option<EntropyInfo>,
+    /// None before a second sample, or where the platform has no /proc/interrupts.
+    pub interrupts: Option<InterruptInfo>,
+    /// Metrics far above their recent baseline.
+    pub anomalies: Vec<AnomalyInfo>,
+    /// Resource limits of the expanded process groups, by PID.
+    pub process_limits: HashMap<u32, ProcessLimits>,
+    /// Nice value and cgroup CPU weight of the listed process groups, by PID.
+    pub process_priorities: HashMap<u32, ProcessPriority>,
+    /// Short ID of the container each listed process runs in, by PID, from
+    /// its cgroup. Processes outside containers are absent.
+    pub process_containers: HashMap<u32, String>,
+    /// CPU, memory and process count per user, busiest first.
+    pub user_usage: Vec<UserUsage>,
+    /// Process groups that came back under a new PID within
+    /// `RestartTracker::KEEP`: new PID -> old PID.
+    pub restarted_processes: HashMap<u32, u32>,
+    /// Listening sockets that appeared or disappeared recently, newest first.
+    pub listener_changes: Vec<ListenerChange>,
+    /// md arrays and ZFS pools.
+    pub storage_arrays: Vec<StorageArrayInfo>,
+    /// LVM thin pools and classic snapshots, fullest first.
+    pub lvm_volumes: Vec<LvmVolumeInfo>,
+    /// Shared memory and huge pages, if the platform reports them.
+    pub memory_detail: Option<MemoryDetail>,
+    /// None when sitrep can't find its own process.
+    pub self_usage: Option<SelfUsage>,
+    /// Docker daemon processes found on this host, dockerd first.
+    pub daemon_processes: Vec<DaemonProcessUsage>,
 }
 
 // --- UI State ---
@@ -127,11 +1401,299 @@ pub enum SortColumn {
     NetUp,
 }
 
+/// How the process table groups processes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ProcessGrouping {
+    /// By parent PID.
+    #[default]
+    Parent,
+    /// By systemd unit or container, from the cgroup path.
+    Unit,
+}
+
+/// What 100% means in the CPU columns of the System and Containers views.
+/// Both sysinfo and the Docker API report percent of one core.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum CpuScale {
+    /// One fully busy core is 100%, as in top and `docker stats`.
+    #[default]
+    #[serde(rename = "core")]
+    Core,
+    /// Every core busy is 100%, as in htop's meters.
+    #[serde(rename = "total")]
+    Total,
+}
+
+impl CpuScale {
+    /// Convert `percent` of one core for display on a host with `cores` CPUs.
+    pub fn apply(self, percent: f64, cores: f64) -> f64 {
+        match self {
+            CpuScale::Core => percent,
+            CpuScale::Total => percent / cores.max(1.0),
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            CpuScale::Core => CpuScale::Total,
+            CpuScale::Total => CpuScale::Core,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CpuScale::Core => "percent of one core",
+            CpuScale::Total => "percent of all cores",
+        }
+    }
+}
+
+/// Levels the System summary and the alerts warn at, from `[thresholds]`.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Thresholds {
+    /// TIME_WAIT sockets above this turn the Sock line's TW count yellow.
+    pub time_wait: u32,
+    /// CLOSE_WAIT sockets above this turn its CW count red.
+    pub close_wait: u32,
+    /// A disk with less free space than this percent is flagged LOW in red
+    /// and raises a critical alert; with less than twice this, LOW in peach.
+    pub disk_free_pct: f64,
+    /// Memory use above this percent is flagged HIGH on the Mem line.
+    pub mem_used_pct: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self { time_wait: 100, close_wait: 10, disk_free_pct: 10.0, mem_used_pct: 85.0 }
+    }
+}
+
+/// A line of the System summary, as named in `[summary] lines`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum SummaryLine {
+    Saturation,
+    Anomaly,
+    /// CPU bar with tasks, load average and the time split.
+    Cpu,
+    /// Memory bar with uptime, OOM, entropy and updates, and the breakdown.
+    Mem,
+    Swap,
+    /// A bar per filesystem, with disk I/O on the first.
+    Disk,
+    Net,
+    Sock,
+    /// Listening sockets that appeared or went away.
+    Port,
+    Raid,
+    Lvm,
+    Fd,
+    Irq,
+    Auth,
+    /// A `[[summary.custom]]` line by name.
+    Custom(String),
+}
+
+impl SummaryLine {
+    /// The built-in lines in their default order.
+    pub const BUILT_IN: [SummaryLine; 14] = [
+        SummaryLine::Saturation,
+        SummaryLine::Anomaly,
+        SummaryLine::Cpu,
+        SummaryLine::Mem,
+        SummaryLine::Swap,
+        SummaryLine::Disk,
+        SummaryLine::Net,
+        SummaryLine::Sock,
+        SummaryLine::Port,
+        SummaryLine::Raid,
+        SummaryLine::Lvm,
+        SummaryLine::Fd,
+        SummaryLine::Irq,
+        SummaryLine::Auth,
+    ];
+}
+
+impl From<String> for SummaryLine {
+    /// Names other than the built-in ones refer to custom lines.
+    fn from(name: String) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "saturation" => SummaryLine::Saturation,
+            "anomaly" => SummaryLine::Anomaly,
+            "cpu" | "load" => SummaryLine::Cpu,
+            "mem" => SummaryLine::Mem,
+            "swap" => SummaryLine::Swap,
+            "disk" | "io" => SummaryLine::Disk,
+            "net" => SummaryLine::Net,
+            "sock" => SummaryLine::Sock,
+            "port" => SummaryLine::Port,
+            "raid" => SummaryLine::Raid,
+            "lvm" => SummaryLine::Lvm,
+            "fd" => SummaryLine::Fd,
+            "irq" => SummaryLine::Irq,
+            "auth" => SummaryLine::Auth,
+            _ => SummaryLine::Custom(name),
+        }
+    }
+}
+
+/// How a custom summary line's command exited, by the Nagios plugin
+/// convention: 0 OK, 1 warning, 2 critical, anything else unknown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckState {
+    Ok,
+    Warning,
+    Critical,
+    Unknown,
+}
+
+/// The latest output of a custom summary line's command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomLineOutput {
+    pub text: String,
+    pub state: CheckState,
+}
+
+/// What the lower half of the System view shows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SystemPanel {
+    Processes,
+    /// Established connections grouped by remote address.
+    Connections,
+    /// Bandwidth graph for the named interface.
+    Bandwidth(String),
+    /// Graphs of the persisted metrics history.
+    History,
+    /// Result of the last CPU profile capture.
+    Profile,
+    /// Result of the last syscall trace.
+    Trace,
+    /// Failed logins and top offending addresses.
+    Security,
+    /// Shared memory segments and huge page usage.
+    Memory,
+    /// sitrep's own CPU, memory, subprocesses and collector back-off.
+    Diagnostics,
+    /// Process creation rate and the commands started most, from the exec
+    /// snoop that runs while the panel is open.
+    Execs,
+    /// IRQ and softirq rates with their spread across CPUs.
+    Interrupts,
+    /// CPU, memory and processes summed per user.
+    Users,
+}
+
+/// One distinct stack seen while profiling, leaf frame first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileEntry {
+    pub percent: f64,
+    pub frames: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileReport {
+    /// "perf" or "sample".
+    pub tool: &'static str,
+    /// Hottest stacks first.
+    pub entries: Vec<ProfileEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProfileState {
+    Running,
+    Done(ProfileReport),
+    Failed(String),
+}
+
+/// A CPU profile capture of one process, started from the process list.
+#[derive(Clone, Debug)]
+pub struct ProfileView {
+    pub pid: u32,
+    pub name: String,
+    pub started: std::time::Instant,
+    pub state: ProfileState,
+}
+
+/// One syscall's totals over a trace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyscallStat {
+    pub name: String,
+    pub calls: u64,
+    pub errors: u64,
+    /// Total time spent in the call (zero where the tool reports no timings).
+    pub seconds: f64,
+    pub usecs_per_call: u64,
+    /// Share of traced syscall time, or of calls when untimed.
+    pub percent: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceReport {
+    /// "strace" or "dtruss".
+    pub tool: &'static str,
+    /// Busiest syscalls first.
+    pub stats: Vec<SyscallStat>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceState {
+    Running,
+    Done(TraceReport),
+    Failed(String),
+}
+
+/// A syscall trace of one process, started from the process list.
+#[derive(Clone, Debug)]
+pub struct TraceView {
+    pub pid: u32,
+    pub name: String,
+    pub started: std::time::Instant,
+    pub state: TraceState,
+}
+
+/// How a metric's value is displayed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MetricUnit {
+    Count,
+    Percent,
+    BytesPerSec,
+}
+
+/// One metric over the history window, bucketed oldest first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistorySeries {
+    pub label: &'static str,
+    pub unit: MetricUnit,
+    pub values: Vec<u64>,
+}
+
 pub struct UIState {
     pub selected_index: usize,
     pub expanded_pids: HashSet<Pid>,
     pub total_rows: usize,
     pub sort_column: SortColumn,
+    pub grouping: ProcessGrouping,
+    pub cpu_scale: CpuScale,
+    pub panel: SystemPanel,
+    pub status_message: Option<String>,
+    /// Loaded from the history store while the History panel is shown.
+    pub history: Vec<HistorySeries>,
+    pub profile: Option<ProfileView>,
+    pub trace: Option<TraceView>,
+    /// Result of the last package update check.
+    pub updates: Option<UpdateStatus>,
+    pub auth: AuthSummary,
+    pub execs: ExecSummary,
+    pub guard: SelfGuard,
+    /// Container names by short ID, from the Containers tab, for labelling
+    /// the processes that run in them.
+    pub container_names: HashMap<String, String>,
+    /// Summary lines in display order, from `[summary]`.
+    pub summary_lines: Vec<SummaryLine>,
+    pub thresholds: Thresholds,
+    /// Latest output of each custom summary line, by name.
+    pub custom_lines: HashMap<String, CustomLineOutput>,
 }
 
 impl Default for UIState {
@@ -141,14 +1703,52 @@ impl Default for UIState {
             expanded_pids: HashSet::new(),
             total_rows: 0,
             sort_column: SortColumn::Cpu,
+            grouping: ProcessGrouping::Parent,
+            cpu_scale: CpuScale::Core,
+            panel: SystemPanel::Processes,
+            status_message: None,
+            history: Vec::new(),
+            profile: None,
+            trace: None,
+            updates: None,
+            auth: AuthSummary::default(),
+            execs: ExecSummary::default(),
+            guard: SelfGuard::default(),
+            container_names: HashMap::new(),
+            summary_lines: SummaryLine::BUILT_IN.to_vec(),
+            thresholds: Thresholds::default(),
+            custom_lines: HashMap::new(),
         }
     }
 }
 
 impl UIState {
+    /// Name of the container process `pid` runs in, or its short ID while
+    /// the Containers tab has not listed it.
+    pub fn container_of<'a>(&'a self, data: &'a MonitorData, pid: Pid) -> Option<&'a str> {
+        let id = data.process_containers.get(&pid.as_u32())?;
+        Some(self.container_names.get(id).unwrap_or(id))
+    }
+
     pub fn has_expansions(&self) -> bool {
         !self.expanded_pids.is_empty()
     }
+
+    /// Step the bandwidth panel to the next interface, returning to the
+    /// process list after the last one.
+    pub fn cycle_bandwidth_panel(&mut self, interfaces: &[InterfaceHistory]) {
+        let next = match &self.panel {
+            SystemPanel::Bandwidth(current) => interfaces
+                .iter()
+                .position(|h| &h.name == current)
+                .and_then(|i| interfaces.get(i + 1)),
+            _ => interfaces.first(),
+        };
+        self.panel = match next {
+            Some(h) => SystemPanel::Bandwidth(h.name.clone()),
+            None => SystemPanel::Processes,
+        };
+    }
 }
 
 #[cfg(test)]
@@ -156,6 +1756,174 @@ mod tests {
     use super::*;
     use sysinfo::Pid;
 
+    #[test]
+    fn groups_docker_daemon_processes() {
+        assert_eq!(daemon_process_name("dockerd"), Some("dockerd"));
+        assert_eq!(daemon_process_name("containerd"), Some("containerd"));
+        assert_eq!(daemon_process_name("containerd-shim"), Some("containerd-shim"));
+        assert_eq!(daemon_process_name("containerd-shim-runc-v2"), Some("containerd-shim"));
+        assert_eq!(daemon_process_name("docker-proxy"), None);
+    }
+
+    #[test]
+    fn self_guard_backs_off_and_recovers() {
+        let mut guard = SelfGuard { cpu_budget: 20.0, factor: 1 };
+        assert!(guard.observe(35.0));
+        assert!(guard.observe(35.0));
+        assert_eq!(guard.factor, 4);
+        for _ in 0..5 {
+            guard.observe(90.0);
+        }
+        assert_eq!(guard.factor, SelfGuard::MAX_FACTOR);
+        // Between half the budget and the budget the factor holds
+        assert!(!guard.observe(15.0));
+        guard.observe(5.0);
+        guard.observe(5.0);
+        guard.observe(5.0);
+        assert!(!guard.backing_off());
+
+        guard.cpu_budget = 0.0;
+        assert!(!guard.observe(500.0));
+    }
+
+    #[test]
+    fn connection_spike_is_flagged_against_baseline() {
+        let mut baselines = HashMap::new();
+        let mut data = MonitorData {
+            core_count: 4.0,
+            ..Default::default()
+        };
+        data.socket_overview.established = 150;
+        for _ in 0..Baseline::WARMUP {
+            assert!(detect_anomalies(&mut baselines, &data).is_empty());
+        }
+
+        data.socket_overview.established = 800;
+        let anomalies = detect_anomalies(&mut baselines, &data);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].metric, "Established");
+        assert_eq!(anomalies[0].summary(), "Established 800 (5.3x baseline 150)");
+    }
+
+    #[test]
+    fn small_absolute_changes_are_not_anomalies() {
+        let mut baseline = Baseline::default();
+        for _ in 0..Baseline::WARMUP {
+            baseline.observe(1.0);
+        }
+        // 10x the norm but only 9 above it
+        assert_eq!(baseline.deviation(10.0, 50.0), None);
+        assert!(baseline.deviation(100.0, 50.0).is_some());
+    }
+
+    #[test]
+    fn listener_changes_are_reported_after_the_first_snapshot() {
+        let listener = |addr: &str, process: &str| ListenerInfo {
+            address: addr.parse().unwrap(),
+            process: Some(process.to_string()),
+        };
+        let mut tracker = ListenerTracker::default();
+        let start = Instant::now();
+        let initial = [listener("0.0.0.0:22", "sshd"), listener("[::]:22", "sshd"), listener("127.0.0.1:5432", "postgres")];
+        assert!(tracker.observe(start, &initial).is_empty());
+
+        let later = start + std::time::Duration::from_secs(60);
+        let changes = tracker.observe(later, &[listener("0.0.0.0:22", "sshd"), listener("0.0.0.0:8080", "python3")]);
+        let summaries: Vec<String> = changes.iter().map(ListenerChange::summary).collect();
+        assert_eq!(summaries, vec!["new listener :8080 (python3)", "listener 127.0.0.1:5432 (postgres) gone"]);
+
+        // Changes expire after KEEP
+        let much_later = later + ListenerTracker::KEEP;
+        assert!(tracker.observe(much_later, &[listener("0.0.0.0:22", "sshd"), listener("0.0.0.0:8080", "python3")]).is_empty());
+    }
+
+    #[test]
+    fn restarted_groups_are_badged_and_their_old_samples_dropped() {
+        let group = |pid: usize, name: &str| {
+            let group = ProcessGroup {
+                pid: Pid::from(pid),
+                user: String::new(),
+                cpu: 0.0,
+                mem: 0,
+                read_bytes: 0,
+                written_bytes: 0,
+                net_rx_bytes: 0,
+                net_tx_bytes: 0,
+                child_count: 0,
+                name: name.to_string(),
+                children: Vec::new(),
+            };
+            (Pid::from(pid), group)
+        };
+        let mut tracker = RestartTracker::default();
+        let start = Instant::now();
+        assert!(tracker.observe(start, &HashMap::from([group(100, "nginx"), group(200, "postgres"), group(300, "cron")])).is_empty());
+
+        // nginx came back as 150, PID 300 now runs something else, postgres exited
+        let later = start + std::time::Duration::from_secs(3);
+        let mut stale = tracker.observe(later, &HashMap::from([group(150, "nginx"), group(300, "python3")]));
+        stale.sort();
+        assert_eq!(stale, vec![Pid::from(100usize), Pid::from(300usize)]);
+        assert_eq!(tracker.restarted(), HashMap::from([(150, 100)]));
+
+        // The badge expires after KEEP
+        tracker.observe(later + RestartTracker::KEEP, &HashMap::from([group(150, "nginx"), group(300, "python3")]));
+        assert!(tracker.restarted().is_empty());
+    }
+
+    #[test]
+    fn auth_failures_age_out_of_the_window() {
+        let mut failures = AuthFailures::default();
+        let start = Instant::now();
+        for i in 0..30u64 {
+            let remote = if i % 3 == 0 { "198.51.100.4" } else { "203.0.113.9" };
+            failures.record(start + std::time::Duration::from_secs(i * 30), remote.to_string());
+        }
+        // 14m30s in: failures from the first 4m30s have aged out
+        let summary = failures.summarize(start + std::time::Duration::from_secs(870));
+        assert_eq!(summary.window_total, 21);
+        assert_eq!(summary.last_minute, 3);
+        assert_eq!(summary.top_offenders[0], ("203.0.113.9".to_string(), 14));
+        assert!(!summary.is_spiking());
+    }
+
+    #[test]
+    fn exec_rate_counts_the_last_ten_seconds() {
+        let mut execs = ExecEvents::default();
+        let start = Instant::now();
+        // A cron burst a minute ago, then a steady health check
+        for _ in 0..50 {
+            execs.record(start, "sh".to_string());
+        }
+        for i in 0..40u64 {
+            execs.record(start + std::time::Duration::from_millis(30_000 + i * 750), "curl".to_string());
+        }
+        let summary = execs.summarize(start + std::time::Duration::from_secs(61));
+        assert_eq!(summary.window_total, 40);
+        assert_eq!(summary.top_commands, vec![("curl".to_string(), 40)]);
+        assert!((summary.per_sec - 1.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn steadily_growing_fd_count_is_a_leak() {
+        let mut tracker = FdTracker::default();
+        let start = Instant::now();
+        for i in 0..20u64 {
+            let at = start + std::time::Duration::from_secs(i * 6);
+            // pid 1 gains 2 FDs per sample; pid 2 grows but dips once
+            let wobbly = if i == 10 { 100 } else { 100 + i * 5 };
+            tracker.observe(at, &[(1, "leaky".to_string(), 500 + i * 2), (2, "pool".to_string(), wobbly)]);
+        }
+        let leaks = tracker.leaks(|pid| (pid == 1).then_some(1024));
+        assert_eq!(leaks.len(), 1);
+        assert_eq!(leaks[0].pid, 1);
+        assert_eq!(leaks[0].count, 538);
+        assert!((leaks[0].per_min - 20.0).abs() < 0.01);
+        // 486 FDs left at 20/min
+        assert_eq!(leaks[0].secs_to_limit(), Some(1458));
+        assert_eq!(leaks[0].summary(), "leaky[1] 538 FDs (+20/min), limit 1024, full in ~24m");
+    }
+
     #[test]
     fn ui_state_has_expansions() {
         let mut state = UIState::default();
@@ -169,4 +1937,80 @@ mod tests {
         let state = UIState::default();
         assert!(matches!(state.sort_column, SortColumn::Cpu));
     }
+
+    #[test]
+    fn bandwidth_panel_cycles_through_interfaces() {
+        let history = |name: &str| InterfaceHistory { name: name.into(), samples: Vec::new() };
+        let interfaces = vec![history("eth0"), history("eth1")];
+        let mut state = UIState::default();
+        state.cycle_bandwidth_panel(&interfaces);
+        assert_eq!(state.panel, SystemPanel::Bandwidth("eth0".into()));
+        state.cycle_bandwidth_panel(&interfaces);
+        assert_eq!(state.panel, SystemPanel::Bandwidth("eth1".into()));
+        state.cycle_bandwidth_panel(&interfaces);
+        assert_eq!(state.panel, SystemPanel::Processes);
+    }
+
+    #[test]
+    fn link_warnings() {
+        let mut link = LinkInfo {
+            name: "eth0".into(),
+            up: true,
+            speed_mbps: Some(10_000),
+            physical: true,
+            carrier_changes: 2,
+            last_flap_secs: None,
+        };
+        assert_eq!(link.warning(), None);
+        link.speed_mbps = Some(100);
+        assert_eq!(link.warning().as_deref(), Some("negotiated 100M"));
+        link.last_flap_secs = Some(125);
+        assert_eq!(link.warning().as_deref(), Some("flapped 2m ago"));
+        link.last_flap_secs = None;
+        link.up = false;
+        assert_eq!(link.warning().as_deref(), Some("link down"));
+        link.physical = false;
+        assert_eq!(link.warning(), None);
+        assert_eq!(format_link_speed(2500), "2.5G");
+    }
+
+    #[test]
+    fn saturation_reports_dominant_bottleneck() {
+        let memory = MemoryInfo { total: 100, available: 50, ..Default::default() };
+        let idle = PressureInfo::default();
+        let ok = assess_saturation((0.5, 0.5, 0.5), 4.0, &idle, &memory, 0.0);
+        assert_eq!(ok.level, SaturationLevel::Ok);
+
+        let busy = assess_saturation((6.0, 4.0, 2.0), 4.0, &idle, &memory, 0.0);
+        assert_eq!(busy.level, SaturationLevel::Pressure);
+        assert!(busy.reason.starts_with("CPU: load 6.00 on 4 cores, rising"));
+
+        let io_stall = PressureInfo { io: Some(55.0), cpu: Some(12.0), ..Default::default() };
+        let io = assess_saturation((1.0, 1.0, 1.0), 4.0, &io_stall, &memory, 0.0);
+        assert_eq!(io.level, SaturationLevel::Saturated);
+        assert!(io.reason.starts_with("I/O"));
+    }
+
+    #[test]
+    fn conntrack_usage_pct() {
+        let info = ConntrackInfo { count: 235_930, max: 262_144 };
+        assert!(info.usage_pct() >= ConntrackInfo::CRITICAL_PCT);
+        assert_eq!(ConntrackInfo { count: 10, max: 0 }.usage_pct(), 0.0);
+    }
+
+    #[test]
+    fn aggregate_remotes_groups_by_ip() {
+        let addr = |s: &str| s.parse::<SocketAddr>().unwrap();
+        let mut remotes = vec![addr("10.0.0.5:5432"); 3];
+        remotes.push(addr("[::ffff:10.0.0.5]:6379"));
+        remotes.push(addr("192.168.1.9:51000"));
+
+        let aggregated = aggregate_remotes(remotes);
+        assert_eq!(aggregated.len(), 2);
+        assert_eq!(aggregated[0].address.to_string(), "10.0.0.5");
+        assert_eq!(aggregated[0].connections, 4);
+        assert_eq!(aggregated[0].ports, vec![5432, 6379]);
+        assert_eq!(aggregated[1].connections, 1);
+        assert!(!aggregated[0].is_heavy());
+    }
 }
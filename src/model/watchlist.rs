@@ -0,0 +1,100 @@
+// --- Watchlist: containers and services pinned to their own tab ---
+
+/// What a watchlist entry refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchKind {
+    Container,
+    Service,
+}
+
+impl WatchKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            WatchKind::Container => "container",
+            WatchKind::Service => "service",
+        }
+    }
+}
+
+/// A pinned object, kept by name so it survives the container or task being
+/// recreated with a new ID.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchItem {
+    pub kind: WatchKind,
+    pub name: String,
+}
+
+/// The objects on the Watchlist tab, in the order they were pinned.
+#[derive(Clone, Debug, Default)]
+pub struct Watchlist {
+    pub items: Vec<WatchItem>,
+    pub selected: usize,
+}
+
+impl Watchlist {
+    /// A watchlist seeded from the `[watchlist]` config.
+    pub fn new(containers: &[String], services: &[String]) -> Self {
+        let mut watchlist = Self::default();
+        for name in containers {
+            watchlist.pin(WatchKind::Container, name);
+        }
+        for name in services {
+            watchlist.pin(WatchKind::Service, name);
+        }
+        watchlist
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, kind: WatchKind, name: &str) -> bool {
+        self.items.iter().any(|i| i.kind == kind && i.name == name)
+    }
+
+    fn pin(&mut self, kind: WatchKind, name: &str) {
+        if !self.contains(kind, name) {
+            self.items.push(WatchItem {
+                kind,
+                name: name.to_string(),
+            });
+        }
+    }
+
+    /// Pin the object, or unpin it when already pinned. Returns whether it is
+    /// pinned afterwards.
+    pub fn toggle(&mut self, kind: WatchKind, name: &str) -> bool {
+        if self.contains(kind, name) {
+            self.items.retain(|i| !(i.kind == kind && i.name == name));
+            self.selected = self.selected.min(self.items.len().saturating_sub(1));
+            false
+        } else {
+            self.pin(kind, name);
+            true
+        }
+    }
+
+    pub fn selected_item(&self) -> Option<&WatchItem> {
+        self.items.get(self.selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggling_pins_and_unpins_by_kind_and_name() {
+        let mut watchlist = Watchlist::new(&["web".to_string()], &["web".to_string(), "api".to_string()]);
+        assert_eq!(watchlist.items.len(), 3);
+        assert!(watchlist.contains(WatchKind::Service, "web"));
+
+        watchlist.selected = 2;
+        assert!(!watchlist.toggle(WatchKind::Service, "api"));
+        assert_eq!(watchlist.selected, 1);
+        assert!(watchlist.contains(WatchKind::Container, "web"));
+
+        assert!(watchlist.toggle(WatchKind::Container, "db"));
+        assert_eq!(watchlist.items.last().map(|i| i.name.as_str()), Some("db"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Warnings acknowledged during a long incident ("api is degraded, we know").
+//! A muted warning leaves the top of the Swarm overview for a muted list
+//! until its mute expires. Mutes live in a small TOML file, like the restart
+//! schedule, so every sitrep on the host hides the same warnings.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MutedWarning {
+    /// `warning_key` of the warning, so it stays muted while its counts change.
+    pub key: String,
+    /// The warning as it read when muted.
+    pub text: String,
+    /// Who muted it, as `user@host`.
+    pub by: String,
+    /// When the mute expires, in Unix seconds.
+    pub until: i64,
+    /// The Alertmanager silence created with it, expired on unmute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub silence_id: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct MuteFile {
+    #[serde(default)]
+    muted: Vec<MutedWarning>,
+}
+
+/// Default mute file: `~/.sitrep/muted.toml`, next to the schedule file.
+pub fn default_path() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    home.join(".sitrep").join("muted.toml")
+}
+
+/// What identifies a warning across refreshes: its text without the counts,
+/// which change while the condition persists ("1/3 replicas", "2 node(s)").
+pub fn warning_key(warning: &str) -> String {
+    warning.chars().filter(|c| !c.is_ascii_digit()).collect()
+}
+
+/// Seconds in a mute typed as `30m`, `4h` or `2d`.
+pub fn parse_duration(text: &str) -> Result<i64, String> {
+    let text = text.trim();
+    let invalid = || format!("Invalid duration '{}': use e.g. 30m, 4h or 2d", text);
+    let (digits, unit) = text.split_at(text.len().saturating_sub(1));
+    let amount: i64 = digits.parse().map_err(|_| invalid())?;
+    let unit = match unit {
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(invalid()),
+    };
+    if amount <= 0 {
+        return Err(invalid());
+    }
+    Ok(amount * unit)
+}
+
+/// The muted warnings and the file they are shared through (none with
+/// `--demo`).
+pub struct MuteList {
+    path: Option<PathBuf>,
+    /// As last read or written, expired mutes included until the next write.
+    pub muted: Vec<MutedWarning>,
+    pub error: Option<String>,
+}
+
+impl MuteList {
+    pub fn new(path: PathBuf) -> Self {
+        let mut list = Self { path: Some(path), muted: Vec::new(), error: None };
+        list.reload();
+        list
+    }
+
+    /// A mute list kept only in memory.
+    pub fn in_memory() -> Self {
+        Self { path: None, muted: Vec::new(), error: None }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The unexpired mute covering `warning`.
+    pub fn find(&self, warning: &str, now: i64) -> Option<&MutedWarning> {
+        let key = warning_key(warning);
+        self.muted.iter().find(|m| m.key == key && m.until > now)
+    }
+
+    /// Split warnings into those still shown and those muted, each muted one
+    /// with its current text.
+    pub fn split(&self, warnings: Vec<String>, now: i64) -> (Vec<String>, Vec<(String, MutedWarning)>) {
+        let mut shown = Vec::new();
+        let mut muted = Vec::new();
+        for warning in warnings {
+            match self.find(&warning, now) {
+                Some(mute) => muted.push((warning, mute.clone())),
+                None => shown.push(warning),
+            }
+        }
+        (shown, muted)
+    }
+
+    /// Mute `warning` for `secs` from `now`, replacing an earlier mute of it.
+    pub fn mute(&mut self, warning: &str, secs: i64, now: i64, by: &str) -> Result<(), String> {
+        let mute = MutedWarning {
+            key: warning_key(warning),
+            text: warning.to_string(),
+            by: by.to_string(),
+            until: now + secs,
+            silence_id: None,
+        };
+        self.update(now, |muted| {
+            muted.retain(|m| m.key != mute.key);
+            muted.push(mute);
+        })
+    }
+
+    /// Record the silence created for the mute with `key`.
+    pub fn set_silence(&mut self, key: &str, id: &str, now: i64) -> Result<(), String> {
+        self.update(now, |muted| {
+            if let Some(mute) = muted.iter_mut().find(|m| m.key == key) {
+                mute.silence_id = Some(id.to_string());
+            }
+        })
+    }
+
+    /// Show the warning again before its mute expires; returns the lifted mute.
+    pub fn unmute(&mut self, warning: &str, now: i64) -> Result<Option<MutedWarning>, String> {
+        let key = warning_key(warning);
+        let mut lifted = None;
+        self.update(now, |muted| {
+            if let Some(i) = muted.iter().position(|m| m.key == key) {
+                lifted = Some(muted.remove(i));
+            }
+        })?;
+        Ok(lifted)
+    }
+
+    /// Re-read the file for mutes set or lifted by other instances.
+    pub fn reload(&mut self) {
+        let Some(ref path) = self.path else { return };
+        match read(path) {
+            Ok(muted) => {
+                self.muted = muted;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Apply `change` to the current mutes (re-read from the file first),
+    /// drop the expired ones and write them back.
+    fn update(&mut self, now: i64, change: impl FnOnce(&mut Vec<MutedWarning>)) -> Result<(), String> {
+        let mut muted = match self.path {
+            Some(ref path) => read(path)?,
+            None => std::mem::take(&mut self.muted),
+        };
+        change(&mut muted);
+        muted.retain(|m| m.until > now);
+        muted.sort_by_key(|m| m.until);
+        if let Some(ref path) = self.path {
+            write(path, &muted)?;
+        }
+        self.muted = muted;
+        self.error = None;
+        Ok(())
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<MutedWarning>, String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let file: MuteFile = toml::from_str(&text).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    Ok(file.muted)
+}
+
+fn write(path: &Path, muted: &[MutedWarning]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let text = toml::to_string(&MuteFile { muted: muted.to_vec() })
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    // Replace the file whole so a reader never sees half of it
+    let partial = path.with_extension("toml.partial");
+    std::fs::write(&partial, text)
+        .and_then(|_| std::fs::rename(&partial, path))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn muted_warning_stays_hidden_as_its_counts_change_until_it_expires() {
+        assert_eq!(parse_duration("4h"), Ok(4 * 3600));
+        assert_eq!(parse_duration("30m"), Ok(1800));
+        assert!(parse_duration("0h").is_err() && parse_duration("soon").is_err());
+
+        let path = std::env::temp_dir().join(format!("sitrep-mutes-{}.toml", std::process::id()));
+        let mut list = MuteList::new(path.clone());
+        list.mute("SERVICE DEGRADED: api has 1/3 replicas", 3600, 1000, "alice@web1").unwrap();
+
+        let warnings = vec!["SERVICE DEGRADED: api has 2/3 replicas".to_string(), "NODE DOWN: 1 node(s) unreachable: b".to_string()];
+        let (shown, muted) = MuteList::new(path.clone()).split(warnings.clone(), 2000);
+        assert_eq!(shown, vec!["NODE DOWN: 1 node(s) unreachable: b".to_string()]);
+        assert_eq!((muted[0].0.as_str(), muted[0].1.by.as_str()), ("SERVICE DEGRADED: api has 2/3 replicas", "alice@web1"));
+
+        // Expired at 4600, and lifted by hand
+        assert_eq!(list.split(warnings.clone(), 4600).0.len(), 2);
+        list.unmute(&warnings[0], 2000).unwrap();
+        assert!(MuteList::new(path.clone()).muted.is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,273 @@
+//! Containers of a containerd host without a Docker daemon (Kubernetes nodes,
+//! bare containerd), read through the `nerdctl` CLI. Every namespace is
+//! listed unless `[containers] namespaces` narrows it, and each container is
+//! mapped onto the same `DockerContainerInfo` the Docker backend fills, so
+//! the Containers tab, logs and start/stop/restart work unchanged. There are
+//! no image digests here; the image drift checks stay Docker only.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::cmd::{self, TrackedChild};
+use crate::error::SitrepError;
+use crate::model::{ContainerStats, DockerContainerInfo, PublishedPort};
+
+/// Log lines replayed when a stream starts, as with Docker.
+pub(crate) const LOG_TAIL: &str = "200";
+
+fn nerdctl(namespace: &str, args: &[&str]) -> Result<String, SitrepError> {
+    let name = format!("nerdctl {}", args.first().unwrap_or(&""));
+    let output = cmd::output(
+        Command::new("nerdctl").arg("--namespace").arg(namespace).args(args),
+        cmd::QUICK,
+    )
+    .map_err(|e| SitrepError::from_io(&format!("Failed to run {}", name), &e))?;
+    if !output.status.success() {
+        return Err(SitrepError::from_stderr(&name, &String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// containerd namespaces, when nerdctl is installed and can reach containerd.
+pub fn namespaces() -> Result<Vec<String>, SitrepError> {
+    let text = nerdctl("default", &["namespace", "ls", "-q"])?;
+    Ok(text.lines().map(str::trim).filter(|l| !l.is_empty()).map(String::from).collect())
+}
+
+/// Running containers of `wanted` namespaces (all when empty), with stats.
+pub fn list_containers(wanted: &[String]) -> Result<Vec<DockerContainerInfo>, SitrepError> {
+    let namespaces = if wanted.is_empty() { namespaces()? } else { wanted.to_vec() };
+    let mut containers = Vec::new();
+    for ns in &namespaces {
+        let text = nerdctl(ns, &["ps", "--format", "{{json .}}"])?;
+        let mut listed: Vec<DockerContainerInfo> = text.lines().filter_map(|l| parse_ps_line(ns, l)).collect();
+        if listed.is_empty() {
+            continue;
+        }
+        // Stats are nice to have; a failure leaves them at zero
+        match nerdctl(ns, &["stats", "--no-stream", "--format", "{{json .}}"]) {
+            Ok(text) => {
+                let stats: HashMap<String, ContainerStats> = text.lines().filter_map(parse_stats_line).collect();
+                for c in &mut listed {
+                    if let Some(s) = stats.get(&c.id) {
+                        s.apply(c);
+                    }
+                }
+            }
+            Err(e) => tracing::debug!("{}", e),
+        }
+        containers.extend(listed);
+    }
+    Ok(containers)
+}
+
+/// One line of `nerdctl ps --format '{{json .}}'`.
+pub fn parse_ps_line(namespace: &str, line: &str) -> Option<DockerContainerInfo> {
+    let v: Value = serde_json::from_str(line).ok()?;
+    let field = |k: &str| v.get(k).and_then(Value::as_str).unwrap_or_default().to_string();
+    let id: String = field("ID").chars().take(12).collect();
+    if id.is_empty() {
+        return None;
+    }
+    let status = field("Status");
+    let state = if status.starts_with("Up") {
+        "running"
+    } else if status.starts_with("Paused") {
+        "paused"
+    } else if status.starts_with("Created") {
+        "created"
+    } else {
+        "exited"
+    };
+    // Older nerdctl reports a bare "Up"; the creation time stands in then
+    let uptime_secs = crate::docker::parse_status_uptime(&status).or_else(|| {
+        let created = field("CreatedAt");
+        let created = chrono::DateTime::parse_from_str(created.get(..25)?, "%Y-%m-%d %H:%M:%S %z").ok()?;
+        crate::docker::secs_since(created.timestamp())
+    });
+    let ports = field("Ports");
+    let labels = field("Labels")
+        .split(',')
+        .filter_map(|kv| kv.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+    Some(DockerContainerInfo {
+        name: match field("Names") {
+            n if n.is_empty() => id.clone(),
+            n => n,
+        },
+        image: field("Image"),
+        state: state.to_string(),
+        uptime: uptime_secs.map(crate::docker::format_uptime).unwrap_or_else(|| "unknown".to_string()),
+        uptime_secs,
+        published_ports: published_tcp_ports(&ports),
+        ports,
+        labels,
+        namespace: namespace.to_string(),
+        status,
+        id,
+        cpu_percent: 0.0,
+        ip_address: String::new(),
+        mem_usage: 0,
+        mem_limit: 0,
+        net_rx: 0,
+        net_tx: 0,
+        image_id: String::new(),
+        image_created: None,
+        image_digests: Vec::new(),
+        image_drift: None,
+        image_scan: None,
+    })
+}
+
+/// TCP bindings out of a ports string like "0.0.0.0:8080->80/tcp, 53/udp".
+pub(crate) fn published_tcp_ports(ports: &str) -> Vec<PublishedPort> {
+    let mut published: Vec<PublishedPort> = ports
+        .split(", ")
+        .filter_map(|p| {
+            let (host, container) = p.trim().split_once("->")?;
+            let container_port = container.strip_suffix("/tcp")?.parse().ok()?;
+            let (host_ip, host_port) = host.rsplit_once(':')?;
+            Some(PublishedPort {
+                host_ip: host_ip.to_string(),
+                host_port: host_port.parse().ok()?,
+                container_port,
+            })
+        })
+        .collect();
+    published.sort_by_key(|p| p.host_port);
+    published.dedup_by_key(|p| p.host_port);
+    published
+}
+
+/// (short ID, stats) from one line of `nerdctl stats --no-stream --format '{{json .}}'`.
+fn parse_stats_line(line: &str) -> Option<(String, ContainerStats)> {
+    let v: Value = serde_json::from_str(line).ok()?;
+    let field = |k: &str| v.get(k).and_then(Value::as_str).unwrap_or_default();
+    let id: String = field("ID").chars().take(12).collect();
+    let pair = |k: &str| {
+        let (a, b) = field(k).split_once(" / ").unwrap_or(("", ""));
+        (parse_size(a).unwrap_or(0), parse_size(b).unwrap_or(0))
+    };
+    let (mem_usage, mem_limit) = pair("MemUsage");
+    let (net_rx, net_tx) = pair("NetIO");
+    let stats = ContainerStats {
+        cpu_percent: field("CPUPerc").trim_end_matches('%').parse().unwrap_or(0.0),
+        mem_usage,
+        mem_limit,
+        net_rx,
+        net_tx,
+    };
+    Some((id, stats))
+}
+
+/// Bytes from a size like "1.5MiB" or "648B", in decimal or binary units.
+pub(crate) fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let scale = match unit {
+        "" | "B" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * scale) as u64)
+}
+
+/// Follow a container's logs. The stream ends when the returned child is
+/// dropped; lines arrive on a channel shaped like the Docker backend's.
+pub fn tail_logs(namespace: &str, id: &str) -> Result<(TrackedChild, mpsc::Receiver<String>), SitrepError> {
+    follow_logs(
+        Command::new("nerdctl").args(["--namespace", namespace, "logs", "--follow", "--timestamps", "--tail", LOG_TAIL, id]),
+        id,
+    )
+}
+
+/// Run a `logs --follow` command and forward its stdout and stderr lines;
+/// shared with the podman backend.
+pub(crate) fn follow_logs(command: &mut Command, id: &str) -> Result<(TrackedChild, mpsc::Receiver<String>), SitrepError> {
+    let spawned = cmd::spawn(command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()))
+        .map_err(|e| SitrepError::from_io(&format!("Failed to follow logs of {}", id), &e))?;
+
+    let (tx, rx) = mpsc::channel::<String>(256);
+    for pipe in [
+        spawned.stdout.map(|p| Box::new(p) as Box<dyn std::io::Read + Send>),
+        spawned.stderr.map(|p| Box::new(p) as Box<dyn std::io::Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(pipe).lines() {
+                let Ok(line) = line else { break };
+                if tx.blocking_send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    Ok((spawned.child, rx))
+}
+
+/// Start, stop or restart a container.
+pub fn container_action(namespace: &str, id: &str, action: &str) -> Result<(), SitrepError> {
+    nerdctl(namespace, &[action, id]).map(|_| ())
+}
+
+/// Give a container a new name.
+pub fn rename(namespace: &str, id: &str, name: &str) -> Result<(), SitrepError> {
+    nerdctl(namespace, &["rename", id, name]).map(|_| ())
+}
+
+/// PID of a running container's init process.
+pub fn container_pid(namespace: &str, id: &str) -> Result<u32, SitrepError> {
+    let text = nerdctl(namespace, &["inspect", "--format", "{{.State.Pid}}", id])?;
+    match text.trim().parse() {
+        Ok(0) | Err(_) => Err(SitrepError::Failed(format!("{} is not running", id))),
+        Ok(pid) => Ok(pid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_nerdctl_ps_and_stats_output() {
+        let line = r#"{"Command":"\"/pause\"","CreatedAt":"2026-10-14 09:00:00 +0000 UTC","ID":"3f1c2a9b8d7e6f5a4b3c","Image":"registry.k8s.io/pause:3.9","Platform":"linux/amd64","Names":"k8s://kube-system/coredns-5d78c9869d-8x2lq","Ports":"0.0.0.0:8080->80/tcp, 53/udp","Status":"Up 5 minutes","Runtime":"io.containerd.runc.v2","Size":"","Labels":"io.kubernetes.pod.namespace=kube-system,nerdctl/platform=linux/amd64"}"#;
+        let c = parse_ps_line("k8s.io", line).unwrap();
+        assert_eq!(c.id, "3f1c2a9b8d7e");
+        assert_eq!(c.name, "k8s://kube-system/coredns-5d78c9869d-8x2lq");
+        assert_eq!((c.state.as_str(), c.uptime_secs, c.namespace.as_str()), ("running", Some(300), "k8s.io"));
+        assert_eq!(c.labels["io.kubernetes.pod.namespace"], "kube-system");
+        assert_eq!(
+            c.published_ports,
+            vec![PublishedPort { host_ip: "0.0.0.0".into(), host_port: 8080, container_port: 80 }]
+        );
+        assert!(parse_ps_line("default", "not json").is_none());
+
+        let (id, stats) = parse_stats_line(
+            r#"{"BlockIO":"0B / 0B","CPUPerc":"1.25%","ID":"3f1c2a9b8d7e","MemUsage":"1.5MiB / 2GiB","Name":"coredns","NetIO":"1.2kB / 648B","PIDs":"1"}"#,
+        )
+        .unwrap();
+        assert_eq!(id, "3f1c2a9b8d7e");
+        assert_eq!(stats.cpu_percent, 1.25);
+        assert_eq!((stats.mem_usage, stats.mem_limit), (1_572_864, 2 * 1024 * 1024 * 1024));
+        assert_eq!((stats.net_rx, stats.net_tx), (1200, 648));
+    }
+}
@@ -0,0 +1,148 @@
+//! A one-line maintenance note ("draining node-3, hands off — alice") that
+//! every sitrep instance shows in a banner, so two responders don't act on
+//! the same thing. On a Swarm manager the note is a label on the local node,
+//! which every manager in the cluster reads with the node list; elsewhere it
+//! is a file shared by the instances on the host.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::SwarmNodeInfo;
+
+/// Node label holding a cluster-wide note.
+pub const NOTE_LABEL: &str = "sitrep.maintenance";
+
+/// Default note file for the instances on one host.
+pub const DEFAULT_FILE: &str = "/run/sitrep/maintenance.json";
+
+/// The current user on this host, as `user@host`.
+pub fn author() -> String {
+    let user = std::env::var("USER").unwrap_or_else(|_| "unknown".to_string());
+    let host = sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string());
+    format!("{}@{}", user, host)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceNote {
+    pub text: String,
+    /// Who set it, as `user@host`.
+    pub by: String,
+    /// When it was set, in Unix seconds.
+    pub at: i64,
+}
+
+impl MaintenanceNote {
+    /// A note set now by the current user on this host.
+    pub fn new(text: &str) -> Self {
+        Self { text: text.trim().to_string(), by: author(), at: chrono::Local::now().timestamp() }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Read a note from a file or label; a bare string (a label set by hand)
+    /// is taken as the text.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        serde_json::from_str(value)
+            .ok()
+            .or_else(|| Some(Self { text: value.to_string(), by: String::new(), at: 0 }))
+    }
+}
+
+/// The newest note among the nodes' labels.
+pub fn from_nodes(nodes: &[SwarmNodeInfo]) -> Option<MaintenanceNote> {
+    nodes
+        .iter()
+        .filter_map(|n| n.labels.get(NOTE_LABEL).and_then(|v| MaintenanceNote::parse(v)))
+        .max_by_key(|n| n.at)
+}
+
+/// The newer of two notes.
+pub fn newest(a: Option<MaintenanceNote>, b: Option<MaintenanceNote>) -> Option<MaintenanceNote> {
+    a.into_iter().chain(b).max_by_key(|n| n.at)
+}
+
+/// The note file of this host and what it held when last read.
+pub struct NoteFile {
+    path: PathBuf,
+    pub note: Option<MaintenanceNote>,
+    pub error: Option<String>,
+}
+
+impl NoteFile {
+    pub fn new(path: PathBuf) -> Self {
+        let mut file = Self { path, note: None, error: None };
+        file.reload();
+        file
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Re-read the file for notes set by other instances.
+    pub fn reload(&mut self) {
+        match std::fs::read_to_string(&self.path) {
+            Ok(text) => {
+                self.note = MaintenanceNote::parse(&text);
+                self.error = None;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                self.note = None;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("Failed to read {}: {}", self.path.display(), e)),
+        }
+    }
+
+    /// Write the note, or remove the file when clearing it.
+    pub fn set(&mut self, note: Option<MaintenanceNote>) -> Result<(), String> {
+        let result = match note {
+            Some(ref note) => self
+                .path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&self.path, note.to_json())),
+            None => match std::fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            },
+        };
+        result.map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+        self.note = note;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newest_note_wins_across_file_and_node_labels() {
+        let note = |text: &str, at| MaintenanceNote { text: text.into(), by: "alice@web1".into(), at };
+        let mut node = SwarmNodeInfo::default();
+        node.labels.insert(NOTE_LABEL.into(), note("draining node-3", 200).to_json());
+        let mut by_hand = SwarmNodeInfo::default();
+        by_hand.labels.insert(NOTE_LABEL.into(), "db failover in progress".into());
+
+        let host = note("rebooting web1", 100);
+        assert_eq!(newest(Some(host.clone()), from_nodes(&[node, by_hand.clone()])).unwrap().text, "draining node-3");
+        assert_eq!(newest(Some(host.clone()), from_nodes(&[by_hand])).unwrap().text, "rebooting web1");
+        assert_eq!(newest(None, from_nodes(&[])), None);
+        assert_eq!(MaintenanceNote::parse("  "), None);
+
+        let path = std::env::temp_dir().join(format!("sitrep-note-{}.json", std::process::id()));
+        let mut file = NoteFile::new(path.clone());
+        file.set(Some(host.clone())).unwrap();
+        assert_eq!(NoteFile::new(path.clone()).note, Some(host));
+        file.set(None).unwrap();
+        assert_eq!(NoteFile::new(path).note, None);
+    }
+}
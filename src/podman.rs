@@ -0,0 +1,218 @@
+//! Containers of a Podman host without Docker (RHEL, Fedora), read through
+//! the `podman` CLI when its Docker-compatible socket is not running. Each
+//! container is mapped onto the same `DockerContainerInfo` the Docker backend
+//! fills, so the Containers tab, logs and start/stop/restart work unchanged.
+//! There are no image digests here; the image drift checks stay Docker only.
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::cmd::{self, TrackedChild};
+use crate::error::SitrepError;
+use crate::model::{ContainerStats, DockerContainerInfo};
+use crate::nerdctl::{follow_logs, parse_size, published_tcp_ports, LOG_TAIL};
+
+fn podman(args: &[&str]) -> Result<String, SitrepError> {
+    let name = format!("podman {}", args.first().unwrap_or(&""));
+    let output = cmd::output(Command::new("podman").args(args), cmd::QUICK)
+        .map_err(|e| SitrepError::from_io(&format!("Failed to run {}", name), &e))?;
+    if !output.status.success() {
+        return Err(SitrepError::from_stderr(&name, &String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether podman is installed and can list containers.
+pub fn available() -> bool {
+    podman(&["ps", "--quiet"]).is_ok()
+}
+
+/// Every container, stopped ones included, with stats for the running ones.
+pub fn list_containers() -> Result<Vec<DockerContainerInfo>, SitrepError> {
+    let mut containers = parse_ps(&podman(&["ps", "--all", "--format", "json"])?)?;
+    if !containers.iter().any(|c| c.state == "running") {
+        return Ok(containers);
+    }
+    // Stats are nice to have; a failure leaves them at zero
+    match podman(&["stats", "--no-stream", "--format", "json"]) {
+        Ok(text) => {
+            let stats = parse_stats(&text);
+            for c in &mut containers {
+                if let Some(s) = stats.get(&c.id) {
+                    s.apply(c);
+                }
+            }
+        }
+        Err(e) => tracing::debug!("{}", e),
+    }
+    Ok(containers)
+}
+
+/// `podman ps --all --format json`: an array of containers.
+pub fn parse_ps(text: &str) -> Result<Vec<DockerContainerInfo>, SitrepError> {
+    let list: Vec<Value> =
+        serde_json::from_str(text).map_err(|e| SitrepError::ParseError(format!("Unexpected podman ps output: {}", e)))?;
+    Ok(list.iter().filter_map(parse_container).collect())
+}
+
+fn parse_container(v: &Value) -> Option<DockerContainerInfo> {
+    let field = |k: &str| v.get(k).and_then(Value::as_str).unwrap_or_default().to_string();
+    let id: String = field("Id").chars().take(12).collect();
+    if id.is_empty() {
+        return None;
+    }
+    let state = match field("State").as_str() {
+        "running" => "running",
+        "paused" => "paused",
+        "created" | "configured" | "initialized" => "created",
+        _ => "exited",
+    };
+    let uptime_secs = (state == "running")
+        .then(|| v.get("StartedAt").and_then(Value::as_i64))
+        .flatten()
+        .and_then(crate::docker::secs_since);
+    let status = match field("Status") {
+        s if !s.is_empty() => s,
+        _ => match uptime_secs {
+            Some(secs) => format!("Up {}", crate::docker::format_uptime(secs)),
+            None if state == "exited" => format!("Exited ({})", v.get("ExitCode").and_then(Value::as_i64).unwrap_or(0)),
+            None => field("State"),
+        },
+    };
+    let name = v
+        .get("Names")
+        .and_then(Value::as_array)
+        .and_then(|names| names.first())
+        .and_then(Value::as_str)
+        .map_or_else(|| id.clone(), String::from);
+    let labels = v
+        .get("Labels")
+        .and_then(Value::as_object)
+        .map(|labels| labels.iter().map(|(k, v)| (k.clone(), v.as_str().unwrap_or_default().to_string())).collect())
+        .unwrap_or_default();
+    // Bindings written the way `docker ps` shows them
+    let ports = v
+        .get("Ports")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|b| {
+            let host_ip = match b.get("host_ip").and_then(Value::as_str) {
+                Some(ip) if !ip.is_empty() => ip,
+                _ => "0.0.0.0",
+            };
+            let protocol = b.get("protocol").and_then(Value::as_str).unwrap_or("tcp");
+            Some(format!("{}:{}->{}/{}", host_ip, b.get("host_port")?, b.get("container_port")?, protocol))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some(DockerContainerInfo {
+        name,
+        image: field("Image"),
+        state: state.to_string(),
+        uptime: uptime_secs.map(crate::docker::format_uptime).unwrap_or_else(|| "unknown".to_string()),
+        uptime_secs,
+        published_ports: published_tcp_ports(&ports),
+        ports,
+        labels,
+        namespace: String::new(),
+        status,
+        id,
+        cpu_percent: 0.0,
+        ip_address: String::new(),
+        mem_usage: 0,
+        mem_limit: 0,
+        net_rx: 0,
+        net_tx: 0,
+        image_id: String::new(),
+        image_created: None,
+        image_digests: Vec::new(),
+        image_drift: None,
+        image_scan: None,
+    })
+}
+
+/// Stats by short ID from `podman stats --no-stream --format json`.
+fn parse_stats(text: &str) -> HashMap<String, ContainerStats> {
+    let list: Vec<Value> = serde_json::from_str(text).unwrap_or_default();
+    list.iter()
+        .filter_map(|v| {
+            let field = |k: &str| v.get(k).and_then(Value::as_str).unwrap_or_default();
+            let id: String = field("id").chars().take(12).collect();
+            let pair = |k: &str| {
+                let (a, b) = field(k).split_once(" / ").unwrap_or(("", ""));
+                (parse_size(a).unwrap_or(0), parse_size(b).unwrap_or(0))
+            };
+            let (mem_usage, mem_limit) = pair("mem_usage");
+            let (net_rx, net_tx) = pair("net_io");
+            let stats = ContainerStats {
+                cpu_percent: field("cpu_percent").trim_end_matches('%').parse().unwrap_or(0.0),
+                mem_usage,
+                mem_limit,
+                net_rx,
+                net_tx,
+            };
+            (!id.is_empty()).then_some((id, stats))
+        })
+        .collect()
+}
+
+/// Follow a container's logs, as `nerdctl::tail_logs` does.
+pub fn tail_logs(id: &str) -> Result<(TrackedChild, mpsc::Receiver<String>), SitrepError> {
+    follow_logs(Command::new("podman").args(["logs", "--follow", "--timestamps", "--tail", LOG_TAIL, id]), id)
+}
+
+/// Start, stop or restart a container.
+pub fn container_action(id: &str, action: &str) -> Result<(), SitrepError> {
+    podman(&[action, id]).map(|_| ())
+}
+
+/// Give a container a new name.
+pub fn rename(id: &str, name: &str) -> Result<(), SitrepError> {
+    podman(&["rename", id, name]).map(|_| ())
+}
+
+/// PID of a running container's init process.
+pub fn container_pid(id: &str) -> Result<u32, SitrepError> {
+    let text = podman(&["inspect", "--format", "{{.State.Pid}}", id])?;
+    match text.trim().parse() {
+        Ok(0) | Err(_) => Err(SitrepError::Failed(format!("{} is not running", id))),
+        Ok(pid) => Ok(pid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::PublishedPort;
+
+    #[test]
+    fn maps_podman_ps_and_stats_output() {
+        let started = chrono::Utc::now().timestamp() - 300;
+        let ps = format!(
+            r#"[{{"Id":"9b2f41c0d1e2a3b4c5d6","Image":"docker.io/library/nginx:1.27","Names":["web"],"State":"running","Status":"",
+                "StartedAt":{},"Labels":{{"app":"shop"}},
+                "Ports":[{{"host_ip":"","container_port":80,"host_port":8080,"range":1,"protocol":"tcp"}}]}},
+               {{"Id":"1a2b3c4d5e6f7a8b9c0d","Image":"registry.access.redhat.com/ubi9","Names":["job"],"State":"exited","ExitCode":1}}]"#,
+            started
+        );
+        let containers = parse_ps(&ps).unwrap();
+        let web = &containers[0];
+        assert_eq!((web.id.as_str(), web.name.as_str(), web.state.as_str()), ("9b2f41c0d1e2", "web", "running"));
+        assert_eq!((web.uptime_secs, web.status.as_str()), (Some(300), "Up 5m"));
+        assert_eq!(web.ports, "0.0.0.0:8080->80/tcp");
+        assert_eq!(web.published_ports, vec![PublishedPort { host_ip: "0.0.0.0".into(), host_port: 8080, container_port: 80 }]);
+        assert_eq!(web.labels["app"], "shop");
+        assert_eq!((containers[1].state.as_str(), containers[1].status.as_str()), ("exited", "Exited (1)"));
+
+        let stats = parse_stats(
+            r#"[{"id":"9b2f41c0d1e2a3b4","name":"web","cpu_percent":"2.50%","mem_usage":"1.5MB / 2GB","net_io":"1.2kB / 648B"}]"#,
+        );
+        let s = &stats["9b2f41c0d1e2"];
+        assert_eq!((s.cpu_percent, s.mem_usage, s.mem_limit, s.net_rx, s.net_tx), (2.5, 1_500_000, 2_000_000_000, 1200, 648));
+    }
+}
@@ -0,0 +1,174 @@
+//! Reachability and health probes run from the host against containers and services.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+/// How long a single connect attempt may take before it counts as a failure.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Address to dial for a port published on `host_ip`. Wildcard binds are
+/// reachable on loopback.
+pub fn dial_host(host_ip: &str) -> &str {
+    match host_ip {
+        "" | "0.0.0.0" => "127.0.0.1",
+        "::" => "::1",
+        ip => ip,
+    }
+}
+
+/// TCP-connect to `host:port` and return the connect latency.
+pub fn probe_tcp(host: &str, port: u16, timeout: Duration) -> Result<Duration, String> {
+    let addr: SocketAddr = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("no address for {}", host))?;
+    let start = Instant::now();
+    TcpStream::connect_timeout(&addr, timeout).map_err(|e| match e.kind() {
+        std::io::ErrorKind::TimedOut => format!("timed out after {}s", timeout.as_secs()),
+        std::io::ErrorKind::ConnectionRefused => "refused".to_string(),
+        _ => e.to_string(),
+    })?;
+    Ok(start.elapsed())
+}
+
+/// Split an `http://host[:port]/path` URL into (host, port, path).
+/// Only plain HTTP is supported.
+pub fn parse_http_url(url: &str) -> Result<(String, u16, String), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("unsupported URL '{}' (only http:// is supported)", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], &rest[pos..]),
+        None => (rest, "/"),
+    };
+    // IPv6 literals are bracketed: http://[::1]:8080/
+    let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+        let (host, after) = bracketed
+            .split_once(']')
+            .ok_or_else(|| format!("invalid host in '{}'", url))?;
+        (host, after.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    let port = match port {
+        Some(p) => p.parse().map_err(|_| format!("invalid port in '{}'", url))?,
+        None => 80,
+    };
+    if host.is_empty() {
+        return Err(format!("missing host in '{}'", url));
+    }
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+/// Issue an HTTP GET and return the response status code and total latency
+/// up to the status line.
+pub fn probe_http(url: &str, timeout: Duration) -> Result<(u16, Duration), String> {
+    let (host, port, path) = parse_http_url(url)?;
+    let addr: SocketAddr = (host.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("no address for {}", host))?;
+
+    let start = Instant::now();
+    let mut stream = TcpStream::connect_timeout(&addr, timeout).map_err(|e| match e.kind() {
+        std::io::ErrorKind::TimedOut => "connect timed out".to_string(),
+        std::io::ErrorKind::ConnectionRefused => "refused".to_string(),
+        _ => e.to_string(),
+    })?;
+    stream.set_read_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: sitrep\r\nConnection: close\r\n\r\n",
+        path, host
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut status_line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut status_line)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => "read timed out".to_string(),
+            _ => e.to_string(),
+        })?;
+    let latency = start.elapsed();
+
+    // "HTTP/1.1 200 OK"
+    let code = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|c| c.parse::<u16>().ok())
+        .ok_or_else(|| "malformed HTTP response".to_string())?;
+    Ok((code, latency))
+}
+
+/// Format a probe latency compactly, e.g. "0.4ms" or "120ms".
+pub fn format_latency(latency: Duration) -> String {
+    let ms = latency.as_secs_f64() * 1000.0;
+    if ms < 10.0 {
+        format!("{:.1}ms", ms)
+    } else {
+        format!("{:.0}ms", ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn dial_host_maps_wildcards_to_loopback() {
+        assert_eq!(dial_host("0.0.0.0"), "127.0.0.1");
+        assert_eq!(dial_host("::"), "::1");
+        assert_eq!(dial_host("10.0.0.5"), "10.0.0.5");
+    }
+
+    #[test]
+    fn parse_http_url_splits_host_port_and_path() {
+        assert_eq!(
+            parse_http_url("http://localhost:8080/healthz").unwrap(),
+            ("localhost".to_string(), 8080, "/healthz".to_string())
+        );
+        assert_eq!(
+            parse_http_url("http://10.0.0.5").unwrap(),
+            ("10.0.0.5".to_string(), 80, "/".to_string())
+        );
+        assert!(parse_http_url("https://example.com/").is_err());
+    }
+
+    #[test]
+    fn probe_http_reads_status_code() {
+        use std::io::Read;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 512];
+            let _ = conn.read(&mut buf);
+            conn.write_all(b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n").unwrap();
+        });
+
+        let (code, _) = probe_http(&format!("http://127.0.0.1:{}/health", port), PROBE_TIMEOUT).unwrap();
+        assert_eq!(code, 503);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn probe_tcp_reports_open_and_closed_ports() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(probe_tcp("127.0.0.1", port, PROBE_TIMEOUT).is_ok());
+
+        drop(listener);
+        assert!(probe_tcp("127.0.0.1", port, PROBE_TIMEOUT).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! Short on-demand CPU profiles of a single process: `perf record` on Linux,
+//! `sample` on macOS. Both block for the capture duration, so callers run
+//! them on a background thread.
+
+use std::collections::HashMap;
+use std::os::unix::fs::DirBuilderExt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cmd;
+use crate::model::{ProfileEntry, ProfileReport};
+
+/// Length of a capture.
+pub const PROFILE_SECS: u64 = 5;
+/// Stacks shown in the result pane.
+const TOP_STACKS: usize = 20;
+/// Frames kept per stack, from the leaf up.
+const STACK_DEPTH: usize = 6;
+
+/// Profile `pid` for PROFILE_SECS and return its hottest stacks.
+pub fn profile(pid: u32) -> Result<ProfileReport, String> {
+    if cfg!(target_os = "macos") {
+        profile_sample(pid)
+    } else {
+        profile_perf(pid)
+    }
+}
+
+fn profile_perf(pid: u32) -> Result<ProfileReport, String> {
+    let dir = private_dir(&format!("sitrep-perf-{}", std::process::id()))?;
+    let data = dir.join("perf.data");
+    let data_arg = data.to_string_lossy().into_owned();
+    let duration = PROFILE_SECS.to_string();
+    let pid_arg = pid.to_string();
+    let recorded = run(
+        "perf",
+        &["record", "-F", "99", "-g", "-p", &pid_arg, "-o", &data_arg, "--", "sleep", &duration],
+    );
+    if let Err(e) = recorded {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+    let script = run("perf", &["script", "-i", &data_arg]);
+    let _ = std::fs::remove_dir_all(&dir);
+    let entries = fold_perf_script(&script?);
+    if entries.is_empty() {
+        return Err("perf captured no samples (process idle?)".to_string());
+    }
+    Ok(ProfileReport {
+        tool: "perf",
+        entries,
+    })
+}
+
+/// Create a fresh 0700 directory called `name` under `$XDG_RUNTIME_DIR` when
+/// set, else the temp directory, with a suffix when the name is taken.
+/// `mkdir` fails on anything already at the path, so a file or symlink
+/// planted there by another user is never written through.
+pub(crate) fn private_dir(name: &str) -> Result<PathBuf, String> {
+    let base = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+    let mut last_err = None;
+    for attempt in 0..8u32 {
+        let dir = match attempt {
+            0 => base.join(name),
+            n => base.join(format!("{}-{:08x}", name, nanos.wrapping_add(n))),
+        };
+        match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => last_err = Some(e),
+            Err(e) => return Err(format!("Failed to create {}: {}", dir.display(), e)),
+        }
+    }
+    Err(format!("Failed to create a private directory: {}", last_err.map(|e| e.to_string()).unwrap_or_default()))
+}
+
+fn profile_sample(pid: u32) -> Result<ProfileReport, String> {
+    let output = run("sample", &[&pid.to_string(), &PROFILE_SECS.to_string()])?;
+    let entries = parse_sample_top_of_stack(&output);
+    if entries.is_empty() {
+        return Err("sample captured no stacks".to_string());
+    }
+    Ok(ProfileReport {
+        tool: "sample",
+        entries,
+    })
+}
+
+/// Run a command and return its stdout, or the last stderr line as the error.
+fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = cmd::output(Command::new(program).args(args), cmd::SLOW).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("{} not found in PATH", program),
+        _ => format!("Failed to run {}: {}", program, e),
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("failed");
+        return Err(format!("{}: {}", program, reason.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Aggregate `perf script` output into identical stacks, busiest first.
+/// Each sample is a header line followed by one frame per line, leaf first,
+/// and a blank line.
+pub fn fold_perf_script(text: &str) -> Vec<ProfileEntry> {
+    let mut counts: HashMap<Vec<String>, u64> = HashMap::new();
+    let mut total = 0u64;
+    let mut frames: Vec<String> = Vec::new();
+    let mut in_sample = false;
+
+    let mut flush = |frames: &mut Vec<String>| {
+        if !frames.is_empty() {
+            *counts.entry(std::mem::take(frames)).or_default() += 1;
+            total += 1;
+        }
+    };
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            flush(&mut frames);
+            in_sample = false;
+        } else if line.starts_with(char::is_whitespace) {
+            if in_sample && frames.len() < STACK_DEPTH {
+                frames.push(parse_perf_frame(line.trim()));
+            }
+        } else {
+            flush(&mut frames);
+            in_sample = true;
+        }
+    }
+    flush(&mut frames);
+
+    let mut entries: Vec<ProfileEntry> = counts
+        .into_iter()
+        .map(|(frames, count)| ProfileEntry {
+            percent: count as f64 / total as f64 * 100.0,
+            frames,
+        })
+        .collect();
+    entries.sort_by(|a, b| b.percent.total_cmp(&a.percent).then_with(|| a.frames.cmp(&b.frames)));
+    entries.truncate(TOP_STACKS);
+    entries
+}
+
+/// "7f12ab memcpy+0x1a (/usr/lib/libc.so.6)" -> "memcpy". Unresolved
+/// symbols fall back to the library name.
+fn parse_perf_frame(line: &str) -> String {
+    let rest = line.split_once(' ').map(|(_, r)| r).unwrap_or(line);
+    let (symbol, dso) = match rest.rfind(" (") {
+        Some(i) => (&rest[..i], rest[i + 2..].trim_end_matches(')')),
+        None => (rest, ""),
+    };
+    let symbol = symbol.split("+0x").next().unwrap_or(symbol);
+    if symbol == "[unknown]" && !dso.is_empty() {
+        let lib = dso.rsplit('/').next().unwrap_or(dso);
+        format!("[{}]", lib)
+    } else {
+        symbol.to_string()
+    }
+}
+
+/// Parse the "Sort by top of stack" summary of macOS `sample` output:
+/// "        memcpy  (in libsystem_platform.dylib)        123".
+pub fn parse_sample_top_of_stack(text: &str) -> Vec<ProfileEntry> {
+    let Some(start) = text.find("Sort by top of stack") else {
+        return Vec::new();
+    };
+    let rows: Vec<(String, u64)> = text[start..]
+        .lines()
+        .skip(1)
+        .map(str::trim)
+        .take_while(|l| !l.is_empty())
+        .filter_map(|l| {
+            let (rest, count) = l.rsplit_once(char::is_whitespace)?;
+            let count: u64 = count.parse().ok()?;
+            let symbol = rest.split("  (in ").next().unwrap_or(rest).trim();
+            Some((symbol.to_string(), count))
+        })
+        .collect();
+    let total: u64 = rows.iter().map(|(_, c)| c).sum();
+    rows.into_iter()
+        .take(TOP_STACKS)
+        .map(|(symbol, count)| ProfileEntry {
+            percent: count as f64 / total.max(1) as f64 * 100.0,
+            frames: vec![symbol],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_identical_perf_stacks() {
+        let script = "\
+python3 4242 [001] 100.000001: 10101010 cpu-clock:pppH:
+\t    7f00aa memcpy+0x1a (/usr/lib/libc.so.6)
+\t    55bb00 parse_json+0x20 (/usr/bin/python3)
+\t    55bb10 main+0x8 (/usr/bin/python3)
+
+python3 4242 [001] 100.010101: 10101010 cpu-clock:pppH:
+\t    7f00ab memcpy+0x2b (/usr/lib/libc.so.6)
+\t    55bb01 parse_json+0x24 (/usr/bin/python3)
+\t    55bb10 main+0x8 (/usr/bin/python3)
+
+python3 4242 [001] 100.020202: 10101010 cpu-clock:pppH:
+\t    7f1000 [unknown] (/usr/lib/libssl.so.3)
+";
+        let entries = fold_perf_script(script);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].frames, vec!["memcpy", "parse_json", "main"]);
+        assert!((entries[0].percent - 66.67).abs() < 0.01);
+        assert_eq!(entries[1].frames, vec!["[libssl.so.3]"]);
+    }
+
+    #[test]
+    fn parses_sample_summary() {
+        let output = "\
+Call graph:
+    100 Thread_1
+Total number in stack (recursive counted multiple, when >=5):
+
+Sort by top of stack, same collapsed (when >= 5):
+        __psynch_cvwait  (in libsystem_kernel.dylib)        30
+        memmove  (in libsystem_platform.dylib)        10
+
+Binary Images:
+";
+        let entries = parse_sample_top_of_stack(output);
+        assert_eq!(entries[0].frames, vec!["__psynch_cvwait"]);
+        assert_eq!(entries[0].percent, 75.0);
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn capture_dir_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = private_dir("sitrep-test-private").unwrap();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
@@ -0,0 +1,180 @@
+//! Session recording (`--record`) and playback (`--replay`). A recording is
+//! one JSON object per line, each the host, container and Swarm state of
+//! one tick, so an incident can be walked through afterwards in the same
+//! views it was watched in. Kubernetes and log streams are not recorded.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{DockerContainerInfo, MonitorData, SwarmClusterInfo, SwarmNodeInfo, SwarmServiceInfo, SwarmTaskInfo};
+
+/// What the monitors held at one tick.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct Frame {
+    /// Unix seconds.
+    pub at: i64,
+    /// None until the first collection finished.
+    #[serde(default)]
+    pub system: Option<MonitorData>,
+    #[serde(default)]
+    pub containers: Vec<DockerContainerInfo>,
+    /// None off Swarm.
+    #[serde(default)]
+    pub swarm: Option<SwarmFrame>,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct SwarmFrame {
+    pub cluster: Option<SwarmClusterInfo>,
+    pub nodes: Vec<SwarmNodeInfo>,
+    pub services: Vec<SwarmServiceInfo>,
+    /// Running tasks of the services in expanded stacks, by service ID.
+    #[serde(default)]
+    pub service_tasks: HashMap<String, Vec<SwarmTaskInfo>>,
+    #[serde(default)]
+    pub failed_tasks: Vec<SwarmTaskInfo>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Appends frames to a recording, flushed after each so a recording cut
+/// short by a crash or `kill` still replays.
+pub struct Recorder {
+    out: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Start a recording at `path`, replacing any file there.
+    pub fn create(path: &Path) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Self { out: BufWriter::new(file) })
+    }
+
+    pub fn write(&mut self, frame: &Frame) -> Result<(), String> {
+        serde_json::to_writer(&mut self.out, frame).map_err(|e| e.to_string())?;
+        self.out.write_all(b"\n").and_then(|_| self.out.flush()).map_err(|e| e.to_string())
+    }
+}
+
+/// A loaded recording and the frame on screen.
+pub struct Replay {
+    /// File name, for the banner.
+    pub name: String,
+    frames: Vec<Frame>,
+    position: usize,
+    /// Frames advance one per tick until paused or the last is reached.
+    pub playing: bool,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        Self::parse(name, &text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    fn parse(name: String, text: &str) -> Result<Self, String> {
+        let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+        let mut frames = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(frame) => frames.push(frame),
+                // The last frame is cut off when sitrep died mid-write
+                Err(e) if i + 1 == lines.len() && i > 0 => tracing::warn!("{}: dropping cut-off last frame: {}", name, e),
+                Err(e) => return Err(format!("frame {}: {}", i + 1, e)),
+            }
+        }
+        if frames.is_empty() {
+            return Err("the recording has no frames".to_string());
+        }
+        Ok(Self { name, frames, position: 0, playing: true })
+    }
+
+    pub fn frame(&self) -> &Frame {
+        &self.frames[self.position]
+    }
+
+    /// The frame on screen, from 1, and how many there are.
+    pub fn position(&self) -> (usize, usize) {
+        (self.position + 1, self.frames.len())
+    }
+
+    /// Whether any frame has containers, so the Containers tab is shown.
+    pub fn has_containers(&self) -> bool {
+        self.frames.iter().any(|f| !f.containers.is_empty())
+    }
+
+    /// The Swarm cluster of the first frame on one, so the Swarm tab is shown.
+    pub fn cluster(&self) -> Option<SwarmClusterInfo> {
+        self.frames.iter().find_map(|f| f.swarm.as_ref()?.cluster.clone())
+    }
+
+    /// Move to the next frame while playing, stopping at the last. Returns
+    /// whether the frame changed.
+    pub fn advance(&mut self) -> bool {
+        if !self.playing {
+            return false;
+        }
+        if self.position + 1 == self.frames.len() {
+            self.playing = false;
+            return false;
+        }
+        self.position += 1;
+        true
+    }
+
+    /// Pause and move one frame back or forward. Returns whether the frame
+    /// changed.
+    pub fn step(&mut self, forward: bool) -> bool {
+        self.playing = false;
+        let next = if forward { self.position + 1 } else { self.position.wrapping_sub(1) };
+        if next >= self.frames.len() {
+            return false;
+        }
+        self.position = next;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_recorded_frames() {
+        let path = std::env::temp_dir().join(format!("sitrep-recording-{}.sitrep", std::process::id()));
+        let mut recorder = Recorder::create(&path).unwrap();
+        for at in [100, 103, 106] {
+            let system = MonitorData { time: format!("t{}", at), ..Default::default() };
+            let swarm = SwarmFrame {
+                nodes: vec![SwarmNodeInfo { id: "n1".into(), hostname: "node-1".into(), ip_address: "10.0.0.1".into(), ..Default::default() }],
+                ..Default::default()
+            };
+            recorder.write(&Frame { at, system: Some(system), containers: Vec::new(), swarm: Some(swarm) }).unwrap();
+        }
+        drop(recorder);
+        // A write cut short by a kill
+        let mut text = std::fs::read_to_string(&path).unwrap();
+        text.push_str("{\"at\": 109, \"sys");
+        std::fs::remove_file(&path).unwrap();
+
+        let mut replay = Replay::parse("test".into(), &text).unwrap();
+        assert_eq!(replay.position(), (1, 3));
+        assert_eq!(replay.frame().system.as_ref().unwrap().time, "t100");
+        // Fields Docker's JSON lacks come back from the recording
+        assert_eq!(replay.frame().swarm.as_ref().unwrap().nodes[0].ip_address, "10.0.0.1");
+        assert!(replay.advance() && replay.advance());
+        assert!(!replay.advance() && !replay.playing);
+        assert_eq!(replay.frame().at, 106);
+        assert!(replay.step(false) && replay.step(true));
+        assert!(!replay.step(true));
+        assert_eq!(replay.position(), (3, 3));
+
+        assert!(Replay::parse("test".into(), "").is_err());
+        assert!(Replay::parse("test".into(), "not json\n{\"at\": 1}\n").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Vulnerability counts per image from `trivy` or `docker scout`, whichever
+//! is installed, with `[containers] scan_images` on. A scan takes minutes and
+//! the counts change with the image or the CVE database, so each image is
+//! scanned at most daily, one at a time, and the counts are kept in
+//! `~/.sitrep/scans.toml` so a restart doesn't scan everything again.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cmd;
+
+/// A first trivy run downloads its vulnerability database.
+const SCAN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scanner {
+    Trivy,
+    Scout,
+}
+
+impl Scanner {
+    /// trivy when installed, else the docker scout plugin.
+    pub fn detect() -> Option<Self> {
+        let runs = |command: &mut Command| cmd::output(command, cmd::QUICK).is_ok_and(|o| o.status.success());
+        if runs(Command::new("trivy").arg("--version")) {
+            Some(Scanner::Trivy)
+        } else if runs(Command::new("docker").args(["scout", "version"])) {
+            Some(Scanner::Scout)
+        } else {
+            None
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Scanner::Trivy => "trivy",
+            Scanner::Scout => "docker scout",
+        }
+    }
+
+    /// Scan `image` for critical and high CVEs.
+    pub fn scan(self, image: &str, now: i64) -> ImageScan {
+        let mut command = match self {
+            Scanner::Trivy => {
+                let mut c = Command::new("trivy");
+                c.args(["image", "--quiet", "--format", "json", "--scanners", "vuln", "--severity", "CRITICAL,HIGH", image]);
+                c
+            }
+            Scanner::Scout => {
+                let mut c = Command::new("docker");
+                c.args(["scout", "cves", "--format", "gitlab", "--only-severity", "critical,high", image]);
+                c
+            }
+        };
+        let result = cmd::output(&mut command, SCAN_TIMEOUT)
+            .map_err(|e| format!("{}: {}", self.label(), e))
+            .and_then(|o| {
+                if o.status.success() {
+                    Ok(String::from_utf8_lossy(&o.stdout).into_owned())
+                } else {
+                    let stderr = String::from_utf8_lossy(&o.stderr);
+                    Err(stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("failed").trim().to_string())
+                }
+            })
+            .and_then(|text| parse_report(self, &text));
+        let (critical, high, error) = match result {
+            Ok((critical, high)) => (critical, high, None),
+            Err(e) => (0, 0, Some(e)),
+        };
+        ImageScan { scanner: self.label().to_string(), critical, high, scanned: now, error }
+    }
+}
+
+/// The outcome of scanning one image.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImageScan {
+    pub scanner: String,
+    /// Distinct CVEs of each severity.
+    pub critical: u32,
+    pub high: u32,
+    /// When it was scanned, in Unix seconds.
+    pub scanned: i64,
+    /// Why the scan failed; it is retried sooner than a finished one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Distinct critical and high CVEs in a trivy JSON or docker scout GitLab
+/// report. A CVE found in several packages counts once.
+pub fn parse_report(scanner: Scanner, text: &str) -> Result<(u32, u32), String> {
+    let report: serde_json::Value = serde_json::from_str(text).map_err(|e| format!("{} report: {}", scanner.label(), e))?;
+    let findings: Vec<(&str, &str)> = match scanner {
+        Scanner::Trivy => report["Results"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .flat_map(|r| r["Vulnerabilities"].as_array().into_iter().flatten())
+            .map(|v| (v["VulnerabilityID"].as_str().unwrap_or_default(), v["Severity"].as_str().unwrap_or_default()))
+            .collect(),
+        Scanner::Scout => report["vulnerabilities"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|v| {
+                let id = v["cve"].as_str().or_else(|| v["name"].as_str()).or_else(|| v["id"].as_str());
+                (id.unwrap_or_default(), v["severity"].as_str().unwrap_or_default())
+            })
+            .collect(),
+    };
+    let mut critical = HashSet::new();
+    let mut high = HashSet::new();
+    for (id, severity) in findings {
+        match severity.to_ascii_lowercase().as_str() {
+            "critical" => critical.insert(id),
+            "high" => high.insert(id),
+            _ => false,
+        };
+    }
+    Ok((critical.len() as u32, high.len() as u32))
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ScanFile {
+    /// By image ID, so a new build or pull of a tag is scanned again.
+    #[serde(default)]
+    images: BTreeMap<String, ImageScan>,
+}
+
+/// Default scan cache: `~/.sitrep/scans.toml`, next to the mute file.
+pub fn default_path() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    home.join(".sitrep").join("scans.toml")
+}
+
+/// Scans by image ID, read from the cache file once and written after each scan.
+pub fn load(path: &std::path::Path) -> BTreeMap<String, ImageScan> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| toml::from_str::<ScanFile>(&text).map_err(|e| tracing::warn!("{}: {}", path.display(), e)).ok())
+        .map(|file| file.images)
+        .unwrap_or_default()
+}
+
+pub fn save(path: &std::path::Path, images: &BTreeMap<String, ImageScan>) -> Result<(), String> {
+    let text = toml::to_string(&ScanFile { images: images.clone() }).map_err(|e| e.to_string())?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    let partial = path.with_extension("toml.partial");
+    std::fs::write(&partial, text)
+        .and_then(|_| std::fs::rename(&partial, path))
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_distinct_critical_and_high_cves() {
+        let trivy = r#"{"Results": [
+            {"Target": "debian", "Vulnerabilities": [
+                {"VulnerabilityID": "CVE-2024-1", "PkgName": "libc", "Severity": "CRITICAL"},
+                {"VulnerabilityID": "CVE-2024-1", "PkgName": "libc-bin", "Severity": "CRITICAL"},
+                {"VulnerabilityID": "CVE-2024-2", "Severity": "HIGH"}]},
+            {"Target": "app.jar"}]}"#;
+        assert_eq!(parse_report(Scanner::Trivy, trivy), Ok((1, 1)));
+        let scout = r#"{"version": "15.0.0", "vulnerabilities": [
+            {"cve": "CVE-2024-3", "severity": "Critical"},
+            {"cve": "CVE-2024-4", "severity": "Critical"},
+            {"cve": "CVE-2024-5", "severity": "Medium"}]}"#;
+        assert_eq!(parse_report(Scanner::Scout, scout), Ok((2, 0)));
+        assert!(parse_report(Scanner::Trivy, "FATAL no such image").is_err());
+
+        let images = BTreeMap::from([(
+            "sha256:abc".to_string(),
+            ImageScan { scanner: "trivy".into(), critical: 1, high: 1, scanned: 1_700_000_000, error: None },
+        )]);
+        let text = toml::to_string(&ScanFile { images: images.clone() }).unwrap();
+        assert_eq!(toml::from_str::<ScanFile>(&text).unwrap().images, images);
+    }
+}
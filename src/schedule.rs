@@ -0,0 +1,222 @@
+//! Rolling restarts scheduled for a quieter time ("restart api at 02:00").
+//! Pending entries live in a small TOML file so a TUI can schedule one and a
+//! `--daemon` instance can carry it out overnight; whichever process sees an
+//! entry come due first removes it from the file and runs it.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledRestart {
+    pub service_id: String,
+    pub service_name: String,
+    /// When it is due, in Unix seconds.
+    pub at: i64,
+}
+
+impl ScheduledRestart {
+    pub fn local_time(&self) -> Option<DateTime<Local>> {
+        Local.timestamp_opt(self.at, 0).single()
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ScheduleFile {
+    #[serde(default)]
+    restart: Vec<ScheduledRestart>,
+}
+
+/// Default schedule file: `~/.sitrep/schedules.toml`, next to the log file.
+pub fn default_path() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    home.join(".sitrep").join("schedules.toml")
+}
+
+/// When a restart typed as `02:00` (the next time the clock shows it) or
+/// `+30m` / `+2h` (from now) should run.
+pub fn parse_when(text: &str, now: DateTime<Local>) -> Result<DateTime<Local>, String> {
+    let text = text.trim();
+    if let Some(offset) = text.strip_prefix('+') {
+        let (digits, unit) = offset.split_at(offset.len().saturating_sub(1));
+        let amount: i64 = digits.parse().map_err(|_| format!("Invalid delay '{}': use e.g. +30m or +2h", text))?;
+        let delay = match unit {
+            "m" => ChronoDuration::minutes(amount),
+            "h" => ChronoDuration::hours(amount),
+            _ => return Err(format!("Invalid delay '{}': use e.g. +30m or +2h", text)),
+        };
+        return Ok(now + delay);
+    }
+    let time = NaiveTime::parse_from_str(text, "%H:%M")
+        .map_err(|_| format!("Invalid time '{}': use HH:MM or a delay like +30m", text))?;
+    let today = now.date_naive().and_time(time);
+    let at = match Local.from_local_datetime(&today).earliest() {
+        Some(at) if at > now => at,
+        _ => Local
+            .from_local_datetime(&(today + ChronoDuration::days(1)))
+            .earliest()
+            .ok_or_else(|| format!("{} does not exist on the local clock tomorrow", text))?,
+    };
+    Ok(at)
+}
+
+/// How long until `at`, e.g. "5h 12m" or "3m".
+pub fn format_until(at: i64, now: i64) -> String {
+    let mins = ((at - now).max(0) + 59) / 60;
+    match (mins / 60, mins % 60) {
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+/// The pending restarts and the file they are shared through (none with
+/// `--demo`, whose services are made up).
+pub struct RestartSchedule {
+    path: Option<PathBuf>,
+    /// As last read or written, soonest first.
+    pub pending: Vec<ScheduledRestart>,
+    pub error: Option<String>,
+}
+
+impl RestartSchedule {
+    pub fn new(path: PathBuf) -> Self {
+        let mut schedule = Self { path: Some(path), pending: Vec::new(), error: None };
+        schedule.reload();
+        schedule
+    }
+
+    /// A schedule kept only in memory.
+    pub fn in_memory() -> Self {
+        Self { path: None, pending: Vec::new(), error: None }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn for_service(&self, service_id: &str) -> Option<&ScheduledRestart> {
+        self.pending.iter().find(|r| r.service_id == service_id)
+    }
+
+    /// Schedule a restart, replacing one already pending for the service.
+    pub fn add(&mut self, restart: ScheduledRestart) -> Result<(), String> {
+        self.update(|pending| {
+            pending.retain(|r| r.service_id != restart.service_id);
+            pending.push(restart);
+        })
+    }
+
+    /// Drop the service's pending restart; returns it if there was one.
+    pub fn cancel(&mut self, service_id: &str) -> Result<Option<ScheduledRestart>, String> {
+        let mut cancelled = None;
+        self.update(|pending| {
+            if let Some(i) = pending.iter().position(|r| r.service_id == service_id) {
+                cancelled = Some(pending.remove(i));
+            }
+        })?;
+        Ok(cancelled)
+    }
+
+    /// Re-read the file and remove the restarts due at `now`, returning them
+    /// to be run. Entries added or cancelled by another process show up here.
+    pub fn take_due(&mut self, now: i64) -> Vec<ScheduledRestart> {
+        let mut due = Vec::new();
+        let result = self.update(|pending| {
+            let (ready, later) = std::mem::take(pending).into_iter().partition(|r| r.at <= now);
+            due = ready;
+            *pending = later;
+        });
+        if let Err(e) = result {
+            self.error = Some(e);
+            return Vec::new();
+        }
+        due
+    }
+
+    /// Re-read the file, e.g. for changes made by the daemon.
+    pub fn reload(&mut self) {
+        let Some(ref path) = self.path else { return };
+        match read(path) {
+            Ok(pending) => {
+                self.pending = pending;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Apply `change` to the current entries (re-read from the file first)
+    /// and write them back.
+    fn update(&mut self, change: impl FnOnce(&mut Vec<ScheduledRestart>)) -> Result<(), String> {
+        let mut pending = match self.path {
+            Some(ref path) => read(path)?,
+            None => std::mem::take(&mut self.pending),
+        };
+        let before = pending.clone();
+        change(&mut pending);
+        pending.sort_by_key(|r| r.at);
+        if let Some(ref path) = self.path
+            && pending != before {
+                write(path, &pending)?;
+            }
+        self.pending = pending;
+        self.error = None;
+        Ok(())
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<ScheduledRestart>, String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let file: ScheduleFile = toml::from_str(&text).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    Ok(file.restart)
+}
+
+fn write(path: &Path, pending: &[ScheduledRestart]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let text = toml::to_string(&ScheduleFile { restart: pending.to_vec() })
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    // Replace the file whole so a reader never sees half of it
+    let partial = path.with_extension("toml.partial");
+    std::fs::write(&partial, text)
+        .and_then(|_| std::fs::rename(&partial, path))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules_by_clock_time_and_hands_out_due_restarts_once() {
+        let now = Local.with_ymd_and_hms(2026, 10, 14, 22, 30, 0).unwrap();
+        assert_eq!(parse_when("02:00", now).unwrap(), Local.with_ymd_and_hms(2026, 10, 15, 2, 0, 0).unwrap());
+        assert_eq!(parse_when("23:15", now).unwrap(), Local.with_ymd_and_hms(2026, 10, 14, 23, 15, 0).unwrap());
+        assert_eq!(parse_when("+90m", now).unwrap(), now + ChronoDuration::minutes(90));
+        assert!(parse_when("later", now).is_err());
+        assert_eq!(format_until(now.timestamp() + 5 * 3600 + 11 * 60 + 5, now.timestamp()), "5h 12m");
+
+        let path = std::env::temp_dir().join(format!("sitrep-schedule-{}.toml", std::process::id()));
+        let mut schedule = RestartSchedule::new(path.clone());
+        let restart = |id: &str, at| ScheduledRestart { service_id: id.into(), service_name: id.into(), at };
+        schedule.add(restart("api", 200)).unwrap();
+        schedule.add(restart("web", 100)).unwrap();
+        schedule.add(restart("api", 300)).unwrap();
+
+        // Another process sharing the file sees the same entries, soonest first
+        let mut other = RestartSchedule::new(path.clone());
+        assert_eq!(other.pending, vec![restart("web", 100), restart("api", 300)]);
+        assert_eq!(other.take_due(150), vec![restart("web", 100)]);
+        assert!(schedule.take_due(150).is_empty());
+        assert_eq!(schedule.cancel("api").unwrap(), Some(restart("api", 300)));
+        other.reload();
+        assert!(other.pending.is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+}
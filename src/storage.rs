@@ -0,0 +1,314 @@
+//! Software RAID and ZFS pool health: `/proc/mdstat` on Linux and
+//! `zpool status -j` wherever OpenZFS 2.3+ is installed. Also LVM thin pool
+//! and snapshot usage from `lvs`, which needs root.
+
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::cmd;
+use crate::model::{ArrayActivity, LvmKind, LvmVolumeInfo, StorageArrayInfo};
+
+/// Collect every md array and ZFS pool on the host.
+pub fn collect() -> Vec<StorageArrayInfo> {
+    let mut arrays = std::fs::read_to_string("/proc/mdstat")
+        .map(|text| parse_mdstat(&text))
+        .unwrap_or_default();
+    if let Ok(output) = cmd::output(Command::new("zpool").args(["status", "-j", "--json-int"]), cmd::QUICK)
+        && output.status.success() {
+            arrays.extend(parse_zpool_status(&String::from_utf8_lossy(&output.stdout)));
+        }
+    arrays
+}
+
+/// Parse /proc/mdstat. Each array is a header line such as
+/// "md1 : active raid5 sdd1[3] sdc1[1] sdb1[0](F)", a status line ending in
+/// "[3/2] [U_U]", and an optional progress line
+/// "[=>....]  recovery =  8.5% (89344/1046528) finish=1.2min speed=12345K/sec".
+pub fn parse_mdstat(text: &str) -> Vec<StorageArrayInfo> {
+    let mut arrays: Vec<StorageArrayInfo> = Vec::new();
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some((name, rest)) = line.split_once(" : ").filter(|(n, _)| n.starts_with("md")) {
+            let mut words = rest.split_whitespace().peekable();
+            let state = words.next().unwrap_or("").to_string();
+            // "(auto-read-only)" and similar qualifiers precede the level
+            while words.peek().is_some_and(|w| w.starts_with('(')) {
+                words.next();
+            }
+            let level = words.peek().filter(|w| !w.contains('[')).map(|w| w.to_string());
+            if level.is_some() {
+                words.next();
+            }
+            let problem_members = words
+                .filter(|w| w.ends_with("(F)"))
+                .map(|w| format!("{} failed", w.split('[').next().unwrap_or(w)))
+                .collect();
+            arrays.push(StorageArrayInfo {
+                name: name.trim().to_string(),
+                kind: "md".to_string(),
+                level: level.unwrap_or_default(),
+                healthy: state == "active",
+                state,
+                problem_members,
+                activity: None,
+            });
+        } else if let Some(array) = arrays.last_mut() {
+            if let Some(slots) = trimmed.rsplit(' ').next().filter(|s| s.starts_with('[') && s.contains('U')) {
+                // "[U_U]": an underscore is a missing member
+                if slots.contains('_') {
+                    array.healthy = false;
+                    array.state = "degraded".to_string();
+                }
+            } else if let Some(activity) = parse_md_progress(trimmed) {
+                array.activity = Some(activity);
+            }
+        }
+    }
+    arrays
+}
+
+/// "[=>..]  recovery =  8.5% (...)" -> ("recovery", 8.5).
+fn parse_md_progress(line: &str) -> Option<ArrayActivity> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let at = words
+        .iter()
+        .position(|w| matches!(*w, "recovery" | "resync" | "check" | "reshape" | "repair"))?;
+    let operation = words[at];
+    if words.get(at + 1) != Some(&"=") {
+        return None;
+    }
+    let percent = words.get(at + 2)?.strip_suffix('%')?.parse().ok()?;
+    Some(ArrayActivity {
+        operation: operation.to_string(),
+        percent,
+    })
+}
+
+/// Parse `zpool status -j --json-int`: pool state, leaf vdevs that are not
+/// ONLINE, and any scrub or resilver in progress.
+pub fn parse_zpool_status(text: &str) -> Vec<StorageArrayInfo> {
+    let Ok(root) = serde_json::from_str::<Value>(text) else {
+        return Vec::new();
+    };
+    let Some(pools) = root.get("pools").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    pools
+        .iter()
+        .map(|(name, pool)| {
+            let state = pool.get("state").and_then(Value::as_str).unwrap_or("UNKNOWN").to_string();
+            let mut problem_members = Vec::new();
+            if let Some(vdevs) = pool.get("vdevs") {
+                collect_problem_vdevs(vdevs, &mut problem_members);
+            }
+            let level = pool
+                .get("vdevs")
+                .and_then(|v| v.get(name))
+                .and_then(|root| root.get("vdevs"))
+                .and_then(Value::as_object)
+                .and_then(|top| top.values().next())
+                .and_then(|v| v.get("vdev_type"))
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string();
+            StorageArrayInfo {
+                name: name.clone(),
+                kind: "zfs".to_string(),
+                level,
+                healthy: state == "ONLINE",
+                state,
+                problem_members,
+                activity: pool.get("scan_stats").and_then(parse_zfs_scan),
+            }
+        })
+        .collect()
+}
+
+/// Leaf vdevs (disks, files) whose state is not ONLINE, as "sdb FAULTED".
+fn collect_problem_vdevs(vdevs: &Value, out: &mut Vec<String>) {
+    let Some(vdevs) = vdevs.as_object() else {
+        return;
+    };
+    for (name, vdev) in vdevs {
+        match vdev.get("vdevs") {
+            Some(children) => collect_problem_vdevs(children, out),
+            None => {
+                let state = vdev.get("state").and_then(Value::as_str).unwrap_or("UNKNOWN");
+                if state != "ONLINE" {
+                    out.push(format!("{} {}", name, state));
+                }
+            }
+        }
+    }
+}
+
+/// A scrub or resilver that is still scanning, with its issued share.
+fn parse_zfs_scan(scan: &Value) -> Option<ArrayActivity> {
+    if scan.get("state").and_then(Value::as_str) != Some("SCANNING") {
+        return None;
+    }
+    let number = |key: &str| -> Option<f64> {
+        match scan.get(key)? {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    };
+    let total = number("to_examine").filter(|t| *t > 0.0)?;
+    let done = number("issued").or_else(|| number("examined"))?;
+    Some(ArrayActivity {
+        operation: scan.get("function").and_then(Value::as_str).unwrap_or("scan").to_lowercase(),
+        percent: (done / total * 100.0).min(100.0),
+    })
+}
+
+/// Collect LVM thin pools and classic snapshots, fullest first.
+pub fn collect_lvm() -> Vec<LvmVolumeInfo> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    let output = cmd::output(
+        Command::new("lvs").args([
+            "--reportformat",
+            "json",
+            "--nosuffix",
+            "-o",
+            "vg_name,lv_name,lv_attr,data_percent,metadata_percent,origin",
+        ]),
+        cmd::QUICK,
+    );
+    match output {
+        Ok(output) if output.status.success() => parse_lvs(&String::from_utf8_lossy(&output.stdout)),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `lvs --reportformat json`. The first lv_attr character gives the
+/// volume type: 't' thin pool, 's' snapshot, 'S' invalid snapshot. Other
+/// volumes, including thin snapshots, cannot fill up on their own.
+pub fn parse_lvs(text: &str) -> Vec<LvmVolumeInfo> {
+    let Ok(root) = serde_json::from_str::<Value>(text) else {
+        return Vec::new();
+    };
+    let field = |lv: &Value, key: &str| lv.get(key).and_then(Value::as_str).unwrap_or("").trim().to_string();
+    let mut volumes: Vec<LvmVolumeInfo> = root
+        .get("report")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|report| report.get("lv").and_then(Value::as_array))
+        .flatten()
+        .filter_map(|lv| {
+            let attr = field(lv, "lv_attr");
+            let (kind, invalid) = match attr.chars().next()? {
+                't' => (LvmKind::ThinPool, false),
+                's' => (LvmKind::Snapshot, false),
+                'S' => (LvmKind::Snapshot, true),
+                _ => return None,
+            };
+            let origin = field(lv, "origin");
+            Some(LvmVolumeInfo {
+                vg: field(lv, "vg_name"),
+                name: field(lv, "lv_name"),
+                kind,
+                data_pct: field(lv, "data_percent").parse().unwrap_or(0.0),
+                metadata_pct: field(lv, "metadata_percent").parse().ok(),
+                origin: (!origin.is_empty()).then_some(origin),
+                invalid,
+            })
+        })
+        .collect();
+    volumes.sort_by(|a, b| b.usage_pct().total_cmp(&a.usage_pct()));
+    volumes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_degraded_md_array_with_recovery() {
+        let mdstat = "\
+Personalities : [raid1] [raid6] [raid5] [raid4]
+md0 : active raid1 sdb1[1] sda1[0]
+      1046528 blocks super 1.2 [2/2] [UU]
+
+md1 : active raid5 sdd1[3] sdc1[1] sdb2[0](F)
+      2093056 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/2] [U_U]
+      [=>...................]  recovery =  8.5% (89344/1046528) finish=1.2min speed=12345K/sec
+
+unused devices: <none>
+";
+        let arrays = parse_mdstat(mdstat);
+        assert_eq!(arrays.len(), 2);
+        assert!(arrays[0].healthy);
+        assert_eq!(arrays[0].level, "raid1");
+        assert!(!arrays[1].healthy);
+        assert_eq!(arrays[1].state, "degraded");
+        assert_eq!(arrays[1].problem_members, vec!["sdb2 failed"]);
+        let activity = arrays[1].activity.as_ref().unwrap();
+        assert_eq!(activity.operation, "recovery");
+        assert_eq!(activity.percent, 8.5);
+    }
+
+    #[test]
+    fn parses_zpool_status_json() {
+        let json = r#"{
+  "output_version": {"command": "zpool status", "vers_major": 0, "vers_minor": 1},
+  "pools": {
+    "tank": {
+      "name": "tank",
+      "state": "DEGRADED",
+      "vdevs": {
+        "tank": {
+          "name": "tank", "vdev_type": "root", "state": "DEGRADED",
+          "vdevs": {
+            "mirror-0": {
+              "name": "mirror-0", "vdev_type": "mirror", "state": "DEGRADED",
+              "vdevs": {
+                "sda": {"name": "sda", "vdev_type": "disk", "state": "ONLINE"},
+                "sdb": {"name": "sdb", "vdev_type": "disk", "state": "FAULTED"}
+              }
+            }
+          }
+        }
+      },
+      "scan_stats": {"function": "RESILVER", "state": "SCANNING", "to_examine": 2000, "examined": 1500, "issued": 500}
+    }
+  }
+}"#;
+        let pools = parse_zpool_status(json);
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].level, "mirror");
+        assert!(!pools[0].healthy);
+        assert_eq!(pools[0].problem_members, vec!["sdb FAULTED"]);
+        let activity = pools[0].activity.as_ref().unwrap();
+        assert_eq!(activity.operation, "resilver");
+        assert_eq!(activity.percent, 25.0);
+    }
+
+    #[test]
+    fn parses_lvs_thin_pools_and_snapshots() {
+        let json = r#"{
+      "report": [
+          {
+              "lv": [
+                  {"vg_name":"vg0", "lv_name":"root", "lv_attr":"-wi-ao----", "data_percent":"", "metadata_percent":"", "origin":""},
+                  {"vg_name":"vg0", "lv_name":"pool", "lv_attr":"twi-aotz--", "data_percent":"45.12", "metadata_percent":"96.50", "origin":""},
+                  {"vg_name":"vg0", "lv_name":"vm1", "lv_attr":"Vwi-aotz--", "data_percent":"30.00", "metadata_percent":"", "origin":""},
+                  {"vg_name":"vg0", "lv_name":"root-snap", "lv_attr":"swi-a-s---", "data_percent":"12.00", "metadata_percent":"", "origin":"root"}
+              ]
+          }
+      ]
+  }"#;
+        let volumes = parse_lvs(json);
+        assert_eq!(volumes.len(), 2);
+        assert_eq!(volumes[0].kind, LvmKind::ThinPool);
+        assert_eq!(volumes[0].usage_pct(), 96.5);
+        assert_eq!(volumes[0].summary(), "vg0/pool thin data 45% meta 96%");
+        assert_eq!(volumes[1].kind, LvmKind::Snapshot);
+        assert_eq!(volumes[1].origin.as_deref(), Some("root"));
+        assert_eq!(volumes[1].metadata_pct, None);
+    }
+}
@@ -1,27 +1,191 @@
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Read};
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{mpsc, Arc};
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
+use std::net::{IpAddr, SocketAddr, TcpStream, UdpSocket};
+use std::time::{Duration, Instant};
 
+use bollard::Docker;
+use bollard::models::{EndpointPortConfigPublishModeEnum, LocalNodeState, Service};
+use bollard::service::ListServicesOptions;
 use serde::Deserialize;
+use tokio::runtime::Runtime;
 
-use crate::model::{SwarmClusterInfo, SwarmNodeInfo, SwarmServiceInfo, SwarmTaskInfo};
+use crate::cmd::{self, TrackedChild};
+use crate::error::SitrepError;
+use crate::model::{LabelEdit, NodeProbe, PortFailure, ServiceSpecInfo, SwarmClusterInfo, SwarmNodeInfo, SwarmServiceInfo, SwarmTaskInfo, TaskTimes};
 
-/// Handle returned by `tail_service_logs` to kill the child process on cleanup.
+/// Handle returned by `tail_service_logs` to kill the child process on
+/// cleanup. Dropping it kills and reaps the child as well.
 pub struct LogStreamHandle {
     pub receiver: mpsc::Receiver<String>,
     kill_flag: Arc<AtomicBool>,
+    child: Option<TrackedChild>,
 }
 
 impl LogStreamHandle {
-    /// Signal the child process to stop and drop resources.
+    /// Stop the readers and kill the child process.
     pub fn kill(&self) {
         self.kill_flag.store(true, Ordering::Release);
+        if let Some(ref child) = self.child {
+            child.kill();
+        }
+    }
+
+    /// A stream with no process behind it: `next` is called for a line every
+    /// `interval` until the handle is killed or dropped. Used by `--demo`.
+    pub fn generated(interval: Duration, mut next: impl FnMut() -> String + Send + 'static) -> Self {
+        let (tx, rx) = mpsc::sync_channel::<String>(1000);
+        let kill_flag = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&kill_flag);
+        thread::spawn(move || {
+            while !flag.load(Ordering::Acquire) && tx.send(next()).is_ok() {
+                thread::sleep(interval);
+            }
+        });
+        LogStreamHandle {
+            receiver: rx,
+            kill_flag,
+            child: None,
+        }
+    }
+
+    /// Forward a stream that is read on the tokio runtime, such as the
+    /// Containers tab's log backends. `child`, if any, is killed with the handle.
+    pub fn forwarded(child: Option<TrackedChild>, mut source: tokio::sync::mpsc::Receiver<String>) -> Self {
+        let (tx, rx) = mpsc::sync_channel::<String>(1000);
+        let kill_flag = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&kill_flag);
+        thread::spawn(move || {
+            while let Some(line) = source.blocking_recv() {
+                if flag.load(Ordering::Acquire) || tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        LogStreamHandle {
+            receiver: rx,
+            kill_flag,
+            child,
+        }
+    }
+
+    /// A stream that ended before it started, carrying only `message`.
+    pub fn failed(message: String) -> Self {
+        let (tx, rx) = mpsc::sync_channel::<String>(1);
+        let _ = tx.try_send(format!("[error] {}", message));
+        LogStreamHandle {
+            receiver: rx,
+            kill_flag: Arc::new(AtomicBool::new(false)),
+            child: None,
+        }
     }
 }
 
+/// How long a cached inspect result is trusted before it is re-fetched anyway.
+const INSPECT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Cache of per-object `docker ... inspect` results, keyed by object ID.
+///
+/// The `ls` commands don't expose an object's `Version.Index`, so a fingerprint
+/// built from the listed fields an update would change stands in for it. An
+/// entry is re-fetched when it is missing, its fingerprint changed, or it is
+/// older than the TTL.
+pub struct InspectCache<T = String> {
+    ttl: Duration,
+    entries: HashMap<String, CacheEntry<T>>,
+}
+
+struct CacheEntry<T> {
+    value: T,
+    fingerprint: String,
+    fetched_at: Instant,
+}
+
+impl<T> InspectCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Return the IDs (from `(id, fingerprint)` pairs) whose cached value must be re-fetched.
+    pub fn stale_ids<'a>(&self, items: &[(&'a str, String)], now: Instant) -> Vec<&'a str> {
+        items
+            .iter()
+            .filter(|(id, fingerprint)| match self.entries.get(*id) {
+                Some(entry) => {
+                    entry.fingerprint != *fingerprint
+                        || now.duration_since(entry.fetched_at) >= self.ttl
+                }
+                None => true,
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    pub fn insert(&mut self, id: &str, value: T, fingerprint: String, now: Instant) {
+        self.entries.insert(
+            id.to_string(),
+            CacheEntry {
+                value,
+                fingerprint,
+                fetched_at: now,
+            },
+        );
+    }
+
+    /// Cache what a batch inspect returned for the `(id, fingerprint)` pairs.
+    /// IDs missing from `fetched`, as when the inspect failed, keep their
+    /// previous entry and stay stale, so the next tick tries again.
+    pub fn insert_fetched(&mut self, items: &[(&str, String)], mut fetched: HashMap<String, T>, now: Instant) {
+        for (id, fingerprint) in items {
+            if let Some(value) = fetched.remove(*id) {
+                self.insert(id, value, fingerprint.clone(), now);
+            }
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&T> {
+        self.entries.get(id).map(|e| &e.value)
+    }
+
+    /// Drop entries for objects that no longer exist.
+    pub fn retain_ids(&mut self, ids: &[&str]) {
+        self.entries.retain(|id, _| ids.contains(&id.as_str()));
+    }
+}
+
+impl<T> Default for InspectCache<T> {
+    fn default() -> Self {
+        Self::new(INSPECT_CACHE_TTL)
+    }
+}
+
+/// Inspect caches for the swarm objects refreshed every tick, shared with the
+/// refresh workers so a timed-out refresh doesn't lose them.
+#[derive(Default)]
+pub struct SwarmCache {
+    pub node_details: Arc<Mutex<InspectCache<NodeDetails>>>,
+    pub stack_labels: Arc<Mutex<InspectCache<ServiceLabels>>>,
+}
+
+/// Label on a service naming the compose file(s) it was deployed from,
+/// comma-separated. `docker stack deploy` does not set it; it is the label
+/// `docker compose` puts on containers, set in `deploy.labels` by hand or by
+/// deploy tooling.
+pub const CONFIG_FILES_LABEL: &str = "com.docker.compose.project.config_files";
+
+/// The service labels sitrep reads from `docker service inspect`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ServiceLabels {
+    pub stack: String,
+    pub config_files: String,
+}
+
 /// Typed struct for docker info Swarm section (avoids serde_json::Value overhead)
 #[derive(Deserialize)]
 struct DockerInfoSwarm {
@@ -53,34 +217,144 @@ struct DockerInfoPartial {
 
 /// Check if the `docker` CLI binary is available in PATH.
 pub fn is_docker_cli_available() -> bool {
-    Command::new("docker")
-        .arg("version")
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map(|s| s.success())
+    cmd::output(Command::new("docker").arg("version"), cmd::QUICK)
+        .map(|o| o.status.success())
         .unwrap_or(false)
 }
 
-/// Detect whether Docker is in Swarm mode by querying `docker info`.
-/// Returns Some(SwarmClusterInfo) if swarm is active, None otherwise.
-pub fn detect_swarm() -> Option<SwarmClusterInfo> {
-    let output = Command::new("docker")
-        .args(["info", "--format", "{{json .}}"])
-        .output()
-        .ok()?;
+/// The Engine API, driven by the tokio runtime `DockerMonitor` uses, for the
+/// Swarm queries bollard has endpoints for: the swarm state in `/info` and the
+/// service list. Nodes, tasks and service logs still go through the CLI, as
+/// does any query the API fails, e.g. when the socket cannot be reached.
+#[derive(Clone)]
+pub struct EngineApi {
+    rt: Arc<Runtime>,
+    client: Docker,
+}
+
+impl EngineApi {
+    /// A client for `DOCKER_HOST` or the local socket. It connects on first
+    /// use, so a daemon that is down only shows when a query fails.
+    pub fn new(rt: Arc<Runtime>) -> Option<Self> {
+        let client = Docker::connect_with_defaults()
+            .inspect_err(|e| tracing::debug!("Engine API unavailable, using the docker CLI: {}", e))
+            .ok()?;
+        Some(Self { rt, client })
+    }
+
+    fn swarm_info(&self) -> Result<Option<SwarmClusterInfo>, SitrepError> {
+        let info = self.rt.block_on(self.client.info()).map_err(|e| SitrepError::from_bollard("Docker info failed", e))?;
+        let Some(swarm) = info.swarm else { return Ok(None) };
+        if swarm.local_node_state != Some(LocalNodeState::ACTIVE) {
+            tracing::debug!("Swarm not active, local node state {:?}", swarm.local_node_state);
+            return Ok(None);
+        }
+        Ok(Some(SwarmClusterInfo {
+            node_id: swarm.node_id.unwrap_or_default(),
+            node_addr: swarm.node_addr.unwrap_or_default(),
+            is_manager: swarm.control_available.unwrap_or(false),
+            managers: swarm.managers.unwrap_or(0).max(0) as u32,
+            nodes_total: swarm.nodes.unwrap_or(0).max(0) as u32,
+        }))
+    }
+
+    fn list_services(&self) -> Result<Vec<SwarmServiceInfo>, SitrepError> {
+        let options = ListServicesOptions::<String> { status: true, ..Default::default() };
+        let services = self
+            .rt
+            .block_on(self.client.list_services(Some(options)))
+            .map_err(|e| SitrepError::from_bollard("Failed to list services", e))?;
+        Ok(services.iter().map(service_from_api).collect())
+    }
+}
+
+/// A service as `docker service ls` would show it, with the stack and
+/// compose-file labels the CLI path has to inspect for.
+fn service_from_api(service: &Service) -> SwarmServiceInfo {
+    let spec = service.spec.as_ref();
+    let mode = spec.and_then(|s| s.mode.as_ref());
+    let mode = match mode {
+        Some(m) if m.global.is_some() => "global",
+        Some(m) if m.replicated_job.is_some() => "replicated job",
+        Some(m) if m.global_job.is_some() => "global job",
+        _ => "replicated",
+    };
+    let status = service.service_status.as_ref();
+    let replicas = format!(
+        "{}/{}",
+        status.and_then(|s| s.running_tasks).unwrap_or(0),
+        status.and_then(|s| s.desired_tasks).unwrap_or(0)
+    );
+    let image = spec
+        .and_then(|s| s.task_template.as_ref())
+        .and_then(|t| t.container_spec.as_ref())
+        .and_then(|c| c.image.as_deref())
+        .unwrap_or("");
+    // Only ingress ports are published on every node, as the CLI lists them
+    let ports: Vec<String> = service
+        .endpoint
+        .as_ref()
+        .and_then(|e| e.ports.as_ref())
+        .into_iter()
+        .flatten()
+        .filter(|p| p.publish_mode != Some(EndpointPortConfigPublishModeEnum::HOST))
+        .filter_map(|p| {
+            let protocol = p.protocol.map(|p| p.to_string()).filter(|p| !p.is_empty()).unwrap_or_else(|| "tcp".to_string());
+            Some(format!("*:{}->{}/{}", p.published_port?, p.target_port?, protocol))
+        })
+        .collect();
+    let label = |key: &str| spec.and_then(|s| s.labels.as_ref()).and_then(|l| l.get(key)).cloned().unwrap_or_default();
+    let id = service.id.as_deref().unwrap_or("");
+    SwarmServiceInfo {
+        // Short like `docker service ls`, so an ID reads the same either way
+        id: id.chars().take(12).collect(),
+        name: spec.and_then(|s| s.name.clone()).unwrap_or_default(),
+        mode: mode.to_string(),
+        replicas,
+        image: familiar_image(image).to_string(),
+        ports: ports.join(", "),
+        stack: label("com.docker.stack.namespace"),
+        config_files: label(CONFIG_FILES_LABEL),
+    }
+}
+
+/// An image reference without the digest Swarm pins a tag to, as the CLI
+/// shows it: "nginx:1.27@sha256:..." is "nginx:1.27". A reference with only
+/// a digest keeps it.
+fn familiar_image(image: &str) -> &str {
+    match image.split_once('@') {
+        Some((named, _)) if named.rsplit('/').next().is_some_and(|last| last.contains(':')) => named,
+        _ => image,
+    }
+}
+
+/// Detect whether Docker is in Swarm mode, through the Engine API or else
+/// `docker info`. Returns Some(SwarmClusterInfo) if swarm is active, None
+/// otherwise.
+pub fn detect_swarm(api: Option<&EngineApi>) -> Option<SwarmClusterInfo> {
+    if let Some(api) = api {
+        match api.swarm_info() {
+            Ok(info) => return info,
+            Err(e) => tracing::debug!("Swarm detection through the Engine API failed, trying the CLI: {}", e),
+        }
+    }
+    let output = cmd::output(Command::new("docker").args(["info", "--format", "{{json .}}"]), cmd::QUICK).ok()?;
 
     if !output.status.success() {
         return None;
     }
 
     let text = String::from_utf8_lossy(&output.stdout);
-    let info: DockerInfoPartial = serde_json::from_str(text.trim()).ok()?;
+    let info: DockerInfoPartial = serde_json::from_str(text.trim())
+        .inspect_err(|e| tracing::debug!("docker info not parsed: {}", e))
+        .ok()?;
     let swarm = info.swarm?;
 
     if swarm.local_node_state != "active" {
+        tracing::debug!("Swarm not active, local node state {:?}", swarm.local_node_state);
         return None;
     }
+    tracing::debug!("Swarm active, manager={}, {} nodes", swarm.control_available, swarm.nodes);
 
     Some(SwarmClusterInfo {
         node_id: swarm.node_id,
@@ -91,19 +365,20 @@ pub fn detect_swarm() -> Option<SwarmClusterInfo> {
     })
 }
 
-/// List all nodes in the Swarm cluster.
-pub fn list_nodes() -> Result<Vec<SwarmNodeInfo>, String> {
-    let output = Command::new("docker")
-        .args(["node", "ls", "--format", "{{json .}}"])
-        .output()
-        .map_err(|e| format!("Failed to run docker node ls: {}", e))?;
-
+/// Run `docker <args>` and return its stdout. `name` is the command as
+/// errors mention it, e.g. "docker node ls".
+fn docker<S: AsRef<std::ffi::OsStr>>(name: &str, args: &[S], timeout: Duration) -> Result<String, SitrepError> {
+    let output = cmd::output(Command::new("docker").args(args), timeout)
+        .map_err(|e| SitrepError::from_io(&format!("Failed to run {}", name), &e))?;
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        return Err(format!("docker node ls failed: {}", stderr));
+        return Err(SitrepError::from_stderr(&format!("{} failed", name), &String::from_utf8_lossy(&output.stderr)));
     }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
 
-    let text = String::from_utf8_lossy(&output.stdout);
+/// List all nodes in the Swarm cluster.
+pub fn list_nodes() -> Result<Vec<SwarmNodeInfo>, SitrepError> {
+    let text = docker("docker node ls", &["node", "ls", "--format", "{{json .}}"], cmd::QUICK)?;
     Ok(text.lines()
         .filter(|l| !l.trim().is_empty())
         .filter_map(|line| {
@@ -116,9 +391,20 @@ pub fn list_nodes() -> Result<Vec<SwarmNodeInfo>, String> {
         .collect())
 }
 
-/// Batch-fetch IP addresses for all nodes in a single `docker node inspect` call.
-/// Returns a map of node ID → IP address.
-pub fn batch_get_node_ips(nodes: &[SwarmNodeInfo]) -> HashMap<String, String> {
+/// What `docker node inspect` adds to a `docker node ls` row: the address,
+/// and the labels and platform placement constraints are matched against.
+#[derive(Clone, Debug, Default)]
+pub struct NodeDetails {
+    pub ip: String,
+    pub labels: BTreeMap<String, String>,
+    pub engine_labels: BTreeMap<String, String>,
+    pub os: String,
+    pub arch: String,
+}
+
+/// Batch-fetch node details for all nodes in a single `docker node inspect` call.
+/// Returns a map of node ID → details.
+pub fn batch_inspect_nodes(nodes: &[SwarmNodeInfo]) -> HashMap<String, NodeDetails> {
     let ids: Vec<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
     if ids.is_empty() {
         return HashMap::new();
@@ -128,48 +414,86 @@ pub fn batch_get_node_ips(nodes: &[SwarmNodeInfo]) -> HashMap<String, String> {
         "node".to_string(),
         "inspect".to_string(),
         "--format".to_string(),
-        "{{.ID}} {{.Status.Addr}}".to_string(),
+        "{{.ID}}\t{{.Status.Addr}}\t{{json .Spec.Labels}}\t{{json .Description.Engine.Labels}}\t{{.Description.Platform.OS}}\t{{.Description.Platform.Architecture}}".to_string(),
     ];
     for id in &ids {
         args.push(id.to_string());
     }
 
-    let output = match Command::new("docker").args(&args).output() {
+    let output = match cmd::output(Command::new("docker").args(&args), cmd::QUICK) {
         Ok(o) if o.status.success() => o,
         _ => return HashMap::new(),
     };
 
     let text = String::from_utf8_lossy(&output.stdout);
-    let mut result = HashMap::new();
-    for line in text.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        if let Some((id, ip)) = line.split_once(' ') {
-            let ip = ip.trim();
-            if !ip.is_empty() {
-                result.insert(id.to_string(), ip.to_string());
-            }
-        }
-    }
-    result
+    text.lines().filter_map(parse_node_details).collect()
+}
+
+/// One `batch_inspect_nodes` line: ID, address, node labels, engine labels,
+/// OS and architecture, tab-separated. Labels are JSON and may be `null`.
+fn parse_node_details(line: &str) -> Option<(String, NodeDetails)> {
+    let mut fields = line.trim().split('\t');
+    let id = fields.next().filter(|id| !id.is_empty())?;
+    let mut next = || fields.next().unwrap_or("").trim();
+    let ip = next().to_string();
+    let labels = |json: &str| serde_json::from_str::<Option<BTreeMap<String, String>>>(json).ok().flatten().unwrap_or_default();
+    let details = NodeDetails {
+        ip,
+        labels: labels(next()),
+        engine_labels: labels(next()),
+        os: next().to_string(),
+        arch: next().to_string(),
+    };
+    Some((id.to_string(), details))
 }
 
-/// List all services in the Swarm.
-/// Uses a single batch `docker service inspect` to get stack labels for all services.
-pub fn list_services() -> Result<Vec<SwarmServiceInfo>, String> {
-    let output = Command::new("docker")
-        .args(["service", "ls", "--format", "{{json .}}"])
-        .output()
-        .map_err(|e| format!("Failed to run docker service ls: {}", e))?;
+/// Fill in the address, labels and platform of each node, inspecting only nodes
+/// whose cached details are stale. A node's status and availability are its
+/// fingerprint: a node rejoining after being down may come back with a new
+/// address. Label edits show up within the cache TTL.
+pub fn apply_node_details(nodes: &mut [SwarmNodeInfo], cache: &mut InspectCache<NodeDetails>) {
+    let now = Instant::now();
+    let fingerprints: Vec<(&str, String)> = nodes
+        .iter()
+        .map(|n| (n.id.as_str(), format!("{}|{}", n.status, n.availability)))
+        .collect();
+    let stale: Vec<&str> = cache.stale_ids(&fingerprints, now);
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        return Err(format!("docker service ls failed: {}", stderr));
+    if !stale.is_empty() {
+        let stale_nodes: Vec<SwarmNodeInfo> = nodes
+            .iter()
+            .filter(|n| stale.contains(&n.id.as_str()))
+            .cloned()
+            .collect();
+        let stale_items: Vec<(&str, String)> = fingerprints.iter().filter(|(id, _)| stale.contains(id)).cloned().collect();
+        cache.insert_fetched(&stale_items, batch_inspect_nodes(&stale_nodes), now);
     }
 
-    let text = String::from_utf8_lossy(&output.stdout);
+    let ids: Vec<&str> = fingerprints.iter().map(|(id, _)| *id).collect();
+    cache.retain_ids(&ids);
+    for node in nodes.iter_mut() {
+        if let Some(details) = cache.get(&node.id) {
+            node.ip_address = details.ip.clone();
+            node.labels = details.labels.clone();
+            node.engine_labels = details.engine_labels.clone();
+            node.os = details.os.clone();
+            node.arch = details.arch.clone();
+        }
+    }
+}
+
+/// List all services in the Swarm, through the Engine API when it answers.
+/// On the CLI path stack labels come from `cache`; a single batch `docker service
+/// inspect` fetches labels only for services that are new or whose image/mode
+/// changed since last tick.
+pub fn list_services(api: Option<&EngineApi>, cache: &mut InspectCache<ServiceLabels>) -> Result<Vec<SwarmServiceInfo>, SitrepError> {
+    if let Some(api) = api {
+        match api.list_services() {
+            Ok(services) => return Ok(services),
+            Err(e) => tracing::debug!("Service list through the Engine API failed, trying the CLI: {}", e),
+        }
+    }
+    let text = docker("docker service ls", &["service", "ls", "--format", "{{json .}}"], cmd::QUICK)?;
     let mut services: Vec<SwarmServiceInfo> = text
         .lines()
         .filter(|l| !l.trim().is_empty())
@@ -180,39 +504,59 @@ pub fn list_services() -> Result<Vec<SwarmServiceInfo>, String> {
         return Ok(services);
     }
 
-    // Batch: fetch all stack labels in one call
-    let stack_labels = batch_get_stack_labels(&services);
+    let now = Instant::now();
+    let fingerprints: Vec<(&str, String)> = services
+        .iter()
+        .map(|s| (s.id.as_str(), format!("{}|{}", s.image, s.mode)))
+        .collect();
+    let stale: Vec<&str> = cache.stale_ids(&fingerprints, now);
+
+    if !stale.is_empty() {
+        // Batch: fetch stale stack labels in one call
+        let stale_services: Vec<SwarmServiceInfo> = services
+            .iter()
+            .filter(|s| stale.contains(&s.id.as_str()))
+            .cloned()
+            .collect();
+        let stale_items: Vec<(&str, String)> = fingerprints.iter().filter(|(id, _)| stale.contains(id)).cloned().collect();
+        cache.insert_fetched(&stale_items, batch_get_stack_labels(&stale_services), now);
+    }
+
+    let ids: Vec<&str> = fingerprints.iter().map(|(id, _)| *id).collect();
+    cache.retain_ids(&ids);
     for svc in &mut services {
-        if let Some(label) = stack_labels.get(&svc.id) {
-            svc.stack = label.clone();
+        if let Some(labels) = cache.get(&svc.id) {
+            svc.stack = labels.stack.clone();
+            svc.config_files = labels.config_files.clone();
         }
     }
 
     Ok(services)
 }
 
-/// Batch-fetch stack labels for all services in a single `docker service inspect` call.
-fn batch_get_stack_labels(services: &[SwarmServiceInfo]) -> HashMap<String, String> {
+/// Batch-fetch stack and compose-file labels for all services in a single
+/// `docker service inspect` call.
+fn batch_get_stack_labels(services: &[SwarmServiceInfo]) -> HashMap<String, ServiceLabels> {
     let ids: Vec<&str> = services.iter().map(|s| s.id.as_str()).collect();
     if ids.is_empty() {
         return HashMap::new();
     }
 
-    // Build args: docker service inspect --format '{{.ID}} {{index .Spec.Labels "com.docker.stack.namespace"}}' id1 id2 ...
+    // Build args: docker service inspect --format '{{.ID}}\t{{index .Spec.Labels "com.docker.stack.namespace"}}\t...' id1 id2 ...
     let mut args = vec![
         "service".to_string(),
         "inspect".to_string(),
         "--format".to_string(),
-        r#"{{.ID}} {{index .Spec.Labels "com.docker.stack.namespace"}}"#.to_string(),
+        format!(
+            r#"{{{{.ID}}}}\t{{{{index .Spec.Labels "com.docker.stack.namespace"}}}}\t{{{{index .Spec.Labels "{}"}}}}"#,
+            CONFIG_FILES_LABEL
+        ),
     ];
     for id in &ids {
         args.push(id.to_string());
     }
 
-    let output = match Command::new("docker")
-        .args(&args)
-        .output()
-    {
+    let output = match cmd::output(Command::new("docker").args(&args), cmd::QUICK) {
         Ok(o) if o.status.success() => o,
         _ => return HashMap::new(),
     };
@@ -224,14 +568,16 @@ fn batch_get_stack_labels(services: &[SwarmServiceInfo]) -> HashMap<String, Stri
         if line.is_empty() {
             continue;
         }
-        // Format: "SERVICE_ID STACK_NAME" or "SERVICE_ID <no value>"
-        if let Some(space_pos) = line.find(' ') {
-            let id = &line[..space_pos];
-            let label = &line[space_pos + 1..];
-            let label = if label == "<no value>" || label.is_empty() {
-                String::new()
-            } else {
-                label.to_string()
+        // Format: "SERVICE_ID\tSTACK_NAME\tCONFIG_FILES", "<no value>" for a missing label
+        let mut fields = line.split('\t');
+        if let Some(id) = fields.next() {
+            let mut label = || match fields.next().map(str::trim) {
+                None | Some("<no value>") => String::new(),
+                Some(value) => value.to_string(),
+            };
+            let labels = ServiceLabels {
+                stack: label(),
+                config_files: label(),
             };
             // Match: `docker service inspect` returns full 64-char IDs,
             // `docker service ls` returns short ~12-char IDs.
@@ -239,7 +585,7 @@ fn batch_get_stack_labels(services: &[SwarmServiceInfo]) -> HashMap<String, Stri
             // length guard of 10 chars to avoid ambiguous prefix collisions.
             for svc_id in &ids {
                 if svc_id.len() >= 10 && id.starts_with(svc_id) {
-                    result.insert(svc_id.to_string(), label.clone());
+                    result.insert(svc_id.to_string(), labels.clone());
                     break;
                 }
             }
@@ -250,7 +596,7 @@ fn batch_get_stack_labels(services: &[SwarmServiceInfo]) -> HashMap<String, Stri
 
 /// Batch-fetch tasks for multiple services in a single subprocess call.
 /// Returns all tasks with `DesiredState == "Running"`.
-pub fn list_tasks_for_services(service_ids: &[&str]) -> Result<Vec<SwarmTaskInfo>, String> {
+pub fn list_tasks_for_services(service_ids: &[&str]) -> Result<Vec<SwarmTaskInfo>, SitrepError> {
     if service_ids.is_empty() {
         return Ok(Vec::new());
     }
@@ -260,17 +606,7 @@ pub fn list_tasks_for_services(service_ids: &[&str]) -> Result<Vec<SwarmTaskInfo
         args.push(id);
     }
 
-    let output = Command::new("docker")
-        .args(&args)
-        .output()
-        .map_err(|e| format!("Failed to run docker service ps: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        return Err(format!("docker service ps failed: {}", stderr));
-    }
-
-    let text = String::from_utf8_lossy(&output.stdout);
+    let text = docker("docker service ps", &args, cmd::QUICK)?;
     Ok(text
         .lines()
         .filter(|l| !l.trim().is_empty())
@@ -279,107 +615,318 @@ pub fn list_tasks_for_services(service_ids: &[&str]) -> Result<Vec<SwarmTaskInfo
 }
 
 /// List tasks (replicas) for a specific service.
-pub fn list_service_tasks(service_id: &str) -> Result<Vec<SwarmTaskInfo>, String> {
-    let output = Command::new("docker")
-        .args(["service", "ps", service_id, "--format", "{{json .}}", "--no-trunc"])
-        .output()
-        .map_err(|e| format!("Failed to run docker service ps: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        return Err(format!("docker service ps failed: {}", stderr));
-    }
-
-    let text = String::from_utf8_lossy(&output.stdout);
+pub fn list_service_tasks(service_id: &str) -> Result<Vec<SwarmTaskInfo>, SitrepError> {
+    let text = docker("docker service ps", &["service", "ps", service_id, "--format", "{{json .}}", "--no-trunc"], cmd::QUICK)?;
     Ok(text.lines()
         .filter(|l| !l.trim().is_empty())
         .filter_map(|line| serde_json::from_str(line).ok())
         .collect())
 }
 
-/// Force-update a service (rolling restart of all replicas).
-pub fn force_update_service(service_id: &str) -> Result<(), String> {
-    let output = Command::new("docker")
-        .args(["service", "update", "--force", service_id])
-        .output()
-        .map_err(|e| e.to_string())?;
+/// Swarm's gossip port, open on every node, managers and workers alike.
+pub const GOSSIP_PORT: u16 = 7946;
+/// Cluster management port, open on managers.
+pub const MANAGER_PORT: u16 = 2377;
+/// Overlay network data plane (VXLAN), UDP.
+pub const VXLAN_PORT: u16 = 4789;
+/// Connects per node in one probe.
+const PROBE_ATTEMPTS: u32 = 3;
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+/// How long to wait for an ICMP port unreachable after a UDP datagram.
+const UDP_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Probe each (node ID, address, is manager) in parallel: TCP connects to the
+/// gossip port for latency and loss, which needs no privileges unlike ICMP,
+/// then the manager port on managers and the VXLAN port. Unparseable
+/// addresses are skipped. Takes at most about 5 seconds.
+pub fn probe_nodes(targets: &[(String, String, bool)]) -> HashMap<String, NodeProbe> {
+    let handles: Vec<_> = targets
+        .iter()
+        .filter_map(|(id, ip, manager)| {
+            let ip: IpAddr = ip.parse().ok()?;
+            let (id, manager) = (id.clone(), *manager);
+            Some(thread::spawn(move || (id, probe_addr(ip, manager))))
+        })
+        .collect();
+    handles.into_iter().filter_map(|h| h.join().ok()).collect()
+}
+
+fn probe_addr(ip: IpAddr, manager: bool) -> NodeProbe {
+    let mut rtts = Vec::new();
+    let mut refused = 0;
+    for _ in 0..PROBE_ATTEMPTS {
+        match check_tcp(SocketAddr::new(ip, GOSSIP_PORT)) {
+            Ok(rtt) => rtts.push(rtt),
+            // The host answered, so it still counts toward latency
+            Err(("refused", rtt)) => {
+                refused += 1;
+                rtts.push(rtt);
+            }
+            Err(_) => {}
+        }
+    }
+
+    // A gossip port that only times out is already reported as loss
+    let mut failed_ports = Vec::new();
+    if refused == PROBE_ATTEMPTS {
+        failed_ports.push(PortFailure { port: GOSSIP_PORT, proto: "tcp".into(), reason: "refused".into() });
+    }
+    if manager
+        && let Err((reason, _)) = check_tcp(SocketAddr::new(ip, MANAGER_PORT)) {
+            failed_ports.push(PortFailure { port: MANAGER_PORT, proto: "tcp".into(), reason: reason.into() });
+        }
+    if udp_port_closed(SocketAddr::new(ip, VXLAN_PORT)) {
+        failed_ports.push(PortFailure { port: VXLAN_PORT, proto: "udp".into(), reason: "closed".into() });
+    }
+
+    NodeProbe {
+        sent: PROBE_ATTEMPTS,
+        received: rtts.len() as u32,
+        rtt_ms: (!rtts.is_empty()).then(|| rtts.iter().sum::<f64>() / rtts.len() as f64),
+        failed_ports,
+    }
+}
+
+/// Connect time in milliseconds, or why the connect failed and how long it took.
+fn check_tcp(addr: SocketAddr) -> Result<f64, (&'static str, f64)> {
+    let started = Instant::now();
+    let result = TcpStream::connect_timeout(&addr, PROBE_TIMEOUT);
+    let elapsed = started.elapsed().as_secs_f64() * 1000.0;
+    match result {
+        Ok(_) => Ok(elapsed),
+        Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => Err(("refused", elapsed)),
+        Err(e) if e.kind() == std::io::ErrorKind::TimedOut => Err(("timeout", elapsed)),
+        Err(_) => Err(("unreachable", elapsed)),
+    }
+}
 
-    if output.status.success() {
-        Ok(())
-    } else {
-        let err = String::from_utf8_lossy(&output.stderr).to_string();
-        tracing::warn!("Docker force-update failed for {}: {}", service_id, err.trim());
-        Err(err)
+/// True when a UDP datagram to `addr` draws an ICMP port unreachable. An open
+/// or filtered port stays silent, so silence is not a failure.
+fn udp_port_closed(addr: SocketAddr) -> bool {
+    let local: SocketAddr = if addr.is_ipv4() { ([0, 0, 0, 0], 0).into() } else { ([0u16; 8], 0).into() };
+    let Ok(socket) = UdpSocket::bind(local) else { return false };
+    if socket.connect(addr).is_err() || socket.set_read_timeout(Some(UDP_PROBE_TIMEOUT)).is_err() {
+        return false;
     }
+    // An empty datagram is not a valid VXLAN frame and is dropped
+    if socket.send(&[]).is_err() {
+        return false;
+    }
+    let mut buf = [0u8; 16];
+    matches!(socket.recv(&mut buf), Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused)
+}
+
+/// Name of the ingress network, which carries the routing mesh for published
+/// ports. `Ok(None)` when it has been removed.
+pub fn ingress_network() -> Result<Option<String>, SitrepError> {
+    let ids = docker("docker network ls", &["network", "ls", "-q", "--filter", "driver=overlay"], cmd::QUICK)?;
+    let mut args = vec!["network", "inspect", "--format", "{{.Name}}\t{{.Ingress}}"];
+    args.extend(ids.split_whitespace());
+    if args.len() == 4 {
+        return Ok(None);
+    }
+
+    Ok(parse_ingress(&docker("docker network inspect", &args, cmd::QUICK)?))
+}
+
+/// The network marked ingress among "name\tingress" lines.
+fn parse_ingress(text: &str) -> Option<String> {
+    text.lines()
+        .filter_map(|line| line.trim().split_once('\t'))
+        .find(|(_, ingress)| *ingress == "true")
+        .map(|(name, _)| name.to_string())
+}
+
+/// Creation time, last state change and state of tasks, by full task ID.
+pub fn inspect_task_times(task_ids: &[&str]) -> Result<HashMap<String, TaskTimes>, SitrepError> {
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut args = vec![
+        "inspect",
+        "--type",
+        "task",
+        "--format",
+        "{{.ID}}\t{{json .CreatedAt}}\t{{json .Status.Timestamp}}\t{{.Status.State}}",
+    ];
+    args.extend(task_ids);
+
+    let text = docker("docker inspect", &args, cmd::QUICK)?;
+    Ok(text.lines().filter_map(parse_task_times).collect())
+}
+
+/// One `inspect_task_times` line: ID, JSON creation and status timestamps, state.
+fn parse_task_times(line: &str) -> Option<(String, TaskTimes)> {
+    let mut fields = line.trim().split('\t');
+    let id = fields.next().filter(|id| !id.is_empty())?;
+    let mut time = || {
+        let json = fields.next()?;
+        let stamp: String = serde_json::from_str(json).ok()?;
+        chrono::DateTime::parse_from_rfc3339(&stamp).ok().map(|t| t.timestamp())
+    };
+    let created = time()?;
+    let updated = time()?;
+    let state = fields.next().unwrap_or("").trim().to_lowercase();
+    Some((id.to_string(), TaskTimes { created, updated, state }))
+}
+
+/// Placement constraints and last-update diff of a service, from `docker service inspect`.
+pub fn inspect_service(service_id: &str) -> Result<ServiceSpecInfo, SitrepError> {
+    let text = docker("docker service inspect", &["service", "inspect", service_id], cmd::QUICK)?;
+    let inspect: Vec<serde_json::Value> = serde_json::from_str(&text)
+        .map_err(|e| SitrepError::ParseError(format!("Unexpected docker service inspect output: {}", e)))?;
+    inspect
+        .first()
+        .map(ServiceSpecInfo::from_inspect)
+        .ok_or_else(|| SitrepError::Failed(format!("No such service: {}", service_id)))
+}
+
+/// Why a task's image could not be pulled, read from the task error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PullFailure {
+    RateLimited,
+    NoSuchImage,
+    AccessDenied,
+}
+
+impl PullFailure {
+    /// Classify a task error such as "No such image: api:1.4" or "toomanyrequests:
+    /// You have reached your pull rate limit". Other failures return None.
+    pub fn classify(error: &str) -> Option<Self> {
+        let error = error.to_lowercase();
+        if error.contains("toomanyrequests") || error.contains("pull rate limit") {
+            Some(PullFailure::RateLimited)
+        } else if error.contains("no such image") || error.contains("manifest unknown") {
+            Some(PullFailure::NoSuchImage)
+        } else if error.contains("pull access denied") || error.contains("unauthorized") {
+            Some(PullFailure::AccessDenied)
+        } else {
+            None
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PullFailure::RateLimited => "PULL RATE LIMITED",
+            PullFailure::NoSuchImage => "IMAGE NOT FOUND",
+            PullFailure::AccessDenied => "PULL DENIED",
+        }
+    }
+}
+
+/// The registry an image reference pulls from, using Docker's rule: the first
+/// path component is a registry host only if it has a '.' or ':' or is
+/// "localhost", otherwise the image lives on Docker Hub.
+pub fn image_registry(image: &str) -> String {
+    match image.split_once('/') {
+        Some((host, _)) if host.contains('.') || host.contains(':') || host == "localhost" => host.to_string(),
+        _ => "docker.io".to_string(),
+    }
+}
+
+/// Service name of a task named e.g. "stack_service.1", by stripping the last ".N".
+pub fn task_service_name(task_name: &str) -> &str {
+    task_name.rfind('.').map_or(task_name, |dot| &task_name[..dot])
+}
+
+/// Tasks of the given services, current and historical, that carry an error.
+pub fn list_failed_tasks(service_ids: &[String]) -> Result<Vec<SwarmTaskInfo>, SitrepError> {
+    if service_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut args = vec!["service", "ps", "--no-trunc", "--format", "{{json .}}"];
+    args.extend(service_ids.iter().map(|s| s.as_str()));
+
+    let text = docker("docker service ps", &args, cmd::QUICK)?;
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str::<SwarmTaskInfo>(line).ok())
+        .filter(|task| !task.error.is_empty())
+        .collect())
+}
+
+/// Force-update a service (rolling restart of all replicas).
+pub fn force_update_service(service_id: &str) -> Result<(), SitrepError> {
+    docker("docker service update", &["service", "update", "--force", service_id], cmd::SLOW)
+        .map(|_| ())
+        .inspect_err(|e| tracing::warn!("Docker force-update failed for {}: {}", service_id, e))
 }
 
 /// Scale a service to a given number of replicas.
-pub fn scale_service(service_id: &str, replicas: u32) -> Result<(), String> {
+pub fn scale_service(service_id: &str, replicas: u32) -> Result<(), SitrepError> {
     let arg = format!("{}={}", service_id, replicas);
-    let output = Command::new("docker")
-        .args(["service", "scale", &arg])
-        .output()
-        .map_err(|e| e.to_string())?;
+    docker("docker service scale", &["service", "scale", &arg], cmd::SLOW)
+        .map(|_| ())
+        .inspect_err(|e| tracing::warn!("Docker scale failed for {} to {}: {}", service_id, replicas, e))
+}
 
-    if output.status.success() {
-        Ok(())
-    } else {
-        let err = String::from_utf8_lossy(&output.stderr).to_string();
-        tracing::warn!("Docker scale failed for {} to {}: {}", service_id, replicas, err.trim());
-        Err(err)
-    }
+/// Set or remove one of a service's labels (`docker service update --label-add/--label-rm`).
+pub fn update_service_label(service_id: &str, edit: &LabelEdit) -> Result<(), SitrepError> {
+    let (flag, arg) = match edit {
+        LabelEdit::Add(_, _) => ("--label-add", edit.to_string()),
+        LabelEdit::Remove(key) => ("--label-rm", key.clone()),
+    };
+    docker("docker service update", &["service", "update", flag, &arg, service_id], cmd::SLOW)
+        .map(|_| ())
+        .inspect_err(|e| tracing::warn!("Docker label update failed for {} ({}): {}", service_id, edit, e))
+}
+
+/// Set (`Some`) or remove (`None`) a label on a node.
+pub fn update_node_label(node_id: &str, key: &str, value: Option<&str>) -> Result<(), SitrepError> {
+    let pair = value.map(|value| format!("{}={}", key, value));
+    let args = match pair {
+        Some(ref pair) => ["node", "update", "--label-add", pair, node_id],
+        None => ["node", "update", "--label-rm", key, node_id],
+    };
+    docker("docker node update", &args, cmd::SLOW)
+        .map(|_| ())
+        .inspect_err(|e| tracing::warn!("Docker node label update failed for {}: {}", node_id, e))
 }
 
 /// Start streaming service logs. Returns a `LogStreamHandle` with the receiver
 /// and a kill mechanism. Call `handle.kill()` to terminate the child process
 /// and avoid zombie processes.
 pub fn tail_service_logs(service_id: &str) -> LogStreamHandle {
+    tail_command(Command::new("docker").args(["service", "logs", "--follow", "--tail", "200", "--timestamps", service_id]))
+}
+
+/// Stream the stdout and stderr lines of a long-running `command`, such as
+/// `kubectl logs --follow`.
+pub(crate) fn tail_command(command: &mut Command) -> LogStreamHandle {
     let (tx, rx) = mpsc::sync_channel::<String>(1000);
     let kill_flag = Arc::new(AtomicBool::new(false));
-    let flag_clone = Arc::clone(&kill_flag);
-
-    let id = service_id.to_string();
-    thread::spawn(move || {
-        let mut child = match Command::new("docker")
-            .args([
-                "service", "logs",
-                "--follow",
-                "--tail", "200",
-                "--timestamps",
-                &id,
-            ])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-        {
-            Ok(c) => c,
-            Err(e) => {
-                let _ = tx.send(format!("[error] Failed to start log stream: {}", e));
-                return;
-            }
-        };
 
-        let Some(stdout) = child.stdout.take() else {
-            let _ = tx.send("[error] Failed to capture stdout from log stream".to_string());
-            return;
-        };
-        let Some(stderr) = child.stderr.take() else {
-            let _ = tx.send("[error] Failed to capture stderr from log stream".to_string());
-            return;
-        };
+    let spawned = match cmd::spawn(command.stdout(Stdio::piped()).stderr(Stdio::piped())) {
+        Ok(s) => s,
+        Err(e) => {
+            let _ = tx.try_send(format!("[error] Failed to start log stream: {}", e));
+            return LogStreamHandle {
+                receiver: rx,
+                kill_flag,
+                child: None,
+            };
+        }
+    };
 
-        // Read stdout in a separate thread
-        let tx_stdout = tx.clone();
-        let flag_stdout = Arc::clone(&flag_clone);
-        let stdout_handle = thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines() {
-                if flag_stdout.load(Ordering::Acquire) {
+    // The child is killed through the handle, which closes both pipes and
+    // ends the readers; each also stops at its next line once flagged.
+    for pipe in [spawned.stdout.map(|p| Box::new(p) as Box<dyn Read + Send>), spawned.stderr.map(|p| Box::new(p) as _)] {
+        let Some(pipe) = pipe else {
+            let _ = tx.try_send("[error] Failed to capture output from log stream".to_string());
+            continue;
+        };
+        let tx = tx.clone();
+        let flag = Arc::clone(&kill_flag);
+        thread::spawn(move || {
+            for line in BufReader::new(pipe).lines() {
+                if flag.load(Ordering::Acquire) {
                     break;
                 }
                 match line {
                     Ok(l) => {
-                        if tx_stdout.send(l).is_err() {
+                        if tx.send(l).is_err() {
                             break;
                         }
                     }
@@ -387,35 +934,171 @@ pub fn tail_service_logs(service_id: &str) -> LogStreamHandle {
                 }
             }
         });
+    }
 
-        // Read stderr in the current thread
-        let reader = BufReader::new(stderr);
-        for line in reader.lines() {
-            if flag_clone.load(Ordering::Acquire) {
-                break;
-            }
-            match line {
-                Ok(l) => {
-                    if tx.send(l).is_err() {
-                        break;
-                    }
-                }
-                Err(_) => break,
-            }
-        }
+    LogStreamHandle {
+        receiver: rx,
+        kill_flag,
+        child: Some(spawned.child),
+    }
+}
 
-        // Kill the child process explicitly to unblock any blocked I/O
-        let _ = child.kill();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Wait for stdout thread with a timeout to avoid hanging
-        let _ = stdout_handle.join();
+    #[test]
+    fn parses_node_inspect_lines() {
+        let (id, node) =
+            parse_node_details("abc123\t10.0.0.5\t{\"zone\":\"east\"}\tnull\tlinux\tx86_64").unwrap();
+        assert_eq!((id.as_str(), node.ip.as_str()), ("abc123", "10.0.0.5"));
+        assert_eq!(node.labels.get("zone").map(String::as_str), Some("east"));
+        assert!(node.engine_labels.is_empty());
+        assert_eq!((node.os.as_str(), node.arch.as_str()), ("linux", "x86_64"));
+        assert!(parse_node_details("").is_none());
+    }
 
-        // Reap the child process
-        let _ = child.wait();
-    });
+    #[test]
+    fn parses_task_inspect_lines() {
+        let (id, times) = parse_task_times(
+            "t1\t\"2024-05-02T10:15:30.123456789Z\"\t\"2024-05-02T10:16:30Z\"\tfailed",
+        )
+        .unwrap();
+        assert_eq!(id, "t1");
+        assert_eq!((times.updated - times.created, times.state.as_str()), (60, "failed"));
+        assert!(parse_task_times("t1\tnull\tnull\trunning").is_none());
+    }
 
-    LogStreamHandle {
-        receiver: rx,
-        kill_flag,
+    #[test]
+    fn classifies_pull_failures_by_registry() {
+        assert_eq!(
+            PullFailure::classify("toomanyrequests: You have reached your pull rate limit."),
+            Some(PullFailure::RateLimited)
+        );
+        assert_eq!(PullFailure::classify("No such image: api:1.4"), Some(PullFailure::NoSuchImage));
+        assert_eq!(
+            PullFailure::classify("pull access denied for private/api, repository does not exist"),
+            Some(PullFailure::AccessDenied)
+        );
+        assert_eq!(PullFailure::classify("task: non-zero exit (1)"), None);
+
+        assert_eq!(image_registry("nginx:1.27"), "docker.io");
+        assert_eq!(image_registry("grafana/grafana:11.0"), "docker.io");
+        assert_eq!(image_registry("ghcr.io/acme/api:1.4@sha256:abc"), "ghcr.io");
+        assert_eq!(image_registry("localhost:5000/api"), "localhost:5000");
+        assert_eq!(task_service_name("shop_api.3"), "shop_api");
+    }
+
+    fn items(pairs: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+        pairs.iter().map(|(id, fp)| (*id, fp.to_string())).collect()
+    }
+
+    #[test]
+    fn inspect_cache_misses_until_inserted() {
+        let mut cache: InspectCache = InspectCache::new(Duration::from_secs(60));
+        let now = Instant::now();
+        let listed = items(&[("a", "v1"), ("b", "v1")]);
+        assert_eq!(cache.stale_ids(&listed, now), vec!["a", "b"]);
+
+        cache.insert("a", "10.0.0.1".into(), "v1".into(), now);
+        assert_eq!(cache.stale_ids(&listed, now), vec!["b"]);
+        assert_eq!(cache.get("a").map(String::as_str), Some("10.0.0.1"));
+    }
+
+    #[test]
+    fn inspect_cache_refetches_on_fingerprint_change() {
+        let mut cache: InspectCache = InspectCache::new(Duration::from_secs(60));
+        let now = Instant::now();
+        cache.insert("a", "stack".into(), "nginx:1|replicated".into(), now);
+        let listed = items(&[("a", "nginx:2|replicated")]);
+        assert_eq!(cache.stale_ids(&listed, now), vec!["a"]);
+    }
+
+    #[test]
+    fn inspect_cache_refetches_after_ttl() {
+        let mut cache: InspectCache = InspectCache::new(Duration::from_secs(60));
+        let then = Instant::now();
+        cache.insert("a", "stack".into(), "v1".into(), then);
+        let listed = items(&[("a", "v1")]);
+        assert!(cache.stale_ids(&listed, then + Duration::from_secs(30)).is_empty());
+        assert_eq!(cache.stale_ids(&listed, then + Duration::from_secs(61)), vec!["a"]);
+    }
+
+    #[test]
+    fn failed_batch_keeps_cached_entries() {
+        let mut cache: InspectCache = InspectCache::new(Duration::from_secs(60));
+        let then = Instant::now();
+        cache.insert("a", "shop".into(), "v1".into(), then);
+        let later = then + Duration::from_secs(61);
+        let listed = items(&[("a", "v1"), ("b", "v1")]);
+        let stale = cache.stale_ids(&listed, later);
+        assert_eq!(stale, vec!["a", "b"]);
+
+        // The inspect failed: nothing came back
+        cache.insert_fetched(&listed, HashMap::new(), later);
+        assert_eq!(cache.get("a").map(String::as_str), Some("shop"));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.stale_ids(&listed, later), vec!["a", "b"]);
+
+        cache.insert_fetched(&listed, HashMap::from([("b".to_string(), "blog".to_string())]), later);
+        assert_eq!(cache.get("b").map(String::as_str), Some("blog"));
+        assert_eq!(cache.stale_ids(&listed, later), vec!["a"]);
+    }
+
+    #[test]
+    fn inspect_cache_retain_drops_removed_objects() {
+        let mut cache: InspectCache = InspectCache::new(Duration::from_secs(60));
+        let now = Instant::now();
+        cache.insert("a", "x".into(), "v1".into(), now);
+        cache.insert("b", "y".into(), "v1".into(), now);
+        cache.retain_ids(&["b"]);
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b").map(String::as_str), Some("y"));
+    }
+
+    #[test]
+    fn port_checks_tell_open_from_refused() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let open = listener.local_addr().unwrap();
+        assert!(check_tcp(open).is_ok());
+        drop(listener);
+        assert!(matches!(check_tcp(open), Err(("refused", _))));
+
+        let udp = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let bound = udp.local_addr().unwrap();
+        assert!(!udp_port_closed(bound));
+        drop(udp);
+        assert!(udp_port_closed(bound));
+    }
+
+    #[test]
+    fn finds_the_ingress_network() {
+        assert_eq!(parse_ingress("web_default\tfalse\ningress\ttrue\n"), Some("ingress".to_string()));
+        assert_eq!(parse_ingress("web_default\tfalse\n"), None);
+    }
+
+    #[test]
+    fn engine_api_services_read_like_service_ls() {
+        let service: Service = serde_json::from_value(serde_json::json!({
+            "ID": "k3clbfqzxm2a9v8r7t6y5u4i3",
+            "Spec": {
+                "Name": "shop_api",
+                "Labels": { "com.docker.stack.namespace": "shop", CONFIG_FILES_LABEL: "/srv/shop/stack.yml" },
+                "TaskTemplate": { "ContainerSpec": { "Image": "registry.local:5000/api:1.4@sha256:0123abcd" } },
+                "Mode": { "Replicated": { "Replicas": 3 } }
+            },
+            "Endpoint": { "Ports": [
+                { "Protocol": "tcp", "TargetPort": 80, "PublishedPort": 8080, "PublishMode": "ingress" },
+                { "Protocol": "udp", "TargetPort": 53, "PublishedPort": 53, "PublishMode": "host" }
+            ] },
+            "ServiceStatus": { "RunningTasks": 2, "DesiredTasks": 3 }
+        }))
+        .unwrap();
+        let info = service_from_api(&service);
+        assert_eq!((info.id.as_str(), info.name.as_str(), info.mode.as_str()), ("k3clbfqzxm2a", "shop_api", "replicated"));
+        assert_eq!((info.replicas.as_str(), info.ports.as_str()), ("2/3", "*:8080->80/tcp"));
+        assert_eq!(info.image, "registry.local:5000/api:1.4");
+        assert_eq!((info.stack.as_str(), info.config_files.as_str()), ("shop", "/srv/shop/stack.yml"));
+        assert_eq!(familiar_image("api@sha256:0123abcd"), "api@sha256:0123abcd");
     }
 }
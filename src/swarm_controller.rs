@@ -1,14 +1,97 @@
-use std::collections::HashMap;
-use std::sync::mpsc;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::model::{
-    SwarmMode, SwarmClusterInfo, SwarmNodeInfo, SwarmServiceInfo,
+    LabelEdit, SwarmMode, SwarmClusterInfo, SwarmNodeInfo, SwarmServiceInfo,
     SwarmTaskInfo, SwarmStackInfo, SwarmUIState, SwarmViewLevel,
-    ServiceLogState,
+    LogPaneSource, ManagerQuorum, NodeProbe, ServiceLogState, ServiceSpecInfo, TaskTimes, SplitLogState,
 };
+use crate::action_queue::{ActionQueue, Job};
+use crate::compose;
+use crate::docker_controller::DockerMonitor;
+use crate::notes::{self, MaintenanceNote};
+use crate::recording::SwarmFrame;
+use crate::error::SitrepError;
 use crate::swarm;
-use crate::swarm::LogStreamHandle;
+use crate::swarm::{LogStreamHandle, PullFailure, SwarmCache};
+
+/// Upper bound on how long a single background refresh may take before the
+/// results that did arrive are applied and the rest are abandoned.
+const SWARM_REFRESH_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a note set here is shown before the node labels are trusted again.
+const NOTE_OVERRIDE: Duration = Duration::from_secs(90);
+/// How often nodes are probed for latency and loss.
+const NODE_PROBE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Which query a `SwarmPart` answers.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SwarmPartKind {
+    Nodes,
+    Services,
+    Tasks,
+    ServiceTasks,
+    FailedTasks,
+    TaskTimes,
+    NodeProbes,
+}
+
+impl SwarmPartKind {
+    fn label(self) -> &'static str {
+        match self {
+            SwarmPartKind::Nodes => "node ls",
+            SwarmPartKind::Services => "service ls",
+            SwarmPartKind::Tasks => "service ps",
+            SwarmPartKind::ServiceTasks => "replica ps",
+            SwarmPartKind::FailedTasks => "failed ps",
+            SwarmPartKind::TaskTimes => "task inspect",
+            SwarmPartKind::NodeProbes => "node probe",
+        }
+    }
+}
+
+/// (current, desired) for a replica count like "2/3" that is short of desired.
+fn replica_shortfall(replicas: &str) -> Option<(u32, u32)> {
+    let (current, desired) = replicas.split_once('/')?;
+    let current: u32 = current.trim().parse().unwrap_or(0);
+    let desired: u32 = desired.trim().parse().unwrap_or(0);
+    (desired > 0 && current < desired).then_some((current, desired))
+}
+
+/// One independently fetched piece of a swarm refresh.
+enum SwarmPart {
+    Nodes(Result<Vec<SwarmNodeInfo>, SitrepError>),
+    Services(Result<Vec<SwarmServiceInfo>, SitrepError>),
+    Tasks(String, Result<Vec<SwarmTaskInfo>, SitrepError>), // (service_id, tasks)
+    ServiceTasks(Result<Vec<SwarmTaskInfo>, SitrepError>),
+    FailedTasks(Result<Vec<SwarmTaskInfo>, SitrepError>),
+    TaskTimes(String, Result<HashMap<String, TaskTimes>, SitrepError>), // (service_id, times)
+    NodeProbes(HashMap<String, NodeProbe>, Result<Option<String>, SitrepError>), // (probes, ingress network)
+}
+
+impl SwarmPart {
+    fn kind(&self) -> SwarmPartKind {
+        match self {
+            SwarmPart::Nodes(..) => SwarmPartKind::Nodes,
+            SwarmPart::Services(..) => SwarmPartKind::Services,
+            SwarmPart::Tasks(..) => SwarmPartKind::Tasks,
+            SwarmPart::ServiceTasks(..) => SwarmPartKind::ServiceTasks,
+            SwarmPart::FailedTasks(..) => SwarmPartKind::FailedTasks,
+            SwarmPart::TaskTimes(..) => SwarmPartKind::TaskTimes,
+            SwarmPart::NodeProbes(..) => SwarmPartKind::NodeProbes,
+        }
+    }
+}
+
+/// An in-flight background refresh.
+struct PendingRefresh {
+    receiver: mpsc::Receiver<SwarmPart>,
+    started: Instant,
+    remaining: Vec<SwarmPartKind>,
+    parts: Vec<SwarmPart>,
+}
 
 /// Manages Docker Swarm data collection, state, and actions.
 pub struct SwarmMonitor {
@@ -23,20 +106,54 @@ pub struct SwarmMonitor {
     pub ui_state: SwarmUIState,
     pub log_state: Option<ServiceLogState>,
     log_handle: Option<LogStreamHandle>,
+    /// First source picked for the split log view, waiting for a second.
+    pub split_mark: Option<LogPaneSource>,
+    pub split_logs: Option<SplitLogState>,
+    split_handles: Vec<LogStreamHandle>,
     pub status_message: Option<String>,
     pub warnings: Vec<String>,
+    /// Tasks of degraded services that carry an error; pull failures among
+    /// them are summarized into warnings.
+    pub failed_tasks: Vec<SwarmTaskInfo>,
+    /// Compose file per stack from the config, over the services' own label.
+    pub stack_files: BTreeMap<String, PathBuf>,
+    /// Differences from its compose file, by stack name.
+    pub stack_drift: HashMap<String, (PathBuf, Vec<String>)>,
+    /// Placement constraints and last-update diff of the service in task view.
+    pub spec: ServiceSpecInfo,
+    /// Task times for the timeline in task view, while it is shown.
+    pub timeline: Option<HashMap<String, TaskTimes>>,
+    /// Latest latency probe of each other node, by node ID.
+    node_probes: HashMap<String, NodeProbe>,
+    last_probe: Option<Instant>,
+    /// Set when the last probe found no ingress network.
+    pub ingress_missing: bool,
     pub docker_cli_available: bool,
-    /// Receiver for background action results (rolling restart, scale).
-    action_receiver: Option<mpsc::Receiver<Result<String, String>>>,
-    /// True while a background action is in flight.
-    pub action_in_progress: bool,
+    /// The Engine API, tried before the CLI for what it covers.
+    api: Option<swarm::EngineApi>,
+    /// Cached inspect results (node IPs, stack labels) reused across ticks.
+    cache: SwarmCache,
+    pending_refresh: Option<PendingRefresh>,
+    /// When cluster data was last refreshed.
+    pub last_updated: Option<Instant>,
+    /// The node or service list failed in that refresh, or it timed out.
+    pub update_failed: bool,
+    /// Rolling restarts and scaling, run in turn.
+    actions: ActionQueue,
+    /// A maintenance note set or cleared here, and when.
+    note_override: Option<(Option<MaintenanceNote>, Instant)>,
+    /// The cluster is made up (`--demo`), counted from this start.
+    demo: Option<Instant>,
+    /// The cluster is shown from a recording (`--replay`); nothing is queried.
+    replayed: bool,
 }
 
 impl SwarmMonitor {
-    pub fn new() -> Self {
+    pub fn new(rt: Arc<tokio::runtime::Runtime>) -> Self {
+        let api = swarm::EngineApi::new(rt);
         let docker_cli_available = swarm::is_docker_cli_available();
-        let cluster_info = if docker_cli_available {
-            swarm::detect_swarm()
+        let cluster_info = if api.is_some() || docker_cli_available {
+            swarm::detect_swarm(api.as_ref())
         } else {
             None
         };
@@ -49,6 +166,18 @@ impl SwarmMonitor {
         Self {
             mode,
             cluster_info,
+            docker_cli_available,
+            api,
+            ..Self::undetected()
+        }
+    }
+
+    /// A standalone monitor that has not asked Docker anything yet, shown
+    /// until detection in `new` finishes.
+    pub fn undetected() -> Self {
+        Self {
+            mode: SwarmMode::Standalone,
+            cluster_info: None,
             nodes: Vec::new(),
             services: Vec::new(),
             stacks: Vec::new(),
@@ -57,14 +186,68 @@ impl SwarmMonitor {
             ui_state: SwarmUIState::default(),
             log_state: None,
             log_handle: None,
+            split_mark: None,
+            split_logs: None,
+            split_handles: Vec::new(),
             status_message: None,
             warnings: Vec::new(),
-            docker_cli_available,
-            action_receiver: None,
-            action_in_progress: false,
+            failed_tasks: Vec::new(),
+            stack_files: BTreeMap::new(),
+            stack_drift: HashMap::new(),
+            spec: ServiceSpecInfo::default(),
+            timeline: None,
+            node_probes: HashMap::new(),
+            last_probe: None,
+            ingress_missing: false,
+            docker_cli_available: false,
+            api: None,
+            cache: SwarmCache::default(),
+            pending_refresh: None,
+            last_updated: None,
+            update_failed: false,
+            actions: ActionQueue::new("Swarm action"),
+            note_override: None,
+            demo: None,
+            replayed: false,
+        }
+    }
+
+    /// The made-up five-node cluster of `--demo`.
+    pub fn demo() -> Self {
+        Self {
+            mode: SwarmMode::Swarm,
+            cluster_info: Some(crate::demo::cluster()),
+            docker_cli_available: true,
+            demo: Some(Instant::now()),
+            ..Self::undetected()
         }
     }
 
+    /// A cluster shown from a recording, or no Swarm tab without `cluster`.
+    pub fn replayed(cluster: Option<SwarmClusterInfo>) -> Self {
+        Self {
+            mode: if cluster.is_some() { SwarmMode::Swarm } else { SwarmMode::Standalone },
+            cluster_info: cluster,
+            docker_cli_available: true,
+            replayed: true,
+            ..Self::undetected()
+        }
+    }
+
+    /// Show a recorded refresh in place of a queried one.
+    pub fn show_replayed(&mut self, frame: SwarmFrame) {
+        if frame.cluster.is_some() {
+            self.cluster_info = frame.cluster;
+        }
+        self.nodes = frame.nodes;
+        self.services = frame.services;
+        self.build_stacks();
+        self.service_tasks = frame.service_tasks;
+        self.failed_tasks = frame.failed_tasks;
+        self.warnings = frame.warnings;
+        self.last_updated = Some(Instant::now());
+    }
+
     /// Build a minimal SwarmMonitor for unit testing (no I/O).
     #[cfg(test)]
     pub fn new_minimal(
@@ -73,8 +256,10 @@ impl SwarmMonitor {
         stacks: Vec<SwarmStackInfo>,
         expanded_ids: std::collections::HashSet<String>,
     ) -> Self {
-        let mut ui_state = SwarmUIState::default();
-        ui_state.expanded_ids = expanded_ids;
+        let ui_state = SwarmUIState {
+            expanded_ids,
+            ..Default::default()
+        };
         Self {
             mode: SwarmMode::Swarm,
             cluster_info: None,
@@ -86,11 +271,29 @@ impl SwarmMonitor {
             ui_state,
             log_state: None,
             log_handle: None,
+            split_mark: None,
+            split_logs: None,
+            split_handles: Vec::new(),
             status_message: None,
             warnings: Vec::new(),
+            failed_tasks: Vec::new(),
+            stack_files: BTreeMap::new(),
+            stack_drift: HashMap::new(),
+            spec: ServiceSpecInfo::default(),
+            timeline: None,
+            node_probes: HashMap::new(),
+            last_probe: None,
+            ingress_missing: false,
             docker_cli_available: false,
-            action_receiver: None,
-            action_in_progress: false,
+            api: None,
+            cache: SwarmCache::default(),
+            pending_refresh: None,
+            last_updated: None,
+            update_failed: false,
+            actions: ActionQueue::new("Swarm action"),
+            note_override: None,
+            demo: None,
+            replayed: false,
         }
     }
 
@@ -100,114 +303,307 @@ impl SwarmMonitor {
 
     /// Recheck swarm mode (called infrequently, e.g. every 30s, when standalone)
     pub fn recheck_swarm(&mut self) {
-        if self.is_swarm() {
+        if self.is_swarm() || self.replayed {
             return;
         }
         self.docker_cli_available = swarm::is_docker_cli_available();
-        if !self.docker_cli_available {
+        if self.api.is_none() && !self.docker_cli_available {
             return;
         }
-        self.cluster_info = swarm::detect_swarm();
+        self.cluster_info = swarm::detect_swarm(self.api.as_ref());
         if self.cluster_info.is_some() {
             self.mode = SwarmMode::Swarm;
         }
     }
 
-    /// Refresh cluster data. Called on the tick interval only when Swarm tab is active.
+    /// Spawn a background refresh of cluster data. Called on the tick interval only when
+    /// the Swarm tab is active. Node, service, and task queries run concurrently so one
+    /// slow CLI call doesn't hold up the others.
     pub fn update(&mut self) {
-        if !self.is_swarm() {
+        if !self.is_swarm() || self.replayed || self.pending_refresh.is_some() {
             return;
         }
 
-        match swarm::list_nodes() {
-            Ok(mut nodes) => {
-                let ips = swarm::batch_get_node_ips(&nodes);
-                for node in &mut nodes {
-                    if let Some(ip) = ips.get(&node.id) {
-                        node.ip_address = ip.clone();
-                    }
-                }
-                self.nodes = nodes;
-            }
-            Err(e) => {
-                tracing::warn!("Swarm node list failed: {}", e);
-                self.status_message = Some(format!("Error: {}", e));
-            }
-        }
+        let (tx, rx) = mpsc::channel();
+        let mut remaining = vec![SwarmPartKind::Nodes, SwarmPartKind::Services];
 
-        match swarm::list_services() {
-            Ok(services) => {
-                self.services = services;
-                self.build_stacks();
-            }
-            Err(e) => {
-                tracing::warn!("Swarm service list failed: {}", e);
-                self.status_message = Some(format!("Error: {}", e));
+        if let Some(started) = self.demo {
+            let t = started.elapsed().as_secs_f64();
+            let _ = tx.send(SwarmPart::Nodes(Ok(crate::demo::nodes())));
+            let _ = tx.send(SwarmPart::Services(Ok(crate::demo::services(t))));
+            if let SwarmViewLevel::ServiceTasks(ref svc_id, _) = self.ui_state.view_level {
+                remaining.push(SwarmPartKind::Tasks);
+                let _ = tx.send(SwarmPart::Tasks(svc_id.clone(), Ok(crate::demo::service_tasks(svc_id, t))));
             }
+            remaining.push(SwarmPartKind::ServiceTasks);
+            let _ = tx.send(SwarmPart::ServiceTasks(Ok(crate::demo::running_tasks(&self.expanded_service_ids(), t))));
+            self.pending_refresh = Some(PendingRefresh { receiver: rx, started: Instant::now(), remaining, parts: Vec::new() });
+            return;
         }
 
+        let node_cache = Arc::clone(&self.cache.node_details);
+        let node_tx = tx.clone();
+        thread::spawn(move || {
+            let result = swarm::list_nodes().map(|mut nodes| {
+                let mut cache = node_cache.lock().unwrap_or_else(|e| e.into_inner());
+                swarm::apply_node_details(&mut nodes, &mut cache);
+                nodes
+            });
+            let _ = node_tx.send(SwarmPart::Nodes(result));
+        });
+
+        let label_cache = Arc::clone(&self.cache.stack_labels);
+        let service_tx = tx.clone();
+        let api = self.api.clone();
+        thread::spawn(move || {
+            let mut cache = label_cache.lock().unwrap_or_else(|e| e.into_inner());
+            let result = swarm::list_services(api.as_ref(), &mut cache);
+            drop(cache);
+            let _ = service_tx.send(SwarmPart::Services(result));
+        });
+
         // Refresh tasks if we're in task view
         if let SwarmViewLevel::ServiceTasks(ref svc_id, _) = self.ui_state.view_level {
-            match swarm::list_service_tasks(svc_id) {
-                Ok(tasks) => self.tasks = tasks,
-                Err(e) => {
-                    tracing::warn!("Swarm task list failed: {}", e);
-                    self.status_message = Some(format!("Error: {}", e));
-                }
+            remaining.push(SwarmPartKind::Tasks);
+            let id = svc_id.clone();
+            let task_tx = tx.clone();
+            let with_times = self.timeline.is_some();
+            if with_times {
+                remaining.push(SwarmPartKind::TaskTimes);
             }
+            thread::spawn(move || {
+                let result = swarm::list_service_tasks(&id);
+                let task_ids: Vec<String> = match (&result, with_times) {
+                    (Ok(tasks), true) => tasks.iter().map(|t| t.id.clone()).collect(),
+                    _ => Vec::new(),
+                };
+                let _ = task_tx.send(SwarmPart::Tasks(id.clone(), result));
+                if with_times {
+                    let id_refs: Vec<&str> = task_ids.iter().map(|s| s.as_str()).collect();
+                    let _ = task_tx.send(SwarmPart::TaskTimes(id, swarm::inspect_task_times(&id_refs)));
+                }
+            });
         }
 
-        // Fetch running tasks for services in expanded stacks (for inline replica sub-rows)
-        self.service_tasks.clear();
-        if !self.ui_state.expanded_ids.is_empty() {
-            // Collect service IDs from expanded stacks
-            let mut svc_ids: Vec<String> = Vec::new();
-            for stack in &self.stacks {
-                if self.ui_state.expanded_ids.contains(&stack.name) {
-                    for &idx in &stack.service_indices {
-                        if let Some(svc) = self.services.get(idx) {
-                            svc_ids.push(svc.id.clone());
-                        }
+        // Running tasks for services in expanded stacks (for inline replica sub-rows).
+        // Uses the previous tick's stack membership so it needn't wait on service ls.
+        let svc_ids = self.expanded_service_ids();
+        if !svc_ids.is_empty() {
+            remaining.push(SwarmPartKind::ServiceTasks);
+            let replica_tx = tx.clone();
+            thread::spawn(move || {
+                let id_refs: Vec<&str> = svc_ids.iter().map(|s| s.as_str()).collect();
+                let result = swarm::list_tasks_for_services(&id_refs);
+                let _ = replica_tx.send(SwarmPart::ServiceTasks(result));
+            });
+        }
+
+        // Task errors of services that were short of replicas last tick, to catch
+        // image pull failures that would otherwise only show in the task view,
+        // and to make them searchable
+        let degraded: Vec<String> = self.services.iter()
+            .filter(|s| replica_shortfall(&s.replicas).is_some())
+            .map(|s| s.id.clone())
+            .collect();
+        if degraded.is_empty() {
+            self.failed_tasks.clear();
+        } else {
+            remaining.push(SwarmPartKind::FailedTasks);
+            let failed_tx = tx.clone();
+            thread::spawn(move || {
+                let _ = failed_tx.send(SwarmPart::FailedTasks(swarm::list_failed_tasks(&degraded)));
+            });
+        }
+
+        // Latency to the other nodes, at the addresses inspected last tick,
+        // and whether the overlay ports and ingress network are in place
+        let probe_due = self.last_probe.is_none_or(|t| t.elapsed() >= NODE_PROBE_INTERVAL);
+        let targets: Vec<(String, String, bool)> = self.nodes.iter()
+            .filter(|n| !n.is_self && !n.ip_address.is_empty() && !n.status.to_lowercase().contains("down"))
+            .map(|n| (n.id.clone(), n.ip_address.clone(), !n.manager_status.is_empty()))
+            .collect();
+        if probe_due && !self.nodes.is_empty() {
+            self.last_probe = Some(Instant::now());
+            remaining.push(SwarmPartKind::NodeProbes);
+            thread::spawn(move || {
+                let probes = swarm::probe_nodes(&targets);
+                let _ = tx.send(SwarmPart::NodeProbes(probes, swarm::ingress_network()));
+            });
+        }
+
+        self.pending_refresh = Some(PendingRefresh {
+            receiver: rx,
+            started: Instant::now(),
+            remaining,
+            parts: Vec::new(),
+        });
+    }
+
+    /// Poll for background refresh completion. Returns true once results were applied,
+    /// either because every query finished or because the combined timeout elapsed.
+    pub fn poll_update(&mut self) -> bool {
+        let Some(ref mut pending) = self.pending_refresh else {
+            return false;
+        };
+
+        while let Ok(part) = pending.receiver.try_recv() {
+            let kind = part.kind();
+            pending.remaining.retain(|k| *k != kind);
+            pending.parts.push(part);
+        }
+
+        let timed_out = pending.started.elapsed() >= SWARM_REFRESH_TIMEOUT;
+        if !pending.remaining.is_empty() && !timed_out {
+            return false;
+        }
+
+        let Some(pending) = self.pending_refresh.take() else {
+            return false;
+        };
+        self.update_failed = !pending.remaining.is_empty();
+        if !pending.remaining.is_empty() {
+            let waiting: Vec<&str> = pending.remaining.iter().map(|k| k.label()).collect();
+            tracing::warn!("Swarm refresh timed out waiting for: {}", waiting.join(", "));
+            self.status_message = Some(format!(
+                "Swarm refresh timed out after {}s waiting for: {}",
+                SWARM_REFRESH_TIMEOUT.as_secs(),
+                waiting.join(", ")
+            ));
+        }
+        tracing::debug!("Swarm refresh finished in {}ms", pending.started.elapsed().as_millis());
+        self.apply_parts(pending.parts);
+        true
+    }
+
+    /// Service IDs belonging to currently expanded stacks.
+    fn expanded_service_ids(&self) -> Vec<String> {
+        let mut svc_ids: Vec<String> = Vec::new();
+        for stack in &self.stacks {
+            if self.ui_state.expanded_ids.contains(&stack.name) {
+                for &idx in &stack.service_indices {
+                    if let Some(svc) = self.services.get(idx) {
+                        svc_ids.push(svc.id.clone());
                     }
                 }
             }
+        }
+        svc_ids
+    }
 
-            if !svc_ids.is_empty() {
-                let id_refs: Vec<&str> = svc_ids.iter().map(|s| s.as_str()).collect();
-                match swarm::list_tasks_for_services(&id_refs) {
-                    Ok(tasks) => {
-                        // Build a name->id lookup from services
-                        let name_to_id: HashMap<String, String> = self.services.iter()
-                            .map(|s| (s.name.clone(), s.id.clone()))
-                            .collect();
-
-                        // Group tasks by service ID
-                        for task in tasks {
-                            // Task name is e.g. "stack_service.1", extract service name
-                            // by stripping the last ".N" suffix
-                            let svc_name = if let Some(dot_pos) = task.name.rfind('.') {
-                                &task.name[..dot_pos]
-                            } else {
-                                &task.name
-                            };
-                            if let Some(svc_id) = name_to_id.get(svc_name) {
-                                self.service_tasks
-                                    .entry(svc_id.clone())
-                                    .or_insert_with(Vec::new)
-                                    .push(task);
-                            }
+    /// Apply the results of a background refresh in the order they arrived.
+    /// Probes are matched to nodes and replica tasks grouped by service only
+    /// once every part is in, so both see this refresh's nodes and services.
+    fn apply_parts(&mut self, parts: Vec<SwarmPart>) {
+        let mut replica_tasks = None;
+
+        for part in parts {
+            match part {
+                SwarmPart::Nodes(result) => {
+                    match result {
+                        Ok(nodes) => {
+                            tracing::debug!("Swarm nodes: {}", nodes.len());
+                            self.nodes = nodes;
+                        }
+                        Err(e) => {
+                            self.update_failed = true;
+                            tracing::warn!("Swarm node list failed: {}", e);
+                            self.status_message = Some(format!("Error: {}", e.with_hint()));
+                        }
+                    }
+                }
+                SwarmPart::Services(result) => {
+                    match result {
+                        Ok(services) => {
+                            tracing::debug!("Swarm services: {}", services.len());
+                            self.services = services;
+                            self.build_stacks();
+                            self.check_stack_drift();
+                        }
+                        Err(e) => {
+                            self.update_failed = true;
+                            tracing::warn!("Swarm service list failed: {}", e);
+                            self.status_message = Some(format!("Error: {}", e.with_hint()));
                         }
                     }
-                    Err(e) => {
-                        tracing::warn!("Swarm task fetch failed: {}", e);
-                        self.status_message = Some(format!("Task fetch error: {}", e));
+                }
+                SwarmPart::Tasks(svc_id, result) => {
+                    // Ignore results for a task view the user has since left.
+                    let still_viewing = matches!(
+                        &self.ui_state.view_level,
+                        SwarmViewLevel::ServiceTasks(id, _) if *id == svc_id
+                    );
+                    if !still_viewing {
+                        continue;
+                    }
+                    match result {
+                        Ok(tasks) => self.tasks = tasks,
+                        Err(e) => {
+                            tracing::warn!("Swarm task list failed: {}", e);
+                            self.status_message = Some(format!("Error: {}", e.with_hint()));
+                        }
                     }
                 }
+                SwarmPart::ServiceTasks(result) => replica_tasks = Some(result),
+                SwarmPart::TaskTimes(svc_id, result) => {
+                    let still_viewing = matches!(
+                        &self.ui_state.view_level,
+                        SwarmViewLevel::ServiceTasks(id, _) if *id == svc_id
+                    );
+                    if !still_viewing || self.timeline.is_none() {
+                        continue;
+                    }
+                    match result {
+                        Ok(times) => self.timeline = Some(times),
+                        Err(e) => tracing::warn!("Swarm task inspect failed: {}", e),
+                    }
+                }
+                SwarmPart::NodeProbes(probes, ingress) => {
+                    self.node_probes = probes;
+                    match ingress {
+                        Ok(network) => self.ingress_missing = network.is_none(),
+                        Err(e) => tracing::warn!("Swarm ingress network check failed: {}", e),
+                    }
+                }
+                SwarmPart::FailedTasks(result) => match result {
+                    Ok(tasks) => self.failed_tasks = tasks,
+                    Err(e) => tracing::warn!("Swarm failed task fetch failed: {}", e),
+                },
             }
         }
 
+        for node in &mut self.nodes {
+            node.probe = self.node_probes.get(&node.id).cloned();
+        }
+
+        // Group replica tasks only after services are applied, so the name lookup is current.
+        self.service_tasks.clear();
+        match replica_tasks {
+            Some(Ok(tasks)) => {
+                // Build a name->id lookup from services
+                let name_to_id: HashMap<String, String> = self.services.iter()
+                    .map(|s| (s.name.clone(), s.id.clone()))
+                    .collect();
+
+                // Group tasks by service ID
+                for task in tasks {
+                    if let Some(svc_id) = name_to_id.get(swarm::task_service_name(&task.name)) {
+                        self.service_tasks
+                            .entry(svc_id.clone())
+                            .or_default()
+                            .push(task);
+                    }
+                }
+            }
+            Some(Err(e)) => {
+                tracing::warn!("Swarm task fetch failed: {}", e);
+                self.status_message = Some(format!("Task fetch error: {}", e.with_hint()));
+            }
+            None => {}
+        }
+
         // Generate warnings
         self.generate_warnings();
+        self.last_updated = Some(Instant::now());
     }
 
     /// Build stack groupings from services.
@@ -244,13 +640,47 @@ impl SwarmMonitor {
         self.stacks = stacks;
     }
 
+    /// The compose file stack `name` was deployed from, if sitrep can find it here.
+    fn stack_file(&self, name: &str, services: &[&SwarmServiceInfo]) -> Option<PathBuf> {
+        if let Some(path) = self.stack_files.get(name) {
+            return Some(path.clone());
+        }
+        services
+            .iter()
+            .flat_map(|s| s.config_files.split(','))
+            .map(|p| PathBuf::from(p.trim()))
+            .find(|p| p.is_file())
+    }
+
+    /// Compare each stack that has a local compose file with what is running.
+    fn check_stack_drift(&mut self) {
+        let mut drift = HashMap::new();
+        for stack in &self.stacks {
+            let services: Vec<&SwarmServiceInfo> =
+                stack.service_indices.iter().filter_map(|&i| self.services.get(i)).collect();
+            let Some(path) = self.stack_file(&stack.name, &services) else { continue };
+            match compose::load(&path) {
+                Ok(file) => {
+                    let lines = compose::stack_drift(&stack.name, &file, &services);
+                    if !lines.is_empty() {
+                        drift.insert(stack.name.clone(), (path, lines));
+                    }
+                }
+                Err(e) => tracing::debug!("Stack {} compose file not read: {}", stack.name, e),
+            }
+        }
+        self.stack_drift = drift;
+    }
+
     /// Generate smart warnings about cluster health.
     fn generate_warnings(&mut self) {
         self.warnings.clear();
 
         if !self.docker_cli_available {
-            self.warnings.push("docker CLI not found in PATH — Swarm data unavailable".to_string());
-            return;
+            self.warnings.push("docker CLI not found in PATH — node and task lists unavailable".to_string());
+            if self.api.is_none() {
+                return;
+            }
         }
 
         // Check for down nodes
@@ -281,30 +711,88 @@ impl SwarmMonitor {
 
         // Check for services with incomplete replicas
         for svc in &self.services {
-            if svc.replicas.contains('/') {
-                let parts: Vec<&str> = svc.replicas.split('/').collect();
-                if parts.len() == 2 {
-                    let current: u32 = parts[0].trim().parse().unwrap_or(0);
-                    let desired: u32 = parts[1].trim().parse().unwrap_or(0);
-                    if desired > 0 && current < desired {
-                        self.warnings.push(format!(
-                            "SERVICE DEGRADED: {} has {}/{} replicas",
-                            svc.name, current, desired
-                        ));
-                    }
-                }
+            if let Some((current, desired)) = replica_shortfall(&svc.replicas) {
+                self.warnings.push(format!(
+                    "SERVICE DEGRADED: {} has {}/{} replicas",
+                    svc.name, current, desired
+                ));
+            }
+        }
+
+        // Image pull failures, one per failure kind and registry
+        let mut pulls: BTreeMap<(PullFailure, String), Vec<&str>> = BTreeMap::new();
+        for task in &self.failed_tasks {
+            let Some(kind) = PullFailure::classify(&task.error) else { continue };
+            let services = pulls.entry((kind, swarm::image_registry(&task.image))).or_default();
+            let name = swarm::task_service_name(&task.name);
+            if !services.contains(&name) {
+                services.push(name);
             }
         }
+        for ((kind, registry), mut services) in pulls {
+            services.sort_unstable();
+            self.warnings.push(format!("{}: {} for {}", kind.label(), registry, services.join(", ")));
+        }
+
+        // Stacks changed by hand since their compose file was deployed
+        let mut drifted: Vec<(&String, &(PathBuf, Vec<String>))> = self.stack_drift.iter().collect();
+        drifted.sort_by_key(|(name, _)| *name);
+        for (stack, (path, lines)) in drifted {
+            self.warnings.push(format!(
+                "STACK DRIFT: {} differs from {} in {} place(s): {}",
+                stack,
+                path.display(),
+                lines.len(),
+                lines.join("; ")
+            ));
+        }
+
+        // Packet loss between this host and other nodes, usually the first
+        // sign of overlay network trouble
+        let lossy: Vec<String> = self.nodes.iter()
+            .filter_map(|n| n.probe.as_ref().filter(|p| p.received < p.sent).map(|p| (n, p)))
+            .map(|(n, p)| format!("{} {}%", n.hostname, p.loss_percent()))
+            .collect();
+        if !lossy.is_empty() {
+            self.warnings.push(format!(
+                "NODE PACKET LOSS: probes to port {} lost for {}",
+                swarm::GOSSIP_PORT,
+                lossy.join(", ")
+            ));
+        }
+
+        // Swarm ports closed between this host and a peer
+        let closed: Vec<String> = self.nodes.iter()
+            .filter_map(|n| n.probe.as_ref().filter(|p| !p.failed_ports.is_empty()).map(|p| (n, p)))
+            .map(|(n, p)| {
+                let ports: Vec<String> = p.failed_ports.iter().map(|f| f.to_string()).collect();
+                format!("{} ({}) {}", n.hostname, n.ip_address, ports.join(", "))
+            })
+            .collect();
+        if !closed.is_empty() {
+            self.warnings.push(format!("SWARM PORTS BLOCKED: {}", closed.join("; ")));
+        }
+        if self.ingress_missing {
+            self.warnings.push(
+                "NO INGRESS NETWORK: published ports get no routing mesh until one is created \
+                 (docker network create --driver overlay --ingress ingress)"
+                    .to_string(),
+            );
+        }
+
+        // Manager quorum, from each manager's reachability
+        if let Some(warning) = ManagerQuorum::from_nodes(&self.nodes).and_then(|q| q.warning()) {
+            self.warnings.push(warning);
+        }
 
         // Check manager count
-        if let Some(ref info) = self.cluster_info {
-            if info.managers < 3 && info.nodes_total > 3 {
+        if let Some(ref info) = self.cluster_info
+            && info.managers < 3 && info.nodes_total > 3 {
                 self.warnings.push(format!(
                     "LOW MANAGERS: Only {} manager(s) for {} nodes (recommend 3+)",
                     info.managers, info.nodes_total
                 ));
             }
-        }
     }
 
     /// Get the total number of selectable rows in the current overview.
@@ -331,9 +819,13 @@ impl SwarmMonitor {
             Ok(tasks) => self.tasks = tasks,
             Err(e) => {
                 self.tasks.clear();
-                self.status_message = Some(format!("Error: {}", e));
+                self.status_message = Some(format!("Error: {}", e.with_hint()));
             }
         }
+        self.spec = swarm::inspect_service(service_id).unwrap_or_else(|e| {
+            tracing::warn!("Service inspect failed: {}", e);
+            ServiceSpecInfo::default()
+        });
         self.ui_state.view_level = SwarmViewLevel::ServiceTasks(
             service_id.to_string(),
             service_name.to_string(),
@@ -341,12 +833,27 @@ impl SwarmMonitor {
         self.ui_state.selected_index = 0;
     }
 
+    /// Show or hide the task timeline, fetching task times when shown.
+    pub fn toggle_timeline(&mut self) {
+        if self.timeline.take().is_some() {
+            return;
+        }
+        let ids: Vec<&str> = self.tasks.iter().map(|t| t.id.as_str()).collect();
+        match swarm::inspect_task_times(&ids) {
+            Ok(times) => self.timeline = Some(times),
+            Err(e) => self.status_message = Some(format!("Error: {}", e.with_hint())),
+        }
+    }
+
     /// Start streaming logs for a service.
     pub fn start_service_log_stream(&mut self, service_id: &str, service_name: &str) {
         // Kill any existing log stream first
         self.stop_log_stream();
 
-        let handle = swarm::tail_service_logs(service_id);
+        let handle = match self.demo {
+            Some(_) => crate::demo::service_logs(service_name),
+            None => swarm::tail_service_logs(service_id),
+        };
         self.log_state = Some(ServiceLogState::new(
             service_id.to_string(),
             service_name.to_string(),
@@ -379,83 +886,197 @@ impl SwarmMonitor {
                 }
                 Err(mpsc::TryRecvError::Empty) => break,
                 Err(mpsc::TryRecvError::Disconnected) => {
-                    log_state.push_line("[log stream ended]".to_string());
+                    log_state.end_stream();
                     break;
                 }
             }
         }
     }
 
-    /// Force-update (rolling restart) a service in a background thread.
-    /// Shows "in progress" status until the operation completes.
-    pub fn force_restart_service(&mut self, service_id: &str) {
-        if self.action_in_progress {
-            self.status_message = Some("An action is already in progress...".to_string());
-            return;
+    /// Pick a source for the split log view. The first pick is remembered;
+    /// the second opens the view. Returns a message to show instead when the
+    /// view was not opened.
+    pub fn mark_split_source(&mut self, source: LogPaneSource, docker: &DockerMonitor) -> Option<String> {
+        let Some(first) = self.split_mark.take() else {
+            let message = format!("Split logs: {} marked, press V on a second service or container", source.label());
+            self.split_mark = Some(source);
+            return Some(message);
+        };
+        if first == source {
+            return Some("Split logs: mark cleared".to_string());
         }
+        if !matches!(first, LogPaneSource::Service { .. }) && !matches!(source, LogPaneSource::Service { .. }) {
+            self.split_mark = Some(first);
+            return Some("Split logs need a service in one pane (use l for several containers)".to_string());
+        }
+        self.start_split_logs(first, source, docker);
+        None
+    }
 
-        let id = service_id.to_string();
-        let (tx, rx) = mpsc::channel();
-        self.action_receiver = Some(rx);
-        self.action_in_progress = true;
-        self.status_message = Some(format!("Rolling restart in progress for {}...", service_id));
+    /// Start streaming both panes of the split log view. Container panes
+    /// follow the Containers tab's backend, so podman and nerdctl hosts work too.
+    pub fn start_split_logs(&mut self, first: LogPaneSource, second: LogPaneSource, docker: &DockerMonitor) {
+        self.stop_split_logs();
+        self.split_handles = [&first, &second]
+            .iter()
+            .map(|source| match source {
+                LogPaneSource::Service { name, .. } | LogPaneSource::Container { name, .. } if self.demo.is_some() => {
+                    crate::demo::service_logs(name)
+                }
+                LogPaneSource::Service { id, .. } => swarm::tail_service_logs(id),
+                LogPaneSource::Container { id, .. } => match docker.follow_logs(id) {
+                    Ok((child, rx)) => LogStreamHandle::forwarded(child, rx),
+                    Err(e) => LogStreamHandle::failed(e),
+                },
+            })
+            .collect();
+        self.split_logs = Some(SplitLogState::new(first, second));
+    }
 
-        thread::spawn(move || {
-            let result = match swarm::force_update_service(&id) {
-                Ok(()) => Ok(format!("Rolling restart initiated for {}", id)),
-                Err(e) => Err(format!("Error: {}", e.trim())),
-            };
-            let _ = tx.send(result);
-        });
+    /// Stop both split log streams.
+    pub fn stop_split_logs(&mut self) {
+        for handle in &self.split_handles {
+            handle.kill();
+        }
+        self.split_handles.clear();
+        self.split_logs = None;
     }
 
-    /// Scale a service in a background thread.
-    pub fn scale_service(&mut self, service_id: &str, replicas: u32) {
-        if self.action_in_progress {
-            self.status_message = Some("An action is already in progress...".to_string());
-            return;
+    /// Drain pending lines into both split panes. Returns true when any arrived.
+    pub fn poll_split_logs(&mut self) -> bool {
+        let Some(ref mut split) = self.split_logs else { return false };
+        let mut received = false;
+        for (handle, pane) in self.split_handles.iter().zip(split.panes.iter_mut()) {
+            for _ in 0..200 {
+                match handle.receiver.try_recv() {
+                    Ok(line) => pane.push_line(line),
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        if pane.ended {
+                            break;
+                        }
+                        pane.end_stream();
+                    }
+                }
+                received = true;
+            }
         }
+        received
+    }
 
+    /// Queue a force-update (rolling restart) of a service, run in the
+    /// background after any action already queued.
+    pub fn force_restart_service(&mut self, service_id: &str) {
         let id = service_id.to_string();
-        let (tx, rx) = mpsc::channel();
-        self.action_receiver = Some(rx);
-        self.action_in_progress = true;
-        self.status_message = Some(format!("Scaling {} to {} replicas...", service_id, replicas));
+        self.queue_action(
+            format!("Rolling restart of {}", service_id),
+            Box::new(move |_| {
+                swarm::force_update_service(&id)
+                    .map(|()| format!("Rolling restart initiated for {}", id))
+                    .map_err(|e| e.with_hint())
+            }),
+        );
+    }
 
-        thread::spawn(move || {
-            let result = match swarm::scale_service(&id, replicas) {
-                Ok(()) => Ok(format!("Scaled {} to {} replicas", id, replicas)),
-                Err(e) => Err(format!("Error: {}", e.trim())),
-            };
-            let _ = tx.send(result);
-        });
+    /// Queue scaling a service, run in the background.
+    pub fn scale_service(&mut self, service_id: &str, replicas: u32) {
+        let id = service_id.to_string();
+        self.queue_action(
+            format!("Scaling {} to {} replicas", service_id, replicas),
+            Box::new(move |_| {
+                swarm::scale_service(&id, replicas)
+                    .map(|()| format!("Scaled {} to {} replicas", id, replicas))
+                    .map_err(|e| e.with_hint())
+            }),
+        );
     }
 
-    /// Poll for background action completion. Returns true if the status changed.
-    pub fn poll_action(&mut self) -> bool {
-        let Some(ref rx) = self.action_receiver else { return false };
-        match rx.try_recv() {
-            Ok(Ok(msg)) => {
-                self.status_message = Some(msg);
-                self.action_in_progress = false;
-                self.action_receiver = None;
-                true
-            }
-            Ok(Err(msg)) => {
-                tracing::error!("Swarm action failed: {}", msg);
-                self.status_message = Some(msg);
-                self.action_in_progress = false;
-                self.action_receiver = None;
-                true
+    /// Queue setting or removing one service label, run in the background.
+    pub fn edit_service_label(&mut self, service_id: &str, service_name: &str, edit: LabelEdit) {
+        let id = service_id.to_string();
+        let name = service_name.to_string();
+        self.queue_action(
+            format!("Labeling {} {}", service_name, edit),
+            Box::new(move |_| {
+                swarm::update_service_label(&id, &edit)
+                    .map(|()| match edit {
+                        LabelEdit::Add(..) => format!("Labeled {} {}", name, edit),
+                        LabelEdit::Remove(ref key) => format!("Removed label {} from {}", key, name),
+                    })
+                    .map_err(|e| e.with_hint())
+            }),
+        );
+    }
+
+    /// Set the cluster-wide maintenance note as a label on this node, or
+    /// clear it from every node that carries one (non-blocking).
+    pub fn set_maintenance_note(&mut self, note: Option<MaintenanceNote>) {
+        let (label, job): (String, Job) = match note {
+            Some(ref note) => {
+                let Some(node_id) = self.cluster_info.as_ref().map(|c| c.node_id.clone()) else { return };
+                let value = note.to_json();
+                (
+                    "Setting the maintenance note".to_string(),
+                    Box::new(move |_| {
+                        swarm::update_node_label(&node_id, notes::NOTE_LABEL, Some(&value))
+                            .map(|()| "Maintenance note set for the cluster".to_string())
+                            .map_err(|e| e.with_hint())
+                    }),
+                )
             }
-            Err(mpsc::TryRecvError::Empty) => false,
-            Err(mpsc::TryRecvError::Disconnected) => {
-                self.status_message = Some("Action failed unexpectedly".to_string());
-                self.action_in_progress = false;
-                self.action_receiver = None;
-                true
+            None => {
+                let ids: Vec<String> = self
+                    .nodes
+                    .iter()
+                    .filter(|n| n.labels.contains_key(notes::NOTE_LABEL))
+                    .map(|n| n.id.clone())
+                    .collect();
+                (
+                    "Clearing the maintenance note".to_string(),
+                    Box::new(move |_| {
+                        for id in &ids {
+                            swarm::update_node_label(id, notes::NOTE_LABEL, None).map_err(|e| e.with_hint())?;
+                        }
+                        Ok("Maintenance note cleared".to_string())
+                    }),
+                )
             }
+        };
+        self.note_override = Some((note, Instant::now()));
+        self.queue_action(label, job);
+    }
+
+    /// The cluster-wide maintenance note: the one just set here until the
+    /// node labels, inspected at most once a minute, catch up, else the
+    /// newest on any node.
+    pub fn maintenance_note(&self) -> Option<MaintenanceNote> {
+        match self.note_override {
+            Some((ref note, at)) if at.elapsed() < NOTE_OVERRIDE => note.clone(),
+            _ => notes::from_nodes(&self.nodes),
+        }
+    }
+
+    /// Add `job` to the action queue and show the queue in the status line.
+    fn queue_action(&mut self, label: String, job: Job) {
+        if !self.actions.push(label.clone(), job) {
+            self.status_message = Some(format!("{} is already queued", label));
+            return;
         }
+        self.status_message = self.actions.status_line();
+    }
+
+    /// Poll the action queue. Returns true if the status changed.
+    pub fn poll_action(&mut self) -> bool {
+        if !self.actions.poll() {
+            return false;
+        }
+        self.status_message = self.actions.status_line();
+        true
+    }
+
+    /// Whether a service action is running or queued.
+    pub fn action_in_progress(&self) -> bool {
+        self.actions.is_busy()
     }
 
     /// Go back one level in the view hierarchy.
@@ -469,6 +1090,7 @@ impl SwarmMonitor {
             }
             SwarmViewLevel::ServiceTasks(_, _) => {
                 self.tasks.clear();
+                self.timeline = None;
                 self.ui_state.view_level = SwarmViewLevel::Overview;
                 self.ui_state.selected_index = 0;
             }
@@ -0,0 +1,168 @@
+//! Forwarding of alerts to the local journald or syslog daemon.
+//!
+//! journald's native socket is preferred because it keeps the structured
+//! fields (`SITREP_SOURCE`, `SITREP_TARGET`, ...) queryable with
+//! `journalctl SITREP_SOURCE=disk`; otherwise a classic syslog line is sent
+//! to /dev/log with the same fields appended as `key=value` pairs.
+
+use std::os::unix::net::UnixDatagram;
+use std::path::{Path, PathBuf};
+
+use crate::alerts::{Alert, AlertTracker, Severity};
+
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+/// /dev/log on Linux, /var/run/syslog on macOS.
+const SYSLOG_SOCKETS: [&str; 2] = ["/dev/log", "/var/run/syslog"];
+const IDENTIFIER: &str = "sitrep";
+/// LOG_DAEMON facility.
+const FACILITY: u8 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Protocol {
+    Journal,
+    Syslog,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Transition {
+    Raised,
+    Resolved,
+}
+
+impl Transition {
+    fn label(self) -> &'static str {
+        match self {
+            Transition::Raised => "raised",
+            Transition::Resolved => "resolved",
+        }
+    }
+}
+
+pub struct SyslogForwarder {
+    socket: UnixDatagram,
+    path: PathBuf,
+    protocol: Protocol,
+    tracker: AlertTracker,
+}
+
+impl SyslogForwarder {
+    /// Find a local journald or syslog socket.
+    pub fn connect() -> Result<Self, String> {
+        let (path, protocol) = if Path::new(JOURNAL_SOCKET).exists() {
+            (PathBuf::from(JOURNAL_SOCKET), Protocol::Journal)
+        } else {
+            let path = SYSLOG_SOCKETS
+                .iter()
+                .find(|p| Path::new(p).exists())
+                .ok_or_else(|| "No journald or syslog socket found".to_string())?;
+            (PathBuf::from(path), Protocol::Syslog)
+        };
+        let socket = UnixDatagram::unbound().map_err(|e| e.to_string())?;
+        Ok(Self {
+            socket,
+            path,
+            protocol,
+            tracker: AlertTracker::default(),
+        })
+    }
+
+    pub fn target(&self) -> &Path {
+        &self.path
+    }
+
+    /// Send newly raised and resolved alerts; unchanged ones are not repeated.
+    pub fn forward(&mut self, alerts: Vec<Alert>) {
+        let changes = self.tracker.update(alerts);
+        let sends = changes
+            .raised
+            .iter()
+            .map(|a| (a, Transition::Raised))
+            .chain(changes.resolved.iter().map(|a| (a, Transition::Resolved)));
+        for (alert, transition) in sends {
+            let message = match self.protocol {
+                Protocol::Journal => journal_message(alert, transition),
+                Protocol::Syslog => syslog_message(alert, transition, std::process::id()),
+            };
+            if let Err(e) = self.socket.send_to(message.as_bytes(), &self.path) {
+                tracing::warn!("Failed to forward alert to {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+fn priority(severity: Severity, transition: Transition) -> u8 {
+    match (transition, severity) {
+        (Transition::Resolved, _) => 6, // info
+        (Transition::Raised, Severity::Warning) => 4,
+        (Transition::Raised, Severity::Critical) => 2,
+    }
+}
+
+fn text(alert: &Alert, transition: Transition) -> String {
+    let message = alert.message.replace('\n', " ");
+    match transition {
+        Transition::Raised => message,
+        Transition::Resolved => format!("RESOLVED: {}", message),
+    }
+}
+
+/// journald native protocol: one `FIELD=value` per line.
+fn journal_message(alert: &Alert, transition: Transition) -> String {
+    let fields = [
+        ("MESSAGE", text(alert, transition)),
+        ("PRIORITY", priority(alert.severity, transition).to_string()),
+        ("SYSLOG_IDENTIFIER", IDENTIFIER.to_string()),
+        ("SITREP_SOURCE", alert.source.to_string()),
+        ("SITREP_TARGET", alert.target.replace('\n', " ")),
+        ("SITREP_SEVERITY", alert.severity.label().to_string()),
+        ("SITREP_STATE", transition.label().to_string()),
+    ];
+    fields.iter().map(|(k, v)| format!("{}={}\n", k, v)).collect()
+}
+
+/// BSD syslog line as accepted on /dev/log; the daemon adds the timestamp.
+fn syslog_message(alert: &Alert, transition: Transition, pid: u32) -> String {
+    format!(
+        "<{}>{}[{}]: {} source={} target=\"{}\" severity={} state={}",
+        FACILITY * 8 + priority(alert.severity, transition),
+        IDENTIFIER,
+        pid,
+        text(alert, transition),
+        alert.source,
+        alert.target.replace('"', "'"),
+        alert.severity.label(),
+        transition.label(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert() -> Alert {
+        Alert {
+            key: "disk:/".into(),
+            source: "disk",
+            target: "/".into(),
+            severity: Severity::Critical,
+            message: "DISK CRITICAL: / 95% used".into(),
+        }
+    }
+
+    #[test]
+    fn journal_message_has_structured_fields() {
+        let msg = journal_message(&alert(), Transition::Raised);
+        assert!(msg.starts_with("MESSAGE=DISK CRITICAL: / 95% used\nPRIORITY=2\n"));
+        assert!(msg.contains("SITREP_SOURCE=disk\n"));
+        assert!(msg.ends_with("SITREP_STATE=raised\n"));
+    }
+
+    #[test]
+    fn syslog_message_uses_daemon_facility() {
+        let msg = syslog_message(&alert(), Transition::Resolved, 42);
+        assert_eq!(
+            msg,
+            "<30>sitrep[42]: RESOLVED: DISK CRITICAL: / 95% used source=disk target=\"/\" severity=critical state=resolved"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Short syscall traces of a single process: `strace -c` on Linux, `dtruss -c`
+//! on macOS. Both tools slow the target noticeably while attached, so traces
+//! are only started after confirmation and always detach after TRACE_SECS.
+
+use std::io::Read;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use crate::cmd;
+use crate::model::{SyscallStat, TraceReport};
+
+/// Length of a capture.
+pub const TRACE_SECS: u64 = 5;
+/// Time a tracer gets to print its summary and exit after being interrupted.
+const DETACH_SECS: u64 = 5;
+
+/// Trace `pid` for TRACE_SECS and return its per-syscall summary.
+pub fn trace(pid: u32) -> Result<TraceReport, String> {
+    let pid_arg = pid.to_string();
+    let (tool, stats) = if cfg!(target_os = "macos") {
+        let output = capture("dtruss", &["-c", "-f", "-p", &pid_arg])?;
+        ("dtruss", parse_dtruss_counts(&output))
+    } else {
+        let output = capture("strace", &["-c", "-f", "-p", &pid_arg])?;
+        ("strace", parse_strace_summary(&output))
+    };
+    if stats.is_empty() {
+        return Err(format!("{} recorded no syscalls (process idle?)", tool));
+    }
+    Ok(TraceReport { tool, stats })
+}
+
+/// Run a tracer for TRACE_SECS, then interrupt it so it detaches and prints
+/// its summary. Both tools write the summary to stderr.
+fn capture(program: &str, args: &[&str]) -> Result<String, String> {
+    let spawned = cmd::spawn(Command::new(program).args(args).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::piped()))
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => format!("{} not found in PATH", program),
+            _ => format!("Failed to run {}: {}", program, e),
+        })?;
+    let child = spawned.child;
+    let mut stderr = spawned.stderr.ok_or("no stderr pipe")?;
+    let reader = thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text);
+        text
+    });
+
+    // Attach failures (permissions, no such process) exit straight away
+    let mut status = wait_for(&child, TRACE_SECS);
+    if status.is_none() {
+        let _ = cmd::output(Command::new("kill").args(["-INT", &child.id().to_string()]), cmd::QUICK);
+        status = wait_for(&child, DETACH_SECS);
+    }
+    // Dropping the handle kills a tracer that ignored the interrupt
+    let status = status.ok_or_else(|| format!("{} did not detach", program))?;
+    let text = reader.join().unwrap_or_default();
+
+    if !text.contains("calls") && !text.contains("COUNT") {
+        let reason = text.lines().rev().find(|l| !l.trim().is_empty());
+        return Err(match reason {
+            Some(line) => format!("{}: {}", program, line.trim()),
+            None => format!("{} exited with {}", program, status),
+        });
+    }
+    Ok(text)
+}
+
+/// Wait up to `secs` for the child to exit.
+fn wait_for(child: &cmd::TrackedChild, secs: u64) -> Option<ExitStatus> {
+    for _ in 0..secs * 10 {
+        if let Some(status) = child.try_wait().ok().flatten() {
+            return Some(status);
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    child.try_wait().ok().flatten()
+}
+
+/// Parse the `strace -c` table:
+/// "% time     seconds  usecs/call     calls    errors syscall", then rows
+/// such as " 62.50    0.000250          12        20         4 futex". The
+/// errors column is blank when no call failed.
+pub fn parse_strace_summary(text: &str) -> Vec<SyscallStat> {
+    let Some(start) = text.find("% time") else {
+        return Vec::new();
+    };
+    let mut stats: Vec<SyscallStat> = text[start..]
+        .lines()
+        .skip(1)
+        .filter(|l| !l.starts_with("---"))
+        .filter_map(|l| {
+            let cols: Vec<&str> = l.split_whitespace().collect();
+            let (errors, name) = match cols.len() {
+                5 => (0, cols[4]),
+                6 => (cols[4].parse().ok()?, cols[5]),
+                _ => return None,
+            };
+            if name == "total" {
+                return None;
+            }
+            Some(SyscallStat {
+                name: name.to_string(),
+                calls: cols[3].parse().ok()?,
+                errors,
+                seconds: cols[1].parse().ok()?,
+                usecs_per_call: cols[2].parse().ok()?,
+                percent: cols[0].parse().ok()?,
+            })
+        })
+        .collect();
+    stats.sort_by(|a, b| b.percent.total_cmp(&a.percent).then_with(|| b.calls.cmp(&a.calls)));
+    stats
+}
+
+/// Parse the `dtruss -c` count table printed after the trace:
+/// "CALL                                        COUNT" followed by
+/// "read                                           12". dtruss reports no
+/// timings, so the share is by call count.
+pub fn parse_dtruss_counts(text: &str) -> Vec<SyscallStat> {
+    let Some(start) = text.rfind("CALL") else {
+        return Vec::new();
+    };
+    let rows: Vec<(String, u64)> = text[start..]
+        .lines()
+        .skip(1)
+        .filter_map(|l| {
+            let (name, count) = l.trim().rsplit_once(char::is_whitespace)?;
+            Some((name.trim().to_string(), count.parse().ok()?))
+        })
+        .collect();
+    let total: u64 = rows.iter().map(|(_, c)| c).sum();
+    let mut stats: Vec<SyscallStat> = rows
+        .into_iter()
+        .map(|(name, calls)| SyscallStat {
+            name,
+            calls,
+            errors: 0,
+            seconds: 0.0,
+            usecs_per_call: 0,
+            percent: calls as f64 / total.max(1) as f64 * 100.0,
+        })
+        .collect();
+    stats.sort_by_key(|s| std::cmp::Reverse(s.calls));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_strace_summary_table() {
+        let output = "\
+strace: Process 4242 attached with 3 threads
+strace: Process 4242 detached
+% time     seconds  usecs/call     calls    errors syscall
+------ ----------- ----------- --------- --------- ----------------
+ 25.00    0.000100          10        10           read
+ 75.00    0.000300          15        20         4 futex
+------ ----------- ----------- --------- --------- ----------------
+100.00    0.000400          13        30         4 total
+";
+        let stats = parse_strace_summary(output);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name, "futex");
+        assert_eq!(stats[0].calls, 20);
+        assert_eq!(stats[0].errors, 4);
+        assert_eq!(stats[0].usecs_per_call, 15);
+        assert_eq!(stats[1].name, "read");
+        assert_eq!(stats[1].errors, 0);
+        assert!((stats[1].seconds - 0.0001).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parses_dtruss_counts() {
+        let output = "\
+SYSCALL(args) \t\t = return
+read(0x3, 0x7FF, 0x400)\t\t = 12 0
+
+CALL                                        COUNT
+write                                           1
+read                                            3
+";
+        let stats = parse_dtruss_counts(output);
+        assert_eq!(stats[0].name, "read");
+        assert_eq!(stats[0].percent, 75.0);
+        assert_eq!(stats.len(), 2);
+    }
+}
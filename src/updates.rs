@@ -0,0 +1,163 @@
+//! Pending package updates and whether a newer kernel than the running one is
+//! installed. Queries only the package manager's local metadata (no refresh),
+//! and is slow enough that the Monitor runs it in the background every
+//! CHECK_INTERVAL.
+
+use std::cmp::Ordering;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use crate::cmd;
+use crate::model::UpdateStatus;
+
+/// How often pending updates are re-checked.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Query the first available package manager and the installed kernels.
+pub fn check() -> UpdateStatus {
+    let mut status = if cfg!(target_os = "macos") {
+        check_brew()
+    } else {
+        check_apt().or_else(check_dnf)
+    }
+    .unwrap_or_default();
+
+    if cfg!(target_os = "linux") {
+        let running = sysinfo::System::kernel_version();
+        status.newer_kernel = newest_installed_kernel(Path::new("/lib/modules"))
+            .filter(|newest| running.as_deref().is_some_and(|r| compare_kernel_versions(newest, r).is_gt()));
+        status.reboot_flagged = Path::new("/var/run/reboot-required").exists();
+    }
+    status
+}
+
+fn check_apt() -> Option<UpdateStatus> {
+    let output = cmd::output(Command::new("apt").args(["list", "--upgradable"]), cmd::SLOW).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let (pending, security) = parse_apt_upgradable(&String::from_utf8_lossy(&output.stdout));
+    Some(UpdateStatus {
+        manager: Some("apt"),
+        pending,
+        security: Some(security),
+        ..Default::default()
+    })
+}
+
+fn check_dnf() -> Option<UpdateStatus> {
+    // check-update exits 100 when updates are available
+    let output = cmd::output(Command::new("dnf").args(["-C", "-q", "check-update"]), cmd::SLOW).ok()?;
+    if !matches!(output.status.code(), Some(0 | 100)) {
+        return None;
+    }
+    let pending = parse_dnf_check_update(&String::from_utf8_lossy(&output.stdout));
+    let security = cmd::output(Command::new("dnf").args(["-C", "-q", "updateinfo", "list", "--security"]), cmd::SLOW)
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_dnf_check_update(&String::from_utf8_lossy(&o.stdout)));
+    Some(UpdateStatus {
+        manager: Some("dnf"),
+        pending,
+        security,
+        ..Default::default()
+    })
+}
+
+fn check_brew() -> Option<UpdateStatus> {
+    let output = cmd::output(
+        Command::new("brew").args(["outdated", "--quiet"]).env("HOMEBREW_NO_AUTO_UPDATE", "1"),
+        cmd::SLOW,
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let pending = String::from_utf8_lossy(&output.stdout).lines().filter(|l| !l.trim().is_empty()).count();
+    Some(UpdateStatus {
+        manager: Some("brew"),
+        pending,
+        ..Default::default()
+    })
+}
+
+/// Count `apt list --upgradable` rows, e.g.
+/// "openssl/jammy-updates,jammy-security 3.0.2-0ubuntu1.15 amd64 [upgradable from: 3.0.2-0ubuntu1.14]",
+/// and those coming from a security pocket: (pending, security).
+pub fn parse_apt_upgradable(text: &str) -> (usize, usize) {
+    let rows: Vec<&str> = text.lines().filter(|l| l.contains("[upgradable from")).collect();
+    let security = rows
+        .iter()
+        .filter(|l| l.split_whitespace().next().is_some_and(|pkg| pkg.contains("-security")))
+        .count();
+    (rows.len(), security)
+}
+
+/// Count package rows ("name.arch  version  repo") in `dnf check-update` or
+/// `dnf updateinfo list` output, ignoring the obsoletes section.
+pub fn parse_dnf_check_update(text: &str) -> usize {
+    text.lines()
+        .take_while(|l| !l.starts_with("Obsoleting"))
+        .filter(|l| l.split_whitespace().count() == 3)
+        .count()
+}
+
+/// Highest kernel version with a modules directory.
+fn newest_installed_kernel(modules: &Path) -> Option<String> {
+    std::fs::read_dir(modules)
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .max_by(|a, b| compare_kernel_versions(a, b))
+}
+
+/// Compare kernel release strings by their numeric components, so
+/// "6.8.0-49-generic" > "6.8.0-45-generic" and "5.15.10" > "5.15.9".
+pub fn compare_kernel_versions(a: &str, b: &str) -> Ordering {
+    let parts = |s: &str| -> Vec<u64> {
+        s.split(|c: char| !c.is_ascii_digit())
+            .filter(|p| !p.is_empty())
+            .filter_map(|p| p.parse().ok())
+            .collect()
+    };
+    parts(a).cmp(&parts(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_apt_security_updates() {
+        let text = "\
+Listing...
+openssl/jammy-updates,jammy-security 3.0.2-0ubuntu1.15 amd64 [upgradable from: 3.0.2-0ubuntu1.14]
+vim/jammy-updates 2:8.2.3995-1ubuntu2.16 amd64 [upgradable from: 2:8.2.3995-1ubuntu2.15]
+";
+        assert_eq!(parse_apt_upgradable(text), (2, 1));
+    }
+
+    #[test]
+    fn counts_dnf_rows_before_obsoletes() {
+        let text = "\
+Last metadata expiration check: 0:12:01 ago.
+kernel.x86_64                 6.9.4-200.fc40          updates
+openssl-libs.x86_64           1:3.2.2-1.fc40          updates
+Obsoleting Packages
+grub2-tools.x86_64            1:2.06-121.fc40         updates
+";
+        assert_eq!(parse_dnf_check_update(text), 2);
+    }
+
+    #[test]
+    fn orders_kernel_versions_numerically() {
+        assert_eq!(compare_kernel_versions("6.8.0-49-generic", "6.8.0-45-generic"), Ordering::Greater);
+        assert_eq!(compare_kernel_versions("5.15.9", "5.15.10"), Ordering::Less);
+        assert_eq!(
+            compare_kernel_versions("5.14.0-427.13.1.el9_4.x86_64", "5.14.0-427.13.1.el9_4.x86_64"),
+            Ordering::Equal
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Attribute, ResetColor, SetAttribute, SetForegroundColor},
+    terminal::{Clear, ClearType},
+};
+use std::io::{self, Write};
+
+use super::shared::{display_width, safe_truncate, screen_size};
+use super::theme::theme;
+use crate::model::Action;
+
+/// What the selected row offers, on the row above the help footer: its name
+/// followed by the key and label of each action that fits.
+pub fn render_action_bar(out: &mut impl Write, name: &str, actions: &[&Action]) -> io::Result<()> {
+    let t = theme();
+    let (width, height) = screen_size()?;
+    let width = width as usize;
+    queue!(out, MoveTo(0, height.saturating_sub(2)), Clear(ClearType::CurrentLine))?;
+
+    let name = safe_truncate(name, 24);
+    queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+    write!(out, " {}", name)?;
+    queue!(out, SetAttribute(Attribute::Reset))?;
+    let mut col = 1 + display_width(name);
+    for action in actions {
+        let len = 2 + display_width(action.key) + 1 + display_width(action.label);
+        if col + len >= width {
+            break;
+        }
+        queue!(out, SetForegroundColor(t.help_key))?;
+        write!(out, "  {}", action.key)?;
+        queue!(out, SetForegroundColor(t.help_desc))?;
+        write!(out, ":{}", action.label)?;
+        col += len;
+    }
+    queue!(out, ResetColor)?;
+    out.flush()
+}
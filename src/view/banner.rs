@@ -0,0 +1,71 @@
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+};
+use std::io::{self, Write};
+
+use super::shared::{pad, screen_size, truncate_str};
+use super::theme::theme;
+use crate::notes::MaintenanceNote;
+use crate::recording::Replay;
+
+/// The maintenance note across the row below the tab bar.
+pub fn render_maintenance_banner(out: &mut impl Write, note: &MaintenanceNote) -> io::Result<()> {
+    let mut line = format!("  ⚠ MAINTENANCE  {}", note.text);
+    let set_at = chrono::DateTime::from_timestamp(note.at, 0).map(|t| t.with_timezone(&chrono::Local));
+    match (note.by.as_str(), set_at) {
+        ("", _) | (_, None) => {}
+        (by, Some(at)) => line.push_str(&format!("  — {} at {}", by, at.format("%H:%M"))),
+    }
+    render_row(out, &line, false)
+}
+
+/// Which recorded frame is on screen, in the maintenance banner's place.
+pub fn render_replay_banner(out: &mut impl Write, replay: &Replay) -> io::Result<()> {
+    let (position, total) = replay.position();
+    let at = chrono::DateTime::from_timestamp(replay.frame().at, 0).map(|t| t.with_timezone(&chrono::Local));
+    let at = at.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default();
+    let state = if replay.playing { "▶ REPLAY" } else { "⏸ REPLAY" };
+    let line = format!("  {}  {}  {}  frame {}/{}  (, / . step, > play)", state, replay.name, at, position, total);
+    render_row(out, &line, false)
+}
+
+/// The prompt for a new maintenance note, in the banner's place.
+pub fn render_note_prompt(out: &mut impl Write, input: &str) -> io::Result<()> {
+    render_row(out, &format!("  Maintenance note (Enter sets, empty clears, Esc cancels): {}_", input), true)
+}
+
+/// The command palette: the filter, then the matching macros with the
+/// highlighted one in brackets.
+pub fn render_palette(out: &mut impl Write, input: &str, names: &[&str], selected: usize) -> io::Result<()> {
+    let mut line = format!("  Run macro (Enter runs, Esc cancels): {}_  ", input);
+    if names.is_empty() {
+        line.push_str(" no match");
+    }
+    for (i, name) in names.iter().enumerate() {
+        if i == selected {
+            line.push_str(&format!(" [{}]", name));
+        } else {
+            line.push_str(&format!("  {} ", name));
+        }
+    }
+    render_row(out, &line, true)
+}
+
+fn render_row(out: &mut impl Write, text: &str, editing: bool) -> io::Result<()> {
+    let t = theme();
+    let width = screen_size()?.0 as usize;
+    let background = if editing { t.teal } else { t.peach };
+    queue!(
+        out,
+        MoveTo(0, 1),
+        SetBackgroundColor(background),
+        SetForegroundColor(t.base),
+        SetAttribute(Attribute::Bold)
+    )?;
+    write!(out, "{}", pad(&truncate_str(text, width), width))?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    out.flush()?;
+    Ok(())
+}
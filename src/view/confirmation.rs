@@ -2,16 +2,15 @@ use crossterm::{
     cursor::MoveTo,
     queue,
     style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
-    terminal,
 };
-use std::io::{self, stdout, Write};
+use std::io::{self, Write};
 
+use super::shared::{pad, screen_size};
 use super::theme::theme;
 
-pub fn render_confirmation(prompt: &str) -> io::Result<()> {
+pub fn render_confirmation(out: &mut impl Write, prompt: &str) -> io::Result<()> {
     let t = theme();
-    let mut out = stdout();
-    let size = terminal::size()?;
+    let size = screen_size()?;
     let y = size.1.saturating_sub(3);
     let width = size.0 as usize;
 
@@ -24,7 +23,7 @@ pub fn render_confirmation(prompt: &str) -> io::Result<()> {
         SetAttribute(Attribute::Bold)
     )?;
     let line = format!("  {} (y to confirm, any other key to cancel)  ", prompt);
-    write!(out, "{:<width$}", line, width = width)?;
+    write!(out, "{}", pad(&line, width))?;
     queue!(out, ResetColor, SetAttribute(Attribute::Reset))?;
     out.flush()?;
     Ok(())
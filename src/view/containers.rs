@@ -2,11 +2,17 @@ use crossterm::{
     cursor, queue,
     style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
 };
-use std::io::{self, stdout, Write};
+use std::io::{self, Write};
 
-use super::shared::{render_help_footer, safe_truncate, writeln};
+use super::shared::{braille_graph, display_width, format_mem_human, pad, render_help_footer, safe_truncate, writeln, Viewport};
 use super::theme::theme;
-use crate::model::{ContainerUIState, DockerContainerInfo};
+use crate::config::{ContainerColumn, ContainersConfig};
+use crate::docker::format_uptime;
+use crate::health_controller::{HealthMonitor, HealthResult, HealthTargetKind};
+use crate::model::{
+    format_label_filter, ContainerGroupInfo, ContainerRow, ContainerStatsHistory, ContainerUIState,
+    CpuScale, DockerContainerInfo,
+};
 
 /// Build a 6-char inline CPU bar using `|` for filled and space for empty.
 fn cpu_bar(percent: f64) -> String {
@@ -16,54 +22,354 @@ fn cpu_bar(percent: f64) -> String {
     format!("[{}{}]", "|".repeat(filled), " ".repeat(empty))
 }
 
+/// Write one cell of a container row and return the number of columns written.
+/// The last column is not padded so long values (e.g. IPs) aren't cut short.
+#[allow(clippy::too_many_arguments)]
+fn write_cell(
+    out: &mut impl Write,
+    c: &DockerContainerInfo,
+    col: ContainerColumn,
+    health: Option<&HealthResult>,
+    selected: bool,
+    recent: bool,
+    stale_image: bool,
+    last: bool,
+    cpu_shown: impl Fn(f64) -> f64,
+) -> io::Result<usize> {
+    let t = theme();
+    let cw = col.width();
+    let max = cw.saturating_sub(1);
+
+    let text = match col {
+        ContainerColumn::Id => c.id.clone(),
+        ContainerColumn::Name => c.name.clone(),
+        ContainerColumn::Image => c.image.clone(),
+        ContainerColumn::Status => c.state.clone(),
+        // Configured HTTP check first, then Docker's HEALTHCHECK state
+        ContainerColumn::Health => match (health, c.docker_health()) {
+            (Some(result), _) => result.summary(),
+            (None, Some(state)) => state.to_string(),
+            (None, None) => "-".to_string(),
+        },
+        ContainerColumn::Uptime => c.uptime.clone(),
+        ContainerColumn::Cpu => {
+            let percent = cpu_shown(c.cpu_percent);
+            let bar = cpu_bar(percent);
+            if !selected {
+                // bar in teal, percent in text
+                queue!(out, SetForegroundColor(t.bar_filled))?;
+                write!(out, "{}", bar)?;
+                queue!(out, SetForegroundColor(t.text))?;
+            } else {
+                write!(out, "{}", bar)?;
+            }
+            write!(out, " {:>5.1}%  ", percent)?;
+            return Ok(cw);
+        }
+        ContainerColumn::Mem => {
+            if c.mem_limit > 0 {
+                format!("{}/{}", format_mem_human(c.mem_usage), format_mem_human(c.mem_limit))
+            } else {
+                format_mem_human(c.mem_usage)
+            }
+        }
+        ContainerColumn::Net => {
+            format!("{}/{}", format_mem_human(c.net_rx), format_mem_human(c.net_tx))
+        }
+        ContainerColumn::Ports => c.ports.clone(),
+        ContainerColumn::Ip => c.ip_address.clone(),
+        ContainerColumn::ImageAge => match c.image_age_secs() {
+            Some(secs) => format!("{}{}", format_uptime(secs), if c.image_drift == Some(true) { " *" } else { "" }),
+            None => "-".to_string(),
+        },
+    };
+
+    if !selected {
+        let color = if col == ContainerColumn::Status {
+            // State with color
+            match c.state.to_lowercase().as_str() {
+                "running" => t.green,
+                "exited" | "stopped" => t.red,
+                "paused" => t.yellow,
+                _ => t.subtext,
+            }
+        } else if col == ContainerColumn::Health {
+            match (health, c.docker_health()) {
+                (Some(result), _) if result.is_healthy() => t.green,
+                (Some(_), _) | (None, Some("unhealthy")) => t.red,
+                (None, Some("healthy")) => t.green,
+                (None, Some(_)) => t.yellow,
+                (None, None) => t.subtext,
+            }
+        } else if (recent && matches!(col, ContainerColumn::Name | ContainerColumn::Uptime))
+            || (stale_image && col == ContainerColumn::ImageAge)
+        {
+            t.peach
+        } else {
+            t.text
+        };
+        queue!(out, SetForegroundColor(color))?;
+    }
+
+    if last {
+        write!(out, "{}", text)?;
+        Ok(display_width(&text))
+    } else {
+        write!(out, "{}", pad(safe_truncate(&text, max), cw))?;
+        Ok(cw)
+    }
+}
+
+/// An image older than `max_age_days` (0 disables) or behind the registry.
+fn image_is_stale(c: &DockerContainerInfo, max_age_days: u64) -> bool {
+    let too_old = max_age_days > 0 && c.image_age_secs().is_some_and(|s| s / 86400 > max_age_days);
+    too_old || c.image_drift == Some(true)
+}
+
+/// Cells in the CPU and memory sparklines; each holds two samples.
+const SPARKLINE_WIDTH: usize = 40;
+
+/// Lines shown under an expanded container: image, status, the image's
+/// vulnerability scan when there is one, any of the configured labels the
+/// container carries, and CPU / memory sparklines once a few samples have
+/// been recorded.
+fn detail_lines(
+    c: &DockerContainerInfo,
+    label_keys: &[String],
+    history: &ContainerStatsHistory,
+    cpu_shown: impl Fn(f64) -> f64,
+) -> Vec<String> {
+    let mut image = format!("       Image: {}", c.image);
+    if let Some(secs) = c.image_age_secs() {
+        image.push_str(&format!("  built {} ago", format_uptime(secs)));
+    }
+    if let Some(digest) = c.image_digests.first().and_then(|d| d.rsplit_once('@')).map(|(_, d)| d) {
+        image.push_str(&format!("  {}", safe_truncate(digest, 19)));
+    }
+    match c.image_drift {
+        Some(true) => image.push_str("  (registry has a newer digest)"),
+        Some(false) => image.push_str("  (matches registry)"),
+        None => {}
+    }
+    let mut lines = vec![image, format!("       Status: {}", c.status)];
+    if let Some(ref scan) = c.image_scan {
+        let age = format_uptime((chrono::Utc::now().timestamp() - scan.scanned).max(0) as u64);
+        lines.push(match scan.error {
+            Some(ref e) => format!("       Scan: failed {} ago ({}): {}", age, scan.scanner, e),
+            None => format!(
+                "       Scan: {} critical, {} high CVEs  ({}, {} ago)",
+                scan.critical, scan.high, scan.scanner, age
+            ),
+        });
+    }
+    let labels: Vec<String> = label_keys
+        .iter()
+        .filter_map(|k| c.labels.get(k).map(|v| format!("{}={}", k, v)))
+        .collect();
+    if !labels.is_empty() {
+        lines.push(format!("       Labels: {}", labels.join("  ")));
+    }
+
+    let cpu = history.cpu(&c.id);
+    if cpu.len() >= 2 {
+        // Tenths of a percent, so low CPU still shows a shape
+        let tenths: Vec<u64> = cpu.iter().map(|v| (v * 10.0).round() as u64).collect();
+        let peak = cpu.iter().cloned().fold(0.0, f64::max);
+        let avg = cpu.iter().sum::<f64>() / cpu.len() as f64;
+        lines.push(format!(
+            "       CPU: {}  now {:.1}%  avg {:.1}%  peak {:.1}%",
+            sparkline(&tenths),
+            cpu_shown(c.cpu_percent),
+            cpu_shown(avg),
+            cpu_shown(peak)
+        ));
+        let mem = history.mem(&c.id);
+        let peak = mem.iter().copied().max().unwrap_or(0);
+        lines.push(format!(
+            "       Mem: {}  now {}  peak {}",
+            sparkline(&mem),
+            format_mem_human(c.mem_usage),
+            format_mem_human(peak)
+        ));
+    }
+    lines
+}
+
+/// One-row braille chart of `values`, scaled to their own peak.
+fn sparkline(values: &[u64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    braille_graph(values, SPARKLINE_WIDTH, 1, max).concat()
+}
+
+/// Write a group header row: name, container count, and aggregate CPU/memory.
+fn write_group_header(
+    out: &mut impl Write,
+    group: &ContainerGroupInfo,
+    containers: &[DockerContainerInfo],
+    collapsed: bool,
+    selected: bool,
+    w: usize,
+    cpu_shown: impl Fn(f64) -> f64,
+) -> io::Result<()> {
+    let t = theme();
+    let indicator = if collapsed { "▶" } else { "▼" };
+    let members = group.container_indices.iter().filter_map(|&i| containers.get(i));
+    let (cpu, mem) = members.fold((0.0, 0u64), |(cpu, mem), c| (cpu + c.cpu_percent, mem + c.mem_usage));
+    let header = format!(
+        "  {} {} ({} containers)  CPU {:.1}%  MEM {}",
+        indicator,
+        group.name,
+        group.container_indices.len(),
+        cpu_shown(cpu),
+        format_mem_human(mem)
+    );
+
+    if selected {
+        queue!(out, SetBackgroundColor(t.selected_bg), SetForegroundColor(t.selected_fg))?;
+        write!(out, "{}\r\n", pad(&header, w))?;
+        queue!(out, ResetColor)?;
+    } else {
+        queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+        write!(out, "{}\r\n", header)?;
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_containers(
+    out: &mut impl Write,
     containers: &[DockerContainerInfo],
-    ui_state: &ContainerUIState,
+    groups: &[ContainerGroupInfo],
+    rows: &[ContainerRow],
+    ui_state: &mut ContainerUIState,
     status_message: &Option<String>,
+    config: &ContainersConfig,
+    health: &HealthMonitor,
+    history: &ContainerStatsHistory,
+    viewport: Viewport,
 ) -> io::Result<()> {
     let t = theme();
-    let mut out = stdout();
-    let (width, _height) = crossterm::terminal::size()?;
-    let w = width as usize;
+    let columns = &config.columns;
+    let w = viewport.width as usize;
+    let (cpu_scale, cores) = (ui_state.cpu_scale, ui_state.host_cores);
+    let cpu_shown = |percent: f64| cpu_scale.apply(percent, cores);
+
+    // Failing health checks, configured or Docker's own
+    let mut warnings = health.warnings(HealthTargetKind::Container);
+    warnings.extend(
+        containers
+            .iter()
+            .filter(|c| c.docker_health() == Some("unhealthy"))
+            .map(|c| format!("UNHEALTHY: {} ({})", c.name, c.status)),
+    );
+
+    // Lines left for rows: column header, help footer, status message (2),
+    // the action bar or filter prompt, warnings.
+    let reserved = 3
+        + warnings.len()
+        + if status_message.is_some() { 2 } else { 0 };
+    let capacity = (viewport.height as usize).saturating_sub(reserved).max(1);
+    let row_heights: Vec<usize> = rows
+        .iter()
+        .map(|row| match row {
+            ContainerRow::Container(i) if ui_state.expanded_ids.contains(&containers[*i].id) => {
+                1 + detail_lines(&containers[*i], &config.labels, history, cpu_shown).len()
+            }
+            _ => 1,
+        })
+        .collect();
+    ui_state.scroll_to_selected(&row_heights, capacity);
+
+    queue!(out, cursor::MoveTo(0, viewport.top))?;
 
-    queue!(out, cursor::MoveTo(0, 2))?;
+    for warning in &warnings {
+        queue!(out, SetForegroundColor(t.red), SetAttribute(Attribute::Bold))?;
+        writeln(out, &format!("  ⚠ {}", warning))?;
+        queue!(out, ResetColor, SetAttribute(Attribute::Reset))?;
+    }
 
-    if containers.is_empty() {
+    if !containers.is_empty() && rows.is_empty() {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, "")?;
+        writeln(
+            out,
+            &format!(
+                "  No containers match label filter: {}",
+                format_label_filter(&ui_state.label_filter)
+            ),
+        )?;
+        writeln(out, "")?;
+        writeln(out, "  Press / and submit an empty filter to show all containers.")?;
+        queue!(out, ResetColor)?;
+    } else if containers.is_empty() {
         queue!(out, SetForegroundColor(t.subtext))?;
-        writeln(&mut out, "")?;
-        writeln(&mut out, "  No running containers found.")?;
-        writeln(&mut out, "")?;
+        writeln(out, "")?;
+        writeln(out, "  No running containers found.")?;
+        writeln(out, "")?;
         writeln(
-            &mut out,
+            out,
             "  Make sure Docker is running and you have containers up.",
         )?;
         queue!(out, ResetColor)?;
     } else {
         // Column header
-        let header = format!(
-            "  {:<4}{:<15}{:<20}{:<11}{:<9}{:<16}{:<28}{}",
-            "##", "CONTAINER ID", "NAME", "STATE", "UPTIME", "CPU", "PORTS", "IP"
-        );
+        let mut header = format!("  {:<4}", "##");
+        for (i, col) in columns.iter().enumerate() {
+            let text = match col {
+                ContainerColumn::Cpu if cpu_scale == CpuScale::Total => "CPU (all cores)",
+                _ => col.header(),
+            };
+            if i + 1 == columns.len() {
+                header.push_str(text);
+            } else {
+                header.push_str(&format!("{:<cw$}", text, cw = col.width()));
+            }
+        }
         queue!(
             out,
             SetForegroundColor(t.header_fg),
             SetAttribute(Attribute::Bold),
         )?;
-        write!(out, "{:<w$}\r\n", header, w = w)?;
-        queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+        // Position indicator (and active filter), right-aligned in the header row
+        let filter_note = if ui_state.label_filter.is_empty() {
+            String::new()
+        } else {
+            format!("[{}] ", format_label_filter(&ui_state.label_filter))
+        };
+        let position = format!(
+            "{}{}/{} ",
+            filter_note,
+            ui_state.selected_index.min(rows.len().saturating_sub(1)) + 1,
+            rows.len()
+        );
+        let header_w = w.saturating_sub(display_width(&position));
+        write!(out, "{}", pad(safe_truncate(&header, header_w), header_w))?;
+        queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+        write!(out, "{}\r\n", position)?;
+        queue!(out, ResetColor)?;
 
-        for (idx, c) in containers.iter().enumerate() {
+        let mut used = 0;
+        for (idx, row) in rows.iter().enumerate().skip(ui_state.scroll_offset) {
+            used += row_heights[idx];
+            if used > capacity && idx > ui_state.scroll_offset {
+                break;
+            }
             let selected = idx == ui_state.selected_index;
+            let c = match *row {
+                ContainerRow::GroupHeader(gi) => {
+                    let group = &groups[gi];
+                    let collapsed = ui_state.collapsed_groups.contains(&group.name);
+                    write_group_header(out, group, containers, collapsed, selected, w, cpu_shown)?;
+                    continue;
+                }
+                ContainerRow::Container(i) => &containers[i],
+            };
             let is_multi_selected = ui_state.selected_containers.contains(&c.id);
 
             // Multi-select marker
             let marker = if is_multi_selected { "[*]" } else { "[ ]" };
 
-            let name_trunc = safe_truncate(&c.name, 18);
-            let ports_trunc = safe_truncate(&c.ports, 26);
-            let bar = cpu_bar(c.cpu_percent);
-            let cpu_str = format!("{} {:>5.1}%", bar, c.cpu_percent);
-
             // Set background for selected row
             if selected {
                 queue!(
@@ -75,84 +381,27 @@ pub fn render_containers(
 
             // Write the marker column
             write!(out, "  ")?;
-            if is_multi_selected {
-                // Mauve asterisk
-                if !selected {
-                    queue!(out, SetForegroundColor(t.mauve))?;
-                }
-                write!(out, "{:<4}", marker)?;
-                if !selected {
-                    queue!(out, ResetColor)?;
-                }
-            } else {
-                if selected {
-                    // Keep selected colors
-                } else {
-                    queue!(out, SetForegroundColor(t.text))?;
-                }
-                write!(out, "{:<4}", marker)?;
-                if !selected {
-                    queue!(out, ResetColor)?;
-                }
-            }
-
-            // Container ID
-            if selected {
-                // already have selected colors
-            } else {
-                queue!(out, SetForegroundColor(t.text))?;
-            }
-            write!(out, "{:<15}", safe_truncate(&c.id, 14))?;
-
-            // Name
-            write!(out, "{:<20}", name_trunc)?;
-
-            // State with color
-            let state_lower = c.state.to_lowercase();
             if !selected {
-                let state_color = if state_lower == "running" {
-                    t.green
-                } else if state_lower == "exited" || state_lower == "stopped" {
-                    t.red
-                } else if state_lower == "paused" {
-                    t.yellow
-                } else {
-                    t.subtext
-                };
-                queue!(out, SetForegroundColor(state_color))?;
+                // Mauve asterisk for multi-selected rows
+                let marker_color = if is_multi_selected { t.mauve } else { t.text };
+                queue!(out, SetForegroundColor(marker_color))?;
             }
-            write!(out, "{:<11}", safe_truncate(&c.state, 10))?;
+            write!(out, "{:<4}", marker)?;
 
-            // Uptime
-            if !selected {
-                queue!(out, SetForegroundColor(t.text))?;
-            }
-            write!(out, "{:<9}", safe_truncate(&c.uptime, 8))?;
+            // Recently (re)started containers are highlighted
+            let recent = c.uptime_secs.is_some_and(|s| s < config.recent_restart_secs);
+            let stale_image = image_is_stale(c, config.image_max_age_days);
 
-            // CPU bar + percent
-            if !selected {
-                // bar in teal, percent in text
-                queue!(out, SetForegroundColor(t.bar_filled))?;
-                write!(out, "{}", bar)?;
-                queue!(out, SetForegroundColor(t.text))?;
-                write!(out, " {:>5.1}%  ", c.cpu_percent)?;
-            } else {
-                write!(out, "{}  ", cpu_str)?;
+            let mut written = 2 + 4;
+            let health_result = health.get(HealthTargetKind::Container, &c.name);
+            for (i, col) in columns.iter().enumerate() {
+                let last = i + 1 == columns.len();
+                written += write_cell(out, c, *col, health_result, selected, recent, stale_image, last, cpu_shown)?;
             }
 
-            // Ports
-            write!(out, "{:<28}", ports_trunc)?;
-
-            // IP
-            write!(out, "{}", c.ip_address)?;
-
             // Pad to full width if selected (for background highlight)
-            if selected {
-                // Calculate how much we've written roughly and pad
-                let written_approx = 2 + 4 + 15 + 20 + 11 + 9 + 16 + 28 + c.ip_address.len();
-                if written_approx < w {
-                    write!(out, "{}", " ".repeat(w - written_approx))?;
-                }
+            if selected && written < w {
+                write!(out, "{}", " ".repeat(w - written))?;
             }
 
             queue!(out, ResetColor, SetAttribute(Attribute::Reset))?;
@@ -161,8 +410,9 @@ pub fn render_containers(
             // Expanded details
             if ui_state.expanded_ids.contains(&c.id) {
                 queue!(out, SetForegroundColor(t.subtext))?;
-                writeln(&mut out, &format!("       Image: {}", c.image))?;
-                writeln(&mut out, &format!("       Status: {}", c.status))?;
+                for line in detail_lines(c, &config.labels, history, cpu_shown) {
+                    writeln(out, &line)?;
+                }
                 queue!(out, ResetColor)?;
             }
         }
@@ -170,27 +420,63 @@ pub fn render_containers(
 
     // Status message
     if let Some(msg) = status_message {
-        writeln(&mut out, "")?;
+        writeln(out, "")?;
         queue!(out, SetForegroundColor(t.yellow))?;
-        writeln(&mut out, &format!("  {}", msg))?;
+        writeln(out, &format!("  {}", msg))?;
         queue!(out, ResetColor)?;
     }
 
     // Help footer
-    let (_width, height) = crossterm::terminal::size()?;
-    let help_y = height.saturating_sub(1);
+    let help_y = viewport.bottom();
+    if ui_state.filter_mode {
+        queue!(out, cursor::MoveTo(0, help_y.saturating_sub(1)), SetForegroundColor(t.teal))?;
+        write!(out, "  Label filter: {}_", ui_state.filter_input)?;
+        queue!(out, ResetColor)?;
+        render_help_footer(
+            out,
+            &[
+                ("Type", "key=value, key!=value, key"),
+                ("Enter", "Apply"),
+                ("Esc", "Cancel"),
+            ],
+            w,
+            help_y,
+        )?;
+        out.flush()?;
+        return Ok(());
+    }
+    if let Some(ref input) = ui_state.rename_input {
+        queue!(out, cursor::MoveTo(0, help_y.saturating_sub(1)), SetForegroundColor(t.teal))?;
+        write!(out, "  Rename to: {}_", input)?;
+        queue!(out, ResetColor)?;
+        render_help_footer(out, &[("Type", "new name"), ("Enter", "Rename"), ("Esc", "Cancel")], w, help_y)?;
+        out.flush()?;
+        return Ok(());
+    }
     render_help_footer(
-        &mut out,
+        out,
         &[
             ("q", "Quit"),
             ("\u{2191}\u{2193}", "Select"),
             ("Enter", "Expand"),
             ("Space", "Select"),
+            ("g", "Group"),
+            ("u", "Uptime sort"),
+            ("/", "Filter"),
+            ("p", "Probe ports"),
+            ("n", "Sockets"),
+            ("x/X", "Export"),
+            ("G", "Graphs"),
+            ("D", "Daemon"),
             ("L", "Logs"),
             ("M", "Multi-Log"),
+            ("V", "Split logs"),
+            ("w", "Watch"),
+            ("P", "Pin summary"),
             ("S", "Start"),
             ("T", "Stop"),
             ("R", "Restart"),
+            ("N", "Rename"),
             ("Tab", "Next"),
         ],
         w,
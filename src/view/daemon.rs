@@ -0,0 +1,173 @@
+use crossterm::{
+    cursor, queue,
+    style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor},
+};
+use std::io::{self, Write};
+
+use super::shared::{format_mem_human, render_help_footer, truncate_str, writeln, screen_size};
+use super::theme::theme;
+use crate::model::{DaemonHealth, DaemonLatency, DaemonProcessUsage};
+
+/// Reads one call's time out of a latency check.
+type LatencyField = fn(&DaemonLatency) -> Option<f64>;
+
+/// Calls slower than this are shown in yellow, and ten times that in red.
+const SLOW_CALL_MS: f64 = 100.0;
+
+fn latency_color(ms: Option<f64>) -> Color {
+    let t = theme();
+    match ms {
+        None => t.red,
+        Some(ms) if ms >= SLOW_CALL_MS * 10.0 => t.red,
+        Some(ms) if ms >= SLOW_CALL_MS => t.yellow,
+        Some(_) => t.green,
+    }
+}
+
+fn format_ms(ms: Option<f64>) -> String {
+    match ms {
+        Some(ms) if ms < 10.0 => format!("{:.1}ms", ms),
+        Some(ms) => format!("{:.0}ms", ms),
+        None => "failed".to_string(),
+    }
+}
+
+fn render_latency(out: &mut impl Write, health: &DaemonHealth) -> io::Result<()> {
+    let t = theme();
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:<16} {:<10} {:<12} FAILED\r\n", "API CALL", "LAST", "WORST")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+
+    let calls: [(&str, LatencyField); 2] = [("ping", |s| s.ping_ms), ("info", |s| s.info_ms)];
+    for (name, field) in calls {
+        let last = health.last().and_then(field);
+        let worst = health.worst_ms(field);
+        queue!(out, SetForegroundColor(latency_color(last)))?;
+        write!(out, "  {:<16} {:<10} ", name, format_ms(last))?;
+        queue!(out, SetForegroundColor(latency_color(worst)))?;
+        write!(out, "{:<12} ", if worst.is_some() { format_ms(worst) } else { "-".to_string() })?;
+        queue!(out, ResetColor)?;
+        write!(out, "{}/{}\r\n", health.failures(field), health.latency.len())?;
+    }
+    if let Some(ref e) = health.error {
+        queue!(out, SetForegroundColor(t.red))?;
+        writeln(out, &format!("  {}", e))?;
+        queue!(out, ResetColor)?;
+    }
+    Ok(())
+}
+
+fn render_processes(out: &mut impl Write, processes: &[DaemonProcessUsage]) -> io::Result<()> {
+    let t = theme();
+    if processes.is_empty() {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, "  No dockerd process on this host (a remote daemon or a Docker Desktop VM).")?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    }
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:<16} {:<7} {:<8} {:<12} THREADS\r\n", "PROCESS", "COUNT", "CPU", "MEM")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    for p in processes {
+        write!(
+            out,
+            "  {:<16} {:<7} {:<8} {:<12} {}\r\n",
+            p.name,
+            p.count,
+            format!("{:.1}%", p.cpu_percent),
+            format_mem_human(p.rss_bytes),
+            p.threads.map_or_else(|| "-".to_string(), |n| n.to_string()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Color of a daemon log line by its logrus level.
+fn log_line_color(line: &str) -> Option<Color> {
+    let t = theme();
+    let lower = line.to_lowercase();
+    if lower.contains("level=error") || lower.contains("level=fatal") || lower.contains("panic") {
+        Some(t.red)
+    } else if lower.contains("level=warn") {
+        Some(t.yellow)
+    } else {
+        None
+    }
+}
+
+pub fn render_docker_health(
+    out: &mut impl Write,
+    health: Option<&DaemonHealth>,
+    processes: &[DaemonProcessUsage],
+    status_message: &Option<String>,
+) -> io::Result<()> {
+    let t = theme();
+    queue!(out, cursor::MoveTo(0, 2))?;
+    let size = screen_size()?;
+    let width = size.0 as usize;
+
+    let title = match health {
+        Some(h) if !h.version.is_empty() => format!(
+            "  Docker Daemon: {}{}, storage driver {}, via {}",
+            h.version,
+            if h.rootless { " (rootless)" } else { "" },
+            h.storage_driver,
+            h.endpoint
+        ),
+        Some(h) => format!("  Docker Daemon via {}", h.endpoint),
+        None => "  Docker Daemon".to_string(),
+    };
+    queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+    writeln(out, &title)?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    writeln(out, "")?;
+
+    let Some(health) = health else {
+        writeln(out, "  Checking the daemon...")?;
+        out.flush()?;
+        return Ok(());
+    };
+    render_latency(out, health)?;
+    writeln(out, "")?;
+    render_processes(out, processes)?;
+    writeln(out, "")?;
+
+    queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+    let source = if health.log_source.is_empty() { "reading..." } else { &health.log_source };
+    writeln(out, &format!("  Daemon log ({})", source))?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    if let Some(ref e) = health.log_error {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, &format!("  {}", e))?;
+        queue!(out, ResetColor)?;
+    }
+
+    // The newest lines that fit above the status line and footer
+    let latency_rows = 3 + usize::from(health.error.is_some());
+    let process_rows = if processes.is_empty() { 1 } else { 1 + processes.len() };
+    let used = 4 + latency_rows + 1 + process_rows + 2 + usize::from(health.log_error.is_some());
+    let room = (size.1 as usize).saturating_sub(used + 3);
+    let skip = health.log_lines.len().saturating_sub(room);
+    for line in &health.log_lines[skip..] {
+        if let Some(color) = log_line_color(line) {
+            queue!(out, SetForegroundColor(color))?;
+        }
+        writeln(out, &truncate_str(&format!("  {}", line), width))?;
+        queue!(out, ResetColor)?;
+    }
+
+    if let Some(msg) = status_message {
+        queue!(out, SetForegroundColor(t.yellow))?;
+        writeln(out, &format!("  {}", msg))?;
+        queue!(out, ResetColor)?;
+    }
+
+    render_help_footer(
+        out,
+        &[("q/Esc/←", "Containers"), ("Tab", "Next")],
+        width,
+        size.1.saturating_sub(1),
+    )?;
+    out.flush()?;
+    Ok(())
+}
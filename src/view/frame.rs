@@ -0,0 +1,204 @@
+//! An in-memory screen the views can draw into instead of the terminal.
+//! It understands the subset of ANSI the views emit (cursor moves, clears,
+//! colors), keeps the characters in a grid and drops the styling, so a
+//! rendered view can be compared as plain text in tests or embedded by a
+//! library consumer.
+
+use std::io::{self, Write};
+
+use super::shared::{char_width, with_screen_size};
+
+/// Fills the second cell of a wide character; left out of the text.
+const WIDE_TAIL: char = '\0';
+
+pub struct Frame {
+    width: u16,
+    height: u16,
+    cells: Vec<Vec<char>>,
+    row: usize,
+    col: usize,
+    /// Bytes of an escape sequence or UTF-8 character split across writes.
+    pending: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![vec![' '; width as usize]; height as usize],
+            row: 0,
+            col: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Run `draw` against this frame, with the views laying out for its size
+    /// rather than the terminal's.
+    pub fn draw<T>(&mut self, draw: impl FnOnce(&mut Self) -> io::Result<T>) -> io::Result<T> {
+        with_screen_size((self.width, self.height), || draw(self))
+    }
+
+    /// The screen as text, one line per row with trailing blanks trimmed.
+    pub fn text(&self) -> String {
+        let lines: Vec<String> = self.cells.iter().map(|row| row_text(row)).collect();
+        lines.join("\n")
+    }
+
+    /// One row of the screen, trailing blanks trimmed.
+    pub fn line(&self, row: u16) -> String {
+        self.cells.get(row as usize).map(|r| row_text(r)).unwrap_or_default()
+    }
+
+    fn put(&mut self, c: char) {
+        match c {
+            '\r' => self.col = 0,
+            '\n' => self.row += 1,
+            c if c.is_control() => {}
+            c => {
+                // A combining mark has no cell of its own and is dropped
+                let width = char_width(c);
+                if let Some(row) = self.cells.get_mut(self.row) {
+                    for (i, fill) in [c, WIDE_TAIL].into_iter().take(width).enumerate() {
+                        if let Some(cell) = row.get_mut(self.col + i) {
+                            *cell = fill;
+                        }
+                    }
+                }
+                self.col += width;
+            }
+        }
+    }
+
+    fn clear_line(&mut self, row: usize, from: usize) {
+        if let Some(cells) = self.cells.get_mut(row) {
+            for cell in cells.iter_mut().skip(from) {
+                *cell = ' ';
+            }
+        }
+    }
+
+    /// Apply a CSI sequence: its parameters and final byte.
+    fn csi(&mut self, params: &str, command: char) {
+        let args: Vec<usize> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+        let arg = |i: usize, default: usize| args.get(i).copied().filter(|n| *n > 0).unwrap_or(default);
+        match command {
+            'H' | 'f' => {
+                self.row = arg(0, 1) - 1;
+                self.col = arg(1, 1) - 1;
+            }
+            'G' => self.col = arg(0, 1) - 1,
+            'C' => self.col += arg(0, 1),
+            'D' => self.col = self.col.saturating_sub(arg(0, 1)),
+            'A' => self.row = self.row.saturating_sub(arg(0, 1)),
+            'B' => self.row += arg(0, 1),
+            'E' => {
+                self.row += arg(0, 1);
+                self.col = 0;
+            }
+            'J' => {
+                let (start, first_col) = match args.first().copied().unwrap_or(0) {
+                    0 => (self.row, self.col),
+                    _ => (0, 0),
+                };
+                self.clear_line(start, first_col);
+                for row in start + 1..self.cells.len() {
+                    self.clear_line(row, 0);
+                }
+            }
+            'K' => {
+                let from = if args.first().copied().unwrap_or(0) == 0 { self.col } else { 0 };
+                self.clear_line(self.row, from);
+            }
+            // Colors, attributes, cursor visibility: no effect on the text
+            _ => {}
+        }
+    }
+
+    /// Consume complete sequences and characters from `pending`.
+    fn process(&mut self) {
+        let mut i = 0;
+        let bytes = std::mem::take(&mut self.pending);
+        while i < bytes.len() {
+            if bytes[i] == 0x1b {
+                match bytes.get(i + 1) {
+                    None => break,
+                    Some(b'[') => {
+                        let Some(end) = bytes[i + 2..].iter().position(|b| (0x40..=0x7e).contains(b)) else { break };
+                        let params = String::from_utf8_lossy(&bytes[i + 2..i + 2 + end]).replace('?', "");
+                        self.csi(&params, bytes[i + 2 + end] as char);
+                        i += end + 3;
+                    }
+                    // OSC (title, clipboard): skip to BEL or ST
+                    Some(b']') => {
+                        let rest = &bytes[i + 2..];
+                        let Some(end) = rest.iter().position(|b| *b == 0x07 || *b == 0x1b) else { break };
+                        let st = usize::from(rest[end] == 0x1b);
+                        if st == 1 && rest.get(end + 1).is_none() {
+                            break;
+                        }
+                        i += end + 3 + st;
+                    }
+                    Some(_) => i += 2,
+                }
+                continue;
+            }
+            let len = match bytes[i] {
+                b if b < 0x80 => 1,
+                b if b >= 0xf0 => 4,
+                b if b >= 0xe0 => 3,
+                _ => 2,
+            };
+            if i + len > bytes.len() {
+                break;
+            }
+            let s = String::from_utf8_lossy(&bytes[i..i + len]).into_owned();
+            for c in s.chars() {
+                self.put(c);
+            }
+            i += len;
+        }
+        self.pending = bytes[i..].to_vec();
+    }
+}
+
+fn row_text(row: &[char]) -> String {
+    row.iter().filter(|c| **c != WIDE_TAIL).collect::<String>().trim_end().to_string()
+}
+
+impl Write for Frame {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        self.process();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::{cursor::MoveTo, queue, style::{Color, SetForegroundColor}, terminal::{Clear, ClearType}};
+
+    #[test]
+    fn places_text_by_cursor_moves_and_ignores_colors() {
+        let mut frame = Frame::new(20, 3);
+        queue!(frame, Clear(ClearType::All), MoveTo(2, 1), SetForegroundColor(Color::Red)).unwrap();
+        write!(frame, "héllo\r\nnext").unwrap();
+        queue!(frame, MoveTo(0, 0)).unwrap();
+        frame.write_all("tôp".as_bytes()).unwrap();
+        assert_eq!(frame.text(), "tôp\n  héllo\nnext");
+        queue!(frame, MoveTo(3, 1), Clear(ClearType::UntilNewLine)).unwrap();
+        assert_eq!(frame.line(1), "  h");
+
+        // A wide character takes two cells, so what follows lands after both
+        queue!(frame, MoveTo(0, 2), Clear(ClearType::CurrentLine)).unwrap();
+        write!(frame, "\u{65e5}x").unwrap();
+        queue!(frame, MoveTo(3, 2)).unwrap();
+        write!(frame, "y").unwrap();
+        assert_eq!(frame.line(2), "\u{65e5}xy");
+    }
+}
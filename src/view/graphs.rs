@@ -0,0 +1,124 @@
+use crossterm::{
+    cursor::MoveTo,
+    execute, queue,
+    style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    terminal::{Clear, ClearType},
+};
+use std::io::{self, Write};
+
+use super::shared::{braille_graph, pad, render_help_footer, truncate_str, screen_size};
+use super::system::{graph_width, GRAPH_AXIS_WIDTH};
+use super::theme::theme;
+use crate::model::GraphViewState;
+
+/// Vertical resolution the values are quantised to before plotting.
+const GRAPH_LEVELS: u64 = 1000;
+
+pub fn render_graphs(out: &mut impl Write, state: &GraphViewState, status_message: &Option<String>) -> io::Result<()> {
+    let t = theme();
+    execute!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+
+    let size = screen_size()?;
+    let width = size.0 as usize;
+    let height = size.1 as usize;
+
+    let label = state.metric().map(|m| m.label()).unwrap_or_else(|| "No metrics".to_string());
+    let header = format!("  Graphs \u{203a} {} [{}]", label, state.range_label());
+    queue!(
+        out,
+        SetBackgroundColor(t.tab_active_bg),
+        SetForegroundColor(t.tab_active_fg),
+        SetAttribute(Attribute::Bold)
+    )?;
+    write!(out, "{}\r\n", pad(&truncate_str(&header, width), width))?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+
+    let recorded: Vec<f64> = state.values.iter().flatten().copied().collect();
+    queue!(out, SetForegroundColor(t.subtext))?;
+    write!(out, "  Metric {}/{}", state.selected + 1, state.metrics.len())?;
+    if let Some(last) = recorded.last() {
+        let min = recorded.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = recorded.iter().copied().fold(0.0, f64::max);
+        let avg = recorded.iter().sum::<f64>() / recorded.len() as f64;
+        write!(
+            out,
+            "   last {}  min {}  avg {}  max {}",
+            format_value(*last),
+            format_value(min),
+            format_value(avg),
+            format_value(max)
+        )?;
+    }
+    write!(out, "\r\n\r\n")?;
+
+    if recorded.is_empty() {
+        write!(out, "  No samples recorded in the last {}", state.range_label())?;
+    } else {
+        // Header, stats, blank line above; time axis, note and footer below
+        let graph_height = height.saturating_sub(6).max(1);
+        let graph_width = graph_width(width);
+        let max = recorded.iter().copied().fold(0.0, f64::max).max(f64::EPSILON);
+        let scaled: Vec<u64> = state
+            .values
+            .iter()
+            .map(|v| v.map_or(0, |v| (v / max * GRAPH_LEVELS as f64).round() as u64))
+            .collect();
+        let rows = braille_graph(&scaled, graph_width, graph_height, GRAPH_LEVELS);
+        for (row, line) in rows.iter().enumerate() {
+            let axis = if row == 0 {
+                format_value(max)
+            } else if row + 1 == rows.len() {
+                "0".to_string()
+            } else {
+                String::new()
+            };
+            queue!(out, SetForegroundColor(t.subtext))?;
+            write!(out, "  {:>width$}", axis, width = GRAPH_AXIS_WIDTH - 1)?;
+            queue!(out, SetForegroundColor(t.teal))?;
+            write!(out, " {}", line)?;
+            queue!(out, ResetColor)?;
+            write!(out, "\r\n")?;
+        }
+        let start = format!("-{}", state.range_label());
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(
+            out,
+            "  {:>axis$} {}{:>rest$}",
+            "",
+            start,
+            "now",
+            axis = GRAPH_AXIS_WIDTH - 1,
+            rest = graph_width.saturating_sub(start.len())
+        )?;
+        queue!(out, ResetColor)?;
+    }
+
+    let help_y = size.1.saturating_sub(1);
+    if let Some(msg) = status_message {
+        queue!(out, MoveTo(1, help_y.saturating_sub(1)), SetForegroundColor(t.yellow))?;
+        write!(out, "{}", truncate_str(msg, width.saturating_sub(2)))?;
+        queue!(out, ResetColor)?;
+    }
+    render_help_footer(
+        out,
+        &[
+            ("\u{2191}\u{2193}", "Metric"),
+            ("+/-", "Zoom"),
+            ("Esc", "Back"),
+        ],
+        width,
+        help_y,
+    )?;
+    out.flush()
+}
+
+/// Compact value label: integers for large values, two decimals for small.
+fn format_value(v: f64) -> String {
+    if v >= 100.0 {
+        format!("{:.0}", v)
+    } else if v >= 10.0 {
+        format!("{:.1}", v)
+    } else {
+        format!("{:.2}", v)
+    }
+}
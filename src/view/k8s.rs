@@ -0,0 +1,139 @@
+use crossterm::{
+    cursor, queue,
+    style::{Attribute, ResetColor, SetAttribute, SetForegroundColor},
+};
+use std::io::{self, Write};
+
+use super::shared::{pad, render_help_footer, screen_size, truncate_str, write_selectable, writeln};
+use super::theme::theme;
+use crate::docker::{format_uptime, secs_since};
+use crate::k8s_controller::KubeMonitor;
+use crate::model::KubeRow;
+
+/// Rows above the list: tab bar, title, summary, error, blank, column header.
+const HEADER_ROWS: usize = 7;
+
+fn age(created: Option<i64>) -> String {
+    created.and_then(secs_since).map_or_else(|| "-".to_string(), format_uptime)
+}
+
+pub fn render_kubernetes(out: &mut impl Write, kube: &KubeMonitor) -> io::Result<()> {
+    let t = theme();
+    queue!(out, cursor::MoveTo(0, 2))?;
+    let size = screen_size()?;
+    let width = size.0 as usize;
+
+    queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+    writeln(out, "  Kubernetes")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+
+    let unready = kube.pods.iter().filter(|p| !p.is_healthy()).count();
+    queue!(out, SetForegroundColor(t.subtext))?;
+    write!(
+        out,
+        "  Context: {} | {} namespaces, {} deployments, {} pods",
+        kube.context.as_deref().unwrap_or("-"),
+        kube.namespaces.len(),
+        kube.deployments.len(),
+        kube.pods.len()
+    )?;
+    if unready > 0 {
+        queue!(out, SetForegroundColor(t.peach))?;
+        write!(out, " ({} not ready)", unready)?;
+    }
+    write!(out, "\r\n")?;
+    queue!(out, ResetColor)?;
+    match kube.error {
+        Some(ref error) => {
+            queue!(out, SetForegroundColor(t.red))?;
+            writeln(out, &format!("  Error: {}", truncate_str(error, width.saturating_sub(10))))?;
+            queue!(out, ResetColor)?;
+        }
+        None => writeln(out, "")?,
+    }
+    writeln(out, "")?;
+
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:<46} {:<7} {:<20} {:<8} {:<16} AGE\r\n", "NAME", "READY", "STATUS", "RESTARTS", "NODE")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+
+    let rows = kube.rows();
+    if rows.is_empty() && kube.last_updated.is_none() {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, "  Collecting...")?;
+        queue!(out, ResetColor)?;
+    }
+    // Keep the selection on screen
+    let capacity = (size.1 as usize).saturating_sub(HEADER_ROWS + 2).max(1);
+    let first = (kube.ui_state.selected_index + 1).saturating_sub(capacity);
+    for (idx, row) in rows.iter().enumerate().skip(first).take(capacity) {
+        let selected = idx == kube.ui_state.selected_index;
+        let (line, color) = match row {
+            KubeRow::Namespace(ns) => {
+                let pods = kube.pods.iter().filter(|p| p.namespace == *ns).count();
+                let arrow = if kube.ui_state.collapsed.contains(ns) { "▸" } else { "▾" };
+                (format!("  {} {} ({} pods)", arrow, ns, pods), t.lavender)
+            }
+            KubeRow::Deployment(i) => {
+                let d = &kube.deployments[*i];
+                let status = format!("{} up-to-date, {} avail", d.up_to_date, d.available);
+                let line = format!(
+                    "    {} {:<7} {:<20} {:<8} {:<16} {}",
+                    pad(&truncate_str(&format!("deploy/{}", d.name), 44), 44),
+                    d.ready_label(),
+                    truncate_str(&status, 20),
+                    "",
+                    truncate_str(&d.images, 16),
+                    age(d.created)
+                );
+                (line, if d.is_degraded() { t.peach } else { t.text })
+            }
+            KubeRow::Pod(i) => {
+                let p = &kube.pods[*i];
+                // Under its deployment, or with the namespace's unowned pods
+                let owned = kube
+                    .deployments
+                    .iter()
+                    .any(|d| d.namespace == p.namespace && p.deployment.as_deref() == Some(d.name.as_str()));
+                let indent = if owned { "      " } else { "    " };
+                let line = format!(
+                    "{}{} {:<7} {:<20} {:<8} {:<16} {}",
+                    indent,
+                    pad(&truncate_str(&p.name, 48 - indent.len()), 48 - indent.len()),
+                    p.ready_label(),
+                    truncate_str(&p.status, 20),
+                    p.restarts,
+                    truncate_str(&p.node, 16),
+                    age(p.created)
+                );
+                let color = if p.is_healthy() {
+                    t.text
+                } else if p.status == "Pending" || p.status.starts_with("Init:") || p.status == "ContainerCreating" {
+                    t.peach
+                } else {
+                    t.red
+                };
+                (line, color)
+            }
+        };
+        queue!(out, SetForegroundColor(color))?;
+        write_selectable(out, &truncate_str(&line, width), selected)?;
+        queue!(out, ResetColor)?;
+    }
+
+    render_help_footer(
+        out,
+        &[
+            ("q", "Quit"),
+            ("\u{2191}\u{2193}", "Select"),
+            ("\u{2192}", "Logs"),
+            ("Space", "Fold namespace"),
+            ("Tab", "Next"),
+        ],
+        width,
+        size.1.saturating_sub(1),
+    )?;
+
+    out.flush()?;
+    Ok(())
+}
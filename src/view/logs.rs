@@ -1,14 +1,26 @@
 use crossterm::{
     cursor::MoveTo,
-    execute, queue,
-    style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
-    terminal::{self, Clear, ClearType},
+    queue,
+    style::{Attribute, Color, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
 };
-use std::io::{self, stdout, Write};
+use std::io::{self, Write};
+use std::time::Instant;
 
-use super::shared::safe_truncate;
+use super::shared::{pad, safe_truncate, wrap, Viewport};
 use super::theme::theme;
-use crate::model::{LogViewState, MultiLogViewState, ServiceLogState};
+use crate::model::{
+    log_timestamp, visible_start, Freshness, LogSelection, LogViewState, MultiLogViewState, ServiceLogState,
+    SplitLogState,
+};
+
+/// Footer while the line cursor is active.
+const SELECTION_FOOTER: &[(&str, &str)] = &[
+    ("↑↓/PgUp/PgDn", "Move"),
+    ("y", "Copy"),
+    ("Enter", "Expand"),
+    ("#", "Line numbers"),
+    ("v/Esc", "Done"),
+];
 
 /// Render a themed help footer at the last row.
 fn render_help_footer(out: &mut impl Write, items: &[(&str, &str)], width: usize, y: u16) -> io::Result<()> {
@@ -31,14 +43,89 @@ fn render_help_footer(out: &mut impl Write, items: &[(&str, &str)], width: usize
     Ok(())
 }
 
-pub fn render_logs(log_state: &LogViewState) -> io::Result<()> {
+/// Header suffix with the age of the newest line, or that the stream ended.
+fn freshness_indicator(last_line_at: Option<Instant>, ended: bool) -> String {
+    format!(" | {}", Freshness::of_log(last_line_at.map(|t| t.elapsed()), ended).label())
+}
+
+/// Header suffix naming the selected or just copied line.
+fn selection_indicator(selection: &LogSelection) -> String {
+    match (selection.line, selection.copied) {
+        (Some(line), Some(copied)) if line == copied => format!(" | COPIED LINE {}", line + 1),
+        (Some(line), _) => format!(" | LINE {}", line + 1),
+        _ => String::new(),
+    }
+}
+
+/// Write one log line: numbered when line numbers are on, and highlighted
+/// across the full width when it carries the cursor. `number` is the
+/// line's absolute, zero-based number.
+fn write_log_line(
+    out: &mut impl Write,
+    text: &str,
+    number: u64,
+    selection: &LogSelection,
+    color: Color,
+    width: usize,
+) -> io::Result<()> {
+    let t = theme();
+    let text = if selection.line_numbers {
+        format!("{:>6} {}", number + 1, text)
+    } else {
+        text.to_string()
+    };
+    let display_line = safe_truncate(&text, width);
+    if selection.line == Some(number) {
+        queue!(out, SetBackgroundColor(t.selected_bg), SetForegroundColor(t.selected_fg))?;
+        write!(out, "{}\r\n", pad(display_line, width))?;
+    } else {
+        queue!(out, SetForegroundColor(color))?;
+        write!(out, "{}\r\n", display_line)?;
+    }
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// The selected line untruncated, wrapped in a box over the log.
+fn render_line_popup(out: &mut impl Write, title: &str, text: &str, viewport: Viewport) -> io::Result<()> {
+    let t = theme();
+    let (width, height) = (viewport.width as usize, viewport.height as usize);
+    let box_width = width.saturating_sub(4).clamp(10, 120);
+    let inner = box_width - 4;
+    let max_rows = height.saturating_sub(8).max(1);
+    let mut rows = wrap(text, inner);
+    if rows.is_empty() {
+        rows.push(String::new());
+    }
+    if rows.len() > max_rows {
+        rows.truncate(max_rows);
+        if let Some(last) = rows.last_mut() {
+            *last = format!("{}…", safe_truncate(last, inner.saturating_sub(1)));
+        }
+    }
+
+    let x = (width.saturating_sub(box_width) / 2) as u16;
+    let top = viewport.top + (height.saturating_sub(rows.len() + 2) / 2) as u16;
+    queue!(out, SetBackgroundColor(t.surface), SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    queue!(out, MoveTo(x, top))?;
+    write!(out, "{}", pad(&format!("  {}", safe_truncate(title, inner)), box_width))?;
+    queue!(out, SetAttribute(Attribute::Reset), SetBackgroundColor(t.surface), SetForegroundColor(t.text))?;
+    for (i, row) in rows.iter().enumerate() {
+        queue!(out, MoveTo(x, top + 1 + i as u16))?;
+        write!(out, "  {}  ", pad(row, inner))?;
+    }
+    queue!(out, MoveTo(x, top + 1 + rows.len() as u16), SetForegroundColor(t.subtext))?;
+    write!(out, "{:<box_width$}", "  y: copy  any other key: close", box_width = box_width)?;
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+pub fn render_logs(out: &mut impl Write, log_state: &LogViewState, viewport: Viewport) -> io::Result<()> {
     let t = theme();
-    let mut out = stdout();
-    execute!(out, Clear(ClearType::All), crossterm::cursor::MoveTo(0, 0))?;
+    viewport.clear(out)?;
 
-    let size = terminal::size()?;
-    let width = size.0 as usize;
-    let height = size.1 as usize;
+    let width = viewport.width as usize;
+    let height = viewport.height as usize;
 
     // Header bar with mauve background
     let follow_indicator = if log_state.auto_follow {
@@ -57,65 +144,59 @@ pub fn render_logs(log_state: &LogViewState) -> io::Result<()> {
         String::new()
     };
     let header = format!(
-        "  Containers › Logs: {} ({}) - {}{}{}",
-        log_state.container_name, log_state.container_id, follow_indicator, search_indicator, truncated_indicator
+        "  {} › Logs: {} ({}) - {}{}{}{}{}",
+        log_state.section,
+        log_state.container_name,
+        log_state.container_id,
+        follow_indicator,
+        search_indicator,
+        truncated_indicator,
+        freshness_indicator(log_state.last_line_at, log_state.ended),
+        selection_indicator(&log_state.selection)
     );
 
     queue!(
-        io::stdout(),
+        out,
         SetBackgroundColor(t.tab_active_bg),
         SetForegroundColor(t.tab_active_fg),
         SetAttribute(Attribute::Bold)
     )?;
     // Pad header to full width
-    write!(out, "{:<width$}\r\n", header, width = width)?;
-    queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
+    write!(out, "{}\r\n", pad(&header, width))?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
 
     // Render truncated count in peach if present (already shown in header)
     // Search prompt line (if active)
     if log_state.search_mode {
-        queue!(io::stdout(), SetForegroundColor(t.teal))?;
+        queue!(out, SetForegroundColor(t.teal))?;
         write!(out, "  Search: {}_\r\n", log_state.search_query)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     } else {
         let sep: String = "─".repeat(width);
-        queue!(io::stdout(), SetForegroundColor(t.separator))?;
+        queue!(out, SetForegroundColor(t.separator))?;
         write!(out, "{}\r\n", sep)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     }
 
     // Log content area
     let log_area_height = height.saturating_sub(4);
     let has_search = !log_state.search_query.is_empty();
+    let selection = &log_state.selection;
     let mut lines_printed = 0;
     log_state.with_filtered_indices(|display_indices| -> io::Result<()> {
         let total_lines = display_indices.len();
-        let start_line = if log_state.auto_follow {
-            total_lines.saturating_sub(log_area_height)
-        } else {
-            let bottom_start = total_lines.saturating_sub(log_area_height);
-            bottom_start.saturating_sub(log_state.scroll_offset)
-        };
+        let start_line = visible_start(total_lines, log_area_height, log_state.auto_follow, log_state.scroll_offset);
         let end_line = (start_line + log_area_height).min(total_lines);
 
         for i in start_line..end_line {
-            if let Some(&line_idx) = display_indices.get(i) {
-                if let Some(line) = log_state.lines.get(line_idx) {
-                    let prefix = format!("{}: ", log_state.container_name);
-                    let full_line = format!("{}{}", prefix, line);
-                    let display_line = safe_truncate(&full_line, width);
-                    if has_search {
-                        queue!(io::stdout(), SetForegroundColor(t.yellow))?;
-                        write!(out, "{}\r\n", display_line)?;
-                        queue!(io::stdout(), ResetColor)?;
-                    } else {
-                        queue!(io::stdout(), SetForegroundColor(t.text))?;
-                        write!(out, "{}\r\n", display_line)?;
-                        queue!(io::stdout(), ResetColor)?;
-                    }
+            if let Some(&line_idx) = display_indices.get(i)
+                && let Some(line) = log_state.lines.get(line_idx) {
+                    let full_line = format!("{}: {}", log_state.container_name, line);
+                    let color = if has_search { t.yellow } else { t.text };
+                    let number = log_state.truncated_count + line_idx as u64;
+                    write_log_line(out, &full_line, number, selection, color, width)?;
                     lines_printed += 1;
                 }
-            }
         }
         Ok(())
     })?;
@@ -125,45 +206,57 @@ pub fn render_logs(log_state: &LogViewState) -> io::Result<()> {
     }
 
     // Footer
-    let help_y = (height.saturating_sub(1)) as u16;
+    let help_y = viewport.bottom();
     if log_state.search_mode {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("Type", "search"),
             ("Enter", "Confirm"),
             ("Esc", "Cancel"),
         ], width, help_y)?;
+    } else if log_state.selection.is_active() {
+        render_help_footer(out, SELECTION_FOOTER, width, help_y)?;
     } else if has_search {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("q/Esc/←", "Back"),
             ("↑↓", "Scroll"),
             ("f/End", "Follow"),
             ("/", "Search"),
             ("n", "Clear search"),
+            ("v", "Select"),
         ], width, help_y)?;
     } else {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("q/Esc/←", "Back"),
             ("↑↓", "Scroll"),
             ("f/End", "Follow"),
             ("/", "Search"),
+            ("v", "Select"),
         ], width, help_y)?;
     }
 
+    if log_state.selection.expanded {
+        let index = log_state.selection.line.map(|l| l.saturating_sub(log_state.truncated_count) as usize);
+        if let (Some(line), Some(text)) = (log_state.selection.line, index.and_then(|i| log_state.lines.get(i))) {
+            let title = format!("{} line {}", log_state.container_name, line + 1);
+            render_line_popup(out, &title, text, viewport)?;
+        }
+    }
+
     out.flush()?;
     Ok(())
 }
 
 pub fn render_multi_container_logs(
+    out: &mut impl Write,
     log_state: &MultiLogViewState,
     active_container_names: &[String],
+    viewport: Viewport,
 ) -> io::Result<()> {
     let t = theme();
-    let mut out = stdout();
-    execute!(out, Clear(ClearType::All), crossterm::cursor::MoveTo(0, 0))?;
+    viewport.clear(out)?;
 
-    let size = terminal::size()?;
-    let width = size.0 as usize;
-    let height = size.1 as usize;
+    let width = viewport.width as usize;
+    let height = viewport.height as usize;
 
     let container_count = active_container_names.len();
     let follow_indicator = if log_state.auto_follow {
@@ -182,68 +275,61 @@ pub fn render_multi_container_logs(
         String::new()
     };
     let header = format!(
-        "  Containers › Multi-Log: {} containers - {}{}{}",
-        container_count, follow_indicator, search_indicator, truncated_indicator
+        "  Containers › Multi-Log: {} containers - {}{}{}{}{}",
+        container_count,
+        follow_indicator,
+        search_indicator,
+        truncated_indicator,
+        freshness_indicator(log_state.last_line_at, false),
+        selection_indicator(&log_state.selection)
     );
 
     queue!(
-        io::stdout(),
+        out,
         SetBackgroundColor(t.tab_active_bg),
         SetForegroundColor(t.tab_active_fg),
         SetAttribute(Attribute::Bold)
     )?;
-    write!(out, "{:<width$}\r\n", header, width = width)?;
-    queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
+    write!(out, "{}\r\n", pad(&header, width))?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
 
     // On-screen indicator for active multi-container streams
     if !active_container_names.is_empty() {
         let names = active_container_names.join(", ");
-        queue!(io::stdout(), SetForegroundColor(t.teal))?;
-        writeln!(&mut out, "  Streaming: {}", names)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, SetForegroundColor(t.teal))?;
+        writeln!(out, "  Streaming: {}", names)?;
+        queue!(out, ResetColor)?;
     }
 
     if log_state.search_mode {
-        queue!(io::stdout(), SetForegroundColor(t.teal))?;
+        queue!(out, SetForegroundColor(t.teal))?;
         write!(out, "  Search: {}_\r\n", log_state.search_query)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     } else {
         let sep: String = "─".repeat(width);
-        queue!(io::stdout(), SetForegroundColor(t.separator))?;
+        queue!(out, SetForegroundColor(t.separator))?;
         write!(out, "{}\r\n", sep)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     }
 
     let log_area_height = height.saturating_sub(4);
     let has_search = !log_state.search_query.is_empty();
+    let selection = &log_state.selection;
     let mut lines_printed = 0;
     log_state.with_filtered_indices(|display_indices| -> io::Result<()> {
         let total_lines = display_indices.len();
-        let start_line = if log_state.auto_follow {
-            total_lines.saturating_sub(log_area_height)
-        } else {
-            let bottom_start = total_lines.saturating_sub(log_area_height);
-            bottom_start.saturating_sub(log_state.scroll_offset)
-        };
+        let start_line = visible_start(total_lines, log_area_height, log_state.auto_follow, log_state.scroll_offset);
         let end_line = (start_line + log_area_height).min(total_lines);
 
         for i in start_line..end_line {
-            if let Some(&line_idx) = display_indices.get(i) {
-                if let Some(entry) = log_state.lines.get(line_idx) {
+            if let Some(&line_idx) = display_indices.get(i)
+                && let Some(entry) = log_state.lines.get(line_idx) {
                     let full_line = format!("{}: {}", entry.container_name, entry.line);
-                    let display_line = safe_truncate(&full_line, width);
-                    if has_search {
-                        queue!(io::stdout(), SetForegroundColor(t.yellow))?;
-                        write!(out, "{}\r\n", display_line)?;
-                        queue!(io::stdout(), ResetColor)?;
-                    } else {
-                        queue!(io::stdout(), SetForegroundColor(t.text))?;
-                        write!(out, "{}\r\n", display_line)?;
-                        queue!(io::stdout(), ResetColor)?;
-                    }
+                    let color = if has_search { t.yellow } else { t.text };
+                    let number = log_state.truncated_count + line_idx as u64;
+                    write_log_line(out, &full_line, number, selection, color, width)?;
                     lines_printed += 1;
                 }
-            }
         }
         Ok(())
     })?;
@@ -252,42 +338,52 @@ pub fn render_multi_container_logs(
         write!(out, "\r\n")?;
     }
 
-    let help_y = (height.saturating_sub(1)) as u16;
+    let help_y = viewport.bottom();
     if log_state.search_mode {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("Type", "search"),
             ("Enter", "Confirm"),
             ("Esc", "Cancel"),
         ], width, help_y)?;
+    } else if log_state.selection.is_active() {
+        render_help_footer(out, SELECTION_FOOTER, width, help_y)?;
     } else if has_search {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("q/Esc/←", "Back"),
             ("↑↓", "Scroll"),
             ("f/End", "Follow"),
             ("/", "Search"),
             ("n", "Clear search"),
+            ("v", "Select"),
         ], width, help_y)?;
     } else {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("q/Esc/←", "Back"),
             ("↑↓", "Scroll"),
             ("f/End", "Follow"),
             ("/", "Search"),
+            ("v", "Select"),
         ], width, help_y)?;
     }
 
+    if log_state.selection.expanded {
+        let index = log_state.selection.line.map(|l| l.saturating_sub(log_state.truncated_count) as usize);
+        if let (Some(line), Some(entry)) = (log_state.selection.line, index.and_then(|i| log_state.lines.get(i))) {
+            let title = format!("{} line {}", entry.container_name, line + 1);
+            render_line_popup(out, &title, &entry.line, viewport)?;
+        }
+    }
+
     out.flush()?;
     Ok(())
 }
 
-pub fn render_service_logs(log_state: &ServiceLogState) -> io::Result<()> {
+pub fn render_service_logs(out: &mut impl Write, log_state: &ServiceLogState, viewport: Viewport) -> io::Result<()> {
     let t = theme();
-    let mut out = stdout();
-    execute!(out, Clear(ClearType::All), crossterm::cursor::MoveTo(0, 0))?;
+    viewport.clear(out)?;
 
-    let size = terminal::size()?;
-    let width = size.0 as usize;
-    let height = size.1 as usize;
+    let width = viewport.width as usize;
+    let height = viewport.height as usize;
 
     // Header bar with mauve background
     let follow_indicator = if log_state.auto_follow {
@@ -305,57 +401,60 @@ pub fn render_service_logs(log_state: &ServiceLogState) -> io::Result<()> {
     } else {
         String::new()
     };
+    let node_indicator = match log_state.node_filter {
+        Some(ref node) => format!(" | NODE: {}", node),
+        None => String::new(),
+    };
     let truncated_indicator = if log_state.truncated_count > 0 {
         format!(" | {} lines truncated", log_state.truncated_count)
     } else {
         String::new()
     };
     let header = format!(
-        "  Swarm › Service Logs: {} ({}) - {}{}{}{}",
+        "  Swarm › Service Logs: {} ({}) - {}{}{}{}{}{}{}",
         log_state.service_name,
         log_state.service_id,
         follow_indicator,
         filter_indicator,
         search_indicator,
-        truncated_indicator
+        node_indicator,
+        truncated_indicator,
+        freshness_indicator(log_state.last_line_at, log_state.ended),
+        selection_indicator(&log_state.selection)
     );
 
     queue!(
-        io::stdout(),
+        out,
         SetBackgroundColor(t.tab_active_bg),
         SetForegroundColor(t.tab_active_fg),
         SetAttribute(Attribute::Bold)
     )?;
-    write!(out, "{:<width$}\r\n", header, width = width)?;
-    queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
+    write!(out, "{}\r\n", pad(&header, width))?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
 
     if log_state.search_mode {
-        queue!(io::stdout(), SetForegroundColor(t.teal))?;
+        queue!(out, SetForegroundColor(t.teal))?;
         write!(out, "  Search: {}_\r\n", log_state.search_query)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     } else {
         let sep: String = "─".repeat(width);
-        queue!(io::stdout(), SetForegroundColor(t.separator))?;
+        queue!(out, SetForegroundColor(t.separator))?;
         write!(out, "{}\r\n", sep)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     }
 
     let has_search = !log_state.search_query.is_empty();
     let log_area_height = height.saturating_sub(4);
+    let selection = &log_state.selection;
     let mut lines_printed = 0;
     log_state.with_filtered_indices(|display_indices| -> io::Result<()> {
         let total_lines = display_indices.len();
-        let start_line = if log_state.auto_follow {
-            total_lines.saturating_sub(log_area_height)
-        } else {
-            let bottom_start = total_lines.saturating_sub(log_area_height);
-            bottom_start.saturating_sub(log_state.scroll_offset)
-        };
+        let start_line = visible_start(total_lines, log_area_height, log_state.auto_follow, log_state.scroll_offset);
         let end_line = (start_line + log_area_height).min(total_lines);
 
         for i in start_line..end_line {
-            if let Some(&line_idx) = display_indices.get(i) {
-                if let Some(line) = log_state.lines.get(line_idx) {
+            if let Some(&line_idx) = display_indices.get(i)
+                && let Some(line) = log_state.lines.get(line_idx) {
                     let lower = line.to_lowercase();
                     let is_error = lower.contains("error")
                         || lower.contains("panic")
@@ -363,20 +462,17 @@ pub fn render_service_logs(log_state: &ServiceLogState) -> io::Result<()> {
                         || lower.contains("exception");
                     let is_match = has_search && lower.contains(&log_state.search_query.to_lowercase());
 
-                    if is_error {
-                        queue!(io::stdout(), SetForegroundColor(t.red))?;
+                    let color = if is_error {
+                        t.red
                     } else if is_match {
-                        queue!(io::stdout(), SetForegroundColor(t.yellow))?;
+                        t.yellow
                     } else {
-                        queue!(io::stdout(), SetForegroundColor(t.text))?;
-                    }
-
-                    let display_line = safe_truncate(line, width);
-                    write!(out, "{}\r\n", display_line)?;
-                    queue!(io::stdout(), ResetColor)?;
+                        t.text
+                    };
+                    let number = log_state.truncated_count + line_idx as u64;
+                    write_log_line(out, line, number, selection, color, width)?;
                     lines_printed += 1;
                 }
-            }
         }
         Ok(())
     })?;
@@ -385,28 +481,191 @@ pub fn render_service_logs(log_state: &ServiceLogState) -> io::Result<()> {
         write!(out, "\r\n")?;
     }
 
-    let help_y = (height.saturating_sub(1)) as u16;
+    let help_y = viewport.bottom();
     if log_state.search_mode {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("Type", "search"),
             ("Enter", "Confirm"),
             ("Esc", "Cancel"),
         ], width, help_y)?;
+    } else if log_state.selection.is_active() {
+        render_help_footer(out, SELECTION_FOOTER, width, help_y)?;
     } else if has_search {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("q/Esc/←", "Back"),
             ("↑↓", "Scroll"),
             ("f/End", "Follow"),
             ("e", "Errors"),
+            ("o", "Node"),
             ("/", "Search"),
             ("n", "Clear search"),
+            ("v", "Select"),
         ], width, help_y)?;
     } else {
-        render_help_footer(&mut out, &[
+        render_help_footer(out, &[
             ("q/Esc/←", "Back"),
             ("↑↓", "Scroll"),
             ("f/End", "Follow"),
             ("e", "Toggle Errors"),
+            ("o", "Node"),
+            ("/", "Search"),
+            ("v", "Select"),
+        ], width, help_y)?;
+    }
+
+    if log_state.selection.expanded {
+        let index = log_state.selection.line.map(|l| l.saturating_sub(log_state.truncated_count) as usize);
+        if let (Some(line), Some(text)) = (log_state.selection.line, index.and_then(|i| log_state.lines.get(i))) {
+            let title = format!("{} line {}", log_state.service_name, line + 1);
+            render_line_popup(out, &title, text, viewport)?;
+        }
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// A split pane line with its timestamp shortened to the time of day, so
+/// both panes share one compact, comparable format.
+fn split_pane_text(line: &str) -> String {
+    match log_timestamp(line) {
+        Some(ts) => {
+            let rest = line.split_once(char::is_whitespace).map_or("", |(_, r)| r.trim_start());
+            format!("{} {}", ts.format("%H:%M:%S%.3f"), rest)
+        }
+        None => line.to_string(),
+    }
+}
+
+/// Render two log streams in side-by-side panes.
+pub fn render_split_logs(out: &mut impl Write, split: &SplitLogState, viewport: Viewport) -> io::Result<()> {
+    let t = theme();
+    viewport.clear(out)?;
+
+    let width = viewport.width as usize;
+    let height = viewport.height as usize;
+
+    let focused = &split.panes[split.focus];
+    let follow_indicator = if focused.auto_follow { "FOLLOWING" } else { "PAUSED (synced)" };
+    let header = format!(
+        "  Swarm › Split Logs: {} │ {} - {}",
+        split.sources[0].label(),
+        split.sources[1].label(),
+        follow_indicator
+    );
+    queue!(
+        out,
+        SetBackgroundColor(t.tab_active_bg),
+        SetForegroundColor(t.tab_active_fg),
+        SetAttribute(Attribute::Bold)
+    )?;
+    write!(out, "{}\r\n", pad(safe_truncate(&header, width), width))?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+
+    if focused.search_mode {
+        queue!(out, SetForegroundColor(t.teal))?;
+        write!(out, "  Search: {}_\r\n", focused.search_query)?;
+        queue!(out, ResetColor)?;
+    } else {
+        queue!(out, SetForegroundColor(t.separator))?;
+        write!(out, "{}\r\n", "─".repeat(width))?;
+        queue!(out, ResetColor)?;
+    }
+
+    let left_width = width.saturating_sub(1) / 2;
+    let widths = [left_width, width.saturating_sub(left_width + 1)];
+
+    // Pane titles, the focused one highlighted
+    for (i, pane) in split.panes.iter().enumerate() {
+        if i > 0 {
+            queue!(out, SetForegroundColor(t.separator))?;
+            write!(out, "│")?;
+        }
+        let mut title = format!(" {}", split.sources[i].label());
+        if pane.filter_errors {
+            title.push_str(" | ERRORS ONLY");
+        }
+        if !pane.search_query.is_empty() {
+            title.push_str(&format!(" | SEARCH: \"{}\"", pane.search_query));
+        }
+        title.push_str(&freshness_indicator(pane.last_line_at, pane.ended));
+        if i == split.focus {
+            queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+        } else {
+            queue!(out, SetForegroundColor(t.overlay))?;
+        }
+        write!(out, "{}", pad(safe_truncate(&title, widths[i]), widths[i]))?;
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    }
+    write!(out, "\r\n")?;
+
+    // Each pane's visible lines, then the rows interleaved
+    let log_area_height = height.saturating_sub(5);
+    let columns: Vec<Vec<(String, Color)>> = split
+        .panes
+        .iter()
+        .enumerate()
+        .map(|(i, pane)| {
+            let query = pane.search_query.to_lowercase();
+            pane.with_filtered_indices(|display| {
+                let start = visible_start(display.len(), log_area_height, pane.auto_follow, pane.scroll_offset);
+                let end = (start + log_area_height).min(display.len());
+                display[start..end]
+                    .iter()
+                    .filter_map(|&idx| pane.lines.get(idx))
+                    .map(|line| {
+                        let lower = line.to_lowercase();
+                        let color = if lower.contains("error")
+                            || lower.contains("panic")
+                            || lower.contains("fatal")
+                            || lower.contains("exception")
+                        {
+                            t.red
+                        } else if !query.is_empty() && lower.contains(&query) {
+                            t.yellow
+                        } else {
+                            t.text
+                        };
+                        let text = split_pane_text(line);
+                        (safe_truncate(&text, widths[i]).to_string(), color)
+                    })
+                    .collect()
+            })
+        })
+        .collect();
+
+    for row in 0..log_area_height {
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                queue!(out, SetForegroundColor(t.separator))?;
+                write!(out, "│")?;
+            }
+            match column.get(row) {
+                Some((text, color)) => {
+                    queue!(out, SetForegroundColor(*color))?;
+                    write!(out, "{}", pad(text, widths[i]))?;
+                }
+                None => write!(out, "{:<w$}", "", w = widths[i])?,
+            }
+            queue!(out, ResetColor)?;
+        }
+        write!(out, "\r\n")?;
+    }
+
+    let help_y = viewport.bottom();
+    if focused.search_mode {
+        render_help_footer(out, &[
+            ("Type", "search"),
+            ("Enter", "Confirm"),
+            ("Esc", "Cancel"),
+        ], width, help_y)?;
+    } else {
+        render_help_footer(out, &[
+            ("q/Esc/←", "Back"),
+            ("Tab", "Switch pane"),
+            ("↑↓", "Scroll (synced)"),
+            ("f/End", "Follow"),
+            ("e", "Errors"),
             ("/", "Search"),
         ], width, help_y)?;
     }
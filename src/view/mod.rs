@@ -1,14 +1,23 @@
+mod action_bar;
+mod banner;
 mod confirmation;
 mod containers;
+mod daemon;
+mod frame;
+mod graphs;
+mod k8s;
 mod logs;
+mod placeholder;
 mod shared;
 mod swarm;
 mod system;
-mod splash;
 mod tab_bar;
+mod toast;
+mod watchlist;
 pub mod theme;
 
 use crate::layout::SectionId;
+use crate::recording::Replay;
 use crossterm::{
     cursor, execute, queue,
     style::{Color, ResetColor, SetForegroundColor},
@@ -17,7 +26,9 @@ use crossterm::{
 use std::io::{self, Write};
 use sysinfo::Pid;
 
-pub use shared::{safe_truncate, truncate_str};
+pub use frame::Frame;
+pub use shared::{display_width, pad, safe_truncate, screen_size, truncate_str, Viewport};
+pub use system::graph_width;
 
 /// What kind of row this is in the row mapping
 #[derive(Clone, Copy, PartialEq)]
@@ -36,10 +47,9 @@ pub const MIN_ROWS: u16 = 10;
 impl Presenter {
     /// Check if the terminal is large enough. If not, render a "too small"
     /// message and return `true` (meaning "skip normal rendering").
-    pub fn render_size_guard() -> io::Result<bool> {
-        let (cols, rows) = terminal::size()?;
+    pub fn render_size_guard(out: &mut impl Write) -> io::Result<bool> {
+        let (cols, rows) = screen_size()?;
         if cols < MIN_COLS || rows < MIN_ROWS {
-            let mut out = std::io::stdout();
             execute!(
                 out,
                 terminal::Clear(terminal::ClearType::All),
@@ -60,6 +70,7 @@ impl Presenter {
         Ok(false)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn render_tab_bar(
         out: &mut impl std::io::Write,
         current_view: &crate::model::AppView,
@@ -67,7 +78,10 @@ impl Presenter {
         container_count: usize,
         swarm_active: bool,
         node_count: u32,
+        kube_pods: Option<usize>,
+        watch_count: usize,
         time: &str,
+        freshness: crate::model::Freshness,
     ) -> io::Result<()> {
         tab_bar::render_tab_bar(
             out,
@@ -76,78 +90,188 @@ impl Presenter {
             container_count,
             swarm_active,
             node_count,
+            kube_pods,
+            watch_count,
             time,
+            freshness,
         )
     }
 
     pub fn render(
+        out: &mut impl Write,
         data: &crate::model::MonitorData,
         ui_state: &mut crate::model::UIState,
         layout: &crate::layout::Layout,
     ) -> io::Result<Vec<(Pid, RowKind)>> {
-        system::render(data, ui_state, layout)
+        system::render(out, data, ui_state, layout)
     }
 
+    /// The summary and process list as plain text, without the terminal.
+    pub fn render_report(
+        data: &crate::model::MonitorData,
+        ui_state: &mut crate::model::UIState,
+        width: u16,
+    ) -> io::Result<String> {
+        system::render_report(data, ui_state, width)
+    }
+
+    /// Draw the summary over the top third of the screen, below the tab bar,
+    /// and return the rows left beneath it.
+    pub fn render_pinned_summary(
+        out: &mut impl Write,
+        data: &crate::model::MonitorData,
+        ui_state: &crate::model::UIState,
+    ) -> io::Result<Viewport> {
+        system::render_pinned_summary(out, data, ui_state)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn render_containers(
+        out: &mut impl Write,
         containers: &[crate::model::DockerContainerInfo],
-        ui_state: &crate::model::ContainerUIState,
+        groups: &[crate::model::ContainerGroupInfo],
+        rows: &[crate::model::ContainerRow],
+        ui_state: &mut crate::model::ContainerUIState,
         status_message: &Option<String>,
+        config: &crate::config::ContainersConfig,
+        health: &crate::health_controller::HealthMonitor,
+        history: &crate::model::ContainerStatsHistory,
+        viewport: Viewport,
     ) -> io::Result<()> {
-        containers::render_containers(containers, ui_state, status_message)
+        containers::render_containers(
+            out, containers, groups, rows, ui_state, status_message, config, health, history, viewport,
+        )
     }
 
-    pub fn render_logs(log_state: &crate::model::LogViewState) -> io::Result<()> {
-        logs::render_logs(log_state)
+    pub fn render_logs(out: &mut impl Write, log_state: &crate::model::LogViewState, viewport: Viewport) -> io::Result<()> {
+        logs::render_logs(out, log_state, viewport)
     }
 
     pub fn render_multi_container_logs(
+        out: &mut impl Write,
         log_state: &crate::model::MultiLogViewState,
         active_container_names: &[String],
+        viewport: Viewport,
     ) -> io::Result<()> {
-        logs::render_multi_container_logs(log_state, active_container_names)
+        logs::render_multi_container_logs(out, log_state, active_container_names, viewport)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn render_swarm_overview(
+        out: &mut impl Write,
         cluster_info: &Option<crate::model::SwarmClusterInfo>,
         nodes: &[crate::model::SwarmNodeInfo],
         stacks: &[crate::model::SwarmStackInfo],
         services: &[crate::model::SwarmServiceInfo],
         ui_state: &crate::model::SwarmUIState,
         warnings: &[String],
+        muted: &[(String, crate::mutes::MutedWarning)],
         status_message: &Option<String>,
         service_tasks: &std::collections::HashMap<String, Vec<crate::model::SwarmTaskInfo>>,
+        stack_drift: &std::collections::HashMap<String, (std::path::PathBuf, Vec<String>)>,
+        health: &crate::health_controller::HealthMonitor,
+        schedule: &crate::schedule::RestartSchedule,
     ) -> io::Result<()> {
         swarm::render_swarm_overview(
+            out,
             cluster_info,
             nodes,
             stacks,
             services,
             ui_state,
             warnings,
+            muted,
             status_message,
             service_tasks,
+            stack_drift,
+            health,
+            schedule,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn render_swarm_tasks(
+        out: &mut impl Write,
         service_name: &str,
         tasks: &[crate::model::SwarmTaskInfo],
         nodes: &[crate::model::SwarmNodeInfo],
+        spec: &crate::model::ServiceSpecInfo,
+        timeline: Option<&std::collections::HashMap<String, crate::model::TaskTimes>>,
         selected_index: usize,
         status_message: &Option<String>,
     ) -> io::Result<()> {
-        swarm::render_swarm_tasks(service_name, tasks, nodes, selected_index, status_message)
+        swarm::render_swarm_tasks(out, service_name, tasks, nodes, spec, timeline, selected_index, status_message)
+    }
+
+    pub fn render_service_logs(out: &mut impl Write, log_state: &crate::model::ServiceLogState, viewport: Viewport) -> io::Result<()> {
+        logs::render_service_logs(out, log_state, viewport)
+    }
+
+    pub fn render_split_logs(out: &mut impl Write, split: &crate::model::SplitLogState, viewport: Viewport) -> io::Result<()> {
+        logs::render_split_logs(out, split, viewport)
+    }
+
+    pub fn render_watchlist(
+        out: &mut impl Write,
+        watchlist: &crate::model::Watchlist,
+        containers: &[crate::model::DockerContainerInfo],
+        services: &[crate::model::SwarmServiceInfo],
+        health: &crate::health_controller::HealthMonitor,
+        status_message: &Option<String>,
+    ) -> io::Result<()> {
+        watchlist::render_watchlist(out, watchlist, containers, services, health, status_message)
+    }
+
+    pub fn render_kubernetes(out: &mut impl Write, kube: &crate::k8s_controller::KubeMonitor) -> io::Result<()> {
+        k8s::render_kubernetes(out, kube)
+    }
+
+    pub fn render_docker_health(
+        out: &mut impl Write,
+        health: Option<&crate::model::DaemonHealth>,
+        processes: &[crate::model::DaemonProcessUsage],
+        status_message: &Option<String>,
+    ) -> io::Result<()> {
+        daemon::render_docker_health(out, health, processes, status_message)
+    }
+
+    pub fn render_graphs(
+        out: &mut impl Write,
+        state: &crate::model::GraphViewState,
+        status_message: &Option<String>,
+    ) -> io::Result<()> {
+        graphs::render_graphs(out, state, status_message)
+    }
+
+    pub fn render_confirmation(out: &mut impl Write, prompt: &str) -> io::Result<()> {
+        confirmation::render_confirmation(out, prompt)
+    }
+
+    pub fn render_action_bar(out: &mut impl Write, name: &str, actions: &[&crate::model::Action]) -> io::Result<()> {
+        action_bar::render_action_bar(out, name, actions)
+    }
+
+    pub fn render_maintenance_banner(out: &mut impl Write, note: &crate::notes::MaintenanceNote) -> io::Result<()> {
+        banner::render_maintenance_banner(out, note)
+    }
+
+    pub fn render_note_prompt(out: &mut impl Write, input: &str) -> io::Result<()> {
+        banner::render_note_prompt(out, input)
+    }
+
+    pub fn render_palette(out: &mut impl Write, input: &str, names: &[&str], selected: usize) -> io::Result<()> {
+        banner::render_palette(out, input, names, selected)
     }
 
-    pub fn render_service_logs(log_state: &crate::model::ServiceLogState) -> io::Result<()> {
-        logs::render_service_logs(log_state)
+    pub fn render_replay_banner(out: &mut impl Write, replay: &Replay) -> io::Result<()> {
+        banner::render_replay_banner(out, replay)
     }
 
-    pub fn render_confirmation(prompt: &str) -> io::Result<()> {
-        confirmation::render_confirmation(prompt)
+    pub fn render_toast(out: &mut impl Write, message: &str) -> io::Result<()> {
+        toast::render_toast(out, message)
     }
 
-    pub fn render_splash() -> io::Result<()> {
-        splash::render_splash()
+    pub fn render_collecting(out: &mut impl Write, probing: bool) -> io::Result<()> {
+        placeholder::render_collecting(out, probing)
     }
 }
@@ -0,0 +1,23 @@
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{ResetColor, SetForegroundColor},
+};
+use std::io::{self, Write};
+
+use super::shared::writeln;
+use super::theme::theme;
+
+/// Stand-in for the System view until the first snapshot arrives, listing
+/// the collectors still starting up.
+pub fn render_collecting(out: &mut impl Write, probing: bool) -> io::Result<()> {
+    let t = theme();
+    queue!(out, MoveTo(0, 2), SetForegroundColor(t.subtext))?;
+    writeln(out, "  Collecting system metrics...")?;
+    if probing {
+        writeln(out, "  Detecting Docker and Swarm...")?;
+    }
+    queue!(out, ResetColor)?;
+    out.flush()?;
+    Ok(())
+}
@@ -1,37 +1,241 @@
 use crossterm::{
     cursor, queue,
     style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType},
 };
+use std::cell::Cell;
 use std::io::{self, Write};
 
 use super::theme::theme;
 
-/// Truncate a string to at most `max_len` characters (not bytes), appending "..."
-/// if truncated. Safe for multi-byte UTF-8.
+thread_local! {
+    /// Size of the `Frame` being drawn on this thread, in place of the terminal's.
+    static FRAME_SIZE: Cell<Option<(u16, u16)>> = const { Cell::new(None) };
+}
+
+/// (columns, rows) the views lay out for: the terminal, or a `Frame` while
+/// one is drawn into.
+pub fn screen_size() -> io::Result<(u16, u16)> {
+    match FRAME_SIZE.get() {
+        Some(size) => Ok(size),
+        None => terminal::size(),
+    }
+}
+
+/// Lay views out for `size` instead of the terminal until `f` returns.
+pub(super) fn with_screen_size<T>(size: (u16, u16), f: impl FnOnce() -> T) -> T {
+    let previous = FRAME_SIZE.replace(Some(size));
+    let result = f();
+    FRAME_SIZE.set(previous);
+    result
+}
+
+/// The rows a view draws in: the whole terminal, or what is left below the
+/// tab bar or the pinned summary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub top: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Viewport {
+    pub fn full() -> io::Result<Self> {
+        Self::below(0)
+    }
+
+    /// Everything from row `top` to the bottom of the terminal.
+    pub fn below(top: u16) -> io::Result<Self> {
+        let (width, height) = screen_size()?;
+        Ok(Self {
+            top: top.min(height),
+            width,
+            height: height.saturating_sub(top),
+        })
+    }
+
+    /// Last row, where the help footer goes.
+    pub fn bottom(&self) -> u16 {
+        (self.top + self.height).saturating_sub(1)
+    }
+
+    /// Blank the viewport and move to its first row.
+    pub fn clear(&self, out: &mut impl Write) -> io::Result<()> {
+        let clear = if self.top == 0 { ClearType::All } else { ClearType::FromCursorDown };
+        queue!(out, cursor::MoveTo(0, self.top), Clear(clear), cursor::MoveTo(0, self.top))?;
+        out.flush()
+    }
+}
+
+/// Code points terminals draw two columns wide: East Asian wide and
+/// fullwidth characters and emoji. The common ranges, not the full tables.
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115f),
+    (0x231a, 0x231b),
+    (0x2329, 0x232a),
+    (0x23e9, 0x23ec),
+    (0x23f0, 0x23f0),
+    (0x23f3, 0x23f3),
+    (0x25fd, 0x25fe),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267f, 0x267f),
+    (0x2693, 0x2693),
+    (0x26a1, 0x26a1),
+    (0x26aa, 0x26ab),
+    (0x26bd, 0x26be),
+    (0x26c4, 0x26c5),
+    (0x26ce, 0x26ce),
+    (0x26d4, 0x26d4),
+    (0x26ea, 0x26ea),
+    (0x26f2, 0x26f3),
+    (0x26f5, 0x26f5),
+    (0x26fa, 0x26fa),
+    (0x26fd, 0x26fd),
+    (0x2705, 0x2705),
+    (0x270a, 0x270b),
+    (0x2728, 0x2728),
+    (0x274c, 0x274c),
+    (0x274e, 0x274e),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27b0, 0x27b0),
+    (0x27bf, 0x27bf),
+    (0x2b1b, 0x2b1c),
+    (0x2b50, 0x2b50),
+    (0x2b55, 0x2b55),
+    (0x2e80, 0x303e),
+    (0x3041, 0x33ff),
+    (0x3400, 0x4dbf),
+    (0x4e00, 0x9fff),
+    (0xa000, 0xa4cf),
+    (0xa960, 0xa97f),
+    (0xac00, 0xd7a3),
+    (0xf900, 0xfaff),
+    (0xfe10, 0xfe19),
+    (0xfe30, 0xfe6f),
+    (0xff00, 0xff60),
+    (0xffe0, 0xffe6),
+    (0x1f004, 0x1f004),
+    (0x1f0cf, 0x1f0cf),
+    (0x1f18e, 0x1f18e),
+    (0x1f191, 0x1f19a),
+    (0x1f200, 0x1f251),
+    (0x1f300, 0x1f64f),
+    (0x1f680, 0x1f6ff),
+    (0x1f7e0, 0x1f7eb),
+    (0x1f900, 0x1f9ff),
+    (0x1fa70, 0x1faff),
+    (0x20000, 0x2fffd),
+    (0x30000, 0x3fffd),
+];
+
+/// Code points that take no column of their own: combining marks, joiners,
+/// direction marks and variation selectors.
+const ZERO: &[(u32, u32)] = &[
+    (0x0300, 0x036f),
+    (0x0483, 0x0489),
+    (0x0591, 0x05bd),
+    (0x0610, 0x061a),
+    (0x064b, 0x065f),
+    (0x1160, 0x11ff),
+    (0x1ab0, 0x1aff),
+    (0x1dc0, 0x1dff),
+    (0x200b, 0x200f),
+    (0x202a, 0x202e),
+    (0x2060, 0x2064),
+    (0x20d0, 0x20ff),
+    (0xfe00, 0xfe0f),
+    (0xfe20, 0xfe2f),
+    (0xfeff, 0xfeff),
+    (0xe0100, 0xe01ef),
+];
+
+fn in_ranges(ranges: &[(u32, u32)], cp: u32) -> bool {
+    ranges
+        .binary_search_by(|&(lo, hi)| {
+            if hi < cp {
+                std::cmp::Ordering::Less
+            } else if lo > cp {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Columns `c` takes on a terminal: 0, 1 or 2.
+pub fn char_width(c: char) -> usize {
+    let cp = c as u32;
+    if cp < 0x300 {
+        return usize::from(!c.is_control());
+    }
+    if in_ranges(ZERO, cp) {
+        0
+    } else if in_ranges(WIDE, cp) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Columns `s` takes on a terminal, which is what aligns a table; neither
+/// its bytes nor its chars do once a name has CJK characters or emoji.
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// `s` left-aligned in `width` columns, as `{:<width$}` would pad it if it
+/// counted display columns. Longer strings are left as they are.
+pub fn pad(s: &str, width: usize) -> String {
+    let fill = width.saturating_sub(display_width(s));
+    let mut padded = String::with_capacity(s.len() + fill);
+    padded.push_str(s);
+    padded.extend(std::iter::repeat_n(' ', fill));
+    padded
+}
+
+/// Truncate a string to at most `max_len` display columns, appending "..."
+/// if truncated. Safe for multi-byte UTF-8 and wide characters.
 pub fn truncate_str(s: &str, max_len: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_len {
+    if display_width(s) <= max_len {
         s.to_string()
     } else if max_len <= 3 {
-        s.chars().take(max_len).collect()
+        safe_truncate(s, max_len).to_string()
     } else {
-        let keep = max_len - 3;
-        let truncated: String = s.chars().take(keep).collect();
-        format!("{}...", truncated)
+        format!("{}...", safe_truncate(s, max_len - 3))
     }
 }
 
-/// Truncate a string to at most `max_len` characters for display. Returns a &str
-/// slice up to the last valid char boundary within `max_len` bytes.
+/// The longest prefix of `s` that fits in `max_len` display columns. A wide
+/// character that would straddle the edge is left out.
 pub fn safe_truncate(s: &str, max_len: usize) -> &str {
-    if s.len() <= max_len {
-        return s;
+    let mut used = 0;
+    for (i, c) in s.char_indices() {
+        used += char_width(c);
+        if used > max_len {
+            return &s[..i];
+        }
     }
-    let mut end = max_len;
-    while end > 0 && !s.is_char_boundary(end) {
-        end -= 1;
+    s
+}
+
+/// `s` cut into rows of at most `width` display columns.
+pub fn wrap(s: &str, width: usize) -> Vec<String> {
+    let mut rows = Vec::new();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let mut row = safe_truncate(rest, width);
+        if row.is_empty() {
+            // A character wider than the row still gets one to itself
+            row = &rest[..rest.chars().next().map_or(0, char::len_utf8)];
+        }
+        rows.push(row.to_string());
+        rest = &rest[row.len()..];
     }
-    &s[..end]
+    rows
 }
 
 pub fn writeln(out: &mut impl Write, text: &str) -> io::Result<()> {
@@ -43,19 +247,19 @@ pub fn write_section_header(out: &mut impl Write, text: &str, selected: bool) ->
     let t = theme();
     if selected {
         queue!(
-            io::stdout(),
+            out,
             SetBackgroundColor(t.selected_bg),
             SetForegroundColor(t.header_fg)
         )?;
     } else {
         queue!(
-            io::stdout(),
+            out,
             SetForegroundColor(t.header_fg),
             SetAttribute(Attribute::Bold)
         )?;
     }
     write!(out, "{}\r\n", text)?;
-    queue!(io::stdout(), ResetColor, SetAttribute(Attribute::Reset))?;
+    queue!(out, ResetColor, SetAttribute(Attribute::Reset))?;
     Ok(())
 }
 
@@ -63,14 +267,14 @@ pub fn write_selectable(out: &mut impl Write, text: &str, selected: bool) -> io:
     let t = theme();
     if selected {
         queue!(
-            io::stdout(),
+            out,
             SetBackgroundColor(t.selected_bg),
             SetForegroundColor(t.selected_fg)
         )?;
     }
     write!(out, "{}\r\n", text)?;
     if selected {
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     }
     Ok(())
 }
@@ -129,44 +333,44 @@ pub fn render_bar(
     let empty = bar_width.saturating_sub(filled);
 
     // Label (3 chars, right-aligned)
-    queue!(io::stdout(), SetForegroundColor(t.text))?;
+    queue!(out, SetForegroundColor(t.text))?;
     write!(out, " {:>3} ", label)?;
 
     // Opening bracket
-    queue!(io::stdout(), SetForegroundColor(t.subtext))?;
+    queue!(out, SetForegroundColor(t.subtext))?;
     write!(out, "[")?;
 
     // Filled portion: teal '|' chars
-    queue!(io::stdout(), SetForegroundColor(t.bar_filled))?;
+    queue!(out, SetForegroundColor(t.bar_filled))?;
     for _ in 0..filled {
         write!(out, "|")?;
     }
 
     // Empty portion: spaces with surface background
     queue!(
-        io::stdout(),
+        out,
         SetBackgroundColor(t.bar_empty)
     )?;
     for _ in 0..empty {
         write!(out, " ")?;
     }
-    queue!(io::stdout(), ResetColor)?;
+    queue!(out, ResetColor)?;
 
     // Closing bracket
-    queue!(io::stdout(), SetForegroundColor(t.subtext))?;
+    queue!(out, SetForegroundColor(t.subtext))?;
     write!(out, "]")?;
 
     // Percent
-    queue!(io::stdout(), SetForegroundColor(t.text))?;
+    queue!(out, SetForegroundColor(t.text))?;
     write!(out, " {:>5.1}%", clamped)?;
 
     // Detail
     if !detail.is_empty() {
-        queue!(io::stdout(), SetForegroundColor(t.subtext))?;
+        queue!(out, SetForegroundColor(t.subtext))?;
         write!(out, "  {}", detail)?;
     }
 
-    queue!(io::stdout(), ResetColor)?;
+    queue!(out, ResetColor)?;
     Ok(())
 }
 
@@ -180,7 +384,7 @@ pub fn render_help_footer(
     y: u16,
 ) -> io::Result<()> {
     let t = theme();
-    queue!(io::stdout(), cursor::MoveTo(1, y))?;
+    queue!(out, cursor::MoveTo(1, y))?;
 
     let mut col: usize = 1;
     for (i, (key, desc)) in items.iter().enumerate() {
@@ -194,7 +398,7 @@ pub fn render_help_footer(
             SetForegroundColor(t.help_key),
             Print(key.to_string()),
         )?;
-        col += key.chars().count();
+        col += display_width(key);
 
         // colon + description in subtext
         queue!(
@@ -202,7 +406,7 @@ pub fn render_help_footer(
             SetForegroundColor(t.help_desc),
             Print(format!(":{}", desc)),
         )?;
-        col += 1 + desc.chars().count();
+        col += 1 + display_width(desc);
     }
 
     // Clear remaining width
@@ -242,10 +446,67 @@ pub fn load_avg_color(val: f64, core_count: f64) -> Color {
     }
 }
 
+/// Render `values` as a filled braille area chart, `width` cells wide and
+/// `height` rows tall, scaled so `max` reaches the top. Each cell holds two
+/// samples side by side and four vertical levels; the newest sample is at
+/// the right edge. Returns one string per row, top row first.
+pub fn braille_graph(values: &[u64], width: usize, height: usize, max: u64) -> Vec<String> {
+    // Dot bits from the top of a braille cell down, for the left and right column.
+    const LEFT: [u32; 4] = [0x01, 0x02, 0x04, 0x40];
+    const RIGHT: [u32; 4] = [0x08, 0x10, 0x20, 0x80];
+
+    let levels = height * 4;
+    let start = values.len().saturating_sub(width * 2);
+    let visible = &values[start..];
+    // Left-pad so the newest sample lands in the last cell.
+    let pad = width * 2 - visible.len();
+    let level_at = |slot: usize| -> usize {
+        if slot < pad || max == 0 {
+            return 0;
+        }
+        let v = visible[slot - pad].min(max);
+        ((v as f64 / max as f64) * levels as f64).ceil() as usize
+    };
+
+    (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|cell| {
+                    let (left, right) = (level_at(cell * 2), level_at(cell * 2 + 1));
+                    let mut bits = 0;
+                    for dot in 0..4 {
+                        let level = (height - 1 - row) * 4 + (4 - dot);
+                        if left >= level {
+                            bits |= LEFT[dot];
+                        }
+                        if right >= level {
+                            bits |= RIGHT[dot];
+                        }
+                    }
+                    char::from_u32(0x2800 + bits).unwrap_or(' ')
+                })
+                .collect()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn braille_graph_fills_from_the_bottom() {
+        // Full-height sample on the right, nothing on the left.
+        let rows = braille_graph(&[0, 10], 1, 2, 10);
+        assert_eq!(rows, vec!["\u{28b8}", "\u{28b8}"]);
+        // Half-height sample fills only the bottom row.
+        let rows = braille_graph(&[5, 5], 1, 2, 10);
+        assert_eq!(rows, vec!["\u{2800}", "\u{28ff}"]);
+        // Short history is right-aligned.
+        let rows = braille_graph(&[10], 2, 1, 10);
+        assert_eq!(rows, vec!["\u{2800}\u{28b8}"]);
+    }
+
     #[test]
     fn truncate_str_short_string() {
         assert_eq!(truncate_str("hello", 10), "hello");
@@ -259,10 +520,22 @@ mod tests {
     #[test]
     fn truncate_str_utf8() {
         assert_eq!(truncate_str("caf\u{e9}", 4), "caf\u{e9}");
-        assert_eq!(truncate_str("\u{65e5}\u{672c}\u{8a9e}", 3), "\u{65e5}\u{672c}\u{8a9e}");
+        assert_eq!(truncate_str("\u{65e5}\u{672c}\u{8a9e}", 6), "\u{65e5}\u{672c}\u{8a9e}");
+        assert_eq!(truncate_str("\u{65e5}\u{672c}\u{8a9e}", 5), "\u{65e5}...");
         assert_eq!(truncate_str("hello\u{4e16}\u{754c}", 6), "hel...");
     }
 
+    #[test]
+    fn pads_by_display_width() {
+        // Two columns per CJK character and emoji, none for a combining accent
+        assert_eq!(display_width("web-\u{65e5}\u{672c}"), 8);
+        assert_eq!(display_width("deploy \u{1f680}"), 9);
+        assert_eq!(display_width("cafe\u{301}"), 4);
+        assert_eq!(pad("\u{65e5}\u{672c}", 6), "\u{65e5}\u{672c}  ");
+        assert_eq!(pad("toolong", 3), "toolong");
+        assert_eq!(safe_truncate("ab\u{65e5}c", 3), "ab");
+    }
+
     #[test]
     fn safe_truncate_short() {
         let s = "hello";
@@ -3,12 +3,17 @@ use crossterm::{
     style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
 };
 use std::collections::HashMap;
-use std::io::{self, stdout, Write};
+use std::io::{self, Write};
+use std::path::PathBuf;
 
-use super::shared::{truncate_str, write_selectable, writeln};
+use super::shared::{pad, truncate_str, write_selectable, writeln, screen_size};
 use super::theme::theme;
+use crate::health_controller::{HealthMonitor, HealthTargetKind};
+use crate::mutes::MutedWarning;
+use crate::schedule::{format_until, RestartSchedule};
 use crate::model::{
-    SwarmClusterInfo, SwarmNodeInfo, SwarmServiceInfo, SwarmStackInfo, SwarmTaskInfo, SwarmUIState,
+    task_timeline, ManagerQuorum, PlacementConstraint, ServiceSpecInfo, SwarmClusterInfo, SwarmNodeInfo,
+    SwarmServiceInfo, SwarmStackInfo, SwarmTaskInfo, SwarmUIState, TaskTimes, TimelineCell,
 };
 
 /// Check if a replica string like "2/3" indicates degraded state.
@@ -59,63 +64,105 @@ fn render_help_footer(out: &mut impl Write, items: &[(&str, &str)], width: u16,
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_swarm_overview(
+    out: &mut impl Write,
     cluster_info: &Option<SwarmClusterInfo>,
     nodes: &[SwarmNodeInfo],
     stacks: &[SwarmStackInfo],
     services: &[SwarmServiceInfo],
     ui_state: &SwarmUIState,
     warnings: &[String],
+    muted: &[(String, MutedWarning)],
     status_message: &Option<String>,
     service_tasks: &HashMap<String, Vec<SwarmTaskInfo>>,
+    stack_drift: &HashMap<String, (PathBuf, Vec<String>)>,
+    health: &HealthMonitor,
+    schedule: &RestartSchedule,
 ) -> io::Result<()> {
     let t = theme();
-    let mut out = stdout();
     queue!(out, cursor::MoveTo(0, 2))?;
 
-    let size = crossterm::terminal::size()?;
+    let size = screen_size()?;
 
     queue!(
-        io::stdout(),
+        out,
         SetForegroundColor(t.lavender),
         SetAttribute(Attribute::Bold)
     )?;
-    writeln(&mut out, "  Swarm Cluster")?;
-    queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
-    writeln(&mut out, "")?;
+    writeln(out, "  Swarm Cluster")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    writeln(out, "")?;
 
     if let Some(info) = cluster_info {
-        queue!(io::stdout(), SetForegroundColor(t.subtext))?;
+        queue!(out, SetForegroundColor(t.subtext))?;
         write!(out, "  Cluster: ")?;
-        queue!(io::stdout(), SetForegroundColor(t.teal))?;
+        queue!(out, SetForegroundColor(t.teal))?;
         write!(out, "{}", info.nodes_total)?;
-        queue!(io::stdout(), SetForegroundColor(t.subtext))?;
+        queue!(out, SetForegroundColor(t.subtext))?;
         write!(out, " nodes (")?;
-        queue!(io::stdout(), SetForegroundColor(t.teal))?;
+        queue!(out, SetForegroundColor(t.teal))?;
         write!(out, "{}", info.managers)?;
-        queue!(io::stdout(), SetForegroundColor(t.subtext))?;
-        write!(out, " managers) | This node: ")?;
-        queue!(io::stdout(), SetForegroundColor(t.teal))?;
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(out, " managers")?;
+        if let Some(quorum) = ManagerQuorum::from_nodes(nodes) {
+            let (color, label) = if !quorum.has_quorum() {
+                (t.red, "quorum lost")
+            } else if quorum.at_risk() {
+                (t.peach, "quorum at risk")
+            } else {
+                (t.green, "quorum ok")
+            };
+            write!(out, ", {}/{} reachable, ", quorum.reachable, quorum.total)?;
+            queue!(out, SetForegroundColor(color))?;
+            write!(out, "{}", label)?;
+            queue!(out, SetForegroundColor(t.subtext))?;
+        }
+        write!(out, ") | This node: ")?;
+        queue!(out, SetForegroundColor(t.teal))?;
         write!(
             out,
             "{}\r\n",
             if info.is_manager { "manager" } else { "worker" }
         )?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     }
-    writeln(&mut out, "")?;
-
-    if !warnings.is_empty() {
+    writeln(out, "")?;
+
+    if let Some(ref ack) = ui_state.warning_ack {
+        // Picking a warning: the muted ones are listed too, to unmute
+        let now = chrono::Local::now().timestamp();
+        let all = warnings.iter().map(|w| (w, None)).chain(muted.iter().map(|(w, m)| (w, Some(m))));
+        for (i, (w, mute)) in all.enumerate() {
+            let line = match mute {
+                Some(m) => format!("  🔇 {} (muted by {}, {} left)", w, m.by, format_until(m.until, now)),
+                None => format!("  ⚠ {}", w),
+            };
+            if i == ack.selected {
+                queue!(out, SetBackgroundColor(t.selected_bg), SetForegroundColor(t.selected_fg))?;
+            } else {
+                queue!(out, SetForegroundColor(if mute.is_some() { t.subtext } else { t.red }))?;
+            }
+            writeln(out, &truncate_str(&line, size.0 as usize))?;
+            queue!(out, ResetColor)?;
+        }
+        writeln(out, "")?;
+    } else if !warnings.is_empty() || !muted.is_empty() {
         for w in warnings {
             queue!(
-                io::stdout(),
+                out,
                 SetForegroundColor(t.red),
                 SetAttribute(Attribute::Bold)
             )?;
-            writeln(&mut out, &format!("  ⚠ {}", w))?;
-            queue!(io::stdout(), ResetColor, SetAttribute(Attribute::Reset))?;
+            writeln(out, &format!("  ⚠ {}", w))?;
+            queue!(out, ResetColor, SetAttribute(Attribute::Reset))?;
         }
-        writeln(&mut out, "")?;
+        if !muted.is_empty() {
+            queue!(out, SetForegroundColor(t.subtext))?;
+            writeln(out, &format!("  🔇 {} muted warning(s) — a to review", muted.len()))?;
+            queue!(out, ResetColor)?;
+        }
+        writeln(out, "")?;
     }
 
     let mut row_idx: usize = 0;
@@ -126,28 +173,28 @@ pub fn render_swarm_overview(
     let node_header = format!("  {} NODES ({})", node_indicator, nodes.len());
     // Render with themed selection
     if row_idx == ui_state.selected_index {
-        queue!(io::stdout(), SetBackgroundColor(t.selected_bg), SetForegroundColor(t.selected_fg))?;
+        queue!(out, SetBackgroundColor(t.selected_bg), SetForegroundColor(t.selected_fg))?;
         write!(out, "{}\r\n", node_header)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     } else {
-        queue!(io::stdout(), SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+        queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
         write!(out, "{}\r\n", node_header)?;
-        queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
     }
     row_idx += 1;
 
     if nodes_expanded {
         queue!(
-            io::stdout(),
+            out,
             SetForegroundColor(t.header_fg),
             SetAttribute(Attribute::Bold)
         )?;
         write!(
             out,
-            "    {:<14} {:<20} {:<16} {:<10} {:<12} {:<14} {}\r\n",
-            "ID", "HOSTNAME", "IP", "STATUS", "AVAIL", "ROLE", "ENGINE"
+            "    {:<14} {:<20} {:<16} {:<14} {:<10} {:<12} {:<14} {:<10} LABELS\r\n",
+            "ID", "HOSTNAME", "IP", "RTT/LOSS", "STATUS", "AVAIL", "ROLE", "ENGINE"
         )?;
-        queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
 
         for node in nodes {
             let role = if !node.manager_status.is_empty() {
@@ -161,70 +208,84 @@ pub fn render_swarm_overview(
             } else {
                 &node.ip_address
             };
+            let labels: Vec<String> = node.labels.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            let probe = match node.probe {
+                Some(ref probe) => probe.label(),
+                None if node.is_self => "local".to_string(),
+                None => "—".to_string(),
+            };
             let line = format!(
-                "    {:<14} {:<20} {:<16} {:<10} {:<12} {:<14} {}{}",
+                "    {:<14} {} {:<16} {:<14} {:<10} {:<12} {:<14} {:<10} {}",
                 truncate_str(&node.id, 12),
-                truncate_str(&node.hostname, 18),
+                pad(&truncate_str(&node.hostname, 18), 20),
                 truncate_str(ip_display, 15),
+                probe,
                 &node.status,
                 &node.availability,
                 role,
-                &node.engine_version,
-                self_marker,
+                format!("{}{}", node.engine_version, self_marker),
+                labels.join(", "),
             );
+            let line = truncate_str(&line, size.0 as usize);
 
             // Color based on node status
             let status_lower = node.status.to_lowercase();
             let avail_lower = node.availability.to_lowercase();
             if status_lower.contains("down") {
-                queue!(io::stdout(), SetForegroundColor(t.red))?;
+                queue!(out, SetForegroundColor(t.red))?;
             } else if avail_lower.contains("drain") {
-                queue!(io::stdout(), SetForegroundColor(t.yellow))?;
+                queue!(out, SetForegroundColor(t.yellow))?;
+            } else if node.probe.as_ref().is_some_and(|p| p.received < p.sent) {
+                queue!(out, SetForegroundColor(t.peach))?;
             } else if status_lower.contains("ready") {
-                queue!(io::stdout(), SetForegroundColor(t.green))?;
+                queue!(out, SetForegroundColor(t.green))?;
             }
 
             // Manager status coloring is embedded in the line; apply general row color
-            write_selectable(&mut out, &line, row_idx == ui_state.selected_index)?;
-            queue!(io::stdout(), ResetColor)?;
+            write_selectable(out, &line, row_idx == ui_state.selected_index)?;
+            queue!(out, ResetColor)?;
             row_idx += 1;
         }
     }
 
-    writeln(&mut out, "")?;
+    writeln(out, "")?;
 
     for stack in stacks {
         let stack_expanded = ui_state.expanded_ids.contains(&stack.name);
         let indicator = if stack_expanded { "▼" } else { "▶" };
         let svc_count = stack.service_indices.len();
-        let stack_header = format!(
+        let drift = stack_drift.get(&stack.name).map(|(_, lines)| lines.as_slice()).unwrap_or_default();
+        let mut stack_header = format!(
             "  {} STACK: {} ({} services)",
             indicator, stack.name, svc_count
         );
+        if !drift.is_empty() {
+            stack_header.push_str(&format!("  ≠ compose file ({})", drift.len()));
+        }
 
         if row_idx == ui_state.selected_index {
-            queue!(io::stdout(), SetBackgroundColor(t.selected_bg), SetForegroundColor(t.selected_fg))?;
+            queue!(out, SetBackgroundColor(t.selected_bg), SetForegroundColor(t.selected_fg))?;
             write!(out, "{}\r\n", stack_header)?;
-            queue!(io::stdout(), ResetColor)?;
+            queue!(out, ResetColor)?;
         } else {
-            queue!(io::stdout(), SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+            queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
             write!(out, "{}\r\n", stack_header)?;
-            queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
+            queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
         }
         row_idx += 1;
 
         if stack_expanded {
             queue!(
-                io::stdout(),
+                out,
                 SetForegroundColor(t.header_fg),
                 SetAttribute(Attribute::Bold)
             )?;
             write!(
                 out,
-                "    {:<14} {:<28} {:<12} {:<10} {:<20} {}\r\n",
-                "ID", "NAME", "MODE", "REPLICAS", "IMAGE", "PORTS"
+                "    {:<14} {:<28} {:<12} {:<10} {:<20} PORTS\r\n",
+                "ID", "NAME", "MODE", "REPLICAS", "IMAGE"
             )?;
-            queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
+            queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
 
             // Build hostname -> IP lookup from nodes
             let node_ip_map: HashMap<&str, &str> = nodes
@@ -235,24 +296,35 @@ pub fn render_swarm_overview(
 
             for &idx in &stack.service_indices {
                 let svc = &services[idx];
-                let line = format!(
-                    "    {:<14} {:<28} {:<12} {:<10} {:<20} {}",
+                let mut line = format!(
+                    "    {:<14} {} {:<12} {:<10} {} {}",
                     truncate_str(&svc.id, 12),
-                    truncate_str(&svc.name, 26),
+                    pad(&truncate_str(&svc.name, 26), 28),
                     &svc.mode,
                     &svc.replicas,
-                    truncate_str(&svc.image, 18),
+                    pad(&truncate_str(&svc.image, 18), 20),
                     truncate_str(&svc.ports, 20),
                 );
+                let health_result = health.get(HealthTargetKind::Service, &svc.name);
+                if let Some(result) = health_result {
+                    line.push_str(&format!("  HEALTH {}", result.summary()));
+                }
+                let drifted = drift.iter().any(|l| l.strip_prefix(svc.name.as_str()).is_some_and(|r| r.starts_with(':')));
+                if drifted {
+                    line.push_str("  ≠ file");
+                }
+                if let Some(time) = schedule.for_service(&svc.id).and_then(|r| r.local_time()) {
+                    line.push_str(&format!("  ⏱ restart {}", time.format("%H:%M")));
+                }
 
-                if is_replica_failed(&svc.replicas) {
-                    queue!(io::stdout(), SetForegroundColor(t.red))?;
-                } else if is_replica_degraded(&svc.replicas) {
-                    queue!(io::stdout(), SetForegroundColor(t.peach))?;
+                if health_result.is_some_and(|r| !r.is_healthy()) || is_replica_failed(&svc.replicas) {
+                    queue!(out, SetForegroundColor(t.red))?;
+                } else if is_replica_degraded(&svc.replicas) || drifted {
+                    queue!(out, SetForegroundColor(t.peach))?;
                 }
 
-                write_selectable(&mut out, &line, row_idx == ui_state.selected_index)?;
-                queue!(io::stdout(), ResetColor)?;
+                write_selectable(out, &line, row_idx == ui_state.selected_index)?;
+                queue!(out, ResetColor)?;
                 row_idx += 1;
 
                 // Render inline replica sub-rows (non-selectable)
@@ -267,9 +339,9 @@ pub fn render_swarm_overview(
                         let node_ip = node_ip_map.get(task.node.as_str()).copied().unwrap_or("—");
 
                         let sub_line = format!(
-                            "                       └ {:<20} {:<18} {:<16} {}",
-                            short_name,
-                            truncate_str(&task.node, 16),
+                            "                       └ {} {} {:<16} {}",
+                            pad(short_name, 20),
+                            pad(&truncate_str(&task.node, 16), 18),
                             truncate_str(node_ip, 14),
                             truncate_str(&task.current_state, 24),
                         );
@@ -277,16 +349,16 @@ pub fn render_swarm_overview(
                         // Color task sub-rows by state
                         let state_lower = task.current_state.to_lowercase();
                         if state_lower.contains("running") {
-                            queue!(io::stdout(), SetForegroundColor(t.green))?;
+                            queue!(out, SetForegroundColor(t.green))?;
                         } else if state_lower.contains("failed") || state_lower.contains("rejected") {
-                            queue!(io::stdout(), SetForegroundColor(t.red))?;
+                            queue!(out, SetForegroundColor(t.red))?;
                         } else if state_lower.contains("shutdown") || state_lower.contains("complete") {
-                            queue!(io::stdout(), SetForegroundColor(t.subtext))?;
+                            queue!(out, SetForegroundColor(t.subtext))?;
                         } else {
-                            queue!(io::stdout(), SetForegroundColor(t.subtext))?;
+                            queue!(out, SetForegroundColor(t.subtext))?;
                         }
-                        writeln(&mut out, &sub_line)?;
-                        queue!(io::stdout(), ResetColor)?;
+                        writeln(out, &sub_line)?;
+                        queue!(out, ResetColor)?;
                     }
                 }
             }
@@ -294,20 +366,82 @@ pub fn render_swarm_overview(
     }
 
     if let Some(msg) = status_message {
-        writeln(&mut out, "")?;
-        queue!(io::stdout(), SetForegroundColor(t.yellow))?;
-        writeln(&mut out, &format!("  {}", msg))?;
-        queue!(io::stdout(), ResetColor)?;
+        writeln(out, "")?;
+        queue!(out, SetForegroundColor(t.yellow))?;
+        writeln(out, &format!("  {}", msg))?;
+        queue!(out, ResetColor)?;
     }
 
     let help_y = size.1.saturating_sub(1);
-    render_help_footer(&mut out, &[
+    if ui_state.search_mode {
+        queue!(out, cursor::MoveTo(0, help_y.saturating_sub(1)), SetForegroundColor(t.teal))?;
+        write!(out, "  Search: {}_", ui_state.search_query)?;
+        queue!(out, ResetColor)?;
+        render_help_footer(out, &[
+            ("Type", "hostname, service, image or task error"),
+            ("Enter", "Done"),
+            ("Esc", "Cancel"),
+        ], size.0, help_y)?;
+        out.flush()?;
+        return Ok(());
+    }
+    if let Some(ref ack) = ui_state.warning_ack {
+        queue!(out, cursor::MoveTo(0, help_y.saturating_sub(1)), SetForegroundColor(t.teal))?;
+        if ack.selected < warnings.len() {
+            write!(out, "  Mute for: {}_", ack.duration)?;
+        } else {
+            write!(out, "  Enter unmutes the selected warning")?;
+        }
+        queue!(out, ResetColor)?;
+        render_help_footer(out, &[
+            ("↑↓", "Warning"),
+            ("Type", "30m / 4h / 2d"),
+            ("Enter", "Mute / unmute"),
+            ("^", "Incident"),
+            ("Esc", "Cancel"),
+        ], size.0, help_y)?;
+        out.flush()?;
+        return Ok(());
+    }
+    if let Some(ref input) = ui_state.schedule_input {
+        queue!(out, cursor::MoveTo(0, help_y.saturating_sub(1)), SetForegroundColor(t.teal))?;
+        write!(out, "  Rolling restart at: {}_", input)?;
+        queue!(out, ResetColor)?;
+        render_help_footer(out, &[
+            ("Type", "HH:MM or +30m / +2h; empty cancels"),
+            ("Enter", "Schedule"),
+            ("Esc", "Back"),
+        ], size.0, help_y)?;
+        out.flush()?;
+        return Ok(());
+    }
+    if let Some(ref input) = ui_state.label_input {
+        queue!(out, cursor::MoveTo(0, help_y.saturating_sub(1)), SetForegroundColor(t.teal))?;
+        write!(out, "  Service label: {}_", input)?;
+        queue!(out, ResetColor)?;
+        render_help_footer(out, &[
+            ("Type", "key=value to set, -key to remove"),
+            ("Enter", "Apply"),
+            ("Esc", "Cancel"),
+        ], size.0, help_y)?;
+        out.flush()?;
+        return Ok(());
+    }
+    render_help_footer(out, &[
         ("q", "Quit"),
         ("↑↓", "Select"),
         ("Enter", "Expand"),
+        ("/", "Search"),
         ("L", "Logs"),
+        ("V", "Split logs"),
+        ("w", "Watch"),
         ("S", "Scale"),
         ("R", "Restart"),
+        ("E", "Label"),
+        ("@", "Schedule restart"),
+        ("a", "Mute warning"),
+        ("x/X", "Export"),
+        ("G", "Graphs"),
         ("Tab", "Next"),
     ], size.0, help_y)?;
 
@@ -315,18 +449,177 @@ pub fn render_swarm_overview(
     Ok(())
 }
 
+/// Placement constraints of a service checked against every node, so "no
+/// suitable node" says which constraint rules which nodes out.
+fn render_placement(out: &mut impl Write, constraints: &[PlacementConstraint], nodes: &[SwarmNodeInfo]) -> io::Result<()> {
+    let t = theme();
+    writeln(out, "")?;
+    queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+    writeln(out, "  Placement")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+
+    for constraint in constraints {
+        let results: Vec<Option<bool>> = nodes.iter().map(|n| constraint.matches(n)).collect();
+        let matched: Vec<&str> = nodes
+            .iter()
+            .zip(&results)
+            .filter(|(_, m)| **m == Some(true))
+            .map(|(n, _)| n.hostname.as_str())
+            .collect();
+        let (color, verdict) = if results.contains(&None) {
+            (t.subtext, "not checked by sitrep".to_string())
+        } else if matched.is_empty() {
+            (t.red, "no node matches".to_string())
+        } else {
+            (t.green, format!("{} of {}: {}", matched.len(), nodes.len(), matched.join(", ")))
+        };
+        queue!(out, SetForegroundColor(color))?;
+        writeln(out, &format!("    {} {}", pad(&truncate_str(&constraint.to_string(), 39), 40), verdict))?;
+        queue!(out, ResetColor)?;
+    }
+
+    let eligible: Vec<&SwarmNodeInfo> = nodes
+        .iter()
+        .filter(|n| constraints.iter().all(|c| c.matches(n) != Some(false)))
+        .collect();
+    let usable: Vec<&str> = eligible
+        .iter()
+        .filter(|n| n.status.eq_ignore_ascii_case("ready") && n.availability.eq_ignore_ascii_case("active"))
+        .map(|n| n.hostname.as_str())
+        .collect();
+    let (color, summary) = if eligible.is_empty() {
+        (t.red, "No node satisfies every constraint: tasks stay pending with \"no suitable node\"".to_string())
+    } else if usable.is_empty() {
+        let names: Vec<&str> = eligible.iter().map(|n| n.hostname.as_str()).collect();
+        (t.red, format!("Matching nodes are all down, paused or drained: {}", names.join(", ")))
+    } else {
+        (t.green, format!("Tasks can run on: {}", usable.join(", ")))
+    };
+    queue!(out, SetForegroundColor(color))?;
+    writeln(out, &format!("    {}", summary))?;
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// Span of the task timeline.
+const TIMELINE_WINDOW_SECS: i64 = 3600;
+
+/// Task starts and stops over the last hour, one row per slot, so restart
+/// storms and rolling-update waves stand out.
+fn render_timeline(
+    out: &mut impl Write,
+    tasks: &[SwarmTaskInfo],
+    times: &HashMap<String, TaskTimes>,
+    width: usize,
+) -> io::Result<()> {
+    let t = theme();
+    let cols = width.saturating_sub(22).clamp(10, 120);
+    let rows = task_timeline(tasks, times, chrono::Utc::now().timestamp(), TIMELINE_WINDOW_SECS, cols);
+
+    writeln(out, "")?;
+    queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+    writeln(out, "  Timeline (last hour)")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    if rows.is_empty() {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, "    No task activity in the last hour.")?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    }
+
+    let half = cols / 2;
+    queue!(out, SetForegroundColor(t.subtext))?;
+    writeln(out, &format!("    {:<16} {:<half$}{:<rest$}now", "", "-60m", "-30m", rest = cols - half - 3))?;
+    queue!(out, ResetColor)?;
+
+    let (mut starts, mut failures) = (0, 0);
+    for (name, cells) in &rows {
+        write!(out, "    {} ", pad(&truncate_str(name, 15), 16))?;
+        for cell in cells {
+            let (color, ch) = match cell {
+                TimelineCell::Idle => (t.subtext, '·'),
+                TimelineCell::Running => (t.green, '━'),
+                TimelineCell::Stopped => (t.subtext, '▼'),
+                TimelineCell::Started => (t.teal, '▲'),
+                TimelineCell::Failed => (t.red, '✖'),
+            };
+            queue!(out, SetForegroundColor(color))?;
+            write!(out, "{}", ch)?;
+        }
+        queue!(out, ResetColor)?;
+        write!(out, "\r\n")?;
+        starts += cells.iter().filter(|c| **c == TimelineCell::Started).count();
+        failures += cells.iter().filter(|c| **c == TimelineCell::Failed).count();
+    }
+    queue!(out, SetForegroundColor(if failures > 0 { t.red } else { t.subtext }))?;
+    writeln(out, &format!("    ▲ start  ▼ stop  ✖ failed   {} starts, {} failures", starts, failures))?;
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// Most spec changes listed before the rest are summarized.
+const MAX_SPEC_CHANGES: usize = 12;
+
+/// What the last `docker service update` changed, from `PreviousSpec` to `Spec`.
+fn render_spec_changes(out: &mut impl Write, spec: &ServiceSpecInfo, width: usize) -> io::Result<()> {
+    let t = theme();
+    writeln(out, "")?;
+    queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+    writeln(out, "  Last update")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+
+    if let Some(ref status) = spec.update_status {
+        let color = if status.contains("rollback") || status.contains("paused") { t.red } else { t.subtext };
+        queue!(out, SetForegroundColor(color))?;
+        writeln(out, &format!("    {}", status))?;
+        queue!(out, ResetColor)?;
+    }
+
+    let Some(ref changes) = spec.changes else {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, "    Never updated since it was created.")?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    };
+    if changes.is_empty() {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, "    Spec unchanged.")?;
+        queue!(out, ResetColor)?;
+    }
+    for change in changes.iter().take(MAX_SPEC_CHANGES) {
+        let (color, line) = match (&change.before, &change.after) {
+            (Some(before), Some(after)) => (t.yellow, format!("~ {}: {} → {}", change.path, before, after)),
+            (None, Some(after)) => (t.green, format!("+ {}: {}", change.path, after)),
+            (Some(before), None) => (t.red, format!("- {}: {}", change.path, before)),
+            (None, None) => continue,
+        };
+        queue!(out, SetForegroundColor(color))?;
+        writeln(out, &truncate_str(&format!("    {}", line), width))?;
+        queue!(out, ResetColor)?;
+    }
+    if changes.len() > MAX_SPEC_CHANGES {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, &format!("    … {} more", changes.len() - MAX_SPEC_CHANGES))?;
+        queue!(out, ResetColor)?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_swarm_tasks(
+    out: &mut impl Write,
     service_name: &str,
     tasks: &[SwarmTaskInfo],
     nodes: &[SwarmNodeInfo],
+    spec: &ServiceSpecInfo,
+    timeline: Option<&HashMap<String, TaskTimes>>,
     selected_index: usize,
     status_message: &Option<String>,
 ) -> io::Result<()> {
     let t = theme();
-    let mut out = stdout();
     queue!(out, cursor::MoveTo(0, 2))?;
 
-    let size = crossterm::terminal::size()?;
+    let size = screen_size()?;
 
     // Build hostname -> IP lookup
     let node_ip_map: HashMap<&str, &str> = nodes
@@ -336,38 +629,38 @@ pub fn render_swarm_tasks(
         .collect();
 
     queue!(
-        io::stdout(),
+        out,
         SetForegroundColor(t.lavender),
         SetAttribute(Attribute::Bold)
     )?;
-    writeln(&mut out, &format!("  Swarm › Tasks: {}", service_name))?;
-    queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
-    writeln(&mut out, "")?;
+    writeln(out, &format!("  Swarm › Tasks: {}", service_name))?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    writeln(out, "")?;
 
     if tasks.is_empty() {
-        queue!(io::stdout(), SetForegroundColor(t.subtext))?;
-        writeln(&mut out, "  No tasks found for this service.")?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, "  No tasks found for this service.")?;
+        queue!(out, ResetColor)?;
     } else {
         queue!(
-            io::stdout(),
+            out,
             SetForegroundColor(t.header_fg),
             SetAttribute(Attribute::Bold)
         )?;
         write!(
             out,
-            "  {:<14} {:<28} {:<18} {:<16} {:<12} {:<24} {}\r\n",
-            "ID", "NAME", "NODE", "IP", "DESIRED", "CURRENT STATE", "ERROR"
+            "  {:<14} {:<28} {:<18} {:<16} {:<12} {:<24} ERROR\r\n",
+            "ID", "NAME", "NODE", "IP", "DESIRED", "CURRENT STATE"
         )?;
-        queue!(io::stdout(), SetAttribute(Attribute::Reset), ResetColor)?;
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
 
         for (idx, task) in tasks.iter().enumerate() {
             let node_ip = node_ip_map.get(task.node.as_str()).copied().unwrap_or("—");
             let line = format!(
-                "  {:<14} {:<28} {:<18} {:<16} {:<12} {:<24} {}",
+                "  {:<14} {} {} {:<16} {:<12} {:<24} {}",
                 truncate_str(&task.id, 12),
-                truncate_str(&task.name, 26),
-                truncate_str(&task.node, 16),
+                pad(&truncate_str(&task.name, 26), 28),
+                pad(&truncate_str(&task.node, 16), 18),
                 truncate_str(node_ip, 14),
                 &task.desired_state,
                 truncate_str(&task.current_state, 22),
@@ -376,31 +669,40 @@ pub fn render_swarm_tasks(
 
             let state_lower = task.current_state.to_lowercase();
             if state_lower.contains("failed") || state_lower.contains("rejected") {
-                queue!(io::stdout(), SetForegroundColor(t.red))?;
+                queue!(out, SetForegroundColor(t.red))?;
             } else if state_lower.contains("shutdown") || state_lower.contains("complete") {
-                queue!(io::stdout(), SetForegroundColor(t.subtext))?;
+                queue!(out, SetForegroundColor(t.subtext))?;
             } else if state_lower.contains("running") {
-                queue!(io::stdout(), SetForegroundColor(t.green))?;
+                queue!(out, SetForegroundColor(t.green))?;
             }
 
-            write_selectable(&mut out, &line, idx == selected_index)?;
-            queue!(io::stdout(), ResetColor)?;
+            write_selectable(out, &line, idx == selected_index)?;
+            queue!(out, ResetColor)?;
         }
     }
 
+    if let Some(times) = timeline {
+        render_timeline(out, tasks, times, size.0 as usize)?;
+    }
+    if !spec.constraints.is_empty() {
+        render_placement(out, &spec.constraints, nodes)?;
+    }
+    render_spec_changes(out, spec, size.0 as usize)?;
+
     if let Some(msg) = status_message {
-        writeln(&mut out, "")?;
-        queue!(io::stdout(), SetForegroundColor(t.yellow))?;
-        writeln(&mut out, &format!("  {}", msg))?;
-        queue!(io::stdout(), ResetColor)?;
+        writeln(out, "")?;
+        queue!(out, SetForegroundColor(t.yellow))?;
+        writeln(out, &format!("  {}", msg))?;
+        queue!(out, ResetColor)?;
     }
 
     let help_y = size.1.saturating_sub(1);
-    render_help_footer(&mut out, &[
+    render_help_footer(out, &[
         ("q/Esc/←", "Back"),
         ("↑↓", "Navigate"),
         ("→/L", "Service Logs"),
         ("R", "Rolling Restart"),
+        ("T", "Timeline"),
     ], size.0, help_y)?;
 
     out.flush()?;
@@ -1,240 +1,1485 @@
 use crossterm::{
     cursor::MoveTo,
     queue,
-    style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    style::{Attribute, Color, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    terminal::{Clear, ClearType},
 };
-use std::io::{self, stdout, Write};
+use std::io::{self, Write};
 use sysinfo::Pid;
 
 use super::shared::{
-    format_bytes_rate, format_mem_human, load_avg_color, render_bar, render_help_footer,
-};
+    braille_graph, display_width, format_bytes_rate, format_mem_human, load_avg_color, pad, render_bar,
+    render_help_footer, safe_truncate, truncate_str, Viewport, screen_size};
 use super::theme::theme;
-use super::RowKind;
+use super::{Frame, RowKind};
 use crate::layout::Layout;
-use crate::model::{MonitorData, SortColumn, UIState};
+use crate::model::{
+    assess_saturation, format_link_speed, AnomalyInfo, SaturationLevel, ConntrackInfo, CpuBreakdown, InterfaceHistory, LinkInfo, LvmVolumeInfo, MemoryDetail, MonitorData, CpuScale, ProcessGrouping, SocketOverviewInfo,
+    HistorySeries, MetricUnit, AuthSummary, ExecEvents, ExecSummary, InterruptInfo, InterruptRate, ProcessLimits, ProcessPriority, ProfileState, ProfileView, CheckState, CustomLineOutput, SummaryLine, SelfGuard, SelfUsage, SortColumn, SystemPanel, TraceState, TraceView, UpdateStatus, UIState, HEAVY_REMOTE_CONNECTIONS,
+};
 
 pub fn render(
+    out: &mut impl Write,
     data: &MonitorData,
     ui_state: &mut UIState,
     _layout: &Layout,
 ) -> io::Result<Vec<(Pid, RowKind)>> {
-    let mut out = stdout();
     let mut rows: Vec<(Pid, RowKind)> = Vec::new();
-    let mut current_row: usize = 0;
     let t = theme();
-    let size = crossterm::terminal::size()?;
+    let size = screen_size()?;
     let term_width = size.0 as usize;
 
-    // ── Top panel: CPU / Mem / Swap bars with right-side stats ──
+    render_summary(out, data, ui_state, term_width)?;
 
-    let bar_width: usize = 40;
+    // ── Separator line ──
+    queue!(out, SetForegroundColor(t.separator))?;
+    let sep: String = "\u{2500}".repeat(term_width);
+    write!(out, "{}\r\n", sep)?;
+    queue!(out, ResetColor)?;
 
-    // CPU bar
-    let cpu_total: f64 = data
-        .historical_top
-        .iter()
-        .map(|p| p.cpu)
-        .sum::<f64>();
-    let cpu_pct = (cpu_total / (data.core_count * 100.0) * 100.0).min(100.0);
-    render_bar(&mut out, "CPU", cpu_pct, "", bar_width)?;
-
-    // Right side: Tasks + Load average (on the same line as CPU bar)
-    let task_count = data.historical_top.len();
-    let right_col = bar_width + 3 + 5 + 8; // label(5) + bar + bracket(2) + percent(7) + spacing
-    let right_start = right_col + 2;
-    if right_start < term_width {
-        queue!(out, SetForegroundColor(t.subtext))?;
-        write!(out, "    Tasks: ", )?;
-        queue!(out, SetForegroundColor(t.text))?;
-        write!(out, "{:<6}", task_count)?;
+    // Tab bar (2), summary, separator
+    let used_rows = 2 + summary_rows(data, ui_state, term_width) + 1;
+    let sock = &data.socket_overview;
+    // Rows left for the panel above the note line and help footer
+    let capacity = (size.1 as usize).saturating_sub(used_rows + 2);
+    match &ui_state.panel {
+        SystemPanel::Processes => {
+            render_process_table(out, data, ui_state, term_width, &mut rows)?;
+        }
+        SystemPanel::Connections => {
+            render_remote_connections(out, sock, term_width, capacity.saturating_sub(2))?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Bandwidth(name) => {
+            let history = data.network.history.iter().find(|h| &h.name == name);
+            render_bandwidth_graph(out, name, history, term_width, capacity)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::History => {
+            render_history_graphs(out, &ui_state.history, term_width, capacity)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Profile => {
+            render_profile(out, ui_state.profile.as_ref(), term_width, capacity)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Trace => {
+            render_trace(out, ui_state.trace.as_ref(), term_width, capacity)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Security => {
+            render_security(out, &ui_state.auth, capacity)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Memory => {
+            render_memory(out, data.memory_detail.as_ref(), term_width, capacity)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Diagnostics => {
+            render_diagnostics(out, data.self_usage.as_ref(), &ui_state.guard, term_width)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Execs => {
+            render_execs(out, &ui_state.execs, term_width, capacity)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Interrupts => {
+            render_interrupts(out, data.interrupts.as_ref(), term_width, capacity)?;
+            ui_state.total_rows = 0;
+        }
+        SystemPanel::Users => {
+            render_users(out, data, ui_state.cpu_scale, capacity)?;
+            ui_state.total_rows = 0;
+        }
+    }
 
-        queue!(out, SetForegroundColor(t.subtext))?;
-        write!(out, "Load average: ")?;
-
-        let (l1, l5, l15) = data.load_avg;
-        let cores = data.core_count;
-        for (i, val) in [l1, l5, l15].iter().enumerate() {
-            let color = load_avg_color(*val, cores);
-            queue!(out, SetForegroundColor(color))?;
-            write!(out, "{:.2}", val)?;
-            queue!(out, ResetColor)?;
-            if i < 2 {
-                write!(out, " ")?;
-            }
+    // ── Help footer (last row) ──
+    let help_y = size.1.saturating_sub(1);
+    render_help_footer(
+        out,
+        &[
+            ("q", "Quit"),
+            ("\u{2191}\u{2193}", "Select"),
+            ("Enter", "Expand"),
+            ("Tab", "Next"),
+            ("s", "Sort"),
+            ("/", "Search"),
+            ("o", "Connections"),
+            ("b", "Bandwidth"),
+            ("h", "History"),
+            ("G", "Graphs"),
+            ("p", "Profile"),
+            ("t", "Trace"),
+            ("g", "Group"),
+            ("C/L", "Container"),
+            ("a", "Security"),
+            ("M", "Memory"),
+            ("D", "Diagnostics"),
+            ("E", "Execs"),
+            ("I", "IRQs"),
+            ("U", "Users"),
+            ("x/X", "Export"),
+        ],
+        term_width,
+        help_y,
+    )?;
+
+    if let Some(ref msg) = ui_state.status_message {
+        let note_y = help_y.saturating_sub(1);
+        queue!(out, MoveTo(1, note_y), SetForegroundColor(t.yellow))?;
+        write!(out, "{}", truncate_str(msg, term_width.saturating_sub(2)))?;
+        queue!(out, ResetColor)?;
+    } else if ui_state.has_expansions() {
+        // Position just above help footer
+        let note_y = help_y.saturating_sub(1);
+        queue!(out, MoveTo(1, note_y), SetForegroundColor(t.yellow))?;
+        write!(out, "(Expanded section data frozen)")?;
+        queue!(out, ResetColor)?;
+    }
+
+    out.flush()?;
+    Ok(rows)
+}
+
+/// Summary rows above the separator.
+fn summary_rows(data: &MonitorData, ui_state: &UIState, term_width: usize) -> usize {
+    // Which lines show depends on the data and the configured order, so the
+    // summary is drawn off screen and its lines counted
+    let mut buf = Vec::new();
+    if render_summary(&mut buf, data, ui_state, term_width).is_err() {
+        return 0;
+    }
+    buf.windows(2).filter(|w| w == b"\r\n").count()
+}
+
+/// A bar under the CPU bar stacking user, system, iowait and steal time, so
+/// a busy host says whether it computes, sits in the kernel, waits on disks
+/// or loses its CPU to the hypervisor.
+fn render_cpu_breakdown(out: &mut impl Write, cpu: &CpuBreakdown, bar_width: usize, term_width: usize) -> io::Result<()> {
+    let t = theme();
+    let states = [
+        ("usr", cpu.user, t.green),
+        ("sys", cpu.system, t.red),
+        ("iowait", cpu.iowait, t.yellow),
+        ("steal", cpu.steal, t.sky),
+    ];
+    queue!(out, SetForegroundColor(t.subtext))?;
+    write!(out, "     [")?;
+    // Round the running total so the segments always add up to the busy share
+    let mut end_pct = 0.0;
+    let mut drawn = 0;
+    for (_, pct, color) in states {
+        end_pct += pct;
+        let end = ((end_pct / 100.0 * bar_width as f64).round() as usize).min(bar_width);
+        queue!(out, SetForegroundColor(color))?;
+        write!(out, "{}", "|".repeat(end.saturating_sub(drawn)))?;
+        drawn = drawn.max(end);
+    }
+    queue!(out, SetBackgroundColor(t.bar_empty))?;
+    write!(out, "{}", " ".repeat(bar_width - drawn))?;
+    queue!(out, ResetColor, SetForegroundColor(t.subtext))?;
+    write!(out, "]")?;
+    let mut width = bar_width + 7;
+    for (label, pct, color) in states {
+        let value = format!("{:.1}%", pct);
+        width += 3 + label.len() + value.len();
+        if width > term_width {
+            break;
         }
+        queue!(out, SetForegroundColor(color))?;
+        write!(out, "  {} ", label)?;
+        queue!(out, SetForegroundColor(t.text))?;
+        write!(out, "{}", value)?;
     }
     queue!(out, ResetColor)?;
-    write!(out, "\r\n")?;
+    write!(out, "\r\n")
+}
 
-    // Mem bar
+/// Links with a problem but no traffic (down, flapping); they get their own line.
+fn idle_link_alerts(data: &MonitorData) -> Vec<&LinkInfo> {
+    data.network
+        .links
+        .iter()
+        .filter(|l| l.warning().is_some())
+        .filter(|l| !data.network.interfaces.iter().any(|i| i.name == l.name))
+        .collect()
+}
+
+/// The host summary: saturation verdict, resource bars, disks, interfaces,
+/// the one-line storage, socket and security reports and any custom lines,
+/// in the configured order.
+fn render_summary(out: &mut impl Write, data: &MonitorData, ui_state: &UIState, term_width: usize) -> io::Result<()> {
+    let t = theme();
+    let bar_width: usize = 40;
     let m = &data.memory;
-    let mem_pct = if m.total > 0 {
-        (m.used as f64 / m.total as f64) * 100.0
-    } else {
-        0.0
+
+    for line in &ui_state.summary_lines {
+        match line {
+            SummaryLine::Saturation => {
+                // ── Saturation badge: a single triage verdict ──
+                let saturation = assess_saturation(
+                    data.load_avg,
+                    data.core_count,
+                    &data.pressure,
+                    &data.memory,
+                    data.disk_busy_pct,
+                );
+                let badge_color = match saturation.level {
+                    SaturationLevel::Ok => t.green,
+                    SaturationLevel::Pressure => t.peach,
+                    SaturationLevel::Saturated => t.red,
+                };
+                queue!(out, SetForegroundColor(t.subtext))?;
+                write!(out, " Saturation ")?;
+                queue!(out, SetForegroundColor(badge_color), SetAttribute(Attribute::Bold))?;
+                write!(out, "{}", saturation.level.label())?;
+                queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+                let reason_width = term_width.saturating_sub(13 + saturation.level.label().len());
+                write!(out, "  {}", truncate_str(&saturation.reason, reason_width))?;
+                queue!(out, ResetColor)?;
+                write!(out, "\r\n")?;
+            }
+            SummaryLine::Anomaly => {
+                // ── Anomalies: metrics far above their recent baseline ──
+                if !data.anomalies.is_empty() {
+                    queue!(out, SetForegroundColor(t.peach), SetAttribute(Attribute::Bold))?;
+                    write!(out, " \u{25b2} ANOMALY")?;
+                    queue!(out, SetAttribute(Attribute::Reset))?;
+                    let text: Vec<String> = data.anomalies.iter().map(AnomalyInfo::summary).collect();
+                    queue!(out, SetForegroundColor(t.text))?;
+                    write!(out, "  {}", truncate_str(&text.join("  "), term_width.saturating_sub(12)))?;
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Cpu => {
+                // CPU bar
+                let cpu_total: f64 = data
+                    .historical_top
+                    .iter()
+                    .map(|p| p.cpu)
+                    .sum::<f64>();
+                let cpu_pct = (cpu_total / (data.core_count * 100.0) * 100.0).min(100.0);
+                render_bar(out, "CPU", cpu_pct, "", bar_width)?;
+
+                // Right side: Tasks + Load average (on the same line as CPU bar)
+                let task_count = data.historical_top.len();
+                let right_col = bar_width + 3 + 5 + 8; // label(5) + bar + bracket(2) + percent(7) + spacing
+                let right_start = right_col + 2;
+                if right_start < term_width {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, "    Tasks: ", )?;
+                    queue!(out, SetForegroundColor(t.text))?;
+                    write!(out, "{:<6}", task_count)?;
+
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, "Load average: ")?;
+
+                    let (l1, l5, l15) = data.load_avg;
+                    let cores = data.core_count;
+                    for (i, val) in [l1, l5, l15].iter().enumerate() {
+                        let color = load_avg_color(*val, cores);
+                        queue!(out, SetForegroundColor(color))?;
+                        write!(out, "{:.2}", val)?;
+                        queue!(out, ResetColor)?;
+                        if i < 2 {
+                            write!(out, " ")?;
+                        }
+                    }
+                }
+                queue!(out, ResetColor)?;
+                write!(out, "\r\n")?;
+
+                if let Some(ref cpu) = data.cpu_breakdown {
+                    render_cpu_breakdown(out, cpu, bar_width, term_width)?;
+                }
+            }
+            SummaryLine::Mem => {
+                // Mem bar
+                let mem_pct = if m.total > 0 {
+                    (m.used as f64 / m.total as f64) * 100.0
+                } else {
+                    0.0
+                };
+                let mem_used_gb = m.used as f64 / 1_073_741_824.0;
+                let mem_total_gb = m.total as f64 / 1_073_741_824.0;
+                let mem_detail = format!("{:.1}G/{:.1}G", mem_used_gb, mem_total_gb);
+                render_bar(out, "Mem", mem_pct, &mem_detail, bar_width)?;
+                if mem_pct > ui_state.thresholds.mem_used_pct {
+                    queue!(out, SetForegroundColor(t.red))?;
+                    write!(out, "  HIGH")?;
+                }
+
+                // Right side: Uptime
+                if !data.time.is_empty() {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, "              Uptime: ")?;
+                    queue!(out, SetForegroundColor(t.text))?;
+                    write!(out, "{}", data.time)?;
+                }
+                if let Some(secs) = data.oom.and_then(|o| o.recent_kill_secs()) {
+                    queue!(out, SetForegroundColor(t.red))?;
+                    write!(out, "  OOM kill {}m ago", secs / 60)?;
+                }
+                if let Some(ref entropy) = data.entropy {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, "  Entropy: ")?;
+                    queue!(out, SetForegroundColor(if entropy.is_low() { t.red } else { t.text }))?;
+                    write!(out, "{}", entropy.available)?;
+                    if entropy.feeder.is_none() {
+                        queue!(out, SetForegroundColor(if entropy.is_low() { t.red } else { t.subtext }))?;
+                        write!(out, " (no rngd)")?;
+                    }
+                }
+                if let Some(ref updates) = ui_state.updates {
+                    write_update_status(out, updates)?;
+                }
+                queue!(out, ResetColor)?;
+                write!(out, "\r\n")?;
+
+                // Buffers, page cache and slab: what a high "used" is made of
+                if let Some(b) = m.breakdown {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, "      ")?;
+                    for (label, bytes) in [("buffers", b.buffers), ("cached", b.cached), ("slab", b.slab)] {
+                        queue!(out, SetForegroundColor(t.subtext))?;
+                        write!(out, "{} ", label)?;
+                        queue!(out, SetForegroundColor(t.text))?;
+                        write!(out, "{}  ", format_mem_human(bytes))?;
+                    }
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, "({} reclaimable)  available ", format_mem_human(b.slab_reclaimable))?;
+                    queue!(out, SetForegroundColor(t.text))?;
+                    write!(out, "{}", format_mem_human(m.available))?;
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Swap => {
+                // Swap bar
+                if m.swap_total > 0 {
+                    let swap_pct = (m.swap_used as f64 / m.swap_total as f64) * 100.0;
+                    let swap_used_gb = m.swap_used as f64 / 1_073_741_824.0;
+                    let swap_total_gb = m.swap_total as f64 / 1_073_741_824.0;
+                    let swap_detail = format!("{:.1}G/{:.1}G", swap_used_gb, swap_total_gb);
+                    render_bar(out, "Swp", swap_pct, &swap_detail, bar_width)?;
+                } else {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " Swp [no swap]")?;
+                }
+                queue!(out, ResetColor)?;
+                write!(out, "\r\n")?;
+            }
+            SummaryLine::Disk => {
+                // ── Disk usage ──
+                if !data.disk_space.is_empty() {
+                    for disk in &data.disk_space {
+                        let used_gb = disk.total_gb - disk.available_gb;
+                        let used_pct = if disk.total_gb > 0.0 {
+                            (used_gb / disk.total_gb) * 100.0
+                        } else {
+                            0.0
+                        };
+                        let label = if disk.mount_point.len() <= 10 {
+                            format!(" {} ", disk.mount_point)
+                        } else {
+                            let short: String = disk.mount_point.chars().take(8).collect();
+                            format!(" {}.. ", short)
+                        };
+                        let detail = format!("{:.1}G/{:.1}G", used_gb, disk.total_gb);
+
+                        // Color the bar based on usage
+                        let bar_color = if disk.percent_free < ui_state.thresholds.disk_free_pct {
+                            t.red
+                        } else if used_pct > 75.0 {
+                            t.peach
+                        } else {
+                            t.bar_filled
+                        };
+
+                        // Render disk bar manually with custom color
+                        let filled = ((used_pct / 100.0) * bar_width as f64).round() as usize;
+                        let empty = bar_width.saturating_sub(filled);
+
+                        queue!(out, SetForegroundColor(t.subtext))?;
+                        write!(out, "{:<4}", label.chars().take(4).collect::<String>())?;
+                        queue!(out, SetForegroundColor(t.subtext))?;
+                        write!(out, "[")?;
+                        queue!(out, SetForegroundColor(bar_color))?;
+                        write!(out, "{}", "|".repeat(filled))?;
+                        queue!(out, SetBackgroundColor(t.bar_empty), SetForegroundColor(t.bar_empty))?;
+                        write!(out, "{}", " ".repeat(empty))?;
+                        queue!(out, ResetColor, SetForegroundColor(t.subtext))?;
+                        write!(out, "]")?;
+                        queue!(out, SetForegroundColor(t.text))?;
+                        write!(out, " {:>5.1}%  ", used_pct)?;
+                        queue!(out, SetForegroundColor(t.subtext))?;
+                        write!(out, "{}", detail)?;
+
+                        // Warning indicator
+                        let low_pct = ui_state.thresholds.disk_free_pct;
+                        if disk.percent_free < low_pct {
+                            queue!(out, SetForegroundColor(t.red))?;
+                            write!(out, "  LOW")?;
+                        } else if disk.percent_free < low_pct * 2.0 {
+                            queue!(out, SetForegroundColor(t.peach))?;
+                            write!(out, "  LOW")?;
+                        }
+
+                        // Disk I/O on the first disk line
+                        if std::ptr::eq(disk, &data.disk_space[0]) && data.disk_busy_pct > 0.0 {
+                            queue!(out, SetForegroundColor(t.subtext))?;
+                            write!(out, "    I/O: ")?;
+                            let io_color = if data.disk_busy_pct > 80.0 {
+                                t.red
+                            } else if data.disk_busy_pct > 50.0 {
+                                t.peach
+                            } else {
+                                t.text
+                            };
+                            queue!(out, SetForegroundColor(io_color))?;
+                            write!(out, "{:.1}%", data.disk_busy_pct)?;
+                        }
+
+                        queue!(out, ResetColor)?;
+                        write!(out, "\r\n")?;
+                    }
+                }
+            }
+            SummaryLine::Net => {
+                // ── Network interfaces ──
+                let links = &data.network.links;
+                if !data.network.interfaces.is_empty() {
+                    for iface in &data.network.interfaces {
+                        let rx_str = format_bytes_rate(iface.rx_rate);
+                        let tx_str = format_bytes_rate(iface.tx_rate);
+
+                        queue!(out, SetForegroundColor(t.subtext))?;
+                        write!(out, " Net ")?;
+                        queue!(out, SetForegroundColor(t.text))?;
+                        write!(out, "{}", pad(&iface.name, 10))?;
+                        queue!(out, SetForegroundColor(t.teal))?;
+                        write!(out, " \u{2193}{:<10}", rx_str)?;
+                        queue!(out, SetForegroundColor(t.peach))?;
+                        write!(out, " \u{2191}{:<10}", tx_str)?;
+                        if let Some(link) = links.iter().find(|l| l.name == iface.name) {
+                            write_link_state(out, link)?;
+                        }
+                        queue!(out, ResetColor)?;
+                        write!(out, "\r\n")?;
+                    }
+                }
+
+                // Links with a problem but no traffic (down, flapping) get their own line.
+                for link in idle_link_alerts(data) {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " Net ")?;
+                    queue!(out, SetForegroundColor(t.text))?;
+                    write!(out, "{}{:<24}", pad(&link.name, 10), "")?;
+                    write_link_state(out, link)?;
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Sock => {
+                // ── Socket summary (compact, single line) ──
+                let sock = &data.socket_overview;
+                if sock.established > 0
+                    || sock.listen > 0
+                    || sock.time_wait > 0
+                    || sock.close_wait > 0
+                    || data.conntrack.is_some()
+                {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " Sock ")?;
+                    queue!(out, SetForegroundColor(t.text))?;
+                    write!(out, "EST:{} ", sock.established)?;
+                    write!(out, "LISTEN:{} ", sock.listen)?;
+
+                    if sock.time_wait > ui_state.thresholds.time_wait {
+                        queue!(out, SetForegroundColor(t.yellow))?;
+                    }
+                    write!(out, "TW:{} ", sock.time_wait)?;
+                    queue!(out, SetForegroundColor(t.text))?;
+
+                    if sock.close_wait > ui_state.thresholds.close_wait {
+                        queue!(out, SetForegroundColor(t.red))?;
+                    }
+                    write!(out, "CW:{}", sock.close_wait)?;
+
+                    if let Some(ct) = data.conntrack {
+                        let pct = ct.usage_pct();
+                        let color = if pct >= ConntrackInfo::CRITICAL_PCT {
+                            t.red
+                        } else if pct >= ConntrackInfo::WARN_PCT {
+                            t.peach
+                        } else {
+                            t.text
+                        };
+                        queue!(out, SetForegroundColor(color))?;
+                        write!(out, "  CT:{}/{} ({:.0}%)", ct.count, ct.max, pct)?;
+                        if pct >= ConntrackInfo::CRITICAL_PCT {
+                            write!(out, " NEAR FULL")?;
+                        }
+                    }
+
+                    if let Some(top) = sock.remotes.first().filter(|r| r.is_heavy()) {
+                        queue!(out, SetForegroundColor(t.red))?;
+                        write!(out, "  HOT {} x{} (o)", top.address, top.connections)?;
+                    }
+
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Port => {
+                // ── Listening sockets that appeared or went away ──
+                if !data.listener_changes.is_empty() {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " Port ")?;
+                    let mut used = 6;
+                    for change in &data.listener_changes {
+                        let process = change.process.as_deref().map(|p| format!(" {}", p)).unwrap_or_default();
+                        let text = format!(
+                            "{}{}{} ({}m)  ",
+                            if change.appeared { "+" } else { "-" },
+                            change.label,
+                            process,
+                            change.secs_ago / 60
+                        );
+                        if used + text.len() > term_width {
+                            break;
+                        }
+                        queue!(out, SetForegroundColor(if change.appeared { t.peach } else { t.red }))?;
+                        write!(out, "{}", text)?;
+                        used += text.len();
+                    }
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Raid => {
+                // ── md arrays and ZFS pools ──
+                if !data.storage_arrays.is_empty() {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " RAID ")?;
+                    let mut used = 6;
+                    for array in &data.storage_arrays {
+                        let text = format!("{}  ", array.summary());
+                        if used + text.len() > term_width {
+                            break;
+                        }
+                        let color = if !array.healthy {
+                            t.red
+                        } else if array.activity.is_some() {
+                            t.peach
+                        } else {
+                            t.green
+                        };
+                        queue!(out, SetForegroundColor(color))?;
+                        write!(out, "{}", text)?;
+                        used += text.len();
+                    }
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Lvm => {
+                // ── LVM thin pools and snapshots ──
+                if !data.lvm_volumes.is_empty() {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " LVM  ")?;
+                    let mut used = 6;
+                    for volume in &data.lvm_volumes {
+                        let text = format!("{}  ", volume.summary());
+                        if used + text.len() > term_width {
+                            break;
+                        }
+                        let pct = volume.usage_pct();
+                        let color = if pct >= LvmVolumeInfo::CRITICAL_PCT {
+                            t.red
+                        } else if pct >= LvmVolumeInfo::WARN_PCT {
+                            t.peach
+                        } else {
+                            t.text
+                        };
+                        queue!(out, SetForegroundColor(color))?;
+                        write!(out, "{}", text)?;
+                        used += text.len();
+                    }
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Fd => {
+                // ── FD leaks: processes whose open FD count only ever grows ──
+                if let Some(leak) = data.fd_info.leaks.first() {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " FD   ")?;
+                    queue!(out, SetForegroundColor(if leak.is_critical() { t.red } else { t.peach }), SetAttribute(Attribute::Bold))?;
+                    write!(out, "LEAK")?;
+                    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.text))?;
+                    let mut text = leak.summary();
+                    if data.fd_info.leaks.len() > 1 {
+                        text.push_str(&format!("  +{} more", data.fd_info.leaks.len() - 1));
+                    }
+                    write!(out, " {}", truncate_str(&text, term_width.saturating_sub(11)))?;
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Irq => {
+                // ── Network receive processing pinned to one CPU ──
+                if let Some((cpu, share, rate)) = data.interrupts.as_ref().and_then(|i| i.network_hotspot()) {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " IRQ  ")?;
+                    queue!(out, SetForegroundColor(t.peach), SetAttribute(Attribute::Bold))?;
+                    write!(out, "HOTSPOT")?;
+                    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.text))?;
+                    write!(out, " cpu{} handles {:.0}% of NET_RX softirqs ({}) (I)", cpu, share * 100.0, format_per_sec(rate))?;
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Auth => {
+                // ── Failed SSH logins over the last 10 minutes ──
+                let auth = &ui_state.auth;
+                if auth.window_total > 0 {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    write!(out, " Auth ")?;
+                    queue!(out, SetForegroundColor(if auth.is_spiking() { t.red } else { t.text }))?;
+                    write!(out, "{}/min failed, {} in 10m", auth.last_minute, auth.window_total)?;
+                    if let Some((remote, count)) = auth.top_offenders.first() {
+                        queue!(out, SetForegroundColor(t.subtext))?;
+                        write!(out, "  top {} x{} (a)", remote, count)?;
+                    }
+                    queue!(out, ResetColor)?;
+                    write!(out, "\r\n")?;
+                }
+            }
+            SummaryLine::Custom(name) => render_custom_line(out, name, ui_state.custom_lines.get(name), term_width)?,
+        }
+    }
+    Ok(())
+}
+
+/// A `[[summary.custom]]` line: its command's first output line, colored by
+/// the exit status.
+fn render_custom_line(out: &mut impl Write, name: &str, output: Option<&CustomLineOutput>, term_width: usize) -> io::Result<()> {
+    let t = theme();
+    let label = format!(" {} ", pad(name, 4));
+    queue!(out, SetForegroundColor(t.subtext))?;
+    write!(out, "{}", label)?;
+    let (text, color) = match output {
+        Some(o) => (
+            o.text.as_str(),
+            match o.state {
+                CheckState::Ok => t.text,
+                CheckState::Warning => t.peach,
+                CheckState::Critical => t.red,
+                CheckState::Unknown => t.subtext,
+            },
+        ),
+        None => ("running...", t.subtext),
     };
-    let mem_used_gb = m.used as f64 / 1_073_741_824.0;
-    let mem_total_gb = m.total as f64 / 1_073_741_824.0;
-    let mem_detail = format!("{:.1}G/{:.1}G", mem_used_gb, mem_total_gb);
-    render_bar(&mut out, "Mem", mem_pct, &mem_detail, bar_width)?;
+    queue!(out, SetForegroundColor(color))?;
+    write!(out, "{}", truncate_str(text, term_width.saturating_sub(display_width(&label))))?;
+    queue!(out, ResetColor)?;
+    write!(out, "\r\n")
+}
 
-    // Right side: Uptime
-    if !data.time.is_empty() {
+/// The summary and the process list as plain text, `width` columns wide,
+/// drawn into a `Frame` rather than the terminal (`sitrep --report`).
+/// Expanded groups are listed without their children.
+pub fn render_report(data: &MonitorData, ui_state: &mut UIState, width: u16) -> io::Result<String> {
+    ui_state.expanded_pids.clear();
+    let rows = summary_rows(data, ui_state, width as usize) + 2 + data.historical_top.len();
+    let mut frame = Frame::new(width, rows as u16 + 1);
+    frame.draw(|out| {
+        render_summary(out, data, ui_state, width as usize)?;
+        write!(out, "{}\r\n", "\u{2500}".repeat(width as usize))?;
+        render_process_table(out, data, ui_state, width as usize, &mut Vec::new())
+    })?;
+    Ok(frame.text().trim_end().to_string())
+}
+
+/// The summary pinned over the top third of the screen, under the tab bar,
+/// with a separator below it. Rows that do not fit are cut off.
+pub fn render_pinned_summary(out: &mut impl Write, data: &MonitorData, ui_state: &UIState) -> io::Result<Viewport> {
+    let t = theme();
+    let (width, height) = screen_size()?;
+    let rows = (2 + summary_rows(data, ui_state, width as usize) + 1).min((height / 3).max(4) as usize) as u16;
+    queue!(out, MoveTo(0, 2))?;
+    render_summary(out, data, ui_state, width as usize)?;
+
+    let separator_y = rows - 1;
+    queue!(out, MoveTo(0, separator_y), Clear(ClearType::FromCursorDown), SetForegroundColor(t.separator))?;
+    write!(out, "{}", "\u{2500}".repeat(width as usize))?;
+    queue!(out, ResetColor)?;
+    Viewport::below(rows)
+}
+
+/// Append " up 10G" (or "DOWN") plus any link warning to an interface line.
+fn write_link_state(out: &mut impl Write, link: &LinkInfo) -> io::Result<()> {
+    let t = theme();
+    if link.up {
         queue!(out, SetForegroundColor(t.subtext))?;
-        write!(out, "              Uptime: ")?;
-        queue!(out, SetForegroundColor(t.text))?;
-        write!(out, "{}", data.time)?;
+        write!(out, " up")?;
+        if let Some(speed) = link.speed_mbps {
+            write!(out, " {}", format_link_speed(speed))?;
+        }
+    } else {
+        queue!(out, SetForegroundColor(t.red))?;
+        write!(out, " DOWN")?;
     }
-    queue!(out, ResetColor)?;
+    if let Some(warning) = link.warning() {
+        queue!(out, SetForegroundColor(t.red))?;
+        write!(out, "  \u{26a0} {}", warning)?;
+    }
+    Ok(())
+}
+
+/// ESTABLISHED connections grouped by remote address, busiest first.
+fn render_remote_connections(
+    out: &mut impl Write,
+    sock: &SocketOverviewInfo,
+    term_width: usize,
+    capacity: usize,
+) -> io::Result<()> {
+    let t = theme();
+
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Established connections by remote")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    write!(
+        out,
+        "  ({} remotes, {}+ highlighted)\r\n",
+        sock.remotes.len(),
+        HEAVY_REMOTE_CONNECTIONS
+    )?;
+
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:<40} {:>7}  PORTS", "REMOTE", "CONNS")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
     write!(out, "\r\n")?;
 
-    // Swap bar
-    if m.swap_total > 0 {
-        let swap_pct = (m.swap_used as f64 / m.swap_total as f64) * 100.0;
-        let swap_used_gb = m.swap_used as f64 / 1_073_741_824.0;
-        let swap_total_gb = m.swap_total as f64 / 1_073_741_824.0;
-        let swap_detail = format!("{:.1}G/{:.1}G", swap_used_gb, swap_total_gb);
-        render_bar(&mut out, "Swp", swap_pct, &swap_detail, bar_width)?;
-    } else {
+    if sock.remotes.is_empty() {
         queue!(out, SetForegroundColor(t.subtext))?;
-        write!(out, " Swp [no swap]")?;
+        write!(out, "  No established connections")?;
+        queue!(out, ResetColor)?;
+        write!(out, "\r\n")?;
+        return Ok(());
     }
-    queue!(out, ResetColor)?;
-    write!(out, "\r\n")?;
 
-    // ── Disk usage ──
-    if !data.disk_space.is_empty() {
-        for disk in &data.disk_space {
-            let used_gb = disk.total_gb - disk.available_gb;
-            let used_pct = if disk.total_gb > 0.0 {
-                (used_gb / disk.total_gb) * 100.0
-            } else {
-                0.0
-            };
-            let label = if disk.mount_point.len() <= 10 {
-                format!(" {} ", disk.mount_point)
-            } else {
-                let short: String = disk.mount_point.chars().take(8).collect();
-                format!(" {}.. ", short)
-            };
-            let detail = format!("{:.1}G/{:.1}G", used_gb, disk.total_gb);
-
-            // Color the bar based on usage
-            let bar_color = if used_pct > 90.0 {
-                t.red
-            } else if used_pct > 75.0 {
-                t.peach
-            } else {
-                t.bar_filled
-            };
-
-            // Render disk bar manually with custom color
-            let filled = ((used_pct / 100.0) * bar_width as f64).round() as usize;
-            let empty = bar_width.saturating_sub(filled);
+    let ports_width = term_width.saturating_sub(2 + 40 + 1 + 7 + 2);
+    for remote in sock.remotes.iter().take(capacity) {
+        let color = if remote.is_heavy() {
+            t.red
+        } else if remote.connections >= HEAVY_REMOTE_CONNECTIONS / 4 {
+            t.peach
+        } else {
+            t.text
+        };
+        queue!(out, SetForegroundColor(color))?;
+        write!(out, "  {:<40} {:>7}  ", remote.address.to_string(), remote.connections)?;
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(out, "{}", truncate_str(&format_remote_ports(&remote.ports), ports_width))?;
+        queue!(out, ResetColor)?;
+        write!(out, "\r\n")?;
+    }
+    Ok(())
+}
 
-            queue!(out, SetForegroundColor(t.subtext))?;
-            write!(out, "{:<4}", label.chars().take(4).collect::<String>())?;
-            queue!(out, SetForegroundColor(t.subtext))?;
-            write!(out, "[")?;
-            queue!(out, SetForegroundColor(bar_color))?;
-            write!(out, "{}", "|".repeat(filled))?;
-            queue!(out, SetBackgroundColor(t.bar_empty), SetForegroundColor(t.bar_empty))?;
-            write!(out, "{}", " ".repeat(empty))?;
-            queue!(out, ResetColor, SetForegroundColor(t.subtext))?;
-            write!(out, "]")?;
-            queue!(out, SetForegroundColor(t.text))?;
-            write!(out, " {:>5.1}%  ", used_pct)?;
-            queue!(out, SetForegroundColor(t.subtext))?;
-            write!(out, "{}", detail)?;
-
-            // Warning indicator
-            if used_pct > 90.0 {
-                queue!(out, SetForegroundColor(t.red))?;
-                write!(out, "  LOW")?;
-            } else if used_pct > 80.0 {
-                queue!(out, SetForegroundColor(t.peach))?;
-                write!(out, "  LOW")?;
-            }
+/// Width of the value label column left of a graph.
+pub(super) const GRAPH_AXIS_WIDTH: usize = 12;
 
-            // Disk I/O on the first disk line
-            if std::ptr::eq(disk, &data.disk_space[0]) && data.disk_busy_pct > 0.0 {
-                queue!(out, SetForegroundColor(t.subtext))?;
-                write!(out, "    I/O: ")?;
-                let io_color = if data.disk_busy_pct > 80.0 {
-                    t.red
-                } else if data.disk_busy_pct > 50.0 {
-                    t.peach
-                } else {
-                    t.text
-                };
-                queue!(out, SetForegroundColor(io_color))?;
-                write!(out, "{:.1}%", data.disk_busy_pct)?;
-            }
+/// Braille cells available for a graph on a terminal `term_width` wide.
+pub fn graph_width(term_width: usize) -> usize {
+    term_width.saturating_sub(GRAPH_AXIS_WIDTH + 2).max(1)
+}
+
+/// Braille graphs of an interface's retained rx and tx rates, one above the other.
+fn render_bandwidth_graph(
+    out: &mut impl Write,
+    name: &str,
+    history: Option<&InterfaceHistory>,
+    term_width: usize,
+    capacity: usize,
+) -> io::Result<()> {
+    let t = theme();
+
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Bandwidth {}", name)?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    let samples = history.map(|h| h.samples.as_slice()).unwrap_or(&[]);
+    write!(out, "  (last {} samples, b: next interface, Esc: back)\r\n", samples.len())?;
+
+    if samples.is_empty() {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(out, "  No traffic recorded for this interface yet")?;
+        queue!(out, ResetColor)?;
+        write!(out, "\r\n")?;
+        return Ok(());
+    }
+
+    let graph_width = graph_width(term_width);
+    let graph_height = (capacity.saturating_sub(3) / 2).max(1);
+
+    let series: [(&str, Vec<u64>, _); 2] = [
+        ("\u{2193} RX", samples.iter().map(|s| s.0).collect(), t.teal),
+        ("\u{2191} TX", samples.iter().map(|s| s.1).collect(), t.peach),
+    ];
+    for (label, values, color) in &series {
+        let now = values.last().copied().unwrap_or(0);
+        let peak = values.iter().copied().max().unwrap_or(0);
+        queue!(out, SetForegroundColor(*color))?;
+        write!(out, "  {}", label)?;
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(
+            out,
+            "  now {}  peak {}\r\n",
+            format_bytes_rate(now),
+            format_bytes_rate(peak)
+        )?;
 
+        for (row, line) in braille_graph(values, graph_width, graph_height, peak).iter().enumerate() {
+            let axis = if row == 0 { format_bytes_rate(peak) } else { String::new() };
+            queue!(out, SetForegroundColor(t.subtext))?;
+            write!(out, "  {:>width$}", axis, width = GRAPH_AXIS_WIDTH - 1)?;
+            queue!(out, SetForegroundColor(*color))?;
+            write!(out, " {}", line)?;
             queue!(out, ResetColor)?;
             write!(out, "\r\n")?;
         }
     }
+    Ok(())
+}
+
+/// Last-24h graphs from the metrics history store, stacked vertically.
+fn render_history_graphs(
+    out: &mut impl Write,
+    series: &[HistorySeries],
+    term_width: usize,
+    capacity: usize,
+) -> io::Result<()> {
+    let t = theme();
 
-    // ── Network interfaces ──
-    if !data.network.interfaces.is_empty() {
-        for iface in &data.network.interfaces {
-            let rx_str = format_bytes_rate(iface.rx_rate);
-            let tx_str = format_bytes_rate(iface.tx_rate);
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  History")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    write!(out, "  (last 24h, peak per column, Esc: back)\r\n")?;
 
+    if series.iter().all(|s| s.values.iter().all(|v| *v == 0)) {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(out, "  No history recorded in the last 24h yet")?;
+        queue!(out, ResetColor)?;
+        write!(out, "\r\n")?;
+        return Ok(());
+    }
+
+    let graph_width = graph_width(term_width);
+    // Title row, then a label row per series, with the time axis at the bottom
+    let graph_height = (capacity.saturating_sub(2 + series.len()) / series.len().max(1)).max(1);
+    let colors = [t.peach, t.mauve, t.teal, t.yellow];
+    for (s, color) in series.iter().zip(colors.iter().cycle()) {
+        let format = |v: u64| match s.unit {
+            MetricUnit::Count => v.to_string(),
+            MetricUnit::Percent => format!("{}%", v),
+            MetricUnit::BytesPerSec => format_bytes_rate(v),
+        };
+        let peak = s.values.iter().copied().max().unwrap_or(0);
+        // Percentages keep a fixed 0-100 scale unless they exceed it
+        let scale = match s.unit {
+            MetricUnit::Percent => peak.max(100),
+            MetricUnit::Count | MetricUnit::BytesPerSec => peak,
+        };
+        queue!(out, SetForegroundColor(*color))?;
+        write!(out, "  {}", s.label)?;
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(out, "  peak {}\r\n", format(peak))?;
+
+        for (row, line) in braille_graph(&s.values, graph_width, graph_height, scale).iter().enumerate() {
+            let axis = if row == 0 { format(scale) } else { String::new() };
             queue!(out, SetForegroundColor(t.subtext))?;
-            write!(out, " Net ")?;
-            queue!(out, SetForegroundColor(t.text))?;
-            write!(out, "{:<10}", iface.name)?;
-            queue!(out, SetForegroundColor(t.teal))?;
-            write!(out, " \u{2193}{:<10}", rx_str)?;
-            queue!(out, SetForegroundColor(t.peach))?;
-            write!(out, " \u{2191}{:<10}", tx_str)?;
+            write!(out, "  {:>width$}", axis, width = GRAPH_AXIS_WIDTH - 1)?;
+            queue!(out, SetForegroundColor(*color))?;
+            write!(out, " {}", line)?;
             queue!(out, ResetColor)?;
             write!(out, "\r\n")?;
         }
     }
 
-    // ── Socket summary (compact, single line) ──
-    let sock = &data.socket_overview;
-    if sock.established > 0 || sock.listen > 0 || sock.time_wait > 0 || sock.close_wait > 0 {
+    queue!(out, SetForegroundColor(t.subtext))?;
+    let pad = graph_width.saturating_sub("-24h".len() + "now".len());
+    write!(out, "  {:>width$} -24h{}now\r\n", "", " ".repeat(pad), width = GRAPH_AXIS_WIDTH - 1)?;
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// Hottest stacks of the last profile capture, one row per stack with the
+/// leaf frame first and its callers after it.
+fn render_profile(
+    out: &mut impl Write,
+    profile: Option<&ProfileView>,
+    term_width: usize,
+    capacity: usize,
+) -> io::Result<()> {
+    let t = theme();
+    let Some(profile) = profile else {
         queue!(out, SetForegroundColor(t.subtext))?;
-        write!(out, " Sock ")?;
-        queue!(out, SetForegroundColor(t.text))?;
-        write!(out, "EST:{} ", sock.established)?;
-        write!(out, "LISTEN:{} ", sock.listen)?;
+        write!(out, "  No profile captured (select a process and press p)\r\n")?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    };
 
-        if sock.time_wait > 100 {
-            queue!(out, SetForegroundColor(t.yellow))?;
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  CPU profile of {} (PID {})", profile.name, profile.pid)?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    match &profile.state {
+        ProfileState::Running => {
+            write!(
+                out,
+                "  capturing... {}s/{}s\r\n",
+                profile.started.elapsed().as_secs().min(crate::profiler::PROFILE_SECS),
+                crate::profiler::PROFILE_SECS
+            )?;
         }
-        write!(out, "TW:{} ", sock.time_wait)?;
-        queue!(out, SetForegroundColor(t.text))?;
-
-        if sock.close_wait > 10 {
+        ProfileState::Failed(e) => {
+            write!(out, "\r\n")?;
             queue!(out, SetForegroundColor(t.red))?;
+            write!(out, "  {}\r\n", truncate_str(e, term_width.saturating_sub(2)))?;
         }
-        write!(out, "CW:{}", sock.close_wait)?;
+        ProfileState::Done(report) => {
+            write!(out, "  ({}, {}s, Esc: back)\r\n", report.tool, crate::profiler::PROFILE_SECS)?;
+            queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+            write!(out, "  {:>6}  STACK (leaf \u{2190} callers)", "CPU%")?;
+            queue!(out, SetAttribute(Attribute::Reset))?;
+            write!(out, "\r\n")?;
+            let stack_width = term_width.saturating_sub(10);
+            for entry in report.entries.iter().take(capacity.saturating_sub(2)) {
+                let color = if entry.percent >= 20.0 { t.red } else { t.text };
+                queue!(out, SetForegroundColor(color))?;
+                write!(out, "  {:>5.1}%  ", entry.percent)?;
+                let (leaf, callers) = entry.frames.split_first().map_or(("", &[][..]), |(l, c)| (l.as_str(), c));
+                write!(out, "{}", truncate_str(leaf, stack_width))?;
+                if !callers.is_empty() {
+                    queue!(out, SetForegroundColor(t.subtext))?;
+                    let rest = format!(" \u{2190} {}", callers.join(" \u{2190} "));
+                    write!(out, "{}", truncate_str(&rest, stack_width.saturating_sub(display_width(leaf))))?;
+                }
+                write!(out, "\r\n")?;
+            }
+        }
+    }
+    queue!(out, ResetColor)?;
+    Ok(())
+}
 
+/// Failed-login rate and the remote addresses behind them.
+fn render_security(out: &mut impl Write, auth: &AuthSummary, capacity: usize) -> io::Result<()> {
+    let t = theme();
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Security")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    match (&auth.source, &auth.error) {
+        (Some(source), _) => write!(out, "  failed SSH logins from {} (Esc: back)\r\n", source)?,
+        (None, Some(e)) => write!(out, "  {}\r\n", e)?,
+        (None, None) => write!(out, "\r\n")?,
+    }
+
+    queue!(out, SetForegroundColor(if auth.is_spiking() { t.red } else { t.text }))?;
+    write!(out, "  {} failed in the last minute, {} in the last 10 minutes", auth.last_minute, auth.window_total)?;
+    if auth.is_spiking() {
+        write!(out, "  SPIKE")?;
+    }
+    write!(out, "\r\n")?;
+    if auth.top_offenders.is_empty() {
         queue!(out, ResetColor)?;
-        write!(out, "\r\n")?;
+        return Ok(());
     }
 
-    // ── Separator line ──
-    queue!(out, SetForegroundColor(t.separator))?;
-    let sep: String = "\u{2500}".repeat(term_width);
-    write!(out, "{}\r\n", sep)?;
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:>8}  REMOTE", "FAILURES")?;
+    queue!(out, SetAttribute(Attribute::Reset))?;
+    write!(out, "\r\n")?;
+    for (remote, count) in auth.top_offenders.iter().take(capacity.saturating_sub(3)) {
+        queue!(out, SetForegroundColor(if *count >= AuthSummary::SPIKE_PER_MIN { t.red } else { t.text }))?;
+        write!(out, "  {:>8}  {}\r\n", count, remote)?;
+    }
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// Process starts per second and the commands started most, which the
+/// 3-second snapshots miss when they exit in between.
+fn render_execs(out: &mut impl Write, execs: &ExecSummary, term_width: usize, capacity: usize) -> io::Result<()> {
+    let t = theme();
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Process Starts")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    match (&execs.source, &execs.error) {
+        (_, Some(e)) => write!(out, "  {}\r\n", e)?,
+        (Some(source), None) => write!(out, "  seen with {} (Esc: back)\r\n", source)?,
+        (None, None) => write!(out, "\r\n")?,
+    }
+
+    queue!(out, SetForegroundColor(t.text))?;
+    write!(
+        out,
+        "  {:.1}/s over the last {}s, {} in the last minute\r\n",
+        execs.per_sec,
+        ExecEvents::RATE_WINDOW.as_secs(),
+        execs.window_total
+    )?;
+    if execs.top_commands.is_empty() {
+        queue!(out, ResetColor)?;
+        return Ok(());
+    }
+
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:>8}  COMMAND", "STARTS")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.text))?;
+    write!(out, "\r\n")?;
+    for (command, count) in execs.top_commands.iter().take(capacity.saturating_sub(3)) {
+        write!(out, "{}\r\n", truncate_str(&format!("  {:>8}  {}", count, command), term_width))?;
+    }
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// "850/s", "12.4k/s" or "1.2M/s".
+fn format_per_sec(rate: f64) -> String {
+    if rate >= 1e6 {
+        format!("{:.1}M/s", rate / 1e6)
+    } else if rate >= 1e3 {
+        format!("{:.1}k/s", rate / 1e3)
+    } else {
+        format!("{:.0}/s", rate)
+    }
+}
+
+/// One interrupt source: its rate, busiest CPU and a braille strip of the
+/// per-CPU rates (two CPUs per cell), then what raises it.
+fn write_interrupt_row(out: &mut impl Write, rate: &InterruptRate, term_width: usize) -> io::Result<()> {
+    let t = theme();
+    let busiest = rate
+        .busiest_cpu()
+        .map(|(cpu, share)| format!("cpu{} {:.0}%", cpu, share * 100.0))
+        .unwrap_or_else(|| "-".to_string());
+    let values: Vec<u64> = rate.per_cpu.iter().map(|r| r.round() as u64).collect();
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    let cells = values.len().div_ceil(2).min(term_width.saturating_sub(50) / 2).max(1);
+    let strip = braille_graph(&values, cells, 1, max).concat();
+    queue!(out, SetForegroundColor(t.text))?;
+    write!(out, "  {:<10} {:<10} {:<11} ", rate.name, format_per_sec(rate.total()), busiest)?;
+    queue!(out, SetForegroundColor(t.sapphire))?;
+    write!(out, "{}", strip)?;
+    queue!(out, SetForegroundColor(t.subtext))?;
+    let rest = term_width.saturating_sub(37 + cells);
+    write!(out, "  {}\r\n", truncate_str(&rate.device, rest))?;
+    Ok(())
+}
+
+/// Softirq and busiest IRQ rates with their spread across CPUs, so one core
+/// doing all the network work stands out.
+fn render_interrupts(
+    out: &mut impl Write,
+    info: Option<&InterruptInfo>,
+    term_width: usize,
+    capacity: usize,
+) -> io::Result<()> {
+    let t = theme();
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Interrupts")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    let Some(info) = info else {
+        write!(out, "  /proc/interrupts is only read on Linux; rates appear after a second refresh\r\n")?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    };
+    write!(out, "  per second since the last refresh (Esc: back)\r\n")?;
+    if let Some((cpu, share, rate)) = info.network_hotspot() {
+        queue!(out, SetForegroundColor(t.peach))?;
+        write!(
+            out,
+            "  cpu{} handles {:.0}% of NET_RX ({}); spread the NIC queues with irqbalance, IRQ affinity or RPS\r\n",
+            cpu,
+            share * 100.0,
+            format_per_sec(rate)
+        )?;
+    }
+
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:<10} {:<10} {:<11} PER CPU\r\n", "SOFTIRQ", "RATE", "BUSIEST")?;
+    queue!(out, SetAttribute(Attribute::Reset))?;
+    let mut room = capacity.saturating_sub(3 + usize::from(info.network_hotspot().is_some()));
+    for rate in info.softirqs.iter().filter(|r| r.total() > 0.0).take(room) {
+        write_interrupt_row(out, rate, term_width)?;
+        room -= 1;
+    }
+    if room < 3 || info.irqs.is_empty() {
+        queue!(out, ResetColor)?;
+        return Ok(());
+    }
+    write!(out, "\r\n")?;
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:<10} {:<10} {:<11} PER CPU\r\n", "IRQ", "RATE", "BUSIEST")?;
+    queue!(out, SetAttribute(Attribute::Reset))?;
+    for rate in info.irqs.iter().take(room - 2) {
+        write_interrupt_row(out, rate, term_width)?;
+    }
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// CPU, memory and process count per user, busiest first.
+fn render_users(out: &mut impl Write, data: &MonitorData, cpu_scale: CpuScale, capacity: usize) -> io::Result<()> {
+    let t = theme();
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Users")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    write!(out, "  every process, summed per user (Esc: back)\r\n")?;
+
+    let cpu_header = if cpu_scale == CpuScale::Total { "ALL%" } else { "CPU%" };
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:<16} {:>6}  {:>7}  {:>7}  {:>5}\r\n", "USER", "PROCS", cpu_header, "MEM", "MEM%")?;
+    queue!(out, SetAttribute(Attribute::Reset))?;
+    for usage in data.user_usage.iter().take(capacity.saturating_sub(2)) {
+        let mem_pct = if data.memory.total > 0 { usage.mem as f64 / data.memory.total as f64 * 100.0 } else { 0.0 };
+        let color = if usage.cpu > 80.0 {
+            t.red
+        } else if usage.cpu > 50.0 {
+            t.peach
+        } else {
+            t.text
+        };
+        queue!(out, SetForegroundColor(color))?;
+        write!(
+            out,
+            "  {:<16} {:>6}  {:>7.1}  {:>7}  {:>4.0}%\r\n",
+            safe_truncate(&usage.user, 16),
+            usage.processes,
+            cpu_scale.apply(usage.cpu, data.core_count),
+            format_mem_human(usage.mem),
+            mem_pct
+        )?;
+    }
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// Shared memory, the HugeTLB pool and THP: the memory that "used" and
+/// "cached" do not explain.
+fn render_memory(
+    out: &mut impl Write,
+    detail: Option<&MemoryDetail>,
+    term_width: usize,
+    capacity: usize,
+) -> io::Result<()> {
+    let t = theme();
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Memory")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    let Some(detail) = detail else {
+        write!(out, "  shared memory and huge pages are only reported on Linux\r\n")?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    };
+    write!(out, "  shared memory and huge pages (Esc: back)\r\n")?;
+    let kb = |v: u64| format_mem_human(v * 1024);
+
+    queue!(out, SetForegroundColor(t.text))?;
+    write!(
+        out,
+        "  Shmem {}  (tmpfs and shm segments, counted as both used and cached)\r\n",
+        kb(detail.shmem_kb)
+    )?;
+    if detail.hugepages_total > 0 || detail.hugepages_surplus > 0 {
+        write!(
+            out,
+            "  HugeTLB {} x {} = {}  free {}  reserved {}  surplus {}\r\n",
+            detail.hugepages_total,
+            kb(detail.hugepage_size_kb),
+            kb(detail.hugetlb_kb()),
+            detail.hugepages_free,
+            detail.hugepages_reserved,
+            detail.hugepages_surplus
+        )?;
+    } else {
+        write!(out, "  HugeTLB no pool configured\r\n")?;
+    }
+    write!(
+        out,
+        "  THP {} (defrag {})  anon {}  shmem {}\r\n",
+        detail.thp_enabled.as_deref().unwrap_or("n/a"),
+        detail.thp_defrag.as_deref().unwrap_or("n/a"),
+        kb(detail.anon_huge_kb),
+        kb(detail.shmem_huge_kb)
+    )?;
+
+    if detail.segments.is_empty() {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(out, "  No shared memory segments over {}\r\n", format_mem_human(MemoryDetail::MIN_SEGMENT_BYTES))?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    }
+    queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+    write!(out, "  {:>8}  {:<5}  {:>6}  {:<24}  OWNER", "SIZE", "TYPE", "NATTCH", "SEGMENT")?;
+    queue!(out, SetAttribute(Attribute::Reset))?;
+    write!(out, "\r\n")?;
+    queue!(out, SetForegroundColor(t.text))?;
+    for segment in detail.segments.iter().take(capacity.saturating_sub(5)) {
+        let attached = segment.attached.map(|n| n.to_string()).unwrap_or_else(|| "-".to_string());
+        let line = format!(
+            "  {:>8}  {:<5}  {:>6}  {}  {}",
+            format_mem_human(segment.bytes),
+            segment.kind,
+            attached,
+            pad(&truncate_str(&segment.name, 24), 24),
+            segment.owner.as_deref().unwrap_or("")
+        );
+        write!(out, "{}\r\n", truncate_str(&line, term_width))?;
+    }
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// sitrep's own footprint and whether the collectors are backing off.
+fn render_diagnostics(
+    out: &mut impl Write,
+    usage: Option<&SelfUsage>,
+    guard: &SelfGuard,
+    term_width: usize,
+) -> io::Result<()> {
+    let t = theme();
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Diagnostics")?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    write!(out, "  sitrep's own resource use (Esc: back)\r\n")?;
+    let Some(usage) = usage else {
+        write!(out, "  sitrep's process was not found in the process table\r\n")?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    };
+
+    let over = guard.cpu_budget > 0.0 && usage.total_cpu_percent() > guard.cpu_budget;
+    queue!(out, SetForegroundColor(if over { t.red } else { t.text }))?;
+    let budget = if guard.cpu_budget > 0.0 {
+        format!("budget {:.0}%", guard.cpu_budget)
+    } else {
+        "no budget".to_string()
+    };
+    write!(
+        out,
+        "  CPU     {:.1}%  + subprocesses {:.1}%  ({} of one core)\r\n",
+        usage.cpu_percent, usage.children_cpu_percent, budget
+    )?;
+    queue!(out, SetForegroundColor(t.text))?;
+    write!(out, "  Memory  {} RSS  PID {}", format_mem_human(usage.rss_bytes), usage.pid)?;
+    if let Some(threads) = usage.threads {
+        write!(out, "  {} threads", threads)?;
+    }
+    write!(out, "\r\n")?;
+    let children: Vec<String> = usage
+        .children
+        .iter()
+        .map(|(name, n)| if *n > 1 { format!("{} x{}", name, n) } else { name.clone() })
+        .collect();
+    let line = format!("  Subprocesses {}  {}", usage.child_count(), children.join(", "));
+    write!(out, "{}\r\n", truncate_str(&line, term_width))?;
+
+    if guard.backing_off() {
+        queue!(out, SetForegroundColor(t.peach))?;
+        write!(
+            out,
+            "  Collectors backed off {}x: sitrep is over its CPU budget\r\n",
+            guard.factor
+        )?;
+    } else {
+        queue!(out, SetForegroundColor(t.green))?;
+        write!(out, "  Collectors at the normal interval\r\n")?;
+    }
+    queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// Per-syscall summary of the last trace, busiest first.
+fn render_trace(
+    out: &mut impl Write,
+    trace: Option<&TraceView>,
+    term_width: usize,
+    capacity: usize,
+) -> io::Result<()> {
+    let t = theme();
+    let Some(trace) = trace else {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(out, "  No trace captured (select a process and press t)\r\n")?;
+        queue!(out, ResetColor)?;
+        return Ok(());
+    };
+
+    queue!(out, SetForegroundColor(t.mauve), SetAttribute(Attribute::Bold))?;
+    write!(out, "  Syscall trace of {} (PID {})", trace.name, trace.pid)?;
+    queue!(out, SetAttribute(Attribute::Reset), SetForegroundColor(t.subtext))?;
+    match &trace.state {
+        TraceState::Running => {
+            write!(
+                out,
+                "  tracing... {}s/{}s\r\n",
+                trace.started.elapsed().as_secs().min(crate::tracer::TRACE_SECS),
+                crate::tracer::TRACE_SECS
+            )?;
+        }
+        TraceState::Failed(e) => {
+            write!(out, "\r\n")?;
+            queue!(out, SetForegroundColor(t.red))?;
+            write!(out, "  {}\r\n", truncate_str(e, term_width.saturating_sub(2)))?;
+        }
+        TraceState::Done(report) => {
+            let total_calls: u64 = report.stats.iter().map(|s| s.calls).sum();
+            write!(
+                out,
+                "  ({}, {}s, {} calls, Esc: back)\r\n",
+                report.tool,
+                crate::tracer::TRACE_SECS,
+                total_calls
+            )?;
+            queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+            write!(
+                out,
+                "  {:>6}  {:>10}  {:>8}  {:>10}  {:>10}  SYSCALL",
+                "TIME%", "CALLS", "ERRORS", "TOTAL", "US/CALL"
+            )?;
+            queue!(out, SetAttribute(Attribute::Reset))?;
+            write!(out, "\r\n")?;
+            let name_width = term_width.saturating_sub(56);
+            for stat in report.stats.iter().take(capacity.saturating_sub(2)) {
+                let color = if stat.percent >= 50.0 { t.red } else { t.text };
+                queue!(out, SetForegroundColor(color))?;
+                write!(out, "  {:>5.1}%  {:>10}  ", stat.percent, stat.calls)?;
+                queue!(out, SetForegroundColor(if stat.errors > 0 { t.yellow } else { color }))?;
+                write!(out, "{:>8}  ", stat.errors)?;
+                queue!(out, SetForegroundColor(color))?;
+                write!(
+                    out,
+                    "{:>9.3}s  {:>10}  {}\r\n",
+                    stat.seconds,
+                    stat.usecs_per_call,
+                    truncate_str(&stat.name, name_width)
+                )?;
+            }
+        }
+    }
     queue!(out, ResetColor)?;
+    Ok(())
+}
+
+/// "Updates: 12 (3 sec)" and a reboot flag, after the uptime.
+fn write_update_status(out: &mut impl Write, updates: &UpdateStatus) -> io::Result<()> {
+    let t = theme();
+    if updates.manager.is_some() && updates.pending > 0 {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        write!(out, "  Updates: ")?;
+        queue!(out, SetForegroundColor(t.text))?;
+        write!(out, "{}", updates.pending)?;
+        if let Some(security) = updates.security.filter(|s| *s > 0) {
+            queue!(out, SetForegroundColor(t.peach))?;
+            write!(out, " ({} sec)", security)?;
+        }
+    }
+    if updates.needs_reboot() {
+        queue!(out, SetForegroundColor(t.yellow), SetAttribute(Attribute::Bold))?;
+        write!(out, "  REBOOT")?;
+        queue!(out, SetAttribute(Attribute::Reset))?;
+        if let Some(ref kernel) = updates.newer_kernel {
+            queue!(out, SetForegroundColor(t.subtext))?;
+            write!(out, " (kernel {})", kernel)?;
+        }
+    }
+    Ok(())
+}
+
+/// One unselectable line under an expanded process: how close it is to its
+/// nofile, nproc and memlock limits.
+fn write_process_limits(out: &mut impl Write, limits: &ProcessLimits, open_files: Option<u64>) -> io::Result<()> {
+    let t = theme();
+    let count = |v: Option<u64>| v.map_or_else(|| "unlimited".to_string(), |v| v.to_string());
+
+    queue!(out, SetForegroundColor(t.subtext))?;
+    write!(out, "      limits  nofile ")?;
+    let nofile_pct = match (open_files, limits.nofile.soft) {
+        (Some(used), Some(soft)) if soft > 0 => Some(used as f64 / soft as f64 * 100.0),
+        _ => None,
+    };
+    let color = match nofile_pct {
+        Some(p) if p >= 90.0 => t.red,
+        Some(p) if p >= 75.0 => t.peach,
+        _ => t.text,
+    };
+    queue!(out, SetForegroundColor(color))?;
+    if let Some(used) = open_files {
+        write!(out, "{}/", used)?;
+    }
+    write!(out, "{}", count(limits.nofile.soft))?;
+    queue!(out, SetForegroundColor(t.subtext))?;
+    write!(out, " (hard {})", count(limits.nofile.hard))?;
+
+    write!(out, "  nproc ")?;
+    queue!(out, SetForegroundColor(t.text))?;
+    write!(out, "{}", count(limits.nproc.soft))?;
+    queue!(out, SetForegroundColor(t.subtext))?;
+    write!(out, " (hard {})", count(limits.nproc.hard))?;
+
+    write!(out, "  memlock ")?;
+    queue!(out, SetForegroundColor(t.text))?;
+    let memlock = limits.memlock.soft.map_or_else(|| "unlimited".to_string(), format_mem_human);
+    write!(out, "{}/{}", format_mem_human(limits.locked_bytes), memlock)?;
+    queue!(out, ResetColor)?;
+    write!(out, "\r\n")
+}
+
+/// "nice 10 cpu.weight 20", leaving out whichever is at its default.
+fn format_priority(priority: &ProcessPriority) -> String {
+    let mut parts = Vec::new();
+    if priority.nice != 0 {
+        parts.push(format!("nice {}", priority.nice));
+    }
+    if let Some(weight) = priority.cpu_weight.filter(|w| *w != 100) {
+        parts.push(format!("cpu.weight {}", weight));
+    }
+    parts.join(" ")
+}
+
+/// List a remote's ports, collapsing long lists (many ephemeral client ports).
+fn format_remote_ports(ports: &[u16]) -> String {
+    const SHOWN: usize = 3;
+    let join = |ports: &[u16]| ports.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ");
+    if ports.len() > SHOWN + 1 {
+        format!("{} +{} more", join(&ports[..SHOWN]), ports.len() - SHOWN)
+    } else {
+        join(ports)
+    }
+}
+
+fn render_process_table(
+    out: &mut impl Write,
+    data: &MonitorData,
+    ui_state: &mut UIState,
+    term_width: usize,
+    rows: &mut Vec<(Pid, RowKind)>,
+) -> io::Result<()> {
+    let t = theme();
+    let mut current_row: usize = 0;
 
     // ── Process table header ──
     let headers: &[(&str, usize, Option<SortColumn>)] = &[
         ("PID", 9, None),
         ("USER", 10, None),
-        ("CPU%", 6, Some(SortColumn::Cpu)),
+        (if ui_state.cpu_scale == CpuScale::Total { "ALL%" } else { "CPU%" }, 6, Some(SortColumn::Cpu)),
         ("MEM", 6, Some(SortColumn::Memory)),
         ("NET I/O", 10, Some(SortColumn::NetDown)),
         ("TIME+", 10, None),
-        ("Command", 0, None),
+        (if ui_state.grouping == ProcessGrouping::Unit { "Unit" } else { "Command" }, 0, None),
     ];
 
     queue!(
@@ -245,7 +1490,7 @@ pub fn render(
 
     write!(out, "  ")?;
     for (text, width, col) in headers {
-        let is_sorted = col.map_or(false, |c| c == ui_state.sort_column);
+        let is_sorted = col.is_some_and(|c| c == ui_state.sort_column);
         if is_sorted {
             queue!(
                 out,
@@ -270,6 +1515,10 @@ pub fn render(
     write!(out, "\r\n")?;
 
     // ── Process rows ──
+    // Colors follow percent of one core, so a busy process stays red with
+    // either scale on a many-core host
+    let cpu_scale = ui_state.cpu_scale;
+    let scale = |cpu: f64| cpu_scale.apply(cpu, data.core_count);
     for g in &data.historical_top {
         let mem_str = format_mem_human(g.mem);
         let net_total = g.net_rx_bytes.saturating_add(g.net_tx_bytes);
@@ -296,20 +1545,16 @@ pub fn render(
 
         // PID
         queue!(out, SetForegroundColor(if is_selected { t.selected_fg } else { t.text }))?;
-        write!(out, "  {:<9}", g.pid)?;
+        // sysinfo's Pid ignores the width, so it is padded as a string
+        write!(out, "  {}", pad(&g.pid.to_string(), 9))?;
 
         // USER
-        let user_display = if g.user.len() > 9 {
-            &g.user[..9]
-        } else {
-            &g.user
-        };
         queue!(out, SetForegroundColor(if is_selected { t.selected_fg } else { t.subtext }))?;
-        write!(out, "{:<10} ", user_display)?;
+        write!(out, "{} ", pad(safe_truncate(&g.user, 9), 10))?;
 
         // CPU%
         queue!(out, SetForegroundColor(if is_selected { t.selected_fg } else { cpu_color }))?;
-        write!(out, "{:<6.1}", g.cpu)?;
+        write!(out, "{:<6.1}", scale(g.cpu))?;
 
         // MEM
         queue!(out, SetForegroundColor(if is_selected { t.selected_fg } else { t.text }))?;
@@ -324,15 +1569,35 @@ pub fn render(
         write!(out, "{:<10} ", time_str)?;
 
         // Command (fill remaining width)
-        let used_cols = 2 + 7 + 10 + 1 + 6 + 6 + 1 + 10 + 1 + 10 + 1;
+        let used_cols = 2 + 9 + 10 + 1 + 6 + 6 + 1 + 10 + 1 + 10 + 1;
         let remaining = term_width.saturating_sub(used_cols);
-        let name = if g.name.len() > remaining {
-            &g.name[..remaining]
-        } else {
-            &g.name
+        let label = match (ui_state.grouping, ui_state.container_of(data, g.pid)) {
+            (ProcessGrouping::Unit, Some(c)) => format!("container {} ({})", c, g.child_count),
+            (ProcessGrouping::Unit, None) => format!("{} ({})", g.name, g.child_count),
+            (ProcessGrouping::Parent, Some(c)) => format!("{} [{}]", g.name, c),
+            (ProcessGrouping::Parent, None) => g.name.clone(),
         };
+        let mut badges: Vec<(String, Color)> = Vec::new();
+        if let Some(old) = data.restarted_processes.get(&g.pid.as_u32()) {
+            badges.push((format!("  restarted (was {})", old), t.peach));
+        }
+        // Nice and cpu.weight only ration CPU, so a batch job can still
+        // starve interactive services of the disk
+        if let Some(priority) = data.process_priorities.get(&g.pid.as_u32()).filter(|p| p.is_adjusted()) {
+            let heavy_io = priority.is_low() && g.read_bytes.saturating_add(g.written_bytes) >= ProcessPriority::HEAVY_IO;
+            badges.push((
+                format!("  {}{}", format_priority(priority), if heavy_io { ", heavy I/O" } else { "" }),
+                if heavy_io { t.red } else { t.subtext },
+            ));
+        }
+        let badge_width: usize = badges.iter().map(|(b, _)| b.chars().count()).sum();
+        let name = truncate_str(&label, remaining.saturating_sub(badge_width));
         queue!(out, SetForegroundColor(if is_selected { t.selected_fg } else { t.text }))?;
         write!(out, "{}", name)?;
+        for (badge, color) in badges {
+            queue!(out, SetForegroundColor(if is_selected { t.selected_fg } else { color }))?;
+            write!(out, "{}", badge)?;
+        }
 
         queue!(out, ResetColor)?;
         write!(out, "\r\n")?;
@@ -340,8 +1605,23 @@ pub fn render(
         rows.push((g.pid, RowKind::ProcessParent));
         current_row += 1;
 
-        // Expanded children
+        // Expanded children, after the group's resource limits
         if ui_state.expanded_pids.contains(&g.pid) {
+            if let Some(limits) = data.process_limits.get(&g.pid.as_u32()) {
+                let open_files = data
+                    .fd_info
+                    .per_process
+                    .iter()
+                    .find(|(pid, _, _)| *pid == g.pid.as_u32())
+                    .map(|(_, _, count)| *count);
+                write_process_limits(out, limits, open_files)?;
+            }
+            if let Some(priority) = data.process_priorities.get(&g.pid.as_u32()) {
+                let weight = priority.cpu_weight.map_or_else(|| "-".to_string(), |w| w.to_string());
+                queue!(out, SetForegroundColor(t.subtext))?;
+                write!(out, "      priority  nice {}  cpu.weight {}\r\n", priority.nice, weight)?;
+                queue!(out, ResetColor)?;
+            }
             for child in &g.children {
                 let child_is_selected = current_row == ui_state.selected_index;
                 let child_mem = format_mem_human(child.mem);
@@ -362,20 +1642,15 @@ pub fn render(
 
                 // Indented PID
                 queue!(out, SetForegroundColor(if child_is_selected { t.selected_fg } else { t.text }))?;
-                write!(out, "      {:<5}  ", child.pid)?;
+                write!(out, "      {}  ", pad(&child.pid.to_string(), 5))?;
 
                 // USER
-                let child_user = if child.user.len() > 9 {
-                    &child.user[..9]
-                } else {
-                    &child.user
-                };
                 queue!(out, SetForegroundColor(if child_is_selected { t.selected_fg } else { t.subtext }))?;
-                write!(out, "{:<10} ", child_user)?;
+                write!(out, "{} ", pad(safe_truncate(&child.user, 9), 10))?;
 
                 // CPU%
                 queue!(out, SetForegroundColor(if child_is_selected { t.selected_fg } else { child_cpu_color }))?;
-                write!(out, "{:<6.1}", child.cpu)?;
+                write!(out, "{:<6.1}", scale(child.cpu as f64))?;
 
                 // MEM
                 queue!(out, SetForegroundColor(if child_is_selected { t.selected_fg } else { t.text }))?;
@@ -391,11 +1666,11 @@ pub fn render(
 
                 // Command
                 let child_remaining = term_width.saturating_sub(used_cols);
-                let child_name = if child.name.len() > child_remaining {
-                    &child.name[..child_remaining]
-                } else {
-                    &child.name
+                let child_label = match ui_state.container_of(data, child.pid) {
+                    Some(c) => format!("{} [{}]", child.name, c),
+                    None => child.name.clone(),
                 };
+                let child_name = truncate_str(&child_label, child_remaining);
                 queue!(out, SetForegroundColor(if child_is_selected { t.selected_fg } else { t.text }))?;
                 write!(out, "{}", child_name)?;
 
@@ -409,31 +1684,17 @@ pub fn render(
     }
 
     ui_state.total_rows = current_row;
+    Ok(())
+}
 
-    // ── Help footer (last row) ──
-    let help_y = size.1.saturating_sub(1);
-    render_help_footer(
-        &mut out,
-        &[
-            ("q", "Quit"),
-            ("\u{2191}\u{2193}", "Select"),
-            ("Enter", "Expand"),
-            ("Tab", "Next"),
-            ("s", "Sort"),
-            ("/", "Search"),
-        ],
-        term_width,
-        help_y,
-    )?;
+#[cfg(test)]
+mod tests {
+    use super::format_remote_ports;
 
-    if ui_state.has_expansions() {
-        // Position just above help footer
-        let note_y = help_y.saturating_sub(1);
-        queue!(out, MoveTo(1, note_y), SetForegroundColor(t.yellow))?;
-        write!(out, "(Expanded section data frozen)")?;
-        queue!(out, ResetColor)?;
+    #[test]
+    fn remote_ports_collapse_long_lists() {
+        assert_eq!(format_remote_ports(&[443]), "443");
+        assert_eq!(format_remote_ports(&[1, 2, 3, 4]), "1, 2, 3, 4");
+        assert_eq!(format_remote_ports(&[1, 2, 3, 4, 5, 6]), "1, 2, 3 +3 more");
     }
-
-    out.flush()?;
-    Ok(rows)
 }
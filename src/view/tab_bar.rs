@@ -1,13 +1,14 @@
 use crossterm::{
     cursor, queue,
     style::{Print, ResetColor, SetBackgroundColor, SetForegroundColor},
-    terminal,
 };
 use std::io::{self, Write};
 
+use super::shared::screen_size;
 use super::theme::theme;
-use crate::model::AppView;
+use crate::model::{AppView, Freshness};
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_tab_bar(
     out: &mut impl Write,
     current_view: &AppView,
@@ -15,7 +16,10 @@ pub fn render_tab_bar(
     container_count: usize,
     swarm_active: bool,
     node_count: u32,
+    kube_pods: Option<usize>,
+    watch_count: usize,
     time: &str,
+    freshness: Freshness,
 ) -> io::Result<()> {
     let t = theme();
 
@@ -25,34 +29,34 @@ pub fn render_tab_bar(
     let system_active = matches!(current_view, AppView::System);
     if system_active {
         queue!(
-            io::stdout(),
+            out,
             SetBackgroundColor(t.tab_active_bg),
             SetForegroundColor(t.tab_active_fg)
         )?;
     } else {
-        queue!(io::stdout(), SetForegroundColor(t.tab_inactive_fg))?;
+        queue!(out, SetForegroundColor(t.tab_inactive_fg))?;
     }
     write!(out, " System ")?;
-    queue!(io::stdout(), ResetColor)?;
+    queue!(out, ResetColor)?;
 
     // --- Containers tab ---
     if docker_available {
         write!(out, "  ")?;
         let containers_active = matches!(
             current_view,
-            AppView::Containers | AppView::ContainerLogs(_)
+            AppView::Containers | AppView::ContainerLogs(_) | AppView::DockerHealth
         );
         if containers_active {
             queue!(
-                io::stdout(),
+                out,
                 SetBackgroundColor(t.tab_active_bg),
                 SetForegroundColor(t.tab_active_fg)
             )?;
         } else {
-            queue!(io::stdout(), SetForegroundColor(t.tab_inactive_fg))?;
+            queue!(out, SetForegroundColor(t.tab_inactive_fg))?;
         }
         write!(out, " Containers({}) ", container_count)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
     }
 
     // --- Swarm tab ---
@@ -60,27 +64,75 @@ pub fn render_tab_bar(
         write!(out, "  ")?;
         let swarm_tab_active = matches!(
             current_view,
-            AppView::Swarm | AppView::SwarmServiceTasks(_, _) | AppView::SwarmServiceLogs(_, _)
+            AppView::Swarm
+            | AppView::SwarmServiceTasks(_, _)
+            | AppView::SwarmServiceLogs(_, _)
+            | AppView::SwarmSplitLogs
         );
         if swarm_tab_active {
             queue!(
-                io::stdout(),
+                out,
                 SetBackgroundColor(t.tab_active_bg),
                 SetForegroundColor(t.tab_active_fg)
             )?;
         } else {
-            queue!(io::stdout(), SetForegroundColor(t.tab_inactive_fg))?;
+            queue!(out, SetForegroundColor(t.tab_inactive_fg))?;
         }
         write!(out, " Swarm({}) ", node_count)?;
-        queue!(io::stdout(), ResetColor)?;
+        queue!(out, ResetColor)?;
+    }
+
+    // --- Kubernetes tab: with a kubectl context ---
+    if let Some(pod_count) = kube_pods {
+        write!(out, "  ")?;
+        if matches!(current_view, AppView::Kubernetes | AppView::KubernetesPodLogs(_, _)) {
+            queue!(
+                out,
+                SetBackgroundColor(t.tab_active_bg),
+                SetForegroundColor(t.tab_active_fg)
+            )?;
+        } else {
+            queue!(out, SetForegroundColor(t.tab_inactive_fg))?;
+        }
+        write!(out, " Kubernetes({}) ", pod_count)?;
+        queue!(out, ResetColor)?;
+    }
+
+    // --- Watchlist tab: only once something is pinned ---
+    if watch_count > 0 || matches!(current_view, AppView::Watchlist) {
+        write!(out, "  ")?;
+        if matches!(current_view, AppView::Watchlist) {
+            queue!(
+                out,
+                SetBackgroundColor(t.tab_active_bg),
+                SetForegroundColor(t.tab_active_fg)
+            )?;
+        } else {
+            queue!(out, SetForegroundColor(t.tab_inactive_fg))?;
+        }
+        write!(out, " Watchlist({}) ", watch_count)?;
+        queue!(out, ResetColor)?;
     }
 
     // --- Right-aligned: sitrep - HH:MM:SS ---
-    let size = terminal::size()?;
+    let size = screen_size()?;
     let time_str = format!("sitrep - {} ", time);
     let col = (size.0 as usize).saturating_sub(time_str.len());
+
+    // --- Left of the clock: how current the active tab's data is, as a
+    // badge once it is stale or its refresh failed ---
+    let label = format!(" {} ", freshness.label());
+    let label_col = col.saturating_sub(label.len() + 1);
+    queue!(out, cursor::MoveTo(label_col as u16, 0))?;
+    match freshness {
+        Freshness::Stale(_) => queue!(out, SetBackgroundColor(t.peach), SetForegroundColor(t.base))?,
+        Freshness::Failed(_) | Freshness::Ended(_) => queue!(out, SetBackgroundColor(t.red), SetForegroundColor(t.base))?,
+        Freshness::Collecting | Freshness::Updated(_) => queue!(out, SetForegroundColor(t.subtext))?,
+    }
+    queue!(out, Print(&label), ResetColor)?;
+
     queue!(
-        io::stdout(),
+        out,
         cursor::MoveTo(col as u16, 0),
         SetForegroundColor(t.subtext),
         Print(&time_str),
@@ -91,9 +143,9 @@ pub fn render_tab_bar(
 
     // --- Separator line: thin horizontal rule in surface color ---
     let sep: String = "\u{2500}".repeat(size.0 as usize);
-    queue!(io::stdout(), SetForegroundColor(t.separator))?;
+    queue!(out, SetForegroundColor(t.separator))?;
     write!(out, "{}\r\n", sep)?;
-    queue!(io::stdout(), ResetColor)?;
+    queue!(out, ResetColor)?;
 
     Ok(())
 }
@@ -1,6 +1,9 @@
 use crossterm::style::Color;
+use serde::Deserialize;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU8, Ordering};
 
-/// Catppuccin Mocha color theme for the TUI.
+/// Color theme for the TUI.
 pub struct Theme {
     pub base: Color,
     pub text: Color,
@@ -33,57 +36,188 @@ pub struct Theme {
     pub separator: Color,
 }
 
+/// The named colors a theme is built from.
+struct Palette {
+    base: Color,
+    text: Color,
+    subtext: Color,
+    surface: Color,
+    overlay: Color,
+    mauve: Color,
+    lavender: Color,
+    teal: Color,
+    green: Color,
+    yellow: Color,
+    peach: Color,
+    red: Color,
+    flamingo: Color,
+    sky: Color,
+    sapphire: Color,
+}
+
+impl Palette {
+    /// Every foreground color halfway to the background, for data that may
+    /// be out of date. Backgrounds stay as they are.
+    fn dimmed(self) -> Self {
+        let toward = |c: Color| match (c, self.base) {
+            (Color::Rgb { r, g, b }, Color::Rgb { r: br, g: bg, b: bb }) => Color::Rgb {
+                r: ((r as u16 + br as u16) / 2) as u8,
+                g: ((g as u16 + bg as u16) / 2) as u8,
+                b: ((b as u16 + bb as u16) / 2) as u8,
+            },
+            (c, _) => c,
+        };
+        Palette {
+            base: self.base,
+            text: toward(self.text),
+            subtext: toward(self.subtext),
+            surface: self.surface,
+            overlay: self.overlay,
+            mauve: toward(self.mauve),
+            lavender: toward(self.lavender),
+            teal: toward(self.teal),
+            green: toward(self.green),
+            yellow: toward(self.yellow),
+            peach: toward(self.peach),
+            red: toward(self.red),
+            flamingo: toward(self.flamingo),
+            sky: toward(self.sky),
+            sapphire: toward(self.sapphire),
+        }
+    }
+}
+
+impl From<Palette> for Theme {
+    fn from(p: Palette) -> Self {
+        Self {
+            base: p.base,
+            text: p.text,
+            subtext: p.subtext,
+            surface: p.surface,
+            overlay: p.overlay,
+            mauve: p.mauve,
+            lavender: p.lavender,
+            teal: p.teal,
+            green: p.green,
+            yellow: p.yellow,
+            peach: p.peach,
+            red: p.red,
+            flamingo: p.flamingo,
+            sky: p.sky,
+            sapphire: p.sapphire,
+            bar_filled: p.teal,
+            bar_empty: p.surface,
+            selected_bg: p.overlay,
+            selected_fg: p.text,
+            header_fg: p.lavender,
+            tab_active_bg: p.mauve,
+            tab_active_fg: p.base,
+            tab_inactive_fg: p.subtext,
+            help_key: p.mauve,
+            help_desc: p.subtext,
+            separator: p.surface,
+        }
+    }
+}
+
 impl Theme {
+    /// Dark theme, the default.
     pub fn catppuccin_mocha() -> Self {
-        let base = Color::Rgb { r: 30, g: 30, b: 46 };
-        let text = Color::Rgb { r: 205, g: 214, b: 244 };
-        let subtext = Color::Rgb { r: 166, g: 173, b: 200 };
-        let surface = Color::Rgb { r: 49, g: 50, b: 68 };
-        let overlay = Color::Rgb { r: 69, g: 71, b: 90 };
-
-        let mauve = Color::Rgb { r: 203, g: 166, b: 247 };
-        let lavender = Color::Rgb { r: 180, g: 190, b: 254 };
-        let teal = Color::Rgb { r: 148, g: 226, b: 213 };
-        let green = Color::Rgb { r: 166, g: 227, b: 161 };
-        let yellow = Color::Rgb { r: 249, g: 226, b: 175 };
-        let peach = Color::Rgb { r: 250, g: 179, b: 135 };
-        let red = Color::Rgb { r: 243, g: 139, b: 168 };
-        let flamingo = Color::Rgb { r: 242, g: 205, b: 205 };
-        let sky = Color::Rgb { r: 137, g: 220, b: 235 };
-        let sapphire = Color::Rgb { r: 116, g: 199, b: 236 };
+        mocha().into()
+    }
 
-        Self {
-            base,
-            text,
-            subtext,
-            surface,
-            overlay,
-            mauve,
-            lavender,
-            teal,
-            green,
-            yellow,
-            peach,
-            red,
-            flamingo,
-            sky,
-            sapphire,
-            bar_filled: teal,
-            bar_empty: surface,
-            selected_bg: overlay,
-            selected_fg: text,
-            header_fg: lavender,
-            tab_active_bg: mauve,
-            tab_active_fg: base,
-            tab_inactive_fg: subtext,
-            help_key: mauve,
-            help_desc: subtext,
-            separator: surface,
+    /// Light theme for light terminal backgrounds.
+    pub fn catppuccin_latte() -> Self {
+        latte().into()
+    }
+}
+
+fn mocha() -> Palette {
+    Palette {
+        base: Color::Rgb { r: 30, g: 30, b: 46 },
+        text: Color::Rgb { r: 205, g: 214, b: 244 },
+        subtext: Color::Rgb { r: 166, g: 173, b: 200 },
+        surface: Color::Rgb { r: 49, g: 50, b: 68 },
+        overlay: Color::Rgb { r: 69, g: 71, b: 90 },
+        mauve: Color::Rgb { r: 203, g: 166, b: 247 },
+        lavender: Color::Rgb { r: 180, g: 190, b: 254 },
+        teal: Color::Rgb { r: 148, g: 226, b: 213 },
+        green: Color::Rgb { r: 166, g: 227, b: 161 },
+        yellow: Color::Rgb { r: 249, g: 226, b: 175 },
+        peach: Color::Rgb { r: 250, g: 179, b: 135 },
+        red: Color::Rgb { r: 243, g: 139, b: 168 },
+        flamingo: Color::Rgb { r: 242, g: 205, b: 205 },
+        sky: Color::Rgb { r: 137, g: 220, b: 235 },
+        sapphire: Color::Rgb { r: 116, g: 199, b: 236 },
+    }
+}
+
+fn latte() -> Palette {
+    Palette {
+        base: Color::Rgb { r: 239, g: 241, b: 245 },
+        text: Color::Rgb { r: 76, g: 79, b: 105 },
+        subtext: Color::Rgb { r: 108, g: 111, b: 133 },
+        surface: Color::Rgb { r: 204, g: 208, b: 218 },
+        overlay: Color::Rgb { r: 188, g: 192, b: 204 },
+        mauve: Color::Rgb { r: 136, g: 57, b: 239 },
+        lavender: Color::Rgb { r: 114, g: 135, b: 253 },
+        teal: Color::Rgb { r: 23, g: 146, b: 153 },
+        green: Color::Rgb { r: 64, g: 160, b: 43 },
+        yellow: Color::Rgb { r: 223, g: 142, b: 29 },
+        peach: Color::Rgb { r: 254, g: 100, b: 11 },
+        red: Color::Rgb { r: 210, g: 15, b: 57 },
+        flamingo: Color::Rgb { r: 221, g: 120, b: 120 },
+        sky: Color::Rgb { r: 4, g: 165, b: 229 },
+        sapphire: Color::Rgb { r: 32, g: 159, b: 181 },
+    }
+}
+
+/// Themes selectable with `[ui] theme` or `SITREP_THEME`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    #[default]
+    Mocha,
+    Latte,
+}
+
+impl ThemeName {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "mocha" | "dark" => Ok(ThemeName::Mocha),
+            "latte" | "light" => Ok(ThemeName::Latte),
+            other => Err(format!("unknown theme '{}' (expected mocha or latte)", other)),
         }
     }
 }
 
-/// Global theme accessor. Returns the Catppuccin Mocha theme.
+static CURRENT: AtomicU8 = AtomicU8::new(ThemeName::Mocha as u8);
+
+thread_local! {
+    /// Set while drawing a view whose data is out of date.
+    static DIMMED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Switch the theme used by every subsequent render.
+pub fn set_theme(name: ThemeName) {
+    CURRENT.store(name as u8, Ordering::Relaxed);
+}
+
+/// Global theme accessor. Returns the selected theme, dimmed inside
+/// `with_dimmed`.
 pub fn theme() -> Theme {
-    Theme::catppuccin_mocha()
+    let palette = if CURRENT.load(Ordering::Relaxed) == ThemeName::Latte as u8 {
+        latte()
+    } else {
+        mocha()
+    };
+    if DIMMED.get() { palette.dimmed().into() } else { palette.into() }
+}
+
+/// Draw with dimmed colors until `f` returns, when `dimmed` is set.
+pub fn with_dimmed<T>(dimmed: bool, f: impl FnOnce() -> T) -> T {
+    let previous = DIMMED.replace(dimmed);
+    let result = f();
+    DIMMED.set(previous);
+    result
 }
@@ -0,0 +1,23 @@
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{ResetColor, SetBackgroundColor, SetForegroundColor},
+};
+use std::io::{self, Write};
+
+use super::shared::{display_width, truncate_str, screen_size};
+use super::theme::theme;
+
+/// A short notice drawn over the right end of the row below the tab bar.
+pub fn render_toast(out: &mut impl Write, message: &str) -> io::Result<()> {
+    let t = theme();
+    let width = screen_size()?.0 as usize;
+    let text = format!(" {} ", truncate_str(message, width.saturating_sub(4)));
+    let x = width.saturating_sub(display_width(&text) + 1);
+
+    queue!(out, MoveTo(x as u16, 1), SetBackgroundColor(t.lavender), SetForegroundColor(t.base))?;
+    write!(out, "{}", text)?;
+    queue!(out, ResetColor)?;
+    out.flush()?;
+    Ok(())
+}
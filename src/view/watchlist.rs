@@ -0,0 +1,185 @@
+use crossterm::{
+    cursor, queue,
+    style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor},
+};
+use std::io::{self, Write};
+
+use super::shared::{format_mem_human, pad, render_help_footer, truncate_str, write_selectable, writeln, screen_size};
+use super::swarm::is_replica_degraded;
+use super::theme::theme;
+use crate::health_controller::{HealthMonitor, HealthTargetKind};
+use crate::model::{DockerContainerInfo, SwarmServiceInfo, WatchItem, WatchKind, Watchlist};
+
+/// Label Docker puts on a Swarm task's container.
+const SERVICE_NAME_LABEL: &str = "com.docker.swarm.service.name";
+
+/// One watchlist line, colored by the worst thing about it.
+struct WatchRow {
+    state: String,
+    health: String,
+    cpu: String,
+    mem: String,
+    detail: String,
+    color: Color,
+}
+
+fn container_row(name: &str, containers: &[DockerContainerInfo], health: &HealthMonitor) -> Option<WatchRow> {
+    let t = theme();
+    let c = containers.iter().find(|c| c.name == name)?;
+    let check = health.get(HealthTargetKind::Container, name);
+    let unhealthy = check.is_some_and(|r| !r.is_healthy()) || c.docker_health() == Some("unhealthy");
+    let color = if c.state != "running" || unhealthy {
+        t.red
+    } else if c.docker_health() == Some("starting") {
+        t.peach
+    } else {
+        t.green
+    };
+    let mem = if c.mem_limit > 0 {
+        format!("{} / {}", format_mem_human(c.mem_usage), format_mem_human(c.mem_limit))
+    } else {
+        format_mem_human(c.mem_usage)
+    };
+    Some(WatchRow {
+        state: c.state.clone(),
+        health: check
+            .map(|r| r.summary())
+            .or_else(|| c.docker_health().map(String::from))
+            .unwrap_or_else(|| "-".to_string()),
+        cpu: format!("{:.1}%", c.cpu_percent),
+        mem,
+        detail: format!("up {}", c.uptime),
+        color,
+    })
+}
+
+/// A service's replicas and health, with CPU and memory summed over the
+/// tasks running on this host.
+fn service_row(
+    name: &str,
+    services: &[SwarmServiceInfo],
+    containers: &[DockerContainerInfo],
+    health: &HealthMonitor,
+) -> Option<WatchRow> {
+    let t = theme();
+    let svc = services.iter().find(|s| s.name == name)?;
+    let check = health.get(HealthTargetKind::Service, name);
+    let color = if check.is_some_and(|r| !r.is_healthy()) || svc.replicas.starts_with("0/") {
+        t.red
+    } else if is_replica_degraded(&svc.replicas) {
+        t.peach
+    } else {
+        t.green
+    };
+    let local: Vec<&DockerContainerInfo> = containers
+        .iter()
+        .filter(|c| c.labels.get(SERVICE_NAME_LABEL).is_some_and(|s| s == name))
+        .collect();
+    let (cpu, mem) = if local.is_empty() {
+        ("-".to_string(), "-".to_string())
+    } else {
+        let cpu: f64 = local.iter().map(|c| c.cpu_percent).sum();
+        let mem: u64 = local.iter().map(|c| c.mem_usage).sum();
+        (format!("{:.1}%", cpu), format_mem_human(mem))
+    };
+    Some(WatchRow {
+        state: svc.replicas.clone(),
+        health: check.map(|r| r.summary()).unwrap_or_else(|| "-".to_string()),
+        cpu,
+        mem,
+        detail: format!("{} local task{}", local.len(), if local.len() == 1 { "" } else { "s" }),
+        color,
+    })
+}
+
+fn watch_row(
+    item: &WatchItem,
+    containers: &[DockerContainerInfo],
+    services: &[SwarmServiceInfo],
+    health: &HealthMonitor,
+) -> WatchRow {
+    let row = match item.kind {
+        WatchKind::Container => container_row(&item.name, containers, health),
+        WatchKind::Service => service_row(&item.name, services, containers, health),
+    };
+    row.unwrap_or_else(|| WatchRow {
+        state: "not found".to_string(),
+        health: "-".to_string(),
+        cpu: "-".to_string(),
+        mem: "-".to_string(),
+        detail: String::new(),
+        color: theme().subtext,
+    })
+}
+
+pub fn render_watchlist(
+    out: &mut impl Write,
+    watchlist: &Watchlist,
+    containers: &[DockerContainerInfo],
+    services: &[SwarmServiceInfo],
+    health: &HealthMonitor,
+    status_message: &Option<String>,
+) -> io::Result<()> {
+    let t = theme();
+    queue!(out, cursor::MoveTo(0, 2))?;
+    let size = screen_size()?;
+
+    queue!(out, SetForegroundColor(t.lavender), SetAttribute(Attribute::Bold))?;
+    writeln(out, "  Watchlist")?;
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    writeln(out, "")?;
+
+    if watchlist.is_empty() {
+        queue!(out, SetForegroundColor(t.subtext))?;
+        writeln(out, "  Nothing pinned. Press w on a container or a Swarm service to watch it here.")?;
+        queue!(out, ResetColor)?;
+    } else {
+        queue!(out, SetForegroundColor(t.header_fg), SetAttribute(Attribute::Bold))?;
+        write!(
+            out,
+            "  {:<10} {:<26} {:<12} {:<16} {:<8} {:<20} DETAIL\r\n",
+            "KIND", "NAME", "STATE", "HEALTH", "CPU", "MEM"
+        )?;
+        queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+
+        for (idx, item) in watchlist.items.iter().enumerate() {
+            let row = watch_row(item, containers, services, health);
+            let line = format!(
+                "  {:<10} {} {:<12} {:<16} {:<8} {:<20} {}",
+                item.kind.label(),
+                pad(&truncate_str(&item.name, 24), 26),
+                truncate_str(&row.state, 11),
+                truncate_str(&row.health, 15),
+                row.cpu,
+                truncate_str(&row.mem, 19),
+                row.detail,
+            );
+            queue!(out, SetForegroundColor(row.color))?;
+            write_selectable(out, &truncate_str(&line, size.0 as usize), idx == watchlist.selected)?;
+            queue!(out, ResetColor)?;
+        }
+    }
+
+    if let Some(msg) = status_message {
+        writeln(out, "")?;
+        queue!(out, SetForegroundColor(t.yellow))?;
+        writeln(out, &format!("  {}", msg))?;
+        queue!(out, ResetColor)?;
+    }
+
+    render_help_footer(
+        out,
+        &[
+            ("q", "Quit"),
+            ("\u{2191}\u{2193}", "Select"),
+            ("\u{2192}", "Logs"),
+            ("w/Del", "Unpin"),
+            ("Tab", "Next"),
+        ],
+        size.0 as usize,
+        size.1.saturating_sub(1),
+    )?;
+
+    out.flush()?;
+    Ok(())
+}